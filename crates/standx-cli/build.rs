@@ -0,0 +1,34 @@
+//! Embeds the git commit the CLI was built from as `STANDX_GIT_SHA`, which
+//! `maker run --trace-out` writes into replay trace headers.
+//!
+//! Builds outside a git checkout (a crates.io or tarball build) can set
+//! `STANDX_GIT_SHA` themselves; otherwise the commit is recorded as `unknown`.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    let text = String::from_utf8(output.stdout).ok()?;
+    let text = text.trim();
+    (output.status.success() && !text.is_empty()).then(|| text.to_string())
+}
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=STANDX_GIT_SHA");
+    let sha = std::env::var("STANDX_GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.trim().is_empty())
+        .or_else(|| git(&["rev-parse", "HEAD"]))
+        .unwrap_or_else(|| "unknown".to_string());
+    // Rebuild when HEAD moves to another branch or commit.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]).map(PathBuf::from) {
+        for path in ["HEAD", "refs/heads", "packed-refs"] {
+            let path = git_dir.join(path);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+    println!("cargo:rustc-env=STANDX_GIT_SHA={sha}");
+}
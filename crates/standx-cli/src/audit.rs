@@ -0,0 +1,470 @@
+//! Append-only audit journal of account mutations
+//!
+//! Every order create/cancel, leverage change, margin transfer or margin-mode
+//! change the CLI sends — from a plain command, `apply`, the MCP and HTTP
+//! servers, the dashboard or the maker — appends one JSON line to
+//! `audit.jsonl` in the data directory. A record names who asked (source
+//! command, config profile, sanitized command line), what was asked (action,
+//! symbol, SHA-256 of the request payload) and what came back (venue
+//! response or error). Each record also carries the hash of the record
+//! before it, so editing, reordering or deleting a line breaks the chain that
+//! `standx audit verify` recomputes.
+//!
+//! Nothing is recorded until [`configure`] installs the process context,
+//! which `main` does for every run; library and test use leave the log
+//! alone. Set `STANDX_AUDIT_LOG` to relocate the file.
+
+use anyhow::{bail, Context as _, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt::Display;
+use std::fs::{File, OpenOptions};
+use std::future::Future;
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(unix)]
+use std::os::fd::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Environment variable that overrides the audit log path.
+pub const AUDIT_LOG_ENV: &str = "STANDX_AUDIT_LOG";
+
+/// `prev_hash` of the first record.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Who is acting, fixed for the whole process.
+#[derive(Clone, Debug)]
+pub struct Context {
+    /// Top-level command label (`order`, `maker`, `mcp`, `apply`, ...).
+    pub source: String,
+    /// `--config` file in effect, or `default`.
+    pub profile: String,
+    /// Command line with secrets redacted.
+    pub command: String,
+    pub log: AuditLog,
+}
+
+static CONTEXT: OnceLock<Context> = OnceLock::new();
+
+/// Start recording mutations for this process. Only the first call takes
+/// effect.
+pub fn configure(context: Context) {
+    let _ = CONTEXT.set(context);
+}
+
+/// How a mutation ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The venue accepted the request (for maker socket commands: the
+    /// request was written; the asynchronous venue answer is correlated by
+    /// `request_id` in the maker event log).
+    Ok,
+    /// The request failed; `response` holds the error.
+    Error,
+}
+
+/// One line of the audit log.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub seq: u64,
+    pub ts: DateTime<Utc>,
+    pub source: String,
+    pub profile: String,
+    pub command: String,
+    pub action: String,
+    pub symbol: String,
+    /// SHA-256 (hex) of the compact JSON request payload.
+    pub request_hash: String,
+    pub response: Value,
+    pub outcome: Outcome,
+    pub prev_hash: String,
+    /// SHA-256 (hex) of this record serialized with an empty `hash`.
+    #[serde(default)]
+    pub hash: String,
+}
+
+impl Record {
+    fn digest(&self) -> String {
+        let unsealed = Record {
+            hash: String::new(),
+            ..self.clone()
+        };
+        sha256_hex(
+            serde_json::to_string(&unsealed)
+                .expect("audit records always serialize")
+                .as_bytes(),
+        )
+    }
+}
+
+/// A mutation about to be appended.
+#[derive(Clone, Debug)]
+pub struct Entry<'a> {
+    pub action: &'a str,
+    pub symbol: &'a str,
+    pub request: &'a Value,
+    pub response: Value,
+    pub outcome: Outcome,
+}
+
+/// Where the chain first fails to verify.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Break {
+    /// 1-based line number in the log file.
+    pub line: usize,
+    pub reason: String,
+}
+
+/// Result of [`AuditLog::verify`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Verification {
+    pub intact: bool,
+    /// Records checked before the first break (all of them when intact).
+    pub records: u64,
+    /// Hash of the last verified record; anchor it elsewhere to also detect
+    /// truncation of the tail.
+    pub head: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broken: Option<Break>,
+}
+
+/// The audit log file.
+#[derive(Clone, Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The log at `$STANDX_AUDIT_LOG`, or `audit.jsonl` in the user data
+    /// directory.
+    pub fn open_default() -> Self {
+        let path = std::env::var_os(AUDIT_LOG_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                dirs::data_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("standx")
+                    .join("audit.jsonl")
+            });
+        Self::new(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Chain `entry` onto the log and return the sealed record.
+    pub fn append(&self, context: &Context, entry: Entry<'_>) -> Result<Record> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&self.path)
+            .with_context(|| format!("failed to open audit log {}", self.path.display()))?;
+        // Serialize concurrent writers (a maker and a manual cancel) so two
+        // records never claim the same predecessor.
+        lock_exclusive(&file, &self.path)?;
+
+        let (seq, prev_hash) = match last_line(&mut file)? {
+            Some(line) => {
+                let last: Record = serde_json::from_str(&line).with_context(|| {
+                    format!(
+                        "last line of audit log {} is not a record; run `standx audit verify`",
+                        self.path.display()
+                    )
+                })?;
+                (last.seq + 1, last.hash)
+            }
+            None => (0, GENESIS_HASH.to_string()),
+        };
+        let mut record = Record {
+            seq,
+            ts: Utc::now(),
+            source: context.source.clone(),
+            profile: context.profile.clone(),
+            command: context.command.clone(),
+            action: entry.action.to_string(),
+            symbol: entry.symbol.to_string(),
+            request_hash: sha256_hex(entry.request.to_string().as_bytes()),
+            response: entry.response,
+            outcome: entry.outcome,
+            prev_hash,
+            hash: String::new(),
+        };
+        record.hash = record.digest();
+
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("failed to append to {}", self.path.display()))?;
+        Ok(record)
+    }
+
+    /// Every record in file order. A missing log reads as empty.
+    pub fn read(&self) -> Result<Vec<Record>> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => {
+                return Err(error).with_context(|| format!("reading {}", self.path.display()))
+            }
+        };
+        contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).with_context(|| {
+                    format!(
+                        "{} line {}: not an audit record",
+                        self.path.display(),
+                        index + 1
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Recompute the hash chain from the first line.
+    pub fn verify(&self) -> Result<Verification> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(error) => {
+                return Err(error).with_context(|| format!("reading {}", self.path.display()))
+            }
+        };
+        let mut verification = Verification {
+            intact: true,
+            records: 0,
+            head: GENESIS_HASH.to_string(),
+            broken: None,
+        };
+        for (index, line) in contents.lines().enumerate() {
+            let reason = match serde_json::from_str::<Record>(line) {
+                Err(error) => Some(format!("not an audit record: {error}")),
+                Ok(record) if record.seq != verification.records => Some(format!(
+                    "sequence {} where {} was expected",
+                    record.seq, verification.records
+                )),
+                Ok(record) if record.prev_hash != verification.head => {
+                    Some("previous-hash link does not match the record before".to_string())
+                }
+                Ok(record) if record.digest() != record.hash => {
+                    Some("record contents do not match its hash".to_string())
+                }
+                Ok(record) => {
+                    verification.records += 1;
+                    verification.head = record.hash;
+                    None
+                }
+            };
+            if let Some(reason) = reason {
+                verification.intact = false;
+                verification.broken = Some(Break {
+                    line: index + 1,
+                    reason,
+                });
+                break;
+            }
+        }
+        Ok(verification)
+    }
+}
+
+/// Append `entry` to the configured log. Failures are logged, not returned:
+/// the mutation already happened and its own result must reach the caller.
+pub fn record(entry: Entry<'_>) {
+    let Some(context) = CONTEXT.get() else {
+        return;
+    };
+    if let Err(error) = context.log.append(context, entry) {
+        tracing::warn!("audit log not written: {error:#}");
+    }
+}
+
+/// Record the result of one mutating call.
+pub fn record_result<T: Serialize, E: Display>(
+    action: &str,
+    symbol: &str,
+    request: &Value,
+    result: &Result<T, E>,
+) {
+    let (outcome, response) = match result {
+        Ok(value) => (
+            Outcome::Ok,
+            serde_json::to_value(value).unwrap_or(Value::Null),
+        ),
+        Err(error) => (
+            Outcome::Error,
+            serde_json::json!({ "error": error.to_string() }),
+        ),
+    };
+    record(Entry {
+        action,
+        symbol,
+        request,
+        response,
+        outcome,
+    });
+}
+
+/// Await a mutating call and record its result.
+pub async fn audited<T, E, F>(action: &str, symbol: &str, request: Value, call: F) -> Result<T, E>
+where
+    T: Serialize,
+    E: Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let result = call.await;
+    record_result(action, symbol, &request, &result);
+    result
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// The last non-empty line of `file`, reading backwards from the end.
+fn last_line(file: &mut File) -> Result<Option<String>> {
+    const CHUNK: u64 = 4096;
+    let len = file.seek(SeekFrom::End(0))?;
+    let mut tail = Vec::new();
+    let mut offset = len;
+    while offset > 0 {
+        let start = offset.saturating_sub(CHUNK);
+        let mut chunk = vec![0; (offset - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&tail);
+        tail = chunk;
+        offset = start;
+        let trimmed = trim_end(&tail);
+        if let Some(newline) = trimmed.iter().rposition(|byte| *byte == b'\n') {
+            return Ok(Some(
+                String::from_utf8_lossy(&trimmed[newline + 1..]).into_owned(),
+            ));
+        }
+    }
+    let trimmed = trim_end(&tail);
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(trimmed).into_owned()))
+}
+
+fn trim_end(bytes: &[u8]) -> &[u8] {
+    let end = bytes
+        .iter()
+        .rposition(|byte| !byte.is_ascii_whitespace())
+        .map_or(0, |index| index + 1);
+    &bytes[..end]
+}
+
+/// Block until this process holds an exclusive `flock` on `file`; it is
+/// released when `file` drops.
+#[cfg(unix)]
+pub(crate) fn lock_exclusive(file: &File, path: &Path) -> Result<()> {
+    // SAFETY: flock only operates on the valid fd owned by `file`; the lock
+    // is released when `file` drops.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        bail!(
+            "failed to lock {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn lock_exclusive(_file: &File, _path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn context(log: &AuditLog) -> Context {
+        Context {
+            source: "order".to_string(),
+            profile: "default".to_string(),
+            command: "standx order cancel BTC-USD 1".to_string(),
+            log: log.clone(),
+        }
+    }
+
+    fn append(log: &AuditLog, symbol: &str, outcome: Outcome) -> Record {
+        log.append(
+            &context(log),
+            Entry {
+                action: "order_cancel",
+                symbol,
+                request: &json!({ "order_id": 1 }),
+                response: json!({ "cancelled": 1 }),
+                outcome,
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn appends_a_verifiable_chain() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = AuditLog::new(dir.path().join("nested").join("audit.jsonl"));
+        assert_eq!(log.verify().unwrap().records, 0);
+
+        let first = append(&log, "BTC-USD", Outcome::Ok);
+        let second = append(&log, "ETH-USD", Outcome::Error);
+        assert_eq!(first.prev_hash, GENESIS_HASH);
+        assert_eq!(second.seq, 1);
+        assert_eq!(second.prev_hash, first.hash);
+        assert_eq!(
+            first.request_hash,
+            sha256_hex(br#"{"order_id":1}"#),
+            "request hash covers the compact payload"
+        );
+
+        assert_eq!(log.read().unwrap(), vec![first, second.clone()]);
+        let verification = log.verify().unwrap();
+        assert!(verification.intact);
+        assert_eq!(verification.records, 2);
+        assert_eq!(verification.head, second.hash);
+    }
+
+    #[test]
+    fn verify_detects_edits_and_deletions() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = AuditLog::new(&path);
+        for _ in 0..3 {
+            append(&log, "BTC-USD", Outcome::Ok);
+        }
+        let original = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = original.lines().collect();
+
+        std::fs::write(&path, original.replacen("BTC-USD", "ETH-USD", 1)).unwrap();
+        let edited = log.verify().unwrap();
+        assert!(!edited.intact);
+        assert_eq!(edited.records, 0);
+        assert_eq!(edited.broken.unwrap().line, 1);
+
+        std::fs::write(&path, format!("{}\n{}\n", lines[0], lines[2])).unwrap();
+        let deleted = log.verify().unwrap();
+        assert_eq!(deleted.records, 1);
+        assert_eq!(deleted.broken.unwrap().line, 2);
+    }
+}
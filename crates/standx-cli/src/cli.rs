@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 const SUPERVISOR_WEBHOOK_ENV: &str = "STANDX_SUPERVISOR_WEBHOOK";
@@ -86,7 +87,18 @@ pub struct Cli {
 
     /// Output format
     #[arg(short, long, global = true, value_enum, default_value = "table")]
-    pub output: OutputFormat,
+    pub output: OutputArg,
+
+    /// Only output these comma-separated fields; dotted paths reach into
+    /// nested records (`account.balance`, `positions.symbol`)
+    #[arg(long, global = true, value_delimiter = ',', value_name = "PATHS")]
+    pub fields: Vec<String>,
+
+    /// Render each record through TEMPLATE (Jinja syntax: `{{ field }}`,
+    /// `{% for %}`, `{% if %}`, filters; `\n`/`\t` escapes) instead of
+    /// --output; `@FILE` reads it from a file
+    #[arg(long, global = true, value_name = "TEMPLATE")]
+    pub template: Option<String>,
 
     /// Verbose output
     #[arg(short, long, global = true)]
@@ -107,6 +119,13 @@ pub struct Cli {
     /// Auto-confirm dangerous operations (skip prompts)
     #[arg(long, global = true, env = "STANDX_AUTO_CONFIRM")]
     pub yes: bool,
+
+    /// Write what a mutating command (order create/cancel/cancel-all,
+    /// leverage set, margin transfer, margin mode --set) would send, plus the
+    /// market it was computed against, to FILE instead of executing it; run
+    /// it later with `standx apply FILE`
+    #[arg(long, global = true, value_name = "FILE")]
+    pub plan_out: Option<PathBuf>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -175,6 +194,13 @@ pub enum Commands {
         /// Compact mode: skip RECENT TRADES section
         #[arg(long)]
         compact: bool,
+        /// Bypass the local market-data cache
+        #[arg(long)]
+        no_cache: bool,
+        /// Interactive full-screen dashboard driven by the WebSocket streams
+        /// (cancel orders, flatten positions, switch symbol)
+        #[arg(long, conflicts_with_all = ["watch", "compact"])]
+        tui: bool,
     },
     /// Portfolio - view portfolio summary and performance (alias for portfolio snapshot)
     #[command(visible_alias = "p")]
@@ -185,6 +211,11 @@ pub enum Commands {
         /// Watch mode: refresh every N seconds (default 5 when flag used without value)
         #[arg(short, long, num_args = 0..=1, default_missing_value = "5")]
         watch: Option<u64>,
+        /// Also store every refresh in the local portfolio history
+        #[arg(long, requires = "watch")]
+        record: bool,
+        #[command(subcommand)]
+        command: Option<PortfolioCommands>,
     },
     /// Block trade operations (authenticated)
     #[command(visible_alias = "b")]
@@ -198,6 +229,46 @@ pub enum Commands {
         #[command(subcommand)]
         command: Box<MakerCommands>,
     },
+    /// Account reports rebuilt from trade and funding history (authenticated)
+    #[command(visible_alias = "r")]
+    Report {
+        #[command(subcommand)]
+        command: ReportCommands,
+    },
+    /// Local historical market-data cache (klines, funding, public trades)
+    Data {
+        #[command(subcommand)]
+        command: DataCommands,
+    },
+    /// Evaluate alert rules on live market data and notify via webhook
+    Watch(WatchArgs),
+    /// Record public market data (price, depth, trades, klines) for many
+    /// symbols to rotating NDJSON files (read-only; no auth, no orders).
+    Record(RecordArgs),
+    /// Serve the Model Context Protocol over stdio so AI agents can call
+    /// market, account, order and maker-status operations as typed tools.
+    Mcp(McpArgs),
+    /// Run a local HTTP API mirroring the CLI commands over one warm,
+    /// authenticated client and WebSocket session, with a server-sent-events
+    /// stream; protected by a bearer token.
+    Serve(ServeArgs),
+    /// Execute a plan written by `--plan-out` after re-checking its age and
+    /// the market against the one it was made for.
+    Apply(ApplyArgs),
+    /// Print a versioned JSON description of every command: its arguments,
+    /// their types and allowed values, and the shape of its JSON output.
+    Schema(SchemaArgs),
+    /// Show, verify or export the local audit log of every order, leverage,
+    /// margin and maker action the CLI sent
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+    /// Margin and liquidation what-if analysis (authenticated, read-only)
+    Risk {
+        #[command(subcommand)]
+        command: RiskCommands,
+    },
     /// Record StandX vs Hyperliquid mark prices to NDJSON to measure StandX's
     /// price lag (read-only diagnostic; no auth, no orders).
     LagRecorder {
@@ -274,6 +345,9 @@ pub enum MarketCommands {
         symbol: String,
         #[arg(short, long)]
         limit: Option<u32>,
+        /// Bypass the local market-data cache
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Get order book depth
     #[command(visible_alias = "dep")]
@@ -301,6 +375,9 @@ pub enum MarketCommands {
         /// Number of candles to fetch (alternative to from/to)
         #[arg(short, long, help = "Number of candles (alternative to from/to)")]
         limit: Option<u32>,
+        /// Bypass the local market-data cache
+        #[arg(long)]
+        no_cache: bool,
     },
     /// Get funding rate history
     #[command(visible_alias = "f")]
@@ -308,6 +385,9 @@ pub enum MarketCommands {
         symbol: String,
         #[arg(short, long, default_value = "7")]
         days: i64,
+        /// Bypass the local market-data cache
+        #[arg(long)]
+        no_cache: bool,
     },
 }
 
@@ -363,6 +443,11 @@ pub enum OrderCommands {
         sl_price: Option<String>,
         #[arg(long)]
         tp_price: Option<String>,
+        /// Idempotency key: a repeated create with the same ID returns the
+        /// order already submitted instead of sending another (generated
+        /// when omitted)
+        #[arg(long)]
+        client_order_id: Option<String>,
     },
     /// Cancel an order by ID
     #[command(visible_alias = "cxl")]
@@ -472,6 +557,435 @@ pub enum BlockCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum DataCommands {
+    /// Backfill and extend the cache, filling uncovered ranges
+    Sync {
+        /// Symbols to sync (comma-separated, defaults to all)
+        #[arg(short, long = "symbols", alias = "symbol")]
+        symbols: Option<String>,
+        /// Kline resolutions to sync (comma-separated minutes, 1D or 1W)
+        #[arg(short, long, default_value = "60,1D")]
+        resolutions: String,
+        /// Start of the backfill (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d)
+        #[arg(short, long, default_value = "7d")]
+        from: String,
+        /// Also sync funding-rate history
+        #[arg(long)]
+        funding: bool,
+        /// Also record a snapshot of the public trade tape
+        #[arg(long)]
+        trades: bool,
+        /// Refetch missing bars inside already cached ranges
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Show cached series, their coverage and known gaps
+    Status,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ReportCommands {
+    /// Realized/unrealized PnL over a period, per symbol and per day
+    Pnl {
+        /// Start time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d)
+        #[arg(short, long)]
+        from: String,
+        /// End time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d, defaults to now)
+        #[arg(short, long)]
+        to: Option<String>,
+        /// Restrict the report to one symbol (default: every listed symbol)
+        #[arg(short, long)]
+        symbol: Option<String>,
+        /// Cost basis used to match closing fills against open inventory
+        #[arg(long, value_enum, default_value = "fifo")]
+        method: CostBasisMethod,
+    },
+    /// Closed tax lots (cost basis, proceeds, fees, holding period) for export
+    Lots {
+        /// Start time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d)
+        #[arg(short, long)]
+        from: String,
+        /// End time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d, defaults to now)
+        #[arg(short, long)]
+        to: Option<String>,
+        /// Restrict the export to one symbol (default: every listed symbol)
+        #[arg(short, long)]
+        symbol: Option<String>,
+        /// Lot matching order; discrete lots rule out average cost
+        #[arg(long, value_enum, default_value = "fifo")]
+        method: LotMethod,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PortfolioCommands {
+    /// Store balance, equity, margin usage and per-position PnL in the local
+    /// portfolio history (authenticated)
+    Record {
+        /// Keep recording every N seconds until interrupted (default: one sample)
+        #[arg(short, long)]
+        interval: Option<u64>,
+    },
+    /// Equity curve, drawdown, daily returns and Sharpe-like statistics from
+    /// the recorded history
+    History {
+        /// Start time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d; default: first sample)
+        #[arg(short, long)]
+        from: Option<String>,
+        /// End time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d, defaults to now)
+        #[arg(short, long)]
+        to: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// List recorded mutations
+    Show(AuditFilter),
+    /// Recompute the hash chain and fail on the first tampered record
+    Verify,
+    /// Write recorded mutations as JSON lines (as stored), or JSON/CSV with -o
+    Export {
+        #[command(flatten)]
+        filter: AuditFilter,
+        /// Write to this file instead of stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+/// Time and symbol filters for `audit show` and `audit export`.
+#[derive(clap::Args, Debug)]
+pub struct AuditFilter {
+    /// Start time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d)
+    #[arg(short, long)]
+    pub from: Option<String>,
+    /// End time (timestamp, ISO date YYYY-MM-DD, or relative like 1h, 1d, 7d, defaults to now)
+    #[arg(short, long)]
+    pub to: Option<String>,
+    /// Only records for this symbol
+    #[arg(short, long)]
+    pub symbol: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RiskCommands {
+    /// Simulate an order against the current balance and positions: margin,
+    /// estimated liquidation price, margin ratio and available balance
+    /// before and after (nothing is placed)
+    Whatif {
+        /// Symbol to trade (e.g., BTC-USD)
+        #[arg(short, long)]
+        symbol: String,
+        /// Order size in base units: positive buys, negative sells
+        #[arg(long, allow_negative_numbers = true)]
+        qty: f64,
+        /// Fill price (default: current mark price)
+        #[arg(long)]
+        price: Option<f64>,
+        /// Leverage to simulate (default: the symbol's current leverage)
+        #[arg(long)]
+        leverage: Option<u32>,
+        /// Margin mode to simulate (default: the symbol's current mode)
+        #[arg(long, value_enum)]
+        margin_mode: Option<MarginMode>,
+        /// Maintenance margin rate, e.g. 0.005 (default: the open position's
+        /// rate, else half the initial rate at maximum leverage)
+        #[arg(long)]
+        mmr: Option<f64>,
+    },
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WatchArgs {
+    /// Rules file, one rule per line (re-read when it changes or on SIGHUP)
+    #[arg(short, long)]
+    pub rules: Option<PathBuf>,
+    /// Inline rule, e.g. "BTC-USD mark > 70000" (repeatable)
+    #[arg(long = "rule")]
+    pub rule: Vec<String>,
+    /// Symbols for rules that do not name one (comma-separated)
+    #[arg(short, long = "symbols", alias = "symbol")]
+    pub symbols: Option<String>,
+    /// Minimum time between two firings of the same rule (30s, 10m, 1h);
+    /// a rule's own cooldown= overrides it
+    #[arg(long, default_value = "5m")]
+    pub cooldown: String,
+    /// Seconds between funding-rate and position polls
+    #[arg(long, default_value_t = 30)]
+    pub poll_secs: u64,
+    /// Seconds between rules-file change checks
+    #[arg(long, default_value_t = 5)]
+    pub reload_secs: u64,
+    /// Also POST alerts to this URL. Payload shape is set by
+    /// --alert-webhook-format
+    #[arg(long, env = "STANDX_SUPERVISOR_WEBHOOK")]
+    pub alert_webhook: Option<String>,
+    /// Webhook payload format for the target chat platform
+    #[arg(
+        long,
+        value_enum,
+        default_value = "slack",
+        env = "STANDX_SUPERVISOR_WEBHOOK_FORMAT"
+    )]
+    pub alert_webhook_format: AlertWebhookFormat,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct RecordArgs {
+    /// Symbols to record (comma-separated, e.g. BTC-USD,ETH-USD)
+    #[arg(short, long = "symbols", alias = "symbol")]
+    pub symbols: String,
+    /// Channels to record (comma-separated)
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "price,depth,trade"
+    )]
+    pub channels: Vec<RecordChannel>,
+    /// Kline interval when the kline channel is recorded
+    #[arg(long, default_value = "1")]
+    pub kline_interval: String,
+    /// Directory the NDJSON files are written to (created if absent)
+    #[arg(short, long)]
+    pub out_dir: String,
+    /// File name prefix
+    #[arg(long, default_value = "standx")]
+    pub prefix: String,
+    /// Start a new file after this many minutes (0 disables)
+    #[arg(long, default_value_t = 60)]
+    pub rotate_minutes: u64,
+    /// Start a new file after this many MiB of uncompressed output (0 disables)
+    #[arg(long, default_value_t = 256)]
+    pub rotate_mb: u64,
+    /// Gzip-compress output files (.ndjson.gz)
+    #[arg(long)]
+    pub compress: bool,
+    /// Seconds between output-file flushes
+    #[arg(long, default_value_t = 5)]
+    pub flush_secs: u64,
+    /// Seconds between stderr status heartbeats
+    #[arg(long, default_value_t = 30)]
+    pub status_secs: u64,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct McpArgs {
+    /// Tool profile: read-only exposes market, account, trade and maker-status
+    /// tools; trading also exposes order create/cancel
+    #[arg(
+        long,
+        value_enum,
+        default_value = "read-only",
+        env = "STANDX_MCP_PROFILE"
+    )]
+    pub profile: ToolProfile,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ServeArgs {
+    /// Address to listen on; keep it on loopback unless something else
+    /// (a TLS proxy, a firewall) guards the port
+    #[arg(long, default_value = "127.0.0.1:8787")]
+    pub listen: SocketAddr,
+    /// Bearer token clients must send; a random one is generated and written
+    /// to the config directory when unset
+    #[arg(long, env = "STANDX_SERVE_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+    /// Endpoint profile: read-only serves queries; trading also serves order
+    /// create/cancel
+    #[arg(
+        long,
+        value_enum,
+        default_value = "read-only",
+        env = "STANDX_SERVE_PROFILE"
+    )]
+    pub profile: ToolProfile,
+    /// Symbols whose price, depth and trade channels the warm WebSocket
+    /// session carries (comma-separated)
+    #[arg(long, value_delimiter = ',', default_value = "BTC-USD")]
+    pub symbols: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct ApplyArgs {
+    /// Plan file written by `--plan-out`
+    pub plan: PathBuf,
+    /// Refuse plans older than this many seconds
+    #[arg(long, default_value_t = 300)]
+    pub max_age_secs: u64,
+    /// Refuse when a symbol's mark price moved more than this many basis
+    /// points since the plan was made
+    #[arg(long, default_value_t = 50.0)]
+    pub max_drift_bps: f64,
+    /// Refuse limit orders priced further than this many basis points from
+    /// the current mark price
+    #[arg(long, default_value_t = 500.0)]
+    pub price_band_bps: f64,
+}
+
+/// Execution assumptions of `maker backtest` and `maker sweep`.
+#[derive(clap::Args, Debug, Clone)]
+pub struct BacktestArgs {
+    /// Symbol to simulate; required when the recording holds several
+    #[arg(short, long)]
+    pub symbol: Option<String>,
+    /// Position held when the simulation starts
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub starting_position: f64,
+    /// Milliseconds from a cycle to its orders and exits reaching the book
+    #[arg(long, default_value_t = 150)]
+    pub order_latency_ms: u64,
+    /// Milliseconds from a cycle to its cancels taking effect
+    #[arg(long, default_value_t = 150)]
+    pub cancel_latency_ms: u64,
+    /// Fee on passive fills in bps of notional; negative for a rebate
+    #[arg(long, default_value_t = 0.0, allow_negative_numbers = true)]
+    pub maker_fee_bps: f64,
+    /// Fee on inventory exits in bps of notional
+    #[arg(long, default_value_t = 0.0)]
+    pub taker_fee_bps: f64,
+    /// Price decimals; inferred from the recorded book when omitted
+    #[arg(long)]
+    pub price_decimals: Option<u32>,
+    /// Quantity decimals; inferred from the recorded book when omitted
+    #[arg(long)]
+    pub qty_decimals: Option<u32>,
+    /// Minimum order quantity; one quantity tick when omitted
+    #[arg(long)]
+    pub min_order_qty: Option<f64>,
+}
+
+/// Metric `maker sweep` ranks candidates by.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SweepRank {
+    /// Net PnL in quote, higher first
+    NetPnl,
+    /// 5s markout in bps, higher first
+    Markout,
+    /// Time-weighted two-sided uptime, higher first
+    Uptime,
+    /// Share of time holding inventory, lower first
+    Inventory,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct SchemaArgs {
+    /// Only describe this command and its subcommands (e.g. `market ticker`)
+    #[arg(value_name = "COMMAND")]
+    pub command: Vec<String>,
+}
+
+/// Which commands `standx mcp` and `standx serve` expose.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolProfile {
+    /// Queries only; order commands are neither listed nor callable
+    ReadOnly,
+    /// Queries plus order create/cancel (still subject to --dry-run and confirmation)
+    Trading,
+}
+
+/// Public market-data channel captured by `standx record`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RecordChannel {
+    /// Mark/index/last price updates
+    Price,
+    /// Order book snapshots
+    Depth,
+    /// Public trades
+    Trade,
+    /// Candles at `--kline-interval`
+    Kline,
+}
+
+impl RecordChannel {
+    /// WebSocket channel name.
+    pub fn topic(self) -> &'static str {
+        match self {
+            RecordChannel::Price => "price",
+            RecordChannel::Depth => "depth_book",
+            RecordChannel::Trade => "public_trade",
+            RecordChannel::Kline => "kline",
+        }
+    }
+}
+
+/// How closing fills are matched against open inventory when realizing PnL.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CostBasisMethod {
+    /// First in, first out: close the oldest open lot first.
+    #[default]
+    Fifo,
+    /// Last in, first out: close the newest open lot first.
+    Lifo,
+    /// Highest in, first out: close the lot that realizes the smallest gain
+    /// first (highest entry for longs, lowest entry for shorts).
+    Hifo,
+    /// Average cost: every open unit carries the running average entry price.
+    Average,
+}
+
+/// How closing fills are matched against discrete tax lots. Average cost
+/// merges lots, losing their open times and trade ids, so it is not offered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LotMethod {
+    /// First in, first out: close the oldest open lot first.
+    #[default]
+    Fifo,
+    /// Last in, first out: close the newest open lot first.
+    Lifo,
+    /// Highest in, first out: close the lot that realizes the smallest gain
+    /// first (highest entry for longs, lowest entry for shorts).
+    Hifo,
+}
+
+impl From<LotMethod> for CostBasisMethod {
+    fn from(method: LotMethod) -> Self {
+        match method {
+            LotMethod::Fifo => Self::Fifo,
+            LotMethod::Lifo => Self::Lifo,
+            LotMethod::Hifo => Self::Hifo,
+        }
+    }
+}
+
+/// Position margin mode.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MarginMode {
+    /// Margin shared across every cross position
+    #[default]
+    Cross,
+    /// Margin assigned to the one position
+    Isolated,
+}
+
+impl MarginMode {
+    /// Name used by the StandX API.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MarginMode::Cross => "cross",
+            MarginMode::Isolated => "isolated",
+        }
+    }
+
+    /// Parse a mode reported by the API.
+    pub fn from_api(mode: &str) -> Option<Self> {
+        match mode.to_ascii_lowercase().as_str() {
+            "cross" => Some(MarginMode::Cross),
+            "isolated" => Some(MarginMode::Isolated),
+            _ => None,
+        }
+    }
+}
+
 // `Run` is intentionally a flat, user-facing strategy CLI. Keeping the
 // supervised canary as a separate small variant makes the command surface
 // clearer but triggers Clippy's enum-layout lint; boxing `Run` would make the
@@ -487,6 +1001,11 @@ pub enum MakerCommands {
         /// StandX config directory when it exists.
         #[arg(long)]
         maker_config: Option<PathBuf>,
+        /// Also reload the maker TOML when the file changes. SIGHUP always
+        /// reloads; either way safe fields apply at the next cycle and
+        /// restart-only fields reject the whole reload
+        #[arg(long)]
+        watch_config: bool,
         /// Half-spread from mark price in basis points
         #[arg(long)]
         spread_bps: Option<f64>,
@@ -600,6 +1119,29 @@ pub enum MakerCommands {
         /// full loop, prints intended actions, no orders placed)
         #[arg(long)]
         live: bool,
+        /// Write the session as a normalized replay trace (header, every
+        /// cycle's planner inputs, fills and funding) for `maker replay`
+        #[arg(long, value_name = "FILE")]
+        trace_out: Option<PathBuf>,
+        /// Persist session accounting (ledger, PnL attribution, stats, alert
+        /// anchors) to this file after every committed cycle and on shutdown.
+        /// Replaced atomically. Live only
+        #[arg(long, value_name = "FILE")]
+        state_file: Option<PathBuf>,
+        /// Continue the session saved in --state-file instead of starting a
+        /// new one. Refuses to start unless the venue position matches the
+        /// saved ledger and no other run's maker orders are open
+        #[arg(long, requires = "state_file")]
+        resume: bool,
+        /// Listen on this Unix domain socket for `maker ctl` commands (pause,
+        /// resume, widen, size, flatten, stop, status). Owner-only (0600)
+        #[arg(long, value_name = "FILE")]
+        control_socket: Option<PathBuf>,
+        /// Serve Prometheus metrics (position, PnL components, uptime,
+        /// resting quotes, order latency, recoveries, guard state, cycle
+        /// durations) at http://ADDR/metrics. Keep it on loopback
+        #[arg(long, value_name = "ADDR")]
+        metrics_listen: Option<SocketAddr>,
         /// Maximum authenticated order-response reconnect attempts per round.
         /// Each attempt first cleans maker orders and must
         /// reconcile an empty maker book before quoting resumes. 0 disables.
@@ -633,10 +1175,76 @@ pub enum MakerCommands {
         #[arg(long, hide = true)]
         controlled_disconnect_after: Option<u64>,
     },
+    /// Send a command to a running maker's control socket (`maker run --control-socket`)
+    Ctl {
+        /// The running maker's control socket
+        #[arg(long, value_name = "FILE")]
+        socket: PathBuf,
+        #[command(subcommand)]
+        command: MakerCtlCommand,
+    },
+    /// Show whether a live maker is running and the orders/positions it manages
+    Status {
+        /// Limit orders and positions to one symbol
+        #[arg(short, long)]
+        symbol: Option<String>,
+    },
     /// Deterministically replay a normalized maker NDJSON trace (no network or order I/O)
     Replay {
         /// Normalized phase-1 trace file; use '-' to read stdin
         trace: PathBuf,
+        /// Replan with the `[strategy]` table of this maker TOML instead of
+        /// the strategy the trace was recorded with (no table means ladder)
+        #[arg(long, value_name = "FILE")]
+        strategy_config: Option<PathBuf>,
+    },
+    /// Simulate a maker config over `standx record` depth and trade data with a
+    /// queue-position fill model (no network or order I/O)
+    Backtest {
+        /// Maker strategy TOML file, as for `maker run`. If omitted, loads
+        /// maker.toml from the StandX config directory when it exists.
+        #[arg(long)]
+        maker_config: Option<PathBuf>,
+        /// `standx record` files (.ndjson or .ndjson.gz) with depth and trade
+        /// channels; the price channel supplies the mark when recorded
+        #[arg(required = true, value_name = "FILE")]
+        data: Vec<PathBuf>,
+        #[command(flatten)]
+        simulation: BacktestArgs,
+    },
+    /// Backtest every combination of a parameter grid over `standx record`
+    /// data in parallel, rank the candidates and write the best maker TOML
+    Sweep {
+        /// `standx record` files (.ndjson or .ndjson.gz) to evaluate every
+        /// candidate on, as for `maker backtest`; replay traces written by
+        /// `maker run --trace-out` are not accepted
+        #[arg(long, required = true, num_args = 1.., value_name = "FILE")]
+        data: Vec<PathBuf>,
+        /// Grid TOML: every array lists the alternatives of one maker
+        /// config key, e.g. `spread_bps = [4, 5, 6]`
+        #[arg(long)]
+        grid: PathBuf,
+        /// Base maker TOML the grid overrides. If omitted, loads maker.toml
+        /// from the StandX config directory when it exists.
+        #[arg(long)]
+        maker_config: Option<PathBuf>,
+        /// Primary ranking metric; the others break ties in the listed order
+        #[arg(long, value_enum, default_value = "net-pnl")]
+        rank_by: SweepRank,
+        /// Candidates shown in the table (0 shows all)
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+        /// Backtests run at once (default: available CPUs)
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Write every candidate's results as CSV
+        #[arg(long, value_name = "FILE")]
+        results_out: Option<PathBuf>,
+        /// Write the best candidate as a ready-to-use maker TOML
+        #[arg(long, value_name = "FILE")]
+        best_out: Option<PathBuf>,
+        #[command(flatten)]
+        simulation: BacktestArgs,
     },
     /// Supervised live-gate check for the WebSocket order:new/order:cancel path.
     #[command(hide = true)]
@@ -666,6 +1274,32 @@ pub enum MakerCommands {
     },
 }
 
+/// Runtime commands for `maker ctl`. Each one is logged and notified by the
+/// running maker.
+#[derive(Subcommand, Clone, Copy, Debug, PartialEq)]
+pub enum MakerCtlCommand {
+    /// Cancel resting quotes and stop quoting; inventory exits keep running
+    Pause,
+    /// Resume quoting after `pause` (a latched `flatten` stays latched)
+    Resume,
+    /// Add this many bps to the half-spread on top of any adaptive tier (0 removes it)
+    Widen {
+        /// Extra half-spread in basis points
+        bps: f64,
+    },
+    /// Override the per-level quote size (0 restores the configured size)
+    Size {
+        /// Per-level order quantity
+        qty: f64,
+    },
+    /// Stop quoting for good and flatten the position via reduce-only exits
+    Flatten,
+    /// Stop the maker gracefully, as Ctrl+C does
+    Stop,
+    /// Print the running maker's position, quotes and active overrides
+    Status,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
 pub enum OutputFormat {
     #[default]
@@ -675,6 +1309,69 @@ pub enum OutputFormat {
     Quiet,
 }
 
+/// Value of the global `--output` flag.
+///
+/// Handlers only ever see an [`OutputFormat`]: the extra structured
+/// encodings are JSON as far as a handler is concerned and are applied by
+/// [`crate::output`] when the JSON is rendered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputArg {
+    #[default]
+    Table,
+    Json,
+    /// Newline-delimited JSON: one compact record per line
+    Ndjson,
+    Yaml,
+    Csv,
+    Quiet,
+}
+
+impl Cli {
+    /// Resolve `--output`, `--quiet`, `--openclaw`, `--fields` and
+    /// `--template` into the format handlers branch on plus the process-wide
+    /// [`output::OutputOptions`](crate::output::OutputOptions).
+    ///
+    /// `--quiet` wins over everything, then `--template`, then OpenClaw's
+    /// forced JSON. A table with `--fields` is rendered from the JSON form
+    /// so the selected paths become its columns.
+    pub fn output_settings(&self) -> crate::Result<(OutputFormat, crate::output::OutputOptions)> {
+        use crate::output::{Encoding, OutputOptions, Template};
+
+        let fields: Vec<String> = self
+            .fields
+            .iter()
+            .map(|field| field.trim().to_string())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let template = self.template.as_deref().map(Template::load).transpose()?;
+        let (format, encoding) = if self.quiet {
+            (OutputFormat::Quiet, Encoding::Json)
+        } else if template.is_some() {
+            (OutputFormat::Json, Encoding::Template)
+        } else if self.openclaw {
+            (OutputFormat::Json, Encoding::Json)
+        } else {
+            match self.output {
+                OutputArg::Table if !fields.is_empty() => (OutputFormat::Json, Encoding::Table),
+                OutputArg::Table => (OutputFormat::Table, Encoding::Json),
+                OutputArg::Json => (OutputFormat::Json, Encoding::Json),
+                OutputArg::Ndjson => (OutputFormat::Json, Encoding::Ndjson),
+                OutputArg::Yaml => (OutputFormat::Json, Encoding::Yaml),
+                OutputArg::Csv => (OutputFormat::Csv, Encoding::Json),
+                OutputArg::Quiet => (OutputFormat::Quiet, Encoding::Json),
+            }
+        };
+        Ok((
+            format,
+            OutputOptions {
+                encoding,
+                fields,
+                template,
+            },
+        ))
+    }
+}
+
 /// Payload shape for `--alert-webhook`, per target chat platform.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
 pub enum AlertWebhookFormat {
@@ -804,4 +1501,105 @@ mod tests {
             assert_eq!(size_skew, Some(expected));
         }
     }
+
+    #[test]
+    fn maker_resume_requires_a_state_file() {
+        assert!(Cli::try_parse_from(["standx", "maker", "run", "XAG-USD", "--resume"]).is_err());
+        let cli = Cli::try_parse_from([
+            "standx",
+            "maker",
+            "run",
+            "XAG-USD",
+            "--state-file",
+            "maker-state.json",
+            "--resume",
+        ])
+        .expect("resume with a state file should parse");
+        let Commands::Maker { command } = cli.command else {
+            panic!("expected maker command");
+        };
+        let MakerCommands::Run {
+            state_file, resume, ..
+        } = *command
+        else {
+            panic!("expected maker run command");
+        };
+        assert_eq!(state_file.as_deref(), Some(Path::new("maker-state.json")));
+        assert!(resume);
+    }
+
+    #[test]
+    fn maker_ctl_parses_commands_with_arguments() {
+        let parse = |args: &[&str]| {
+            let mut argv = vec!["standx", "maker", "ctl", "--socket", "maker.sock"];
+            argv.extend_from_slice(args);
+            let cli = Cli::try_parse_from(argv).expect("maker ctl should parse");
+            let Commands::Maker { command } = cli.command else {
+                panic!("expected maker command");
+            };
+            let MakerCommands::Ctl { socket, command } = *command else {
+                panic!("expected maker ctl command");
+            };
+            assert_eq!(socket, Path::new("maker.sock"));
+            command
+        };
+
+        assert_eq!(parse(&["pause"]), MakerCtlCommand::Pause);
+        assert_eq!(
+            parse(&["widen", "4.5"]),
+            MakerCtlCommand::Widen { bps: 4.5 }
+        );
+        assert_eq!(
+            parse(&["size", "0.02"]),
+            MakerCtlCommand::Size { qty: 0.02 }
+        );
+        assert!(
+            Cli::try_parse_from(["standx", "maker", "ctl", "--socket", "m.sock", "widen"]).is_err()
+        );
+        assert!(Cli::try_parse_from(["standx", "maker", "ctl", "pause"]).is_err());
+    }
+
+    #[test]
+    fn output_settings_route_structured_encodings_through_json() {
+        use crate::output::Encoding;
+
+        let settings = |args: &[&str]| {
+            let mut argv = vec!["standx", "market", "symbols"];
+            argv.extend_from_slice(args);
+            let (format, options) = Cli::try_parse_from(argv)
+                .expect("output flags should parse")
+                .output_settings()
+                .expect("output settings should resolve");
+            (format, options.encoding, options.fields)
+        };
+
+        assert_eq!(
+            settings(&[]),
+            (OutputFormat::Table, Encoding::Json, Vec::<String>::new())
+        );
+        assert_eq!(settings(&["-o", "ndjson"]).1, Encoding::Ndjson);
+        assert_eq!(settings(&["-o", "yaml"]).0, OutputFormat::Json);
+        assert_eq!(
+            settings(&["--fields", "symbol, maker_fee"]),
+            (
+                OutputFormat::Json,
+                Encoding::Table,
+                vec!["symbol".to_string(), "maker_fee".to_string()]
+            )
+        );
+        assert_eq!(
+            settings(&["-o", "csv", "--template", "{{symbol}}"]).1,
+            Encoding::Template
+        );
+        assert_eq!(
+            settings(&["-q", "--template", "{{symbol}}"]).0,
+            OutputFormat::Quiet
+        );
+        assert!(
+            Cli::try_parse_from(["standx", "--template", "{{x", "market", "symbols"])
+                .unwrap()
+                .output_settings()
+                .is_err()
+        );
+    }
 }
@@ -14,7 +14,7 @@ pub async fn handle_account(command: AccountCommands, output_format: OutputForma
             match output_format {
                 OutputFormat::Table => println!("{}", output::format_item(balance)),
                 OutputFormat::Json => println!("{}", output::format_json(&balance)?),
-                OutputFormat::Csv => println!("CSV format not supported for single item"),
+                OutputFormat::Csv => println!("{}", output::format_csv(&[balance])?),
                 OutputFormat::Quiet => {}
             }
         }
@@ -62,7 +62,7 @@ pub async fn handle_account(command: AccountCommands, output_format: OutputForma
     Ok(())
 }
 
-fn position_quantity_is_nonzero_or_invalid(value: &str) -> bool {
+pub(super) fn position_quantity_is_nonzero_or_invalid(value: &str) -> bool {
     match value.parse::<f64>() {
         Ok(quantity) if quantity.is_finite() => quantity != 0.0,
         _ => true,
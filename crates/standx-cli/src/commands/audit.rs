@@ -0,0 +1,226 @@
+//! `standx audit` — read and check the local audit log
+//!
+//! `show` lists recorded mutations, `export` writes them out for archiving
+//! (JSON lines exactly as stored by default, or JSON/CSV with `-o`), and
+//! `verify` recomputes the hash chain of [`crate::audit`] and fails on the
+//! first record that was edited, reordered or removed.
+
+use super::schema::{array_of, trace, Definitions};
+use super::util::parse_time_string;
+use crate::audit::{AuditLog, Break, Outcome, Record, Verification};
+use crate::cli::*;
+use crate::output;
+use anyhow::{bail, Context, Result};
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+
+/// Handle audit commands
+pub async fn handle_audit(command: AuditCommands, output_format: OutputFormat) -> Result<()> {
+    let log = AuditLog::open_default();
+    match command {
+        AuditCommands::Show(filter) => {
+            let records = filtered(&log, &filter)?;
+            match output_format {
+                OutputFormat::Table if records.is_empty() => {
+                    println!("ℹ️  No audit records in {}", log.path().display());
+                }
+                OutputFormat::Table => print!("{}", format_table(&records)),
+                OutputFormat::Json => println!("{}", output::format_json(&records)?),
+                OutputFormat::Csv => print!("{}", output::format_csv(&csv_rows(&records))?),
+                OutputFormat::Quiet => println!("{}", records.len()),
+            }
+        }
+        AuditCommands::Export { filter, out } => {
+            let records = filtered(&log, &filter)?;
+            let text = match output_format {
+                OutputFormat::Json => format!("{}\n", output::format_json(&records)?),
+                OutputFormat::Csv => output::format_csv(&csv_rows(&records))?,
+                OutputFormat::Table | OutputFormat::Quiet => json_lines(&records)?,
+            };
+            match out {
+                Some(path) => {
+                    std::fs::write(&path, text)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    if output_format != OutputFormat::Quiet {
+                        eprintln!(
+                            "✅ Exported {} audit records to {}",
+                            records.len(),
+                            path.display()
+                        );
+                    }
+                }
+                None => std::io::stdout().write_all(text.as_bytes())?,
+            }
+        }
+        AuditCommands::Verify => {
+            let verification = log.verify()?;
+            match output_format {
+                OutputFormat::Json | OutputFormat::Csv => {
+                    println!("{}", output::format_json(&verification)?)
+                }
+                OutputFormat::Quiet => println!("{}", verification.head),
+                OutputFormat::Table if verification.intact => {
+                    println!(
+                        "✅ Audit log intact: {} records in {}",
+                        verification.records,
+                        log.path().display()
+                    );
+                    println!("   Head: {}", verification.head);
+                }
+                OutputFormat::Table => {}
+            }
+            if let Some(broken) = verification.broken {
+                bail!(
+                    "audit log {} fails verification at line {} (after {} intact records): {}",
+                    log.path().display(),
+                    broken.line,
+                    verification.records,
+                    broken.reason
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Records inside the `--from`/`--to` window and on `--symbol`.
+fn filtered(log: &AuditLog, filter: &AuditFilter) -> Result<Vec<Record>> {
+    let from = filter
+        .from
+        .as_deref()
+        .map(|from| parse_time_string(from, false))
+        .transpose()?;
+    let to = match &filter.to {
+        Some(to) => parse_time_string(to, true)?,
+        None => Utc::now().timestamp(),
+    };
+    Ok(log
+        .read()?
+        .into_iter()
+        .filter(|record| {
+            let ts = record.ts.timestamp();
+            from.map_or(true, |from| ts >= from)
+                && ts <= to
+                && filter
+                    .symbol
+                    .as_deref()
+                    .map_or(true, |symbol| record.symbol.eq_ignore_ascii_case(symbol))
+        })
+        .collect())
+}
+
+fn json_lines(records: &[Record]) -> Result<String> {
+    let mut text = String::new();
+    for record in records {
+        text.push_str(&serde_json::to_string(record)?);
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// A record flattened for CSV, with the venue response as JSON text.
+#[derive(Serialize)]
+struct CsvRow<'a> {
+    seq: u64,
+    ts: String,
+    source: &'a str,
+    profile: &'a str,
+    command: &'a str,
+    action: &'a str,
+    symbol: &'a str,
+    request_hash: &'a str,
+    response: String,
+    outcome: &'static str,
+    prev_hash: &'a str,
+    hash: &'a str,
+}
+
+fn csv_rows(records: &[Record]) -> Vec<CsvRow<'_>> {
+    records
+        .iter()
+        .map(|record| CsvRow {
+            seq: record.seq,
+            ts: record.ts.to_rfc3339(),
+            source: &record.source,
+            profile: &record.profile,
+            command: &record.command,
+            action: &record.action,
+            symbol: &record.symbol,
+            request_hash: &record.request_hash,
+            response: record.response.to_string(),
+            outcome: outcome_label(record.outcome),
+            prev_hash: &record.prev_hash,
+            hash: &record.hash,
+        })
+        .collect()
+}
+
+fn outcome_label(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Ok => "ok",
+        Outcome::Error => "error",
+    }
+}
+
+fn format_table(records: &[Record]) -> String {
+    let header = format!(
+        "{:>6} {:<20} {:<10} {:<18} {:<12} {:<7} {:<12}",
+        "SEQ", "TIME", "SOURCE", "ACTION", "SYMBOL", "OUTCOME", "HASH"
+    );
+    let separator = "-".repeat(header.len());
+    let mut text = format!("{header}\n{separator}\n");
+    for record in records {
+        text.push_str(&format!(
+            "{:>6} {:<20} {:<10} {:<18} {:<12} {:<7} {:<12}\n",
+            record.seq,
+            record.ts.format("%Y-%m-%d %H:%M:%S"),
+            record.source,
+            record.action,
+            record.symbol,
+            outcome_label(record.outcome),
+            &record.hash[..record.hash.len().min(12)],
+        ));
+    }
+    text
+}
+
+/// JSON output schema of `audit <subcommand>`, for `standx schema`.
+pub(super) fn output_schema(subcommand: &str, definitions: &mut Definitions) -> Option<Value> {
+    match subcommand {
+        "show" | "export" => {
+            trace(definitions, &Outcome::Error);
+            Some(array_of(
+                definitions,
+                &Record {
+                    seq: 0,
+                    ts: Utc::now(),
+                    source: String::new(),
+                    profile: String::new(),
+                    command: String::new(),
+                    action: String::new(),
+                    symbol: String::new(),
+                    request_hash: String::new(),
+                    response: Value::Null,
+                    outcome: Outcome::Ok,
+                    prev_hash: String::new(),
+                    hash: String::new(),
+                },
+            ))
+        }
+        "verify" => Some(trace(
+            definitions,
+            &Verification {
+                intact: false,
+                records: 0,
+                head: String::new(),
+                broken: Some(Break {
+                    line: 0,
+                    reason: String::new(),
+                }),
+            },
+        )),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 use crate::cli::*;
+use crate::output;
 use anyhow::Result;
 use standx_sdk::client::StandXClient;
 use standx_sdk::models::BlockTrade;
@@ -30,22 +31,27 @@ pub async fn handle_block(command: BlockCommands, output_format: OutputFormat) -
             }
 
             match output_format {
-                OutputFormat::Json => {
-                    println!("{}", serde_json::to_string_pretty(&trades)?);
-                }
-                _ => {
-                    println!("{}", format_block_trades_table(&trades));
-                }
+                OutputFormat::Json => println!("{}", output::format_json(&trades)?),
+                OutputFormat::Csv => println!("{}", output::format_csv(&trades)?),
+                OutputFormat::Quiet => {}
+                OutputFormat::Table => println!("{}", format_block_trades_table(&trades)),
             }
         }
         BlockCommands::Watch { symbol, interval } => {
-            println!(
+            // Structured output keeps stdout to one record per trade.
+            let structured = output_format == OutputFormat::Json;
+            let banner = format!(
                 "Watching block trades{} (Ctrl+C to exit)\n",
                 symbol
                     .as_ref()
                     .map(|s| format!(" for {}", s))
                     .unwrap_or_default()
             );
+            if structured {
+                eprintln!("{}", banner);
+            } else {
+                println!("{}", banner);
+            }
 
             let mut last_id: Option<i64> = None;
             loop {
@@ -55,7 +61,14 @@ pub async fn handle_block(command: BlockCommands, output_format: OutputFormat) -
                             Ok(trades) => {
                                 for trade in &trades {
                                     if last_id.map(|id| trade.id > id).unwrap_or(true) {
-                                        print_block_trade_line(trade);
+                                        if structured {
+                                            println!(
+                                                "{}",
+                                                output::format_event(&serde_json::to_value(trade)?)
+                                            );
+                                        } else {
+                                            print_block_trade_line(trade);
+                                        }
                                         last_id = Some(trade.id).max(last_id);
                                     }
                                 }
@@ -66,7 +79,11 @@ pub async fn handle_block(command: BlockCommands, output_format: OutputFormat) -
                         }
                     }
                     _ = tokio::signal::ctrl_c() => {
-                        println!("\nExiting block trade watch");
+                        if structured {
+                            eprintln!("\nExiting block trade watch");
+                        } else {
+                            println!("\nExiting block trade watch");
+                        }
                         break;
                     }
                 }
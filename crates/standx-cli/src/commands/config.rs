@@ -1,5 +1,6 @@
 use crate::cli::*;
 use crate::config::Config;
+use crate::output;
 use anyhow::Result;
 
 /// Handle config commands
@@ -15,7 +16,7 @@ pub async fn handle_config(command: ConfigCommands, output_format: OutputFormat)
                         "message": "Configuration initialized",
                         "config_file": config.config_file()
                     });
-                    println!("{}", serde_json::to_string_pretty(&json)?);
+                    println!("{}", output::format_json(&json)?);
                 }
                 OutputFormat::Quiet => {}
                 _ => println!("✅ Configuration initialized at {:?}", config.config_file()),
@@ -31,7 +32,7 @@ pub async fn handle_config(command: ConfigCommands, output_format: OutputFormat)
                         "key": key,
                         "value": value
                     });
-                    println!("{}", serde_json::to_string_pretty(&json)?);
+                    println!("{}", output::format_json(&json)?);
                 }
                 OutputFormat::Quiet => {}
                 _ => println!("✅ Set {} = {}", key, value),
@@ -46,7 +47,7 @@ pub async fn handle_config(command: ConfigCommands, output_format: OutputFormat)
                         "key": key,
                         "value": value
                     });
-                    println!("{}", serde_json::to_string_pretty(&json)?);
+                    println!("{}", output::format_json(&json)?);
                 }
                 OutputFormat::Quiet => println!("{}", value),
                 _ => println!("{}: {}", key, value),
@@ -61,7 +62,7 @@ pub async fn handle_config(command: ConfigCommands, output_format: OutputFormat)
                         "output_format": config.output_format,
                         "default_symbol": config.default_symbol
                     });
-                    println!("{}", serde_json::to_string_pretty(&json)?);
+                    println!("{}", output::format_json(&json)?);
                 }
                 OutputFormat::Quiet => {}
                 _ => {
@@ -1,5 +1,8 @@
+use super::data::cached_klines;
+use super::tui::run_tui;
 use super::util::{is_auth_error, run_watch_loop};
 use crate::cli::*;
+use crate::market_cache::MarketCache;
 use crate::output;
 use anyhow::Result;
 use futures::future::join_all;
@@ -16,6 +19,8 @@ pub async fn handle_dashboard(
     verbose: bool,
     watch: Option<u64>,
     compact: bool,
+    no_cache: bool,
+    tui: bool,
     output_format: OutputFormat,
 ) -> Result<()> {
     // Build list of symbols to track
@@ -28,7 +33,11 @@ pub async fn handle_dashboard(
     } else {
         vec![]
     };
+    if tui {
+        return run_tui(symbol_list, verbose).await;
+    }
     let client = StandXClient::new()?;
+    let cache = (!no_cache).then(MarketCache::open_default);
     let ws_trades: Arc<RwLock<VecDeque<Trade>>> = Arc::new(RwLock::new(VecDeque::new()));
     let mut ws_trade_updates_rx: Option<watch::Receiver<u64>> = None;
     let mut ws_trades_enabled = false;
@@ -92,6 +101,7 @@ pub async fn handle_dashboard(
         || {
             build_dashboard_output(
                 &client,
+                cache.as_ref(),
                 &symbol_list,
                 verbose,
                 output_format,
@@ -112,6 +122,7 @@ pub async fn handle_dashboard(
 /// Build dashboard output with optional symbol filtering
 async fn build_dashboard_output(
     client: &StandXClient,
+    cache: Option<&MarketCache>,
     symbol_filter: &[String],
     _verbose: bool,
     output_format: OutputFormat,
@@ -215,7 +226,7 @@ async fn build_dashboard_output(
         join_all(
             symbol_list
                 .iter()
-                .map(|symbol| client.get_kline(symbol, "1D", from_ts, now_ts))
+                .map(|symbol| cached_klines(client, cache, symbol, "1D", from_ts, now_ts))
         )
     );
 
@@ -291,6 +302,9 @@ async fn build_dashboard_output(
             text
         }
         OutputFormat::Json => format!("{}\n", output::format_json(&snapshot)?),
+        OutputFormat::Csv if output::fields_selected() => {
+            format!("{}\n", output::format_csv(&[&snapshot])?)
+        }
         OutputFormat::Csv => {
             // For CSV, output positions as they're the most important
             if !snapshot.positions.is_empty() {
@@ -0,0 +1,697 @@
+//! `standx data` — local historical market-data cache
+//!
+//! `data sync` backfills and extends the on-disk series in
+//! [`crate::market_cache`] and repairs holes; `data status` summarizes what is
+//! cached. The `cached_*` helpers let the read commands (`market kline`,
+//! `market funding`, `market trades`) and the dashboard serve from the cache,
+//! only asking the API for ranges the cache does not cover yet.
+
+use super::schema::{array_of, Definitions};
+use super::util::parse_time_string;
+use crate::cli::*;
+use crate::market_cache::{
+    resolution_secs, CachedRecord, CachedSeries, MarketCache, SeriesKey, SeriesKind,
+};
+use crate::output;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{FundingRate, Kline, Trade};
+
+/// Bars requested per kline call while backfilling.
+const KLINE_CHUNK_BARS: i64 = 1_000;
+
+/// Window length per funding-rate call while backfilling.
+const FUNDING_CHUNK_SECS: i64 = 7 * 86_400;
+
+/// Funding records newer than this are not treated as final yet.
+const FUNDING_SETTLE_SECS: i64 = 300;
+
+/// A series written this recently may serve its not-yet-final tail (the open
+/// kline bar, the latest funding interval) without another request.
+const TAIL_TTL_SECS: i64 = 60;
+
+/// Trades requested per public-tape snapshot.
+const TRADE_SNAPSHOT_LIMIT: u32 = 500;
+
+/// Handle data commands
+pub async fn handle_data(command: DataCommands, output_format: OutputFormat) -> Result<()> {
+    let cache = MarketCache::open_default();
+    match command {
+        DataCommands::Sync {
+            symbols,
+            resolutions,
+            from,
+            funding,
+            trades,
+            repair,
+        } => {
+            let client = StandXClient::new()?;
+            let symbols = match symbols {
+                Some(symbols) => split_list(&symbols),
+                None => client
+                    .get_symbol_info()
+                    .await?
+                    .into_iter()
+                    .map(|info| info.symbol)
+                    .collect(),
+            };
+            let resolutions = split_list(&resolutions);
+            for resolution in &resolutions {
+                if resolution_secs(resolution).is_none() {
+                    bail!("resolution '{resolution}' cannot be cached (use minutes, 1D or 1W)");
+                }
+            }
+            let now = chrono::Utc::now().timestamp();
+            let from = parse_time_string(&from, false)?;
+            if from >= now {
+                bail!("--from must be in the past");
+            }
+
+            let mut rows = Vec::new();
+            for symbol in &symbols {
+                for resolution in &resolutions {
+                    rows.push(
+                        sync_klines(&client, &cache, symbol, resolution, from, now, repair).await?,
+                    );
+                }
+                if funding {
+                    rows.push(sync_funding(&client, &cache, symbol, from, now).await?);
+                }
+                if trades {
+                    rows.push(sync_trades(&client, &cache, symbol).await?);
+                }
+            }
+            print_rows(&rows, output_format, format_sync_table)?;
+        }
+        DataCommands::Status => {
+            let mut rows = Vec::new();
+            for key in cache.list()? {
+                rows.push(match &key.kind {
+                    SeriesKind::Kline { resolution } => {
+                        let series: CachedSeries<Kline> = cache.load(&key)?;
+                        let step = resolution_secs(resolution).unwrap_or(60);
+                        StatusRow::new(&key, &series, open_holes(&series, step).len())
+                    }
+                    SeriesKind::Funding => {
+                        StatusRow::new(&key, &cache.load::<FundingRate>(&key)?, 0)
+                    }
+                    SeriesKind::Trades => StatusRow::new(&key, &cache.load::<Trade>(&key)?, 0),
+                });
+            }
+            if rows.is_empty() && output_format == OutputFormat::Table {
+                println!(
+                    "ℹ️  Market cache at {} is empty; run `standx data sync`",
+                    cache.root().display()
+                );
+                return Ok(());
+            }
+            print_rows(&rows, output_format, format_status_table)?;
+        }
+    }
+    Ok(())
+}
+
+fn split_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn series_label(key: &SeriesKey) -> String {
+    match &key.kind {
+        SeriesKind::Kline { resolution } => format!("kline-{resolution}"),
+        SeriesKind::Funding => "funding".to_string(),
+        SeriesKind::Trades => "trades".to_string(),
+    }
+}
+
+/// Result of syncing one series.
+#[derive(Debug, Serialize)]
+struct SyncRow {
+    symbol: String,
+    series: String,
+    requests: usize,
+    fetched: usize,
+    records: usize,
+    repaired: usize,
+    /// Ranges known to be missing after the sync.
+    gaps: usize,
+}
+
+/// Summary of one cached series.
+#[derive(Debug, Serialize)]
+struct StatusRow {
+    symbol: String,
+    series: String,
+    records: usize,
+    first: Option<String>,
+    last: Option<String>,
+    /// Uncovered stretches between the first and last covered time.
+    coverage_gaps: usize,
+    /// Missing bars inside covered ranges not yet repaired.
+    holes: usize,
+    /// Ranges marked unrecoverable.
+    gap_markers: usize,
+    synced_at: Option<String>,
+}
+
+impl StatusRow {
+    fn new<T: CachedRecord>(key: &SeriesKey, series: &CachedSeries<T>, holes: usize) -> Self {
+        let coverage = series.coverage();
+        Self {
+            symbol: key.symbol.clone(),
+            series: series_label(key),
+            records: series.len(),
+            first: coverage.first().map(|(start, _)| format_time(*start)),
+            last: coverage.last().map(|(_, end)| format_time(*end)),
+            coverage_gaps: coverage.len().saturating_sub(1),
+            holes,
+            gap_markers: series.gap_markers().len(),
+            synced_at: series.synced_at().map(format_time),
+        }
+    }
+}
+
+/// JSON output schema of `data <subcommand>`, for `standx schema`.
+pub(super) fn output_schema(subcommand: &str, definitions: &mut Definitions) -> Option<Value> {
+    match subcommand {
+        "sync" => Some(array_of(
+            definitions,
+            &SyncRow {
+                symbol: String::new(),
+                series: String::new(),
+                requests: 0,
+                fetched: 0,
+                records: 0,
+                repaired: 0,
+                gaps: 0,
+            },
+        )),
+        "status" => Some(array_of(
+            definitions,
+            &StatusRow {
+                symbol: String::new(),
+                series: String::new(),
+                records: 0,
+                first: Some(String::new()),
+                last: Some(String::new()),
+                coverage_gaps: 0,
+                holes: 0,
+                gap_markers: 0,
+                synced_at: Some(String::new()),
+            },
+        )),
+        _ => None,
+    }
+}
+
+fn format_time(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|| secs.to_string())
+}
+
+fn print_rows<R: Serialize>(
+    rows: &[R],
+    output_format: OutputFormat,
+    table: fn(&[R]) -> String,
+) -> Result<()> {
+    match output_format {
+        OutputFormat::Table => print!("{}", table(rows)),
+        OutputFormat::Json => println!("{}", output::format_json(&rows)?),
+        OutputFormat::Csv => print!("{}", output::format_csv(rows)?),
+        OutputFormat::Quiet => {}
+    }
+    Ok(())
+}
+
+fn format_sync_table(rows: &[SyncRow]) -> String {
+    let header = format!(
+        "{:<12} {:<12} {:>9} {:>9} {:>9} {:>9} {:>6}",
+        "SYMBOL", "SERIES", "REQUESTS", "FETCHED", "CACHED", "REPAIRED", "GAPS"
+    );
+    let separator = "-".repeat(header.len());
+    let mut text = format!("{header}\n{separator}\n");
+    for row in rows {
+        text.push_str(&format!(
+            "{:<12} {:<12} {:>9} {:>9} {:>9} {:>9} {:>6}\n",
+            row.symbol, row.series, row.requests, row.fetched, row.records, row.repaired, row.gaps
+        ));
+    }
+    text
+}
+
+fn format_status_table(rows: &[StatusRow]) -> String {
+    let header = format!(
+        "{:<12} {:<12} {:>9} {:<16} {:<16} {:>5} {:>5} {:<16}",
+        "SYMBOL", "SERIES", "RECORDS", "FROM", "TO", "GAPS", "HOLES", "SYNCED"
+    );
+    let separator = "-".repeat(header.len());
+    let mut text = format!("{header}\n{separator}\n");
+    for row in rows {
+        text.push_str(&format!(
+            "{:<12} {:<12} {:>9} {:<16} {:<16} {:>5} {:>5} {:<16}\n",
+            row.symbol,
+            row.series,
+            row.records,
+            row.first.as_deref().unwrap_or("-"),
+            row.last.as_deref().unwrap_or("-"),
+            row.coverage_gaps + row.gap_markers,
+            row.holes,
+            row.synced_at.as_deref().unwrap_or("-")
+        ));
+    }
+    text
+}
+
+/// Missing bars inside coverage that have not been marked unrecoverable.
+fn open_holes(series: &CachedSeries<Kline>, step: i64) -> Vec<(i64, i64)> {
+    series
+        .holes(step)
+        .into_iter()
+        .filter(|hole| !series.gap_markers().contains(hole))
+        .collect()
+}
+
+/// Load a series, treating an unreadable file as empty (it is refetched and
+/// overwritten on the next save).
+fn load_or_empty<T: CachedRecord>(cache: &MarketCache, key: &SeriesKey) -> CachedSeries<T> {
+    cache.load(key).unwrap_or_else(|error| {
+        eprintln!("Warning: ignoring unreadable market cache: {error:#}");
+        CachedSeries::default()
+    })
+}
+
+fn save_or_warn<T: CachedRecord>(
+    cache: &MarketCache,
+    key: &SeriesKey,
+    series: &mut CachedSeries<T>,
+) {
+    if let Err(error) = cache.save(key, series) {
+        eprintln!("Warning: failed to update market cache: {error:#}");
+    }
+}
+
+/// Last time whose kline bar is closed: a bar opening at `t` is final once
+/// `t + step <= now`.
+fn kline_final_until(now: i64, step: i64) -> i64 {
+    now - step
+}
+
+/// Fetch `[from, to]` klines in chunks, merging into the series; the part up
+/// to `final_until` is marked covered. Returns (requests, records fetched).
+async fn fetch_klines_into(
+    client: &StandXClient,
+    series: &mut CachedSeries<Kline>,
+    symbol: &str,
+    resolution: &str,
+    step: i64,
+    (from, to): (i64, i64),
+    final_until: i64,
+) -> Result<(usize, usize)> {
+    let mut requests = 0;
+    let mut fetched = 0;
+    let mut start = from;
+    while start <= to {
+        let end = (start + KLINE_CHUNK_BARS * step - 1).min(to);
+        let klines = client.get_kline(symbol, resolution, start, end).await?;
+        requests += 1;
+        fetched += klines.len();
+        series.merge(klines, start, end.min(final_until));
+        start = end + 1;
+    }
+    Ok((requests, fetched))
+}
+
+/// Klines for `[from, to]`, served from the cache where it is covered.
+///
+/// With `cache == None` (`--no-cache`) this is a plain API call. Calendar
+/// resolutions (`1M`) are never cached. A read that fails against the API
+/// falls back to whatever the cache holds for the range.
+pub(super) async fn cached_klines(
+    client: &StandXClient,
+    cache: Option<&MarketCache>,
+    symbol: &str,
+    resolution: &str,
+    from: i64,
+    to: i64,
+) -> Result<Vec<Kline>> {
+    let (Some(cache), Some(step)) = (cache, resolution_secs(resolution)) else {
+        return Ok(client.get_kline(symbol, resolution, from, to).await?);
+    };
+    let key = SeriesKey::kline(symbol, resolution);
+    let mut series: CachedSeries<Kline> = load_or_empty(cache, &key);
+    let now = chrono::Utc::now().timestamp();
+    let missing = fetch_plan(&series, from, to, now, step);
+    if missing.is_empty() {
+        return Ok(series.range(from, to));
+    }
+    for range in missing {
+        let fetched = fetch_klines_into(
+            client,
+            &mut series,
+            symbol,
+            resolution,
+            step,
+            range,
+            kline_final_until(now, step),
+        )
+        .await;
+        if let Err(error) = fetched {
+            if series.range(from, to).is_empty() {
+                return Err(error);
+            }
+            eprintln!("Warning: serving cached {symbol} klines; API request failed: {error:#}");
+            return Ok(series.range(from, to));
+        }
+    }
+    save_or_warn(cache, &key, &mut series);
+    Ok(series.range(from, to))
+}
+
+/// Uncovered ranges of `[from, to]` that still need a request. The tail past
+/// the covered data is skipped only when the series was written within
+/// [`TAIL_TTL_SECS`] and its coverage reaches `min(to, now)` but for the
+/// `open_tail_secs` a fetch leaves uncovered (one kline bar, the funding
+/// settle window); any longer tail is always fetched.
+fn fetch_plan<T: CachedRecord>(
+    series: &CachedSeries<T>,
+    from: i64,
+    to: i64,
+    now: i64,
+    open_tail_secs: i64,
+) -> Vec<(i64, i64)> {
+    let missing = series.uncovered(from, to);
+    let fresh = series
+        .synced_at()
+        .is_some_and(|synced_at| now - synced_at <= TAIL_TTL_SECS);
+    let covered_until = series.coverage().last().map(|(_, end)| *end);
+    match (missing.as_slice(), covered_until) {
+        ([(start, _)], Some(end))
+            if fresh
+                && *start == end + 1
+                && to.min(now) - end <= open_tail_secs + TAIL_TTL_SECS =>
+        {
+            Vec::new()
+        }
+        _ => missing,
+    }
+}
+
+/// Funding rates for `[from, to]`, served from the cache where it is covered.
+pub(super) async fn cached_funding(
+    client: &StandXClient,
+    cache: Option<&MarketCache>,
+    symbol: &str,
+    from: i64,
+    to: i64,
+) -> Result<Vec<FundingRate>> {
+    let Some(cache) = cache else {
+        return Ok(client.get_funding_rate(symbol, from, to).await?);
+    };
+    let key = SeriesKey::funding(symbol);
+    let mut series: CachedSeries<FundingRate> = load_or_empty(cache, &key);
+    let now = chrono::Utc::now().timestamp();
+    let missing = fetch_plan(&series, from, to, now, FUNDING_SETTLE_SECS);
+    if missing.is_empty() {
+        return Ok(series.range(from, to));
+    }
+    for range in missing {
+        if let Err(error) = fetch_funding_into(client, &mut series, symbol, range, now).await {
+            if series.range(from, to).is_empty() {
+                return Err(error);
+            }
+            eprintln!("Warning: serving cached {symbol} funding; API request failed: {error:#}");
+            return Ok(series.range(from, to));
+        }
+    }
+    save_or_warn(cache, &key, &mut series);
+    Ok(series.range(from, to))
+}
+
+async fn fetch_funding_into(
+    client: &StandXClient,
+    series: &mut CachedSeries<FundingRate>,
+    symbol: &str,
+    (from, to): (i64, i64),
+    now: i64,
+) -> Result<(usize, usize)> {
+    let mut requests = 0;
+    let mut fetched = 0;
+    let mut start = from;
+    while start <= to {
+        let end = (start + FUNDING_CHUNK_SECS - 1).min(to);
+        let rates = client.get_funding_rate(symbol, start, end).await?;
+        requests += 1;
+        fetched += rates.len();
+        series.merge(rates, start, end.min(now - FUNDING_SETTLE_SECS));
+        start = end + 1;
+    }
+    Ok((requests, fetched))
+}
+
+/// Recent public trades, newest first. The public tape has no history
+/// endpoint, so every snapshot is recorded into the cache and the cache is
+/// only served when the API is unreachable.
+pub(super) async fn cached_recent_trades(
+    client: &StandXClient,
+    cache: Option<&MarketCache>,
+    symbol: &str,
+    limit: Option<u32>,
+) -> Result<Vec<Trade>> {
+    let Some(cache) = cache else {
+        return Ok(client.get_recent_trades(symbol, limit).await?);
+    };
+    let key = SeriesKey::trades(symbol);
+    let mut series: CachedSeries<Trade> = load_or_empty(cache, &key);
+    match client.get_recent_trades(symbol, limit).await {
+        Ok(trades) => {
+            record_trades(&mut series, trades.clone());
+            save_or_warn(cache, &key, &mut series);
+            Ok(trades)
+        }
+        Err(error) if !series.is_empty() => {
+            eprintln!("Warning: serving cached {symbol} trades; API request failed: {error}");
+            Ok(series.latest(limit.map_or(usize::MAX, |limit| limit as usize)))
+        }
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Merge a tape snapshot: the span it covers is complete. When the snapshot
+/// starts after the newest cached trade, the trades in between are lost for
+/// good and the span is marked as a gap.
+fn record_trades(series: &mut CachedSeries<Trade>, trades: Vec<Trade>) {
+    let times: Vec<i64> = trades
+        .iter()
+        .filter_map(|trade| trade.cache_key().map(|(time, _)| time))
+        .collect();
+    let (Some(&oldest), Some(&newest)) = (times.iter().min(), times.iter().max()) else {
+        return;
+    };
+    if let Some(last) = series.last_time() {
+        if oldest > last + 1 {
+            series.mark_gap(last + 1, oldest - 1);
+        }
+    }
+    series.merge(trades, oldest, newest);
+}
+
+async fn sync_klines(
+    client: &StandXClient,
+    cache: &MarketCache,
+    symbol: &str,
+    resolution: &str,
+    from: i64,
+    now: i64,
+    repair: bool,
+) -> Result<SyncRow> {
+    let step = resolution_secs(resolution).expect("resolution validated");
+    let key = SeriesKey::kline(symbol, resolution);
+    let mut series: CachedSeries<Kline> = load_or_empty(cache, &key);
+    let final_until = kline_final_until(now, step);
+    let mut requests = 0;
+    let mut fetched = 0;
+    for range in series.uncovered(from, now) {
+        let (calls, records) = fetch_klines_into(
+            client,
+            &mut series,
+            symbol,
+            resolution,
+            step,
+            range,
+            final_until,
+        )
+        .await?;
+        requests += calls;
+        fetched += records;
+    }
+
+    let mut repaired = 0;
+    if repair {
+        for hole in open_holes(&series, step) {
+            let before = series.len();
+            let (calls, records) = fetch_klines_into(
+                client,
+                &mut series,
+                symbol,
+                resolution,
+                step,
+                hole,
+                final_until,
+            )
+            .await?;
+            requests += calls;
+            fetched += records;
+            repaired += series.len() - before;
+            if series.len() == before {
+                // The venue has no bars there either (e.g. no trading).
+                series.mark_gap(hole.0, hole.1);
+            }
+        }
+    }
+    let gaps = open_holes(&series, step).len() + series.coverage().len().saturating_sub(1);
+    cache.save(&key, &mut series)?;
+    Ok(SyncRow {
+        symbol: key.symbol.clone(),
+        series: series_label(&key),
+        requests,
+        fetched,
+        records: series.len(),
+        repaired,
+        gaps,
+    })
+}
+
+async fn sync_funding(
+    client: &StandXClient,
+    cache: &MarketCache,
+    symbol: &str,
+    from: i64,
+    now: i64,
+) -> Result<SyncRow> {
+    let key = SeriesKey::funding(symbol);
+    let mut series: CachedSeries<FundingRate> = load_or_empty(cache, &key);
+    let mut requests = 0;
+    let mut fetched = 0;
+    for range in series.uncovered(from, now) {
+        let (calls, records) = fetch_funding_into(client, &mut series, symbol, range, now).await?;
+        requests += calls;
+        fetched += records;
+    }
+    let gaps = series.coverage().len().saturating_sub(1);
+    cache.save(&key, &mut series)?;
+    Ok(SyncRow {
+        symbol: key.symbol.clone(),
+        series: series_label(&key),
+        requests,
+        fetched,
+        records: series.len(),
+        repaired: 0,
+        gaps,
+    })
+}
+
+async fn sync_trades(client: &StandXClient, cache: &MarketCache, symbol: &str) -> Result<SyncRow> {
+    let key = SeriesKey::trades(symbol);
+    let mut series: CachedSeries<Trade> = load_or_empty(cache, &key);
+    let trades = client
+        .get_recent_trades(symbol, Some(TRADE_SNAPSHOT_LIMIT))
+        .await?;
+    let fetched = trades.len();
+    record_trades(&mut series, trades);
+    cache.save(&key, &mut series)?;
+    Ok(SyncRow {
+        symbol: key.symbol.clone(),
+        series: series_label(&key),
+        requests: 1,
+        fetched,
+        records: series.len(),
+        repaired: 0,
+        gaps: series.gap_markers().len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(id: u64, time: i64) -> Trade {
+        Trade {
+            id,
+            time: time.to_string(),
+            price: "100".to_string(),
+            qty: "1".to_string(),
+            side: Some("buy".to_string()),
+            is_buyer_taker: true,
+            fee_asset: None,
+            fee_qty: None,
+            pnl: None,
+            order_id: None,
+            symbol: Some("BTC-USD".to_string()),
+            value: None,
+        }
+    }
+
+    #[test]
+    fn trade_snapshot_after_the_cached_tail_marks_a_gap() {
+        let mut series = CachedSeries::<Trade>::default();
+        record_trades(&mut series, vec![trade(2, 110), trade(1, 100)]);
+        record_trades(&mut series, vec![trade(3, 120), trade(2, 110)]);
+        assert!(series.gap_markers().is_empty());
+        assert_eq!(series.coverage(), &[(100, 120)]);
+
+        record_trades(&mut series, vec![trade(9, 200)]);
+
+        assert_eq!(series.gap_markers(), &[(121, 199)]);
+        assert_eq!(series.latest(2)[0].id, 9);
+    }
+
+    #[test]
+    fn fresh_series_serves_its_open_tail_from_cache() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(Vec::new(), 0, 999);
+        assert_eq!(
+            fetch_plan(&series, 500, 1_100, 1_100, 60),
+            vec![(1_000, 1_100)]
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MarketCache::new(dir.path());
+        let key = SeriesKey::kline("BTC-USD", "1");
+        let now = chrono::Utc::now().timestamp();
+        series.merge(Vec::new(), 1_000, kline_final_until(now, 60));
+        cache.save(&key, &mut series).unwrap();
+        let now = series.synced_at().unwrap();
+
+        assert!(fetch_plan(&series, 500, now, now, 60).is_empty());
+        // An uncovered range before the cached data is always fetched.
+        let missing = fetch_plan(&series, -100, now, now, 60);
+        assert_eq!(missing[0], (-100, -1));
+    }
+
+    #[test]
+    fn fresh_series_still_fetches_a_tail_short_of_the_request() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(Vec::new(), 0, 999);
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MarketCache::new(dir.path());
+        cache
+            .save(&SeriesKey::kline("BTC-USD", "1"), &mut series)
+            .unwrap();
+        let now = series.synced_at().unwrap();
+
+        // Fresh, but coverage stops long before both `to` and now.
+        assert_eq!(
+            fetch_plan(&series, 500, 5_000, now, 60),
+            vec![(1_000, 5_000)]
+        );
+        assert_eq!(fetch_plan(&series, 500, now, now, 60), vec![(1_000, now)]);
+        // Within one bar (plus the TTL) of `to`, the open tail is served.
+        assert!(fetch_plan(&series, 500, 1_100, now, 60).is_empty());
+    }
+}
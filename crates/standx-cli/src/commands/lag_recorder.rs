@@ -21,6 +21,7 @@ use std::fs::OpenOptions;
 use std::io::{BufWriter, Write};
 use std::time::{Duration, Instant};
 
+use super::util::shutdown_signal;
 use standx_sdk::websocket::{StandXWebSocket, WsMessage};
 use tokio::sync::mpsc;
 use tokio_tungstenite::tungstenite::Message;
@@ -230,31 +231,6 @@ fn fmt_mark(mark: Option<f64>) -> String {
     mark.map(|m| format!("{m}")).unwrap_or_else(|| "-".into())
 }
 
-/// Complete on SIGINT or (unix) SIGTERM so supervisors and Ctrl+C both stop the
-/// recorder gracefully.
-async fn shutdown_signal() {
-    #[cfg(unix)]
-    {
-        use tokio::signal::unix::{signal, SignalKind};
-        let mut sigint = match signal(SignalKind::interrupt()) {
-            Ok(stream) => stream,
-            Err(_) => return std::future::pending().await,
-        };
-        let mut sigterm = match signal(SignalKind::terminate()) {
-            Ok(stream) => stream,
-            Err(_) => return std::future::pending().await,
-        };
-        tokio::select! {
-            _ = sigint.recv() => {}
-            _ = sigterm.recv() => {}
-        }
-    }
-    #[cfg(not(unix))]
-    {
-        let _ = tokio::signal::ctrl_c().await;
-    }
-}
-
 /// StandX producer: subscribe to the public `price` and `depth_book` channels
 /// (no auth) and forward each update as a `LagRecord`. Rebuilds the connection
 /// when the managed stream ends.
@@ -1,6 +1,8 @@
+use crate::audit;
 use crate::cli::*;
 use crate::output;
 use anyhow::Result;
+use standx_sdk::client::account::change_leverage_body;
 use standx_sdk::client::StandXClient;
 
 /// Handle leverage commands
@@ -48,7 +50,7 @@ pub async fn handle_leverage(command: LeverageCommands, output_format: OutputFor
                 .parse()
                 .map_err(|_| anyhow::anyhow!("Invalid leverage value: {}", leverage))?;
 
-            match client.change_leverage(&symbol, leverage_val).await {
+            match change_leverage(&client, &symbol, leverage_val).await {
                 Ok(_) => println!("✅ Leverage for {} set to {}x", symbol, leverage),
                 Err(e) => {
                     println!("⚠️  Leverage change failed");
@@ -61,3 +63,18 @@ pub async fn handle_leverage(command: LeverageCommands, output_format: OutputFor
     }
     Ok(())
 }
+
+/// Change leverage, recording it in the audit log.
+pub(super) async fn change_leverage(
+    client: &StandXClient,
+    symbol: &str,
+    leverage: u32,
+) -> standx_sdk::Result<()> {
+    audit::audited(
+        "leverage_change",
+        symbol,
+        change_leverage_body(symbol, leverage),
+        client.change_leverage(symbol, leverage),
+    )
+    .await
+}
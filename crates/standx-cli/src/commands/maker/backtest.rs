@@ -0,0 +1,589 @@
+//! CLI adapter for `maker backtest`: turns `standx record` files into
+//! [`BacktestEvent`]s and resolves the maker TOML into the simulated config.
+
+use super::config::{self, MakerFileConfig};
+use super::output::performance_json;
+use crate::cli::{BacktestArgs, OutputFormat};
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::read::MultiGzDecoder;
+use serde::Deserialize;
+use standx_maker::{
+    self as maker, BacktestEvent, BacktestResult, BacktestSettings, BookLevel, MakerConfig,
+};
+use standx_sdk::models::{OrderBook, PriceData, Trade};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Envelope line of a `standx record` file, as far as the backtest reads it.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordLine {
+    Header {
+        format_version: u32,
+        #[serde(default)]
+        symbols: Vec<String>,
+    },
+    Data {
+        channel: String,
+        symbol: Option<String>,
+        received_at: String,
+        data: serde_json::Value,
+    },
+    Gap {
+        from: String,
+    },
+}
+
+/// One recorded update with its receipt time, before symbol selection.
+#[derive(Debug)]
+struct RecordedEvent {
+    symbol: Option<String>,
+    event: BacktestEvent,
+}
+
+/// Market data of one symbol, in time order.
+#[derive(Debug)]
+pub(super) struct Recording {
+    pub(super) symbol: String,
+    pub(super) events: Vec<BacktestEvent>,
+    pub(super) price_decimals: u32,
+    pub(super) qty_decimals: u32,
+}
+
+pub(super) fn run(
+    maker_config: Option<&Path>,
+    data: &[PathBuf],
+    simulation: &BacktestArgs,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let file = config::load(maker_config)?;
+    let recording = load_recording(data, simulation.symbol.as_deref())?;
+    warn_unmodeled(&file);
+    let (cfg, settings) = resolve(&file, simulation, &recording)?;
+    let result = maker::run_backtest(&cfg, &settings, &recording.events)?;
+    emit(&recording.symbol, &result, output_format);
+    Ok(())
+}
+
+/// Strategy and execution assumptions of one backtest, resolved with the same
+/// defaults and checks as `maker run`.
+pub(super) fn resolve(
+    file: &MakerFileConfig,
+    simulation: &BacktestArgs,
+    recording: &Recording,
+) -> Result<(MakerConfig, BacktestSettings)> {
+    if file.vol_window.is_some() && file.vol_window_secs.is_some() {
+        anyhow::bail!(
+            "vol_window conflicts with vol_window_secs in TOML; choose samples or seconds"
+        );
+    }
+    if file.vol_window_secs == Some(0) {
+        anyhow::bail!("vol_window_secs in TOML must be greater than 0");
+    }
+    let adaptive_spread = file
+        .adaptive_spread
+        .clone()
+        .map(|config| config.into_domain(None))
+        .unwrap_or_default();
+    if adaptive_spread.enabled && file.vol_window_secs.is_none() {
+        anyhow::bail!("adaptive spread requires vol_window_secs in TOML");
+    }
+    let size_skew = file
+        .size_skew
+        .clone()
+        .map(|config| config.into_domain(None))
+        .unwrap_or_default();
+    let price_decimals = simulation
+        .price_decimals
+        .unwrap_or(recording.price_decimals);
+    let qty_decimals = simulation.qty_decimals.unwrap_or(recording.qty_decimals);
+    let cfg = MakerConfig {
+        spread_bps: file.spread_bps.unwrap_or(5.0),
+        band_bps: file.band_bps.unwrap_or(20.0),
+        level_step_bps: file.level_step_bps.unwrap_or(2.0),
+        refresh_bps: file.refresh_bps.unwrap_or(3.0),
+        levels: file.levels.unwrap_or(1).max(1),
+        size: file.size.unwrap_or(0.01),
+        max_position: file.max_position.unwrap_or(0.05),
+        skew_bps: file.skew_bps.unwrap_or(0.0),
+        price_decimals,
+        qty_decimals,
+        min_order_qty: simulation
+            .min_order_qty
+            .unwrap_or_else(|| 10f64.powi(-(qty_decimals as i32))),
+    };
+    let interval = file.interval.unwrap_or(5);
+    let inventory_exit_pct = file.inventory_exit_pct.unwrap_or(0.0);
+    let inventory_exit_qty = file.inventory_exit_qty.unwrap_or(0.0);
+
+    if cfg.spread_bps <= 0.0 {
+        anyhow::bail!("spread_bps must be > 0");
+    }
+    if interval == 0 {
+        anyhow::bail!("interval must be at least 1 second");
+    }
+    if cfg.skew_bps < 0.0 {
+        anyhow::bail!("skew_bps must be >= 0");
+    }
+    if !(0.0..=100.0).contains(&inventory_exit_pct) || inventory_exit_qty < 0.0 {
+        anyhow::bail!("inventory_exit_pct must be 0..=100 and inventory_exit_qty must be >= 0");
+    }
+    if (inventory_exit_pct > 0.0) != (inventory_exit_qty > 0.0) {
+        anyhow::bail!(
+            "active inventory exit requires both inventory_exit_pct and inventory_exit_qty"
+        );
+    }
+    if cfg.band_bps <= cfg.spread_bps {
+        anyhow::bail!(
+            "band_bps ({}) must be greater than spread_bps ({})",
+            cfg.band_bps,
+            cfg.spread_bps
+        );
+    }
+    let rounded_size = maker::round_to_decimals(cfg.size, cfg.qty_decimals);
+    if rounded_size < cfg.min_order_qty || rounded_size <= 0.0 {
+        anyhow::bail!(
+            "size {} (rounded to {} at {} decimals) is below min order qty {} for {}",
+            cfg.size,
+            rounded_size,
+            cfg.qty_decimals,
+            cfg.min_order_qty,
+            recording.symbol
+        );
+    }
+
+    let settings = BacktestSettings {
+        starting_position: simulation.starting_position,
+        cycle_interval_ms: interval as i64 * 1_000,
+        order_latency_ms: simulation.order_latency_ms as i64,
+        cancel_latency_ms: simulation.cancel_latency_ms as i64,
+        maker_fee_bps: simulation.maker_fee_bps,
+        taker_fee_bps: simulation.taker_fee_bps,
+        max_divergence_bps: file.max_divergence_bps.unwrap_or(25.0),
+        vol_window: file.vol_window.unwrap_or(12).max(1) as usize,
+        vol_window_secs: file.vol_window_secs,
+        vol_pause_bps: file.vol_pause_bps.unwrap_or(0.0),
+        adaptive_spread,
+        size_skew,
+        inventory_exit_pct,
+        inventory_exit_qty,
+        strategy: file
+            .strategy
+            .clone()
+            .map(|config| config.into_domain())
+            .unwrap_or_default(),
+        fair_value: file
+            .fair_value
+            .clone()
+            .map(|config| config.into_domain())
+            .unwrap_or_default(),
+    };
+    Ok((cfg, settings))
+}
+
+/// The backtest does not model these TOML-only controllers.
+pub(super) fn warn_unmodeled(file: &MakerFileConfig) {
+    let enabled = |enabled: Option<bool>| enabled.unwrap_or(false);
+    if file
+        .nonlinear_skew
+        .as_ref()
+        .is_some_and(|config| enabled(config.enabled))
+        || file
+            .external_guard
+            .as_ref()
+            .is_some_and(|config| enabled(config.enabled))
+    {
+        eprintln!(
+            "⚠️ maker backtest does not model nonlinear skew or the external guard; both are ignored"
+        );
+    }
+}
+
+/// Read every file and keep the selected symbol, merged in receipt order.
+pub(super) fn load_recording(paths: &[PathBuf], symbol: Option<&str>) -> Result<Recording> {
+    let mut symbols = BTreeSet::new();
+    let mut recorded = Vec::new();
+    for path in paths {
+        let reader = open(path)?;
+        let (file_symbols, events) =
+            parse(reader).with_context(|| format!("invalid record file {}", path.display()))?;
+        symbols.extend(file_symbols);
+        recorded.extend(events);
+    }
+    let symbol = match symbol {
+        Some(symbol) => symbols
+            .iter()
+            .find(|recorded| recorded.eq_ignore_ascii_case(symbol))
+            .cloned()
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "symbol {symbol} is not in the recording (recorded: {})",
+                    symbols.iter().cloned().collect::<Vec<_>>().join(", ")
+                )
+            })?,
+        None if symbols.len() == 1 => symbols.into_iter().next().unwrap_or_default(),
+        None => anyhow::bail!(
+            "the recording holds {} symbols; choose one with --symbol",
+            symbols.len()
+        ),
+    };
+    // Depth updates may arrive without a symbol. A single-symbol file has
+    // already attributed them; in a multi-symbol file they are ambiguous and
+    // dropped. Gaps apply to every symbol.
+    let mut events: Vec<BacktestEvent> = recorded
+        .into_iter()
+        .filter(|recorded| {
+            matches!(recorded.event, BacktestEvent::Gap { .. })
+                || recorded.symbol.as_deref() == Some(symbol.as_str())
+        })
+        .map(|recorded| recorded.event)
+        .collect();
+    events.sort_by_key(BacktestEvent::event_time_ms);
+    if !events
+        .iter()
+        .any(|event| matches!(event, BacktestEvent::Depth { .. }))
+    {
+        anyhow::bail!(
+            "the recording has no depth updates for {symbol}; record with --channels depth,trade"
+        );
+    }
+    let (price_decimals, qty_decimals) = inferred_decimals(&events);
+    Ok(Recording {
+        symbol,
+        events,
+        price_decimals,
+        qty_decimals,
+    })
+}
+
+fn open(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+    let mut reader = BufReader::new(file);
+    let gzip = reader
+        .fill_buf()
+        .with_context(|| format!("failed to read {}", path.display()))?
+        .starts_with(&[0x1f, 0x8b]);
+    Ok(if gzip {
+        Box::new(BufReader::new(MultiGzDecoder::new(reader)))
+    } else {
+        Box::new(reader)
+    })
+}
+
+/// Symbols named by the file and its events in file order. Gaps carry no
+/// symbol because a reconnect drops every subscription at once.
+fn parse(reader: impl Read) -> Result<(Vec<String>, Vec<RecordedEvent>)> {
+    let mut header_symbols = None;
+    let mut events = Vec::new();
+    for (index, line) in BufReader::new(reader).lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.with_context(|| format!("failed to read line {line_number}"))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: RecordLine = serde_json::from_str(&line)
+            .with_context(|| format!("invalid record at line {line_number}"))?;
+        let symbols: &Vec<String> = match (&record, &header_symbols) {
+            (
+                RecordLine::Header {
+                    format_version,
+                    symbols,
+                },
+                None,
+            ) => {
+                if *format_version != super::super::record::RECORD_FORMAT_VERSION {
+                    anyhow::bail!("unsupported record format_version {format_version}");
+                }
+                header_symbols = Some(symbols.clone());
+                continue;
+            }
+            (RecordLine::Header { .. }, Some(_)) => {
+                anyhow::bail!("duplicate header at line {line_number}")
+            }
+            (_, None) => anyhow::bail!("record file does not start with a header"),
+            (_, Some(symbols)) => symbols,
+        };
+        let event = match record {
+            RecordLine::Header { .. } => unreachable!("headers are handled above"),
+            RecordLine::Gap { from } => RecordedEvent {
+                symbol: None,
+                event: BacktestEvent::Gap {
+                    event_time_ms: parse_time(&from, line_number)?,
+                },
+            },
+            RecordLine::Data {
+                channel,
+                symbol,
+                received_at,
+                data,
+            } => {
+                let symbol = symbol.or_else(|| match symbols.as_slice() {
+                    [only] => Some(only.clone()),
+                    _ => None,
+                });
+                let event_time_ms = parse_time(&received_at, line_number)?;
+                let event = match channel.as_str() {
+                    "price" => {
+                        let price: PriceData = payload(data, line_number)?;
+                        BacktestEvent::Mark {
+                            event_time_ms,
+                            mark: number(&price.mark_price, line_number)?,
+                        }
+                    }
+                    "depth" => {
+                        let book: OrderBook = payload(data, line_number)?;
+                        BacktestEvent::Depth {
+                            event_time_ms,
+                            bids: levels(&book.bids, line_number)?,
+                            asks: levels(&book.asks, line_number)?,
+                        }
+                    }
+                    "trade" => {
+                        let trade: Trade = payload(data, line_number)?;
+                        BacktestEvent::Trade {
+                            event_time_ms,
+                            price: number(&trade.price, line_number)?,
+                            qty: number(&trade.qty, line_number)?,
+                        }
+                    }
+                    _ => continue,
+                };
+                RecordedEvent { symbol, event }
+            }
+        };
+        events.push(event);
+    }
+    let symbols = header_symbols.ok_or_else(|| anyhow::anyhow!("record file is empty"))?;
+    Ok((symbols, events))
+}
+
+fn payload<T: serde::de::DeserializeOwned>(data: serde_json::Value, line: usize) -> Result<T> {
+    serde_json::from_value(data).with_context(|| format!("invalid payload at line {line}"))
+}
+
+fn parse_time(value: &str, line: usize) -> Result<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.timestamp_millis())
+        .with_context(|| format!("invalid timestamp '{value}' at line {line}"))
+}
+
+fn number(value: &str, line: usize) -> Result<f64> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite())
+        .ok_or_else(|| anyhow::anyhow!("invalid number '{value}' at line {line}"))
+}
+
+fn levels(levels: &[[String; 2]], line: usize) -> Result<Vec<BookLevel>> {
+    levels
+        .iter()
+        .map(|[price, qty]| {
+            Ok(BookLevel {
+                price: number(price, line)?,
+                qty: number(qty, line)?,
+            })
+        })
+        .collect()
+}
+
+/// Price and quantity decimals are not part of the recording; the venue
+/// prints both at tick precision, so the longest fraction seen is the tick.
+fn inferred_decimals(events: &[BacktestEvent]) -> (u32, u32) {
+    fn decimals(value: f64) -> u32 {
+        (0..=12)
+            .find(|digits| {
+                let scaled = value * 10f64.powi(*digits);
+                (scaled - scaled.round()).abs() < 1e-6
+            })
+            .unwrap_or(12) as u32
+    }
+    let mut price = 0;
+    let mut qty = 0;
+    for event in events {
+        match event {
+            BacktestEvent::Depth { bids, asks, .. } => {
+                for level in bids.iter().chain(asks) {
+                    price = price.max(decimals(level.price));
+                    qty = qty.max(decimals(level.qty));
+                }
+            }
+            BacktestEvent::Trade {
+                price: trade_price,
+                qty: trade_qty,
+                ..
+            } => {
+                price = price.max(decimals(*trade_price));
+                qty = qty.max(decimals(*trade_qty));
+            }
+            BacktestEvent::Mark { .. } | BacktestEvent::Gap { .. } => {}
+        }
+    }
+    (price, qty)
+}
+
+fn format_ms(ms: i64) -> String {
+    DateTime::<Utc>::from_timestamp_millis(ms)
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .unwrap_or_else(|| ms.to_string())
+}
+
+fn emit(symbol: &str, result: &BacktestResult, output_format: OutputFormat) {
+    let stats = &result.stats;
+    let performance = &result.performance;
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            crate::output::format_event(&serde_json::json!({
+                "action": "backtest_summary",
+                "symbol": symbol,
+                "start_time": format_ms(result.start_time_ms),
+                "end_time": format_ms(result.end_time_ms),
+                "final_mark": result.final_mark,
+                "cycles": stats.cycles,
+                "skipped_cycles": stats.skipped_cycles,
+                "halted_cycles": stats.halted_cycles,
+                "places": stats.places,
+                "cancels": stats.cancels,
+                "post_only_rejects": stats.post_only_rejects,
+                "passive_fills": stats.passive_fills,
+                "partial_fills": stats.partial_fills,
+                "exits": stats.exits,
+                "failed_exits": stats.failed_exits,
+                "gaps": stats.gaps,
+                "performance": performance_json(performance),
+            }))
+        );
+    } else if output_format != OutputFormat::Quiet {
+        println!(
+            "Backtest {} {} → {} cycles={} places={} cancels={} post_only_rejects={} gaps={}",
+            symbol,
+            format_ms(result.start_time_ms),
+            format_ms(result.end_time_ms),
+            stats.cycles,
+            stats.places,
+            stats.cancels,
+            stats.post_only_rejects,
+            stats.gaps,
+        );
+        println!(
+            "  passive_fills={} exit_fills={} position={} net_pnl={:.6} markout_5s={} uptime={:.2}%",
+            performance.passive_fills,
+            performance.exit_fills,
+            performance.position,
+            performance.net_pnl_quote,
+            performance.markouts[1]
+                .avg_bps
+                .map_or_else(|| "n/a".to_string(), |bps| format!("{bps:.2}bps")),
+            performance.quote_time.two_sided_uptime_pct,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECORDING: &str = r#"{"type":"header","format_version":1,"file_index":0,"started_at":"2026-01-01T00:00:00.000Z","symbols":["BTC-USD"],"channels":["price","depth","trade"],"kline_interval":null}
+{"type":"data","n":1,"channel":"price","symbol":"BTC-USD","seq":null,"server_time":null,"received_at":"2026-01-01T00:00:00.000Z","recv_mono_ms":0,"connection":1,"data":{"symbol":"BTC-USD","mark_price":"100.00","index_price":"100.00","last_price":"100.00","timestamp":"0"}}
+{"type":"data","n":2,"channel":"depth","symbol":null,"seq":1,"server_time":null,"received_at":"2026-01-01T00:00:00.000Z","recv_mono_ms":0,"connection":1,"data":{"symbol":"","bids":[["99.99","5.000"]],"asks":[["100.01","5.000"]],"timestamp":""}}
+{"type":"data","n":3,"channel":"trade","symbol":"BTC-USD","seq":null,"server_time":null,"received_at":"2026-01-01T00:00:01.000Z","recv_mono_ms":1000,"connection":1,"data":{"id":7,"time":"","price":"99.90","qty":"20.000","is_taker":false}}
+{"type":"data","n":4,"channel":"trade","symbol":"BTC-USD","seq":null,"server_time":null,"received_at":"2026-01-01T00:00:04.000Z","recv_mono_ms":4000,"connection":1,"data":{"id":8,"time":"","price":"100.00","qty":"0.001","is_taker":true}}
+{"type":"gap","n":5,"reason":"reconnect","from":"2026-01-01T00:00:05.000Z","to":"2026-01-01T00:00:09.000Z","connection":2}
+"#;
+
+    fn simulation() -> BacktestArgs {
+        BacktestArgs {
+            symbol: None,
+            starting_position: 0.0,
+            order_latency_ms: 150,
+            cancel_latency_ms: 150,
+            maker_fee_bps: 0.0,
+            taker_fee_bps: 0.0,
+            price_decimals: None,
+            qty_decimals: None,
+            min_order_qty: None,
+        }
+    }
+
+    #[test]
+    fn record_lines_map_to_backtest_events() {
+        let (symbols, events) = parse(RECORDING.as_bytes()).unwrap();
+        assert_eq!(symbols, ["BTC-USD"]);
+        assert_eq!(events.len(), 5);
+        assert!(events
+            .iter()
+            .all(|event| matches!(event.event, BacktestEvent::Gap { .. })
+                || event.symbol.as_deref() == Some("BTC-USD")));
+        assert_eq!(
+            events[1].event,
+            BacktestEvent::Depth {
+                event_time_ms: 1_767_225_600_000,
+                bids: vec![BookLevel {
+                    price: 99.99,
+                    qty: 5.0
+                }],
+                asks: vec![BookLevel {
+                    price: 100.01,
+                    qty: 5.0
+                }],
+            }
+        );
+        assert_eq!(
+            events[4].event,
+            BacktestEvent::Gap {
+                event_time_ms: 1_767_225_605_000
+            }
+        );
+        assert_eq!(
+            inferred_decimals(&events.into_iter().map(|e| e.event).collect::<Vec<_>>()),
+            (2, 3)
+        );
+    }
+
+    #[test]
+    fn recording_without_header_is_rejected() {
+        let body = RECORDING.lines().skip(1).collect::<Vec<_>>().join("\n");
+        let error = parse(body.as_bytes()).unwrap_err();
+        assert!(error.to_string().contains("header"), "{error}");
+    }
+
+    #[test]
+    fn gzipped_recording_backtests_end_to_end() {
+        use flate2::write::GzEncoder;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("btc.ndjson.gz");
+        let mut encoder = GzEncoder::new(File::create(&path).unwrap(), Default::default());
+        encoder.write_all(RECORDING.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+
+        let recording = load_recording(&[path], None).unwrap();
+        assert_eq!(recording.symbol, "BTC-USD");
+        let file: MakerFileConfig =
+            toml::from_str("spread_bps = 5\nband_bps = 20\nsize = 0.002\ninterval = 1\n").unwrap();
+        let (cfg, settings) = resolve(&file, &simulation(), &recording).unwrap();
+        assert_eq!((cfg.price_decimals, cfg.qty_decimals), (2, 3));
+        assert_eq!(cfg.min_order_qty, 0.001);
+        assert_eq!(settings.cycle_interval_ms, 1_000);
+
+        let result = maker::run_backtest(&cfg, &settings, &recording.events).unwrap();
+        // The sell-side sweep through 99.90 fills the resting bid.
+        assert_eq!(result.performance.passive_fills, 1);
+        assert_eq!(result.performance.position, 0.002);
+        assert_eq!(result.stats.gaps, 1);
+    }
+
+    #[test]
+    fn unknown_symbol_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("btc.ndjson");
+        std::fs::write(&path, RECORDING).unwrap();
+        let error = load_recording(&[path], Some("ETH-USD")).unwrap_err();
+        assert!(error.to_string().contains("BTC-USD"), "{error}");
+    }
+}
@@ -3,6 +3,8 @@
 //! This intentionally lives in the CLI: it executes network I/O and does not
 //! alter the maker planner or its normal quote decisions.
 
+use super::super::order::cancel;
+use super::cycle::audit_order_write;
 use super::model::position_for_symbol;
 use super::notify::MakerNotifier;
 use super::{FailSafeShutdown, LIVE_MAKER_ENV};
@@ -10,7 +12,7 @@ use crate::cli::{AlertWebhookFormat, OutputFormat};
 use anyhow::Result;
 use standx_maker::{format_decimals, round_to_decimals};
 use standx_sdk::auth::Credentials;
-use standx_sdk::client::order::CreateOrderParams;
+use standx_sdk::client::order::{cancel_order_body, create_order_body, CreateOrderParams};
 use standx_sdk::client::StandXClient;
 use standx_sdk::models::{Order, OrderSide, OrderType, TimeInForce};
 use standx_sdk::order_response::{OrderCommandSender, OrderResponse, OrderResponseStream};
@@ -99,18 +101,14 @@ impl CanaryEvidence<'_> {
         response: Option<&OrderResponse>,
     ) {
         let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
-        println!(
-            "{}",
-            self.value_at(&timestamp, stage, request_id, order_id, response, None)
-        );
+        let value = self.value_at(&timestamp, stage, request_id, order_id, response, None);
+        println!("{}", crate::output::format_event(&value));
     }
 
     fn emit_position(&self, stage: CanaryStage, order_id: Option<&str>, position: f64) {
         let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
-        println!(
-            "{}",
-            self.value_at(&timestamp, stage, None, order_id, None, Some(position))
-        );
+        let value = self.value_at(&timestamp, stage, None, order_id, None, Some(position));
+        println!("{}", crate::output::format_event(&value));
     }
 }
 
@@ -214,7 +212,7 @@ async fn cleanup_current_order(
         .find(|order| order.cl_ord_id.as_deref() == Some(client_order_id))
     {
         let order_id = order.id.clone();
-        client.cancel_order(symbol, &order.id).await?;
+        cancel(client, symbol, &order.id).await?;
         wait_until_absent(client, symbol, client_order_id, timeout).await?;
         return Ok(Some(order_id));
     }
@@ -376,21 +374,30 @@ async fn run_commands(
     timeout: Duration,
     evidence: &CanaryEvidence<'_>,
 ) -> Result<()> {
-    let create_request_id = commands
-        .create_order(&CreateOrderParams {
-            symbol: symbol.to_string(),
-            cl_ord_id: Some(client_order_id.to_string()),
-            side: OrderSide::Buy,
-            order_type: OrderType::Limit,
-            quantity: format_decimals(quantity, qty_decimals),
-            price: Some(format_decimals(price, price_decimals)),
-            time_in_force: Some(TimeInForce::Alo),
-            reduce_only: false,
-            stop_price: None,
-            sl_price: None,
-            tp_price: None,
-        })
-        .await?;
+    let params = CreateOrderParams {
+        symbol: symbol.to_string(),
+        cl_ord_id: Some(client_order_id.to_string()),
+        side: OrderSide::Buy,
+        order_type: OrderType::Limit,
+        quantity: format_decimals(quantity, qty_decimals),
+        price: Some(format_decimals(price, price_decimals)),
+        time_in_force: Some(TimeInForce::Alo),
+        reduce_only: false,
+        stop_price: None,
+        sl_price: None,
+        tp_price: None,
+    };
+    let command = commands.prepare_create_order(&params)?;
+    let create_request_id = command.request_id().to_string();
+    let sent = commands.send_prepared(command).await;
+    audit_order_write(
+        "order_create",
+        symbol,
+        &create_order_body(&params),
+        &create_request_id,
+        &sent,
+    );
+    sent?;
     evidence.emit(
         CanaryStage::CreateSubmitted,
         Some(&create_request_id),
@@ -418,7 +425,16 @@ async fn run_commands(
     );
     let order = wait_for_order(client, symbol, client_order_id, timeout).await?;
     evidence.emit(CanaryStage::OrderVisible, None, Some(&order.id), None);
-    let cancel_request_id = commands.cancel_order(&order.id).await?;
+    let command = commands.prepare_cancel_order(&order.id)?;
+    let cancel_request_id = command.request_id().to_string();
+    let sent = commands.send_prepared(command).await;
+    let request = order
+        .id
+        .parse()
+        .map(cancel_order_body)
+        .unwrap_or(serde_json::Value::Null);
+    audit_order_write("order_cancel", symbol, &request, &cancel_request_id, &sent);
+    sent?;
     evidence.emit(
         CanaryStage::CancelSubmitted,
         Some(&cancel_request_id),
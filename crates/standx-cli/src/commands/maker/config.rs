@@ -90,6 +90,33 @@ impl NonlinearSkewFileConfig {
     }
 }
 
+/// Fair-value quote anchor (`[fair_value]`). Field defaults match
+/// [`standx_maker::FairValueConfig`] so partial files stay valid.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct FairValueFileConfig {
+    pub enabled: Option<bool>,
+    pub mark_weight: Option<f64>,
+    pub mid_weight: Option<f64>,
+    pub microprice_weight: Option<f64>,
+    pub depth_bps: Option<f64>,
+    pub max_deviation_bps: Option<f64>,
+}
+
+impl FairValueFileConfig {
+    pub(super) fn into_domain(self) -> standx_maker::FairValueConfig {
+        let defaults = standx_maker::FairValueConfig::default();
+        standx_maker::FairValueConfig {
+            enabled: self.enabled.unwrap_or(false),
+            mark_weight: self.mark_weight.unwrap_or(defaults.mark_weight),
+            mid_weight: self.mid_weight.unwrap_or(defaults.mid_weight),
+            microprice_weight: self.microprice_weight.unwrap_or(defaults.microprice_weight),
+            depth_bps: self.depth_bps.unwrap_or(defaults.depth_bps),
+            max_deviation_bps: self.max_deviation_bps.unwrap_or(defaults.max_deviation_bps),
+        }
+    }
+}
+
 /// External-price defensive guard (`[external_guard]`). Field defaults match
 /// [`standx_maker::GuardConfig`] so partial files stay valid.
 #[derive(Debug, Clone, Deserialize)]
@@ -120,6 +147,45 @@ impl ExternalGuardFileConfig {
     }
 }
 
+/// Quote model selection (`[strategy]`). `kind` picks the
+/// [`standx_maker::QuoteStrategy`]; a missing table keeps the ladder. Model
+/// parameter defaults match the maker crate so partial tables stay valid.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub(super) enum StrategyFileConfig {
+    Ladder {},
+    AvellanedaStoikov {
+        risk_aversion: Option<f64>,
+        kappa: Option<f64>,
+        kappa_prior_fills: Option<f64>,
+        kappa_window: Option<usize>,
+    },
+}
+
+impl StrategyFileConfig {
+    pub(super) fn into_domain(self) -> standx_maker::StrategyConfig {
+        match self {
+            Self::Ladder {} => standx_maker::StrategyConfig::Ladder,
+            Self::AvellanedaStoikov {
+                risk_aversion,
+                kappa,
+                kappa_prior_fills,
+                kappa_window,
+            } => {
+                let defaults = standx_maker::AvellanedaStoikovConfig::default();
+                standx_maker::StrategyConfig::AvellanedaStoikov(
+                    standx_maker::AvellanedaStoikovConfig {
+                        risk_aversion: risk_aversion.unwrap_or(defaults.risk_aversion),
+                        kappa: kappa.unwrap_or(defaults.kappa),
+                        kappa_prior_fills: kappa_prior_fills.unwrap_or(defaults.kappa_prior_fills),
+                        kappa_window: kappa_window.unwrap_or(defaults.kappa_window),
+                    },
+                )
+            }
+        }
+    }
+}
+
 /// Values are optional so an explicit CLI flag can override one field without
 /// requiring every strategy default to be repeated in TOML.
 #[derive(Debug, Default, Deserialize)]
@@ -144,6 +210,8 @@ pub(super) struct MakerFileConfig {
     pub size_skew: Option<SizeSkewFileConfig>,
     pub nonlinear_skew: Option<NonlinearSkewFileConfig>,
     pub external_guard: Option<ExternalGuardFileConfig>,
+    pub strategy: Option<StrategyFileConfig>,
+    pub fair_value: Option<FairValueFileConfig>,
     pub stop_loss: Option<f64>,
     pub alert_loss: Option<f64>,
     pub alert_inventory_pct: Option<f64>,
@@ -163,12 +231,18 @@ pub(super) struct MakerFileConfig {
 }
 
 pub(super) fn load(path: Option<&Path>) -> Result<MakerFileConfig> {
-    let path = path
-        .map(PathBuf::from)
-        .unwrap_or_else(|| Config::default_config_dir().join("maker.toml"));
+    let (path, table) = load_table(path)?;
+    parse(table)
+        .map_err(|error| anyhow::anyhow!("invalid maker config {}: {}", path.display(), error))
+}
+
+/// The maker TOML as an untyped table, with the path it was read from. A
+/// missing default file is an empty table.
+pub(super) fn load_table(path: Option<&Path>) -> Result<(PathBuf, toml::Table)> {
+    let path = resolve_path(path);
     if !path.exists() {
         if path.as_path() == Config::default_config_dir().join("maker.toml") {
-            return Ok(MakerFileConfig::default());
+            return Ok((path, toml::Table::new()));
         }
         return Err(anyhow::anyhow!(
             "maker config file not found: {}",
@@ -176,8 +250,21 @@ pub(super) fn load(path: Option<&Path>) -> Result<MakerFileConfig> {
         ));
     }
     let content = std::fs::read_to_string(&path)?;
-    toml::from_str(&content)
-        .map_err(|error| anyhow::anyhow!("invalid maker config {}: {}", path.display(), error))
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|error| anyhow::anyhow!("invalid maker config {}: {}", path.display(), error))?;
+    Ok((path, table))
+}
+
+/// The maker TOML a run reads: the explicit path, else `maker.toml` in the
+/// StandX config directory.
+pub(super) fn resolve_path(path: Option<&Path>) -> PathBuf {
+    path.map(PathBuf::from)
+        .unwrap_or_else(|| Config::default_config_dir().join("maker.toml"))
+}
+
+pub(super) fn parse(table: toml::Table) -> std::result::Result<MakerFileConfig, toml::de::Error> {
+    toml::Value::Table(table).try_into()
 }
 
 #[cfg(test)]
@@ -293,6 +380,43 @@ add_side_factor = 0.5
         );
     }
 
+    #[test]
+    fn strategy_table_selects_the_quote_model_and_rejects_unknown_kinds() {
+        let config: MakerFileConfig = toml::from_str("spread_bps = 8\n").unwrap();
+        assert_eq!(config.strategy, None);
+
+        let config: MakerFileConfig = toml::from_str("[strategy]\nkind = \"ladder\"\n").unwrap();
+        assert_eq!(
+            config.strategy.map(StrategyFileConfig::into_domain),
+            Some(standx_maker::StrategyConfig::Ladder)
+        );
+
+        let error = toml::from_str::<MakerFileConfig>("[strategy]\nkind = \"grid\"\n").unwrap_err();
+        assert!(error.to_string().contains("grid"), "{error}");
+        let error = toml::from_str::<MakerFileConfig>("[strategy]\nkind = \"ladder\"\ngamma = 1\n")
+            .unwrap_err();
+        assert!(error.to_string().contains("gamma"), "{error}");
+    }
+
+    #[test]
+    fn avellaneda_stoikov_table_fills_unset_parameters_from_defaults() {
+        let config: MakerFileConfig = toml::from_str(
+            "[strategy]\nkind = \"avellaneda_stoikov\"\nrisk_aversion = 0.1\nkappa_window = 50\n",
+        )
+        .unwrap();
+        let defaults = standx_maker::AvellanedaStoikovConfig::default();
+        assert_eq!(
+            config.strategy.map(StrategyFileConfig::into_domain),
+            Some(standx_maker::StrategyConfig::AvellanedaStoikov(
+                standx_maker::AvellanedaStoikovConfig {
+                    risk_aversion: 0.1,
+                    kappa_window: 50,
+                    ..defaults
+                }
+            ))
+        );
+    }
+
     #[test]
     fn xag_example_enables_twenty_percent_position_jump_alert() {
         let config: MakerFileConfig = toml::from_str(include_str!(concat!(
@@ -419,6 +543,21 @@ add_side_factor = 0.5
         assert_eq!(guard.exit_bps, 3.0);
     }
 
+    #[test]
+    fn fair_value_section_fills_unset_fields_from_defaults() {
+        let config: MakerFileConfig = toml::from_str(
+            "[fair_value]\nenabled = true\nmicroprice_weight = 2.0\ndepth_bps = 5.0\n",
+        )
+        .unwrap();
+        let fair_value = config.fair_value.unwrap().into_domain();
+        assert!(fair_value.enabled);
+        assert_eq!(fair_value.microprice_weight, 2.0);
+        assert_eq!(fair_value.depth_bps, 5.0);
+        assert_eq!(fair_value.mark_weight, 0.5);
+        assert_eq!(fair_value.max_deviation_bps, 2.0);
+        assert!(toml::from_str::<MakerFileConfig>("[fair_value]\nweight = 1.0\n").is_err());
+    }
+
     #[test]
     fn stage3v1_live_arms_only_differ_by_combined_enable_switches() {
         let baseline = include_str!(concat!(
@@ -0,0 +1,644 @@
+//! Runtime control socket (`maker run --control-socket`, `maker ctl`).
+//!
+//! The running maker listens on a Unix domain socket for one JSON request per
+//! connection and answers with one JSON line. Requests are checked against
+//! the running config here, then fed to the runtime state machine as
+//! [`MakerEvent::Control`] between cycles, so an operator command never
+//! races the planner. Access control is the socket file's owner-only mode.
+
+use super::super::schema::{trace, Definitions};
+use super::startup::validate_run_config;
+use super::*;
+use crate::output;
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use tokio::sync::{mpsc, oneshot};
+
+/// Longest request line the socket reads; every valid request is far shorter.
+#[cfg(unix)]
+const MAX_REQUEST_BYTES: u64 = 4096;
+
+/// How long `maker ctl` waits for an answer. The runtime answers between
+/// cycles, so only a maker stuck in recovery comes near this.
+const CTL_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The wire form of a `maker ctl` command.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub(super) enum ControlRequest {
+    Pause,
+    Resume,
+    Widen { bps: f64 },
+    Size { qty: f64 },
+    Flatten,
+    Stop,
+    Status,
+}
+
+impl From<MakerCtlCommand> for ControlRequest {
+    fn from(command: MakerCtlCommand) -> Self {
+        match command {
+            MakerCtlCommand::Pause => Self::Pause,
+            MakerCtlCommand::Resume => Self::Resume,
+            MakerCtlCommand::Widen { bps } => Self::Widen { bps },
+            MakerCtlCommand::Size { qty } => Self::Size { qty },
+            MakerCtlCommand::Flatten => Self::Flatten,
+            MakerCtlCommand::Stop => Self::Stop,
+            MakerCtlCommand::Status => Self::Status,
+        }
+    }
+}
+
+impl ControlRequest {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Widen { .. } => "widen",
+            Self::Size { .. } => "size",
+            Self::Flatten => "flatten",
+            Self::Stop => "stop",
+            Self::Status => "status",
+        }
+    }
+
+    pub(super) fn value(self) -> Option<f64> {
+        match self {
+            Self::Widen { bps } => Some(bps),
+            Self::Size { qty } => Some(qty),
+            _ => None,
+        }
+    }
+}
+
+/// Check `request` against the running config and turn it into the runtime
+/// command. `None` is the read-only `status`.
+pub(super) fn command(
+    request: ControlRequest,
+    args: &MakerRunArgs,
+    cfg: &MakerConfig,
+    symbol: &str,
+) -> Result<Option<maker::ControlCommand>> {
+    use maker::ControlCommand;
+    let overrides_quoting = matches!(
+        request,
+        ControlRequest::Pause
+            | ControlRequest::Resume
+            | ControlRequest::Widen { .. }
+            | ControlRequest::Size { .. }
+    );
+    if let Some(path) = args.trace_out.as_deref().filter(|_| overrides_quoting) {
+        // The trace does not record overrides, so replay could not follow.
+        // `flatten` and `stop` stay available as the signals' equivalents.
+        return Err(anyhow::anyhow!(
+            "replay trace {} does not record operator overrides; only flatten, stop and status are accepted",
+            path.display()
+        ));
+    }
+    let command = match request {
+        ControlRequest::Pause => ControlCommand::Pause,
+        ControlRequest::Resume => ControlCommand::Resume,
+        ControlRequest::Widen { bps } => {
+            if !bps.is_finite() || bps < 0.0 {
+                return Err(anyhow::anyhow!("widen must be a finite bps value >= 0"));
+            }
+            // The adaptive controller may already sit at its widest tier;
+            // the widen lands on top of that.
+            let widest = if args.adaptive_spread.enabled {
+                cfg.spread_bps.max(args.adaptive_spread.max_spread_bps)
+            } else {
+                cfg.spread_bps
+            };
+            if widest + bps >= cfg.band_bps {
+                return Err(anyhow::anyhow!(
+                    "widen {bps} would put the spread at {} bps, at or beyond the {} bps band",
+                    widest + bps,
+                    cfg.band_bps
+                ));
+            }
+            ControlCommand::Widen(bps)
+        }
+        ControlRequest::Size { qty: 0.0 } => ControlCommand::Size(None),
+        ControlRequest::Size { qty } => {
+            if !qty.is_finite() || qty < 0.0 {
+                return Err(anyhow::anyhow!("size must be a finite quantity >= 0"));
+            }
+            if qty > cfg.max_position {
+                return Err(anyhow::anyhow!(
+                    "size {qty} exceeds max_position {}",
+                    cfg.max_position
+                ));
+            }
+            let mut sized = cfg.clone();
+            sized.size = qty;
+            validate_run_config(args, &sized, symbol)
+                .with_context(|| format!("size {qty} rejected"))?;
+            ControlCommand::Size(Some(qty))
+        }
+        ControlRequest::Flatten => ControlCommand::Flatten,
+        ControlRequest::Stop => ControlCommand::Stop,
+        ControlRequest::Status => return Ok(None),
+    };
+    Ok(Some(command))
+}
+
+/// Re-check the active widen and size overrides against a reloaded config,
+/// so a reload cannot narrow the band or lower the limits underneath them.
+pub(super) fn recheck(
+    controls: maker::OperatorControls,
+    args: &MakerRunArgs,
+    cfg: &MakerConfig,
+    symbol: &str,
+) -> Result<()> {
+    if controls.widen_bps > 0.0 {
+        command(
+            ControlRequest::Widen {
+                bps: controls.widen_bps,
+            },
+            args,
+            cfg,
+            symbol,
+        )
+        .context("active operator widen no longer fits; clear it with `maker ctl widen 0` first")?;
+    }
+    if let Some(qty) = controls.size {
+        command(ControlRequest::Size { qty }, args, cfg, symbol).context(
+            "active operator size no longer fits; clear it with `maker ctl size 0` first",
+        )?;
+    }
+    Ok(())
+}
+
+/// The active operator overrides, as every reply carries them.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub(super) struct ControlsReply {
+    paused: bool,
+    widen_bps: f64,
+    size: Option<f64>,
+    flatten: bool,
+}
+
+impl From<maker::OperatorControls> for ControlsReply {
+    fn from(controls: maker::OperatorControls) -> Self {
+        Self {
+            paused: controls.paused,
+            widen_bps: controls.widen_bps,
+            size: controls.size,
+            flatten: controls.flatten,
+        }
+    }
+}
+
+/// The reply to an applied command.
+#[derive(Debug, Serialize)]
+pub(super) struct AppliedReply {
+    pub(super) ok: bool,
+    pub(super) command: &'static str,
+    pub(super) value: Option<f64>,
+    pub(super) controls: ControlsReply,
+}
+
+/// The reply to `status`.
+#[derive(Debug, Serialize)]
+pub(super) struct StatusReply {
+    pub(super) ok: bool,
+    pub(super) command: &'static str,
+    pub(super) symbol: String,
+    pub(super) live: bool,
+    pub(super) cycle: u64,
+    pub(super) position: f64,
+    pub(super) mark: Option<f64>,
+    pub(super) bids: usize,
+    pub(super) asks: usize,
+    pub(super) spread_bps: f64,
+    pub(super) size: f64,
+    pub(super) wind_down: bool,
+    pub(super) fills: u64,
+    pub(super) controls: ControlsReply,
+}
+
+/// JSON output schema of `maker ctl <command>`, for `standx schema`. Only
+/// successful replies are printed; rejections exit non-zero.
+pub(in crate::commands) fn ctl_output_schema(
+    command: &str,
+    definitions: &mut Definitions,
+) -> Value {
+    let controls = ControlsReply::from(maker::OperatorControls {
+        size: Some(0.0),
+        ..maker::OperatorControls::default()
+    });
+    if command == "status" {
+        let status = StatusReply {
+            ok: true,
+            command: "status",
+            symbol: String::new(),
+            live: false,
+            cycle: 0,
+            position: 0.0,
+            mark: Some(0.0),
+            bids: 0,
+            asks: 0,
+            spread_bps: 0.0,
+            size: 0.0,
+            wind_down: false,
+            fills: 0,
+            controls,
+        };
+        return trace(definitions, &status);
+    }
+    let applied = AppliedReply {
+        ok: true,
+        command: "widen",
+        value: Some(0.0),
+        controls,
+    };
+    trace(definitions, &applied)
+}
+
+pub(super) fn controls_json(controls: maker::OperatorControls) -> Value {
+    json!(ControlsReply::from(controls))
+}
+
+/// One-line summary of the active overrides in a [`controls_json`] value.
+pub(super) fn describe_controls(controls: &Value) -> String {
+    let mut parts = Vec::new();
+    if controls["paused"].as_bool() == Some(true) {
+        parts.push("paused".to_string());
+    }
+    if let Some(bps) = controls["widen_bps"].as_f64().filter(|bps| *bps > 0.0) {
+        parts.push(format!("widen +{bps} bps"));
+    }
+    if let Some(size) = controls["size"].as_f64() {
+        parts.push(format!("size {size}"));
+    }
+    if controls["flatten"].as_bool() == Some(true) {
+        parts.push("flattening".to_string());
+    }
+    if parts.is_empty() {
+        "none".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+/// A request waiting for the runtime, with the connection's reply slot.
+pub(super) struct ControlEnvelope {
+    pub(super) request: ControlRequest,
+    reply: oneshot::Sender<Value>,
+}
+
+impl ControlEnvelope {
+    pub(super) fn reply(self, body: Value) {
+        // The client may have given up; nothing is waiting then.
+        let _ = self.reply.send(body);
+    }
+}
+
+/// The listening socket. Dropping it stops accepting and removes the file.
+pub(super) struct ControlSocket {
+    path: PathBuf,
+    requests: mpsc::Receiver<ControlEnvelope>,
+    /// A request taken off the channel that waits for the cycle boundary.
+    deferred: Option<ControlEnvelope>,
+    accept: tokio::task::JoinHandle<()>,
+}
+
+impl ControlSocket {
+    /// Bind `path` owner-only. A stale socket left by a crashed run is
+    /// replaced; a live one, or any other kind of file, is refused.
+    #[cfg(unix)]
+    pub(super) fn bind(path: &Path) -> Result<Self> {
+        use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+        match std::fs::symlink_metadata(path) {
+            Ok(metadata) if !metadata.file_type().is_socket() => {
+                return Err(anyhow::anyhow!(
+                    "control socket path {} exists and is not a socket",
+                    path.display()
+                ));
+            }
+            Ok(_) => {
+                if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                    return Err(anyhow::anyhow!(
+                        "control socket {} is in use by another maker",
+                        path.display()
+                    ));
+                }
+                std::fs::remove_file(path).with_context(|| {
+                    format!("failed to remove stale control socket {}", path.display())
+                })?;
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(error).with_context(|| format!("failed to inspect {}", path.display()));
+            }
+        }
+        let listener = tokio::net::UnixListener::bind(path)
+            .with_context(|| format!("failed to bind control socket {}", path.display()))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+            .with_context(|| format!("failed to restrict control socket {}", path.display()))?;
+        let (tx, requests) = mpsc::channel(8);
+        let accept = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => {
+                        tokio::spawn(serve_connection(stream, tx.clone()));
+                    }
+                    Err(error) => {
+                        eprintln!("⚠️ maker control socket stopped accepting: {error}");
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(Self {
+            path: path.to_path_buf(),
+            requests,
+            deferred: None,
+            accept,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub(super) fn bind(_path: &Path) -> Result<Self> {
+        Err(anyhow::anyhow!(
+            "--control-socket needs Unix domain sockets, which this platform lacks"
+        ))
+    }
+
+    pub(super) async fn recv(&mut self) -> Option<ControlEnvelope> {
+        self.requests.recv().await
+    }
+
+    pub(super) fn try_recv(&mut self) -> Option<ControlEnvelope> {
+        self.deferred
+            .take()
+            .or_else(|| self.requests.try_recv().ok())
+    }
+
+    /// Hand `envelope` back so the next [`Self::try_recv`] returns it first.
+    pub(super) fn defer(&mut self, envelope: ControlEnvelope) {
+        self.deferred = Some(envelope);
+    }
+}
+
+impl Drop for ControlSocket {
+    fn drop(&mut self) {
+        self.accept.abort();
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(unix)]
+async fn serve_connection(stream: tokio::net::UnixStream, requests: mpsc::Sender<ControlEnvelope>) {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    let (read, mut write) = stream.into_split();
+    let mut line = String::new();
+    let reply = match BufReader::new(read.take(MAX_REQUEST_BYTES))
+        .read_line(&mut line)
+        .await
+    {
+        Err(error) => json!({ "ok": false, "error": format!("failed to read request: {error}") }),
+        Ok(_) => match serde_json::from_str::<ControlRequest>(line.trim()) {
+            Err(error) => {
+                json!({ "ok": false, "error": format!("invalid control request: {error}") })
+            }
+            Ok(request) => {
+                let (reply, answer) = oneshot::channel();
+                let stopped = json!({ "ok": false, "error": "maker is shutting down" });
+                if requests
+                    .send(ControlEnvelope { request, reply })
+                    .await
+                    .is_err()
+                {
+                    stopped
+                } else {
+                    answer.await.unwrap_or(stopped)
+                }
+            }
+        },
+    };
+    let _ = write.write_all(format!("{reply}\n").as_bytes()).await;
+}
+
+/// `maker ctl`: send one command and print the maker's answer. A rejected
+/// command is an error so scripts can branch on the exit code.
+pub(super) async fn run_ctl(
+    socket: &Path,
+    command: MakerCtlCommand,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let request = ControlRequest::from(command);
+    let reply = tokio::time::timeout(CTL_REPLY_TIMEOUT, exchange(socket, request))
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "no answer from the maker at {} within {}s (it may be recovering)",
+                socket.display(),
+                CTL_REPLY_TIMEOUT.as_secs()
+            )
+        })??;
+    if reply["ok"].as_bool() != Some(true) {
+        return Err(anyhow::anyhow!(
+            "maker rejected `{}`: {}",
+            request.label(),
+            reply["error"].as_str().unwrap_or("no reason given")
+        ));
+    }
+    match output_format {
+        OutputFormat::Json => println!("{}", output::format_json(&reply)?),
+        OutputFormat::Quiet => {}
+        _ if request == ControlRequest::Status => print_status(&reply),
+        _ => println!(
+            "✅ {} applied — overrides: {}",
+            request.label(),
+            describe_controls(&reply["controls"])
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn exchange(socket: &Path, request: ControlRequest) -> Result<Value> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    let mut stream = tokio::net::UnixStream::connect(socket)
+        .await
+        .with_context(|| {
+            format!(
+                "no maker control socket at {} (start it with `maker run --control-socket`)",
+                socket.display()
+            )
+        })?;
+    let mut line = serde_json::to_string(&request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).await?;
+    serde_json::from_str(reply.trim()).context("malformed reply from the maker control socket")
+}
+
+#[cfg(not(unix))]
+async fn exchange(_socket: &Path, _request: ControlRequest) -> Result<Value> {
+    Err(anyhow::anyhow!(
+        "maker ctl needs Unix domain sockets, which this platform lacks"
+    ))
+}
+
+fn print_status(status: &Value) {
+    let mode = if status["live"].as_bool() == Some(true) {
+        "live"
+    } else {
+        "paper"
+    };
+    println!(
+        "maker {} ({mode}) — cycle {}",
+        status["symbol"].as_str().unwrap_or("?"),
+        status["cycle"]
+    );
+    println!(
+        "position {:+} @ mark {}",
+        status["position"].as_f64().unwrap_or(0.0),
+        status["mark"]
+            .as_f64()
+            .map_or_else(|| "n/a".to_string(), |mark| mark.to_string())
+    );
+    println!(
+        "quotes: {} bid / {} ask (spread {} bps, size {})",
+        status["bids"], status["asks"], status["spread_bps"], status["size"]
+    );
+    if status["wind_down"].as_bool() == Some(true) {
+        println!("wind-down: flattening, no new quotes");
+    }
+    println!("overrides: {}", describe_controls(&status["controls"]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn running() -> (MakerRunArgs, MakerConfig) {
+        let args = MakerRunArgs {
+            interval: 5,
+            ..MakerRunArgs::default()
+        };
+        let cfg = MakerConfig {
+            spread_bps: 8.0,
+            band_bps: 30.0,
+            level_step_bps: 2.0,
+            refresh_bps: 4.0,
+            levels: 1,
+            size: 0.01,
+            max_position: 0.2,
+            skew_bps: 0.0,
+            price_decimals: 2,
+            qty_decimals: 3,
+            min_order_qty: 0.001,
+        };
+        (args, cfg)
+    }
+
+    #[test]
+    fn requests_round_trip_the_wire_form() {
+        let request = ControlRequest::Widen { bps: 2.5 };
+        let line = serde_json::to_string(&request).expect("serialize request");
+        assert_eq!(line, r#"{"command":"widen","bps":2.5}"#);
+        assert_eq!(
+            serde_json::from_str::<ControlRequest>(r#"{"command":"pause"}"#).expect("parse"),
+            ControlRequest::Pause
+        );
+        assert!(serde_json::from_str::<ControlRequest>(r#"{"command":"reboot"}"#).is_err());
+    }
+
+    #[test]
+    fn widen_and_size_are_checked_against_the_running_config() {
+        let (mut args, cfg) = running();
+        let check = |args: &MakerRunArgs, request| command(request, args, &cfg, "BTC-USD");
+
+        assert_eq!(
+            check(&args, ControlRequest::Widen { bps: 10.0 }).expect("fits the band"),
+            Some(maker::ControlCommand::Widen(10.0))
+        );
+        assert!(check(&args, ControlRequest::Widen { bps: 22.0 }).is_err());
+        assert!(check(&args, ControlRequest::Widen { bps: -1.0 }).is_err());
+        args.adaptive_spread.enabled = true;
+        args.adaptive_spread.max_spread_bps = 20.0;
+        assert!(check(&args, ControlRequest::Widen { bps: 10.0 }).is_err());
+
+        let (args, _) = running();
+        assert_eq!(
+            check(&args, ControlRequest::Size { qty: 0.05 }).expect("valid size"),
+            Some(maker::ControlCommand::Size(Some(0.05)))
+        );
+        assert_eq!(
+            check(&args, ControlRequest::Size { qty: 0.0 }).expect("reset"),
+            Some(maker::ControlCommand::Size(None))
+        );
+        assert!(check(&args, ControlRequest::Size { qty: 0.0004 }).is_err());
+        assert!(check(&args, ControlRequest::Size { qty: 0.3 }).is_err());
+        assert_eq!(check(&args, ControlRequest::Status).expect("status"), None);
+    }
+
+    #[test]
+    fn a_recorded_trace_refuses_quoting_overrides() {
+        let (mut args, cfg) = running();
+        args.trace_out = Some(PathBuf::from("session.ndjson"));
+        let check = |request| command(request, &args, &cfg, "BTC-USD");
+
+        assert!(check(ControlRequest::Pause).is_err());
+        assert!(check(ControlRequest::Widen { bps: 1.0 }).is_err());
+        assert!(check(ControlRequest::Size { qty: 0.05 }).is_err());
+        assert_eq!(
+            check(ControlRequest::Flatten).expect("flatten"),
+            Some(maker::ControlCommand::Flatten)
+        );
+        assert_eq!(
+            check(ControlRequest::Stop).expect("stop"),
+            Some(maker::ControlCommand::Stop)
+        );
+        assert_eq!(check(ControlRequest::Status).expect("status"), None);
+    }
+    #[test]
+    fn controls_summary_lists_only_active_overrides() {
+        assert_eq!(
+            describe_controls(&controls_json(maker::OperatorControls::default())),
+            "none"
+        );
+        assert_eq!(
+            describe_controls(&controls_json(maker::OperatorControls {
+                paused: true,
+                widen_bps: 3.0,
+                size: Some(0.02),
+                flatten: false,
+            })),
+            "paused, widen +3 bps, size 0.02"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn socket_round_trips_a_request_and_cleans_up() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maker.sock");
+        std::fs::write(dir.path().join("plain"), "").unwrap();
+        assert!(ControlSocket::bind(&dir.path().join("plain")).is_err());
+
+        let mut socket = ControlSocket::bind(&path).expect("bind control socket");
+        assert!(
+            ControlSocket::bind(&path).is_err(),
+            "a live socket is refused"
+        );
+        let client = tokio::spawn({
+            let path = path.clone();
+            async move { exchange(&path, ControlRequest::Widen { bps: 2.0 }).await }
+        });
+        let envelope = socket.recv().await.expect("request arrives");
+        assert_eq!(envelope.request, ControlRequest::Widen { bps: 2.0 });
+        envelope.reply(json!({ "ok": true, "command": "widen" }));
+        let reply = client.await.unwrap().expect("reply arrives");
+        assert_eq!(reply["command"], "widen");
+
+        drop(socket);
+        assert!(!path.exists());
+    }
+}
@@ -10,7 +10,10 @@ use super::pipeline::{
     fetch_account_audit, CycleRequest, CycleResult, CycleState, OrderRequestKind,
 };
 use super::recovery::PositionReconciliationError;
+use super::replay::{record_trace, TraceWriter};
+use crate::audit;
 use anyhow::Result;
+use serde_json::Value;
 use standx_maker::{
     self as maker, AccountProjectionEvent, MakerAccountProjection, MakerFill, MakerLedger,
     MakerStats, OrderLatencyTracker, ProjectionPendingCancel, ProjectionPendingPlace,
@@ -19,7 +22,7 @@ use standx_maker::{
 use standx_sdk::account_stream::AccountStreamHealth;
 #[cfg(test)]
 use standx_sdk::account_stream::{OrderUpdate, TradeUpdate};
-use standx_sdk::client::order::CreateOrderParams;
+use standx_sdk::client::order::{cancel_order_body, create_order_body, CreateOrderParams};
 use standx_sdk::models::{Balance, OrderSide, OrderType, TimeInForce, Trade};
 use standx_sdk::order_response::{OrderCommandSender, OrderResponseHealth};
 use std::time::Instant;
@@ -98,6 +101,22 @@ fn observe_order_write(
     }
 }
 
+/// Audit a socket order command once its write completed. The venue answers
+/// asynchronously, so the record carries the request ID that correlates the
+/// answer in the maker event log.
+pub(super) fn audit_order_write(
+    action: &str,
+    symbol: &str,
+    request: &Value,
+    request_id: &str,
+    sent: &standx_sdk::Result<()>,
+) {
+    let written = sent
+        .as_ref()
+        .map(|()| serde_json::json!({ "request_id": request_id }));
+    audit::record_result(action, symbol, request, &written);
+}
+
 fn elapsed_ms(started: Instant) -> u64 {
     u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
 }
@@ -203,6 +222,8 @@ pub(super) async fn maker_cycle(
         mark,
         best_bid,
         best_ask,
+        bid_levels,
+        ask_levels,
         market_data_mode,
         market_source,
         recovery,
@@ -212,6 +233,7 @@ pub(super) async fn maker_cycle(
         inventory_exit_pct,
         inventory_exit_qty,
         wind_down,
+        controls,
         qty_tolerance,
         session_started_at,
         run_order_prefix,
@@ -233,6 +255,8 @@ pub(super) async fn maker_cycle(
         spread_controller,
         size_skew_controller,
         nonlinear_skew,
+        strategy,
+        fair_value,
         guard_controller,
         external_divergence,
         external_basis_bps,
@@ -240,8 +264,11 @@ pub(super) async fn maker_cycle(
         live_account_poll,
         mut order_latency,
         latency_started,
+        trace,
     } = state;
     use maker::{format_decimals, paper_quote_filled, Action, CycleInput, MarketSnapshot};
+    let sized_cfg = controls.sized(cfg);
+    let cfg = &sized_cfg;
 
     // 0. Run all market-only guards before any account/order I/O. The pure
     // planner owns breaker observation and data-consistency policy; this
@@ -251,13 +278,16 @@ pub(super) async fn maker_cycle(
         best_bid,
         best_ask,
     };
+    // Fills and funding the ledger accepted between cycles (account stream
+    // events) precede this cycle in the trace.
+    trace_journal(trace, ledger);
+    let observed_resting = account_projection
+        .as_deref()
+        .map(|projection| projection.resting_quotes())
+        .unwrap_or_else(|| resting.clone());
+    let (eligible_bid_qty, eligible_ask_qty) =
+        eligible_quote_qty(&observed_resting, mark, cfg.band_bps);
     if let Some(performance) = ledger.performance_mut() {
-        let observed_resting = account_projection
-            .as_deref()
-            .map(|projection| projection.resting_quotes())
-            .unwrap_or_else(|| resting.clone());
-        let (eligible_bid_qty, eligible_ask_qty) =
-            eligible_quote_qty(&observed_resting, mark, cfg.band_bps);
         let observation = performance
             .observe_market(performance_time_ms, mark)
             .and_then(|()| {
@@ -280,7 +310,7 @@ pub(super) async fn maker_cycle(
         live,
     )?;
     let spread_decision = spread_controller.observe(breaker.vol_bps(), cfg);
-    let effective_cfg = spread_controller.effective_config(cfg, &spread_decision);
+    let effective_cfg = controls.widened(spread_controller.effective_config(cfg, &spread_decision));
     let cfg = &effective_cfg;
     let halted = match preflight.skip {
         Some(skip) => {
@@ -295,6 +325,24 @@ pub(super) async fn maker_cycle(
                 skip,
             );
             if market_data_mode == maker::MarketDataMode::Active {
+                if trace.is_some() {
+                    let cycle = maker::ReplayCycle {
+                        event_time_ms: performance_time_ms,
+                        cycle,
+                        market,
+                        position: if live {
+                            ledger.expected_position
+                        } else {
+                            *sim_position
+                        },
+                        resting: observed_resting,
+                        pending_slots: pending_place_slots(account_projection.as_deref()),
+                        eligible_bid_qty,
+                        eligible_ask_qty,
+                        fair_value: None,
+                    };
+                    record_trace(trace, &[maker::ReplayEvent::Cycle(cycle)]);
+                }
                 return Ok(CycleResult::default());
             }
             preflight.halted
@@ -526,15 +574,23 @@ pub(super) async fn maker_cycle(
     } else {
         resting.as_slice()
     };
-    let pending_slots = account_projection
-        .as_deref()
-        .map(|projection| projection.pending_places())
-        .unwrap_or_default()
-        .iter()
-        .map(|place| (place.side, place.level))
-        .collect::<Vec<_>>();
-    let plan = maker::plan_cycle(
+    let pending_slots = pending_place_slots(account_projection.as_deref());
+    let fair_value = fair_value.estimate(mark, bid_levels, ask_levels);
+    // The planner inputs, completed with end-of-cycle quote quality below.
+    let mut traced_cycle = trace.is_some().then(|| maker::ReplayCycle {
+        event_time_ms: performance_time_ms,
+        cycle,
+        market,
+        position,
+        resting: active_resting.to_vec(),
+        pending_slots: pending_slots.clone(),
+        eligible_bid_qty: 0.0,
+        eligible_ask_qty: 0.0,
+        fair_value: fair_value.map(|fair| fair.price),
+    });
+    let plan = maker::plan_cycle_with(
         cfg,
+        &*strategy,
         CycleInput {
             cycle,
             market,
@@ -549,10 +605,19 @@ pub(super) async fn maker_cycle(
             nonlinear_skew,
             guard: guard_decision,
             wind_down,
+            paused: controls.paused,
             qty_tolerance,
+            vol_bps: breaker.vol_bps(),
+            fair_value: fair_value.map(|fair| fair.price),
         },
         halted,
     );
+    // The trace records this cycle's own fills after the cycle itself, so the
+    // strategy learns from them only once the plan is built; replay feeds
+    // them in the same order.
+    for fill in &fills {
+        strategy.record_fill(fill.role, fill.side, fill.price, fill.mark_at_fill);
+    }
     let raw_inventory_exit = plan.requested_inventory_exit;
     if exit_fill_observed {
         *inventory_exit_pending = false;
@@ -620,6 +685,7 @@ pub(super) async fn maker_cycle(
 
     // The pure planner provides the anti-flicker anchor for new placements.
     let ref_center = plan.ref_center;
+    let anchor = fair_value.map_or(mark, |fair| fair.price);
 
     // 4. Execute. A socket-write failure propagates toward the fail-safe;
     // business acceptance/rejection is handled later through the correlated
@@ -689,6 +755,13 @@ pub(super) async fn maker_cycle(
                             &request_id,
                             sent.is_ok(),
                         );
+                        audit_order_write(
+                            "order_cancel",
+                            symbol,
+                            &cancel_order_body(order_id as i64),
+                            &request_id,
+                            &sent,
+                        );
                         sent?;
                         cancels += 1;
                     }
@@ -704,7 +777,7 @@ pub(super) async fn maker_cycle(
                     let cl_ord_id =
                         maker::quote_client_order_id(run_order_prefix, cycle, q.side, q.level);
                     let commands = live_order_commands(order_commands)?;
-                    let command = commands.prepare_create_order(&CreateOrderParams {
+                    let params = CreateOrderParams {
                         symbol: symbol.to_string(),
                         cl_ord_id: Some(cl_ord_id.clone()),
                         side: q.side,
@@ -718,7 +791,8 @@ pub(super) async fn maker_cycle(
                         stop_price: None,
                         sl_price: None,
                         tp_price: None,
-                    })?;
+                    };
+                    let command = commands.prepare_create_order(&params)?;
                     let request_id = command.request_id().to_string();
                     let projection = account_projection
                         .as_deref_mut()
@@ -763,6 +837,13 @@ pub(super) async fn maker_cycle(
                         &request_id,
                         sent.is_ok(),
                     );
+                    audit_order_write(
+                        "order_create",
+                        symbol,
+                        &create_order_body(&params),
+                        &request_id,
+                        &sent,
+                    );
                     sent?;
                     places += 1;
                 } else {
@@ -796,7 +877,7 @@ pub(super) async fn maker_cycle(
             ensure_request_registry_capacity(account_projection.as_deref())?;
             let cl_ord_id = maker::exit_client_order_id(run_order_prefix, cycle);
             let commands = live_order_commands(order_commands)?;
-            let command = commands.prepare_create_order(&CreateOrderParams {
+            let params = CreateOrderParams {
                 symbol: symbol.to_string(),
                 cl_ord_id: Some(cl_ord_id.clone()),
                 side: exit.side,
@@ -808,7 +889,8 @@ pub(super) async fn maker_cycle(
                 stop_price: None,
                 sl_price: None,
                 tp_price: None,
-            })?;
+            };
+            let command = commands.prepare_create_order(&params)?;
             let request_id = command.request_id().to_string();
             // Register the exit submission so its asynchronous ack correlates
             // to a pending entry instead of counting as an unmatched response.
@@ -862,6 +944,13 @@ pub(super) async fn maker_cycle(
                 &request_id,
                 sent.is_ok(),
             );
+            audit_order_write(
+                "order_create",
+                symbol,
+                &create_order_body(&params),
+                &request_id,
+                &sent,
+            );
             sent?;
             *inventory_exit_pending = true;
             log_maker_event(MakerLogEvent {
@@ -891,9 +980,14 @@ pub(super) async fn maker_cycle(
     let two_sided = final_resting.iter().any(|r| r.side == OrderSide::Buy)
         && final_resting.iter().any(|r| r.side == OrderSide::Sell);
     stats.end_cycle(position, two_sided);
+    let (eligible_bid_qty, eligible_ask_qty) =
+        eligible_quote_qty(&final_resting, mark, cfg.band_bps);
+    if let Some(mut cycle) = traced_cycle.take() {
+        cycle.eligible_bid_qty = eligible_bid_qty;
+        cycle.eligible_ask_qty = eligible_ask_qty;
+        record_trace(trace, &[maker::ReplayEvent::Cycle(cycle)]);
+    }
     let quote_observation = if let Some(performance) = ledger.performance_mut() {
-        let (eligible_bid_qty, eligible_ask_qty) =
-            eligible_quote_qty(&final_resting, mark, cfg.band_bps);
         performance.observe_quote_quality(maker::QuoteQualityInterval {
             event_time_ms: performance_time_ms,
             eligible_bid_qty,
@@ -906,6 +1000,8 @@ pub(super) async fn maker_cycle(
         eprintln!("⚠️ maker performance observation disabled: {error}");
         ledger.disable_performance();
     }
+    // This cycle's own fills follow it, as `maker replay` expects.
+    trace_journal(trace, ledger);
     let performance_summary = match ledger
         .performance()
         .map(|performance| performance.summary(mark))
@@ -942,11 +1038,12 @@ pub(super) async fn maker_cycle(
         size_skew_decision: &size_skew_decision,
         guard_decision: &guard_decision,
         external_basis_bps,
-        skew_shift_bps: if mark > 0.0 {
-            (mark - ref_center) / mark * 1e4
+        skew_shift_bps: if anchor > 0.0 {
+            (anchor - ref_center) / anchor * 1e4
         } else {
             0.0
         },
+        fair_value: fair_value.as_ref(),
         cfg,
         performance: performance_summary.as_ref(),
     });
@@ -960,6 +1057,29 @@ pub(super) async fn maker_cycle(
     })
 }
 
+fn pending_place_slots(projection: Option<&MakerAccountProjection>) -> Vec<(OrderSide, u32)> {
+    projection
+        .map(|projection| projection.pending_places())
+        .unwrap_or_default()
+        .iter()
+        .map(|place| (place.side, place.level))
+        .collect()
+}
+
+/// Move the fills and funding the performance ledger journaled into the
+/// session trace. Without the performance ledger there is no journal, so the
+/// trace would silently lose fills; stop it instead.
+pub(super) fn trace_journal(trace: &mut Option<TraceWriter>, ledger: &mut MakerLedger) {
+    match ledger.performance_mut() {
+        Some(performance) => record_trace(trace, &performance.take_journal()),
+        None if trace.is_some() => {
+            eprintln!("⚠️ maker replay trace disabled: performance observation stopped");
+            *trace = None;
+        }
+        None => {}
+    }
+}
+
 fn eligible_quote_qty(resting: &[RestingQuote], mark: f64, band_bps: f64) -> (f64, f64) {
     let band = mark * band_bps / 10_000.0;
     resting
@@ -1,4 +1,5 @@
 use anyhow::Result;
+use standx_maker::BookLevel;
 use standx_sdk::client::StandXClient;
 use standx_sdk::websocket::{StandXWebSocket, WsMarketUpdate, WsMessage};
 use std::sync::Arc;
@@ -13,6 +14,8 @@ pub(super) struct FeedState {
     mark_meta: Option<FeedMeta>,
     best_bid: Option<f64>,
     best_ask: Option<f64>,
+    bid_levels: Vec<BookLevel>,
+    ask_levels: Vec<BookLevel>,
     book_meta: Option<FeedMeta>,
     reconnect_issue: Option<WsSnapshotIssue>,
 }
@@ -49,6 +52,9 @@ pub(super) struct AcquiredMarketSnapshot {
     pub(super) mark: f64,
     pub(super) best_bid: Option<f64>,
     pub(super) best_ask: Option<f64>,
+    /// Displayed book behind the touch, for the fair-value estimate only.
+    pub(super) bid_levels: Vec<BookLevel>,
+    pub(super) ask_levels: Vec<BookLevel>,
     pub(super) source: &'static str,
     pub(super) fallback_reason: Option<&'static str>,
     pub(super) ws_snapshot: Option<WsSnapshotDiagnostics>,
@@ -438,6 +444,8 @@ pub(super) fn spawn_market_feed(
                                     if update_is_newer(s.book_meta.as_ref(), &update) {
                                         s.best_bid = best_bid;
                                         s.best_ask = best_ask;
+                                        s.bid_levels = book_levels(&update.data.bids);
+                                        s.ask_levels = book_levels(&update.data.asks);
                                         s.book_meta = Some(update_meta(&update));
                                         if s.mark_meta.is_some() {
                                             s.reconnect_issue = None;
@@ -505,6 +513,8 @@ pub(super) async fn market_snapshot(
                     mark,
                     best_bid,
                     best_ask,
+                    bid_levels: s.bid_levels.clone(),
+                    ask_levels: s.ask_levels.clone(),
                     source: "ws",
                     fallback_reason: None,
                     ws_snapshot,
@@ -533,6 +543,8 @@ pub(super) async fn market_snapshot(
             mark,
             best_bid,
             best_ask,
+            bid_levels: book_levels(&depth.bids),
+            ask_levels: book_levels(&depth.asks),
             source,
             fallback_reason: ws_issue,
             ws_snapshot,
@@ -540,6 +552,20 @@ pub(super) async fn market_snapshot(
     )
 }
 
+/// Parsed `[price, qty]` levels; unparseable ones are dropped and the
+/// fair-value estimator ignores non-positive ones.
+fn book_levels(levels: &[[String; 2]]) -> Vec<BookLevel> {
+    levels
+        .iter()
+        .filter_map(|[price, qty]| {
+            Some(BookLevel {
+                price: price.parse().ok()?,
+                qty: qty.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
 fn validated_snapshot(
     mark: f64,
     best_bid: Option<f64>,
@@ -638,6 +664,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(meta(
                 1,
                 "2026-07-14T00:00:00Z",
@@ -656,6 +684,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(meta(2, "2026-07-14T00:00:03Z", now)),
             reconnect_issue: None,
         };
@@ -670,6 +700,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(meta(2, "2026-07-14T00:00:06Z", now)),
             reconnect_issue: None,
         };
@@ -687,6 +719,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(FeedMeta {
                 exchange_seq: Some(2),
                 server_time: None,
@@ -707,6 +741,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(FeedMeta {
                 exchange_seq: Some(2),
                 server_time: None,
@@ -736,6 +772,8 @@ mod tests {
             mark_meta: Some(mark),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(book),
             reconnect_issue: None,
         };
@@ -766,6 +804,8 @@ mod tests {
             mark_meta: Some(meta(1, "2026-07-14T00:00:00Z", now)),
             best_bid: Some(99.9),
             best_ask: Some(100.1),
+            bid_levels: Vec::new(),
+            ask_levels: Vec::new(),
             book_meta: Some(meta(2, "2026-07-14T00:00:00Z", now - WS_STALE_AFTER)),
             reconnect_issue: None,
         };
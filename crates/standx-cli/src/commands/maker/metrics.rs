@@ -0,0 +1,659 @@
+//! Prometheus metrics endpoint (`maker run --metrics-listen`).
+//!
+//! The runtime publishes a [`MetricsSnapshot`] at every loop boundary into a
+//! watch channel; the HTTP task renders the latest one in the Prometheus text
+//! exposition format on each scrape, so a slow scraper never holds up a
+//! cycle and a scrape never reads half-updated state.
+
+use super::*;
+use anyhow::Context;
+use http_body_util::{BodyExt, Full};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{self, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use standx_maker::{LatencyRequestKind, LatencySummary, PerformanceSummary};
+use standx_sdk::models::OrderSide;
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+
+/// Upper bounds, in seconds, of the cycle duration histogram buckets.
+const CYCLE_BUCKETS_SECS: [f64; 10] = [0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Pause after a failed accept. Errors such as EMFILE persist until a
+/// connection closes, so retrying at once would spin a core.
+const ACCEPT_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Everything a scrape reports, copied out of the runtime at one boundary.
+#[derive(Clone, Debug, Default)]
+pub(super) struct MetricsSnapshot {
+    pub(super) symbol: String,
+    pub(super) live: bool,
+    pub(super) cycles: u64,
+    pub(super) places: u64,
+    pub(super) cancels: u64,
+    pub(super) fills: u64,
+    pub(super) position: f64,
+    pub(super) mark: Option<f64>,
+    pub(super) performance: Option<PerformanceSummary>,
+    pub(super) bids: usize,
+    pub(super) asks: usize,
+    /// Order-lifecycle latency; live sessions only.
+    pub(super) latency: Vec<LatencySummary>,
+    pub(super) recovery_counts: maker::RecoveryCounts,
+    pub(super) market_data_paused: bool,
+    pub(super) frozen: bool,
+    pub(super) breaker_halted: bool,
+    pub(super) vol_bps: f64,
+    pub(super) guard_endangered: Option<OrderSide>,
+    pub(super) wind_down: bool,
+    pub(super) controls: maker::OperatorControls,
+}
+
+/// Cumulative cycle wall-clock durations since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(super) struct CycleHistogram {
+    /// Observations at or below each bound of [`CYCLE_BUCKETS_SECS`].
+    buckets: [u64; CYCLE_BUCKETS_SECS.len()],
+    count: u64,
+    sum_secs: f64,
+}
+
+impl CycleHistogram {
+    pub(super) fn observe(&mut self, elapsed: Duration) {
+        let secs = elapsed.as_secs_f64();
+        for (bucket, bound) in self.buckets.iter_mut().zip(CYCLE_BUCKETS_SECS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum_secs += secs;
+    }
+}
+
+type Published = Option<(MetricsSnapshot, CycleHistogram)>;
+
+/// The bound listener. Dropping it stops serving.
+pub(super) struct MetricsExporter {
+    published: watch::Sender<Published>,
+    cycle_durations: CycleHistogram,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl MetricsExporter {
+    pub(super) async fn bind(addr: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("failed to listen for metrics on {addr}"))?;
+        if !addr.ip().is_loopback() {
+            eprintln!(
+                "maker: ⚠️  metrics listening on {addr} — anyone who can reach it can read \
+                 position and PnL"
+            );
+        }
+        let (published, scrapes) = watch::channel(None);
+        let server = tokio::spawn(serve(listener, scrapes));
+        Ok(Self {
+            published,
+            cycle_durations: CycleHistogram::default(),
+            server,
+        })
+    }
+
+    pub(super) fn observe_cycle(&mut self, elapsed: Duration) {
+        self.cycle_durations.observe(elapsed);
+    }
+
+    pub(super) fn publish(&self, snapshot: MetricsSnapshot) {
+        self.published
+            .send_replace(Some((snapshot, self.cycle_durations)));
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+async fn serve(listener: TcpListener, scrapes: watch::Receiver<Published>) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(error) => {
+                tracing::warn!("maker metrics: accept failed: {error}");
+                tokio::time::sleep(ACCEPT_RETRY_DELAY).await;
+                continue;
+            }
+        };
+        let scrapes = scrapes.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let response = respond(&request, &scrapes.borrow());
+                async move { Ok::<_, Infallible>(response) }
+            });
+            if let Err(error) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("maker metrics: connection from {peer}: {error}");
+            }
+        });
+    }
+}
+
+fn respond(
+    request: &Request<Incoming>,
+    published: &Published,
+) -> Response<http_body_util::combinators::UnsyncBoxBody<Bytes, Infallible>> {
+    let (status, body) = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => match published {
+            Some((snapshot, cycle_durations)) => {
+                (StatusCode::OK, render(snapshot, cycle_durations))
+            }
+            None => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "maker is starting\n".to_string(),
+            ),
+        },
+        (_, "/metrics") => (StatusCode::METHOD_NOT_ALLOWED, "use GET\n".to_string()),
+        _ => (
+            StatusCode::NOT_FOUND,
+            "metrics are at /metrics\n".to_string(),
+        ),
+    };
+    let mut response = Response::new(Full::new(Bytes::from(body)).boxed_unsync());
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE));
+    response
+}
+
+/// Text exposition writer; every sample carries the `symbol` label.
+struct Exposition {
+    out: String,
+    symbol: String,
+}
+
+impl Exposition {
+    fn family(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP standx_maker_{name} {help}");
+        let _ = writeln!(self.out, "# TYPE standx_maker_{name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let _ = write!(self.out, "standx_maker_{name}{{symbol=\"{}\"", self.symbol);
+        for (key, label) in labels {
+            let _ = write!(self.out, ",{key}=\"{}\"", escape_label(label));
+        }
+        let _ = writeln!(self.out, "}} {}", format_value(value));
+    }
+
+    /// A family with a single unlabelled sample.
+    fn single(&mut self, name: &str, kind: &str, help: &str, value: f64) {
+        self.family(name, kind, help);
+        self.sample(name, &[], value);
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', r"\\")
+        .replace('"', r#"\""#)
+        .replace('\n', r"\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn flag(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+fn side_label(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "buy",
+        OrderSide::Sell => "sell",
+    }
+}
+
+fn kind_label(kind: LatencyRequestKind) -> &'static str {
+    match kind {
+        LatencyRequestKind::Place => "place",
+        LatencyRequestKind::Cancel => "cancel",
+    }
+}
+
+/// Render one scrape. Series whose source is unavailable (no mark yet,
+/// performance observation disabled, paper mode latency) are omitted rather
+/// than reported as zero.
+pub(super) fn render(snapshot: &MetricsSnapshot, cycle_durations: &CycleHistogram) -> String {
+    let mut out = Exposition {
+        out: String::new(),
+        symbol: escape_label(&snapshot.symbol),
+    };
+    out.family(
+        "info",
+        "gauge",
+        "Maker session; the mode label is live or paper.",
+    );
+    out.sample(
+        "info",
+        &[("mode", if snapshot.live { "live" } else { "paper" })],
+        1.0,
+    );
+    out.single(
+        "cycles_total",
+        "counter",
+        "Quoting cycles run.",
+        snapshot.cycles as f64,
+    );
+    out.single(
+        "orders_placed_total",
+        "counter",
+        "Maker orders placed.",
+        snapshot.places as f64,
+    );
+    out.single(
+        "orders_cancelled_total",
+        "counter",
+        "Maker orders cancelled.",
+        snapshot.cancels as f64,
+    );
+    out.single(
+        "fills_total",
+        "counter",
+        "Maker fills observed.",
+        snapshot.fills as f64,
+    );
+    out.single(
+        "position",
+        "gauge",
+        "Signed position in base units.",
+        snapshot.position,
+    );
+    if let Some(mark) = snapshot.mark {
+        out.single("mark_price", "gauge", "Last mark price.", mark);
+    }
+
+    out.family("resting_quotes", "gauge", "Resting maker quotes per side.");
+    out.sample("resting_quotes", &[("side", "buy")], snapshot.bids as f64);
+    out.sample("resting_quotes", &[("side", "sell")], snapshot.asks as f64);
+
+    if let Some(performance) = snapshot.performance.as_ref() {
+        render_performance(&mut out, performance);
+    }
+    render_latency(&mut out, &snapshot.latency);
+
+    out.family(
+        "freezes_total",
+        "counter",
+        "Times quoting froze for recovery, by recovery target.",
+    );
+    for target in RecoveryTarget::ALL {
+        out.sample(
+            "freezes_total",
+            &[("target", target.label())],
+            snapshot.recovery_counts.freezes(target) as f64,
+        );
+    }
+    out.family(
+        "recoveries_total",
+        "counter",
+        "Completed recoveries (reconnect or reconcile), by recovery target.",
+    );
+    for target in RecoveryTarget::ALL {
+        out.sample(
+            "recoveries_total",
+            &[("target", target.label())],
+            snapshot.recovery_counts.recoveries(target) as f64,
+        );
+    }
+
+    out.family(
+        "market_data_mode",
+        "gauge",
+        "Market-data mode; 1 on the current mode.",
+    );
+    out.sample(
+        "market_data_mode",
+        &[("mode", "active")],
+        flag(!snapshot.market_data_paused),
+    );
+    out.sample(
+        "market_data_mode",
+        &[("mode", "paused")],
+        flag(snapshot.market_data_paused),
+    );
+    out.single(
+        "frozen",
+        "gauge",
+        "1 while quoting is frozen for recovery.",
+        flag(snapshot.frozen),
+    );
+    out.single(
+        "vol_breaker_halted",
+        "gauge",
+        "1 while the volatility breaker halts quoting.",
+        flag(snapshot.breaker_halted),
+    );
+    out.single(
+        "realized_vol_bps",
+        "gauge",
+        "Realized volatility the breaker compares against its threshold.",
+        snapshot.vol_bps,
+    );
+    out.family(
+        "guard_endangered",
+        "gauge",
+        "1 on the side the external price guard has pulled.",
+    );
+    for side in [OrderSide::Buy, OrderSide::Sell] {
+        out.sample(
+            "guard_endangered",
+            &[("side", side_label(side))],
+            flag(snapshot.guard_endangered == Some(side)),
+        );
+    }
+    out.single(
+        "wind_down",
+        "gauge",
+        "1 once the maker is flattening without new quotes.",
+        flag(snapshot.wind_down),
+    );
+    out.single(
+        "operator_paused",
+        "gauge",
+        "1 while `maker ctl pause` is in force.",
+        flag(snapshot.controls.paused),
+    );
+    out.single(
+        "operator_widen_bps",
+        "gauge",
+        "Extra spread added by `maker ctl widen`.",
+        snapshot.controls.widen_bps,
+    );
+
+    out.family(
+        "cycle_duration_seconds",
+        "histogram",
+        "Wall-clock duration of quoting cycles, planning through commit.",
+    );
+    for (bound, count) in CYCLE_BUCKETS_SECS.iter().zip(cycle_durations.buckets) {
+        out.sample(
+            "cycle_duration_seconds_bucket",
+            &[("le", &bound.to_string())],
+            count as f64,
+        );
+    }
+    out.sample(
+        "cycle_duration_seconds_bucket",
+        &[("le", "+Inf")],
+        cycle_durations.count as f64,
+    );
+    out.sample("cycle_duration_seconds_sum", &[], cycle_durations.sum_secs);
+    out.sample(
+        "cycle_duration_seconds_count",
+        &[],
+        cycle_durations.count as f64,
+    );
+    out.out
+}
+
+fn render_performance(out: &mut Exposition, performance: &PerformanceSummary) {
+    out.family(
+        "pnl_quote",
+        "gauge",
+        "Session PnL attribution in quote currency, by component.",
+    );
+    let mut components = vec![
+        ("gross_spread", performance.gross_spread_quote),
+        ("fees", performance.fee_quote),
+        ("rebates", performance.rebate_quote),
+        ("exit_cost", performance.exit_cost_quote),
+        ("inventory_mtm", performance.inventory_mtm_change_quote),
+        ("net", performance.net_pnl_quote),
+    ];
+    if performance.funding_available {
+        components.push(("funding", performance.funding_quote));
+    }
+    for (component, value) in components {
+        out.sample("pnl_quote", &[("component", component)], value);
+    }
+    out.single(
+        "pnl_complete",
+        "gauge",
+        "1 when every PnL component was observed (fees, rebates, funding).",
+        flag(performance.net_pnl_complete),
+    );
+    out.family(
+        "performance_fills_total",
+        "counter",
+        "Fills attributed by the performance ledger, by role.",
+    );
+    out.sample(
+        "performance_fills_total",
+        &[("role", "passive")],
+        performance.passive_fills as f64,
+    );
+    out.sample(
+        "performance_fills_total",
+        &[("role", "exit")],
+        performance.exit_fills as f64,
+    );
+    out.family(
+        "markout_bps",
+        "gauge",
+        "Quantity-weighted post-fill markout, by horizon.",
+    );
+    for markout in &performance.markouts {
+        if let Some(avg_bps) = markout.avg_bps {
+            out.sample(
+                "markout_bps",
+                &[("window_ms", &markout.window_ms.to_string())],
+                avg_bps,
+            );
+        }
+    }
+    out.single(
+        "two_sided_uptime_ratio",
+        "gauge",
+        "Share of observed market time with eligible quotes on both sides.",
+        performance.quote_time.two_sided_uptime_pct / 100.0,
+    );
+}
+
+fn render_latency(out: &mut Exposition, latency: &[LatencySummary]) {
+    if latency.is_empty() {
+        return;
+    }
+    out.family(
+        "order_requests_total",
+        "counter",
+        "Order requests by kind and outcome.",
+    );
+    for summary in latency {
+        let kind = kind_label(summary.kind);
+        for (outcome, count) in [
+            ("accepted", summary.accepted),
+            ("rejected", summary.rejected),
+            ("effective", summary.effective),
+            ("timeout", summary.timeout),
+            ("pending", summary.pending),
+        ] {
+            out.sample(
+                "order_requests_total",
+                &[("kind", kind), ("outcome", outcome)],
+                count as f64,
+            );
+        }
+    }
+    out.family(
+        "order_latency_ms",
+        "gauge",
+        "Order latency percentiles in milliseconds, by kind and stage.",
+    );
+    for summary in latency {
+        let kind = kind_label(summary.kind);
+        for (stage, metric) in [
+            ("write", summary.write),
+            ("ack", summary.ack),
+            ("effective", summary.effective_latency),
+            ("fill_after_cancel", summary.fill_after_cancel),
+        ] {
+            for (quantile, value) in [
+                ("0.5", metric.p50_ms),
+                ("0.95", metric.p95_ms),
+                ("0.99", metric.p99_ms),
+            ] {
+                if let Some(value) = value {
+                    out.sample(
+                        "order_latency_ms",
+                        &[("kind", kind), ("stage", stage), ("quantile", quantile)],
+                        value as f64,
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot() -> MetricsSnapshot {
+        MetricsSnapshot {
+            symbol: "BTC-USD".to_string(),
+            live: true,
+            cycles: 12,
+            fills: 3,
+            position: -0.02,
+            mark: Some(65_000.5),
+            bids: 2,
+            asks: 1,
+            market_data_paused: true,
+            guard_endangered: Some(OrderSide::Sell),
+            ..MetricsSnapshot::default()
+        }
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let mut histogram = CycleHistogram::default();
+        histogram.observe(Duration::from_millis(20));
+        histogram.observe(Duration::from_millis(300));
+        histogram.observe(Duration::from_secs(60));
+        let text = render(&snapshot(), &histogram);
+        for line in [
+            r#"standx_maker_cycle_duration_seconds_bucket{symbol="BTC-USD",le="0.01"} 0"#,
+            r#"standx_maker_cycle_duration_seconds_bucket{symbol="BTC-USD",le="0.025"} 1"#,
+            r#"standx_maker_cycle_duration_seconds_bucket{symbol="BTC-USD",le="0.5"} 2"#,
+            r#"standx_maker_cycle_duration_seconds_bucket{symbol="BTC-USD",le="10"} 2"#,
+            r#"standx_maker_cycle_duration_seconds_bucket{symbol="BTC-USD",le="+Inf"} 3"#,
+            r#"standx_maker_cycle_duration_seconds_count{symbol="BTC-USD"} 3"#,
+        ] {
+            assert!(
+                text.lines().any(|actual| actual == line),
+                "missing {line}\n{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn render_reports_state_and_omits_unavailable_series() {
+        let text = render(&snapshot(), &CycleHistogram::default());
+        for line in [
+            "# TYPE standx_maker_cycles_total counter",
+            r#"standx_maker_info{symbol="BTC-USD",mode="live"} 1"#,
+            r#"standx_maker_position{symbol="BTC-USD"} -0.02"#,
+            r#"standx_maker_mark_price{symbol="BTC-USD"} 65000.5"#,
+            r#"standx_maker_resting_quotes{symbol="BTC-USD",side="buy"} 2"#,
+            r#"standx_maker_resting_quotes{symbol="BTC-USD",side="sell"} 1"#,
+            r#"standx_maker_market_data_mode{symbol="BTC-USD",mode="paused"} 1"#,
+            r#"standx_maker_guard_endangered{symbol="BTC-USD",side="sell"} 1"#,
+            r#"standx_maker_recoveries_total{symbol="BTC-USD",target="market_data"} 0"#,
+        ] {
+            assert!(
+                text.lines().any(|actual| actual == line),
+                "missing {line}\n{text}"
+            );
+        }
+        // No performance ledger or latency tracker: no invented zeros.
+        assert!(!text.contains("standx_maker_pnl_quote"));
+        assert!(!text.contains("standx_maker_order_latency_ms"));
+        // Every sample belongs to a declared family.
+        let families: Vec<&str> = text
+            .lines()
+            .filter_map(|line| line.strip_prefix("# TYPE "))
+            .filter_map(|line| line.split(' ').next())
+            .collect();
+        for sample in text.lines().filter(|line| !line.starts_with('#')) {
+            let name = sample.split('{').next().expect("metric name");
+            assert!(
+                families.iter().any(|family| name.starts_with(family)),
+                "undeclared {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn labels_are_escaped() {
+        let snapshot = MetricsSnapshot {
+            symbol: "odd\"sym\\bol".to_string(),
+            ..MetricsSnapshot::default()
+        };
+        let text = render(&snapshot, &CycleHistogram::default());
+        assert!(text.contains(r#"standx_maker_position{symbol="odd\"sym\\bol"} 0"#));
+    }
+
+    #[tokio::test]
+    async fn serves_the_latest_snapshot_over_http() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("local addr");
+        let (published, scrapes) = watch::channel(None);
+        let server = tokio::spawn(serve(listener, scrapes));
+        let scrape = |path: &'static str| async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.expect("connect");
+            stream
+                .write_all(
+                    format!("GET {path} HTTP/1.1\r\nHost: x\r\nConnection: close\r\n\r\n")
+                        .as_bytes(),
+                )
+                .await
+                .expect("write");
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.expect("read");
+            response
+        };
+        assert!(scrape("/metrics").await.starts_with("HTTP/1.1 503"));
+        published.send_replace(Some((snapshot(), CycleHistogram::default())));
+        let response = scrape("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains("text/plain; version=0.0.4"));
+        assert!(response.contains(r#"standx_maker_fills_total{symbol="BTC-USD"} 3"#));
+        assert!(scrape("/").await.starts_with("HTTP/1.1 404"));
+        server.abort();
+    }
+}
@@ -16,28 +16,36 @@ use standx_sdk::order_response::OrderResponseStream;
 use std::collections::HashSet;
 use std::time::Duration;
 
+mod backtest;
 mod canary;
 mod config;
+mod control;
 mod cycle;
 mod external_feed;
 mod feed;
 mod ledger;
 mod market_data;
+mod metrics;
 mod model;
 mod notify;
 mod output;
 mod pipeline;
 mod process_lock;
 mod recovery;
+mod reload;
 mod replay;
 mod runtime;
 mod startup;
+mod state_file;
+mod status;
+mod sweep;
 #[cfg(test)]
 use runtime::apply_order_responses;
 #[cfg(test)]
 use startup::new_maker_rest_client;
 use startup::{run_startup, LiveSession, MakerStartup};
 
+pub(super) use control::ctl_output_schema;
 use cycle::maker_cycle;
 use feed::{fresh_ws_sample, market_snapshot, spawn_market_feed, ws_snapshot_issue};
 use market_data::{
@@ -49,9 +57,9 @@ use model::{is_maker_order, position_for_symbol, MakerExit};
 pub use model::{FailSafeShutdown, FAIL_SAFE_EXIT_CODE};
 #[cfg(test)]
 use notify::webhook_body;
+pub(super) use notify::MakerNotifier;
 use notify::{
-    token_expiry_level, MakerNotifier, PositionChange, RequestTimeoutNotice, RiskNotice,
-    TokenExpiryLevel,
+    token_expiry_level, PositionChange, RequestTimeoutNotice, RiskNotice, TokenExpiryLevel,
 };
 use pipeline::{
     CycleRequest, CycleState, LiveAccountPollState, OrderRequestDeadlines, TimedOutOrderRequest,
@@ -72,6 +80,7 @@ use standx_maker::ProjectionPendingPlace;
 use standx_sdk::models::{Order, OrderSide, Position, Trade};
 #[cfg(test)]
 use standx_sdk::order_response::OrderResponse;
+pub(super) use status::{maker_status, MakerStatus};
 
 // ============================================================================
 // Maker bot (SIP-5A community maker yield)
@@ -127,6 +136,7 @@ pub async fn handle_maker(
         MakerCommands::Run {
             symbol,
             maker_config,
+            watch_config,
             spread_bps,
             band_bps,
             size,
@@ -154,6 +164,11 @@ pub async fn handle_maker(
             alert_webhook_format,
             no_ws,
             live,
+            trace_out,
+            state_file,
+            resume,
+            control_socket,
+            metrics_listen,
             order_response_reconnect_attempts,
             order_response_reconnect_backoff,
             account_stream_reconnect_attempts,
@@ -163,122 +178,55 @@ pub async fn handle_maker(
             controlled_disconnect_after,
         } => {
             let file = config::load(maker_config.as_deref())?;
-            let selected_vol_window = vol_window.or(file.vol_window);
-            let selected_vol_window_secs = file.vol_window_secs;
-            if selected_vol_window.is_some() && selected_vol_window_secs.is_some() {
-                return Err(anyhow::anyhow!(
-                    "--vol-window conflicts with vol_window_secs in TOML; choose samples or seconds"
-                ));
-            }
-            let adaptive_spread = match file.adaptive_spread {
-                Some(config) => config.into_domain(adaptive_spread),
-                None if adaptive_spread.unwrap_or(false) => {
-                    return Err(anyhow::anyhow!(
-                        "--adaptive-spread requires an [adaptive_spread] TOML section"
-                    ));
-                }
-                None => maker::AdaptiveSpreadConfig::default(),
-            };
-            if adaptive_spread.enabled && selected_vol_window_secs.is_none() {
-                return Err(anyhow::anyhow!(
-                    "adaptive spread requires vol_window_secs in TOML"
-                ));
-            }
-            let size_skew = match file.size_skew {
-                Some(config) => config.into_domain(size_skew),
-                None if size_skew.is_some() => {
-                    return Err(anyhow::anyhow!(
-                        "--size-skew requires a [size_skew] TOML section"
-                    ));
-                }
-                None => maker::SizeSkewConfig::default(),
-            };
-            // Stage 3 v1 combined candidate: TOML-only switches (no CLI
-            // overrides) so frozen A/B configs stay the single source of truth.
-            let nonlinear_skew = file
-                .nonlinear_skew
-                .map(|config| config.into_domain())
-                .unwrap_or_default();
-            let external_guard_basis_half_life_secs = file
-                .external_guard
-                .as_ref()
-                .and_then(|config| config.basis_half_life_secs)
-                .unwrap_or(config::DEFAULT_GUARD_BASIS_HALF_LIFE_SECS);
-            let external_guard = file
-                .external_guard
-                .map(|config| config.into_domain())
-                .unwrap_or_default();
-            // Keep accepting the removed rolling-circuit knobs for one
-            // compatibility window so existing production commands/configs do
-            // not fail to parse. They deliberately do not enter MakerRunArgs.
-            let _legacy_recovery_circuit = (
-                recovery_incidents_per_window.or(file.recovery_incidents_per_window),
-                recovery_window_secs.or(file.recovery_window_secs),
-            );
-            runtime::run_maker(
-                symbol,
-                MakerRunArgs {
-                    spread_bps: choose(spread_bps, file.spread_bps, 5.0),
-                    band_bps: choose(band_bps, file.band_bps, 20.0),
-                    size: choose(size, file.size, 0.01),
-                    levels: choose(levels, file.levels, 1),
-                    level_step_bps: choose(level_step_bps, file.level_step_bps, 2.0),
-                    refresh_bps: choose(refresh_bps, file.refresh_bps, 3.0),
-                    interval: choose(interval, file.interval, 5),
-                    max_position: choose(max_position, file.max_position, 0.05),
-                    skew_bps: choose(skew_bps, file.skew_bps, 0.0),
-                    inventory_exit_pct: choose(inventory_exit_pct, file.inventory_exit_pct, 0.0),
-                    inventory_exit_qty: choose(inventory_exit_qty, file.inventory_exit_qty, 0.0),
-                    max_divergence_bps: choose(max_divergence_bps, file.max_divergence_bps, 25.0),
-                    vol_pause_bps: choose(vol_pause_bps, file.vol_pause_bps, 0.0),
-                    vol_window: selected_vol_window.unwrap_or(12),
-                    vol_window_secs: selected_vol_window_secs,
+            let args = MakerRunArgs {
+                maker_config,
+                watch_config,
+                overrides: MakerRunOverrides {
+                    spread_bps,
+                    band_bps,
+                    size,
+                    levels,
+                    level_step_bps,
+                    refresh_bps,
+                    interval,
+                    max_position,
+                    skew_bps,
+                    inventory_exit_pct,
+                    inventory_exit_qty,
+                    max_divergence_bps,
+                    vol_pause_bps,
+                    vol_window,
                     adaptive_spread,
                     size_skew,
-                    nonlinear_skew,
-                    external_guard,
-                    external_guard_basis_half_life_secs,
-                    stop_loss: choose(stop_loss, file.stop_loss, 0.0),
-                    alert_loss: choose(alert_loss, file.alert_loss, 0.0),
-                    alert_inventory_pct: choose(alert_inventory_pct, file.alert_inventory_pct, 0.0),
-                    alert_position_change_pct: choose(
-                        alert_position_change_pct,
-                        file.alert_position_change_pct,
-                        0.0,
-                    ),
-                    alert_uptime: choose(alert_uptime, file.alert_uptime, 0.0),
-                    alert_equity_below: choose(alert_equity_below, file.alert_equity_below, 0.0),
-                    alert_margin_below: choose(alert_margin_below, file.alert_margin_below, 0.0),
-                    alert_webhook,
-                    alert_webhook_format,
-                    no_ws: choose(no_ws, file.no_ws, false),
-                    live,
-                    order_response_reconnect_attempts: choose(
-                        order_response_reconnect_attempts,
-                        file.order_response_reconnect_attempts,
-                        3,
-                    ),
-                    order_response_reconnect_backoff: choose(
-                        order_response_reconnect_backoff,
-                        file.order_response_reconnect_backoff,
-                        2,
-                    ),
-                    account_stream_reconnect_attempts: choose(
-                        account_stream_reconnect_attempts,
-                        file.account_stream_reconnect_attempts,
-                        3,
-                    ),
-                    account_stream_reconnect_backoff: choose(
-                        account_stream_reconnect_backoff,
-                        file.account_stream_reconnect_backoff,
-                        2,
-                    ),
-                    controlled_disconnect_after,
-                    verbose,
+                    stop_loss,
+                    alert_loss,
+                    alert_inventory_pct,
+                    alert_position_change_pct,
+                    alert_uptime,
+                    alert_equity_below,
+                    alert_margin_below,
+                    no_ws,
+                    order_response_reconnect_attempts,
+                    order_response_reconnect_backoff,
+                    account_stream_reconnect_attempts,
+                    account_stream_reconnect_backoff,
+                    recovery_incidents_per_window,
+                    recovery_window_secs,
                 },
-                output_format,
-            )
-            .await
+                alert_webhook,
+                alert_webhook_format,
+                live,
+                trace_out,
+                state_file,
+                resume,
+                control_socket,
+                metrics_listen,
+                controlled_disconnect_after,
+                verbose,
+                ..MakerRunArgs::default()
+            }
+            .with_file(file)?;
+            runtime::run_maker(symbol, args, output_format).await
         }
         MakerCommands::WsCommandCanary {
             symbol,
@@ -299,7 +247,43 @@ pub async fn handle_maker(
             )
             .await
         }
-        MakerCommands::Replay { trace } => replay::run(&trace, output_format),
+        MakerCommands::Ctl { socket, command } => {
+            control::run_ctl(&socket, command, output_format).await
+        }
+        MakerCommands::Status { symbol } => status::run(symbol, output_format).await,
+        MakerCommands::Replay {
+            trace,
+            strategy_config,
+        } => replay::run(&trace, strategy_config.as_deref(), output_format),
+        MakerCommands::Backtest {
+            maker_config,
+            data,
+            simulation,
+        } => backtest::run(maker_config.as_deref(), &data, &simulation, output_format),
+        MakerCommands::Sweep {
+            data,
+            grid,
+            maker_config,
+            rank_by,
+            top,
+            jobs,
+            results_out,
+            best_out,
+            simulation,
+        } => sweep::run(
+            sweep::SweepRequest {
+                data,
+                grid,
+                maker_config,
+                rank_by,
+                top,
+                jobs,
+                results_out,
+                best_out,
+                simulation,
+            },
+            output_format,
+        ),
     }
 }
 
@@ -307,7 +291,47 @@ fn choose<T: Copy>(cli: Option<T>, file: Option<T>, default: T) -> T {
     cli.or(file).unwrap_or(default)
 }
 
+/// Strategy values given as `maker run` flags. Each one beats the TOML, at
+/// startup and on every config reload.
+#[derive(Clone, Debug, Default)]
+struct MakerRunOverrides {
+    spread_bps: Option<f64>,
+    band_bps: Option<f64>,
+    size: Option<f64>,
+    levels: Option<u32>,
+    level_step_bps: Option<f64>,
+    refresh_bps: Option<f64>,
+    interval: Option<u64>,
+    max_position: Option<f64>,
+    skew_bps: Option<f64>,
+    inventory_exit_pct: Option<f64>,
+    inventory_exit_qty: Option<f64>,
+    max_divergence_bps: Option<f64>,
+    vol_pause_bps: Option<f64>,
+    vol_window: Option<u32>,
+    adaptive_spread: Option<bool>,
+    size_skew: Option<bool>,
+    stop_loss: Option<f64>,
+    alert_loss: Option<f64>,
+    alert_inventory_pct: Option<f64>,
+    alert_position_change_pct: Option<f64>,
+    alert_uptime: Option<f64>,
+    alert_equity_below: Option<f64>,
+    alert_margin_below: Option<f64>,
+    no_ws: Option<bool>,
+    order_response_reconnect_attempts: Option<u32>,
+    order_response_reconnect_backoff: Option<u64>,
+    account_stream_reconnect_attempts: Option<u32>,
+    account_stream_reconnect_backoff: Option<u64>,
+    recovery_incidents_per_window: Option<u32>,
+    recovery_window_secs: Option<u64>,
+}
+
+#[derive(Clone, Default)]
 struct MakerRunArgs {
+    maker_config: Option<std::path::PathBuf>,
+    watch_config: bool,
+    overrides: MakerRunOverrides,
     spread_bps: f64,
     band_bps: f64,
     size: f64,
@@ -326,6 +350,8 @@ struct MakerRunArgs {
     adaptive_spread: maker::AdaptiveSpreadConfig,
     size_skew: maker::SizeSkewConfig,
     nonlinear_skew: maker::NonlinearSkewConfig,
+    strategy: maker::StrategyConfig,
+    fair_value: maker::FairValueConfig,
     external_guard: maker::GuardConfig,
     external_guard_basis_half_life_secs: u64,
     stop_loss: f64,
@@ -339,6 +365,11 @@ struct MakerRunArgs {
     alert_webhook_format: AlertWebhookFormat,
     no_ws: bool,
     live: bool,
+    trace_out: Option<std::path::PathBuf>,
+    state_file: Option<std::path::PathBuf>,
+    resume: bool,
+    control_socket: Option<std::path::PathBuf>,
+    metrics_listen: Option<std::net::SocketAddr>,
     order_response_reconnect_attempts: u32,
     order_response_reconnect_backoff: u64,
     account_stream_reconnect_attempts: u32,
@@ -347,6 +378,133 @@ struct MakerRunArgs {
     verbose: bool,
 }
 
+impl MakerRunArgs {
+    /// Resolve every TOML-backed setting from `file`: the flag, else the
+    /// file, else the default. Invocation-only fields (`--live`, the webhook,
+    /// state and trace paths) are kept, so a reload reuses this unchanged.
+    fn with_file(self, file: config::MakerFileConfig) -> Result<Self> {
+        let cli = &self.overrides;
+        let selected_vol_window = cli.vol_window.or(file.vol_window);
+        let selected_vol_window_secs = file.vol_window_secs;
+        if selected_vol_window.is_some() && selected_vol_window_secs.is_some() {
+            return Err(anyhow::anyhow!(
+                "--vol-window conflicts with vol_window_secs in TOML; choose samples or seconds"
+            ));
+        }
+        let adaptive_spread = match file.adaptive_spread {
+            Some(config) => config.into_domain(cli.adaptive_spread),
+            None if cli.adaptive_spread.unwrap_or(false) => {
+                return Err(anyhow::anyhow!(
+                    "--adaptive-spread requires an [adaptive_spread] TOML section"
+                ));
+            }
+            None => maker::AdaptiveSpreadConfig::default(),
+        };
+        if adaptive_spread.enabled && selected_vol_window_secs.is_none() {
+            return Err(anyhow::anyhow!(
+                "adaptive spread requires vol_window_secs in TOML"
+            ));
+        }
+        let size_skew = match file.size_skew {
+            Some(config) => config.into_domain(cli.size_skew),
+            None if cli.size_skew.is_some() => {
+                return Err(anyhow::anyhow!(
+                    "--size-skew requires a [size_skew] TOML section"
+                ));
+            }
+            None => maker::SizeSkewConfig::default(),
+        };
+        // Stage 3 v1 combined candidate: TOML-only switches (no CLI
+        // overrides) so frozen A/B configs stay the single source of truth.
+        let nonlinear_skew = file
+            .nonlinear_skew
+            .map(|config| config.into_domain())
+            .unwrap_or_default();
+        let strategy = file
+            .strategy
+            .map(|config| config.into_domain())
+            .unwrap_or_default();
+        let fair_value = file
+            .fair_value
+            .map(|config| config.into_domain())
+            .unwrap_or_default();
+        let external_guard_basis_half_life_secs = file
+            .external_guard
+            .as_ref()
+            .and_then(|config| config.basis_half_life_secs)
+            .unwrap_or(config::DEFAULT_GUARD_BASIS_HALF_LIFE_SECS);
+        let external_guard = file
+            .external_guard
+            .map(|config| config.into_domain())
+            .unwrap_or_default();
+        // Keep accepting the removed rolling-circuit knobs for one
+        // compatibility window so existing production commands/configs do
+        // not fail to parse. They deliberately do not enter MakerRunArgs.
+        let _legacy_recovery_circuit = (
+            cli.recovery_incidents_per_window
+                .or(file.recovery_incidents_per_window),
+            cli.recovery_window_secs.or(file.recovery_window_secs),
+        );
+        Ok(Self {
+            spread_bps: choose(cli.spread_bps, file.spread_bps, 5.0),
+            band_bps: choose(cli.band_bps, file.band_bps, 20.0),
+            size: choose(cli.size, file.size, 0.01),
+            levels: choose(cli.levels, file.levels, 1),
+            level_step_bps: choose(cli.level_step_bps, file.level_step_bps, 2.0),
+            refresh_bps: choose(cli.refresh_bps, file.refresh_bps, 3.0),
+            interval: choose(cli.interval, file.interval, 5),
+            max_position: choose(cli.max_position, file.max_position, 0.05),
+            skew_bps: choose(cli.skew_bps, file.skew_bps, 0.0),
+            inventory_exit_pct: choose(cli.inventory_exit_pct, file.inventory_exit_pct, 0.0),
+            inventory_exit_qty: choose(cli.inventory_exit_qty, file.inventory_exit_qty, 0.0),
+            max_divergence_bps: choose(cli.max_divergence_bps, file.max_divergence_bps, 25.0),
+            vol_pause_bps: choose(cli.vol_pause_bps, file.vol_pause_bps, 0.0),
+            vol_window: selected_vol_window.unwrap_or(12),
+            vol_window_secs: selected_vol_window_secs,
+            adaptive_spread,
+            size_skew,
+            nonlinear_skew,
+            strategy,
+            fair_value,
+            external_guard,
+            external_guard_basis_half_life_secs,
+            stop_loss: choose(cli.stop_loss, file.stop_loss, 0.0),
+            alert_loss: choose(cli.alert_loss, file.alert_loss, 0.0),
+            alert_inventory_pct: choose(cli.alert_inventory_pct, file.alert_inventory_pct, 0.0),
+            alert_position_change_pct: choose(
+                cli.alert_position_change_pct,
+                file.alert_position_change_pct,
+                0.0,
+            ),
+            alert_uptime: choose(cli.alert_uptime, file.alert_uptime, 0.0),
+            alert_equity_below: choose(cli.alert_equity_below, file.alert_equity_below, 0.0),
+            alert_margin_below: choose(cli.alert_margin_below, file.alert_margin_below, 0.0),
+            no_ws: choose(cli.no_ws, file.no_ws, false),
+            order_response_reconnect_attempts: choose(
+                cli.order_response_reconnect_attempts,
+                file.order_response_reconnect_attempts,
+                3,
+            ),
+            order_response_reconnect_backoff: choose(
+                cli.order_response_reconnect_backoff,
+                file.order_response_reconnect_backoff,
+                2,
+            ),
+            account_stream_reconnect_attempts: choose(
+                cli.account_stream_reconnect_attempts,
+                file.account_stream_reconnect_attempts,
+                3,
+            ),
+            account_stream_reconnect_backoff: choose(
+                cli.account_stream_reconnect_backoff,
+                file.account_stream_reconnect_backoff,
+                2,
+            ),
+            ..self
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
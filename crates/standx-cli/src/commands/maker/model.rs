@@ -36,6 +36,8 @@ impl std::error::Error for FailSafeShutdown {}
 #[derive(Debug)]
 pub(super) enum MakerExit {
     CtrlC,
+    /// `stop` over the control socket; graceful like Ctrl+C.
+    Operator,
     OrderResponse(String),
     ConsecutiveErrors(String),
     PositionReconciliation(String),
@@ -48,6 +50,7 @@ impl MakerExit {
     pub(super) fn lifecycle_reason(&self) -> String {
         match self {
             Self::CtrlC => "Ctrl+C".to_string(),
+            Self::Operator => "operator stop (control socket)".to_string(),
             Self::OrderResponse(error) => {
                 format!("fail-safe: order-response stream unavailable: {error}")
             }
@@ -71,7 +74,7 @@ impl MakerExit {
 
     pub(super) fn terminal_error(&self) -> Option<String> {
         match self {
-            Self::CtrlC => None,
+            Self::CtrlC | Self::Operator => None,
             Self::OrderResponse(error) => Some(format!(
                 "maker stopped immediately (fail-safe): order-response stream unavailable: {error}"
             )),
@@ -98,6 +101,7 @@ impl From<standx_maker::RuntimeStopReason> for MakerExit {
     fn from(reason: standx_maker::RuntimeStopReason) -> Self {
         match reason {
             standx_maker::RuntimeStopReason::CtrlC => Self::CtrlC,
+            standx_maker::RuntimeStopReason::Operator => Self::Operator,
             standx_maker::RuntimeStopReason::OrderResponse(detail) => Self::OrderResponse(detail),
             standx_maker::RuntimeStopReason::PositionReconciliation(detail) => {
                 Self::PositionReconciliation(detail)
@@ -268,6 +272,10 @@ mod exit_mapping_tests {
             MakerExit::from(RuntimeStopReason::CtrlC),
             MakerExit::CtrlC
         ));
+        assert!(matches!(
+            MakerExit::from(RuntimeStopReason::Operator),
+            MakerExit::Operator
+        ));
         assert!(matches!(
             MakerExit::from(RuntimeStopReason::OrderResponse("boom".to_string())),
             MakerExit::OrderResponse(detail) if detail == "boom"
@@ -321,10 +329,12 @@ mod exit_mapping_tests {
     }
 
     /// Every fail-safe exit must surface a terminal error (only a clean
-    /// Ctrl+C stop is silent) so supervisors always see a reason on exit 75.
+    /// Ctrl+C or operator stop is silent) so supervisors always see a reason
+    /// on exit 75.
     #[test]
-    fn only_ctrl_c_exits_without_a_terminal_error() {
+    fn only_graceful_stops_exit_without_a_terminal_error() {
         assert!(MakerExit::CtrlC.terminal_error().is_none());
+        assert!(MakerExit::Operator.terminal_error().is_none());
         for exit in [
             MakerExit::OrderResponse("boom".to_string()),
             MakerExit::ConsecutiveErrors("boom".to_string()),
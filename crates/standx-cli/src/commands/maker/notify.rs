@@ -77,8 +77,10 @@ fn risk_kind_descriptor(kind: PositionRiskKind) -> (&'static str, &'static str)
     }
 }
 
+/// Alert fan-out shared by the maker and `standx watch`: stderr or JSON
+/// stdout, plus the optional chat webhook.
 #[derive(Clone)]
-pub(super) struct MakerNotifier {
+pub(in crate::commands) struct MakerNotifier {
     output_format: OutputFormat,
     http: Option<reqwest::Client>,
     webhook_url: Option<String>,
@@ -86,7 +88,7 @@ pub(super) struct MakerNotifier {
 }
 
 impl MakerNotifier {
-    pub(super) fn new(
+    pub(in crate::commands) fn new(
         output_format: OutputFormat,
         webhook_url: Option<String>,
         webhook_format: AlertWebhookFormat,
@@ -111,10 +113,10 @@ impl MakerNotifier {
         if self.output_format == OutputFormat::Json {
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "ts": ts, "symbol": symbol, "action": "lifecycle",
                     "event": event, "message": text,
-                })
+                }))
             );
         }
         let raw = serde_json::json!({
@@ -153,7 +155,7 @@ impl MakerNotifier {
             "observed_position": notice.observed,
         });
         if self.output_format == OutputFormat::Json {
-            println!("{raw}");
+            println!("{}", crate::output::format_event(&raw));
         } else {
             eprintln!(
                 "⚠️  risk [{}/{}] {}: {}",
@@ -171,7 +173,7 @@ impl MakerNotifier {
         let ts = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
         let (text, raw) = request_timeout_payload(&notice, &ts);
         if self.output_format == OutputFormat::Json {
-            println!("{raw}");
+            println!("{}", crate::output::format_event(&raw));
         } else {
             eprintln!(
                 "⚠️  risk [warning/order_request_timeout] {}: {}",
@@ -224,7 +226,12 @@ impl MakerNotifier {
         .await;
     }
 
-    pub(super) async fn alert(&self, alert: &Alert, symbol: &str, await_delivery: bool) {
+    pub(in crate::commands) async fn alert(
+        &self,
+        alert: &Alert,
+        symbol: &str,
+        await_delivery: bool,
+    ) {
         let ts = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
         let label = if alert.firing {
             "🚨 ALERT"
@@ -234,11 +241,11 @@ impl MakerNotifier {
         if self.output_format == OutputFormat::Json {
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "ts": ts, "symbol": symbol, "action": "alert",
                     "kind": alert.kind, "firing": alert.firing,
                     "message": alert.message,
-                })
+                }))
             );
         } else {
             eprintln!("{} [{}] {} — {}", label, alert.kind, symbol, alert.message);
@@ -30,7 +30,7 @@ pub(super) fn emit_account_event_lag(
     let event_time_ms = parse_event_time_ms(event_time);
     println!(
         "{}",
-        serde_json::json!({
+        crate::output::format_event(&serde_json::json!({
             "action": "account_event_lag",
             "symbol": symbol,
             "cycle": cycle,
@@ -43,7 +43,7 @@ pub(super) fn emit_account_event_lag(
                 received.timestamp_millis().saturating_sub(event_time_ms)
             }),
             "available": event_time_ms.is_some(),
-        })
+        }))
     );
 }
 
@@ -71,7 +71,7 @@ pub(super) fn emit_order_latency(
             let context = &request.context;
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "action": "order_latency",
                     "request_id": context.request_id,
                     "kind": latency_kind(context.kind),
@@ -100,12 +100,15 @@ pub(super) fn emit_order_latency(
                     "timeout_phase": request.timeout_phase.map(|phase| phase.label()),
                     "timeout_ms": request.timeout_ms,
                     "outcome": request.outcome.map(latency_outcome),
-                })
+                }))
             );
         }
         for kind in [LatencyRequestKind::Place, LatencyRequestKind::Cancel] {
             let summary = tracker.summary(kind);
-            println!("{}", latency_summary_json(symbol, &summary));
+            println!(
+                "{}",
+                crate::output::format_event(&latency_summary_json(symbol, &summary))
+            );
         }
     } else if output_format != OutputFormat::Quiet {
         for kind in [LatencyRequestKind::Place, LatencyRequestKind::Cancel] {
@@ -232,9 +235,11 @@ pub(super) struct CycleOutput<'a> {
     pub(super) guard_decision: &'a maker::GuardDecision,
     /// Divergence-basis EMA the guard's excess is measured against.
     pub(super) external_basis_bps: Option<f64>,
-    /// Realized quote-center shift in bps (mark vs plan ref_center); covers
+    /// Realized quote-center shift in bps (anchor vs plan ref_center); covers
     /// linear and nonlinear skew alike. Telemetry only.
     pub(super) skew_shift_bps: f64,
+    /// This cycle's fair-value anchor; `None` when disabled or not planned.
+    pub(super) fair_value: Option<&'a maker::FairValue>,
     pub(super) cfg: &'a MakerConfig,
     pub(super) performance: Option<&'a maker::PerformanceSummary>,
 }
@@ -263,6 +268,7 @@ pub(super) fn emit_maker_cycle(output: CycleOutput<'_>) {
         guard_decision,
         external_basis_bps,
         skew_shift_bps,
+        fair_value,
         cfg,
         performance,
     } = output;
@@ -287,7 +293,7 @@ pub(super) fn emit_maker_cycle(output: CycleOutput<'_>) {
             for fill in fills {
                 println!(
                     "{}",
-                    serde_json::json!({
+                    crate::output::format_event(&serde_json::json!({
                         "ts": ts, "cycle": cycle, "mode": mode, "symbol": symbol,
                         "action": "fill", "side": fill.side,
                         "price": format_decimals(fill.price, cfg.price_decimals),
@@ -304,7 +310,7 @@ pub(super) fn emit_maker_cycle(output: CycleOutput<'_>) {
                         },
                         "fee_quote": fill.costs.map(|costs| costs.fee_quote),
                         "rebate_quote": fill.costs.map(|costs| costs.rebate_quote),
-                    })
+                    }))
                 );
             }
             for a in actions {
@@ -344,42 +350,45 @@ pub(super) fn emit_maker_cycle(output: CycleOutput<'_>) {
                         "drift_bps": (drift_bps * 100.0).round() / 100.0,
                     }),
                 };
-                println!("{}", obj);
+                println!("{}", crate::output::format_event(&obj));
             }
             println!(
                 "{}",
-                with_guard_fields(
-                    with_size_skew_fields(
-                        with_spread_fields(
-                            serde_json::json!({
-                                "ts": ts, "cycle": cycle, "mode": mode, "symbol": symbol,
-                                "action": "cycle_summary",
-                                "mark": format_decimals(mark, cfg.price_decimals),
-                                "best_bid": best_bid, "best_ask": best_ask,
-                                "market_source": market_source,
-                                "market_fallback_reason": market_fallback_reason,
-                                "ws_snapshot": ws_snapshot.map(ws_snapshot_json),
-                                "position": position,
-                                "starting_position": starting_position,
-                                "account": account.map(account_json),
-                                "holds": holds, "places": places, "cancels": cancels,
-                                "fills": fills.len(),
-                                "pnl": (pnl * 1e6).round() / 1e6,
-                                "fills_total": stats.fills(),
-                                "uptime_pct": (stats.uptime_pct() * 10.0).round() / 10.0,
-                                "avg_capture_bps": (stats.avg_spread_capture_bps() * 100.0).round() / 100.0,
-                                "performance": performance.map(performance_json),
-                                "halted": halt_vol_bps.is_some(),
-                                "vol_bps": halt_vol_bps.map(|v| (v * 100.0).round() / 100.0),
-                            }),
-                            spread_decision,
+                crate::output::format_event(&with_fair_value_fields(
+                    with_guard_fields(
+                        with_size_skew_fields(
+                            with_spread_fields(
+                                serde_json::json!({
+                                    "ts": ts, "cycle": cycle, "mode": mode, "symbol": symbol,
+                                    "action": "cycle_summary",
+                                    "mark": format_decimals(mark, cfg.price_decimals),
+                                    "best_bid": best_bid, "best_ask": best_ask,
+                                    "market_source": market_source,
+                                    "market_fallback_reason": market_fallback_reason,
+                                    "ws_snapshot": ws_snapshot.map(ws_snapshot_json),
+                                    "position": position,
+                                    "starting_position": starting_position,
+                                    "account": account.map(account_json),
+                                    "holds": holds, "places": places, "cancels": cancels,
+                                    "fills": fills.len(),
+                                    "pnl": (pnl * 1e6).round() / 1e6,
+                                    "fills_total": stats.fills(),
+                                    "uptime_pct": (stats.uptime_pct() * 10.0).round() / 10.0,
+                                    "avg_capture_bps": (stats.avg_spread_capture_bps() * 100.0).round() / 100.0,
+                                    "performance": performance.map(performance_json),
+                                    "halted": halt_vol_bps.is_some(),
+                                    "vol_bps": halt_vol_bps.map(|v| (v * 100.0).round() / 100.0),
+                                }),
+                                spread_decision,
+                            ),
+                            size_skew_decision,
                         ),
-                        size_skew_decision,
+                        guard_decision,
+                        external_basis_bps,
+                        skew_shift_bps,
                     ),
-                    guard_decision,
-                    external_basis_bps,
-                    skew_shift_bps,
-                )
+                    fair_value,
+                ))
             );
         }
         OutputFormat::Quiet => {
@@ -572,6 +581,44 @@ fn with_guard_fields(
     summary
 }
 
+/// Fair-value anchor and its book inputs, so markouts can be compared with
+/// and without it. Disabled serializes nulls, never drops the keys.
+fn with_fair_value_fields(
+    mut summary: serde_json::Value,
+    fair_value: Option<&maker::FairValue>,
+) -> serde_json::Value {
+    let object = summary
+        .as_object_mut()
+        .expect("cycle summary JSON must be an object");
+    object.insert(
+        "fair_value".to_string(),
+        serde_json::json!(fair_value.map(|fair| fair.price)),
+    );
+    object.insert(
+        "fair_value_deviation_bps".to_string(),
+        serde_json::json!(fair_value.map(|fair| (fair.deviation_bps * 100.0).round() / 100.0)),
+    );
+    object.insert(
+        "fair_value_capped".to_string(),
+        serde_json::json!(fair_value.map(|fair| fair.capped)),
+    );
+    object.insert(
+        "book_mid".to_string(),
+        serde_json::json!(fair_value.and_then(|fair| fair.mid)),
+    );
+    object.insert(
+        "microprice".to_string(),
+        serde_json::json!(fair_value.and_then(|fair| fair.microprice)),
+    );
+    object.insert(
+        "book_imbalance".to_string(),
+        serde_json::json!(fair_value
+            .and_then(|fair| fair.imbalance)
+            .map(|imbalance| (imbalance * 1e4).round() / 1e4)),
+    );
+    summary
+}
+
 fn with_size_skew_fields(
     mut summary: serde_json::Value,
     decision: &maker::SizeSkewDecision,
@@ -602,7 +649,7 @@ fn with_size_skew_fields(
     summary
 }
 
-fn performance_json(summary: &maker::PerformanceSummary) -> serde_json::Value {
+pub(super) fn performance_json(summary: &maker::PerformanceSummary) -> serde_json::Value {
     serde_json::json!({
         "passive_fills": summary.passive_fills,
         "passive_qty": summary.passive_qty,
@@ -653,7 +700,7 @@ pub(super) fn emit_performance_summary(
             );
             object.insert("symbol".to_string(), serde_json::json!(symbol));
         }
-        println!("{value}");
+        println!("{}", crate::output::format_event(&value));
     } else if output_format != OutputFormat::Quiet {
         println!(
             "Performance: passive={} exit={} net_pnl={:.6} time-weighted uptime={:.2}%",
@@ -705,7 +752,7 @@ pub(super) fn emit_guard_transition(
         OutputFormat::Json => {
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                     "cycle": cycle, "symbol": symbol,
                     "action": "external_guard",
@@ -713,7 +760,7 @@ pub(super) fn emit_guard_transition(
                     "side": decision.endangered,
                     "divergence_bps": decision.divergence_bps
                         .map(|d| (d * 100.0).round() / 100.0),
-                })
+                }))
             );
         }
         OutputFormat::Quiet => {}
@@ -760,13 +807,13 @@ pub(super) fn log_maker_event(event: MakerLogEvent<'_>) {
         OutputFormat::Json => {
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                     "cycle": cycle, "mode": "live", "symbol": symbol,
                     "action": action, "side": side, "level": level,
                     "price": format_decimals(price, price_decimals),
                     "detail": detail,
-                })
+                }))
             );
         }
         _ => {
@@ -791,7 +838,7 @@ pub(super) fn emit_live_fill(
     match output_format {
         OutputFormat::Json => println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "cycle": cycle,
@@ -811,7 +858,7 @@ pub(super) fn emit_live_fill(
                 },
                 "fee_quote": fill.costs.map(|costs| costs.fee_quote),
                 "rebate_quote": fill.costs.map(|costs| costs.rebate_quote),
-            })
+            }))
         ),
         _ => eprintln!(
             "⚡ account fill {:?} {} @ {} (order {})",
@@ -835,7 +882,7 @@ pub(super) fn emit_reconciliation_state(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "cycle": cycle,
@@ -844,7 +891,7 @@ pub(super) fn emit_reconciliation_state(
                 "cause": cause,
                 "expected_position": expected,
                 "observed_position": observed,
-            })
+            }))
         );
     } else {
         eprintln!(
@@ -863,7 +910,7 @@ pub(super) fn emit_stop_loss_triggered(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "cycle": cycle,
@@ -871,7 +918,7 @@ pub(super) fn emit_stop_loss_triggered(
                 "event": "triggered",
                 "pnl": pnl,
                 "stop_loss": stop_loss,
-            })
+            }))
         );
     } else {
         eprintln!(
@@ -893,7 +940,7 @@ pub(super) fn emit_reconciliation_snapshot_error(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "cycle": cycle,
@@ -901,7 +948,7 @@ pub(super) fn emit_reconciliation_snapshot_error(
                 "event": "snapshot_failed",
                 "severity": "warning",
                 "message": message,
-            })
+            }))
         );
     } else {
         eprintln!("⚠️  bounded position reconciliation snapshot failed: {message}");
@@ -919,7 +966,7 @@ pub(super) fn emit_ledger_sync(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "action": "ledger_sync",
@@ -933,12 +980,12 @@ pub(super) fn emit_ledger_sync(
                 "history_order_limit": ORDER_HISTORY_LIMIT,
                 "history_trade_limit": TRADE_LOOKBACK_LIMIT,
                 "current_run_fills": 0,
-            })
+            }))
         );
         if starting_position.abs() > f64::EPSILON {
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                     "symbol": symbol,
                     "action": "inventory_adopted",
@@ -946,7 +993,7 @@ pub(super) fn emit_ledger_sync(
                     "starting_position": starting_position,
                     "baseline_mark": baseline_mark,
                     "pnl_baseline": 0.0,
-                })
+                }))
             );
         }
     } else {
@@ -956,6 +1003,152 @@ pub(super) fn emit_ledger_sync(
     }
 }
 
+pub(super) fn emit_session_resumed(
+    output_format: OutputFormat,
+    symbol: &str,
+    state: &super::state_file::StateFile,
+    position: f64,
+) {
+    let downtime_ms = chrono::Utc::now()
+        .timestamp_millis()
+        .saturating_sub(state.saved_at_ms);
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            crate::output::format_event(&serde_json::json!({
+                "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "symbol": symbol,
+                "action": "session_resumed",
+                "event": "complete",
+                "run_order_prefix": state.run_order_prefix,
+                "session_started_at": state.session_started_at,
+                "saved_at_ms": state.saved_at_ms,
+                "downtime_ms": downtime_ms,
+                "cycle": state.counters.cycle,
+                "current_run_fills": state.counters.total_fills,
+                "position": position,
+            }))
+        );
+    } else {
+        eprintln!(
+            "✅ maker session resumed: prefix={}, cycle={}, fills={}, position={position:+.8}, downtime={:.1}s",
+            state.run_order_prefix,
+            state.counters.cycle,
+            state.counters.total_fills,
+            downtime_ms as f64 / 1_000.0
+        );
+    }
+}
+
+/// Report a config reload: what changed, and whether it was applied.
+pub(super) fn emit_config_reload(
+    output_format: OutputFormat,
+    symbol: &str,
+    cycle: u64,
+    trigger: super::reload::ReloadTrigger,
+    path: &std::path::Path,
+    plan: &super::reload::ReloadPlan,
+) {
+    use super::reload::ReloadPlan;
+    let (event, changes, error) = match plan {
+        ReloadPlan::Invalid(error) => ("invalid", &[][..], Some(format!("{error:#}"))),
+        ReloadPlan::Unchanged => ("unchanged", &[][..], None),
+        ReloadPlan::Rejected(changes) => ("rejected", changes.as_slice(), None),
+        ReloadPlan::Apply { changes, .. } => ("applied", changes.as_slice(), None),
+    };
+    let restart_only: Vec<&str> = changes
+        .iter()
+        .filter(|change| change.restart_only)
+        .map(|change| change.field)
+        .collect();
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            crate::output::format_event(&serde_json::json!({
+                "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "symbol": symbol,
+                "action": "config_reload",
+                "event": event,
+                "cycle": cycle,
+                "source": trigger.as_str(),
+                "path": path.display().to_string(),
+                "changes": changes
+                    .iter()
+                    .map(|change| serde_json::json!({
+                        "field": change.field,
+                        "from": change.from,
+                        "to": change.to,
+                        "restart_only": change.restart_only,
+                    }))
+                    .collect::<Vec<_>>(),
+                "restart_only_fields": restart_only,
+                "error": error,
+            }))
+        );
+        return;
+    }
+    let source = trigger.as_str();
+    match plan {
+        ReloadPlan::Invalid(_) => eprintln!(
+            "⚠️  maker config reload failed ({source}): {}; keeping the running config",
+            error.unwrap_or_default()
+        ),
+        ReloadPlan::Unchanged => eprintln!("ℹ️  maker config reload ({source}): no changes"),
+        ReloadPlan::Rejected(_) => eprintln!(
+            "⚠️  maker config reload rejected ({source}): restart-only settings changed ({}); nothing applied",
+            restart_only.join(", ")
+        ),
+        ReloadPlan::Apply { .. } => eprintln!(
+            "🔄 maker config reloaded ({source}) at cycle {cycle}: {}",
+            changes
+                .iter()
+                .map(|change| format!("{} {} → {}", change.field, change.from, change.to))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+pub(super) fn emit_control(
+    output_format: OutputFormat,
+    symbol: &str,
+    cycle: u64,
+    request: super::control::ControlRequest,
+    event: &str,
+    controls: maker::OperatorControls,
+    error: Option<&str>,
+) {
+    if output_format == OutputFormat::Json {
+        println!(
+            "{}",
+            crate::output::format_event(&serde_json::json!({
+                "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                "symbol": symbol,
+                "action": "control",
+                "event": event,
+                "cycle": cycle,
+                "command": request.label(),
+                "value": request.value(),
+                "controls": super::control::controls_json(controls),
+                "error": error,
+            }))
+        );
+        return;
+    }
+    let command = match request.value() {
+        Some(value) => format!("{} {value}", request.label()),
+        None => request.label().to_string(),
+    };
+    match error {
+        Some(error) => eprintln!("⚠️  maker control `{command}` rejected: {error}"),
+        None if event == "status" => {}
+        None => eprintln!(
+            "🎛️  maker control `{command}` applied at cycle {cycle}; overrides: {}",
+            super::control::describe_controls(&super::control::controls_json(controls))
+        ),
+    }
+}
+
 pub(super) fn emit_startup_rejected(
     output_format: OutputFormat,
     symbol: &str,
@@ -968,7 +1161,7 @@ pub(super) fn emit_startup_rejected(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "action": "startup_rejected",
@@ -976,7 +1169,7 @@ pub(super) fn emit_startup_rejected(
                 "position": position,
                 "max_position": max_position,
                 "message": message,
-            })
+            }))
         );
     } else {
         eprintln!("⚠️  {message}");
@@ -1029,7 +1222,7 @@ pub(super) fn emit_cycle_skip(
                 fields.insert("reason".into(), "missing_touch".into());
             }
         }
-        println!("{event}");
+        println!("{}", crate::output::format_event(&event));
         return;
     }
     match skip {
@@ -1256,4 +1449,38 @@ mod tests {
         assert!(idle["external_basis_bps"].is_null());
         assert_eq!(idle["skew_shift_bps"], 0.0);
     }
+
+    #[test]
+    fn cycle_summary_fair_value_fields_report_the_anchor_and_book_inputs() {
+        let fair_value = maker::FairValue {
+            price: 100.005,
+            mid: Some(100.0),
+            microprice: Some(100.01),
+            imbalance: Some(0.333333),
+            deviation_bps: 0.4999,
+            capped: false,
+        };
+        let json = with_fair_value_fields(
+            serde_json::json!({"action": "cycle_summary"}),
+            Some(&fair_value),
+        );
+        assert_eq!(json["fair_value"], 100.005);
+        assert_eq!(json["fair_value_deviation_bps"], 0.5);
+        assert_eq!(json["fair_value_capped"], false);
+        assert_eq!(json["book_mid"], 100.0);
+        assert_eq!(json["microprice"], 100.01);
+        assert_eq!(json["book_imbalance"], 0.3333);
+
+        let disabled = with_fair_value_fields(serde_json::json!({}), None);
+        for key in [
+            "fair_value",
+            "fair_value_deviation_bps",
+            "fair_value_capped",
+            "book_mid",
+            "microprice",
+            "book_imbalance",
+        ] {
+            assert!(disabled[key].is_null(), "{key}");
+        }
+    }
 }
@@ -1,9 +1,10 @@
 use super::feed::WsSnapshotDiagnostics;
+use super::replay::TraceWriter;
 use super::{ORDER_HISTORY_LIMIT, TRADE_LOOKBACK_LIMIT};
 use crate::cli::OutputFormat;
 use anyhow::Result;
 use standx_maker::{
-    MakerAccountProjection, MakerConfig, MakerLedger, MakerStats, MarketDataMode,
+    MakerAccountProjection, MakerConfig, MakerLedger, MakerStats, MarketDataMode, OperatorControls,
     OrderLatencyTracker, RequestTimeoutPhase, RestingQuote, SizeSkewController, SpreadController,
     VolBreaker,
 };
@@ -120,6 +121,9 @@ pub(super) struct CycleRequest<'a> {
     pub(super) mark: f64,
     pub(super) best_bid: Option<f64>,
     pub(super) best_ask: Option<f64>,
+    /// Displayed book for the fair-value estimate; may be empty.
+    pub(super) bid_levels: &'a [standx_maker::BookLevel],
+    pub(super) ask_levels: &'a [standx_maker::BookLevel],
     pub(super) market_data_mode: MarketDataMode,
     pub(super) market_source: &'static str,
     /// Observation-only classification. The first successfully committed
@@ -133,8 +137,11 @@ pub(super) struct CycleRequest<'a> {
     pub(super) inventory_exit_pct: f64,
     pub(super) inventory_exit_qty: f64,
     /// Latched supervisor wind-down request (SIGUSR1 from the A/B
-    /// orchestrator): stop quoting and flatten via reduce-only exits.
+    /// orchestrator, or `maker ctl flatten`): stop quoting and flatten via
+    /// reduce-only exits.
     pub(super) wind_down: bool,
+    /// Operator overrides from the control socket (pause, widen, size).
+    pub(super) controls: OperatorControls,
     /// Venue-quantity tolerance; positions at or below it count as flat.
     pub(super) qty_tolerance: f64,
     pub(super) session_started_at: i64,
@@ -161,6 +168,8 @@ pub(super) struct CycleState<'a> {
     pub(super) size_skew_controller: &'a mut SizeSkewController,
     /// Stage 3 v1 nonlinear price-skew strength (disabled ≡ legacy linear).
     pub(super) nonlinear_skew: standx_maker::NonlinearSkewConfig,
+    pub(super) strategy: &'a mut dyn standx_maker::QuoteStrategy,
+    pub(super) fair_value: standx_maker::FairValueConfig,
     pub(super) guard_controller: &'a mut standx_maker::GuardController,
     /// Caller-normalized external leader observation for this cycle; `None`
     /// when the guard is disabled or the feed has no usable sample.
@@ -175,6 +184,9 @@ pub(super) struct CycleState<'a> {
     /// safety decisions.
     pub(super) order_latency: Option<&'a mut OrderLatencyTracker>,
     pub(super) latency_started: Option<Instant>,
+    /// `maker run --trace-out` writer; `None` when the session is untraced
+    /// or a write already failed.
+    pub(super) trace: &'a mut Option<TraceWriter>,
 }
 
 #[derive(Debug, Default)]
@@ -18,10 +18,44 @@ pub(super) struct LiveProcessLock {
     _ab_guard: Option<File>,
 }
 
+/// Path of the per-host live maker lock.
+pub(super) fn maker_lock_path() -> String {
+    std::env::var("STANDX_MAKER_LOCK_PATH").unwrap_or_else(|_| DEFAULT_MAKER_LOCK.to_string())
+}
+
+/// Whether some process currently holds the live maker lock.
+///
+/// Probes without creating the lock file; a missing file means no live
+/// maker has run on this host since boot.
+pub(super) fn live_maker_running() -> Result<bool> {
+    lock_held(&maker_lock_path())
+}
+
+#[cfg(unix)]
+fn lock_held(path: &str) -> Result<bool> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(error) => {
+            return Err(error).with_context(|| format!("failed to open live lock {path}"));
+        }
+    };
+    // SAFETY: flock only observes the valid fd owned by `file`; a lock taken
+    // here is released when `file` drops at the end of this function.
+    let result = unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH | libc::LOCK_NB) };
+    Ok(result != 0)
+}
+
+#[cfg(not(unix))]
+fn lock_held(_path: &str) -> Result<bool> {
+    Err(anyhow::anyhow!(
+        "live maker process locking is unavailable on this platform"
+    ))
+}
+
 impl LiveProcessLock {
     pub(super) fn acquire() -> Result<Self> {
-        let maker_path = std::env::var("STANDX_MAKER_LOCK_PATH")
-            .unwrap_or_else(|_| DEFAULT_MAKER_LOCK.to_string());
+        let maker_path = maker_lock_path();
         let ab_guard_path = std::env::var("STANDX_STAGE2_AB_LOCK_PATH")
             .unwrap_or_else(|_| DEFAULT_AB_GUARD_LOCK.to_string());
         let orchestrated = std::env::var("STANDX_STAGE2_AB_MEMBER").ok().as_deref() == Some("1");
@@ -91,4 +125,19 @@ mod tests {
         drop(first);
         assert!(lock(path, "replacement maker").is_ok());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_sees_a_held_lock_without_creating_one() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("maker.lock");
+        let path = path.to_str().unwrap();
+        assert!(!lock_held(path).unwrap());
+        assert!(!Path::new(path).exists());
+
+        let held = lock(path, "running maker").unwrap();
+        assert!(lock_held(path).unwrap());
+        drop(held);
+        assert!(!lock_held(path).unwrap());
+    }
 }
@@ -2,12 +2,14 @@ use super::ledger::{adopt_order, apply_rest_trade};
 use super::model::{is_current_run_order, is_maker_order, position_for_symbol};
 use super::output::emit_live_fill;
 use super::pipeline::{fetch_account_audit, AccountAudit};
+use crate::audit;
 use crate::cli::OutputFormat;
 use anyhow::Result;
 use standx_maker::{MakerFill, MakerLedger, MakerStats};
 use standx_sdk::account_stream::{
     AccountChannel, AccountEvent, AccountStream, AccountStreamHealth,
 };
+use standx_sdk::client::order::cancel_orders_body;
 use standx_sdk::client::StandXClient;
 use standx_sdk::models::{Order, Position, Trade};
 use standx_sdk::order_response::{
@@ -365,13 +367,13 @@ pub(super) async fn cancel_maker_orders_with_retry(
                 if output_format == OutputFormat::Json {
                     println!(
                         "{}",
-                        serde_json::json!({
+                        crate::output::format_event(&serde_json::json!({
                             "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                             "symbol": symbol,
                             "action": "maker_cleanup",
                             "event": "complete",
                             "remaining_maker_orders": 0,
-                        })
+                        }))
                     );
                 } else {
                     println!("✅ All maker-owned {} orders cancelled", symbol);
@@ -385,7 +387,7 @@ pub(super) async fn cancel_maker_orders_with_retry(
                 if output_format == OutputFormat::Json {
                     println!(
                         "{}",
-                        serde_json::json!({
+                        crate::output::format_event(&serde_json::json!({
                             "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                             "symbol": symbol,
                             "action": "maker_cleanup",
@@ -394,7 +396,7 @@ pub(super) async fn cancel_maker_orders_with_retry(
                             "attempt": attempt,
                             "max_attempts": attempts,
                             "message": error.to_string(),
-                        })
+                        }))
                     );
                 } else {
                     eprintln!(
@@ -434,7 +436,13 @@ async fn cleanup_once(client: &StandXClient, symbol: &str) -> Result<()> {
     if order_ids.is_empty() {
         return Ok(());
     }
-    client.cancel_orders(&order_ids).await?;
+    audit::audited(
+        "orders_cancel",
+        symbol,
+        cancel_orders_body(&order_ids),
+        client.cancel_orders(&order_ids),
+    )
+    .await?;
     tokio::time::sleep(MAKER_CLEANUP_VERIFY_DELAY).await;
     let residual_ids = client
         .get_open_orders(Some(symbol))
@@ -481,7 +489,7 @@ fn emit_order_response_reconnect(
     if output_format == OutputFormat::Json {
         println!(
             "{}",
-            serde_json::json!({
+            crate::output::format_event(&serde_json::json!({
                 "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                 "symbol": symbol,
                 "action": "order_response_reconnect",
@@ -489,7 +497,7 @@ fn emit_order_response_reconnect(
                 "attempt": attempt,
                 "max_attempts": max_attempts,
                 "message": message,
-            })
+            }))
         );
     } else {
         eprintln!(
@@ -0,0 +1,465 @@
+//! Maker TOML reload without a restart (SIGHUP / `--watch-config`).
+//!
+//! A reload re-resolves the file with the startup precedence (flag, then
+//! TOML, then default) and the startup checks, then diffs the result against
+//! the running settings. Settings the runtime reads every cycle apply at the
+//! next cycle boundary. Settings whose state is built once at startup — the
+//! transport, the volatility window, the quote model, the external guard —
+//! are restart-only: changing any of them rejects the whole reload, so a file
+//! never half-applies. The symbol and `--live` are not TOML keys at all; a
+//! file naming them fails to parse and is rejected the same way. While
+//! `--trace-out` records a session every change is refused, since the trace
+//! header holds the startup config that replay plans from.
+
+use super::config::MakerFileConfig;
+use super::control;
+use super::startup::{maker_config, validate_live_alerts, validate_run_config};
+use super::*;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// How often `--watch-config` checks the file's modification time.
+const CONFIG_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Settings (or setting groups, matched by dotted prefix) that only take
+/// effect at startup.
+const RESTART_ONLY_FIELDS: &[&str] = &[
+    "no_ws",
+    "vol_window",
+    "vol_window_secs",
+    "vol_pause_bps",
+    "strategy",
+    "external_guard",
+    "order_response_reconnect_attempts",
+    "order_response_reconnect_backoff",
+    "account_stream_reconnect_attempts",
+    "account_stream_reconnect_backoff",
+];
+
+/// What asked for a reload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ReloadTrigger {
+    Signal,
+    FileChanged,
+}
+
+impl ReloadTrigger {
+    pub(super) fn as_str(self) -> &'static str {
+        match self {
+            Self::Signal => "sighup",
+            Self::FileChanged => "watch",
+        }
+    }
+}
+
+/// One setting whose resolved value differs after the reload.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct ConfigChange {
+    pub(super) field: &'static str,
+    pub(super) from: Value,
+    pub(super) to: Value,
+    pub(super) restart_only: bool,
+}
+
+/// The decision for one reload request.
+pub(super) enum ReloadPlan {
+    /// The file did not load, resolve, or pass the startup checks.
+    Invalid(anyhow::Error),
+    Unchanged,
+    /// At least one restart-only setting changed; nothing is applied.
+    Rejected(Vec<ConfigChange>),
+    Apply {
+        args: Box<MakerRunArgs>,
+        cfg: MakerConfig,
+        changes: Vec<ConfigChange>,
+    },
+}
+
+/// Decide what a freshly loaded `file` would change in the running session.
+/// Active operator overrides must still pass their own checks under the new
+/// settings.
+pub(super) fn plan(
+    current: &MakerRunArgs,
+    current_cfg: &MakerConfig,
+    controls: maker::OperatorControls,
+    symbol: &str,
+    file: Result<MakerFileConfig>,
+) -> ReloadPlan {
+    let resolved = file
+        .and_then(|file| current.clone().with_file(file))
+        .and_then(|args| {
+            let cfg = maker_config(
+                &args,
+                current_cfg.price_decimals,
+                current_cfg.qty_decimals,
+                current_cfg.min_order_qty,
+            );
+            validate_run_config(&args, &cfg, symbol)?;
+            if args.live {
+                validate_live_alerts(&args)?;
+            }
+            control::recheck(controls, &args, &cfg, symbol)?;
+            Ok((args, cfg))
+        });
+    let (args, cfg) = match resolved {
+        Ok(resolved) => resolved,
+        Err(error) => return ReloadPlan::Invalid(error),
+    };
+    let changes = diff(current, &args);
+    if changes.is_empty() {
+        ReloadPlan::Unchanged
+    } else if let Some(path) = current.trace_out.as_deref() {
+        // The trace header pins the startup config; replay could not follow.
+        ReloadPlan::Invalid(anyhow::anyhow!(
+            "replay trace {} records the startup config; restart to apply config changes",
+            path.display()
+        ))
+    } else if changes.iter().any(|change| change.restart_only) {
+        ReloadPlan::Rejected(changes)
+    } else {
+        ReloadPlan::Apply {
+            args: Box::new(args),
+            cfg,
+            changes,
+        }
+    }
+}
+
+/// Every TOML-resolvable setting whose value differs, in file order.
+pub(super) fn diff(current: &MakerRunArgs, reloaded: &MakerRunArgs) -> Vec<ConfigChange> {
+    fields(current)
+        .into_iter()
+        .zip(fields(reloaded))
+        .filter(|((_, from), (_, to))| from != to)
+        .map(|((field, from), (_, to))| ConfigChange {
+            field,
+            from,
+            to,
+            restart_only: RESTART_ONLY_FIELDS.iter().any(|restart_only| {
+                field == *restart_only
+                    || field
+                        .strip_prefix(restart_only)
+                        .is_some_and(|rest| rest.starts_with('.'))
+            }),
+        })
+        .collect()
+}
+
+fn fields(args: &MakerRunArgs) -> Vec<(&'static str, Value)> {
+    let adaptive = &args.adaptive_spread;
+    let strategy = match &args.strategy {
+        maker::StrategyConfig::Ladder => json!({ "kind": args.strategy.name() }),
+        maker::StrategyConfig::AvellanedaStoikov(config) => json!({
+            "kind": args.strategy.name(),
+            "risk_aversion": config.risk_aversion,
+            "kappa": config.kappa,
+            "kappa_prior_fills": config.kappa_prior_fills,
+            "kappa_window": config.kappa_window,
+        }),
+    };
+    vec![
+        ("spread_bps", json!(args.spread_bps)),
+        ("band_bps", json!(args.band_bps)),
+        ("size", json!(args.size)),
+        ("levels", json!(args.levels)),
+        ("level_step_bps", json!(args.level_step_bps)),
+        ("refresh_bps", json!(args.refresh_bps)),
+        ("interval", json!(args.interval)),
+        ("max_position", json!(args.max_position)),
+        ("skew_bps", json!(args.skew_bps)),
+        ("inventory_exit_pct", json!(args.inventory_exit_pct)),
+        ("inventory_exit_qty", json!(args.inventory_exit_qty)),
+        ("max_divergence_bps", json!(args.max_divergence_bps)),
+        ("vol_pause_bps", json!(args.vol_pause_bps)),
+        ("vol_window", json!(args.vol_window)),
+        ("vol_window_secs", json!(args.vol_window_secs)),
+        ("adaptive_spread.enabled", json!(adaptive.enabled)),
+        (
+            "adaptive_spread.min_spread_bps",
+            json!(adaptive.min_spread_bps),
+        ),
+        (
+            "adaptive_spread.max_spread_bps",
+            json!(adaptive.max_spread_bps),
+        ),
+        (
+            "adaptive_spread.tiers",
+            Value::Array(
+                adaptive
+                    .tiers
+                    .iter()
+                    .map(|tier| {
+                        json!({
+                            "enter_vol_bps": tier.enter_vol_bps,
+                            "exit_vol_bps": tier.exit_vol_bps,
+                            "spread_bps": tier.spread_bps,
+                            "refresh_bps": tier.refresh_bps,
+                        })
+                    })
+                    .collect(),
+            ),
+        ),
+        ("size_skew.enabled", json!(args.size_skew.enabled)),
+        ("size_skew.activate_pct", json!(args.size_skew.activate_pct)),
+        ("size_skew.release_pct", json!(args.size_skew.release_pct)),
+        (
+            "size_skew.add_side_factor",
+            json!(args.size_skew.add_side_factor),
+        ),
+        ("nonlinear_skew.enabled", json!(args.nonlinear_skew.enabled)),
+        ("nonlinear_skew.boost", json!(args.nonlinear_skew.boost)),
+        ("nonlinear_skew.cap_bps", json!(args.nonlinear_skew.cap_bps)),
+        ("fair_value.enabled", json!(args.fair_value.enabled)),
+        ("fair_value.mark_weight", json!(args.fair_value.mark_weight)),
+        ("fair_value.mid_weight", json!(args.fair_value.mid_weight)),
+        (
+            "fair_value.microprice_weight",
+            json!(args.fair_value.microprice_weight),
+        ),
+        ("fair_value.depth_bps", json!(args.fair_value.depth_bps)),
+        (
+            "fair_value.max_deviation_bps",
+            json!(args.fair_value.max_deviation_bps),
+        ),
+        ("strategy", strategy),
+        ("external_guard.enabled", json!(args.external_guard.enabled)),
+        (
+            "external_guard.enter_bps",
+            json!(args.external_guard.enter_bps),
+        ),
+        (
+            "external_guard.exit_bps",
+            json!(args.external_guard.exit_bps),
+        ),
+        (
+            "external_guard.max_age_ms",
+            json!(args.external_guard.max_age_ms),
+        ),
+        (
+            "external_guard.basis_half_life_secs",
+            json!(args.external_guard_basis_half_life_secs),
+        ),
+        ("stop_loss", json!(args.stop_loss)),
+        ("alert_loss", json!(args.alert_loss)),
+        ("alert_inventory_pct", json!(args.alert_inventory_pct)),
+        (
+            "alert_position_change_pct",
+            json!(args.alert_position_change_pct),
+        ),
+        ("alert_uptime", json!(args.alert_uptime)),
+        ("alert_equity_below", json!(args.alert_equity_below)),
+        ("alert_margin_below", json!(args.alert_margin_below)),
+        ("no_ws", json!(args.no_ws)),
+        (
+            "order_response_reconnect_attempts",
+            json!(args.order_response_reconnect_attempts),
+        ),
+        (
+            "order_response_reconnect_backoff",
+            json!(args.order_response_reconnect_backoff),
+        ),
+        (
+            "account_stream_reconnect_attempts",
+            json!(args.account_stream_reconnect_attempts),
+        ),
+        (
+            "account_stream_reconnect_backoff",
+            json!(args.account_stream_reconnect_backoff),
+        ),
+    ]
+}
+
+/// Poll `path` for `--watch-config` and request a reload whenever its
+/// modification time changes (including the file appearing or vanishing).
+/// Requests coalesce while one is pending; the task ends with the runtime.
+pub(super) fn spawn_config_watcher(
+    path: PathBuf,
+    reload_tx: tokio::sync::mpsc::Sender<ReloadTrigger>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut last_modified = modified_at(&path);
+        let mut interval = tokio::time::interval(CONFIG_WATCH_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if reload_tx.is_closed() {
+                break;
+            }
+            let modified = modified_at(&path);
+            if modified != last_modified {
+                last_modified = modified;
+                let _ = reload_tx.try_send(ReloadTrigger::FileChanged);
+            }
+        }
+    })
+}
+
+fn modified_at(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn venue_cfg(args: &MakerRunArgs) -> MakerConfig {
+        maker_config(args, 2, 3, 0.001)
+    }
+
+    fn running(toml: &str) -> MakerRunArgs {
+        MakerRunArgs {
+            overrides: MakerRunOverrides {
+                alert_loss: Some(5.0),
+                ..MakerRunOverrides::default()
+            },
+            ..MakerRunArgs::default()
+        }
+        .with_file(toml::from_str(toml).unwrap())
+        .unwrap()
+    }
+
+    fn reload(current: &MakerRunArgs, toml: &str) -> ReloadPlan {
+        reload_under(current, maker::OperatorControls::default(), toml)
+    }
+
+    fn reload_under(
+        current: &MakerRunArgs,
+        controls: maker::OperatorControls,
+        toml: &str,
+    ) -> ReloadPlan {
+        plan(
+            current,
+            &venue_cfg(current),
+            controls,
+            "XAG-USD",
+            toml::from_str(toml).map_err(anyhow::Error::from),
+        )
+    }
+
+    #[test]
+    fn safe_changes_apply_and_cli_flags_still_win() {
+        let current = running("spread_bps = 8\nalert_loss = 1\n");
+        let ReloadPlan::Apply { args, cfg, changes } = reload(
+            &current,
+            "spread_bps = 9\nrefresh_bps = 4\nalert_loss = 2\nalert_uptime = 40\n",
+        ) else {
+            panic!("safe reload should apply");
+        };
+
+        assert_eq!(cfg.spread_bps, 9.0);
+        // `alert_loss` came from a flag, so the file cannot move it.
+        assert_eq!(args.alert_loss, 5.0);
+        assert_eq!(
+            changes
+                .iter()
+                .map(|change| change.field)
+                .collect::<Vec<_>>(),
+            vec!["spread_bps", "refresh_bps", "alert_uptime"]
+        );
+        assert_eq!(changes[0].from, json!(8.0));
+        assert_eq!(changes[0].to, json!(9.0));
+        assert!(changes.iter().all(|change| !change.restart_only));
+        assert!(matches!(
+            reload(&current, "spread_bps = 8\nalert_loss = 1\n"),
+            ReloadPlan::Unchanged
+        ));
+    }
+
+    #[test]
+    fn restart_only_changes_reject_the_whole_reload() {
+        let current = running("spread_bps = 8\n");
+        let ReloadPlan::Rejected(changes) = reload(
+            &current,
+            "spread_bps = 9\n[strategy]\nkind = \"avellaneda_stoikov\"\n",
+        ) else {
+            panic!("strategy change should be rejected");
+        };
+        let restart_only: Vec<_> = changes
+            .iter()
+            .filter(|change| change.restart_only)
+            .map(|change| change.field)
+            .collect();
+        assert_eq!(restart_only, vec!["strategy"]);
+
+        let ReloadPlan::Rejected(changes) = reload(
+            &current,
+            "spread_bps = 8\n[external_guard]\nenter_bps = 17\n",
+        ) else {
+            panic!("external guard change should be rejected");
+        };
+        assert_eq!(changes[0].field, "external_guard.enter_bps");
+        assert!(changes[0].restart_only);
+    }
+
+    #[test]
+    fn reload_runs_the_startup_checks() {
+        let current = running("spread_bps = 8\n");
+        let ReloadPlan::Invalid(error) = reload(&current, "spread_bps = 8\nband_bps = 6\n") else {
+            panic!("band inside the spread should be refused");
+        };
+        assert!(error.to_string().contains("--band-bps"), "{error}");
+
+        let ReloadPlan::Invalid(error) = reload(&current, "size = 0.0001\n") else {
+            panic!("size below the venue minimum should be refused");
+        };
+        assert!(error.to_string().contains("min order qty"), "{error}");
+
+        // Not TOML keys, so a file cannot switch symbol or mode.
+        assert!(matches!(
+            reload(&current, "live = false\n"),
+            ReloadPlan::Invalid(_)
+        ));
+        assert!(matches!(
+            reload(&current, "symbol = \"BTC-USD\"\n"),
+            ReloadPlan::Invalid(_)
+        ));
+    }
+
+    #[test]
+    fn reload_cannot_pull_the_limits_under_an_active_override() {
+        let current = running("spread_bps = 8\nband_bps = 30\n");
+        let widened = maker::OperatorControls {
+            widen_bps: 10.0,
+            ..maker::OperatorControls::default()
+        };
+        let ReloadPlan::Invalid(error) =
+            reload_under(&current, widened, "spread_bps = 8\nband_bps = 15\n")
+        else {
+            panic!("a band inside the widened spread should be refused");
+        };
+        assert!(format!("{error:#}").contains("widen 0"), "{error:#}");
+        assert!(matches!(
+            reload_under(&current, widened, "spread_bps = 8\nband_bps = 25\n"),
+            ReloadPlan::Apply { .. }
+        ));
+
+        let sized = maker::OperatorControls {
+            size: Some(0.05),
+            ..maker::OperatorControls::default()
+        };
+        let ReloadPlan::Invalid(error) = reload_under(
+            &current,
+            sized,
+            "spread_bps = 8\nband_bps = 30\nmax_position = 0.02\n",
+        ) else {
+            panic!("a max position below the sized quote should be refused");
+        };
+        assert!(format!("{error:#}").contains("size 0"), "{error:#}");
+    }
+
+    #[test]
+    fn a_recorded_trace_refuses_changes() {
+        let mut current = running("spread_bps = 8\n");
+        current.trace_out = Some(PathBuf::from("session.ndjson"));
+        let ReloadPlan::Invalid(error) = reload(&current, "spread_bps = 9\n") else {
+            panic!("a reload under --trace-out should be refused");
+        };
+        assert!(error.to_string().contains("session.ndjson"), "{error}");
+        assert!(matches!(
+            reload(&current, "spread_bps = 8\n"),
+            ReloadPlan::Unchanged
+        ));
+    }
+}
@@ -1,20 +1,26 @@
 //! CLI adapter for normalized deterministic maker replay traces.
+//!
+//! [`run`] reads a trace for `maker replay`; [`TraceWriter`] produces one from
+//! a live or paper `maker run --trace-out`, in the same schema.
 
+use super::config;
 use crate::cli::OutputFormat;
 use anyhow::{Context, Result};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use standx_maker::{
-    run_replay, Action, AdaptiveSpreadConfig, ExecutionCosts, FillRole, MakerConfig,
-    MarketSnapshot, PerformanceFill, ReplayCycle, ReplayEvent, ReplayResult, ReplaySettings,
-    RestingQuote, SizeSkewConfig, SpreadTier,
+    run_replay, Action, AdaptiveSpreadConfig, AvellanedaStoikovConfig, ExecutionCosts, FillRole,
+    MakerConfig, MarketSnapshot, PerformanceFill, ReplayCycle, ReplayEvent, ReplayResult,
+    ReplaySettings, RestingQuote, SizeSkewConfig, SpreadTier, StrategyConfig,
 };
 use standx_sdk::models::OrderSide;
-use std::io::{BufRead, BufReader, Read};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
 use std::path::Path;
 
 const TRACE_SCHEMA_VERSION: u32 = 1;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
 enum TraceRecord {
     Header {
@@ -33,12 +39,15 @@ enum TraceRecord {
         best_bid: Option<f64>,
         best_ask: Option<f64>,
         position: f64,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         resting: Vec<TraceRestingQuote>,
-        #[serde(default)]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
         pending_slots: Vec<TraceQuoteSlot>,
         eligible_bid_qty: f64,
         eligible_ask_qty: f64,
+        /// Quote anchor when `[fair_value]` was enabled; absent means mark.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        fair_value: Option<f64>,
     },
     Fill {
         trade_id: u64,
@@ -60,7 +69,7 @@ enum TraceRecord {
     },
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 enum TraceFillRole {
     PassiveMaker,
@@ -76,7 +85,7 @@ impl From<TraceFillRole> for FillRole {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceExecutionCosts {
     fee_quote: f64,
@@ -92,7 +101,7 @@ impl From<TraceExecutionCosts> for ExecutionCosts {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceMakerConfig {
     spread_bps: f64,
@@ -126,27 +135,31 @@ impl From<TraceMakerConfig> for MakerConfig {
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceReplaySettings {
     starting_position: f64,
     starting_mark: f64,
     max_divergence_bps: f64,
     require_full_touch: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
     vol_window: Option<usize>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     vol_window_secs: Option<u64>,
     vol_pause_bps: f64,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     adaptive_spread: Option<TraceAdaptiveSpreadConfig>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     size_skew: Option<TraceSizeSkewConfig>,
     active_exit_enabled: bool,
     inventory_exit_pct: f64,
     inventory_exit_qty: f64,
+    /// Absent for the ladder, so pre-strategy traces keep their config hash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    strategy: Option<TraceStrategyConfig>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceAdaptiveSpreadConfig {
     enabled: bool,
@@ -155,18 +168,18 @@ struct TraceAdaptiveSpreadConfig {
     tiers: Vec<TraceSpreadTier>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceSpreadTier {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     enter_vol_bps: Option<f64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     exit_vol_bps: Option<f64>,
     spread_bps: f64,
     refresh_bps: f64,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceSizeSkewConfig {
     enabled: bool,
@@ -175,6 +188,51 @@ struct TraceSizeSkewConfig {
     add_side_factor: f64,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+enum TraceStrategyConfig {
+    Ladder {},
+    AvellanedaStoikov {
+        risk_aversion: f64,
+        kappa: f64,
+        kappa_prior_fills: f64,
+        kappa_window: usize,
+    },
+}
+
+impl From<TraceStrategyConfig> for StrategyConfig {
+    fn from(value: TraceStrategyConfig) -> Self {
+        match value {
+            TraceStrategyConfig::Ladder {} => Self::Ladder,
+            TraceStrategyConfig::AvellanedaStoikov {
+                risk_aversion,
+                kappa,
+                kappa_prior_fills,
+                kappa_window,
+            } => Self::AvellanedaStoikov(AvellanedaStoikovConfig {
+                risk_aversion,
+                kappa,
+                kappa_prior_fills,
+                kappa_window,
+            }),
+        }
+    }
+}
+
+impl From<&StrategyConfig> for TraceStrategyConfig {
+    fn from(value: &StrategyConfig) -> Self {
+        match value {
+            StrategyConfig::Ladder => Self::Ladder {},
+            StrategyConfig::AvellanedaStoikov(config) => Self::AvellanedaStoikov {
+                risk_aversion: config.risk_aversion,
+                kappa: config.kappa,
+                kappa_prior_fills: config.kappa_prior_fills,
+                kappa_window: config.kappa_window,
+            },
+        }
+    }
+}
+
 impl TryFrom<TraceReplaySettings> for ReplaySettings {
     type Error = anyhow::Error;
 
@@ -225,11 +283,12 @@ impl TryFrom<TraceReplaySettings> for ReplaySettings {
             active_exit_enabled: value.active_exit_enabled,
             inventory_exit_pct: value.inventory_exit_pct,
             inventory_exit_qty: value.inventory_exit_qty,
+            strategy: value.strategy.map(Into::into).unwrap_or_default(),
         })
     }
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceRestingQuote {
     order_id: Option<String>,
@@ -255,13 +314,212 @@ impl From<TraceRestingQuote> for RestingQuote {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 struct TraceQuoteSlot {
     side: OrderSide,
     level: u32,
 }
 
+impl From<&MakerConfig> for TraceMakerConfig {
+    fn from(value: &MakerConfig) -> Self {
+        Self {
+            spread_bps: value.spread_bps,
+            band_bps: value.band_bps,
+            level_step_bps: value.level_step_bps,
+            refresh_bps: value.refresh_bps,
+            levels: value.levels,
+            size: value.size,
+            max_position: value.max_position,
+            skew_bps: value.skew_bps,
+            price_decimals: value.price_decimals,
+            qty_decimals: value.qty_decimals,
+            min_order_qty: value.min_order_qty,
+        }
+    }
+}
+
+impl From<&ReplaySettings> for TraceReplaySettings {
+    fn from(value: &ReplaySettings) -> Self {
+        let adaptive_spread = &value.adaptive_spread;
+        Self {
+            starting_position: value.starting_position,
+            starting_mark: value.starting_mark,
+            max_divergence_bps: value.max_divergence_bps,
+            require_full_touch: value.require_full_touch,
+            vol_window: value.vol_window_secs.is_none().then_some(value.vol_window),
+            vol_window_secs: value.vol_window_secs,
+            vol_pause_bps: value.vol_pause_bps,
+            adaptive_spread: (*adaptive_spread != AdaptiveSpreadConfig::default()).then(|| {
+                TraceAdaptiveSpreadConfig {
+                    enabled: adaptive_spread.enabled,
+                    min_spread_bps: adaptive_spread.min_spread_bps,
+                    max_spread_bps: adaptive_spread.max_spread_bps,
+                    tiers: adaptive_spread
+                        .tiers
+                        .iter()
+                        .map(|tier| TraceSpreadTier {
+                            enter_vol_bps: tier.enter_vol_bps,
+                            exit_vol_bps: tier.exit_vol_bps,
+                            spread_bps: tier.spread_bps,
+                            refresh_bps: tier.refresh_bps,
+                        })
+                        .collect(),
+                }
+            }),
+            size_skew: (value.size_skew != SizeSkewConfig::default()).then_some(
+                TraceSizeSkewConfig {
+                    enabled: value.size_skew.enabled,
+                    activate_pct: value.size_skew.activate_pct,
+                    release_pct: value.size_skew.release_pct,
+                    add_side_factor: value.size_skew.add_side_factor,
+                },
+            ),
+            active_exit_enabled: value.active_exit_enabled,
+            inventory_exit_pct: value.inventory_exit_pct,
+            inventory_exit_qty: value.inventory_exit_qty,
+            strategy: (value.strategy != StrategyConfig::default())
+                .then(|| TraceStrategyConfig::from(&value.strategy)),
+        }
+    }
+}
+
+impl From<&RestingQuote> for TraceRestingQuote {
+    fn from(value: &RestingQuote) -> Self {
+        Self {
+            order_id: value.order_id.clone(),
+            side: value.side,
+            level: value.level,
+            price: value.price,
+            qty: value.qty,
+            ref_center: value.ref_center,
+            placed_at_cycle: value.placed_at_cycle,
+        }
+    }
+}
+
+impl TraceRecord {
+    fn from_event(event: &ReplayEvent) -> Self {
+        match event {
+            ReplayEvent::Cycle(cycle) => Self::Cycle {
+                event_time_ms: cycle.event_time_ms,
+                cycle: cycle.cycle,
+                mark: cycle.market.mark,
+                best_bid: cycle.market.best_bid,
+                best_ask: cycle.market.best_ask,
+                position: cycle.position,
+                resting: cycle.resting.iter().map(Into::into).collect(),
+                pending_slots: cycle
+                    .pending_slots
+                    .iter()
+                    .map(|&(side, level)| TraceQuoteSlot { side, level })
+                    .collect(),
+                eligible_bid_qty: cycle.eligible_bid_qty,
+                eligible_ask_qty: cycle.eligible_ask_qty,
+                fair_value: cycle.fair_value,
+            },
+            ReplayEvent::Fill(fill) => Self::Fill {
+                trade_id: fill.trade_id,
+                order_id: fill.order_id,
+                role: match fill.role {
+                    FillRole::PassiveMaker => TraceFillRole::PassiveMaker,
+                    FillRole::InventoryExit => TraceFillRole::InventoryExit,
+                },
+                side: fill.side,
+                price: fill.price,
+                qty: fill.qty,
+                mark_at_fill: fill.mark_at_fill,
+                event_time_ms: fill.event_time_ms,
+                costs: fill.costs.map(|costs| TraceExecutionCosts {
+                    fee_quote: costs.fee_quote,
+                    rebate_quote: costs.rebate_quote,
+                }),
+            },
+            ReplayEvent::Funding {
+                event_time_ms,
+                cashflow_quote,
+            } => Self::Funding {
+                event_time_ms: *event_time_ms,
+                cashflow_quote: *cashflow_quote,
+            },
+        }
+    }
+}
+
+/// Writes a `maker run` session as a normalized trace for `maker replay`.
+///
+/// Every record is flushed as it is written, so a crashed session still
+/// leaves a readable prefix (it only lacks the `finish` record).
+pub(super) struct TraceWriter {
+    out: BufWriter<File>,
+}
+
+impl TraceWriter {
+    /// Create `path` and write the header: the build's git commit, the
+    /// effective config and settings, and a SHA-256 over both. The seed is
+    /// always 0 because the planner draws no random numbers.
+    pub(super) fn create(
+        path: &Path,
+        symbol: &str,
+        config: &MakerConfig,
+        settings: &ReplaySettings,
+    ) -> Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("failed to create replay trace {}", path.display()))?;
+        let mut writer = Self {
+            out: BufWriter::new(file),
+        };
+        let config = TraceMakerConfig::from(config);
+        let settings = TraceReplaySettings::from(settings);
+        let config_hash = hex::encode(Sha256::digest(serde_json::to_vec(&(&config, &settings))?));
+        writer.write(&TraceRecord::Header {
+            schema_version: TRACE_SCHEMA_VERSION,
+            symbol: symbol.to_string(),
+            git_sha: env!("STANDX_GIT_SHA").to_string(),
+            config_hash,
+            seed: 0,
+            config,
+            settings: Box::new(settings),
+        })?;
+        Ok(writer)
+    }
+
+    /// Append cycles, fills and funding in the order the session saw them.
+    pub(super) fn events(&mut self, events: &[ReplayEvent]) -> Result<()> {
+        for event in events {
+            self.write(&TraceRecord::from_event(event))?;
+        }
+        Ok(())
+    }
+
+    /// Close the trace at the session's final performance timestamp.
+    pub(super) fn finish(mut self, event_time_ms: i64) -> Result<()> {
+        self.write(&TraceRecord::Finish { event_time_ms })
+    }
+
+    fn write(&mut self, record: &TraceRecord) -> Result<()> {
+        serde_json::to_writer(&mut self.out, record)?;
+        self.out.write_all(b"\n")?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Append `events` to the session trace. A failed write stops tracing with a
+/// warning but never the maker itself.
+pub(super) fn record_trace(trace: &mut Option<TraceWriter>, events: &[ReplayEvent]) {
+    let Some(writer) = trace.as_mut() else {
+        return;
+    };
+    if events.is_empty() {
+        return;
+    }
+    if let Err(error) = writer.events(events) {
+        eprintln!("⚠️ maker replay trace disabled: {error}");
+        *trace = None;
+    }
+}
+
 struct ParsedTrace {
     symbol: String,
     git_sha: String,
@@ -273,7 +531,11 @@ struct ParsedTrace {
     end_time_ms: i64,
 }
 
-pub(super) fn run(path: &Path, output_format: OutputFormat) -> Result<()> {
+pub(super) fn run(
+    path: &Path,
+    strategy_config: Option<&Path>,
+    output_format: OutputFormat,
+) -> Result<()> {
     let reader: Box<dyn Read> = if path == Path::new("-") {
         Box::new(std::io::stdin())
     } else {
@@ -282,7 +544,15 @@ pub(super) fn run(path: &Path, output_format: OutputFormat) -> Result<()> {
                 .with_context(|| format!("failed to open replay trace {}", path.display()))?,
         )
     };
-    let trace = parse(BufReader::new(reader))?;
+    let mut trace = parse(BufReader::new(reader))?;
+    if let Some(strategy_config) = strategy_config {
+        // Only the quoting strategy changes; market, fills and every other
+        // setting stay as recorded so the two plans are directly comparable.
+        trace.settings.strategy = config::load(Some(strategy_config))?
+            .strategy
+            .map(|strategy| strategy.into_domain())
+            .unwrap_or_default();
+    }
     let result = run_replay(
         &trace.config,
         trace.settings.clone(),
@@ -353,6 +623,7 @@ fn parse(reader: impl BufRead) -> Result<ParsedTrace> {
                 pending_slots,
                 eligible_bid_qty,
                 eligible_ask_qty,
+                fair_value,
             } => {
                 require_header(&header, line_number)?;
                 events.push(ReplayEvent::Cycle(ReplayCycle {
@@ -371,6 +642,7 @@ fn parse(reader: impl BufRead) -> Result<ParsedTrace> {
                         .collect(),
                     eligible_bid_qty,
                     eligible_ask_qty,
+                    fair_value,
                 }));
             }
             TraceRecord::Fill {
@@ -444,7 +716,7 @@ fn emit(trace: &ParsedTrace, result: &ReplayResult, output_format: OutputFormat)
                 .map(|plan| plan.actions.iter().map(action_json).collect::<Vec<_>>());
             println!(
                 "{}",
-                serde_json::json!({
+                crate::output::format_event(&serde_json::json!({
                     "action": "replay_cycle",
                     "symbol": trace.symbol,
                     "git_sha": trace.git_sha,
@@ -465,14 +737,18 @@ fn emit(trace: &ParsedTrace, result: &ReplayResult, output_format: OutputFormat)
                     "size_skew_inventory_ratio": cycle.size_skew_decision.inventory_ratio,
                     "size_skew_add_qty": cycle.size_skew_decision.add_qty,
                     "actions": actions,
-                })
+                }))
             );
         }
-        println!("{}", summary_json(trace, result));
+        println!(
+            "{}",
+            crate::output::format_event(&summary_json(trace, result))
+        );
     } else {
         println!(
-            "Replay {} cycles={} passive_fills={} exit_fills={} net_pnl={:.6} uptime={:.2}%",
+            "Replay {} strategy={} cycles={} passive_fills={} exit_fills={} net_pnl={:.6} uptime={:.2}%",
             trace.symbol,
+            trace.settings.strategy.name(),
             result.cycles.len(),
             result.performance.passive_fills,
             result.performance.exit_fills,
@@ -535,6 +811,7 @@ fn summary_json(trace: &ParsedTrace, result: &ReplayResult) -> serde_json::Value
         "git_sha": trace.git_sha,
         "config_hash": trace.config_hash,
         "seed": trace.seed,
+        "strategy": trace.settings.strategy.name(),
         "cycles": result.cycles.len(),
         "passive_fills": performance.passive_fills,
         "passive_qty": performance.passive_qty,
@@ -601,6 +878,41 @@ mod tests {
         assert_eq!(first.performance.passive_fills, 1);
     }
 
+    #[test]
+    fn written_trace_reparses_to_the_same_replay() {
+        let trace = parse(BufReader::new(TRACE.as_bytes())).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let mut writer =
+            TraceWriter::create(&path, &trace.symbol, &trace.config, &trace.settings).unwrap();
+        writer.events(&trace.events).unwrap();
+        writer.finish(trace.end_time_ms).unwrap();
+
+        let written = parse(BufReader::new(File::open(&path).unwrap())).unwrap();
+        assert_eq!(written.symbol, trace.symbol);
+        assert_eq!(written.git_sha, env!("STANDX_GIT_SHA"));
+        assert_eq!(written.config_hash.len(), 64);
+        assert_eq!(written.settings, trace.settings);
+        assert_eq!(written.events, trace.events);
+        assert_eq!(written.end_time_ms, trace.end_time_ms);
+        assert_eq!(
+            run_replay(
+                &written.config,
+                written.settings.clone(),
+                &written.events,
+                written.end_time_ms,
+            )
+            .unwrap(),
+            run_replay(
+                &trace.config,
+                trace.settings.clone(),
+                &trace.events,
+                trace.end_time_ms,
+            )
+            .unwrap()
+        );
+    }
+
     #[test]
     fn schema_v1_without_size_skew_still_parses_as_disabled() {
         let trace = parse(BufReader::new(TRACE.as_bytes())).unwrap();
@@ -641,6 +953,26 @@ mod tests {
         assert_eq!(trace.settings.size_skew.add_side_factor, 0.5);
     }
 
+    #[test]
+    fn schema_v1_strategy_defaults_to_ladder_and_is_omitted_when_default() {
+        let trace = parse(BufReader::new(TRACE.as_bytes())).unwrap();
+        assert_eq!(trace.settings.strategy, StrategyConfig::Ladder);
+        let header = serde_json::to_value(TraceReplaySettings::from(&trace.settings)).unwrap();
+        assert!(header.get("strategy").is_none());
+
+        let configured = TRACE.replace(
+            "\"inventory_exit_qty\":0.0}",
+            "\"inventory_exit_qty\":0.0,\"strategy\":{\"kind\":\"ladder\"}}",
+        );
+        let trace = parse(BufReader::new(configured.as_bytes())).unwrap();
+        assert_eq!(trace.settings.strategy, StrategyConfig::Ladder);
+        let unknown = TRACE.replace(
+            "\"inventory_exit_qty\":0.0}",
+            "\"inventory_exit_qty\":0.0,\"strategy\":{\"kind\":\"grid\"}}",
+        );
+        assert!(parse(BufReader::new(unknown.as_bytes())).is_err());
+    }
+
     #[test]
     fn replay_rejects_sample_and_duration_windows_together() {
         let conflict = TRACE.replace(
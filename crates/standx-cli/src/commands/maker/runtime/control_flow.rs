@@ -0,0 +1,125 @@
+use super::super::control::{self, ControlEnvelope, ControlSocket};
+use super::super::output::emit_control;
+use super::*;
+use serde_json::json;
+
+impl MakerRuntime {
+    /// Answer control requests that queued while a cycle or recovery ran.
+    pub(super) async fn drain_control_requests(&mut self) {
+        while let Some(envelope) = self.control.as_mut().and_then(ControlSocket::try_recv) {
+            self.handle_control(envelope).await;
+        }
+    }
+
+    /// Validate one operator request, feed it to the runtime state machine,
+    /// then log, answer and notify it. `status` is answered and logged only.
+    async fn handle_control(&mut self, envelope: ControlEnvelope) {
+        let request = envelope.request;
+        let output_format = self.deps.output_format;
+        let symbol = &self.deps.symbol;
+        let cycle = self.loop_state.counters.cycle;
+        let checked = if self.recovery.runtime_state.is_stopping() {
+            Err(anyhow::anyhow!("maker is stopping"))
+        } else {
+            control::command(request, &self.deps.args, &self.deps.cfg, symbol)
+        };
+        let command = match checked {
+            Ok(Some(command)) => command,
+            Ok(None) => {
+                self.answer_control_status(envelope);
+                return;
+            }
+            Err(error) => {
+                let error = format!("{error:#}");
+                let controls = self.recovery.runtime_state.controls();
+                emit_control(
+                    output_format,
+                    symbol,
+                    cycle,
+                    request,
+                    "rejected",
+                    controls,
+                    Some(&error),
+                );
+                envelope.reply(json!({
+                    "ok": false,
+                    "command": request.label(),
+                    "error": error,
+                }));
+                return;
+            }
+        };
+        self.recovery
+            .runtime_state
+            .handle(MakerEvent::Control(command));
+        if command == maker::ControlCommand::Flatten {
+            // The same latch SIGUSR1 sets: it never unlatches.
+            self.loop_state.wind_down = true;
+        }
+        let controls = self.recovery.runtime_state.controls();
+        emit_control(
+            output_format,
+            symbol,
+            cycle,
+            request,
+            "applied",
+            controls,
+            None,
+        );
+        let summary = control::describe_controls(&control::controls_json(controls));
+        envelope.reply(json!(control::AppliedReply {
+            ok: true,
+            command: request.label(),
+            value: request.value(),
+            controls: controls.into(),
+        }));
+        let command_text = match request.value() {
+            Some(value) => format!("{} {value}", request.label()),
+            None => request.label().to_string(),
+        };
+        self.deps
+            .notifier
+            .lifecycle(
+                "control",
+                &format!("🎛️ operator {command_text} at cycle {cycle} — overrides: {summary}"),
+                symbol,
+                false,
+            )
+            .await;
+    }
+
+    /// `status` only reads, so the wait phase answers it without waking a
+    /// cycle.
+    pub(super) fn answer_control_status(&self, envelope: ControlEnvelope) {
+        emit_control(
+            self.deps.output_format,
+            &self.deps.symbol,
+            self.loop_state.counters.cycle,
+            envelope.request,
+            "status",
+            self.recovery.runtime_state.controls(),
+            None,
+        );
+        envelope.reply(self.control_status());
+    }
+
+    fn control_status(&self) -> serde_json::Value {
+        let (bids, asks) = self.resting_per_side();
+        json!(control::StatusReply {
+            ok: true,
+            command: "status",
+            symbol: self.deps.symbol.clone(),
+            live: self.deps.args.live,
+            cycle: self.loop_state.counters.cycle,
+            position: self.current_position(),
+            mark: self.market.last_mark,
+            bids,
+            asks,
+            spread_bps: self.deps.cfg.spread_bps,
+            size: self.deps.cfg.size,
+            wind_down: self.loop_state.wind_down,
+            fills: self.loop_state.counters.total_fills,
+            controls: self.recovery.runtime_state.controls().into(),
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use super::super::control::ControlRequest;
 use super::*;
 
 pub(super) struct CycleAttempt {
@@ -50,13 +51,14 @@ pub(super) fn commit_cycle_effect(runtime_state: &mut MakerState, token: WorkTok
 impl MakerRuntime {
     pub(super) async fn drive(mut self) -> (Self, MakerExit) {
         let exit = 'main: loop {
+            self.publish_metrics();
             match self.pre_cycle_phase().await {
                 LoopDirective::Proceed => {}
                 LoopDirective::Restart => continue 'main,
                 LoopDirective::Exit(exit) => break exit,
             }
             match self.run_cycle_phase().await {
-                LoopDirective::Proceed => {}
+                LoopDirective::Proceed => self.publish_metrics(),
                 LoopDirective::Restart => continue 'main,
                 LoopDirective::Exit(exit) => break exit,
             }
@@ -69,11 +71,18 @@ impl MakerRuntime {
     }
 
     async fn run_cycle_phase(&mut self) -> LoopDirective {
+        self.reload_config_if_requested();
+        self.drain_control_requests().await;
+        let started = std::time::Instant::now();
         let attempt = match self.execute_cycle().await {
             Ok(attempt) => attempt,
             Err(directive) => return directive,
         };
-        self.finish_cycle(attempt).await
+        let directive = self.finish_cycle(attempt).await;
+        if let Some(metrics) = self.metrics.as_mut() {
+            metrics.observe_cycle(started.elapsed());
+        }
+        directive
     }
 
     async fn execute_cycle(&mut self) -> std::result::Result<CycleAttempt, LoopDirective> {
@@ -121,6 +130,7 @@ impl MakerRuntime {
                 Ok(None) => return Err(LoopDirective::Restart),
                 Err(exit) => break 'execute exit,
             };
+            let controls = self.recovery.runtime_state.controls();
             // Split the live session into disjoint field borrows once per cycle:
             // the pinned `work` future holds the command/health/projection/poll
             // halves for its whole lifetime while the select loop below drains
@@ -235,6 +245,8 @@ impl MakerRuntime {
                         mark,
                         best_bid,
                         best_ask,
+                        bid_levels: &market.bid_levels,
+                        ask_levels: &market.ask_levels,
                         market_data_mode,
                         market_source: src,
                         recovery: recovery_cycle,
@@ -244,6 +256,7 @@ impl MakerRuntime {
                         inventory_exit_pct: args.inventory_exit_pct,
                         inventory_exit_qty: args.inventory_exit_qty,
                         wind_down: self.loop_state.wind_down,
+                        controls,
                         qty_tolerance,
                         session_started_at,
                         run_order_prefix,
@@ -268,6 +281,8 @@ impl MakerRuntime {
                         spread_controller: &mut self.loop_state.spread_controller,
                         size_skew_controller: &mut self.loop_state.size_skew_controller,
                         nonlinear_skew: self.loop_state.nonlinear_skew,
+                        strategy: self.loop_state.strategy.as_mut(),
+                        fair_value: self.loop_state.fair_value,
                         guard_controller: &mut self.loop_state.guard_controller,
                         external_divergence: cycle_external_divergence,
                         external_basis_bps: cycle_external_basis_bps,
@@ -275,6 +290,7 @@ impl MakerRuntime {
                         live_account_poll: cycle_account_poll.as_deref_mut(),
                         order_latency: cycle_order_latency.as_deref_mut(),
                         latency_started: cycle_latency_started,
+                        trace: &mut self.loop_state.trace,
                     },
                 )
                 .await?;
@@ -440,6 +456,7 @@ impl MakerRuntime {
                         &mut AccountEventState {
                             ledger: &mut self.loop_state.ledger,
                             stats: &mut self.loop_state.stats,
+                            strategy: self.loop_state.strategy.as_mut(),
                             projection: &mut session.projection,
                         },
                         &AccountEventContext {
@@ -788,6 +805,7 @@ impl MakerRuntime {
                             );
                         }
                     }
+                    self.persist_session();
                 }
                 Err(e) => {
                     if let Some(degraded) = e.downcast_ref::<MarketDataDegradedError>() {
@@ -885,6 +903,7 @@ impl MakerRuntime {
                                     &mut AccountEventState {
                                         ledger: &mut self.loop_state.ledger,
                                         stats: &mut self.loop_state.stats,
+                                        strategy: self.loop_state.strategy.as_mut(),
                                         projection: &mut session.projection,
                                     },
                                     &AccountEventContext {
@@ -1214,6 +1233,12 @@ impl MakerRuntime {
                         None => std::future::pending().await,
                     }
                 };
+                let control_request = async {
+                    match self.control.as_mut() {
+                        Some(control) => control.recv().await,
+                        None => std::future::pending().await,
+                    }
+                };
                 tokio::select! {
                     _ = ctrl_c_latched(&mut self.ctrl_c_rx) => {
                         self.recovery.runtime_state.handle(MakerEvent::StopRequested(RuntimeStopReason::CtrlC));
@@ -1222,6 +1247,23 @@ impl MakerRuntime {
                     _ = tokio::time::sleep_until(deadline) => break,
                     _ = request_timeout => break,
                     _ = market_wakeup => break,
+                    envelope = control_request => {
+                        let Some(envelope) = envelope else {
+                            self.control = None;
+                            continue;
+                        };
+                        if envelope.request == ControlRequest::Status {
+                            self.answer_control_status(envelope);
+                            continue;
+                        }
+                        // Commands apply at the cycle boundary, reached right
+                        // away: the replan floor is for market noise, not for
+                        // an operator reacting to news.
+                        if let Some(control) = self.control.as_mut() {
+                            control.defer(envelope);
+                        }
+                        break;
+                    },
                     ok = external_update => {
                         // Leader-feed early wake: replan only when the fresh
                         // divergence would change the guard's decision, and
@@ -1286,6 +1328,7 @@ impl MakerRuntime {
                                 &mut AccountEventState {
                                     ledger: &mut self.loop_state.ledger,
                                     stats: &mut self.loop_state.stats,
+                                    strategy: self.loop_state.strategy.as_mut(),
                                     projection: &mut session.projection,
                                 },
                                 &AccountEventContext {
@@ -262,6 +262,7 @@ pub(super) struct AccountEventContext<'a> {
 pub(super) struct AccountEventState<'a> {
     pub(super) ledger: &'a mut MakerLedger,
     pub(super) stats: &'a mut MakerStats,
+    pub(super) strategy: &'a mut dyn maker::QuoteStrategy,
     pub(super) projection: &'a mut MakerAccountProjection,
 }
 
@@ -489,6 +490,9 @@ pub(super) fn apply_account_event(
                         },
                     );
                 }
+                state
+                    .strategy
+                    .record_fill(fill.role, fill.side, fill.price, fill.mark_at_fill);
                 emit_live_fill(fill, context.symbol, context.cycle, context.output_format);
             }
             Ok(AccountEventOutcome {
@@ -548,6 +552,9 @@ pub(super) fn apply_account_event(
                         },
                     );
                 }
+                state
+                    .strategy
+                    .record_fill(fill.role, fill.side, fill.price, fill.mark_at_fill);
                 emit_live_fill(fill, context.symbol, context.cycle, context.output_format);
             }
             Ok(AccountEventOutcome {
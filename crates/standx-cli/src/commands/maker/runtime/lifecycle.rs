@@ -1,3 +1,4 @@
+use super::super::ledger::{adopt_order, apply_rest_trade};
 use super::*;
 
 pub(super) struct ShutdownReport<'a> {
@@ -5,7 +6,8 @@ pub(super) struct ShutdownReport<'a> {
     pub(super) output_format: OutputFormat,
     pub(super) symbol: &'a str,
     pub(super) cfg: &'a MakerConfig,
-    pub(super) client: &'a StandXClient,
+    /// Why cancelling the remaining maker orders failed, if it did.
+    pub(super) cleanup_error: Option<anyhow::Error>,
     pub(super) notifier: &'a MakerNotifier,
     pub(super) ledger: &'a MakerLedger,
     pub(super) stats: &'a MakerStats,
@@ -24,8 +26,7 @@ pub(super) struct ShutdownReport<'a> {
     pub(super) order_response_handle: Option<tokio::task::JoinHandle<()>>,
 }
 
-/// Abort feed/stream tasks, cancel any residual maker orders, print the human
-/// summary, and deliver the stopped-lifecycle notifications. Runs on every
+/// Abort feed/stream tasks, print the human summary, and deliver the stopped-lifecycle notifications. Runs on every
 /// exit path; returns the process result (fail-safe error or clean Ok).
 pub(super) async fn shutdown_report(report: ShutdownReport<'_>) -> Result<()> {
     let ShutdownReport {
@@ -33,7 +34,7 @@ pub(super) async fn shutdown_report(report: ShutdownReport<'_>) -> Result<()> {
         output_format,
         symbol,
         cfg,
-        client,
+        cleanup_error,
         notifier,
         ledger,
         stats,
@@ -95,16 +96,6 @@ pub(super) async fn shutdown_report(report: ShutdownReport<'_>) -> Result<()> {
     if let Some(handle) = order_response_handle {
         handle.abort();
     }
-    // Do not return early on cleanup failure: operators need the stopped
-    // lifecycle alert most when residual maker orders may still be live.
-    let cleanup_error = if live {
-        cancel_maker_orders_with_retry(client, symbol, 3, output_format)
-            .await
-            .err()
-    } else {
-        None
-    };
-
     // Notify stop on every exit path. Await delivery so the message lands
     // before the process exits.
     let reason = exit.lifecycle_reason();
@@ -134,7 +125,7 @@ pub(super) async fn shutdown_report(report: ShutdownReport<'_>) -> Result<()> {
             )
             .await;
     }
-    if !matches!(&exit, MakerExit::CtrlC) {
+    if !matches!(&exit, MakerExit::CtrlC | MakerExit::Operator) {
         notifier
             .risk(
                 RiskNotice {
@@ -208,6 +199,34 @@ impl MakerRuntime {
                 "│ size {} | max-position {} | interval {}s",
                 cfg.size, cfg.max_position, args.interval
             );
+            if args.strategy != maker::StrategyConfig::default() {
+                println!("│ strategy: {}", args.strategy.name());
+            }
+            if let Some(path) = args.state_file.as_deref() {
+                println!("│ state file: {}", path.display());
+            }
+            if args.watch_config {
+                println!(
+                    "│ config reload: watching {} (SIGHUP also reloads)",
+                    super::super::config::resolve_path(args.maker_config.as_deref()).display()
+                );
+            }
+            if let Some(path) = args.control_socket.as_deref() {
+                println!(
+                    "│ control socket: {} (`standx maker ctl --socket {}`)",
+                    path.display(),
+                    path.display()
+                );
+            }
+            if let Some(addr) = args.metrics_listen {
+                println!("│ metrics: http://{addr}/metrics");
+            }
+            if let Some(state) = startup.resume.as_ref() {
+                println!(
+                    "│ resuming session {} from cycle {} ({} fills)",
+                    state.run_order_prefix, state.counters.cycle, state.counters.total_fills
+                );
+            }
             if cfg.skew_bps > 0.0 {
                 println!(
                     "│ inventory skew {}bps (live only; paper holds no position)",
@@ -332,7 +351,73 @@ impl MakerRuntime {
         .await;
     }
 
-    pub(super) async fn shutdown(self, exit: MakerExit) -> Result<()> {
+    /// Cancel the remaining maker orders, fold in fills that raced the
+    /// cancel, then write the final `--state-file` snapshot, so a clean stop
+    /// always leaves a resumable file.
+    async fn cancel_and_persist(&mut self) -> Option<anyhow::Error> {
+        if !self.deps.args.live {
+            return None;
+        }
+        // Do not return early on cleanup failure: operators need the stopped
+        // lifecycle alert most when residual maker orders may still be live.
+        let cleanup_error = cancel_maker_orders_with_retry(
+            &self.deps.client,
+            &self.deps.symbol,
+            3,
+            self.deps.output_format,
+        )
+        .await
+        .err();
+        if self.deps.args.state_file.is_some() {
+            match self.backfill_final_fills().await {
+                Ok(0) => {}
+                Ok(count) => {
+                    eprintln!("ℹ️  {count} maker fill(s) landed during shutdown cleanup")
+                }
+                Err(error) => {
+                    eprintln!("⚠️ fills during shutdown cleanup not reconciled: {error:#}")
+                }
+            }
+        }
+        self.persist_session();
+        cleanup_error
+    }
+
+    /// Account this run's REST fills the stopped account stream never
+    /// delivered. Returns the number of fills added.
+    async fn backfill_final_fills(&mut self) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp();
+        let session_started_at = self.deps.session_started_at;
+        let audit = super::super::pipeline::fetch_account_audit(
+            &self.deps.client,
+            &self.deps.symbol,
+            session_started_at,
+            now,
+        )
+        .await?;
+        let ledger = &mut self.loop_state.ledger;
+        for order in audit.open_orders.iter().chain(audit.filled_orders.iter()) {
+            adopt_order(ledger, order, &self.deps.run_order_prefix)?;
+        }
+        let mark = self.market.last_mark.unwrap_or(self.deps.baseline_mark);
+        let mut fills = Vec::new();
+        for trade in audit.trades {
+            apply_rest_trade(
+                ledger,
+                trade,
+                session_started_at,
+                now,
+                mark,
+                &mut self.loop_state.stats,
+                &mut fills,
+            )?;
+        }
+        self.loop_state.counters.total_fills += fills.len() as u64;
+        Ok(fills.len())
+    }
+
+    pub(super) async fn shutdown(mut self, exit: MakerExit) -> Result<()> {
+        let cleanup_error = self.cancel_and_persist().await;
         let MakerRuntime {
             deps,
             loop_state,
@@ -343,7 +428,6 @@ impl MakerRuntime {
         let RuntimeDeps {
             args,
             output_format,
-            client,
             cfg,
             symbol,
             notifier,
@@ -357,6 +441,7 @@ impl MakerRuntime {
             breaker,
             sim_position,
             counters,
+            mut trace,
             ..
         } = loop_state;
         let RuntimeCounters {
@@ -374,10 +459,16 @@ impl MakerRuntime {
             ..
         } = market;
 
+        let end_time_ms = performance_epoch_ms.saturating_add(
+            i64::try_from(performance_started.elapsed().as_millis()).unwrap_or(i64::MAX),
+        );
+        super::super::cycle::trace_journal(&mut trace, &mut ledger);
+        if let Some(trace) = trace {
+            if let Err(error) = trace.finish(end_time_ms) {
+                eprintln!("⚠️ maker replay trace finalization unavailable: {error}");
+            }
+        }
         if let (Some(performance), Some(final_mark)) = (ledger.performance_mut(), last_mark) {
-            let end_time_ms = performance_epoch_ms.saturating_add(
-                i64::try_from(performance_started.elapsed().as_millis()).unwrap_or(i64::MAX),
-            );
             if let Err(error) = performance.finish(end_time_ms) {
                 eprintln!("⚠️ performance finalization unavailable: {error}");
             }
@@ -406,7 +497,7 @@ impl MakerRuntime {
             output_format,
             symbol: &symbol,
             cfg: &cfg,
-            client: &client,
+            cleanup_error,
             notifier: &notifier,
             ledger: &ledger,
             stats: &stats,
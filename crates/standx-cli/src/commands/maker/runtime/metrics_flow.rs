@@ -0,0 +1,73 @@
+use super::super::metrics::MetricsSnapshot;
+use super::*;
+use standx_sdk::models::OrderSide;
+
+impl MakerRuntime {
+    /// Hand the `--metrics-listen` endpoint a fresh snapshot; a no-op
+    /// without one.
+    pub(super) fn publish_metrics(&self) {
+        let Some(metrics) = self.metrics.as_ref() else {
+            return;
+        };
+        let performance = match (self.loop_state.ledger.performance(), self.market.last_mark) {
+            (Some(performance), Some(mark)) => performance.summary(mark).ok(),
+            _ => None,
+        };
+        let latency = self
+            .live_session
+            .as_ref()
+            .map(|session| {
+                [
+                    maker::LatencyRequestKind::Place,
+                    maker::LatencyRequestKind::Cancel,
+                ]
+                .into_iter()
+                .map(|kind| session.order_latency.summary(kind))
+                .collect()
+            })
+            .unwrap_or_default();
+        let (bids, asks) = self.resting_per_side();
+        let counters = &self.loop_state.counters;
+        metrics.publish(MetricsSnapshot {
+            symbol: self.deps.symbol.clone(),
+            live: self.deps.args.live,
+            cycles: counters.cycle,
+            places: counters.total_places,
+            cancels: counters.total_cancels,
+            fills: counters.total_fills,
+            position: self.current_position(),
+            mark: self.market.last_mark,
+            performance,
+            bids,
+            asks,
+            latency,
+            recovery_counts: self.recovery.runtime_state.recovery_counts(),
+            market_data_paused: self.market.health.is_degraded(),
+            frozen: self.recovery.runtime_state.is_frozen(),
+            breaker_halted: self.loop_state.breaker.halted(),
+            vol_bps: self.loop_state.breaker.vol_bps(),
+            guard_endangered: self.loop_state.guard_controller.endangered(),
+            wind_down: self.loop_state.wind_down,
+            controls: self.recovery.runtime_state.controls(),
+        });
+    }
+
+    /// The ledger's expected position live; the simulated one on paper.
+    pub(super) fn current_position(&self) -> f64 {
+        if self.deps.args.live {
+            self.loop_state.ledger.expected_position
+        } else {
+            self.loop_state.sim_position
+        }
+    }
+
+    /// Resting maker quotes as `(bids, asks)`.
+    pub(super) fn resting_per_side(&self) -> (usize, usize) {
+        let resting = match self.live_session.as_ref() {
+            Some(session) => session.projection.resting_quotes(),
+            None => self.loop_state.resting.clone(),
+        };
+        let side_count = |side| resting.iter().filter(|quote| quote.side == side).count();
+        (side_count(OrderSide::Buy), side_count(OrderSide::Sell))
+    }
+}
@@ -1,3 +1,5 @@
+use super::control::ControlSocket;
+use super::metrics::MetricsExporter;
 use super::output::{
     emit_live_fill, emit_reconciliation_snapshot_error, emit_reconciliation_state,
     emit_stop_loss_triggered,
@@ -5,10 +7,13 @@ use super::output::{
 use super::*;
 use standx_sdk::order_response::OrderResponse;
 
+mod control_flow;
 mod cycle_flow;
 mod events;
 mod lifecycle;
+mod metrics_flow;
 mod recovery_flow;
+mod reload_flow;
 mod state;
 
 #[cfg(test)]
@@ -33,9 +38,20 @@ pub(super) async fn run_maker(
     args: MakerRunArgs,
     output_format: OutputFormat,
 ) -> Result<()> {
+    // Bind before startup touches the venue, so a busy socket path or port
+    // fails without side effects.
+    let control = args
+        .control_socket
+        .as_deref()
+        .map(ControlSocket::bind)
+        .transpose()?;
+    let metrics = match args.metrics_listen {
+        Some(addr) => Some(MetricsExporter::bind(addr).await?),
+        None => None,
+    };
     let startup = run_startup(symbol, &args, output_format).await?;
     MakerRuntime::announce_start(&args, output_format, &startup).await;
-    let runtime = MakerRuntime::new(args, output_format, startup)?;
+    let runtime = MakerRuntime::new(args, output_format, startup, control, metrics)?;
     let (runtime, exit) = runtime.drive().await;
     runtime.shutdown(exit).await
 }
@@ -850,6 +850,7 @@ impl MakerRuntime {
                         &mut AccountEventState {
                             ledger: &mut self.loop_state.ledger,
                             stats: &mut self.loop_state.stats,
+                            strategy: self.loop_state.strategy.as_mut(),
                             projection,
                         },
                         &AccountEventContext {
@@ -922,6 +923,7 @@ impl MakerRuntime {
                                 &mut AccountEventState {
                                     ledger: &mut self.loop_state.ledger,
                                     stats: &mut self.loop_state.stats,
+                                    strategy: self.loop_state.strategy.as_mut(),
                                     projection,
                                 },
                                 &AccountEventContext {
@@ -1199,7 +1201,7 @@ impl MakerRuntime {
                                         if output_format == OutputFormat::Json {
                                             println!(
                                                 "{}",
-                                                serde_json::json!({
+                                                crate::output::format_event(&serde_json::json!({
                                                     "ts": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
                                                     "symbol": symbol,
                                                     "action": "position_reconciliation",
@@ -1207,7 +1209,7 @@ impl MakerRuntime {
                                                     "expected_position": reconciliation.expected,
                                                     "observed_position": reconciliation.observed,
                                                     "message": "post-reconnect venue position cannot be explained by current-run maker fills",
-                                                })
+                                                }))
                                             );
                                         }
                                         let reconciliation_message = format!(
@@ -1494,6 +1496,7 @@ impl MakerRuntime {
                 &mut AccountEventState {
                     ledger: &mut self.loop_state.ledger,
                     stats: &mut self.loop_state.stats,
+                    strategy: self.loop_state.strategy.as_mut(),
                     projection: &mut session.projection,
                 },
                 &AccountEventContext {
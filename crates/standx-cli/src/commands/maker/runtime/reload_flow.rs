@@ -0,0 +1,71 @@
+use super::super::config;
+use super::super::output::emit_config_reload;
+use super::super::reload::{self, ReloadPlan};
+use super::*;
+
+impl MakerRuntime {
+    /// Apply a pending SIGHUP / `--watch-config` reload. Runs between cycles,
+    /// so every cycle plans against one consistent config.
+    pub(super) fn reload_config_if_requested(&mut self) {
+        let mut trigger = None;
+        while let Ok(next) = self.reload_rx.try_recv() {
+            trigger = Some(next);
+        }
+        let Some(trigger) = trigger else {
+            return;
+        };
+        let path = config::resolve_path(self.deps.args.maker_config.as_deref());
+        let plan = reload::plan(
+            &self.deps.args,
+            &self.deps.cfg,
+            self.recovery.runtime_state.controls(),
+            &self.deps.symbol,
+            config::load(Some(&path)),
+        );
+        emit_config_reload(
+            self.deps.output_format,
+            &self.deps.symbol,
+            self.loop_state.counters.cycle,
+            trigger,
+            &path,
+            &plan,
+        );
+        if let ReloadPlan::Apply { args, cfg, .. } = plan {
+            self.apply_reloaded_config(*args, cfg);
+        }
+    }
+
+    /// Swap in validated settings. Controllers are rebuilt only when their
+    /// own config changed, so an unrelated edit keeps their hysteresis; alert
+    /// latches and the position-alert anchor carry over under the new
+    /// thresholds. Resting quotes follow the usual anti-flicker rules.
+    fn apply_reloaded_config(&mut self, args: MakerRunArgs, cfg: MakerConfig) {
+        let loop_state = &mut self.loop_state;
+        let current = &self.deps.args;
+        if args.adaptive_spread != current.adaptive_spread {
+            // Validated by the reload plan against this same config.
+            if let Ok(controller) = maker::SpreadController::new(args.adaptive_spread.clone(), &cfg)
+            {
+                loop_state.spread_controller = controller;
+            }
+        }
+        if args.size_skew != current.size_skew {
+            if let Ok(controller) = maker::SizeSkewController::new(args.size_skew, &cfg) {
+                loop_state.size_skew_controller = controller;
+            }
+        }
+        loop_state.nonlinear_skew = args.nonlinear_skew;
+        loop_state.fair_value = args.fair_value;
+        loop_state.alerts.retune(
+            AlertMonitor::new(args.alert_loss, args.alert_inventory_pct, args.alert_uptime)
+                .with_account_floors(args.alert_equity_below, args.alert_margin_below),
+        );
+        loop_state.position_alert_anchor = PositionAlertAnchor::restore(
+            loop_state.position_alert_anchor.snapshot(),
+            args.alert_position_change_pct,
+            cfg.size / 2.0,
+        );
+        self.deps.args = args;
+        self.deps.cfg = cfg;
+    }
+}
@@ -1,4 +1,9 @@
+use super::super::control::ControlSocket;
 use super::super::feed::FeedState;
+use super::super::metrics::MetricsExporter;
+use super::super::reload::{self, ReloadTrigger};
+use super::super::replay::TraceWriter;
+use super::super::state_file::{self, StateCounters, StateFile};
 use super::*;
 
 pub(super) struct RuntimeDeps {
@@ -26,6 +31,32 @@ pub(super) struct RuntimeCounters {
     pub(super) total_halted: u64,
 }
 
+impl From<StateCounters> for RuntimeCounters {
+    fn from(value: StateCounters) -> Self {
+        Self {
+            cycle: value.cycle,
+            total_places: value.total_places,
+            total_cancels: value.total_cancels,
+            total_holds: value.total_holds,
+            total_fills: value.total_fills,
+            total_halted: value.total_halted,
+        }
+    }
+}
+
+impl From<&RuntimeCounters> for StateCounters {
+    fn from(value: &RuntimeCounters) -> Self {
+        Self {
+            cycle: value.cycle,
+            total_places: value.total_places,
+            total_cancels: value.total_cancels,
+            total_holds: value.total_holds,
+            total_fills: value.total_fills,
+            total_halted: value.total_halted,
+        }
+    }
+}
+
 pub(super) struct RuntimeLoopState {
     pub(super) resting: Vec<RestingQuote>,
     pub(super) inventory_exit_pending: bool,
@@ -44,6 +75,8 @@ pub(super) struct RuntimeLoopState {
     pub(super) spread_controller: maker::SpreadController,
     pub(super) size_skew_controller: maker::SizeSkewController,
     pub(super) nonlinear_skew: maker::NonlinearSkewConfig,
+    pub(super) strategy: Box<dyn maker::QuoteStrategy>,
+    pub(super) fair_value: maker::FairValueConfig,
     pub(super) guard_controller: maker::GuardController,
     /// Latest leader (Hyperliquid) sample for the external guard; `None` when
     /// the guard is disabled and no feed task runs.
@@ -59,8 +92,13 @@ pub(super) struct RuntimeLoopState {
     #[allow(dead_code)]
     pub(super) external_feed_handle: Option<tokio::task::JoinHandle<()>>,
     pub(super) alerts: AlertMonitor,
+    /// Replay trace for `--trace-out`, fed by the performance ledger journal.
+    pub(super) trace: Option<TraceWriter>,
     pub(super) account_balance_refresh_requested: bool,
     pub(super) balance_floor_parse_warned: bool,
+    /// Latched after a failed `--state-file` write so a full disk warns once
+    /// instead of every cycle; cleared by the next successful write.
+    pub(super) state_file_warned: bool,
 }
 
 pub(super) struct RuntimeMarketState {
@@ -100,6 +138,14 @@ pub(super) struct MakerRuntime {
     pub(super) live_session: Option<LiveSession>,
     pub(super) ctrl_c_rx: tokio::sync::watch::Receiver<bool>,
     pub(super) wind_down_rx: tokio::sync::watch::Receiver<bool>,
+    /// Pending config reload requests (SIGHUP / `--watch-config`), applied
+    /// at the next cycle boundary.
+    pub(super) reload_rx: tokio::sync::mpsc::Receiver<ReloadTrigger>,
+    /// `--control-socket` listener; operator requests are answered between
+    /// cycles.
+    pub(super) control: Option<ControlSocket>,
+    /// `--metrics-listen` endpoint; refreshed at every loop boundary.
+    pub(super) metrics: Option<MetricsExporter>,
 }
 
 pub(super) enum LoopDirective {
@@ -113,6 +159,8 @@ impl MakerRuntime {
         args: MakerRunArgs,
         output_format: OutputFormat,
         startup: MakerStartup,
+        control: Option<ControlSocket>,
+        metrics: Option<MetricsExporter>,
     ) -> Result<Self> {
         let MakerStartup {
             live_process_lock,
@@ -126,6 +174,7 @@ impl MakerRuntime {
             baseline_mark,
             session_started_at,
             live_session,
+            resume,
         } = startup;
 
         let (feed, updates, feed_handle) = if args.no_ws {
@@ -136,20 +185,32 @@ impl MakerRuntime {
         };
         let market_watchdog_updates = updates.as_ref().cloned();
 
-        let mut ledger = MakerLedger::new(starting_position);
-        ledger.enable_performance(baseline_mark)?;
+        let (mut ledger, stats, position_alert_anchor, counters) = match resume {
+            Some(state) => {
+                let (ledger, stats, anchor) = maker::SessionSnapshot::from(state.session)
+                    .restore(args.alert_position_change_pct, cfg.size / 2.0)?;
+                (ledger, stats, anchor, RuntimeCounters::from(state.counters))
+            }
+            None => (
+                MakerLedger::new(starting_position),
+                if args.live {
+                    MakerStats::with_inventory_baseline(starting_position, baseline_mark)
+                } else {
+                    MakerStats::default()
+                },
+                PositionAlertAnchor::new(
+                    starting_position,
+                    args.alert_position_change_pct,
+                    cfg.size / 2.0,
+                ),
+                RuntimeCounters::default(),
+            ),
+        };
+        if ledger.performance().is_none() {
+            ledger.enable_performance(baseline_mark)?;
+        }
         let performance_started = std::time::Instant::now();
         let performance_epoch_ms = chrono::Utc::now().timestamp_millis();
-        let position_alert_anchor = PositionAlertAnchor::new(
-            starting_position,
-            args.alert_position_change_pct,
-            cfg.size / 2.0,
-        );
-        let stats = if args.live {
-            MakerStats::with_inventory_baseline(starting_position, baseline_mark)
-        } else {
-            MakerStats::default()
-        };
         let breaker = match args.vol_window_secs {
             Some(seconds) => VolBreaker::new_duration(
                 seconds
@@ -161,10 +222,10 @@ impl MakerRuntime {
         };
         let spread_controller = maker::SpreadController::new(args.adaptive_spread.clone(), &cfg)?;
         let size_skew_controller = maker::SizeSkewController::new(args.size_skew, &cfg)?;
-        // Stage 3 v1 combined candidate: validate both switches up front so a
-        // bad file never rides along silently (band red line included).
+        // Startup already validated these with the rest of the run config.
         let nonlinear_skew = args.nonlinear_skew;
-        nonlinear_skew.validate(&cfg)?;
+        let strategy = args.strategy.build();
+        let fair_value = args.fair_value;
         let guard_basis_half_life_secs = args.external_guard_basis_half_life_secs;
         let guard_controller = maker::GuardController::new(args.external_guard)?;
         let (external_feed, external_updates, external_feed_handle) = if args.external_guard.enabled
@@ -185,6 +246,41 @@ impl MakerRuntime {
         let alerts =
             AlertMonitor::new(args.alert_loss, args.alert_inventory_pct, args.alert_uptime)
                 .with_account_floors(args.alert_equity_below, args.alert_margin_below);
+        let trace = match args.trace_out.as_deref() {
+            Some(path) => {
+                // Schema v1 replays the planner without these controllers, so
+                // a trace of a session that used them cannot match bit-for-bit.
+                if args.nonlinear_skew.enabled || args.external_guard.enabled {
+                    eprintln!(
+                        "⚠️ replay trace v1 does not model nonlinear skew or the external guard; replay of {} will diverge",
+                        path.display()
+                    );
+                }
+                let settings = maker::ReplaySettings {
+                    // The resume point under `--resume`; equal to the session
+                    // starting position otherwise.
+                    starting_position: ledger.expected_position,
+                    starting_mark: baseline_mark,
+                    max_divergence_bps: args.max_divergence_bps,
+                    require_full_touch: args.live,
+                    vol_window: args.vol_window.max(1) as usize,
+                    vol_window_secs: args.vol_window_secs,
+                    vol_pause_bps: args.vol_pause_bps,
+                    adaptive_spread: args.adaptive_spread.clone(),
+                    size_skew: args.size_skew,
+                    active_exit_enabled: args.live,
+                    inventory_exit_pct: args.inventory_exit_pct,
+                    inventory_exit_qty: args.inventory_exit_qty,
+                    strategy: args.strategy.clone(),
+                };
+                let writer = TraceWriter::create(path, &symbol, &cfg, &settings)?;
+                if let Some(performance) = ledger.performance_mut() {
+                    performance.enable_journal();
+                }
+                Some(writer)
+            }
+            None => None,
+        };
         let market_health_started = std::time::Instant::now();
         let mut runtime_state = MakerState::starting();
         runtime_state.handle(MakerEvent::StartupReady);
@@ -198,6 +294,14 @@ impl MakerRuntime {
         // on the venue (observed on the 2026-07-17 stage-2 arm boundary).
         let (ctrl_c_tx, ctrl_c_rx) = tokio::sync::watch::channel(false);
         let (wind_down_tx, wind_down_rx) = tokio::sync::watch::channel(false);
+        // One slot: requests arriving while a reload is pending coalesce.
+        let (reload_tx, reload_rx) = tokio::sync::mpsc::channel(1);
+        if args.watch_config {
+            reload::spawn_config_watcher(
+                super::super::config::resolve_path(args.maker_config.as_deref()),
+                reload_tx.clone(),
+            );
+        }
         tokio::spawn(async move {
             #[cfg(unix)]
             {
@@ -214,6 +318,11 @@ impl MakerRuntime {
                 let mut sigusr1 =
                     tokio::signal::unix::signal(tokio::signal::unix::SignalKind::user_defined1())
                         .expect("failed to install SIGUSR1 handler");
+                // SIGHUP reloads the maker TOML (the daemon convention) rather
+                // than killing the process with its quotes still resting.
+                let mut sighup =
+                    tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                        .expect("failed to install SIGHUP handler");
                 loop {
                     tokio::select! {
                         _ = sigint.recv() => {
@@ -225,11 +334,15 @@ impl MakerRuntime {
                         _ = sigusr1.recv() => {
                             let _ = wind_down_tx.send(true);
                         }
+                        _ = sighup.recv() => {
+                            let _ = reload_tx.try_send(ReloadTrigger::Signal);
+                        }
                     }
                 }
             }
             #[cfg(not(unix))]
             {
+                drop(reload_tx);
                 while tokio::signal::ctrl_c().await.is_ok() {
                     let _ = ctrl_c_tx.send(true);
                 }
@@ -259,7 +372,7 @@ impl MakerRuntime {
                 performance_started,
                 performance_epoch_ms,
                 position_alert_anchor,
-                counters: RuntimeCounters::default(),
+                counters,
                 next_cycle_is_recovery: false,
                 sim_position: 0.0,
                 stats,
@@ -267,6 +380,8 @@ impl MakerRuntime {
                 spread_controller,
                 size_skew_controller,
                 nonlinear_skew,
+                strategy,
+                fair_value,
                 guard_controller,
                 external_feed,
                 external_updates,
@@ -275,8 +390,10 @@ impl MakerRuntime {
                 ),
                 external_feed_handle,
                 alerts,
+                trace,
                 account_balance_refresh_requested: false,
                 balance_floor_parse_warned: false,
+                state_file_warned: false,
             },
             market: RuntimeMarketState {
                 feed,
@@ -306,6 +423,51 @@ impl MakerRuntime {
             live_session,
             ctrl_c_rx,
             wind_down_rx,
+            reload_rx,
+            control,
+            metrics,
         })
     }
+
+    /// Write the `--state-file` snapshot, if configured. A failed write warns
+    /// but never stops quoting: the previous snapshot stays intact, and a
+    /// stale file is caught by the venue checks on `--resume`.
+    pub(super) fn persist_session(&mut self) {
+        let Some(path) = self.deps.args.state_file.as_deref() else {
+            return;
+        };
+        let saved_at_ms = self.loop_state.performance_epoch_ms.saturating_add(
+            i64::try_from(self.loop_state.performance_started.elapsed().as_millis())
+                .unwrap_or(i64::MAX),
+        );
+        let result = maker::SessionSnapshot::capture(
+            &self.loop_state.ledger,
+            &self.loop_state.stats,
+            &self.loop_state.position_alert_anchor,
+            saved_at_ms,
+        )
+        .map_err(anyhow::Error::from)
+        .and_then(|session| {
+            state_file::save(
+                path,
+                &StateFile::new(
+                    &self.deps.symbol,
+                    &self.deps.run_order_prefix,
+                    self.deps.session_started_at,
+                    self.deps.starting_position,
+                    saved_at_ms,
+                    StateCounters::from(&self.loop_state.counters),
+                    session,
+                ),
+            )
+        });
+        match result {
+            Ok(()) => self.loop_state.state_file_warned = false,
+            Err(error) if !self.loop_state.state_file_warned => {
+                self.loop_state.state_file_warned = true;
+                eprintln!("⚠️ maker state file not written: {error:#}");
+            }
+            Err(_) => {}
+        }
+    }
 }
@@ -11,6 +11,7 @@ fn drain_positions(events: Vec<AccountEvent>) -> AccountEventOutcome {
     let mut state = AccountEventState {
         ledger: &mut ledger,
         stats: &mut stats,
+        strategy: &mut maker::LadderStrategy,
         projection: &mut projection,
     };
     let context = AccountEventContext {
@@ -88,6 +89,7 @@ fn balance_event_updates_raw_projection_without_touching_fill_accounting() {
         let mut state = AccountEventState {
             ledger: &mut ledger,
             stats: &mut stats,
+            strategy: &mut maker::LadderStrategy,
             projection: &mut projection,
         };
         apply_account_event(
@@ -121,6 +123,7 @@ fn uncorrelated_current_run_order_requires_reconciliation_without_stream_failure
     let mut state = AccountEventState {
         ledger: &mut ledger,
         stats: &mut stats,
+        strategy: &mut maker::LadderStrategy,
         projection: &mut projection,
     };
     let context = AccountEventContext {
@@ -211,6 +214,7 @@ fn stable_trade_reports_current_run_inventory_exit_once() {
     let mut state = AccountEventState {
         ledger: &mut ledger,
         stats: &mut stats,
+        strategy: &mut maker::LadderStrategy,
         projection: &mut projection,
     };
     let context = AccountEventContext {
@@ -1,4 +1,5 @@
-use super::output::{emit_ledger_sync, emit_startup_rejected};
+use super::output::{emit_ledger_sync, emit_session_resumed, emit_startup_rejected};
+use super::state_file::{self, StateFile};
 use super::*;
 use standx_sdk::order_response::{OrderCommandSender, OrderResponse, OrderResponseHealth};
 
@@ -62,6 +63,113 @@ pub(super) fn validate_alert_thresholds(
     Ok(())
 }
 
+/// Overlay the strategy settings in `args` on the venue's tick and minimum
+/// quantity metadata for the symbol.
+pub(super) fn maker_config(
+    args: &MakerRunArgs,
+    price_decimals: u32,
+    qty_decimals: u32,
+    min_order_qty: f64,
+) -> MakerConfig {
+    MakerConfig {
+        spread_bps: args.spread_bps,
+        band_bps: args.band_bps,
+        level_step_bps: args.level_step_bps,
+        refresh_bps: args.refresh_bps,
+        levels: args.levels.max(1),
+        size: args.size,
+        max_position: args.max_position,
+        skew_bps: args.skew_bps,
+        price_decimals,
+        qty_decimals,
+        min_order_qty,
+    }
+}
+
+/// The strategy checks a run must pass before quoting, shared by startup and
+/// config reload so a reloaded file can never arm what startup would refuse.
+pub(super) fn validate_run_config(
+    args: &MakerRunArgs,
+    cfg: &MakerConfig,
+    symbol: &str,
+) -> Result<()> {
+    if cfg.spread_bps <= 0.0 {
+        return Err(anyhow::anyhow!("--spread-bps must be > 0"));
+    }
+    if args.interval == 0 {
+        return Err(anyhow::anyhow!("--interval must be at least 1 second"));
+    }
+    if args.vol_window_secs == Some(0) {
+        return Err(anyhow::anyhow!(
+            "vol_window_secs in TOML must be greater than 0"
+        ));
+    }
+    if cfg.skew_bps < 0.0 {
+        return Err(anyhow::anyhow!("--skew-bps must be >= 0"));
+    }
+    if !(0.0..=100.0).contains(&args.inventory_exit_pct) || args.inventory_exit_qty < 0.0 {
+        return Err(anyhow::anyhow!(
+            "--inventory-exit-pct must be 0..=100 and --inventory-exit-qty must be >= 0"
+        ));
+    }
+    if (args.inventory_exit_pct > 0.0) != (args.inventory_exit_qty > 0.0) {
+        return Err(anyhow::anyhow!(
+            "active inventory exit requires both --inventory-exit-pct and --inventory-exit-qty"
+        ));
+    }
+    validate_alert_thresholds(
+        args.alert_loss,
+        args.alert_inventory_pct,
+        args.alert_position_change_pct,
+        args.alert_uptime,
+    )?;
+    if cfg.band_bps <= cfg.spread_bps {
+        return Err(anyhow::anyhow!(
+            "--band-bps ({}) must be greater than --spread-bps ({}): quotes clamped to the band edge would sit exactly at the boundary",
+            cfg.band_bps,
+            cfg.spread_bps
+        ));
+    }
+    maker::SpreadController::new(args.adaptive_spread.clone(), cfg)
+        .map_err(|error| anyhow::anyhow!("invalid adaptive spread config: {error}"))?;
+    maker::SizeSkewController::new(args.size_skew, cfg)
+        .map_err(|error| anyhow::anyhow!("invalid size skew config: {error}"))?;
+    let rounded_size = maker::round_to_decimals(cfg.size, cfg.qty_decimals);
+    if rounded_size < cfg.min_order_qty || rounded_size <= 0.0 {
+        return Err(anyhow::anyhow!(
+            "--size {} (rounded to {} at {} decimals) is below min order qty {} for {}",
+            cfg.size,
+            rounded_size,
+            cfg.qty_decimals,
+            cfg.min_order_qty,
+            symbol
+        ));
+    }
+    // Stage 3 v1 combined candidate: validate both switches up front so a
+    // bad file never rides along silently (band red line included).
+    args.nonlinear_skew.validate(cfg)?;
+    args.strategy.validate()?;
+    args.fair_value.validate(cfg)?;
+    Ok(())
+}
+
+/// A live run must keep at least one threshold armed, or the required
+/// webhook could never fire.
+pub(super) fn validate_live_alerts(args: &MakerRunArgs) -> Result<()> {
+    if args.alert_loss <= 0.0
+        && args.alert_inventory_pct <= 0.0
+        && args.alert_position_change_pct <= 0.0
+        && args.alert_uptime <= 0.0
+        && args.alert_equity_below <= 0.0
+        && args.alert_margin_below <= 0.0
+    {
+        return Err(anyhow::anyhow!(
+            "live mode requires at least one alert threshold; all maker and account thresholds are 0 so the webhook would never fire"
+        ));
+    }
+    Ok(())
+}
+
 /// Everything the quoting loop needs after startup: the REST client, resolved
 /// config, canonical symbol casing, the notifier, and — in live mode — the
 /// initialized [`LiveSession`] plus the adopted ledger baseline.
@@ -77,6 +185,8 @@ pub(super) struct MakerStartup {
     pub(super) baseline_mark: f64,
     pub(super) session_started_at: i64,
     pub(super) live_session: Option<LiveSession>,
+    /// The saved session under `--resume`, already checked against the venue.
+    pub(super) resume: Option<StateFile>,
 }
 
 /// Validate arguments, resolve symbol metadata into a [`MakerConfig`], and — in
@@ -95,8 +205,23 @@ pub(super) async fn run_startup(
         .then(super::process_lock::LiveProcessLock::acquire)
         .transpose()?;
     let order_session_id = args.live.then(|| uuid::Uuid::new_v4().to_string());
-    let run_uuid = uuid::Uuid::new_v4().simple().to_string();
-    let run_order_prefix = format!("{}{}-", MAKER_CL_ORD_ID_PREFIX, &run_uuid[..12]);
+    if args.state_file.is_some() && !args.live {
+        return Err(anyhow::anyhow!("--state-file requires --live"));
+    }
+    let mut resume = match (args.resume, args.state_file.as_deref()) {
+        (true, Some(path)) => Some(state_file::load(path)?),
+        (true, None) => return Err(anyhow::anyhow!("--resume requires --state-file")),
+        (false, _) => None,
+    };
+    // A resumed session keeps its run prefix so its saved order IDs stay
+    // current-run and its client order IDs continue from the saved cycle.
+    let run_order_prefix = match resume.as_ref() {
+        Some(state) => state.run_order_prefix.clone(),
+        None => {
+            let run_uuid = uuid::Uuid::new_v4().simple().to_string();
+            format!("{}{}-", MAKER_CL_ORD_ID_PREFIX, &run_uuid[..12])
+        }
+    };
     let mut starting_position = 0.0_f64;
     let mut baseline_mark = 0.0_f64;
     let mut session_started_at = chrono::Utc::now().timestamp();
@@ -167,6 +292,22 @@ pub(super) async fn run_startup(
         ));
     }
     let symbol = info.symbol.clone(); // canonical casing
+    if let Some(state) = resume.as_ref() {
+        if !state.symbol.eq_ignore_ascii_case(&symbol) {
+            return Err(anyhow::anyhow!(
+                "maker state file is for {}, not {}",
+                state.symbol,
+                symbol
+            ));
+        }
+        if !state.run_order_prefix.starts_with(MAKER_CL_ORD_ID_PREFIX) {
+            return Err(anyhow::anyhow!(
+                "maker state file run prefix '{}' is not a {} prefix",
+                state.run_order_prefix,
+                MAKER_CL_ORD_ID_PREFIX
+            ));
+        }
+    }
 
     let min_order_qty: f64 = info.min_order_qty.parse().map_err(|_| {
         anyhow::anyhow!(
@@ -175,72 +316,15 @@ pub(super) async fn run_startup(
             info.symbol
         )
     })?;
-    let cfg = MakerConfig {
-        spread_bps: args.spread_bps,
-        band_bps: args.band_bps,
-        level_step_bps: args.level_step_bps,
-        refresh_bps: args.refresh_bps,
-        levels: args.levels.max(1),
-        size: args.size,
-        max_position: args.max_position,
-        skew_bps: args.skew_bps,
-        price_decimals: info.price_tick_decimals,
-        qty_decimals: info.qty_tick_decimals,
+    let cfg = maker_config(
+        args,
+        info.price_tick_decimals,
+        info.qty_tick_decimals,
         min_order_qty,
-    };
-
-    if cfg.spread_bps <= 0.0 {
-        return Err(anyhow::anyhow!("--spread-bps must be > 0"));
-    }
-    if args.interval == 0 {
-        return Err(anyhow::anyhow!("--interval must be at least 1 second"));
-    }
-    if args.vol_window_secs == Some(0) {
-        return Err(anyhow::anyhow!(
-            "vol_window_secs in TOML must be greater than 0"
-        ));
-    }
-    if cfg.skew_bps < 0.0 {
-        return Err(anyhow::anyhow!("--skew-bps must be >= 0"));
-    }
-    if !(0.0..=100.0).contains(&args.inventory_exit_pct) || args.inventory_exit_qty < 0.0 {
-        return Err(anyhow::anyhow!(
-            "--inventory-exit-pct must be 0..=100 and --inventory-exit-qty must be >= 0"
-        ));
-    }
-    if (args.inventory_exit_pct > 0.0) != (args.inventory_exit_qty > 0.0) {
-        return Err(anyhow::anyhow!(
-            "active inventory exit requires both --inventory-exit-pct and --inventory-exit-qty"
-        ));
-    }
-    validate_alert_thresholds(
-        args.alert_loss,
-        args.alert_inventory_pct,
-        args.alert_position_change_pct,
-        args.alert_uptime,
-    )?;
-    if cfg.band_bps <= cfg.spread_bps {
-        return Err(anyhow::anyhow!(
-            "--band-bps ({}) must be greater than --spread-bps ({}): quotes clamped to the band edge would sit exactly at the boundary",
-            cfg.band_bps,
-            cfg.spread_bps
-        ));
-    }
-    maker::SpreadController::new(args.adaptive_spread.clone(), &cfg)
-        .map_err(|error| anyhow::anyhow!("invalid adaptive spread config: {error}"))?;
+    );
+    validate_run_config(args, &cfg, &symbol)?;
     let size_skew_controller = maker::SizeSkewController::new(args.size_skew, &cfg)
         .map_err(|error| anyhow::anyhow!("invalid size skew config: {error}"))?;
-    let rounded_size = maker::round_to_decimals(cfg.size, cfg.qty_decimals);
-    if rounded_size < cfg.min_order_qty || rounded_size <= 0.0 {
-        return Err(anyhow::anyhow!(
-            "--size {} (rounded to {} at {} decimals) is below min order qty {} for {}",
-            cfg.size,
-            rounded_size,
-            cfg.qty_decimals,
-            cfg.min_order_qty,
-            symbol
-        ));
-    }
     if size_skew_controller.is_degenerate(&cfg) {
         eprintln!("⚠️  size skew 缩量低于最小下单量，退化为二值加仓侧压制");
     }
@@ -294,17 +378,7 @@ pub(super) async fn run_startup(
                 "live mode requires --alert-webhook so the maker can push risk/stop notifications; refusing to run live with no push channel"
             ));
         }
-        if args.alert_loss <= 0.0
-            && args.alert_inventory_pct <= 0.0
-            && args.alert_position_change_pct <= 0.0
-            && args.alert_uptime <= 0.0
-            && args.alert_equity_below <= 0.0
-            && args.alert_margin_below <= 0.0
-        {
-            return Err(anyhow::anyhow!(
-                "live mode requires at least one alert threshold; all maker and account thresholds are 0 so the webhook would never fire"
-            ));
-        }
+        validate_live_alerts(args)?;
         let creds = Credentials::load()?;
         if creds.is_expired() {
             return Err(anyhow::anyhow!(
@@ -327,6 +401,14 @@ pub(super) async fn run_startup(
                 manual_orders, symbol, MAKER_CL_ORD_ID_PREFIX
             );
         }
+        if resume.is_some() {
+            if let Err(error) = maker::check_resume_orders(
+                &run_order_prefix,
+                open_orders.iter().map(|order| order.cl_ord_id.as_deref()),
+            ) {
+                return Err(reject_resume(&notifier, &symbol, error, None).await);
+            }
+        }
         // Clean only leftover orders owned by this maker. Manual/API orders
         // are not part of the strategy's reconciliation state and must never
         // be adopted or cancelled as stale.
@@ -410,6 +492,24 @@ pub(super) async fn run_startup(
             ));
         }
         session_started_at = chrono::Utc::now().timestamp();
+        if let Some(state) = resume.as_mut() {
+            let backfilled =
+                state.backfill(&filled_orders, &historical_trades, mark, history_to)?;
+            if backfilled > 0 {
+                eprintln!(
+                    "ℹ️  resumed session picked up {backfilled} fill(s) made after its last snapshot"
+                );
+            }
+            let snapshot = maker::SessionSnapshot::from(state.session.clone());
+            if let Err(error) =
+                maker::check_resume_position(&snapshot, starting_position, qty_tolerance)
+            {
+                return Err(
+                    reject_resume(&notifier, &symbol, error, Some(starting_position)).await,
+                );
+            }
+            session_started_at = state.session_started_at;
+        }
 
         // Authenticated account state is a hard live dependency. Connect it
         // before order-response readiness, then require a second REST
@@ -491,15 +591,19 @@ pub(super) async fn run_startup(
             order_latency: maker::OrderLatencyTracker::default(),
             latency_started: std::time::Instant::now(),
         });
-        emit_ledger_sync(
-            output_format,
-            &symbol,
-            starting_position,
-            baseline_mark,
-            historical_maker_orders,
-            historical_maker_trades,
-        );
-        if starting_position.abs() > qty_tolerance {
+        if let Some(state) = resume.as_ref() {
+            emit_session_resumed(output_format, &symbol, state, starting_position);
+        } else {
+            emit_ledger_sync(
+                output_format,
+                &symbol,
+                starting_position,
+                baseline_mark,
+                historical_maker_orders,
+                historical_maker_trades,
+            );
+        }
+        if resume.is_none() && starting_position.abs() > qty_tolerance {
             let message = format!("adopted non-zero starting inventory {starting_position:+.8}");
             notifier
                 .risk(
@@ -529,9 +633,47 @@ pub(super) async fn run_startup(
         notifier,
         qty_tolerance,
         run_order_prefix,
-        starting_position,
+        // Session telemetry reports the original boundary, not the resume.
+        starting_position: resume
+            .as_ref()
+            .map_or(starting_position, |state| state.starting_position),
         baseline_mark,
         session_started_at,
         live_session,
+        resume,
     })
 }
+
+/// Alert and build the startup error for a saved session the venue no longer
+/// matches. Resuming it would corrupt session PnL, so a human must choose
+/// between reconciling the file and starting a fresh session.
+async fn reject_resume(
+    notifier: &MakerNotifier,
+    symbol: &str,
+    error: maker::ResumeError,
+    observed: Option<f64>,
+) -> anyhow::Error {
+    let expected = match &error {
+        maker::ResumeError::PositionMismatch { expected, .. } => Some(*expected),
+        maker::ResumeError::ForeignMakerOrder { .. } => None,
+    };
+    let message = format!("refusing to resume maker session: {error}");
+    notifier
+        .risk(
+            RiskNotice {
+                kind: "session_resume",
+                severity: "critical",
+                event: "resume_rejected",
+                message: &message,
+                symbol,
+                cycle: 0,
+                position_before: None,
+                position_after: observed,
+                expected,
+                observed,
+            },
+            true,
+        )
+        .await;
+    anyhow::anyhow!("{message}; restart without --resume to begin a new session")
+}
@@ -0,0 +1,683 @@
+//! Durable `maker run --state-file` snapshots for `--resume`.
+//!
+//! [`StateFile`] is the on-disk JSON form of a [`SessionSnapshot`] plus the
+//! identity a resumed process must reuse: symbol, run order prefix, session
+//! start, and the cycle counters that keep client order IDs unique under that
+//! prefix. [`save`] replaces the file atomically, so a crash mid-write leaves
+//! the previous snapshot intact.
+
+use super::ledger::{adopt_order, apply_rest_trade};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use standx_maker::{
+    ExecutionCosts, InventoryTimeSummary, LedgerSnapshot, MakerStatsSnapshot, MarkoutAccumulator,
+    PerformanceSnapshot, PositionAlertSnapshot, QuoteTimeSummary, SessionSnapshot,
+};
+use standx_sdk::models::{Order, Trade};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+
+const STATE_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct StateFile {
+    pub(super) schema_version: u32,
+    pub(super) symbol: String,
+    pub(super) run_order_prefix: String,
+    /// Unix seconds; bounds REST trade backfill for the whole logical session.
+    pub(super) session_started_at: i64,
+    pub(super) starting_position: f64,
+    pub(super) saved_at_ms: i64,
+    pub(super) counters: StateCounters,
+    pub(super) session: StateSession,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct StateCounters {
+    pub(super) cycle: u64,
+    pub(super) total_places: u64,
+    pub(super) total_cancels: u64,
+    pub(super) total_holds: u64,
+    pub(super) total_fills: u64,
+    pub(super) total_halted: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct StateSession {
+    ledger: StateLedger,
+    stats: StateStats,
+    position_alert: StatePositionAlert,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateLedger {
+    expected_position: f64,
+    maker_order_ids: Vec<u64>,
+    exit_order_ids: Vec<u64>,
+    seen_trade_ids: Vec<u64>,
+    performance: Option<StatePerformance>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StatePerformance {
+    starting_position: f64,
+    starting_mark: f64,
+    position: f64,
+    fill_cash: f64,
+    passive_fills: u64,
+    passive_qty: f64,
+    passive_cashflow_quote: f64,
+    passive_capture_bps_qty_sum: f64,
+    exit_fills: u64,
+    exit_qty: f64,
+    exit_cashflow_quote: f64,
+    gross_spread_quote: f64,
+    fee_quote: f64,
+    rebate_quote: f64,
+    funding_quote: f64,
+    funding_observed: bool,
+    exit_cost_quote: f64,
+    seen_trade_ids: Vec<u64>,
+    execution_costs: Vec<StateExecutionCosts>,
+    markouts: [StateMarkout; 3],
+    last_funding_time_ms: Option<i64>,
+    quote_time: StateQuoteTime,
+    inventory_time: StateInventoryTime,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateExecutionCosts {
+    trade_id: u64,
+    fee_quote: f64,
+    rebate_quote: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateMarkout {
+    qty: f64,
+    quote_pnl: f64,
+    bps_qty_sum: f64,
+    samples: u64,
+    unavailable: u64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateQuoteTime {
+    observed_ms: i64,
+    two_sided_ms: i64,
+    two_sided_uptime_pct: f64,
+    eligible_bid_qty_ms: f64,
+    eligible_ask_qty_ms: f64,
+    eligible_total_qty_ms: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateInventoryTime {
+    observed_ms: i64,
+    nonzero_ms: i64,
+    abs_qty_ms: f64,
+    avg_abs_qty: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StateStats {
+    cycles: u64,
+    two_sided_cycles: u64,
+    buy_fills: u64,
+    sell_fills: u64,
+    filled_qty: f64,
+    cash: f64,
+    spread_bps_sum: f64,
+    spread_bps_n: u64,
+    max_abs_position: f64,
+    last_position: f64,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct StatePositionAlert {
+    last_observed_position: f64,
+    notification_anchor: f64,
+}
+
+impl StateFile {
+    pub(super) fn new(
+        symbol: &str,
+        run_order_prefix: &str,
+        session_started_at: i64,
+        starting_position: f64,
+        saved_at_ms: i64,
+        counters: StateCounters,
+        session: SessionSnapshot,
+    ) -> Self {
+        Self {
+            schema_version: STATE_SCHEMA_VERSION,
+            symbol: symbol.to_string(),
+            run_order_prefix: run_order_prefix.to_string(),
+            session_started_at,
+            starting_position,
+            saved_at_ms,
+            counters,
+            session: session.into(),
+        }
+    }
+}
+
+impl StateFile {
+    /// Replay this run's REST fills the snapshot never saw (quotes that filled
+    /// after a crash, or while the shutdown cancel raced) so the resume
+    /// position check compares against everything the venue booked. Trades
+    /// the ledger already counted are deduplicated by trade ID. Returns the
+    /// number of fills added.
+    pub(super) fn backfill(
+        &mut self,
+        orders: &[Order],
+        trades: &[Trade],
+        mark: f64,
+        now: i64,
+    ) -> Result<usize> {
+        // Alert thresholds only matter once the runtime restores the anchor;
+        // the snapshot keeps its position state either way.
+        let (mut ledger, mut stats, anchor) =
+            SessionSnapshot::from(self.session.clone()).restore(0.0, 0.0)?;
+        let mut run_orders = HashSet::new();
+        for order in orders {
+            if adopt_order(&mut ledger, order, &self.run_order_prefix)? {
+                run_orders.extend(order.id.parse::<u64>().ok());
+            }
+        }
+        let mut fills = Vec::new();
+        for trade in trades {
+            if trade
+                .order_id
+                .is_some_and(|order_id| run_orders.contains(&order_id))
+            {
+                apply_rest_trade(
+                    &mut ledger,
+                    trade.clone(),
+                    self.session_started_at,
+                    now,
+                    mark,
+                    &mut stats,
+                    &mut fills,
+                )?;
+            }
+        }
+        if fills.is_empty() {
+            return Ok(0);
+        }
+        self.session = SessionSnapshot::capture(&ledger, &stats, &anchor, now * 1_000)?.into();
+        self.counters.total_fills += fills.len() as u64;
+        Ok(fills.len())
+    }
+}
+
+impl From<SessionSnapshot> for StateSession {
+    fn from(value: SessionSnapshot) -> Self {
+        Self {
+            ledger: value.ledger.into(),
+            stats: value.stats.into(),
+            position_alert: value.position_alert.into(),
+        }
+    }
+}
+
+impl From<StateSession> for SessionSnapshot {
+    fn from(value: StateSession) -> Self {
+        Self {
+            ledger: value.ledger.into(),
+            stats: value.stats.into(),
+            position_alert: value.position_alert.into(),
+        }
+    }
+}
+
+impl From<LedgerSnapshot> for StateLedger {
+    fn from(value: LedgerSnapshot) -> Self {
+        Self {
+            expected_position: value.expected_position,
+            maker_order_ids: value.maker_order_ids,
+            exit_order_ids: value.exit_order_ids,
+            seen_trade_ids: value.seen_trade_ids,
+            performance: value.performance.map(Into::into),
+        }
+    }
+}
+
+impl From<StateLedger> for LedgerSnapshot {
+    fn from(value: StateLedger) -> Self {
+        Self {
+            expected_position: value.expected_position,
+            maker_order_ids: value.maker_order_ids,
+            exit_order_ids: value.exit_order_ids,
+            seen_trade_ids: value.seen_trade_ids,
+            performance: value.performance.map(Into::into),
+        }
+    }
+}
+
+impl From<PerformanceSnapshot> for StatePerformance {
+    fn from(value: PerformanceSnapshot) -> Self {
+        Self {
+            starting_position: value.starting_position,
+            starting_mark: value.starting_mark,
+            position: value.position,
+            fill_cash: value.fill_cash,
+            passive_fills: value.passive_fills,
+            passive_qty: value.passive_qty,
+            passive_cashflow_quote: value.passive_cashflow_quote,
+            passive_capture_bps_qty_sum: value.passive_capture_bps_qty_sum,
+            exit_fills: value.exit_fills,
+            exit_qty: value.exit_qty,
+            exit_cashflow_quote: value.exit_cashflow_quote,
+            gross_spread_quote: value.gross_spread_quote,
+            fee_quote: value.fee_quote,
+            rebate_quote: value.rebate_quote,
+            funding_quote: value.funding_quote,
+            funding_observed: value.funding_observed,
+            exit_cost_quote: value.exit_cost_quote,
+            seen_trade_ids: value.seen_trade_ids,
+            execution_costs: value
+                .execution_costs
+                .into_iter()
+                .map(|(trade_id, costs)| StateExecutionCosts {
+                    trade_id,
+                    fee_quote: costs.fee_quote,
+                    rebate_quote: costs.rebate_quote,
+                })
+                .collect(),
+            markouts: value.markouts.map(Into::into),
+            last_funding_time_ms: value.last_funding_time_ms,
+            quote_time: value.quote_time.into(),
+            inventory_time: value.inventory_time.into(),
+        }
+    }
+}
+
+impl From<StatePerformance> for PerformanceSnapshot {
+    fn from(value: StatePerformance) -> Self {
+        Self {
+            starting_position: value.starting_position,
+            starting_mark: value.starting_mark,
+            position: value.position,
+            fill_cash: value.fill_cash,
+            passive_fills: value.passive_fills,
+            passive_qty: value.passive_qty,
+            passive_cashflow_quote: value.passive_cashflow_quote,
+            passive_capture_bps_qty_sum: value.passive_capture_bps_qty_sum,
+            exit_fills: value.exit_fills,
+            exit_qty: value.exit_qty,
+            exit_cashflow_quote: value.exit_cashflow_quote,
+            gross_spread_quote: value.gross_spread_quote,
+            fee_quote: value.fee_quote,
+            rebate_quote: value.rebate_quote,
+            funding_quote: value.funding_quote,
+            funding_observed: value.funding_observed,
+            exit_cost_quote: value.exit_cost_quote,
+            seen_trade_ids: value.seen_trade_ids,
+            execution_costs: value
+                .execution_costs
+                .into_iter()
+                .map(|costs| {
+                    (
+                        costs.trade_id,
+                        ExecutionCosts {
+                            fee_quote: costs.fee_quote,
+                            rebate_quote: costs.rebate_quote,
+                        },
+                    )
+                })
+                .collect(),
+            markouts: value.markouts.map(Into::into),
+            last_funding_time_ms: value.last_funding_time_ms,
+            quote_time: value.quote_time.into(),
+            inventory_time: value.inventory_time.into(),
+        }
+    }
+}
+
+impl From<MarkoutAccumulator> for StateMarkout {
+    fn from(value: MarkoutAccumulator) -> Self {
+        Self {
+            qty: value.qty,
+            quote_pnl: value.quote_pnl,
+            bps_qty_sum: value.bps_qty_sum,
+            samples: value.samples,
+            unavailable: value.unavailable,
+        }
+    }
+}
+
+impl From<StateMarkout> for MarkoutAccumulator {
+    fn from(value: StateMarkout) -> Self {
+        Self {
+            qty: value.qty,
+            quote_pnl: value.quote_pnl,
+            bps_qty_sum: value.bps_qty_sum,
+            samples: value.samples,
+            unavailable: value.unavailable,
+        }
+    }
+}
+
+impl From<QuoteTimeSummary> for StateQuoteTime {
+    fn from(value: QuoteTimeSummary) -> Self {
+        Self {
+            observed_ms: value.observed_ms,
+            two_sided_ms: value.two_sided_ms,
+            two_sided_uptime_pct: value.two_sided_uptime_pct,
+            eligible_bid_qty_ms: value.eligible_bid_qty_ms,
+            eligible_ask_qty_ms: value.eligible_ask_qty_ms,
+            eligible_total_qty_ms: value.eligible_total_qty_ms,
+        }
+    }
+}
+
+impl From<StateQuoteTime> for QuoteTimeSummary {
+    fn from(value: StateQuoteTime) -> Self {
+        Self {
+            observed_ms: value.observed_ms,
+            two_sided_ms: value.two_sided_ms,
+            two_sided_uptime_pct: value.two_sided_uptime_pct,
+            eligible_bid_qty_ms: value.eligible_bid_qty_ms,
+            eligible_ask_qty_ms: value.eligible_ask_qty_ms,
+            eligible_total_qty_ms: value.eligible_total_qty_ms,
+        }
+    }
+}
+
+impl From<InventoryTimeSummary> for StateInventoryTime {
+    fn from(value: InventoryTimeSummary) -> Self {
+        Self {
+            observed_ms: value.observed_ms,
+            nonzero_ms: value.nonzero_ms,
+            abs_qty_ms: value.abs_qty_ms,
+            avg_abs_qty: value.avg_abs_qty,
+        }
+    }
+}
+
+impl From<StateInventoryTime> for InventoryTimeSummary {
+    fn from(value: StateInventoryTime) -> Self {
+        Self {
+            observed_ms: value.observed_ms,
+            nonzero_ms: value.nonzero_ms,
+            abs_qty_ms: value.abs_qty_ms,
+            avg_abs_qty: value.avg_abs_qty,
+        }
+    }
+}
+
+impl From<MakerStatsSnapshot> for StateStats {
+    fn from(value: MakerStatsSnapshot) -> Self {
+        Self {
+            cycles: value.cycles,
+            two_sided_cycles: value.two_sided_cycles,
+            buy_fills: value.buy_fills,
+            sell_fills: value.sell_fills,
+            filled_qty: value.filled_qty,
+            cash: value.cash,
+            spread_bps_sum: value.spread_bps_sum,
+            spread_bps_n: value.spread_bps_n,
+            max_abs_position: value.max_abs_position,
+            last_position: value.last_position,
+        }
+    }
+}
+
+impl From<StateStats> for MakerStatsSnapshot {
+    fn from(value: StateStats) -> Self {
+        Self {
+            cycles: value.cycles,
+            two_sided_cycles: value.two_sided_cycles,
+            buy_fills: value.buy_fills,
+            sell_fills: value.sell_fills,
+            filled_qty: value.filled_qty,
+            cash: value.cash,
+            spread_bps_sum: value.spread_bps_sum,
+            spread_bps_n: value.spread_bps_n,
+            max_abs_position: value.max_abs_position,
+            last_position: value.last_position,
+        }
+    }
+}
+
+impl From<PositionAlertSnapshot> for StatePositionAlert {
+    fn from(value: PositionAlertSnapshot) -> Self {
+        Self {
+            last_observed_position: value.last_observed_position,
+            notification_anchor: value.notification_anchor,
+        }
+    }
+}
+
+impl From<StatePositionAlert> for PositionAlertSnapshot {
+    fn from(value: StatePositionAlert) -> Self {
+        Self {
+            last_observed_position: value.last_observed_position,
+            notification_anchor: value.notification_anchor,
+        }
+    }
+}
+
+/// Write-then-rename so an interrupted write never truncates the last good
+/// snapshot. The partial file is synced before the rename.
+pub(super) fn save(path: &Path, state: &StateFile) -> Result<()> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut partial = path.as_os_str().to_owned();
+    partial.push(".tmp");
+    let partial = Path::new(&partial);
+    let mut file = std::fs::File::create(partial)
+        .with_context(|| format!("failed to write {}", partial.display()))?;
+    file.write_all(&serde_json::to_vec(state)?)
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("failed to write {}", partial.display()))?;
+    std::fs::rename(partial, path).with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub(super) fn load(path: &Path) -> Result<StateFile> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read maker state file {}", path.display()))?;
+    let state: StateFile = serde_json::from_str(&contents)
+        .with_context(|| format!("failed to parse maker state file {}", path.display()))?;
+    if state.schema_version != STATE_SCHEMA_VERSION {
+        return Err(anyhow::anyhow!(
+            "maker state file {} has schema_version {}; expected {}",
+            path.display(),
+            state.schema_version,
+            STATE_SCHEMA_VERSION
+        ));
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use standx_maker::{LedgerTrade, MakerLedger, MakerStats, PositionAlertAnchor, TradeSource};
+    use standx_sdk::models::OrderSide;
+
+    fn snapshot() -> SessionSnapshot {
+        let prefix = "sxmk-0123456789ab-";
+        let mut ledger = MakerLedger::new(0.0);
+        ledger.enable_performance(100.0).unwrap();
+        let mut stats = MakerStats::with_inventory_baseline(0.0, 100.0);
+        assert!(ledger.adopt_order(7, Some("sxmk-0123456789ab-q00000001b0"), prefix));
+        ledger
+            .record_trade(
+                LedgerTrade {
+                    trade_id: 11,
+                    order_id: 7,
+                    side: OrderSide::Buy,
+                    price: 99.9,
+                    qty: 0.2,
+                    mark: 100.0,
+                    trade_ts: "2026-07-14T00:00:00Z",
+                    event_time_ms: 1_000,
+                    costs: Some(ExecutionCosts {
+                        fee_quote: 0.0,
+                        rebate_quote: 0.004,
+                    }),
+                    source: TradeSource::AccountStream,
+                },
+                &mut stats,
+            )
+            .unwrap();
+        let anchor = PositionAlertAnchor::new(0.2, 20.0, 0.05);
+        SessionSnapshot::capture(&ledger, &stats, &anchor, 2_000).unwrap()
+    }
+
+    #[test]
+    fn state_file_round_trips_the_session_snapshot() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maker-state.json");
+        let session = snapshot();
+        let counters = StateCounters {
+            cycle: 42,
+            total_fills: 1,
+            ..StateCounters::default()
+        };
+        save(
+            &path,
+            &StateFile::new(
+                "BTC-USD",
+                "sxmk-0123456789ab-",
+                1_752_451_200,
+                0.0,
+                2_000,
+                counters,
+                session.clone(),
+            ),
+        )
+        .unwrap();
+        // Re-saving replaces the file in place and leaves no partial behind.
+        save(
+            &path,
+            &StateFile::new(
+                "BTC-USD",
+                "sxmk-0123456789ab-",
+                1_752_451_200,
+                0.0,
+                2_000,
+                counters,
+                session.clone(),
+            ),
+        )
+        .unwrap();
+        assert!(!dir.path().join("maker-state.json.tmp").exists());
+
+        let state = load(&path).unwrap();
+        assert_eq!(state.symbol, "BTC-USD");
+        assert_eq!(state.run_order_prefix, "sxmk-0123456789ab-");
+        assert_eq!(state.counters.cycle, 42);
+        assert_eq!(SessionSnapshot::from(state.session), session);
+    }
+
+    #[test]
+    fn load_rejects_a_future_schema_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("maker-state.json");
+        let mut state = StateFile::new(
+            "BTC-USD",
+            "sxmk-0123456789ab-",
+            0,
+            0.0,
+            0,
+            StateCounters::default(),
+            snapshot(),
+        );
+        state.schema_version = STATE_SCHEMA_VERSION + 1;
+        save(&path, &state).unwrap();
+        let error = load(&path).unwrap_err().to_string();
+        assert!(error.contains("schema_version 2"), "{error}");
+    }
+
+    fn order(id: u64, client_order_id: &str) -> Order {
+        Order {
+            id: id.to_string(),
+            cl_ord_id: Some(client_order_id.to_string()),
+            symbol: "BTC-USD".to_string(),
+            side: OrderSide::Sell,
+            order_type: standx_sdk::models::OrderType::Limit,
+            qty: "0.05".to_string(),
+            fill_qty: "0.05".to_string(),
+            price: "100.5".to_string(),
+            status: standx_sdk::models::OrderStatus::Filled,
+            created_at: "2025-07-14T00:00:01Z".to_string(),
+            updated_at: "2025-07-14T00:00:02Z".to_string(),
+        }
+    }
+
+    fn trade(id: u64, order_id: u64, side: &str, qty: &str) -> Trade {
+        Trade {
+            id,
+            time: "2025-07-14T00:00:02Z".to_string(),
+            price: "100.5".to_string(),
+            qty: qty.to_string(),
+            side: Some(side.to_string()),
+            is_buyer_taker: false,
+            fee_asset: None,
+            fee_qty: None,
+            pnl: None,
+            order_id: Some(order_id),
+            symbol: Some("BTC-USD".to_string()),
+            value: None,
+        }
+    }
+
+    #[test]
+    fn backfill_replays_only_unseen_fills_of_this_run() {
+        let mut state = StateFile::new(
+            "BTC-USD",
+            "sxmk-0123456789ab-",
+            1_752_451_200,
+            0.0,
+            2_000,
+            StateCounters {
+                total_fills: 1,
+                ..StateCounters::default()
+            },
+            snapshot(),
+        );
+        let orders = [
+            order(7, "sxmk-0123456789ab-q00000001b0"),
+            order(8, "sxmk-0123456789ab-q00000002a0"),
+            order(9, "manual-1"),
+            order(10, "sxmk-fedcba987654-q00000001a0"),
+        ];
+        let trades = [
+            // Already in the snapshot.
+            trade(11, 7, "buy", "0.2"),
+            // Filled after the last snapshot was written.
+            trade(12, 8, "sell", "0.05"),
+            // Manual and foreign-run fills are not this session's.
+            trade(13, 9, "buy", "1"),
+            trade(14, 10, "buy", "1"),
+        ];
+        let now = 1_752_451_300;
+
+        assert_eq!(state.backfill(&orders, &trades, 100.0, now).unwrap(), 1);
+        let session = SessionSnapshot::from(state.session.clone());
+        assert!((session.ledger.expected_position - 0.15).abs() < 1e-12);
+        assert_eq!(state.counters.total_fills, 2);
+        // A second pass finds nothing new.
+        assert_eq!(state.backfill(&orders, &trades, 100.0, now).unwrap(), 0);
+    }
+}
@@ -0,0 +1,87 @@
+//! `standx maker status` — is a live maker running, and what does it own?
+//!
+//! The maker keeps no status file, so this is assembled from what is
+//! observable from outside the process: the per-host live lock and the
+//! maker-tagged (`sxmk-` client id) orders resting on the venue, plus the
+//! positions they manage.
+
+use super::super::account::position_quantity_is_nonzero_or_invalid;
+use super::model::is_maker_order;
+use super::process_lock::{live_maker_running, maker_lock_path};
+use crate::cli::OutputFormat;
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{Order, Position};
+
+/// Observable maker state.
+#[derive(Debug, Serialize)]
+pub(in crate::commands) struct MakerStatus {
+    /// A process holds the live maker lock on this host.
+    pub live_running: bool,
+    pub lock_path: String,
+    pub symbol: Option<String>,
+    /// Open orders carrying a maker client order id.
+    pub maker_orders: Vec<Order>,
+    /// Non-flat positions (for `symbol` when given).
+    pub positions: Vec<Position>,
+}
+
+/// Probe the live lock and fetch maker orders and positions.
+pub(in crate::commands) async fn maker_status(
+    client: &StandXClient,
+    symbol: Option<&str>,
+) -> Result<MakerStatus> {
+    let live_running = live_maker_running()?;
+    let (orders, positions) =
+        tokio::join!(client.get_open_orders(symbol), client.get_positions(symbol));
+    let maker_orders = orders?.into_iter().filter(is_maker_order).collect();
+    let positions = positions?
+        .into_iter()
+        .filter(|position| position_quantity_is_nonzero_or_invalid(&position.qty))
+        .collect();
+    Ok(MakerStatus {
+        live_running,
+        lock_path: maker_lock_path(),
+        symbol: symbol.map(str::to_string),
+        maker_orders,
+        positions,
+    })
+}
+
+pub(super) async fn run(symbol: Option<String>, output_format: OutputFormat) -> Result<()> {
+    let client = StandXClient::new()?;
+    let status = maker_status(&client, symbol.as_deref()).await?;
+    match output_format {
+        OutputFormat::Json => println!("{}", output::format_json(&status)?),
+        OutputFormat::Quiet => println!(
+            "{}",
+            if status.live_running {
+                "running"
+            } else {
+                "idle"
+            }
+        ),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!(
+                "live maker: {} (lock {})",
+                if status.live_running {
+                    "running"
+                } else {
+                    "idle"
+                },
+                status.lock_path
+            );
+            println!("\nmaker orders: {}", status.maker_orders.len());
+            if !status.maker_orders.is_empty() {
+                println!("{}", output::format_table(status.maker_orders));
+            }
+            println!("\npositions: {}", status.positions.len());
+            if !status.positions.is_empty() {
+                println!("{}", output::format_table(status.positions));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,689 @@
+//! `maker sweep`: backtest every combination of a parameter grid over one
+//! recording and rank the candidates.
+//!
+//! The grid is a TOML file in which every array lists the alternatives of one
+//! maker config key; nested tables address sections, so `spread_bps = [4, 5]`
+//! and `adaptive_spread.enabled = [false, true]` are two axes. An array of
+//! tables or arrays (such as whole `adaptive_spread.tiers` sets) is one axis
+//! whose alternatives replace the value wholesale. Each candidate is the base
+//! maker TOML with one alternative per axis applied.
+
+use super::backtest::{self, Recording};
+use super::config::{self, MakerFileConfig};
+use crate::cli::{BacktestArgs, OutputFormat, SweepRank};
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use standx_maker::{self as maker, BacktestResult, PerformanceSummary};
+use std::cmp::Ordering;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+/// Upper bound on grid size, so a typo such as a 1000-value axis fails fast
+/// instead of running for hours.
+const MAX_CANDIDATES: usize = 10_000;
+
+pub(super) struct SweepRequest {
+    pub(super) data: Vec<PathBuf>,
+    pub(super) grid: PathBuf,
+    pub(super) maker_config: Option<PathBuf>,
+    pub(super) rank_by: SweepRank,
+    pub(super) top: usize,
+    pub(super) jobs: Option<usize>,
+    pub(super) results_out: Option<PathBuf>,
+    pub(super) best_out: Option<PathBuf>,
+    pub(super) simulation: BacktestArgs,
+}
+
+/// One grid dimension: a dotted key and its alternatives.
+#[derive(Debug, PartialEq)]
+struct Axis {
+    path: Vec<String>,
+    values: Vec<toml::Value>,
+}
+
+impl Axis {
+    fn key(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+struct Candidate {
+    /// `key=value` per axis, in axis order.
+    params: Vec<(String, toml::Value)>,
+    table: toml::Table,
+    file: MakerFileConfig,
+}
+
+/// Ranking metrics of one evaluated candidate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Metrics {
+    net_pnl_quote: f64,
+    markout_5s_bps: Option<f64>,
+    uptime_pct: f64,
+    inventory_pct: f64,
+}
+
+impl Metrics {
+    fn of(performance: &PerformanceSummary) -> Self {
+        let inventory = performance.inventory_time;
+        Self {
+            net_pnl_quote: performance.net_pnl_quote,
+            markout_5s_bps: performance.markouts[1].avg_bps,
+            uptime_pct: performance.quote_time.two_sided_uptime_pct,
+            inventory_pct: if inventory.observed_ms > 0 {
+                inventory.nonzero_ms as f64 / inventory.observed_ms as f64 * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+
+    /// Better candidates sort first: `rank` decides, the remaining metrics
+    /// break ties in the order of [`SweepRank`].
+    fn compare(&self, other: &Self, rank: SweepRank) -> Ordering {
+        let by = |key: SweepRank| match key {
+            SweepRank::NetPnl => other.net_pnl_quote.total_cmp(&self.net_pnl_quote),
+            SweepRank::Markout => match (self.markout_5s_bps, other.markout_5s_bps) {
+                (Some(ours), Some(theirs)) => theirs.total_cmp(&ours),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            },
+            SweepRank::Uptime => other.uptime_pct.total_cmp(&self.uptime_pct),
+            SweepRank::Inventory => self.inventory_pct.total_cmp(&other.inventory_pct),
+        };
+        [
+            SweepRank::NetPnl,
+            SweepRank::Markout,
+            SweepRank::Uptime,
+            SweepRank::Inventory,
+        ]
+        .into_iter()
+        .filter(|key| *key != rank)
+        .fold(by(rank), |ordering, key| ordering.then_with(|| by(key)))
+    }
+}
+
+/// One row of the results table and CSV.
+#[derive(Debug, Serialize)]
+struct SweepRow {
+    rank: Option<usize>,
+    params: String,
+    net_pnl_quote: Option<f64>,
+    markout_5s_bps: Option<f64>,
+    uptime_pct: Option<f64>,
+    inventory_pct: Option<f64>,
+    passive_fills: Option<u64>,
+    exit_fills: Option<u64>,
+    post_only_rejects: Option<u64>,
+    error: Option<String>,
+}
+
+pub(super) fn run(request: SweepRequest, output_format: OutputFormat) -> Result<()> {
+    let grid_text = std::fs::read_to_string(&request.grid)
+        .with_context(|| format!("failed to read grid {}", request.grid.display()))?;
+    let grid = grid_text
+        .parse::<toml::Table>()
+        .with_context(|| format!("invalid grid {}", request.grid.display()))?;
+    let axes = axes(&grid)?;
+    let (base_path, base) = config::load_table(request.maker_config.as_deref())?;
+    let candidates = candidates(&base, &axes)?;
+    let recording = backtest::load_recording(&request.data, request.simulation.symbol.as_deref())?;
+    if let Some(candidate) = candidates.first() {
+        backtest::warn_unmodeled(&candidate.file);
+    }
+
+    let outcomes = evaluate(&candidates, &recording, &request.simulation, request.jobs);
+    let ranked = rank(&outcomes, request.rank_by);
+    let rows: Vec<SweepRow> = ranked
+        .iter()
+        .map(|(rank, index)| row(*rank, &candidates[*index], &outcomes[*index]))
+        .collect();
+
+    if let Some(path) = &request.results_out {
+        let mut writer = csv::Writer::from_path(path)
+            .with_context(|| format!("failed to create {}", path.display()))?;
+        for row in &rows {
+            writer.serialize(row)?;
+        }
+        writer
+            .flush()
+            .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+    let best = ranked
+        .first()
+        .filter(|(rank, _)| rank.is_some())
+        .map(|(_, index)| &candidates[*index]);
+    if let (Some(path), Some(best)) = (&request.best_out, best) {
+        let index = ranked[0].1;
+        std::fs::write(
+            path,
+            best_toml(best, &base_path, &outcomes[index], request.rank_by)?,
+        )
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    }
+
+    let evaluated = rows.iter().filter(|row| row.rank.is_some()).count();
+    if output_format == OutputFormat::Json {
+        for row in &rows {
+            println!(
+                "{}",
+                crate::output::format_event(&serde_json::json!({
+                    "action": "sweep_candidate",
+                    "rank": row.rank,
+                    "params": row.params,
+                    "net_pnl_quote": row.net_pnl_quote,
+                    "markout_5s_bps": row.markout_5s_bps,
+                    "uptime_pct": row.uptime_pct,
+                    "inventory_pct": row.inventory_pct,
+                    "passive_fills": row.passive_fills,
+                    "exit_fills": row.exit_fills,
+                    "post_only_rejects": row.post_only_rejects,
+                    "error": row.error,
+                }))
+            );
+        }
+        println!(
+            "{}",
+            crate::output::format_event(&serde_json::json!({
+                "action": "sweep_summary",
+                "symbol": recording.symbol,
+                "rank_by": rank_name(request.rank_by),
+                "candidates": rows.len(),
+                "evaluated": evaluated,
+                "rejected": rows.len() - evaluated,
+                "best_params": best.map(|best| params_text(&best.params)),
+                "results_out": request.results_out.as_ref().map(|path| path.display().to_string()),
+                "best_out": best
+                    .and(request.best_out.as_ref())
+                    .map(|path| path.display().to_string()),
+            }))
+        );
+    } else if output_format != OutputFormat::Quiet {
+        let shown = if request.top == 0 {
+            rows.len()
+        } else {
+            request.top.min(rows.len())
+        };
+        println!("{}", table(&rows[..shown]));
+        println!(
+            "Sweep {}: {} candidates, {} evaluated, {} rejected",
+            recording.symbol,
+            rows.len(),
+            evaluated,
+            rows.len() - evaluated
+        );
+        if let (Some(path), Some(_)) = (&request.best_out, best) {
+            println!("Best config written to {}", path.display());
+        }
+    }
+    if evaluated == 0 {
+        anyhow::bail!(
+            "no sweep candidate could be backtested: {}",
+            rows.first()
+                .and_then(|row| row.error.as_deref())
+                .unwrap_or("empty grid")
+        );
+    }
+    Ok(())
+}
+
+/// Every array in the grid is an axis; tables recurse into config sections.
+fn axes(grid: &toml::Table) -> Result<Vec<Axis>> {
+    fn walk(table: &toml::Table, prefix: &[String], axes: &mut Vec<Axis>) -> Result<()> {
+        for (key, value) in table {
+            let mut path = prefix.to_vec();
+            path.push(key.clone());
+            match value {
+                toml::Value::Table(section) => walk(section, &path, axes)?,
+                toml::Value::Array(values) if values.is_empty() => {
+                    anyhow::bail!("grid key {} has no alternatives", path.join("."))
+                }
+                toml::Value::Array(values) => axes.push(Axis {
+                    path,
+                    values: values.clone(),
+                }),
+                _ => anyhow::bail!(
+                    "grid key {} must be an array of alternatives",
+                    path.join(".")
+                ),
+            }
+        }
+        Ok(())
+    }
+    let mut axes = Vec::new();
+    walk(grid, &[], &mut axes)?;
+    if axes.is_empty() {
+        anyhow::bail!("the grid has no parameters to sweep");
+    }
+    Ok(axes)
+}
+
+/// The cartesian product of `axes` over `base`, first axis varying slowest.
+fn candidates(base: &toml::Table, axes: &[Axis]) -> Result<Vec<Candidate>> {
+    let count = axes
+        .iter()
+        .try_fold(1usize, |count, axis| count.checked_mul(axis.values.len()))
+        .filter(|count| *count <= MAX_CANDIDATES)
+        .ok_or_else(|| anyhow::anyhow!("the grid has more than {MAX_CANDIDATES} combinations"))?;
+    (0..count)
+        .map(|mut index| {
+            let mut choices = vec![0; axes.len()];
+            for (choice, axis) in choices.iter_mut().zip(axes).rev() {
+                *choice = index % axis.values.len();
+                index /= axis.values.len();
+            }
+            let mut table = base.clone();
+            let mut params = Vec::with_capacity(axes.len());
+            for (axis, choice) in axes.iter().zip(choices) {
+                let value = axis.values[choice].clone();
+                set(&mut table, &axis.path, value.clone())?;
+                params.push((axis.key(), value));
+            }
+            let file = config::parse(table.clone()).map_err(|error| {
+                anyhow::anyhow!(
+                    "sweep candidate {} is not a valid maker config: {error}",
+                    params_text(&params)
+                )
+            })?;
+            Ok(Candidate {
+                params,
+                table,
+                file,
+            })
+        })
+        .collect()
+}
+
+fn set(table: &mut toml::Table, path: &[String], value: toml::Value) -> Result<()> {
+    let Some((last, parents)) = path.split_last() else {
+        return Ok(());
+    };
+    let mut current = table;
+    for key in parents {
+        current = match current
+            .entry(key.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+        {
+            toml::Value::Table(section) => section,
+            _ => anyhow::bail!("{key} is not a table in the base maker config"),
+        };
+    }
+    current.insert(last.clone(), value);
+    Ok(())
+}
+
+/// Backtest every candidate on `jobs` threads; outcomes are in candidate order.
+fn evaluate(
+    candidates: &[Candidate],
+    recording: &Recording,
+    simulation: &BacktestArgs,
+    jobs: Option<usize>,
+) -> Vec<Result<BacktestResult, String>> {
+    let jobs = jobs
+        .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, NonZeroUsize::get))
+        .clamp(1, candidates.len().max(1));
+    let next = AtomicUsize::new(0);
+    let mut outcomes: Vec<(usize, Result<BacktestResult, String>)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, AtomicOrdering::Relaxed);
+                        let Some(candidate) = candidates.get(index) else {
+                            break;
+                        };
+                        let outcome = backtest::resolve(&candidate.file, simulation, recording)
+                            .and_then(|(cfg, settings)| {
+                                Ok(maker::run_backtest(&cfg, &settings, &recording.events)?)
+                            })
+                            .map_err(|error| error.to_string());
+                        done.push((index, outcome));
+                    }
+                    done
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("sweep worker panicked"))
+            .collect()
+    });
+    outcomes.sort_by_key(|(index, _)| *index);
+    outcomes.into_iter().map(|(_, outcome)| outcome).collect()
+}
+
+/// Candidate indexes, best first, with their rank; rejected candidates
+/// follow without one.
+fn rank(
+    outcomes: &[Result<BacktestResult, String>],
+    rank_by: SweepRank,
+) -> Vec<(Option<usize>, usize)> {
+    let mut evaluated: Vec<(usize, Metrics)> = outcomes
+        .iter()
+        .enumerate()
+        .filter_map(|(index, outcome)| {
+            outcome
+                .as_ref()
+                .ok()
+                .map(|result| (index, Metrics::of(&result.performance)))
+        })
+        .collect();
+    evaluated.sort_by(|(_, left), (_, right)| left.compare(right, rank_by));
+    evaluated
+        .iter()
+        .enumerate()
+        .map(|(rank, (index, _))| (Some(rank + 1), *index))
+        .chain(
+            outcomes
+                .iter()
+                .enumerate()
+                .filter(|(_, outcome)| outcome.is_err())
+                .map(|(index, _)| (None, index)),
+        )
+        .collect()
+}
+
+fn row(
+    rank: Option<usize>,
+    candidate: &Candidate,
+    outcome: &Result<BacktestResult, String>,
+) -> SweepRow {
+    let params = params_text(&candidate.params);
+    match outcome {
+        Ok(result) => {
+            let metrics = Metrics::of(&result.performance);
+            SweepRow {
+                rank,
+                params,
+                net_pnl_quote: Some(metrics.net_pnl_quote),
+                markout_5s_bps: metrics.markout_5s_bps,
+                uptime_pct: Some(metrics.uptime_pct),
+                inventory_pct: Some(metrics.inventory_pct),
+                passive_fills: Some(result.performance.passive_fills),
+                exit_fills: Some(result.performance.exit_fills),
+                post_only_rejects: Some(result.stats.post_only_rejects),
+                error: None,
+            }
+        }
+        Err(error) => SweepRow {
+            rank,
+            params,
+            net_pnl_quote: None,
+            markout_5s_bps: None,
+            uptime_pct: None,
+            inventory_pct: None,
+            passive_fills: None,
+            exit_fills: None,
+            post_only_rejects: None,
+            error: Some(error.clone()),
+        },
+    }
+}
+
+fn rank_name(rank: SweepRank) -> String {
+    rank.to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
+
+fn params_text(params: &[(String, toml::Value)]) -> String {
+    params
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn table(rows: &[SweepRow]) -> String {
+    let optional = |value: Option<f64>, decimals: usize| {
+        value.map_or_else(|| "-".to_string(), |value| format!("{value:.decimals$}"))
+    };
+    let mut builder = tabled::builder::Builder::default();
+    builder.push_record([
+        "Rank",
+        "Params",
+        "Net PnL",
+        "Markout 5s (bps)",
+        "Uptime %",
+        "Inventory %",
+        "Fills",
+        "Exits",
+        "Rejected",
+        "Error",
+    ]);
+    for row in rows {
+        builder.push_record([
+            row.rank
+                .map_or_else(|| "-".to_string(), |rank| rank.to_string()),
+            row.params.clone(),
+            optional(row.net_pnl_quote, 6),
+            optional(row.markout_5s_bps, 2),
+            optional(row.uptime_pct, 2),
+            optional(row.inventory_pct, 2),
+            row.passive_fills
+                .map_or_else(|| "-".to_string(), |fills| fills.to_string()),
+            row.exit_fills
+                .map_or_else(|| "-".to_string(), |fills| fills.to_string()),
+            row.post_only_rejects
+                .map_or_else(|| "-".to_string(), |rejects| rejects.to_string()),
+            row.error.clone().unwrap_or_default(),
+        ]);
+    }
+    builder.build().to_string()
+}
+
+/// The winning candidate as a complete maker TOML, headed by a comment that
+/// records where it came from.
+fn best_toml(
+    best: &Candidate,
+    base_path: &std::path::Path,
+    outcome: &Result<BacktestResult, String>,
+    rank_by: SweepRank,
+) -> Result<String> {
+    let body = toml::to_string(&best.table).context("failed to serialize the best config")?;
+    let mut header = format!(
+        "# Best `standx maker sweep` candidate (ranked by {}) over base {}\n# {}\n",
+        rank_name(rank_by),
+        base_path.display(),
+        params_text(&best.params)
+    );
+    if let Ok(result) = outcome {
+        let metrics = Metrics::of(&result.performance);
+        header.push_str(&format!(
+            "# net_pnl_quote={:.6} markout_5s_bps={} uptime_pct={:.2} inventory_pct={:.2}\n",
+            metrics.net_pnl_quote,
+            metrics
+                .markout_5s_bps
+                .map_or_else(|| "n/a".to_string(), |bps| format!("{bps:.2}")),
+            metrics.uptime_pct,
+            metrics.inventory_pct,
+        ));
+    }
+    Ok(format!("{header}\n{body}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use standx_maker::{BacktestEvent, BookLevel};
+
+    fn simulation() -> BacktestArgs {
+        BacktestArgs {
+            symbol: None,
+            starting_position: 0.0,
+            order_latency_ms: 100,
+            cancel_latency_ms: 100,
+            maker_fee_bps: 0.0,
+            taker_fee_bps: 0.0,
+            price_decimals: None,
+            qty_decimals: None,
+            min_order_qty: None,
+        }
+    }
+
+    /// A steady book with sell flow sweeping down through the bids.
+    fn recording() -> Recording {
+        let depth = |event_time_ms| BacktestEvent::Depth {
+            event_time_ms,
+            bids: vec![BookLevel {
+                price: 99.99,
+                qty: 5.0,
+            }],
+            asks: vec![BookLevel {
+                price: 100.01,
+                qty: 5.0,
+            }],
+        };
+        Recording {
+            symbol: "BTC-USD".to_string(),
+            events: vec![
+                BacktestEvent::Mark {
+                    event_time_ms: 0,
+                    mark: 100.0,
+                },
+                depth(0),
+                BacktestEvent::Trade {
+                    event_time_ms: 1_000,
+                    price: 99.90,
+                    qty: 20.0,
+                },
+                depth(3_000),
+            ],
+            price_decimals: 2,
+            qty_decimals: 3,
+        }
+    }
+
+    #[test]
+    fn grid_arrays_become_axes_and_tables_recurse() {
+        let grid: toml::Table =
+            "spread_bps = [4, 5]\n[size_skew]\nadd_side_factor = [0.3, 0.5, 0.7]\n"
+                .parse()
+                .unwrap();
+        let axes = axes(&grid).unwrap();
+        assert_eq!(
+            axes.iter().map(Axis::key).collect::<Vec<_>>(),
+            ["size_skew.add_side_factor", "spread_bps"]
+        );
+        let scalar: toml::Table = "spread_bps = 4\n".parse().unwrap();
+        assert!(axes_error(&scalar).contains("array of alternatives"));
+    }
+
+    fn axes_error(grid: &toml::Table) -> String {
+        axes(grid).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn candidates_overlay_each_combination_on_the_base() {
+        let base: toml::Table = "spread_bps = 5\nband_bps = 30\n".parse().unwrap();
+        let grid: toml::Table = "spread_bps = [4, 6]\nlevels = [1, 2, 3]\n".parse().unwrap();
+        let candidates = candidates(&base, &axes(&grid).unwrap()).unwrap();
+        assert_eq!(candidates.len(), 6);
+        assert_eq!(params_text(&candidates[0].params), "levels=1 spread_bps=4");
+        assert_eq!(params_text(&candidates[5].params), "levels=3 spread_bps=6");
+        assert_eq!(candidates[5].file.spread_bps, Some(6.0));
+        assert_eq!(candidates[5].file.band_bps, Some(30.0));
+    }
+
+    #[test]
+    fn unknown_grid_key_is_rejected_up_front() {
+        let grid: toml::Table = "spread_bp = [4, 6]\n".parse().unwrap();
+        let error = candidates(&toml::Table::new(), &axes(&grid).unwrap())
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("spread_bp=4"), "{error}");
+    }
+
+    #[test]
+    fn ranking_breaks_ties_with_the_remaining_metrics() {
+        let metrics = |net_pnl_quote, markout_5s_bps, inventory_pct| Metrics {
+            net_pnl_quote,
+            markout_5s_bps,
+            uptime_pct: 90.0,
+            inventory_pct,
+        };
+        let better = metrics(1.0, Some(2.0), 10.0);
+        let worse = metrics(1.0, Some(1.0), 5.0);
+        assert_eq!(better.compare(&worse, SweepRank::NetPnl), Ordering::Less);
+        assert_eq!(
+            better.compare(&worse, SweepRank::Inventory),
+            Ordering::Greater
+        );
+        assert_eq!(
+            metrics(0.0, None, 0.0).compare(&worse, SweepRank::Markout),
+            Ordering::Greater
+        );
+    }
+
+    #[test]
+    fn sweep_ranks_evaluated_candidates_before_rejected_ones() {
+        let base: toml::Table = "band_bps = 20\nsize = 0.002\ninterval = 1\n"
+            .parse()
+            .unwrap();
+        // spread_bps = 25 is rejected: the band must be wider than the spread.
+        let grid: toml::Table = "spread_bps = [25, 5, 15]\n".parse().unwrap();
+        let candidates = candidates(&base, &axes(&grid).unwrap()).unwrap();
+        let outcomes = evaluate(&candidates, &recording(), &simulation(), Some(2));
+        assert!(outcomes[0].as_ref().unwrap_err().contains("band_bps"));
+
+        let ranked = rank(&outcomes, SweepRank::NetPnl);
+        assert_eq!(ranked.len(), 3);
+        assert_eq!(ranked[2], (None, 0));
+        // The sell sweep to 99.90 fills the 5 bps bid and misses the 15 bps one.
+        assert_eq!(ranked[0], (Some(1), 1));
+        assert_eq!(ranked[1], (Some(2), 2));
+
+        let best = best_toml(
+            &candidates[1],
+            std::path::Path::new("maker.toml"),
+            &outcomes[1],
+            SweepRank::NetPnl,
+        )
+        .unwrap();
+        let body = best
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let parsed = config::parse(body.parse().unwrap()).unwrap();
+        assert_eq!(parsed.spread_bps, Some(5.0));
+        assert_eq!(parsed.size, Some(0.002));
+    }
+
+    #[test]
+    fn table_keeps_rejects_and_errors_in_their_own_columns() {
+        let row = |post_only_rejects, error: Option<&str>| SweepRow {
+            rank: None,
+            params: "spread_bps=8".to_string(),
+            net_pnl_quote: None,
+            markout_5s_bps: None,
+            uptime_pct: None,
+            inventory_pct: None,
+            passive_fills: None,
+            exit_fills: None,
+            post_only_rejects,
+            error: error.map(str::to_string),
+        };
+        let rendered = table(&[row(Some(3), None), row(None, Some("band too tight"))]);
+        let lines: Vec<&str> = rendered.lines().collect();
+        let cells = |line: &str| {
+            line.split('|')
+                .map(str::trim)
+                .filter(|cell| !cell.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let header = cells(lines[1]);
+        assert_eq!(&header[header.len() - 2..], ["Rejected", "Error"]);
+        assert_eq!(cells(lines[3]).last().map(String::as_str), Some("3"));
+        assert_eq!(
+            &cells(lines[5])[header.len() - 3..],
+            ["-", "-", "band too tight"]
+        );
+    }
+}
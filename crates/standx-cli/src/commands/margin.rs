@@ -1,5 +1,8 @@
+use crate::audit;
 use crate::cli::*;
 use anyhow::Result;
+use serde_json::Value;
+use standx_sdk::client::account::{change_margin_mode_body, transfer_margin_body};
 use standx_sdk::client::StandXClient;
 
 /// Handle margin commands
@@ -11,7 +14,7 @@ pub async fn handle_margin(command: MarginCommands) -> Result<()> {
             symbol,
             amount,
             direction,
-        } => match client.transfer_margin(&symbol, &amount, &direction).await {
+        } => match transfer_margin(&client, &symbol, &amount, &direction).await {
             Ok(_) => {
                 println!(
                     "✅ Margin transferred for {}: {} (direction: {})",
@@ -29,7 +32,7 @@ pub async fn handle_margin(command: MarginCommands) -> Result<()> {
         MarginCommands::Mode { symbol, set } => {
             if let Some(mode) = set {
                 // Set margin mode
-                match client.change_margin_mode(&symbol, &mode).await {
+                match change_margin_mode(&client, &symbol, &mode).await {
                     Ok(_) => println!("✅ Margin mode for {} set to {}", symbol, mode),
                     Err(e) => {
                         println!("⚠️  Margin mode change failed");
@@ -55,3 +58,36 @@ pub async fn handle_margin(command: MarginCommands) -> Result<()> {
     }
     Ok(())
 }
+
+/// Transfer margin, recording it in the audit log.
+pub(super) async fn transfer_margin(
+    client: &StandXClient,
+    symbol: &str,
+    amount: &str,
+    direction: &str,
+) -> standx_sdk::Result<()> {
+    // A malformed amount never reaches the venue; the SDK reports it.
+    let request = transfer_margin_body(symbol, amount, direction).unwrap_or(Value::Null);
+    audit::audited(
+        "margin_transfer",
+        symbol,
+        request,
+        client.transfer_margin(symbol, amount, direction),
+    )
+    .await
+}
+
+/// Change the margin mode, recording it in the audit log.
+pub(super) async fn change_margin_mode(
+    client: &StandXClient,
+    symbol: &str,
+    mode: &str,
+) -> standx_sdk::Result<()> {
+    audit::audited(
+        "margin_mode_change",
+        symbol,
+        change_margin_mode_body(symbol, mode),
+        client.change_margin_mode(symbol, mode),
+    )
+    .await
+}
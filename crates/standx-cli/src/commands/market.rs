@@ -1,5 +1,7 @@
+use super::data::{cached_funding, cached_klines, cached_recent_trades};
 use super::util::parse_time_string;
 use crate::cli::*;
+use crate::market_cache::MarketCache;
 use crate::output;
 use anyhow::Result;
 use standx_sdk::client::StandXClient;
@@ -47,8 +49,13 @@ pub async fn handle_market(command: MarketCommands, output_format: OutputFormat)
                 OutputFormat::Quiet => {}
             }
         }
-        MarketCommands::Trades { symbol, limit } => {
-            let trades = client.get_recent_trades(&symbol, limit).await?;
+        MarketCommands::Trades {
+            symbol,
+            limit,
+            no_cache,
+        } => {
+            let cache = (!no_cache).then(MarketCache::open_default);
+            let trades = cached_recent_trades(&client, cache.as_ref(), &symbol, limit).await?;
 
             match output_format {
                 OutputFormat::Table => println!("{}", output::format_table(trades)),
@@ -66,7 +73,7 @@ pub async fn handle_market(command: MarketCommands, output_format: OutputFormat)
                     output::format_order_book(&book, limit.unwrap_or(10) as usize)
                 ),
                 OutputFormat::Json => println!("{}", output::format_json(&book)?),
-                OutputFormat::Csv => println!("CSV format not supported for order book"),
+                OutputFormat::Csv => println!("{}", output::format_csv(&[book])?),
                 OutputFormat::Quiet => {
                     if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
                         println!("{} {}", bid, ask);
@@ -80,6 +87,7 @@ pub async fn handle_market(command: MarketCommands, output_format: OutputFormat)
             from,
             to,
             limit,
+            no_cache,
         } => {
             // Parse time parameters
             let now = chrono::Utc::now().timestamp();
@@ -92,9 +100,16 @@ pub async fn handle_market(command: MarketCommands, output_format: OutputFormat)
                 None => now,                              // Default: now
             };
 
-            let klines = client
-                .get_kline(&symbol, &resolution, from_ts, to_ts)
-                .await?;
+            let cache = (!no_cache).then(MarketCache::open_default);
+            let klines = cached_klines(
+                &client,
+                cache.as_ref(),
+                &symbol,
+                &resolution,
+                from_ts,
+                to_ts,
+            )
+            .await?;
 
             // Apply limit if specified
             let klines: Vec<_> = if let Some(lim) = limit {
@@ -125,10 +140,16 @@ pub async fn handle_market(command: MarketCommands, output_format: OutputFormat)
                 OutputFormat::Quiet => {}
             }
         }
-        MarketCommands::Funding { symbol, days } => {
+        MarketCommands::Funding {
+            symbol,
+            days,
+            no_cache,
+        } => {
             let now = chrono::Utc::now().timestamp();
             let start_time = now - days * 24 * 60 * 60;
-            let funding_rates = client.get_funding_rate(&symbol, start_time, now).await?;
+            let cache = (!no_cache).then(MarketCache::open_default);
+            let funding_rates =
+                cached_funding(&client, cache.as_ref(), &symbol, start_time, now).await?;
 
             if funding_rates.is_empty() {
                 println!(
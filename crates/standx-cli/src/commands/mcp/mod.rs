@@ -0,0 +1,307 @@
+//! `standx mcp` — Model Context Protocol server over stdio
+//!
+//! Speaks newline-delimited JSON-RPC 2.0 on stdin/stdout (logs go to
+//! stderr). Every tool is a CLI subcommand: its input schema is derived from
+//! the clap definition and its arguments are validated by re-parsing them as
+//! a command line, so the tools cannot drift from the shell interface.
+//!
+//! Order tools follow the shared [`Policy`]: hidden in the `read-only`
+//! profile (default), previewed under `--dry-run`, and otherwise run only
+//! with `"confirm": true` unless the server was started with `--yes`.
+
+use super::tools::{self, Access, Policy, Prepared};
+use crate::cli::{McpArgs, ToolProfile};
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+use standx_sdk::client::StandXClient;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+/// Protocol revisions this server speaks, newest first.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Handle the mcp command
+pub async fn handle_mcp(args: McpArgs, dry_run: bool, auto_confirm: bool) -> Result<()> {
+    let mut server = Server::new(args.profile, dry_run, auto_confirm);
+    eprintln!(
+        "mcp: serving {} tools on stdio ({} profile{})",
+        server.policy.tools().count(),
+        tools::profile_name(args.profile),
+        if dry_run { ", dry run" } else { "" }
+    );
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Value>(&line) {
+            Ok(message) => server.handle(message).await,
+            Err(error) => Some(failure(Value::Null, PARSE_ERROR, &error.to_string())),
+        };
+        if let Some(response) = response {
+            let mut bytes = serde_json::to_vec(&response)?;
+            bytes.push(b'\n');
+            stdout.write_all(&bytes).await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+fn success(id: Value, result: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result })
+}
+
+fn failure(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// A `tools/call` result carrying `text`.
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+struct Server {
+    policy: Policy,
+    /// Built on first use so `initialize` and `tools/list` work offline.
+    client: Option<StandXClient>,
+}
+
+impl Server {
+    fn new(profile: ToolProfile, dry_run: bool, auto_confirm: bool) -> Self {
+        Self {
+            policy: Policy::new(profile, dry_run, auto_confirm),
+            client: None,
+        }
+    }
+
+    /// Answer one JSON-RPC message; notifications get no response.
+    async fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            return Some(failure(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "expected a JSON-RPC request object",
+            ));
+        };
+        // Notifications (`notifications/initialized`, `notifications/cancelled`)
+        // need no answer.
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+        Some(match method {
+            "initialize" => success(id, self.initialize(&params)),
+            "ping" => success(id, json!({})),
+            "tools/list" => success(id, json!({ "tools": self.list() })),
+            "tools/call" => match self.call(&params).await {
+                Ok(result) => success(id, result),
+                Err(message) => failure(id, INVALID_PARAMS, &message),
+            },
+            _ => failure(id, METHOD_NOT_FOUND, &format!("method not found: {method}")),
+        })
+    }
+
+    fn initialize(&self, params: &Value) -> Value {
+        let requested = params.get("protocolVersion").and_then(Value::as_str);
+        let version = requested
+            .filter(|version| PROTOCOL_VERSIONS.contains(version))
+            .unwrap_or(PROTOCOL_VERSIONS[0]);
+        let policy = &self.policy;
+        let mut instructions = format!(
+            "StandX perpetuals tools ({} profile). Tool results are JSON.",
+            tools::profile_name(policy.profile)
+        );
+        if policy.profile == ToolProfile::Trading {
+            instructions.push_str(if policy.dry_run {
+                " Dry run: order tools only report what they would do."
+            } else if policy.auto_confirm {
+                " Order tools execute immediately."
+            } else {
+                " Order tools need \"confirm\": true; without it the call is refused."
+            });
+        }
+        json!({
+            "protocolVersion": version,
+            "capabilities": { "tools": { "listChanged": false } },
+            "serverInfo": { "name": "standx", "version": env!("CARGO_PKG_VERSION") },
+            "instructions": instructions,
+        })
+    }
+
+    fn list(&self) -> Vec<Value> {
+        self.policy
+            .tools()
+            .map(|tool| {
+                json!({
+                    "name": tool.name,
+                    "description": self.policy.description(tool),
+                    "inputSchema": self.policy.input_schema(tool),
+                    "annotations": {
+                        "readOnlyHint": tool.access == Access::Read,
+                        "destructiveHint": tool.access == Access::Write,
+                        "openWorldHint": true,
+                    },
+                })
+            })
+            .collect()
+    }
+
+    /// `tools/call`. Protocol problems (unknown tool, malformed params) are
+    /// `Err`; everything the agent can act on is an `isError` result.
+    async fn call(&mut self, params: &Value) -> std::result::Result<Value, String> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or("tools/call needs a tool name")?;
+        let tool = tools::find(name).ok_or_else(|| format!("unknown tool: {name}"))?;
+        let arguments = match params.get("arguments") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(arguments)) => arguments.clone(),
+            Some(_) => return Err("tool arguments must be an object".to_string()),
+        };
+
+        let command = match self.policy.prepare(tool, arguments) {
+            Ok(Prepared::Run(command)) => command,
+            Ok(Prepared::Preview(preview)) => return Ok(tool_result(pretty(&preview), false)),
+            Err(refusal) => return Ok(tool_result(refusal.message().to_string(), true)),
+        };
+        let client = match self.client() {
+            Ok(client) => client,
+            Err(error) => return Ok(tool_result(format!("{error:#}"), true)),
+        };
+        Ok(match tools::execute(client, command).await {
+            Ok(value) => tool_result(pretty(&value), false),
+            Err(error) => tool_result(format!("{error:#}"), true),
+        })
+    }
+
+    fn client(&mut self) -> Result<&StandXClient> {
+        if self.client.is_none() {
+            self.client = Some(StandXClient::new()?);
+        }
+        Ok(self.client.as_ref().expect("client was just built"))
+    }
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn request(server: &mut Server, method: &str, params: Value) -> Value {
+        let message = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        server
+            .handle(message)
+            .await
+            .expect("requests get a response")
+    }
+
+    fn names(response: &Value) -> Vec<String> {
+        response["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn initialize_negotiates_the_protocol_version() {
+        let mut server = Server::new(ToolProfile::ReadOnly, false, false);
+        let response = request(
+            &mut server,
+            "initialize",
+            json!({ "protocolVersion": "2024-11-05" }),
+        )
+        .await;
+        assert_eq!(response["result"]["protocolVersion"], "2024-11-05");
+        let response = request(
+            &mut server,
+            "initialize",
+            json!({ "protocolVersion": "1999-01-01" }),
+        )
+        .await;
+        assert_eq!(response["result"]["protocolVersion"], PROTOCOL_VERSIONS[0]);
+
+        let notification = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(server.handle(notification).await.is_none());
+        let response = request(&mut server, "resources/list", json!({})).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn read_only_profile_hides_and_refuses_order_tools() {
+        let mut server = Server::new(ToolProfile::ReadOnly, false, true);
+        let listed = names(&request(&mut server, "tools/list", json!({})).await);
+        assert!(listed.contains(&"market_depth".to_string()));
+        assert!(!listed.iter().any(|name| name.starts_with("order_")));
+
+        let call = json!({ "name": "order_cancel_all", "arguments": { "symbol": "BTC-USD" } });
+        let response = request(&mut server, "tools/call", call).await;
+        assert_eq!(response["result"]["isError"], true);
+
+        let call = json!({ "name": "order_teleport" });
+        let response = request(&mut server, "tools/call", call).await;
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn order_tools_need_confirmation_and_honor_dry_run() {
+        let mut server = Server::new(ToolProfile::Trading, false, false);
+        let listed = request(&mut server, "tools/list", json!({})).await;
+        let create = listed["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|tool| tool["name"] == "order_create")
+            .unwrap();
+        assert_eq!(
+            create["inputSchema"]["properties"]["confirm"]["type"],
+            "boolean"
+        );
+        assert_eq!(create["annotations"]["destructiveHint"], true);
+
+        let call = json!({
+            "name": "order_cancel",
+            "arguments": { "symbol": "BTC-USD", "order_id": "42" },
+        });
+        let response = request(&mut server, "tools/call", call.clone()).await;
+        assert_eq!(response["result"]["isError"], true);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("confirmation required"));
+        assert!(text.contains("standx order cancel --order-id=42 -- BTC-USD"));
+
+        let mut server = Server::new(ToolProfile::Trading, true, false);
+        let mut confirmed = call;
+        confirmed["arguments"]["confirm"] = json!(true);
+        let response = request(&mut server, "tools/call", confirmed).await;
+        assert_eq!(response["result"]["isError"], false);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("\"dry_run\": true"));
+    }
+
+    #[tokio::test]
+    async fn invalid_arguments_are_tool_errors() {
+        let mut server = Server::new(ToolProfile::ReadOnly, false, false);
+        let call =
+            json!({ "name": "market_depth", "arguments": { "symbol": "BTC-USD", "limit": "x" } });
+        let response = request(&mut server, "tools/call", call).await;
+        assert_eq!(response["result"]["isError"], true);
+        assert!(response["result"]["content"][0]["text"]
+            .as_str()
+            .unwrap()
+            .contains("invalid value"));
+    }
+}
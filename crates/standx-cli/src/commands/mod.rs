@@ -4,33 +4,55 @@
 //! re-exports the public handler surface consumed by `main.rs`.
 
 mod account;
+mod audit;
 mod auth;
 mod block;
 mod config;
 mod dashboard;
+mod data;
 mod lag_recorder;
 mod leverage;
 mod maker;
 mod margin;
 mod market;
+mod mcp;
 mod order;
+mod plan;
 mod portfolio;
+mod record;
+mod report;
+mod risk;
+mod schema;
+mod serve;
 mod stream;
+mod tools;
 mod trade;
+mod tui;
 mod util;
+mod watch;
 
 pub use account::handle_account;
+pub use audit::handle_audit;
 pub use auth::handle_auth;
 pub use block::handle_block;
 pub use config::handle_config;
 pub use dashboard::handle_dashboard;
+pub use data::handle_data;
 pub use lag_recorder::handle_lag_recorder;
 pub use leverage::handle_leverage;
 pub use maker::{handle_maker, panic_webhook_body, FailSafeShutdown, FAIL_SAFE_EXIT_CODE};
 pub use margin::handle_margin;
 pub use market::handle_market;
+pub use mcp::handle_mcp;
 pub use order::handle_order;
+pub use plan::{handle_apply, handle_plan_out};
 pub use portfolio::{handle_portfolio, PortfolioCommand};
+pub use record::handle_record;
+pub use report::handle_report;
+pub use risk::handle_risk;
+pub use schema::handle_schema;
+pub use serve::handle_serve;
 pub use stream::handle_stream;
 pub use trade::handle_trade;
 pub use util::parse_time_string;
+pub use watch::handle_watch;
@@ -0,0 +1,256 @@
+//! Local journal of client order IDs sent by `order create`.
+//!
+//! Each submission is recorded before the request goes out and marked
+//! acknowledged once the venue accepts it, so a retry with the same
+//! `--client-order-id` can tell "never reached the venue" from "accepted,
+//! look it up" and a reused ID for a different order is refused. Entries
+//! older than [`RETENTION_DAYS`] are dropped on the next write. Every access
+//! goes through a [`JournalHold`], an exclusive lock on a sidecar `.lock`
+//! file that a submission keeps from its dedupe check until the venue
+//! answers, so two submissions of one ID — from separate processes or from
+//! concurrent requests in one server — cannot both send.
+
+use crate::config::Config;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use standx_sdk::client::order::CreateOrderParams;
+use standx_sdk::models::{OrderSide, OrderType};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+/// How long a client order ID is remembered.
+pub(super) const RETENTION_DAYS: i64 = 7;
+
+/// One journaled submission.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct Entry {
+    pub symbol: String,
+    pub side: OrderSide,
+    pub order_type: OrderType,
+    pub qty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(default)]
+    pub reduce_only: bool,
+    pub submitted_at: DateTime<Utc>,
+    /// Venue request ID, set once the submission was acknowledged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
+
+impl Entry {
+    pub(super) fn new(params: &CreateOrderParams) -> Self {
+        Self {
+            symbol: params.symbol.clone(),
+            side: params.side,
+            order_type: params.order_type,
+            qty: params.quantity.clone(),
+            price: params.price.clone(),
+            reduce_only: params.reduce_only,
+            submitted_at: Utc::now(),
+            request_id: None,
+        }
+    }
+
+    /// Whether `other` asks for the same order (ignoring when and whether
+    /// it was sent).
+    pub(super) fn same_order(&self, other: &Entry) -> bool {
+        self.symbol == other.symbol
+            && self.side == other.side
+            && self.order_type == other.order_type
+            && self.qty == other.qty
+            && self.price == other.price
+            && self.reduce_only == other.reduce_only
+    }
+
+    pub(super) fn describe(&self) -> String {
+        let side = match self.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        };
+        match &self.price {
+            Some(price) => format!("{side} {} {} @ {price}", self.qty, self.symbol),
+            None => format!("{side} {} {}", self.qty, self.symbol),
+        }
+    }
+}
+
+/// The journal file.
+#[derive(Debug, Clone)]
+pub(in crate::commands) struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub(super) fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `order-journal.json` in the config directory.
+    pub(in crate::commands) fn open_default() -> Self {
+        Self::new(Config::default_config_dir().join("order-journal.json"))
+    }
+
+    /// Wait for exclusive use of the journal, off the async runtime.
+    pub(super) async fn hold(&self) -> Result<JournalHold> {
+        let journal = self.clone();
+        tokio::task::spawn_blocking(move || journal.hold_blocking())
+            .await
+            .context("order journal lock task failed")?
+    }
+
+    /// The journal itself is replaced by rename, so the lock lives on a
+    /// sidecar file.
+    fn hold_blocking(self) -> Result<JournalHold> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let path = self.path.with_extension("json.lock");
+        let lock = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("failed to open {}", path.display()))?;
+        crate::audit::lock_exclusive(&lock, &path)?;
+        Ok(JournalHold {
+            journal: self,
+            _lock: lock,
+        })
+    }
+
+    fn load(&self) -> Result<BTreeMap<String, Entry>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .with_context(|| format!("parsing order journal {}", self.path.display())),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(error) => Err(error).with_context(|| format!("reading {}", self.path.display())),
+        }
+    }
+
+    fn store(&self, mut entries: BTreeMap<String, Entry>) -> Result<()> {
+        let cutoff = Utc::now() - Duration::days(RETENTION_DAYS);
+        entries.retain(|_, entry| entry.submitted_at >= cutoff);
+        // Write-then-rename so an interrupted write never truncates the journal.
+        let partial = self.path.with_extension("json.tmp");
+        std::fs::write(&partial, serde_json::to_vec_pretty(&entries)?)
+            .with_context(|| format!("failed to write {}", partial.display()))?;
+        std::fs::rename(&partial, &self.path)
+            .with_context(|| format!("failed to write {}", self.path.display()))
+    }
+}
+
+/// Exclusive use of the journal, released on drop.
+#[derive(Debug)]
+pub(super) struct JournalHold {
+    journal: Journal,
+    _lock: std::fs::File,
+}
+
+impl JournalHold {
+    pub(super) fn get(&self, client_order_id: &str) -> Result<Option<Entry>> {
+        Ok(self.journal.load()?.remove(client_order_id))
+    }
+
+    /// Record a submission about to be sent.
+    pub(super) fn record(&self, client_order_id: &str, entry: Entry) -> Result<()> {
+        let mut entries = self.journal.load()?;
+        entries.insert(client_order_id.to_string(), entry);
+        self.journal.store(entries)
+    }
+
+    /// Mark a recorded submission as accepted by the venue.
+    pub(super) fn acknowledge(&self, client_order_id: &str, request_id: &str) -> Result<()> {
+        let mut entries = self.journal.load()?;
+        if let Some(entry) = entries.get_mut(client_order_id) {
+            entry.request_id = Some(request_id.to_string());
+        }
+        self.journal.store(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(qty: &str) -> CreateOrderParams {
+        CreateOrderParams {
+            symbol: "BTC-USD".to_string(),
+            side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            quantity: qty.to_string(),
+            price: Some("65000".to_string()),
+            ..CreateOrderParams::default()
+        }
+    }
+
+    #[test]
+    fn records_and_acknowledges_submissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("nested").join("order-journal.json"))
+            .hold_blocking()
+            .unwrap();
+        assert_eq!(journal.get("sxcli-a").unwrap(), None);
+
+        journal
+            .record("sxcli-a", Entry::new(&params("0.1")))
+            .unwrap();
+        let pending = journal.get("sxcli-a").unwrap().unwrap();
+        assert_eq!(pending.request_id, None);
+        assert_eq!(pending.describe(), "sell 0.1 BTC-USD @ 65000");
+
+        journal.acknowledge("sxcli-a", "req-1").unwrap();
+        let acknowledged = journal.get("sxcli-a").unwrap().unwrap();
+        assert_eq!(acknowledged.request_id.as_deref(), Some("req-1"));
+        assert!(acknowledged.same_order(&Entry::new(&params("0.1"))));
+        assert!(!acknowledged.same_order(&Entry::new(&params("0.2"))));
+    }
+
+    #[test]
+    fn forgets_entries_past_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("order-journal.json"))
+            .hold_blocking()
+            .unwrap();
+        let mut stale = Entry::new(&params("0.1"));
+        stale.submitted_at = Utc::now() - Duration::days(RETENTION_DAYS + 1);
+        journal.record("sxcli-old", stale).unwrap();
+        journal
+            .record("sxcli-new", Entry::new(&params("0.1")))
+            .unwrap();
+
+        assert_eq!(journal.get("sxcli-old").unwrap(), None);
+        assert!(journal.get("sxcli-new").unwrap().is_some());
+    }
+
+    #[test]
+    fn a_hold_serializes_check_and_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Journal::new(dir.path().join("order-journal.json"));
+        // Every writer checks for the same ID and records it only when
+        // absent, as `submit` does; exactly one may find it missing.
+        let recorded: Vec<_> = (0..8)
+            .map(|_| {
+                let journal = journal.clone();
+                std::thread::spawn(move || {
+                    let hold = journal.hold_blocking().unwrap();
+                    if hold.get("sxcli-a").unwrap().is_some() {
+                        return false;
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                    hold.record("sxcli-a", Entry::new(&params("0.1"))).unwrap();
+                    true
+                })
+            })
+            .collect();
+        let recorded = recorded
+            .into_iter()
+            .map(|writer| writer.join().unwrap())
+            .filter(|recorded| *recorded)
+            .count();
+
+        assert_eq!(recorded, 1);
+    }
+}
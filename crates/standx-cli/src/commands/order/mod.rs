@@ -0,0 +1,247 @@
+mod journal;
+
+use crate::audit;
+use crate::cli::*;
+use anyhow::{bail, Context, Result};
+use journal::Entry;
+
+pub(super) use journal::Journal;
+use serde_json::json;
+use standx_sdk::client::order::{cancel_order_body, create_order_body, CreateOrderParams};
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{Order, OrderSide, OrderType, TimeInForce};
+
+/// Prefix of client order IDs generated for `order create`.
+const CLIENT_ORDER_ID_PREFIX: &str = "sxcli-";
+
+/// Orders of recent history searched when reconciling a client order ID.
+const RECONCILE_HISTORY_LIMIT: u32 = 100;
+
+/// Handle order commands
+pub async fn handle_order(command: OrderCommands) -> Result<()> {
+    let client = StandXClient::new()?;
+
+    match command {
+        command @ OrderCommands::Create { .. } => {
+            let params = create_params(command)?;
+            let submission = submit(&client, params, &Journal::open_default()).await?;
+            let order = submission.order;
+            if submission.existing {
+                println!("✅ Order already submitted; not sending it again");
+            } else {
+                println!("✅ Order created successfully!");
+            }
+            println!("   Order ID: {}", order.id);
+            if let Some(client_order_id) = &order.cl_ord_id {
+                println!("   Client Order ID: {}", client_order_id);
+            }
+            println!("   Symbol: {}", order.symbol);
+            println!("   Side: {:?}", order.side);
+            println!("   Type: {:?}", order.order_type);
+            println!("   Quantity: {}", order.qty);
+            if !order.price.is_empty() && order.price != "0" {
+                println!("   Price: {}", order.price);
+            }
+        }
+        OrderCommands::Cancel { symbol, order_id } => {
+            cancel(&client, &symbol, &order_id).await?;
+            println!("✅ Order {} cancelled successfully", order_id);
+        }
+        OrderCommands::CancelAll { symbol } => {
+            cancel_all(&client, &symbol).await?;
+            println!("✅ All orders for {} cancelled successfully", symbol);
+        }
+    }
+    Ok(())
+}
+
+/// Result of [`submit`].
+pub(super) struct Submission {
+    pub order: Order,
+    /// The client order ID matched an order already on the venue, so
+    /// nothing was sent.
+    pub existing: bool,
+}
+
+/// A fresh client order ID.
+pub(super) fn new_client_order_id() -> String {
+    let id = uuid::Uuid::new_v4().simple().to_string();
+    format!("{CLIENT_ORDER_ID_PREFIX}{}", &id[..16])
+}
+
+/// Create an order at most once per client order ID.
+///
+/// Without an ID one is generated and the order is sent. With one, the
+/// journal and the venue are checked first: an order already open or in
+/// recent history under that ID is returned instead of sending another, and
+/// an ID already used for a different order, or accepted earlier but no
+/// longer visible, is refused. An ID that was journaled but never
+/// acknowledged is sent again, since the earlier attempt may not have
+/// reached the venue. The journal stays locked from the check until the
+/// venue answers, so concurrent submissions of one ID send at most once.
+pub(super) async fn submit(
+    client: &StandXClient,
+    mut params: CreateOrderParams,
+    journal: &Journal,
+) -> Result<Submission> {
+    let supplied = params.cl_ord_id.is_some();
+    let client_order_id = params
+        .cl_ord_id
+        .get_or_insert_with(new_client_order_id)
+        .clone();
+    let entry = Entry::new(&params);
+    // Held until the venue answers: a concurrent submission of the same ID
+    // waits here and then finds this one journaled or on the venue.
+    let journal = journal.hold().await?;
+    if supplied {
+        let previous = journal.get(&client_order_id)?;
+        if let Some(previous) = previous.as_ref().filter(|p| !p.same_order(&entry)) {
+            bail!(
+                "client order id {client_order_id} was already used for a different order ({}); \
+                 pick a new --client-order-id",
+                previous.describe()
+            );
+        }
+        if let Some(order) = find_order(client, &params.symbol, &client_order_id).await? {
+            return Ok(Submission {
+                order,
+                existing: true,
+            });
+        }
+        if let Some(request_id) = previous.and_then(|previous| previous.request_id) {
+            bail!(
+                "client order id {client_order_id} was accepted earlier (request {request_id}) \
+                 but is neither open nor in recent order history; not sending it again \
+                 (use a new --client-order-id to place another order)"
+            );
+        }
+    }
+
+    journal.record(&client_order_id, entry)?;
+    let symbol = params.symbol.clone();
+    let request = create_order_body(&params);
+    let order = audit::audited(
+        "order_create",
+        &symbol,
+        request,
+        client.create_order(params),
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "order with client order id {client_order_id} may not have been placed; \
+             retry with --client-order-id {client_order_id} to avoid a duplicate"
+        )
+    })?;
+    journal.acknowledge(&client_order_id, &order.id)?;
+    Ok(Submission {
+        order,
+        existing: false,
+    })
+}
+
+/// Cancel one order, recording it in the audit log.
+pub(super) async fn cancel(client: &StandXClient, symbol: &str, order_id: &str) -> Result<()> {
+    let request = order_id
+        .parse()
+        .map(cancel_order_body)
+        .unwrap_or_else(|_| json!({ "order_id": order_id }));
+    audit::audited(
+        "order_cancel",
+        symbol,
+        request,
+        client.cancel_order(symbol, order_id),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Cancel every open order on `symbol`, recording it in the audit log.
+pub(super) async fn cancel_all(client: &StandXClient, symbol: &str) -> Result<()> {
+    audit::audited(
+        "order_cancel_all",
+        symbol,
+        json!({ "symbol": symbol }),
+        client.cancel_all_orders(symbol),
+    )
+    .await?;
+    Ok(())
+}
+
+/// The order carrying `client_order_id`, open or in recent history.
+async fn find_order(
+    client: &StandXClient,
+    symbol: &str,
+    client_order_id: &str,
+) -> Result<Option<Order>> {
+    let matches = |order: &Order| order.cl_ord_id.as_deref() == Some(client_order_id);
+    if let Some(order) = client
+        .get_open_orders(Some(symbol))
+        .await?
+        .into_iter()
+        .find(matches)
+    {
+        return Ok(Some(order));
+    }
+    Ok(client
+        .get_order_history(Some(symbol), Some(RECONCILE_HISTORY_LIMIT))
+        .await?
+        .into_iter()
+        .find(matches))
+}
+
+/// Validate `order create` arguments into SDK order parameters.
+pub(super) fn create_params(command: OrderCommands) -> Result<CreateOrderParams> {
+    let OrderCommands::Create {
+        symbol,
+        side,
+        order_type,
+        qty,
+        price,
+        tif,
+        reduce_only,
+        sl_price,
+        tp_price,
+        client_order_id,
+    } = command
+    else {
+        return Err(anyhow::anyhow!("not an order create command"));
+    };
+
+    // Parse side
+    let side = match side.to_lowercase().as_str() {
+        "buy" => OrderSide::Buy,
+        "sell" => OrderSide::Sell,
+        _ => return Err(anyhow::anyhow!("Invalid side: {}", side)),
+    };
+
+    // Parse order type
+    let order_type = match order_type.to_lowercase().as_str() {
+        "limit" => OrderType::Limit,
+        "market" => OrderType::Market,
+        _ => return Err(anyhow::anyhow!("Invalid order type: {}", order_type)),
+    };
+
+    // Parse time in force
+    let time_in_force = tif.map(|t| match t.to_uppercase().as_str() {
+        "GTC" => TimeInForce::Gtc,
+        "IOC" => TimeInForce::Ioc,
+        "FOK" => TimeInForce::Fok,
+        "ALO" => TimeInForce::Alo,
+        _ => TimeInForce::Gtc,
+    });
+
+    Ok(CreateOrderParams {
+        symbol,
+        cl_ord_id: client_order_id,
+        side,
+        order_type,
+        quantity: qty,
+        price,
+        time_in_force,
+        reduce_only,
+        stop_price: None,
+        sl_price,
+        tp_price,
+    })
+}
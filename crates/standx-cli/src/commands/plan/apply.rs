@@ -0,0 +1,476 @@
+//! `standx apply` — re-validate a plan, then execute it.
+//!
+//! Before anything is sent, every check must pass:
+//! - **request**: each recorded request is exactly what its action produces,
+//!   so a hand-edited body cannot slip past the reviewed summary;
+//! - **age**: the plan is no older than `--max-age-secs`;
+//! - **drift**: each symbol's mark price is within `--max-drift-bps` of the
+//!   mark the plan was made against;
+//! - **band**: each limit price is within `--price-band-bps` of the current
+//!   mark.
+//!
+//! Cancels are narrowed to the planned orders that are still open. With
+//! `--dry-run` the checks are reported and nothing is sent; otherwise the
+//! plan runs after a confirmation prompt (or `--yes`).
+
+use super::{Action, Plan, PLAN_VERSION};
+use crate::cli::{ApplyArgs, OutputFormat};
+use crate::commands::schema::{trace, Definitions};
+use crate::output;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use standx_sdk::client::StandXClient;
+use std::collections::{BTreeMap, HashSet};
+use std::io::{IsTerminal, Write};
+
+/// Clock skew tolerated for plans stamped in the future.
+const MAX_FUTURE_SECS: i64 = 60;
+
+/// One pre-flight check and its outcome.
+#[derive(Debug, Serialize, PartialEq)]
+struct Check {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, ok: bool, detail: String) -> Self {
+        Self { name, ok, detail }
+    }
+}
+
+/// Outcome of one executed action.
+#[derive(Debug, Serialize)]
+struct Executed {
+    action: String,
+    result: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Report<'a> {
+    plan: &'a str,
+    checks: &'a [Check],
+    dry_run: bool,
+    executed: &'a [Executed],
+}
+
+fn parse_price(raw: &str) -> Option<f64> {
+    raw.parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+}
+
+/// Distance of `price` from `reference` in basis points.
+fn distance_bps(reference: f64, price: f64) -> f64 {
+    (price - reference).abs() / reference * 10_000.0
+}
+
+fn age_check(created_at: DateTime<Utc>, now: DateTime<Utc>, max_age_secs: u64) -> Check {
+    let age = (now - created_at).num_seconds();
+    if age < -MAX_FUTURE_SECS {
+        return Check::new(
+            "age",
+            false,
+            format!("plan is stamped {}s in the future", -age),
+        );
+    }
+    let age = age.max(0);
+    Check::new(
+        "age",
+        age as u64 <= max_age_secs,
+        format!("{age}s old (max {max_age_secs}s)"),
+    )
+}
+
+fn drift_check(symbol: &str, planned: &str, current: &str, max_bps: f64) -> Check {
+    match (parse_price(planned), parse_price(current)) {
+        (Some(planned), Some(current)) => {
+            let drift = distance_bps(planned, current);
+            Check::new(
+                "drift",
+                drift <= max_bps,
+                format!("{symbol} mark {planned} → {current} ({drift:.1} bps, max {max_bps} bps)"),
+            )
+        }
+        _ => Check::new(
+            "drift",
+            false,
+            format!("{symbol} mark is not comparable ('{planned}' → '{current}')"),
+        ),
+    }
+}
+
+fn band_check(symbol: &str, limit: &str, mark: &str, max_bps: f64) -> Check {
+    match (parse_price(limit), parse_price(mark)) {
+        (Some(limit), Some(mark)) => {
+            let distance = distance_bps(mark, limit);
+            Check::new(
+                "band",
+                distance <= max_bps,
+                format!(
+                    "{symbol} limit {limit} is {distance:.1} bps from mark {mark} \
+                     (max {max_bps} bps)"
+                ),
+            )
+        }
+        _ => Check::new(
+            "band",
+            false,
+            format!("{symbol} limit '{limit}' is not comparable to mark '{mark}'"),
+        ),
+    }
+}
+
+/// Checks that need no network: version, request integrity and age.
+fn offline_checks(plan: &Plan, args: &ApplyArgs, now: DateTime<Utc>) -> Vec<Check> {
+    let mut checks = vec![Check::new(
+        "version",
+        plan.version == PLAN_VERSION,
+        format!("plan format v{} (expected v{PLAN_VERSION})", plan.version),
+    )];
+    for planned in &plan.actions {
+        let (ok, detail) = match planned.action.request() {
+            Ok(request) if request == planned.request => {
+                (true, format!("{} {}", request.method, request.path))
+            }
+            Ok(request) => (
+                false,
+                format!(
+                    "recorded request does not match `{}` (expected {} {} {})",
+                    planned.action.describe(),
+                    request.method,
+                    request.path,
+                    request.body
+                ),
+            ),
+            Err(error) => (false, format!("{error:#}")),
+        };
+        checks.push(Check::new("request", ok, detail));
+    }
+    if plan.actions.is_empty() {
+        checks.push(Check::new("request", false, "plan has no actions".into()));
+    }
+    checks.push(age_check(plan.created_at, now, args.max_age_secs));
+    checks
+}
+
+/// Drop cancel targets that are no longer open; `None` when nothing is left.
+fn narrow_cancel(action: &Action, open: &HashSet<i64>) -> Option<Action> {
+    match action {
+        Action::CancelOrder { order_id, .. } => open.contains(order_id).then(|| action.clone()),
+        Action::CancelOrders { symbol, order_ids } => {
+            let order_ids: Vec<i64> = order_ids
+                .iter()
+                .copied()
+                .filter(|id| open.contains(id))
+                .collect();
+            (!order_ids.is_empty()).then(|| Action::CancelOrders {
+                symbol: symbol.clone(),
+                order_ids,
+            })
+        }
+        _ => Some(action.clone()),
+    }
+}
+
+/// Handle the apply command
+pub async fn handle_apply(
+    args: ApplyArgs,
+    output_format: OutputFormat,
+    dry_run: bool,
+    auto_confirm: bool,
+) -> Result<()> {
+    let text = std::fs::read_to_string(&args.plan)
+        .with_context(|| format!("failed to read plan {}", args.plan.display()))?;
+    let plan: Plan = serde_json::from_str(&text)
+        .with_context(|| format!("{} is not a standx plan", args.plan.display()))?;
+    let mut checks = offline_checks(&plan, &args, Utc::now());
+
+    let client = StandXClient::new()?;
+    let mut marks = BTreeMap::new();
+    for (symbol, context) in &plan.market {
+        let current = client
+            .get_symbol_price(symbol)
+            .await
+            .with_context(|| format!("failed to fetch {symbol} price"))?;
+        checks.push(drift_check(
+            symbol,
+            &context.mark_price,
+            &current.mark_price,
+            args.max_drift_bps,
+        ));
+        marks.insert(symbol.clone(), current.mark_price);
+    }
+
+    let mut actions = Vec::new();
+    for planned in &plan.actions {
+        let action = &planned.action;
+        if let Action::CreateOrder {
+            symbol,
+            price: Some(price),
+            ..
+        } = action
+        {
+            let mark = match marks.get(symbol) {
+                Some(mark) => mark.clone(),
+                None => client.get_symbol_price(symbol).await?.mark_price,
+            };
+            checks.push(band_check(symbol, price, &mark, args.price_band_bps));
+        }
+        if let Action::CancelOrder { symbol, .. } | Action::CancelOrders { symbol, .. } = action {
+            let open: HashSet<i64> = client
+                .get_open_orders(Some(symbol))
+                .await?
+                .iter()
+                .filter_map(|order| order.id.parse().ok())
+                .collect();
+            match narrow_cancel(action, &open) {
+                Some(narrowed) => {
+                    if &narrowed != action {
+                        checks.push(Check::new(
+                            "open",
+                            true,
+                            format!(
+                                "some planned orders already closed; {}",
+                                narrowed.describe()
+                            ),
+                        ));
+                    }
+                    actions.push(narrowed);
+                }
+                None => checks.push(Check::new(
+                    "open",
+                    true,
+                    format!("{symbol}: every planned order is already closed; skipped"),
+                )),
+            }
+            continue;
+        }
+        actions.push(action.clone());
+    }
+
+    let plan_path = args.plan.display().to_string();
+    let failed = checks.iter().filter(|check| !check.ok).count();
+    if failed > 0 || dry_run || actions.is_empty() {
+        print_report(&plan_path, &plan, &checks, dry_run, &[], output_format)?;
+        if failed > 0 {
+            bail!("plan rejected: {failed} check(s) failed");
+        }
+        return Ok(());
+    }
+
+    if output_format == OutputFormat::Table {
+        print_report(&plan_path, &plan, &checks, false, &[], output_format)?;
+    }
+    if !auto_confirm && !confirm(actions.len())? {
+        bail!("apply cancelled; nothing was sent");
+    }
+
+    let mut executed = Vec::new();
+    for action in &actions {
+        match action.execute(&client).await {
+            Ok(result) => executed.push(Executed {
+                action: action.describe(),
+                result,
+            }),
+            Err(error) => {
+                let done: Vec<&str> = executed.iter().map(|done| done.action.as_str()).collect();
+                return Err(error.context(format!(
+                    "failed to {}; already executed: {}",
+                    action.describe(),
+                    if done.is_empty() {
+                        "nothing".to_string()
+                    } else {
+                        done.join(", ")
+                    }
+                )));
+            }
+        }
+    }
+    match output_format {
+        OutputFormat::Table => {
+            for done in &executed {
+                println!("✅ {}", done.action);
+            }
+        }
+        _ => print_report(&plan_path, &plan, &checks, false, &executed, output_format)?,
+    }
+    Ok(())
+}
+
+fn confirm(actions: usize) -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        bail!("refusing to apply without confirmation; pass --yes to run non-interactively");
+    }
+    print!("Execute {actions} action(s)? [y/N] ");
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// JSON output schema of `standx apply`, for `standx schema`.
+pub(in crate::commands) fn output_schema(definitions: &mut Definitions) -> Value {
+    let report = Report {
+        plan: "",
+        checks: &[Check::new("age", true, String::new())],
+        dry_run: false,
+        executed: &[Executed {
+            action: String::new(),
+            result: Value::Object(Default::default()),
+        }],
+    };
+    trace(definitions, &report)
+}
+
+fn print_report(
+    plan_path: &str,
+    plan: &Plan,
+    checks: &[Check],
+    dry_run: bool,
+    executed: &[Executed],
+    output_format: OutputFormat,
+) -> Result<()> {
+    match output_format {
+        OutputFormat::Json => {
+            let report = Report {
+                plan: plan_path,
+                checks,
+                dry_run,
+                executed,
+            };
+            println!("{}", output::format_json(&report)?);
+        }
+        OutputFormat::Quiet => {
+            let failed = checks.iter().filter(|check| !check.ok).count();
+            println!("{}", if failed == 0 { "ok" } else { "rejected" });
+        }
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!(
+                "📋 Plan {plan_path} (made {} by `{}`)",
+                plan.created_at.to_rfc3339(),
+                plan.command
+            );
+            for planned in &plan.actions {
+                println!("   • {}", planned.action.describe());
+            }
+            println!();
+            for check in checks {
+                let mark = if check.ok { "✅" } else { "❌" };
+                println!("{mark} {:<8} {}", check.name, check.detail);
+            }
+            if dry_run {
+                println!("\n🔍 DRY RUN: checks only, nothing was sent");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{MarketContext, PlannedAction};
+    use super::*;
+    use chrono::Duration;
+    use std::path::PathBuf;
+
+    fn args() -> ApplyArgs {
+        ApplyArgs {
+            plan: PathBuf::from("plan.json"),
+            max_age_secs: 300,
+            max_drift_bps: 50.0,
+            price_band_bps: 500.0,
+        }
+    }
+
+    fn plan(action: Action, created_at: DateTime<Utc>) -> Plan {
+        Plan {
+            version: PLAN_VERSION,
+            created_at,
+            command: "standx leverage set BTC-USD 5 --plan-out plan.json".to_string(),
+            actions: vec![PlannedAction {
+                request: action.request().unwrap(),
+                action,
+            }],
+            market: BTreeMap::from([(
+                "BTC-USD".to_string(),
+                MarketContext {
+                    mark_price: "100000".to_string(),
+                    index_price: "100000".to_string(),
+                    last_price: "100000".to_string(),
+                    best_bid: None,
+                    best_ask: None,
+                },
+            )]),
+            cancels: Vec::new(),
+        }
+    }
+
+    fn leverage(leverage: u32) -> Action {
+        Action::ChangeLeverage {
+            symbol: "BTC-USD".to_string(),
+            leverage,
+        }
+    }
+
+    #[test]
+    fn edited_requests_and_stale_plans_fail_offline_checks() {
+        let now = Utc::now();
+        let fresh = plan(leverage(5), now - Duration::seconds(30));
+        assert!(offline_checks(&fresh, &args(), now)
+            .iter()
+            .all(|check| check.ok));
+
+        let mut edited = fresh.clone();
+        edited.actions[0].request.body["leverage"] = serde_json::json!(50);
+        let checks = offline_checks(&edited, &args(), now);
+        assert!(checks
+            .iter()
+            .any(|check| check.name == "request" && !check.ok));
+
+        let stale = plan(leverage(5), now - Duration::seconds(301));
+        let checks = offline_checks(&stale, &args(), now);
+        assert!(checks.iter().any(|check| check.name == "age" && !check.ok));
+
+        let future = plan(leverage(5), now + Duration::seconds(120));
+        assert!(!age_check(future.created_at, now, 300).ok);
+    }
+
+    #[test]
+    fn drift_and_band_are_measured_in_basis_points() {
+        assert!(drift_check("BTC-USD", "100000", "100400", 50.0).ok);
+        assert!(!drift_check("BTC-USD", "100000", "100600", 50.0).ok);
+        assert!(!drift_check("BTC-USD", "100000", "", 50.0).ok);
+
+        assert!(band_check("BTC-USD", "96000", "100000", 500.0).ok);
+        assert!(!band_check("BTC-USD", "94000", "100000", 500.0).ok);
+        assert!(!band_check("BTC-USD", "-1", "100000", 500.0).ok);
+    }
+
+    #[test]
+    fn cancels_narrow_to_orders_still_open() {
+        let planned = Action::CancelOrders {
+            symbol: "BTC-USD".to_string(),
+            order_ids: vec![1, 2, 3],
+        };
+        let open = HashSet::from([2, 3, 4]);
+        assert_eq!(
+            narrow_cancel(&planned, &open),
+            Some(Action::CancelOrders {
+                symbol: "BTC-USD".to_string(),
+                order_ids: vec![2, 3],
+            })
+        );
+        assert_eq!(narrow_cancel(&planned, &HashSet::new()), None);
+
+        let single = Action::CancelOrder {
+            symbol: "BTC-USD".to_string(),
+            order_id: 9,
+        };
+        assert_eq!(narrow_cancel(&single, &open), None);
+        assert_eq!(narrow_cancel(&leverage(3), &open), Some(leverage(3)));
+    }
+}
@@ -0,0 +1,629 @@
+//! `--plan-out` and `standx apply` — two-phase execution
+//!
+//! A mutating command run with `--plan-out FILE` sends nothing. It records
+//! the exact request bodies the SDK would sign and post, the market each
+//! symbol was trading at, and the open orders any cancel targets, so a human
+//! can review what an agent intends to do. `standx apply FILE` then checks
+//! the plan is still fresh and the market has not moved away from it before
+//! executing (see [`apply`]).
+//!
+//! A cancel-all is planned as the explicit list of order IDs open at the
+//! time: applying it never cancels an order the reviewer did not see.
+
+mod apply;
+
+pub use apply::handle_apply;
+pub(super) use apply::output_schema as apply_output_schema;
+
+use super::leverage::change_leverage;
+use super::margin::{change_margin_mode, transfer_margin};
+use super::order::{cancel, create_params, new_client_order_id, submit, Journal};
+use crate::audit;
+use crate::cli::{Commands, LeverageCommands, MarginCommands, OrderCommands, OutputFormat};
+use crate::output;
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use standx_sdk::client::account::{
+    change_leverage_body, change_margin_mode_body, transfer_margin_body,
+};
+use standx_sdk::client::order::{
+    cancel_order_body, cancel_orders_body, create_order_body, CreateOrderParams,
+};
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{Order, OrderSide, OrderType, TimeInForce};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Plan file format version; `apply` refuses any other.
+const PLAN_VERSION: u32 = 1;
+
+/// Depth levels fetched to record the top of book.
+const CONTEXT_DEPTH_LEVELS: u32 = 5;
+
+/// A reviewed-then-applied set of actions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(super) struct Plan {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// The command line that produced the plan.
+    pub command: String,
+    pub actions: Vec<PlannedAction>,
+    /// Market state per symbol when the plan was made.
+    pub market: BTreeMap<String, MarketContext>,
+    /// Open orders the plan cancels, as they were when it was made.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cancels: Vec<Order>,
+}
+
+/// One action and the request it sends.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(super) struct PlannedAction {
+    pub action: Action,
+    pub request: PlannedRequest,
+}
+
+/// The signed-ready request: `apply` signs exactly this body at send time.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(super) struct PlannedRequest {
+    pub method: String,
+    pub path: String,
+    pub body: Value,
+}
+
+/// What a plan does, in the terms of the SDK call that does it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(super) enum Action {
+    CreateOrder {
+        symbol: String,
+        side: String,
+        order_type: String,
+        qty: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        price: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        time_in_force: Option<String>,
+        #[serde(default)]
+        reduce_only: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        sl_price: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tp_price: Option<String>,
+        /// Fixed when the plan is made, so applying it twice cannot place
+        /// the order twice.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        client_order_id: Option<String>,
+    },
+    CancelOrder {
+        symbol: String,
+        order_id: i64,
+    },
+    CancelOrders {
+        symbol: String,
+        order_ids: Vec<i64>,
+    },
+    ChangeLeverage {
+        symbol: String,
+        leverage: u32,
+    },
+    TransferMargin {
+        symbol: String,
+        amount: String,
+        direction: String,
+    },
+    ChangeMarginMode {
+        symbol: String,
+        margin_mode: String,
+    },
+}
+
+/// Prices a symbol was trading at when the plan was made.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(super) struct MarketContext {
+    pub mark_price: String,
+    pub index_price: String,
+    pub last_price: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_bid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_ask: Option<String>,
+}
+
+impl Action {
+    pub(super) fn symbol(&self) -> &str {
+        match self {
+            Action::CreateOrder { symbol, .. }
+            | Action::CancelOrder { symbol, .. }
+            | Action::CancelOrders { symbol, .. }
+            | Action::ChangeLeverage { symbol, .. }
+            | Action::TransferMargin { symbol, .. }
+            | Action::ChangeMarginMode { symbol, .. } => symbol,
+        }
+    }
+
+    /// SDK parameters of a `CreateOrder`, validated as `order create` would.
+    fn order_params(&self) -> Result<CreateOrderParams> {
+        let Action::CreateOrder {
+            symbol,
+            side,
+            order_type,
+            qty,
+            price,
+            time_in_force,
+            reduce_only,
+            sl_price,
+            tp_price,
+            client_order_id,
+        } = self
+        else {
+            bail!("not an order creation");
+        };
+        create_params(OrderCommands::Create {
+            symbol: symbol.clone(),
+            side: side.clone(),
+            order_type: order_type.clone(),
+            qty: qty.clone(),
+            price: price.clone(),
+            tif: time_in_force.clone(),
+            reduce_only: *reduce_only,
+            sl_price: sl_price.clone(),
+            tp_price: tp_price.clone(),
+            client_order_id: client_order_id.clone(),
+        })
+    }
+
+    /// The request the SDK signs and posts for this action.
+    pub(super) fn request(&self) -> Result<PlannedRequest> {
+        let (path, body) = match self {
+            Action::CreateOrder { .. } => {
+                ("/api/new_order", create_order_body(&self.order_params()?))
+            }
+            Action::CancelOrder { order_id, .. } => {
+                ("/api/cancel_order", cancel_order_body(*order_id))
+            }
+            Action::CancelOrders { order_ids, .. } => {
+                ("/api/cancel_orders", cancel_orders_body(order_ids))
+            }
+            Action::ChangeLeverage { symbol, leverage } => (
+                "/api/change_leverage",
+                change_leverage_body(symbol, *leverage),
+            ),
+            Action::TransferMargin {
+                symbol,
+                amount,
+                direction,
+            } => (
+                "/api/transfer_margin",
+                transfer_margin_body(symbol, amount, direction)?,
+            ),
+            Action::ChangeMarginMode {
+                symbol,
+                margin_mode,
+            } => (
+                "/api/change_margin_mode",
+                change_margin_mode_body(symbol, margin_mode),
+            ),
+        };
+        Ok(PlannedRequest {
+            method: "POST".to_string(),
+            path: path.to_string(),
+            body,
+        })
+    }
+
+    /// Send the action and return what the venue acknowledged.
+    pub(super) async fn execute(&self, client: &StandXClient) -> Result<Value> {
+        Ok(match self {
+            Action::CreateOrder { .. } => {
+                let submission =
+                    submit(client, self.order_params()?, &Journal::open_default()).await?;
+                serde_json::to_value(submission.order)?
+            }
+            Action::CancelOrder { order_id, symbol } => {
+                cancel(client, symbol, &order_id.to_string()).await?;
+                serde_json::json!({ "cancelled": order_id })
+            }
+            Action::CancelOrders { symbol, order_ids } => {
+                audit::audited(
+                    "orders_cancel",
+                    symbol,
+                    cancel_orders_body(order_ids),
+                    client.cancel_orders(order_ids),
+                )
+                .await?;
+                serde_json::json!({ "cancelled": order_ids })
+            }
+            Action::ChangeLeverage { symbol, leverage } => {
+                change_leverage(client, symbol, *leverage).await?;
+                serde_json::json!({ "leverage": leverage })
+            }
+            Action::TransferMargin {
+                symbol,
+                amount,
+                direction,
+            } => {
+                transfer_margin(client, symbol, amount, direction).await?;
+                serde_json::json!({ "transferred": amount, "direction": direction })
+            }
+            Action::ChangeMarginMode {
+                symbol,
+                margin_mode,
+            } => {
+                change_margin_mode(client, symbol, margin_mode).await?;
+                serde_json::json!({ "margin_mode": margin_mode })
+            }
+        })
+    }
+
+    /// One-line summary for review output.
+    pub(super) fn describe(&self) -> String {
+        match self {
+            Action::CreateOrder {
+                symbol,
+                side,
+                order_type,
+                qty,
+                price,
+                reduce_only,
+                ..
+            } => {
+                let mut text = format!("{side} {qty} {symbol} {order_type}");
+                if let Some(price) = price {
+                    text.push_str(&format!(" @ {price}"));
+                }
+                if *reduce_only {
+                    text.push_str(" reduce-only");
+                }
+                text
+            }
+            Action::CancelOrder { symbol, order_id } => format!("cancel {symbol} order {order_id}"),
+            Action::CancelOrders { symbol, order_ids } => {
+                format!("cancel {} {symbol} order(s)", order_ids.len())
+            }
+            Action::ChangeLeverage { symbol, leverage } => {
+                format!("set {symbol} leverage to {leverage}x")
+            }
+            Action::TransferMargin {
+                symbol,
+                amount,
+                direction,
+            } => format!("{direction} {amount} margin on {symbol}"),
+            Action::ChangeMarginMode {
+                symbol,
+                margin_mode,
+            } => format!("set {symbol} margin mode to {margin_mode}"),
+        }
+    }
+}
+
+/// The action a mutating command would take, before any lookups.
+fn action_for(command: &Commands) -> Result<Action> {
+    let unsupported = || {
+        anyhow!(
+            "--plan-out only applies to mutating commands: order create/cancel/cancel-all, \
+             leverage set, margin transfer and margin mode --set"
+        )
+    };
+    Ok(match command {
+        Commands::Order { command } => match command {
+            OrderCommands::Create {
+                symbol,
+                side,
+                order_type,
+                qty,
+                price,
+                tif,
+                reduce_only,
+                sl_price,
+                tp_price,
+                client_order_id,
+            } => {
+                let action = Action::CreateOrder {
+                    symbol: symbol.clone(),
+                    side: side.to_lowercase(),
+                    order_type: order_type.to_lowercase(),
+                    qty: qty.clone(),
+                    price: price.clone(),
+                    time_in_force: tif.as_ref().map(|tif| tif.to_uppercase()),
+                    reduce_only: *reduce_only,
+                    sl_price: sl_price.clone(),
+                    tp_price: tp_price.clone(),
+                    client_order_id: Some(
+                        client_order_id.clone().unwrap_or_else(new_client_order_id),
+                    ),
+                };
+                let params = action.order_params()?;
+                if params.order_type == OrderType::Limit && params.price.is_none() {
+                    bail!("a limit order needs --price");
+                }
+                normalize_order(action, &params)
+            }
+            OrderCommands::Cancel { symbol, order_id } => Action::CancelOrder {
+                symbol: symbol.clone(),
+                order_id: order_id
+                    .parse()
+                    .map_err(|_| anyhow!("expected an integer order ID, got '{order_id}'"))?,
+            },
+            OrderCommands::CancelAll { symbol } => Action::CancelOrders {
+                symbol: symbol.clone(),
+                order_ids: Vec::new(),
+            },
+        },
+        Commands::Leverage {
+            command: LeverageCommands::Set { symbol, leverage },
+        } => Action::ChangeLeverage {
+            symbol: symbol.clone(),
+            leverage: leverage
+                .parse()
+                .map_err(|_| anyhow!("Invalid leverage value: {leverage}"))?,
+        },
+        Commands::Margin { command } => match command {
+            MarginCommands::Transfer {
+                symbol,
+                amount,
+                direction,
+            } => Action::TransferMargin {
+                symbol: symbol.clone(),
+                amount: amount.clone(),
+                direction: direction.clone(),
+            },
+            MarginCommands::Mode {
+                symbol,
+                set: Some(mode),
+            } => Action::ChangeMarginMode {
+                symbol: symbol.clone(),
+                margin_mode: mode.clone(),
+            },
+            MarginCommands::Mode { set: None, .. } => return Err(unsupported()),
+        },
+        _ => return Err(unsupported()),
+    })
+}
+
+/// Spell side, type and time in force the way the request will.
+fn normalize_order(action: Action, params: &CreateOrderParams) -> Action {
+    let Action::CreateOrder {
+        symbol,
+        qty,
+        price,
+        reduce_only,
+        sl_price,
+        tp_price,
+        client_order_id,
+        ..
+    } = action
+    else {
+        return action;
+    };
+    Action::CreateOrder {
+        symbol,
+        side: match params.side {
+            OrderSide::Buy => "buy",
+            OrderSide::Sell => "sell",
+        }
+        .to_string(),
+        order_type: match params.order_type {
+            OrderType::Limit => "limit",
+            OrderType::Market => "market",
+        }
+        .to_string(),
+        qty,
+        price,
+        time_in_force: params.time_in_force.map(|tif| {
+            match tif {
+                TimeInForce::Gtc => "GTC",
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Fok => "FOK",
+                TimeInForce::Alo => "ALO",
+            }
+            .to_string()
+        }),
+        reduce_only,
+        sl_price,
+        tp_price,
+        client_order_id,
+    }
+}
+
+async fn market_context(client: &StandXClient, symbol: &str) -> Result<MarketContext> {
+    let (price, depth) = tokio::join!(
+        client.get_symbol_price(symbol),
+        client.get_depth(symbol, Some(CONTEXT_DEPTH_LEVELS))
+    );
+    let price = price.with_context(|| format!("failed to fetch {symbol} price"))?;
+    let depth = depth.ok();
+    Ok(MarketContext {
+        mark_price: price.mark_price,
+        index_price: price.index_price,
+        last_price: price.last_price,
+        best_bid: depth
+            .as_ref()
+            .and_then(|book| book.best_bid().map(str::to_string)),
+        best_ask: depth
+            .as_ref()
+            .and_then(|book| book.best_ask().map(str::to_string)),
+    })
+}
+
+/// Build the plan for `command`, resolving cancels against open orders.
+async fn build(client: &StandXClient, command: &Commands, command_line: String) -> Result<Plan> {
+    let mut action = action_for(command)?;
+    let symbol = action.symbol().to_string();
+    let mut cancels = Vec::new();
+    match &mut action {
+        Action::CancelOrder { order_id, .. } => {
+            let open = client.get_open_orders(Some(&symbol)).await?;
+            let order = open
+                .into_iter()
+                .find(|order| order.id == order_id.to_string())
+                .ok_or_else(|| anyhow!("order {order_id} is not open on {symbol}"))?;
+            cancels.push(order);
+        }
+        Action::CancelOrders { order_ids, .. } => {
+            cancels = client.get_open_orders(Some(&symbol)).await?;
+            if cancels.is_empty() {
+                bail!("no open orders on {symbol}; nothing to plan");
+            }
+            *order_ids = cancels
+                .iter()
+                .map(|order| {
+                    order.id.parse().map_err(|_| {
+                        anyhow!("exchange returned non-integer order ID '{}'", order.id)
+                    })
+                })
+                .collect::<Result<_>>()?;
+        }
+        _ => {}
+    }
+    let mut market = BTreeMap::new();
+    market.insert(symbol.clone(), market_context(client, &symbol).await?);
+    let request = action.request()?;
+    Ok(Plan {
+        version: PLAN_VERSION,
+        created_at: Utc::now(),
+        command: command_line,
+        actions: vec![PlannedAction { action, request }],
+        market,
+        cancels,
+    })
+}
+
+/// Record `command` as a plan in `path` instead of executing it.
+pub async fn handle_plan_out(
+    command: &Commands,
+    path: &Path,
+    output_format: OutputFormat,
+) -> Result<()> {
+    // Reject non-mutating commands before touching the network.
+    action_for(command)?;
+    let client = StandXClient::new()?;
+    let command_line = std::env::args().collect::<Vec<_>>().join(" ");
+    let plan = build(&client, command, command_line).await?;
+    std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(&plan)?))
+        .with_context(|| format!("failed to write plan to {}", path.display()))?;
+
+    match output_format {
+        OutputFormat::Json => println!("{}", output::format_json(&plan)?),
+        OutputFormat::Quiet => println!("{}", path.display()),
+        OutputFormat::Table | OutputFormat::Csv => {
+            println!("📝 Plan written to {} (nothing was sent)", path.display());
+            for planned in &plan.actions {
+                println!("   • {}", planned.action.describe());
+            }
+            for (symbol, context) in &plan.market {
+                println!(
+                    "   {symbol}: mark {} / bid {} / ask {}",
+                    context.mark_price,
+                    context.best_bid.as_deref().unwrap_or("-"),
+                    context.best_ask.as_deref().unwrap_or("-")
+                );
+            }
+            println!("\nReview it, then run: standx apply {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn command(argv: &[&str]) -> Commands {
+        Cli::try_parse_from(argv).unwrap().command
+    }
+
+    #[test]
+    fn order_create_is_planned_as_its_exact_request() {
+        let action = action_for(&command(&[
+            "standx", "order", "create", "BTC-USD", "Buy", "LIMIT", "--qty", "0.1", "--price",
+            "65000", "--tif", "alo",
+        ]))
+        .unwrap();
+        assert_eq!(action.symbol(), "BTC-USD");
+        let request = action.request().unwrap();
+        assert_eq!(request.path, "/api/new_order");
+        assert_eq!(request.body["side"], "buy");
+        assert_eq!(request.body["order_type"], "limit");
+        assert_eq!(request.body["price"], "65000");
+        assert_eq!(request.body["time_in_force"], "alo");
+        assert_eq!(action.describe(), "buy 0.1 BTC-USD limit @ 65000");
+        let generated = request.body["cl_ord_id"].as_str().unwrap();
+        assert!(generated.starts_with("sxcli-"));
+        assert_eq!(action.request().unwrap().body["cl_ord_id"], generated);
+
+        let keyed = action_for(&command(&[
+            "standx",
+            "order",
+            "create",
+            "BTC-USD",
+            "sell",
+            "market",
+            "--qty",
+            "0.1",
+            "--client-order-id",
+            "retry-1",
+        ]))
+        .unwrap();
+        assert_eq!(keyed.request().unwrap().body["cl_ord_id"], "retry-1");
+
+        let error = action_for(&command(&[
+            "standx", "order", "create", "BTC-USD", "buy", "limit", "--qty", "0.1",
+        ]))
+        .unwrap_err();
+        assert!(error.to_string().contains("--price"));
+    }
+
+    #[test]
+    fn only_mutating_commands_can_be_planned() {
+        let leverage = action_for(&command(&["standx", "leverage", "set", "BTC-USD", "10"]));
+        assert_eq!(
+            leverage.unwrap().request().unwrap().body,
+            serde_json::json!({ "symbol": "BTC-USD", "leverage": 10 })
+        );
+        let withdraw = action_for(&command(&[
+            "standx", "margin", "transfer", "BTC-USD", "25", "-d", "withdraw",
+        ]))
+        .unwrap();
+        assert_eq!(withdraw.request().unwrap().body["amount_in"], "-25");
+
+        for argv in [
+            &["standx", "market", "ticker", "BTC-USD"][..],
+            &["standx", "margin", "mode", "BTC-USD"][..],
+            &["standx", "leverage", "get", "BTC-USD"][..],
+        ] {
+            assert!(action_for(&command(argv)).is_err(), "{argv:?}");
+        }
+        assert!(action_for(&command(&[
+            "standx", "order", "cancel", "BTC-USD", "-i", "abc"
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn plans_round_trip_through_json() {
+        let action = Action::CancelOrders {
+            symbol: "ETH-USD".to_string(),
+            order_ids: vec![7, 9],
+        };
+        let plan = Plan {
+            version: PLAN_VERSION,
+            created_at: Utc::now(),
+            command: "standx order cancel-all ETH-USD --plan-out p.json".to_string(),
+            actions: vec![PlannedAction {
+                request: action.request().unwrap(),
+                action,
+            }],
+            market: BTreeMap::new(),
+            cancels: Vec::new(),
+        };
+        let text = serde_json::to_string_pretty(&plan).unwrap();
+        assert!(text.contains("\"type\": \"cancel_orders\""));
+        assert!(text.contains("\"order_id_list\""));
+        let parsed: Plan = serde_json::from_str(&text).unwrap();
+        assert_eq!(parsed, plan);
+    }
+}
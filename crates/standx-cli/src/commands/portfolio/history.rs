@@ -0,0 +1,260 @@
+//! Local store of portfolio samples written by `portfolio record` and
+//! `portfolio --watch --record`.
+//!
+//! One JSON line per sample in `portfolio-history.jsonl` under the data
+//! directory (`STANDX_PORTFOLIO_HISTORY` relocates it). Lines are only ever
+//! appended, so a crash mid-write costs at most the last sample, which
+//! [`History::read`] skips.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use standx_sdk::models::{Balance, OrderSide, Position};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the history file.
+const HISTORY_ENV: &str = "STANDX_PORTFOLIO_HISTORY";
+
+/// Account state at one point in time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct Sample {
+    pub ts: DateTime<Utc>,
+    pub balance: f64,
+    pub equity: f64,
+    pub available: f64,
+    /// Equity not available for new positions (position and order margin).
+    pub margin_used: f64,
+    /// `margin_used` as a percentage of equity.
+    pub margin_usage_pct: f64,
+    pub upnl: f64,
+    pub pnl_24h: f64,
+    #[serde(default)]
+    pub positions: Vec<PositionSample>,
+}
+
+/// One open position within a [`Sample`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(super) struct PositionSample {
+    pub symbol: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<OrderSide>,
+    pub qty: f64,
+    pub entry_price: f64,
+    pub mark_price: f64,
+    pub upnl: f64,
+    pub realized_pnl: f64,
+    pub margin: f64,
+}
+
+fn number(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+impl Sample {
+    pub(super) fn new(ts: DateTime<Utc>, balance: &Balance, positions: &[Position]) -> Self {
+        let equity = number(&balance.equity);
+        let available = number(&balance.cross_available);
+        let margin_used = (equity - available).max(0.0);
+        Self {
+            ts,
+            balance: number(&balance.balance),
+            equity,
+            available,
+            margin_used,
+            margin_usage_pct: if equity > 0.0 {
+                margin_used / equity * 100.0
+            } else {
+                0.0
+            },
+            upnl: number(&balance.upnl),
+            pnl_24h: number(&balance.pnl_24h),
+            positions: positions
+                .iter()
+                .map(|position| PositionSample {
+                    symbol: position.symbol.clone(),
+                    side: position.side,
+                    qty: number(&position.qty),
+                    entry_price: number(&position.entry_price),
+                    mark_price: number(&position.mark_price),
+                    upnl: number(&position.upnl),
+                    realized_pnl: number(&position.realized_pnl),
+                    margin: number(&position.holding_margin),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A sample with every optional field set, for `standx schema`.
+pub(super) fn example_sample() -> Sample {
+    Sample {
+        ts: Utc::now(),
+        balance: 0.0,
+        equity: 0.0,
+        available: 0.0,
+        margin_used: 0.0,
+        margin_usage_pct: 0.0,
+        upnl: 0.0,
+        pnl_24h: 0.0,
+        positions: vec![PositionSample {
+            symbol: String::new(),
+            side: Some(OrderSide::Buy),
+            qty: 0.0,
+            entry_price: 0.0,
+            mark_price: 0.0,
+            upnl: 0.0,
+            realized_pnl: 0.0,
+            margin: 0.0,
+        }],
+    }
+}
+
+/// The history file.
+#[derive(Debug, Clone)]
+pub(super) struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub(super) fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The history at `$STANDX_PORTFOLIO_HISTORY`, or in the user data
+    /// directory.
+    pub(super) fn open_default() -> Self {
+        let path = std::env::var_os(HISTORY_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                dirs::data_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("standx")
+                    .join("portfolio-history.jsonl")
+            });
+        Self::new(path)
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(super) fn append(&self, sample: &Sample) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let mut line = serde_json::to_string(sample)?;
+        line.push('\n');
+        let mut append = || -> std::io::Result<()> {
+            let mut file = std::fs::OpenOptions::new()
+                .read(true)
+                .append(true)
+                .create(true)
+                .open(&self.path)?;
+            // Start on a fresh line after a torn write so this sample is not
+            // lost with it.
+            if file.metadata()?.len() > 0 {
+                let mut last = [0u8];
+                file.seek(SeekFrom::End(-1))?;
+                file.read_exact(&mut last)?;
+                if last[0] != b'\n' {
+                    line.insert(0, '\n');
+                }
+            }
+            file.write_all(line.as_bytes())
+        };
+        append().with_context(|| format!("failed to append to {}", self.path.display()))
+    }
+
+    /// Samples with `from <= ts <= to` (Unix seconds), oldest first.
+    pub(super) fn read(&self, from: Option<i64>, to: i64) -> Result<Vec<Sample>> {
+        let contents = match std::fs::read_to_string(&self.path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => {
+                return Err(error).with_context(|| format!("reading {}", self.path.display()))
+            }
+        };
+        let mut samples: Vec<Sample> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Sample>(line).ok())
+            .filter(|sample| {
+                let ts = sample.ts.timestamp();
+                from.map_or(true, |from| ts >= from) && ts <= to
+            })
+            .collect();
+        samples.sort_by_key(|sample| sample.ts);
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample(ts: i64, equity: f64) -> Sample {
+        Sample {
+            ts: Utc.timestamp_opt(ts, 0).unwrap(),
+            balance: equity,
+            equity,
+            available: equity,
+            margin_used: 0.0,
+            margin_usage_pct: 0.0,
+            upnl: 0.0,
+            pnl_24h: 0.0,
+            positions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn appends_and_reads_a_time_range() {
+        let dir = tempfile::tempdir().unwrap();
+        let history = History::new(dir.path().join("nested").join("history.jsonl"));
+        assert!(history.read(None, i64::MAX).unwrap().is_empty());
+
+        for (ts, equity) in [(300, 103.0), (100, 100.0), (200, 101.0)] {
+            history.append(&sample(ts, equity)).unwrap();
+        }
+        // A torn line from an interrupted write is skipped, and the next
+        // sample still lands on its own line.
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(history.path())
+            .unwrap()
+            .write_all(br#"{"ts":"#)
+            .unwrap();
+        history.append(&sample(400, 104.0)).unwrap();
+
+        let all = history.read(None, i64::MAX).unwrap();
+        let equities: Vec<f64> = all.iter().map(|sample| sample.equity).collect();
+        assert_eq!(equities, vec![100.0, 101.0, 103.0, 104.0]);
+        assert_eq!(
+            history.read(Some(150), 250).unwrap(),
+            vec![sample(200, 101.0)]
+        );
+    }
+
+    #[test]
+    fn derives_margin_usage_from_balance() {
+        let balance = Balance {
+            balance: "1000".to_string(),
+            cross_available: "750".to_string(),
+            cross_balance: "1000".to_string(),
+            cross_margin: "250".to_string(),
+            cross_upnl: "-12.5".to_string(),
+            equity: "1000".to_string(),
+            isolated_balance: "0".to_string(),
+            isolated_upnl: "0".to_string(),
+            locked: "0".to_string(),
+            pnl_24h: "4".to_string(),
+            pnl_freeze: "0".to_string(),
+            upnl: "-12.5".to_string(),
+        };
+        let sample = Sample::new(Utc::now(), &balance, &[]);
+        assert_eq!(sample.margin_used, 250.0);
+        assert_eq!(sample.margin_usage_pct, 25.0);
+        assert_eq!(sample.upnl, -12.5);
+    }
+}
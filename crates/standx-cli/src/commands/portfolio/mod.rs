@@ -0,0 +1,272 @@
+//! `standx portfolio` — live portfolio summary and its recorded history
+//!
+//! The bare command renders a [`PortfolioSnapshot`]; `portfolio record` (or
+//! `--watch --record`) appends samples to the local [`History`], and
+//! `portfolio history` turns them into an equity curve with drawdown and
+//! daily-return statistics.
+
+mod history;
+mod stats;
+
+use super::schema::{trace, Definitions};
+use super::util::{is_auth_error, parse_time_string, run_watch_loop, shutdown_signal};
+use crate::cli::*;
+use crate::output;
+use anyhow::{bail, Result};
+use chrono::Utc;
+use history::{History, Sample};
+use serde_json::Value;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{PortfolioSnapshot, Position};
+use std::time::Duration;
+
+/// Portfolio command for direct execution (without subcommands)
+#[derive(Debug)]
+pub enum PortfolioCommand {
+    Snapshot {
+        _verbose: bool,
+        watch: Option<u64>,
+        record: bool,
+    },
+    Record {
+        interval: Option<u64>,
+    },
+    History {
+        from: Option<String>,
+        to: Option<String>,
+    },
+}
+
+/// Handle portfolio commands - view portfolio summary and performance
+pub async fn handle_portfolio(
+    command: PortfolioCommand,
+    output_format: OutputFormat,
+) -> Result<()> {
+    match command {
+        PortfolioCommand::Snapshot {
+            _verbose,
+            watch,
+            record,
+        } => {
+            let client = StandXClient::new()?;
+            let history = record.then(History::open_default);
+            run_watch_loop(
+                watch,
+                || build_portfolio_output(&client, _verbose, output_format, history.as_ref()),
+                "Portfolio refresh failed",
+                None,
+            )
+            .await?;
+        }
+        PortfolioCommand::Record { interval } => {
+            let client = StandXClient::new()?;
+            let history = History::open_default();
+            let Some(interval) = interval else {
+                return record_sample(&client, &history, output_format).await;
+            };
+            let shutdown = shutdown_signal();
+            tokio::pin!(shutdown);
+            loop {
+                if let Err(error) = record_sample(&client, &history, output_format).await {
+                    eprintln!("⚠️  Portfolio sample failed: {error}");
+                }
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(Duration::from_secs(interval.max(1))) => {}
+                }
+            }
+        }
+        PortfolioCommand::History { from, to } => {
+            let now = Utc::now().timestamp();
+            let from = from
+                .as_deref()
+                .map(|from| parse_time_string(from, false))
+                .transpose()?;
+            let to = match to {
+                Some(to) => parse_time_string(&to, true)?.min(now),
+                None => now,
+            };
+            if from.is_some_and(|from| from >= to) {
+                bail!("history start must be before its end");
+            }
+            let samples = History::open_default().read(from, to)?;
+            let label = |secs: i64| {
+                chrono::DateTime::from_timestamp(secs, 0)
+                    .map(|time| time.to_rfc3339())
+                    .unwrap_or_else(|| secs.to_string())
+            };
+            let from = match (from, samples.first()) {
+                (Some(from), _) => label(from),
+                (None, Some(first)) => first.ts.to_rfc3339(),
+                (None, None) => "-".to_string(),
+            };
+            let report = stats::equity_report(from, label(to), &samples);
+            print!("{}", stats::render(&report, output_format)?);
+        }
+    }
+    Ok(())
+}
+
+/// Fetch the account and append one sample to `history`.
+async fn record_sample(
+    client: &StandXClient,
+    history: &History,
+    output_format: OutputFormat,
+) -> Result<()> {
+    let balance = client.get_balance().await?;
+    let positions = open_positions(client.get_positions(None).await?);
+    let sample = Sample::new(Utc::now(), &balance, &positions);
+    history.append(&sample)?;
+    match output_format {
+        OutputFormat::Table => println!(
+            "📈 {} equity ${:.2}, margin usage {:.1}%, {} positions → {}",
+            sample.ts.format("%Y-%m-%d %H:%M:%S"),
+            sample.equity,
+            sample.margin_usage_pct,
+            sample.positions.len(),
+            history.path().display()
+        ),
+        OutputFormat::Quiet => {}
+        OutputFormat::Json | OutputFormat::Csv => {
+            println!("{}", output::format_event(&serde_json::to_value(&sample)?))
+        }
+    }
+    Ok(())
+}
+
+fn open_positions(positions: Vec<Position>) -> Vec<Position> {
+    positions
+        .into_iter()
+        .filter(|p| p.qty.parse::<f64>().unwrap_or(0.0) > 0.0)
+        .collect()
+}
+
+/// JSON schema of one `portfolio record` line, for `standx schema`.
+pub(super) fn sample_schema(definitions: &mut Definitions) -> Value {
+    trace(definitions, &history::example_sample())
+}
+
+/// JSON output schema of `portfolio history`, for `standx schema`.
+pub(super) fn history_schema(definitions: &mut Definitions) -> Value {
+    trace(definitions, &stats::example_report())
+}
+
+/// Build portfolio output
+async fn build_portfolio_output(
+    client: &StandXClient,
+    verbose: bool,
+    output_format: OutputFormat,
+    history: Option<&History>,
+) -> Result<String> {
+    // Try to fetch authenticated data, handle auth errors gracefully
+    let balance_result = client.get_balance().await;
+    let balance = match balance_result {
+        Ok(b) => Some(b),
+        Err(e) => {
+            if is_auth_error(&e) {
+                None
+            } else {
+                return Err(e.into());
+            }
+        }
+    };
+
+    // If not authenticated, show market data only
+    let positions = if balance.is_some() {
+        let positions = match client.get_positions(None).await {
+            Ok(positions) => positions,
+            Err(e) if is_auth_error(&e) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        // Filter out zero-qty positions
+        open_positions(positions)
+    } else {
+        Vec::new()
+    };
+
+    if let Some(history) = history {
+        let Some(balance) = &balance else {
+            bail!("--record needs account data; run 'standx auth login'");
+        };
+        history.append(&Sample::new(Utc::now(), balance, &positions))?;
+    }
+
+    // Calculate total values
+    let total_value_usd = balance
+        .as_ref()
+        .map(|b| b.equity.clone())
+        .unwrap_or_default();
+    let total_pnl_24h = balance
+        .as_ref()
+        .map(|b| b.pnl_24h.clone())
+        .unwrap_or_default();
+    let total_pnl_realized = balance.as_ref().map(|b| b.upnl.clone()).unwrap_or_default();
+
+    // Create portfolio snapshot
+    let snapshot = PortfolioSnapshot {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        total_value_usd,
+        total_pnl_24h,
+        total_pnl_realized,
+        positions,
+    };
+
+    let rendered = match output_format {
+        OutputFormat::Table => {
+            let mut text = String::new();
+            if balance.is_none() {
+                text.push_str(
+                    "⚠️  Not authenticated. Run 'standx auth login' to access account data.\n\n",
+                );
+            }
+            text.push_str("=== Portfolio Summary ===\n");
+            text.push_str(&format!("Timestamp: {}\n\n", snapshot.timestamp));
+
+            // Account summary
+            text.push_str("--- Account ---\n");
+            text.push_str(&format!("  Total Value: ${}\n", snapshot.total_value_usd));
+            text.push_str(&format!("  PnL 24h: ${}\n", snapshot.total_pnl_24h));
+            text.push_str(&format!(
+                "  Unrealized PnL: ${}\n\n",
+                snapshot.total_pnl_realized
+            ));
+
+            // Positions
+            if !snapshot.positions.is_empty() {
+                text.push_str(&format!(
+                    "--- Positions ({}) ---\n",
+                    snapshot.positions.len()
+                ));
+                text.push_str(&format!("{}\n", output::format_table(snapshot.positions)));
+            } else {
+                text.push_str("--- No open positions ---\n");
+            }
+
+            if verbose {
+                text.push_str("\n--- Verbose Details ---\n");
+                if let Some(b) = &balance {
+                    text.push_str(&format!("  Balance: ${}\n", b.balance));
+                    text.push_str(&format!("  Available: ${}\n", b.cross_available));
+                    text.push_str(&format!("  Equity: ${}\n", b.equity));
+                    text.push_str(&format!("  Cross Margin: ${}\n", b.cross_margin));
+                    text.push_str(&format!("  Cross UPNL: ${}\n", b.cross_upnl));
+                    text.push_str(&format!("  Locked: ${}\n", b.locked));
+                } else {
+                    text.push_str("  (Not authenticated - no balance details)\n");
+                }
+            }
+            text
+        }
+        OutputFormat::Json => format!("{}\n", output::format_json(&snapshot)?),
+        OutputFormat::Csv => {
+            if !snapshot.positions.is_empty() {
+                format!("{}\n", output::format_csv(&snapshot.positions)?)
+            } else {
+                "No positions to display\n".to_string()
+            }
+        }
+        OutputFormat::Quiet => String::new(),
+    };
+
+    Ok(rendered)
+}
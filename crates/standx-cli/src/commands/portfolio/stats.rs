@@ -0,0 +1,366 @@
+//! Equity curve, drawdown and daily-return statistics over recorded samples.
+//!
+//! Returns are computed from equity alone, so deposits, withdrawals and
+//! transfers show up as gains and losses; compare against `report pnl` when
+//! the account was funded during the range.
+
+use super::history::Sample;
+use crate::cli::OutputFormat;
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Days per year used to annualize the Sharpe and Sortino ratios (crypto
+/// markets trade every day).
+const TRADING_DAYS: f64 = 365.0;
+
+/// Width of the equity sparkline in the table output.
+const SPARKLINE_WIDTH: usize = 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct CurvePoint {
+    pub ts: String,
+    pub equity: f64,
+    /// Distance below the running peak, in percent.
+    pub drawdown_pct: f64,
+}
+
+/// Closing state of one UTC day.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct DayRow {
+    pub date: String,
+    pub equity: f64,
+    /// Change from the previous day's close; `None` on the first day.
+    pub return_pct: Option<f64>,
+    pub drawdown_pct: f64,
+    pub margin_usage_pct: f64,
+    pub upnl: f64,
+}
+
+/// Statistics of the daily returns.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct ReturnStats {
+    pub days: usize,
+    pub mean_daily_return_pct: Option<f64>,
+    pub daily_volatility_pct: Option<f64>,
+    /// Annualized mean over standard deviation of daily returns (risk-free
+    /// rate taken as zero).
+    pub sharpe: Option<f64>,
+    /// Like `sharpe`, with only downside deviation in the denominator.
+    pub sortino: Option<f64>,
+    pub best_day_pct: Option<f64>,
+    pub worst_day_pct: Option<f64>,
+    pub positive_days: usize,
+    pub negative_days: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct EquityReport {
+    pub from: String,
+    pub to: String,
+    pub samples: usize,
+    pub start_equity: f64,
+    pub end_equity: f64,
+    pub change: f64,
+    pub return_pct: Option<f64>,
+    pub peak_equity: f64,
+    pub max_drawdown: f64,
+    pub max_drawdown_pct: f64,
+    pub current_drawdown_pct: f64,
+    pub stats: ReturnStats,
+    pub daily: Vec<DayRow>,
+    pub curve: Vec<CurvePoint>,
+}
+
+/// A report with every optional field set, for `standx schema`.
+pub(super) fn example_report() -> EquityReport {
+    let stats = ReturnStats {
+        mean_daily_return_pct: Some(0.0),
+        daily_volatility_pct: Some(0.0),
+        sharpe: Some(0.0),
+        sortino: Some(0.0),
+        best_day_pct: Some(0.0),
+        worst_day_pct: Some(0.0),
+        ..ReturnStats::default()
+    };
+    EquityReport {
+        return_pct: Some(0.0),
+        stats,
+        daily: vec![DayRow {
+            return_pct: Some(0.0),
+            ..DayRow::default()
+        }],
+        curve: vec![CurvePoint::default()],
+        ..EquityReport::default()
+    }
+}
+
+fn pct(part: f64, whole: f64) -> Option<f64> {
+    (whole > 0.0).then(|| part / whole * 100.0)
+}
+
+/// Build the report from samples sorted oldest first.
+pub(super) fn equity_report(from: String, to: String, samples: &[Sample]) -> EquityReport {
+    let mut report = EquityReport {
+        from,
+        to,
+        samples: samples.len(),
+        ..EquityReport::default()
+    };
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        return report;
+    };
+    report.start_equity = first.equity;
+    report.end_equity = last.equity;
+    report.change = last.equity - first.equity;
+    report.return_pct = pct(report.change, first.equity);
+
+    let mut peak = f64::MIN;
+    for sample in samples {
+        peak = peak.max(sample.equity);
+        let drawdown = peak - sample.equity;
+        let drawdown_pct = pct(drawdown, peak).unwrap_or(0.0);
+        report.max_drawdown = report.max_drawdown.max(drawdown);
+        report.max_drawdown_pct = report.max_drawdown_pct.max(drawdown_pct);
+        report.current_drawdown_pct = drawdown_pct;
+        report.curve.push(CurvePoint {
+            ts: sample.ts.to_rfc3339(),
+            equity: sample.equity,
+            drawdown_pct,
+        });
+
+        let date = sample.ts.format("%Y-%m-%d").to_string();
+        let day = DayRow {
+            date,
+            equity: sample.equity,
+            return_pct: None,
+            drawdown_pct,
+            margin_usage_pct: sample.margin_usage_pct,
+            upnl: sample.upnl,
+        };
+        match report.daily.last_mut() {
+            Some(previous) if previous.date == day.date => *previous = day,
+            _ => report.daily.push(day),
+        }
+    }
+    report.peak_equity = peak;
+
+    let mut returns = Vec::new();
+    for index in 1..report.daily.len() {
+        let previous = report.daily[index - 1].equity;
+        let day = &mut report.daily[index];
+        day.return_pct = pct(day.equity - previous, previous);
+        returns.extend(day.return_pct);
+    }
+    report.stats = return_stats(&returns);
+    report
+}
+
+fn return_stats(returns: &[f64]) -> ReturnStats {
+    let mut stats = ReturnStats {
+        days: returns.len(),
+        positive_days: returns.iter().filter(|r| **r > 0.0).count(),
+        negative_days: returns.iter().filter(|r| **r < 0.0).count(),
+        best_day_pct: returns.iter().copied().reduce(f64::max),
+        worst_day_pct: returns.iter().copied().reduce(f64::min),
+        ..ReturnStats::default()
+    };
+    if returns.is_empty() {
+        return stats;
+    }
+    let n = returns.len() as f64;
+    let mean = returns.iter().sum::<f64>() / n;
+    stats.mean_daily_return_pct = Some(mean);
+    if returns.len() < 2 {
+        return stats;
+    }
+    let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    let volatility = variance.sqrt();
+    stats.daily_volatility_pct = Some(volatility);
+    let annualize = TRADING_DAYS.sqrt();
+    if volatility > 0.0 {
+        stats.sharpe = Some(mean / volatility * annualize);
+    }
+    let downside = (returns.iter().map(|r| r.min(0.0).powi(2)).sum::<f64>() / n).sqrt();
+    if downside > 0.0 {
+        stats.sortino = Some(mean / downside * annualize);
+    }
+    stats
+}
+
+pub(super) fn render(report: &EquityReport, output_format: OutputFormat) -> Result<String> {
+    Ok(match output_format {
+        OutputFormat::Table => format_table(report),
+        OutputFormat::Json => format!("{}\n", output::format_json(report)?),
+        OutputFormat::Csv => output::format_csv(&report.daily)?,
+        OutputFormat::Quiet => match report.return_pct {
+            Some(return_pct) => format!("{return_pct:.2}\n"),
+            None => String::new(),
+        },
+    })
+}
+
+fn optional(value: Option<f64>, decimals: usize) -> String {
+    value
+        .map(|value| format!("{value:.decimals$}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Equity scaled onto block characters, resampled to `width` columns.
+fn sparkline(curve: &[CurvePoint], width: usize) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    if curve.is_empty() {
+        return String::new();
+    }
+    let columns = width.min(curve.len());
+    let values: Vec<f64> = (0..columns)
+        .map(|column| curve[column * (curve.len() - 1) / (columns - 1).max(1)].equity)
+        .collect();
+    let low = values.iter().copied().fold(f64::MAX, f64::min);
+    let high = values.iter().copied().fold(f64::MIN, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if high > low {
+                BLOCKS[(((value - low) / (high - low)) * 7.0).round() as usize]
+            } else {
+                BLOCKS[3]
+            }
+        })
+        .collect()
+}
+
+fn format_table(report: &EquityReport) -> String {
+    let mut text = format!(
+        "=== Portfolio History ===\n{} → {}\n\n",
+        report.from, report.to
+    );
+    if report.samples == 0 {
+        text.push_str(
+            "ℹ️  No portfolio samples in the specified time range; \
+             run `standx portfolio record`\n",
+        );
+        return text;
+    }
+    text.push_str(&format!(
+        "  Equity curve: {}\n",
+        sparkline(&report.curve, SPARKLINE_WIDTH)
+    ));
+    text.push_str(&format!("  Samples: {}\n", report.samples));
+    text.push_str(&format!(
+        "  Equity: ${:.2} → ${:.2} ({:+.2}, {}%)\n",
+        report.start_equity,
+        report.end_equity,
+        report.change,
+        optional(report.return_pct, 2)
+    ));
+    text.push_str(&format!("  Peak: ${:.2}\n", report.peak_equity));
+    text.push_str(&format!(
+        "  Max drawdown: ${:.2} ({:.2}%), current {:.2}%\n",
+        report.max_drawdown, report.max_drawdown_pct, report.current_drawdown_pct
+    ));
+    let stats = &report.stats;
+    text.push_str(&format!(
+        "  Daily returns: mean {}%, volatility {}%, best {}%, worst {}% ({} up / {} down)\n",
+        optional(stats.mean_daily_return_pct, 3),
+        optional(stats.daily_volatility_pct, 3),
+        optional(stats.best_day_pct, 2),
+        optional(stats.worst_day_pct, 2),
+        stats.positive_days,
+        stats.negative_days
+    ));
+    text.push_str(&format!(
+        "  Sharpe (annualized): {}   Sortino: {}\n\n",
+        optional(stats.sharpe, 2),
+        optional(stats.sortino, 2)
+    ));
+
+    let header = format!(
+        "{:<10} {:>14} {:>9} {:>9} {:>8} {:>12}",
+        "DATE", "EQUITY", "RETURN%", "DD%", "MARGIN%", "UPNL"
+    );
+    let separator = "-".repeat(header.len());
+    text.push_str(&format!("{header}\n{separator}\n"));
+    for day in &report.daily {
+        text.push_str(&format!(
+            "{:<10} {:>14.2} {:>9} {:>9.2} {:>8.1} {:>12.2}\n",
+            day.date,
+            day.equity,
+            optional(day.return_pct, 2),
+            day.drawdown_pct,
+            day.margin_usage_pct,
+            day.upnl
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    const DAY: i64 = 86_400;
+
+    fn sample(ts: i64, equity: f64) -> Sample {
+        Sample {
+            ts: Utc.timestamp_opt(ts, 0).unwrap(),
+            balance: equity,
+            equity,
+            available: equity,
+            margin_used: 0.0,
+            margin_usage_pct: 0.0,
+            upnl: 0.0,
+            pnl_24h: 0.0,
+            positions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn computes_drawdown_and_daily_returns() {
+        // Two samples on day 0 (the later one closes it), then a drawdown
+        // from 110 to 99 and a partial recovery.
+        let samples = [
+            sample(0, 100.0),
+            sample(3_600, 110.0),
+            sample(DAY, 99.0),
+            sample(2 * DAY, 104.5),
+        ];
+        let report = equity_report("a".into(), "b".into(), &samples);
+
+        assert_eq!(report.samples, 4);
+        assert_eq!(report.change, 4.5);
+        assert!((report.return_pct.unwrap() - 4.5).abs() < 1e-9);
+        assert_eq!(report.peak_equity, 110.0);
+        assert_eq!(report.max_drawdown, 11.0);
+        assert!((report.max_drawdown_pct - 10.0).abs() < 1e-9);
+        assert!((report.current_drawdown_pct - 5.0).abs() < 1e-9);
+
+        let returns: Vec<Option<f64>> = report.daily.iter().map(|day| day.return_pct).collect();
+        assert_eq!(report.daily.len(), 3);
+        assert_eq!(returns[0], None);
+        assert!((returns[1].unwrap() + 10.0).abs() < 1e-9);
+        assert!((returns[2].unwrap() - 5.555_555_555).abs() < 1e-6);
+
+        let stats = &report.stats;
+        assert_eq!((stats.positive_days, stats.negative_days), (1, 1));
+        assert_eq!(stats.worst_day_pct, returns[1]);
+        let mean = (returns[1].unwrap() + returns[2].unwrap()) / 2.0;
+        assert!((stats.mean_daily_return_pct.unwrap() - mean).abs() < 1e-9);
+        let volatility = stats.daily_volatility_pct.unwrap();
+        assert!((stats.sharpe.unwrap() - mean / volatility * 365f64.sqrt()).abs() < 1e-9);
+        assert!(stats.sortino.is_some());
+    }
+
+    #[test]
+    fn empty_and_single_day_ranges_have_no_ratios() {
+        let empty = equity_report("a".into(), "b".into(), &[]);
+        assert_eq!(empty.samples, 0);
+        assert!(format_table(&empty).contains("portfolio record"));
+
+        let single = equity_report("a".into(), "b".into(), &[sample(0, 50.0)]);
+        assert_eq!(single.stats.days, 0);
+        assert_eq!(single.stats.sharpe, None);
+        assert_eq!(sparkline(&single.curve, 10).chars().count(), 1);
+    }
+}
@@ -0,0 +1,718 @@
+//! `standx record` — general-purpose public market-data recorder
+//!
+//! Subscribes to any combination of the public `price`, `depth_book`,
+//! `public_trade` and `kline` channels for many symbols on one socket and
+//! appends every update as an NDJSON envelope to rotating (optionally gzip
+//! compressed) files, for replay and offline research.
+//!
+//! Each file starts with a `header` line describing the session. `data` lines
+//! carry the raw channel payload plus envelope metadata: the recorder line
+//! number `n` (gap-free across files of one run), the venue `seq` and
+//! `server_time` when the venue sent them, and `received_at` stamped when the
+//! producer read the message off the socket. A dropped connection produces a
+//! `gap` line spanning the time no data could have been received, so replay
+//! never silently stitches across an outage.
+//!
+//! Read-only: no authentication, no orders.
+
+use super::util::shutdown_signal;
+use crate::cli::{RecordArgs, RecordChannel};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::Serialize;
+use standx_sdk::websocket::{StandXWebSocket, WsMessage};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+/// Version of the envelope schema written into every file header.
+pub(super) const RECORD_FORMAT_VERSION: u32 = 1;
+
+/// How long the producer waits before rebuilding a dropped connection.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Receipt stamp taken by the producer as soon as a message is read.
+#[derive(Debug, Clone, Copy)]
+struct Stamp {
+    at: DateTime<Utc>,
+    /// Monotonic milliseconds since the recorder started.
+    mono_ms: i64,
+}
+
+/// What the producer forwards to the writer.
+#[derive(Debug)]
+enum Observation {
+    Message(Box<WsMessage>, Stamp),
+    /// The managed connection gave up; the producer is rebuilding it.
+    SessionLost(Stamp),
+}
+
+/// One NDJSON line.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordLine {
+    Header {
+        format_version: u32,
+        file_index: u64,
+        started_at: String,
+        symbols: Vec<String>,
+        channels: Vec<RecordChannel>,
+        kline_interval: Option<String>,
+    },
+    Data {
+        n: u64,
+        channel: RecordChannel,
+        symbol: Option<String>,
+        /// Venue sequence number, when present.
+        seq: Option<u64>,
+        /// Venue timestamp, copied verbatim.
+        server_time: Option<String>,
+        received_at: String,
+        recv_mono_ms: i64,
+        /// Connection counter; increments on every (re)connect.
+        connection: u64,
+        data: serde_json::Value,
+    },
+    Gap {
+        n: u64,
+        reason: &'static str,
+        /// Last moment data was known to be flowing.
+        from: String,
+        /// Moment the connection was re-established.
+        to: String,
+        connection: u64,
+    },
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Turns socket observations into envelope lines and tracks connection gaps.
+#[derive(Debug)]
+struct Recorder {
+    channels: Vec<RecordChannel>,
+    /// The only symbol, used for payloads that do not name theirs.
+    sole_symbol: Option<String>,
+    next_n: u64,
+    connection: u64,
+    /// Set while disconnected: when data stopped flowing.
+    down_since: Option<DateTime<Utc>>,
+    last_data_at: Option<DateTime<Utc>>,
+    counts: BTreeMap<RecordChannel, u64>,
+    gaps: u64,
+}
+
+impl Recorder {
+    fn new(symbols: &[String], channels: &[RecordChannel]) -> Self {
+        Self {
+            channels: channels.to_vec(),
+            sole_symbol: (symbols.len() == 1).then(|| symbols[0].clone()),
+            next_n: 0,
+            connection: 0,
+            down_since: None,
+            last_data_at: None,
+            counts: BTreeMap::new(),
+            gaps: 0,
+        }
+    }
+
+    fn next_n(&mut self) -> u64 {
+        let n = self.next_n;
+        self.next_n += 1;
+        n
+    }
+
+    fn mark_down(&mut self, stamp: Stamp) {
+        if self.down_since.is_none() && self.connection > 0 {
+            self.down_since = Some(self.last_data_at.unwrap_or(stamp.at));
+        }
+    }
+
+    fn observe(&mut self, observation: Observation) -> Result<Option<RecordLine>> {
+        let (message, stamp) = match observation {
+            Observation::Message(message, stamp) => (*message, stamp),
+            Observation::SessionLost(stamp) => {
+                self.mark_down(stamp);
+                return Ok(None);
+            }
+        };
+        let (channel, symbol, seq, server_time, data) = match message {
+            WsMessage::Connected => {
+                self.connection += 1;
+                let Some(from) = self.down_since.take() else {
+                    return Ok(None);
+                };
+                self.gaps += 1;
+                return Ok(Some(RecordLine::Gap {
+                    n: self.next_n(),
+                    reason: "reconnect",
+                    from: format_time(from),
+                    to: format_time(stamp.at),
+                    connection: self.connection,
+                }));
+            }
+            WsMessage::Disconnected | WsMessage::Error(_) => {
+                self.mark_down(stamp);
+                return Ok(None);
+            }
+            WsMessage::Price(update) => (
+                RecordChannel::Price,
+                Some(update.data.symbol.clone()),
+                update.seq,
+                update.server_time,
+                serde_json::to_value(&update.data)?,
+            ),
+            WsMessage::Depth(update) => (
+                RecordChannel::Depth,
+                Some(update.data.symbol.clone()).filter(|symbol| !symbol.is_empty()),
+                update.seq,
+                update.server_time,
+                serde_json::to_value(&update.data)?,
+            ),
+            WsMessage::Trade(trade) => (
+                RecordChannel::Trade,
+                trade.symbol.clone(),
+                None,
+                Some(trade.time.clone()).filter(|time| !time.is_empty()),
+                serde_json::to_value(&trade)?,
+            ),
+            WsMessage::Kline(kline) => (
+                RecordChannel::Kline,
+                kline.symbol.clone(),
+                None,
+                Some(kline.time.to_string()),
+                serde_json::to_value(&kline)?,
+            ),
+            _ => return Ok(None),
+        };
+        if !self.channels.contains(&channel) {
+            return Ok(None);
+        }
+        self.last_data_at = Some(stamp.at);
+        *self.counts.entry(channel).or_default() += 1;
+        Ok(Some(RecordLine::Data {
+            n: self.next_n(),
+            channel,
+            symbol: symbol.or_else(|| self.sole_symbol.clone()),
+            seq,
+            server_time,
+            received_at: format_time(stamp.at),
+            recv_mono_ms: stamp.mono_ms,
+            connection: self.connection,
+            data,
+        }))
+    }
+}
+
+enum Sink {
+    Plain(BufWriter<File>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Sink {
+    fn writer(&mut self) -> &mut dyn Write {
+        match self {
+            Sink::Plain(writer) => writer,
+            Sink::Gzip(writer) => writer,
+        }
+    }
+
+    fn finish(self) -> std::io::Result<()> {
+        match self {
+            Sink::Plain(mut writer) => writer.flush(),
+            Sink::Gzip(writer) => writer.finish()?.flush(),
+        }
+    }
+}
+
+struct OpenFile {
+    sink: Sink,
+    path: PathBuf,
+    /// Uncompressed bytes written.
+    bytes: u64,
+    opened: Instant,
+}
+
+/// Session details repeated in every file header.
+#[derive(Debug, Clone)]
+struct SessionInfo {
+    symbols: Vec<String>,
+    channels: Vec<RecordChannel>,
+    kline_interval: Option<String>,
+}
+
+/// NDJSON writer that rolls over to a new file by age or size.
+struct RotatingWriter {
+    dir: PathBuf,
+    prefix: String,
+    compress: bool,
+    max_bytes: Option<u64>,
+    max_age: Option<Duration>,
+    session: SessionInfo,
+    file: Option<OpenFile>,
+    file_index: u64,
+}
+
+impl RotatingWriter {
+    fn new(
+        dir: PathBuf,
+        prefix: String,
+        compress: bool,
+        max_bytes: Option<u64>,
+        max_age: Option<Duration>,
+        session: SessionInfo,
+    ) -> Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create output directory '{}'", dir.display()))?;
+        Ok(Self {
+            dir,
+            prefix,
+            compress,
+            max_bytes,
+            max_age,
+            session,
+            file: None,
+            file_index: 0,
+        })
+    }
+
+    fn write(&mut self, line: &RecordLine) -> Result<()> {
+        if self.should_rotate() {
+            self.close()?;
+        }
+        if self.file.is_none() {
+            self.open()?;
+        }
+        let file = self.file.as_mut().expect("file opened above");
+        write_line(file, line)
+    }
+
+    fn should_rotate(&self) -> bool {
+        let Some(file) = &self.file else {
+            return false;
+        };
+        self.max_bytes.is_some_and(|max| file.bytes >= max)
+            || self.max_age.is_some_and(|max| file.opened.elapsed() >= max)
+    }
+
+    fn open(&mut self) -> Result<()> {
+        let now = Utc::now();
+        let extension = if self.compress { "ndjson.gz" } else { "ndjson" };
+        let path = self.dir.join(format!(
+            "{}-{}-{:04}.{extension}",
+            self.prefix,
+            now.format("%Y%m%dT%H%M%SZ"),
+            self.file_index
+        ));
+        let handle = File::options()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("failed to create output file '{}'", path.display()))?;
+        let buffered = BufWriter::new(handle);
+        let sink = if self.compress {
+            Sink::Gzip(GzEncoder::new(buffered, Compression::default()))
+        } else {
+            Sink::Plain(buffered)
+        };
+        let mut file = OpenFile {
+            sink,
+            path,
+            bytes: 0,
+            opened: Instant::now(),
+        };
+        write_line(
+            &mut file,
+            &RecordLine::Header {
+                format_version: RECORD_FORMAT_VERSION,
+                file_index: self.file_index,
+                started_at: format_time(now),
+                symbols: self.session.symbols.clone(),
+                channels: self.session.channels.clone(),
+                kline_interval: self.session.kline_interval.clone(),
+            },
+        )?;
+        eprintln!("record: writing {}", file.path.display());
+        self.file_index += 1;
+        self.file = Some(file);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.sink
+                .writer()
+                .flush()
+                .with_context(|| format!("failed to flush '{}'", file.path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn close(&mut self) -> Result<()> {
+        if let Some(file) = self.file.take() {
+            let path = file.path;
+            file.sink
+                .finish()
+                .with_context(|| format!("failed to finish '{}'", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_line(file: &mut OpenFile, line: &RecordLine) -> Result<()> {
+    let mut text = serde_json::to_string(line).context("failed to serialize record")?;
+    text.push('\n');
+    file.sink
+        .writer()
+        .write_all(text.as_bytes())
+        .with_context(|| format!("failed to write '{}'", file.path.display()))?;
+    file.bytes += text.len() as u64;
+    Ok(())
+}
+
+pub async fn handle_record(args: RecordArgs, verbose: bool) -> Result<()> {
+    let symbols: Vec<String> = args
+        .symbols
+        .split(',')
+        .map(str::trim)
+        .filter(|symbol| !symbol.is_empty())
+        .map(str::to_uppercase)
+        .collect();
+    if symbols.is_empty() {
+        bail!("--symbols must name at least one symbol");
+    }
+    let mut channels = args.channels.clone();
+    channels.sort();
+    channels.dedup();
+    let records_klines = channels.contains(&RecordChannel::Kline);
+    let session = SessionInfo {
+        symbols: symbols.clone(),
+        channels: channels.clone(),
+        kline_interval: records_klines.then(|| args.kline_interval.clone()),
+    };
+    let mut writer = RotatingWriter::new(
+        PathBuf::from(&args.out_dir),
+        args.prefix.clone(),
+        args.compress,
+        (args.rotate_mb > 0).then(|| args.rotate_mb.saturating_mul(1024 * 1024)),
+        (args.rotate_minutes > 0).then(|| Duration::from_secs(args.rotate_minutes * 60)),
+        session.clone(),
+    )?;
+    let mut recorder = Recorder::new(&symbols, &channels);
+
+    eprintln!(
+        "record: {} [{}] -> {}\nread-only; no auth, no orders. Press Ctrl+C to stop.",
+        symbols.join(","),
+        channels
+            .iter()
+            .map(|channel| channel.topic())
+            .collect::<Vec<_>>()
+            .join(","),
+        args.out_dir,
+    );
+
+    let (tx, mut rx) = mpsc::channel::<Observation>(8192);
+    let producer = tokio::spawn(run_producer(session, Instant::now(), tx, verbose));
+
+    let mut flush_tick = tokio::time::interval(Duration::from_secs(args.flush_secs.max(1)));
+    let mut status_tick = tokio::time::interval(Duration::from_secs(args.status_secs.max(1)));
+    flush_tick.reset();
+    status_tick.reset();
+    // Created once, so a signal between iterations is not lost and no
+    // signal streams are registered per message.
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => {
+                eprintln!("record: shutdown signal received, flushing…");
+                break;
+            }
+            maybe = rx.recv() => {
+                let Some(observation) = maybe else {
+                    eprintln!("record: producer ended, stopping.");
+                    break;
+                };
+                if let Some(line) = recorder.observe(observation)? {
+                    writer.write(&line)?;
+                }
+            }
+            _ = flush_tick.tick() => {
+                writer.flush()?;
+            }
+            _ = status_tick.tick() => {
+                emit_status(&recorder);
+            }
+        }
+    }
+
+    producer.abort();
+    while let Ok(observation) = rx.try_recv() {
+        if let Some(line) = recorder.observe(observation)? {
+            writer.write(&line)?;
+        }
+    }
+    writer.close()?;
+    emit_status(&recorder);
+    Ok(())
+}
+
+fn emit_status(recorder: &Recorder) {
+    let counts = recorder
+        .counts
+        .iter()
+        .map(|(channel, count)| format!("{}={count}", channel.topic()))
+        .collect::<Vec<_>>()
+        .join(" ");
+    eprintln!(
+        "record: lines={} {} gaps={} connection={}",
+        recorder.next_n,
+        if counts.is_empty() { "-" } else { &counts },
+        recorder.gaps,
+        recorder.connection
+    );
+}
+
+/// Producer: one public socket carrying every symbol × channel; rebuilt when
+/// the managed connection gives up.
+async fn run_producer(
+    session: SessionInfo,
+    origin: Instant,
+    tx: mpsc::Sender<Observation>,
+    verbose: bool,
+) {
+    let stamp = || Stamp {
+        at: Utc::now(),
+        mono_ms: origin.elapsed().as_millis() as i64,
+    };
+    loop {
+        if let Err(error) = producer_session(&session, &stamp, &tx, verbose).await {
+            eprintln!("record: feed error: {error:#}");
+        }
+        if tx.send(Observation::SessionLost(stamp())).await.is_err() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn producer_session(
+    session: &SessionInfo,
+    stamp: &impl Fn() -> Stamp,
+    tx: &mpsc::Sender<Observation>,
+    verbose: bool,
+) -> Result<()> {
+    let ws = StandXWebSocket::without_auth_with_verbose(verbose)
+        .context("failed to build StandX websocket client")?;
+    for symbol in &session.symbols {
+        for channel in &session.channels {
+            match channel {
+                RecordChannel::Kline => {
+                    ws.subscribe_with_interval(
+                        channel.topic(),
+                        Some(symbol),
+                        session.kline_interval.as_deref(),
+                    )
+                    .await?
+                }
+                _ => ws.subscribe(channel.topic(), Some(symbol)).await?,
+            }
+        }
+    }
+    let (mut rx, _handle) = ws
+        .connect_managed()
+        .await
+        .context("failed to connect StandX websocket")?;
+    while let Some(message) = rx.recv().await {
+        let observed = Observation::Message(Box::new(message), stamp());
+        if tx.send(observed).await.is_err() {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use standx_sdk::models::Trade;
+    use std::io::Read;
+
+    fn stamp(secs: i64) -> Stamp {
+        Stamp {
+            at: DateTime::from_timestamp(secs, 0).unwrap(),
+            mono_ms: secs * 1_000,
+        }
+    }
+
+    fn trade(id: u64) -> WsMessage {
+        WsMessage::Trade(Trade {
+            id,
+            time: "2026-07-19T00:00:00Z".to_string(),
+            price: "100".to_string(),
+            qty: "1".to_string(),
+            side: Some("buy".to_string()),
+            is_buyer_taker: true,
+            fee_asset: None,
+            fee_qty: None,
+            pnl: None,
+            order_id: None,
+            symbol: None,
+            value: None,
+        })
+    }
+
+    fn observe(recorder: &mut Recorder, message: WsMessage, secs: i64) -> Option<RecordLine> {
+        recorder
+            .observe(Observation::Message(Box::new(message), stamp(secs)))
+            .unwrap()
+    }
+
+    #[test]
+    fn reconnect_writes_a_gap_from_the_last_data() {
+        let mut recorder = Recorder::new(&["BTC-USD".to_string()], &[RecordChannel::Trade]);
+        assert!(observe(&mut recorder, WsMessage::Connected, 0).is_none());
+        let Some(RecordLine::Data {
+            n,
+            symbol,
+            connection,
+            ..
+        }) = observe(&mut recorder, trade(1), 5)
+        else {
+            panic!("expected a data line");
+        };
+        assert_eq!((n, symbol.as_deref(), connection), (0, Some("BTC-USD"), 1));
+
+        assert!(observe(&mut recorder, WsMessage::Disconnected, 9).is_none());
+        let gap = observe(&mut recorder, WsMessage::Connected, 20).unwrap();
+
+        assert_eq!(
+            gap,
+            RecordLine::Gap {
+                n: 1,
+                reason: "reconnect",
+                from: "1970-01-01T00:00:05.000Z".to_string(),
+                to: "1970-01-01T00:00:20.000Z".to_string(),
+                connection: 2,
+            }
+        );
+        assert_eq!(recorder.gaps, 1);
+    }
+
+    #[test]
+    fn unselected_channels_are_not_recorded() {
+        let mut recorder = Recorder::new(
+            &["BTC-USD".to_string(), "ETH-USD".to_string()],
+            &[RecordChannel::Price],
+        );
+        observe(&mut recorder, WsMessage::Connected, 0);
+        assert!(observe(&mut recorder, trade(1), 1).is_none());
+        assert_eq!(recorder.next_n, 0);
+    }
+
+    #[test]
+    fn data_line_carries_envelope_metadata() {
+        let mut recorder = Recorder::new(
+            &["BTC-USD".to_string(), "ETH-USD".to_string()],
+            &[RecordChannel::Trade],
+        );
+        observe(&mut recorder, WsMessage::Connected, 0);
+        let line = observe(&mut recorder, trade(7), 3).unwrap();
+        let value = serde_json::to_value(&line).unwrap();
+
+        assert_eq!(value["type"], "data");
+        assert_eq!(value["channel"], "trade");
+        assert!(value["symbol"].is_null());
+        assert!(value["seq"].is_null());
+        assert_eq!(value["server_time"], "2026-07-19T00:00:00Z");
+        assert_eq!(value["received_at"], "1970-01-01T00:00:03.000Z");
+        assert_eq!(value["recv_mono_ms"], 3_000);
+        assert_eq!(value["data"]["id"], 7);
+    }
+
+    fn session() -> SessionInfo {
+        SessionInfo {
+            symbols: vec!["BTC-USD".to_string()],
+            channels: vec![RecordChannel::Trade],
+            kline_interval: None,
+        }
+    }
+
+    #[test]
+    fn writer_rotates_by_size_with_a_header_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = RotatingWriter::new(
+            dir.path().to_path_buf(),
+            "test".to_string(),
+            false,
+            Some(1),
+            None,
+            session(),
+        )
+        .unwrap();
+        let mut recorder = Recorder::new(&["BTC-USD".to_string()], &[RecordChannel::Trade]);
+        observe(&mut recorder, WsMessage::Connected, 0);
+        for id in 0..2 {
+            let line = observe(&mut recorder, trade(id), 1).unwrap();
+            writer.write(&line).unwrap();
+        }
+        writer.close().unwrap();
+
+        let mut files: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        let first = std::fs::read_to_string(&files[0]).unwrap();
+        let lines: Vec<serde_json::Value> = first
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["type"], "header");
+        assert_eq!(lines[0]["file_index"], 0);
+        assert_eq!(lines[1]["n"], 0);
+        assert!(files[1].to_string_lossy().ends_with("-0001.ndjson"));
+    }
+
+    #[test]
+    fn compressed_output_is_valid_gzip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut writer = RotatingWriter::new(
+            dir.path().to_path_buf(),
+            "test".to_string(),
+            true,
+            None,
+            None,
+            session(),
+        )
+        .unwrap();
+        let mut recorder = Recorder::new(&["BTC-USD".to_string()], &[RecordChannel::Trade]);
+        observe(&mut recorder, WsMessage::Connected, 0);
+        let line = observe(&mut recorder, trade(1), 1).unwrap();
+        writer.write(&line).unwrap();
+        writer.close().unwrap();
+
+        let path = std::fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap()
+            .path();
+        assert!(path.to_string_lossy().ends_with(".ndjson.gz"));
+        let mut text = String::new();
+        flate2::read::GzDecoder::new(File::open(path).unwrap())
+            .read_to_string(&mut text)
+            .unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().nth(1).unwrap().contains("\"type\":\"data\""));
+    }
+}
@@ -0,0 +1,322 @@
+//! Account-history fetching shared by the reports.
+//!
+//! `/api/query_trades` only takes a time window and a row limit, so a long
+//! report range is walked by bisecting any window whose page came back full
+//! until every sub-window fits in one page. That works whichever order the
+//! venue returns rows in, and the trade ID dedupes the window edges.
+
+use super::inventory::QTY_EPSILON;
+use anyhow::{bail, Result};
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{OrderSide, Position, Trade};
+use std::collections::HashSet;
+
+/// Rows requested per trade-history page.
+const TRADE_PAGE_LIMIT: u32 = 500;
+
+/// How close to "now" an end time must be to use the live mark instead of a
+/// historical candle close.
+const LIVE_PRICE_WINDOW_SECS: i64 = 60;
+
+/// Candle lookback used to find the last traded close at or before a time.
+const PRICE_LOOKBACK_SECS: i64 = 3_600;
+
+/// One user fill parsed into numbers, in the signed-quantity convention of
+/// [`super::inventory::InventoryBook`].
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct HistoryFill {
+    pub trade_id: u64,
+    pub order_id: Option<u64>,
+    pub time_ms: i64,
+    pub side: OrderSide,
+    pub price: f64,
+    pub qty: f64,
+    /// Fee paid in quote units (negative for a rebate).
+    pub fee: f64,
+}
+
+impl HistoryFill {
+    pub(super) fn signed_qty(&self) -> f64 {
+        match self.side {
+            OrderSide::Buy => self.qty,
+            OrderSide::Sell => -self.qty,
+        }
+    }
+}
+
+/// Fetch every user trade on `symbol` in `[from, to]` (Unix seconds).
+async fn fetch_all_trades(
+    client: &StandXClient,
+    symbol: &str,
+    from: i64,
+    to: i64,
+) -> Result<Vec<Trade>> {
+    let mut windows = vec![(from, to)];
+    let mut seen = HashSet::new();
+    let mut trades = Vec::new();
+    while let Some((start, end)) = windows.pop() {
+        let page = client
+            .get_user_trades(symbol, start, end, Some(TRADE_PAGE_LIMIT))
+            .await?;
+        if page.len() >= TRADE_PAGE_LIMIT as usize {
+            if end <= start {
+                bail!(
+                    "more than {TRADE_PAGE_LIMIT} trades on {symbol} share timestamp {start}; \
+                     cannot page the trade history any further"
+                );
+            }
+            let mid = start + (end - start) / 2;
+            windows.push((mid + 1, end));
+            windows.push((start, mid));
+            continue;
+        }
+        for trade in page {
+            if trade.id == 0 || seen.insert(trade.id) {
+                trades.push(trade);
+            }
+        }
+    }
+    Ok(trades)
+}
+
+/// One symbol's fills over a report period plus the inventory around them.
+#[derive(Debug, Clone)]
+pub(super) struct PeriodFills {
+    pub symbol: String,
+    pub from_ms: i64,
+    /// Signed position held at the start of the period.
+    pub opening_position: f64,
+    /// Price the opening position is adopted at (its cost basis).
+    pub opening_price: f64,
+    /// Fills inside the period, sorted by time.
+    pub fills: Vec<HistoryFill>,
+}
+
+impl PeriodFills {
+    /// Signed position at the end of the period.
+    pub(super) fn closing_position(&self) -> f64 {
+        self.opening_position + self.fills.iter().map(HistoryFill::signed_qty).sum::<f64>()
+    }
+}
+
+/// Load one symbol's fills over `[from, to]`, or `None` when the symbol was
+/// neither traded nor held during the period.
+///
+/// The position held at `from` is not queryable directly, so it is rebuilt by
+/// unwinding every fill since `from` off the current position, and adopted at
+/// the price at `from` — the same way the maker adopts pre-existing inventory
+/// at the startup mark.
+pub(super) async fn load_period_fills(
+    client: &StandXClient,
+    positions: &[Position],
+    symbol: &str,
+    from: i64,
+    to: i64,
+) -> Result<Option<PeriodFills>> {
+    let now = chrono::Utc::now().timestamp();
+    let trades = fetch_all_trades(client, symbol, from, now).await?;
+    let fills = parse_fills(symbol, &trades)?;
+    let current = signed_position(positions, symbol)?;
+    let opening_position = current - fills.iter().map(HistoryFill::signed_qty).sum::<f64>();
+    let fills: Vec<_> = fills
+        .into_iter()
+        .filter(|fill| fill.time_ms <= to.saturating_mul(1_000))
+        .collect();
+
+    let held_at_open = opening_position.abs() > QTY_EPSILON;
+    if fills.is_empty() && !held_at_open {
+        return Ok(None);
+    }
+    let opening_price = if held_at_open {
+        price_at(client, symbol, from).await?
+    } else {
+        0.0
+    };
+    Ok(Some(PeriodFills {
+        symbol: symbol.to_string(),
+        from_ms: from.saturating_mul(1_000),
+        opening_position,
+        opening_price,
+        fills,
+    }))
+}
+
+/// Parse raw trades into fills sorted by time (then trade ID).
+pub(super) fn parse_fills(symbol: &str, trades: &[Trade]) -> Result<Vec<HistoryFill>> {
+    let mut fills = trades
+        .iter()
+        .map(|trade| parse_fill(symbol, trade))
+        .collect::<Result<Vec<_>>>()?;
+    fills.sort_by(|a, b| {
+        a.time_ms
+            .cmp(&b.time_ms)
+            .then_with(|| a.trade_id.cmp(&b.trade_id))
+    });
+    Ok(fills)
+}
+
+fn parse_fill(symbol: &str, trade: &Trade) -> Result<HistoryFill> {
+    let side = match trade.side.as_deref() {
+        Some(side) if side.eq_ignore_ascii_case("buy") => OrderSide::Buy,
+        Some(side) if side.eq_ignore_ascii_case("sell") => OrderSide::Sell,
+        _ => bail!("trade {} is missing a valid side", trade.id),
+    };
+    let price = parse_positive(trade.id, "price", &trade.price)?;
+    let qty = parse_positive(trade.id, "qty", &trade.qty)?;
+    let time_ms = parse_time_ms(&trade.time)
+        .ok_or_else(|| anyhow::anyhow!("trade {} has invalid time '{}'", trade.id, trade.time))?;
+    Ok(HistoryFill {
+        trade_id: trade.id,
+        order_id: trade.order_id,
+        time_ms,
+        side,
+        price,
+        qty,
+        fee: fee_in_quote(symbol, trade, price)?,
+    })
+}
+
+fn parse_positive(trade_id: u64, field: &str, raw: &str) -> Result<f64> {
+    match raw.parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => Ok(value),
+        _ => bail!("trade {trade_id} has invalid {field} '{raw}'"),
+    }
+}
+
+/// Trade fees are normally charged in the quote (or its D-prefixed
+/// settlement token); a fee charged in the base asset is converted at the
+/// fill price.
+fn fee_in_quote(symbol: &str, trade: &Trade, price: f64) -> Result<f64> {
+    let Some(raw) = trade.fee_qty.as_deref() else {
+        return Ok(0.0);
+    };
+    let fee = raw
+        .parse::<f64>()
+        .ok()
+        .filter(|fee| fee.is_finite())
+        .ok_or_else(|| anyhow::anyhow!("trade {} has invalid fee qty '{raw}'", trade.id))?;
+    let base = symbol.split_once('-').map(|(base, _)| base);
+    Ok(match (trade.fee_asset.as_deref(), base) {
+        (Some(asset), Some(base)) if asset.eq_ignore_ascii_case(base) => fee * price,
+        _ => fee,
+    })
+}
+
+/// Parse an RFC3339 or Unix (seconds or milliseconds) timestamp into Unix
+/// milliseconds.
+pub(super) fn parse_time_ms(value: &str) -> Option<i64> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.timestamp_millis());
+    }
+    let raw = value.parse::<i64>().ok()?;
+    Some(if raw.abs() < 1_000_000_000_000 {
+        raw.saturating_mul(1_000)
+    } else {
+        raw
+    })
+}
+
+/// Signed current position on `symbol` (0 when flat).
+fn signed_position(positions: &[Position], symbol: &str) -> Result<f64> {
+    positions
+        .iter()
+        .filter(|position| position.symbol.eq_ignore_ascii_case(symbol))
+        .try_fold(0.0, |total, position| {
+            let qty = position
+                .qty
+                .parse::<f64>()
+                .ok()
+                .filter(|qty| qty.is_finite())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("position on {symbol} has invalid qty '{}'", position.qty)
+                })?;
+            Ok(total
+                + match position.side {
+                    Some(OrderSide::Sell) => -qty.abs(),
+                    Some(OrderSide::Buy) => qty.abs(),
+                    None => qty,
+                })
+        })
+}
+
+/// Price of `symbol` at `at` (Unix seconds): the live mark when `at` is
+/// effectively now, otherwise the last one-minute candle close at or before
+/// `at`.
+pub(super) async fn price_at(client: &StandXClient, symbol: &str, at: i64) -> Result<f64> {
+    let now = chrono::Utc::now().timestamp();
+    if now - at <= LIVE_PRICE_WINDOW_SECS {
+        let price = client.get_symbol_price(symbol).await?;
+        return price
+            .mark_price
+            .parse::<f64>()
+            .ok()
+            .filter(|price| price.is_finite() && *price > 0.0)
+            .ok_or_else(|| {
+                anyhow::anyhow!("{symbol} has invalid mark price '{}'", price.mark_price)
+            });
+    }
+    let klines = client
+        .get_kline(symbol, "1", at - PRICE_LOOKBACK_SECS, at)
+        .await?;
+    klines
+        .iter()
+        .filter(|kline| kline.time.parse::<i64>().is_ok_and(|time| time <= at))
+        .filter_map(|kline| kline.close.parse::<f64>().ok())
+        .rfind(|close| close.is_finite() && *close > 0.0)
+        .ok_or_else(|| {
+            anyhow::anyhow!("no {symbol} candle within an hour before {at} to price inventory")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(id: u64, time: &str, side: &str, fee_asset: &str, fee: &str) -> Trade {
+        Trade {
+            id,
+            time: time.to_string(),
+            price: "100".to_string(),
+            qty: "2".to_string(),
+            side: Some(side.to_string()),
+            is_buyer_taker: false,
+            fee_asset: Some(fee_asset.to_string()),
+            fee_qty: Some(fee.to_string()),
+            pnl: None,
+            order_id: Some(id * 10),
+            symbol: Some("BTC-USD".to_string()),
+            value: None,
+        }
+    }
+
+    #[test]
+    fn fills_are_sorted_and_fees_converted_to_quote() {
+        let trades = vec![
+            trade(2, "2026-07-10T15:00:01Z", "sell", "DUSD", "-0.01"),
+            trade(1, "2026-07-10T15:00:00Z", "buy", "BTC", "0.001"),
+        ];
+
+        let fills = parse_fills("BTC-USD", &trades).unwrap();
+
+        assert_eq!(fills[0].trade_id, 1);
+        assert_eq!(fills[0].signed_qty(), 2.0);
+        assert!((fills[0].fee - 0.1).abs() < 1e-12);
+        assert_eq!(fills[1].signed_qty(), -2.0);
+        assert!((fills[1].fee + 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fill_without_side_is_rejected() {
+        let mut bad = trade(3, "2026-07-10T15:00:00Z", "buy", "DUSD", "0");
+        bad.side = None;
+        assert!(parse_fills("BTC-USD", &[bad]).is_err());
+    }
+
+    #[test]
+    fn time_parser_accepts_rfc3339_seconds_and_millis() {
+        assert_eq!(parse_time_ms("1970-01-01T00:00:01Z"), Some(1_000));
+        assert_eq!(parse_time_ms("1783696499"), Some(1_783_696_499_000));
+        assert_eq!(parse_time_ms("1783696499123"), Some(1_783_696_499_123));
+        assert_eq!(parse_time_ms("yesterday"), None);
+    }
+}
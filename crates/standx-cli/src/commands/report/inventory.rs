@@ -0,0 +1,285 @@
+//! Cost-basis inventory shared by the account reports.
+//!
+//! Positions are signed (long > 0, short < 0). A fill on the same side as the
+//! open inventory opens a lot; a fill on the opposite side closes lots in the
+//! order the cost-basis method picks, and any remainder flips the position
+//! into a fresh lot at the fill price. Fees ride along with the quantity they
+//! were paid on, so a partially closed lot keeps the unallocated share of its
+//! opening fee.
+
+use super::history::HistoryFill;
+use crate::cli::CostBasisMethod;
+
+/// Quantities below this are treated as fully closed (float dust).
+pub(super) const QTY_EPSILON: f64 = 1e-12;
+
+/// One open lot: signed quantity at a per-unit entry price.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct OpenLot {
+    pub qty: f64,
+    pub price: f64,
+    pub opened_ms: i64,
+    /// Opening trade, or `None` for inventory adopted at the period start.
+    pub open_trade_id: Option<u64>,
+    /// Opening fee still attributable to the remaining quantity.
+    pub fee: f64,
+}
+
+/// The closed part of a lot, matched against one closing fill.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ClosedLot {
+    /// Signed closed quantity (long > 0, short < 0).
+    pub qty: f64,
+    pub open_price: f64,
+    pub close_price: f64,
+    pub opened_ms: i64,
+    pub closed_ms: i64,
+    pub open_trade_id: Option<u64>,
+    pub close_trade_id: u64,
+    pub open_fee: f64,
+    pub close_fee: f64,
+}
+
+impl ClosedLot {
+    /// Gross PnL before fees.
+    pub(super) fn realized(&self) -> f64 {
+        self.qty * (self.close_price - self.open_price)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct InventoryBook {
+    method: CostBasisMethod,
+    lots: Vec<OpenLot>,
+}
+
+impl InventoryBook {
+    pub(super) fn new(method: CostBasisMethod) -> Self {
+        Self {
+            method,
+            lots: Vec::new(),
+        }
+    }
+
+    /// Signed open quantity.
+    pub(super) fn position(&self) -> f64 {
+        self.lots.iter().map(|lot| lot.qty).sum()
+    }
+
+    /// Open lots in opening order.
+    #[cfg(test)]
+    pub(super) fn open_lots(&self) -> &[OpenLot] {
+        &self.lots
+    }
+
+    /// Mark-to-market PnL of the open inventory at `price`.
+    pub(super) fn unrealized(&self, price: f64) -> f64 {
+        self.lots
+            .iter()
+            .map(|lot| lot.qty * (price - lot.price))
+            .sum()
+    }
+
+    /// Seed inventory that predates the fill history (no trade, no fee).
+    pub(super) fn adopt(&mut self, signed_qty: f64, price: f64, opened_ms: i64) {
+        self.open(OpenLot {
+            qty: signed_qty,
+            price,
+            opened_ms,
+            open_trade_id: None,
+            fee: 0.0,
+        });
+    }
+
+    /// Apply a fill and return the lot slices it closed.
+    pub(super) fn apply(&mut self, fill: &HistoryFill) -> Vec<ClosedLot> {
+        let signed_qty = fill.signed_qty();
+        let position = self.position();
+        if position.abs() < QTY_EPSILON || position.signum() == signed_qty.signum() {
+            self.open(OpenLot {
+                qty: signed_qty,
+                price: fill.price,
+                opened_ms: fill.time_ms,
+                open_trade_id: Some(fill.trade_id),
+                fee: fill.fee,
+            });
+            return Vec::new();
+        }
+
+        let mut remaining = fill.qty;
+        let mut closed_lots = Vec::new();
+        while remaining > QTY_EPSILON {
+            let Some(index) = self.next_to_close() else {
+                break;
+            };
+            let lot = &mut self.lots[index];
+            let closed = remaining.min(lot.qty.abs());
+            let share = closed / lot.qty.abs();
+            let open_fee = lot.fee * share;
+            closed_lots.push(ClosedLot {
+                qty: closed * lot.qty.signum(),
+                open_price: lot.price,
+                close_price: fill.price,
+                opened_ms: lot.opened_ms,
+                closed_ms: fill.time_ms,
+                open_trade_id: lot.open_trade_id,
+                close_trade_id: fill.trade_id,
+                open_fee,
+                close_fee: fill.fee * closed / fill.qty,
+            });
+            lot.fee -= open_fee;
+            lot.qty -= closed * lot.qty.signum();
+            remaining -= closed;
+            if lot.qty.abs() < QTY_EPSILON {
+                self.lots.remove(index);
+            }
+        }
+        if remaining > QTY_EPSILON {
+            self.open(OpenLot {
+                qty: remaining * signed_qty.signum(),
+                price: fill.price,
+                opened_ms: fill.time_ms,
+                open_trade_id: Some(fill.trade_id),
+                fee: fill.fee * remaining / fill.qty,
+            });
+        }
+        closed_lots
+    }
+
+    /// Index of the lot the method closes next.
+    ///
+    /// HIFO closes the lot that realizes the smallest gain first: the highest
+    /// entry for a long, the lowest entry for a short.
+    fn next_to_close(&self) -> Option<usize> {
+        if self.lots.is_empty() {
+            return None;
+        }
+        Some(match self.method {
+            CostBasisMethod::Fifo | CostBasisMethod::Average => 0,
+            CostBasisMethod::Lifo => self.lots.len() - 1,
+            CostBasisMethod::Hifo => {
+                let long = self.position() > 0.0;
+                let mut best = 0;
+                for (index, lot) in self.lots.iter().enumerate().skip(1) {
+                    let current = self.lots[best].price;
+                    if (long && lot.price > current) || (!long && lot.price < current) {
+                        best = index;
+                    }
+                }
+                best
+            }
+        })
+    }
+
+    fn open(&mut self, lot: OpenLot) {
+        if self.method != CostBasisMethod::Average || self.lots.is_empty() {
+            self.lots.push(lot);
+            return;
+        }
+        let existing = &mut self.lots[0];
+        let qty = existing.qty + lot.qty;
+        existing.price = (existing.qty * existing.price + lot.qty * lot.price) / qty;
+        existing.qty = qty;
+        existing.fee += lot.fee;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use standx_sdk::models::OrderSide;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    fn fill(trade_id: u64, side: OrderSide, qty: f64, price: f64) -> HistoryFill {
+        HistoryFill {
+            trade_id,
+            order_id: None,
+            time_ms: trade_id as i64 * 1_000,
+            side,
+            price,
+            qty,
+            fee: 0.0,
+        }
+    }
+
+    fn realized(closed: &[ClosedLot]) -> f64 {
+        closed.iter().map(ClosedLot::realized).sum()
+    }
+
+    fn long_book(method: CostBasisMethod) -> InventoryBook {
+        let mut book = InventoryBook::new(method);
+        book.apply(&fill(1, OrderSide::Buy, 1.0, 100.0));
+        book.apply(&fill(2, OrderSide::Buy, 1.0, 120.0));
+        book.apply(&fill(3, OrderSide::Buy, 1.0, 110.0));
+        book
+    }
+
+    #[test]
+    fn methods_pick_different_lots_to_close() {
+        for (method, expected) in [
+            (CostBasisMethod::Fifo, 30.0),
+            (CostBasisMethod::Lifo, 20.0),
+            (CostBasisMethod::Hifo, 10.0),
+            (CostBasisMethod::Average, 20.0),
+        ] {
+            let mut book = long_book(method);
+            let closed = book.apply(&fill(4, OrderSide::Sell, 1.0, 130.0));
+            assert_close(realized(&closed), expected);
+            assert_close(book.position(), 2.0);
+        }
+    }
+
+    #[test]
+    fn hifo_on_a_short_closes_the_lowest_entry_first() {
+        let mut book = InventoryBook::new(CostBasisMethod::Hifo);
+        book.apply(&fill(1, OrderSide::Sell, 1.0, 100.0));
+        book.apply(&fill(2, OrderSide::Sell, 1.0, 90.0));
+
+        let closed = book.apply(&fill(3, OrderSide::Buy, 1.0, 80.0));
+
+        assert_eq!(closed[0].open_trade_id, Some(2));
+        assert_close(realized(&closed), 10.0);
+    }
+
+    #[test]
+    fn partial_close_splits_the_lot_and_its_fee() {
+        let mut book = InventoryBook::new(CostBasisMethod::Fifo);
+        let mut open = fill(1, OrderSide::Buy, 2.0, 100.0);
+        open.fee = 0.4;
+        book.apply(&open);
+        let mut close = fill(2, OrderSide::Sell, 0.5, 110.0);
+        close.fee = 0.1;
+
+        let closed = book.apply(&close);
+
+        assert_eq!(closed.len(), 1);
+        assert_close(closed[0].qty, 0.5);
+        assert_close(closed[0].open_fee, 0.1);
+        assert_close(closed[0].close_fee, 0.1);
+        assert_close(book.open_lots()[0].qty, 1.5);
+        assert_close(book.open_lots()[0].fee, 0.3);
+    }
+
+    #[test]
+    fn fill_through_zero_flips_into_a_new_lot() {
+        let mut book = InventoryBook::new(CostBasisMethod::Fifo);
+        book.adopt(-2.0, 50.0, 0);
+        let mut flip = fill(1, OrderSide::Buy, 3.0, 40.0);
+        flip.fee = 0.3;
+
+        let closed = book.apply(&flip);
+
+        assert_close(realized(&closed), 20.0);
+        assert_eq!(closed[0].open_trade_id, None);
+        assert_close(closed[0].close_fee, 0.2);
+        assert_close(book.position(), 1.0);
+        assert_close(book.open_lots()[0].fee, 0.1);
+        assert_close(book.unrealized(45.0), 5.0);
+    }
+}
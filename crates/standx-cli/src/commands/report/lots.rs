@@ -0,0 +1,339 @@
+//! Tax-lot export: every closed slice of a lot with its open/close time,
+//! cost basis, proceeds, fees and holding period.
+//!
+//! The CSV column order is the field order of [`LotRow`] and is a stable
+//! interface for the accounting import; append new columns at the end.
+
+use super::history::PeriodFills;
+use super::inventory::{ClosedLot, InventoryBook, QTY_EPSILON};
+use crate::cli::{LotMethod, OutputFormat};
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Holding periods longer than this count as long-term.
+const LONG_TERM_SECS: i64 = 365 * 86_400;
+
+/// One closed lot slice.
+///
+/// Inventory held before the period has no opening fill in the fetched
+/// history, so its slices are flagged `adopted` and leave the open time,
+/// holding period, open price, the open leg's notional and the gain empty,
+/// with term `unknown`, rather than inventing a basis.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(super) struct LotRow {
+    pub symbol: String,
+    /// `long` or `short`.
+    pub direction: &'static str,
+    pub qty: f64,
+    /// RFC3339 (UTC); empty for adopted inventory.
+    pub opened_at: Option<String>,
+    pub closed_at: String,
+    pub holding_secs: Option<i64>,
+    /// `short`, `long` or (adopted inventory) `unknown` term.
+    pub term: &'static str,
+    pub open_price: Option<f64>,
+    pub close_price: f64,
+    /// What the position cost: the buy leg's notional.
+    pub cost_basis: Option<f64>,
+    /// What the position realized: the sell leg's notional.
+    pub proceeds: Option<f64>,
+    pub open_fee: f64,
+    pub close_fee: f64,
+    /// `proceeds - cost_basis - open_fee - close_fee`.
+    pub gain: Option<f64>,
+    /// Empty for adopted inventory.
+    pub open_trade_id: Option<u64>,
+    pub close_trade_id: u64,
+    /// Opened before the period: basis and term are unknown.
+    pub adopted: bool,
+}
+
+impl LotRow {
+    /// The CSV header, in field order; an empty export still prints it.
+    const COLUMNS: &'static [&'static str] = &[
+        "symbol",
+        "direction",
+        "qty",
+        "opened_at",
+        "closed_at",
+        "holding_secs",
+        "term",
+        "open_price",
+        "close_price",
+        "cost_basis",
+        "proceeds",
+        "open_fee",
+        "close_fee",
+        "gain",
+        "open_trade_id",
+        "close_trade_id",
+        "adopted",
+    ];
+
+    fn new(symbol: &str, lot: &ClosedLot) -> Self {
+        let qty = lot.qty.abs();
+        let adopted = lot.open_trade_id.is_none();
+        let open_leg = (!adopted).then_some(qty * lot.open_price);
+        let close_leg = Some(qty * lot.close_price);
+        let (direction, cost_basis, proceeds) = if lot.qty > 0.0 {
+            ("long", open_leg, close_leg)
+        } else {
+            ("short", close_leg, open_leg)
+        };
+        let holding_secs = (!adopted).then_some((lot.closed_ms - lot.opened_ms) / 1_000);
+        Self {
+            symbol: symbol.to_string(),
+            direction,
+            qty,
+            opened_at: (!adopted).then(|| format_time(lot.opened_ms)),
+            closed_at: format_time(lot.closed_ms),
+            holding_secs,
+            term: match holding_secs {
+                None => "unknown",
+                Some(secs) if secs > LONG_TERM_SECS => "long",
+                Some(_) => "short",
+            },
+            open_price: (!adopted).then_some(lot.open_price),
+            close_price: lot.close_price,
+            cost_basis,
+            proceeds,
+            open_fee: lot.open_fee,
+            close_fee: lot.close_fee,
+            gain: cost_basis
+                .zip(proceeds)
+                .map(|(cost_basis, proceeds)| proceeds - cost_basis - lot.open_fee - lot.close_fee),
+            open_trade_id: lot.open_trade_id,
+            close_trade_id: lot.close_trade_id,
+            adopted,
+        }
+    }
+}
+
+/// Match one symbol's period fills into closed lots.
+pub(super) fn symbol_lots(period: &PeriodFills, method: LotMethod) -> Vec<LotRow> {
+    let mut book = InventoryBook::new(method.into());
+    if period.opening_position.abs() > QTY_EPSILON {
+        book.adopt(
+            period.opening_position,
+            period.opening_price,
+            period.from_ms,
+        );
+    }
+    period
+        .fills
+        .iter()
+        .flat_map(|fill| book.apply(fill))
+        .map(|lot| LotRow::new(&period.symbol, &lot))
+        .collect()
+}
+
+fn format_time(time_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(time_ms)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_else(|| time_ms.to_string())
+}
+
+pub(super) fn render(lots: &[LotRow], output_format: OutputFormat) -> Result<String> {
+    Ok(match output_format {
+        OutputFormat::Table => format_lots_table(lots),
+        OutputFormat::Json => format!("{}\n", output::format_json(&lots)?),
+        OutputFormat::Csv => output::format_csv_with_header(lots, LotRow::COLUMNS)?,
+        OutputFormat::Quiet => String::new(),
+    })
+}
+
+fn format_lots_table(lots: &[LotRow]) -> String {
+    if lots.is_empty() {
+        return "ℹ️  No closed lots in the specified time range\n".to_string();
+    }
+    let header = format!(
+        "{:<12} {:<5} {:>10} {:<20} {:<20} {:<7} {:>14} {:>14} {:>10} {:>12}",
+        "SYMBOL",
+        "SIDE",
+        "QTY",
+        "OPENED",
+        "CLOSED",
+        "TERM",
+        "COST BASIS",
+        "PROCEEDS",
+        "FEES",
+        "GAIN"
+    );
+    let separator = "-".repeat(header.len());
+    let amount = |value: Option<f64>, width: usize| match value {
+        Some(value) => format!("{value:>width$.4}"),
+        None => format!("{:>width$}", "-"),
+    };
+    let mut text = format!("{header}\n{separator}\n");
+    for lot in lots {
+        text.push_str(&format!(
+            "{:<12} {:<5} {:>10} {:<20} {:<20} {:<7} {} {} {:>10.4} {}\n",
+            lot.symbol,
+            lot.direction,
+            lot.qty,
+            lot.opened_at.as_deref().unwrap_or("adopted"),
+            lot.closed_at,
+            lot.term,
+            amount(lot.cost_basis, 14),
+            amount(lot.proceeds, 14),
+            lot.open_fee + lot.close_fee,
+            amount(lot.gain, 12)
+        ));
+    }
+    let gain = lots
+        .iter()
+        .filter_map(|lot| lot.gain)
+        .fold(0.0, |sum, gain| sum + gain);
+    text.push_str(&format!(
+        "{separator}\n{} closed lot(s), net gain {gain:.4}\n",
+        lots.len()
+    ));
+    let adopted = lots.iter().filter(|lot| lot.adopted).count();
+    if adopted > 0 {
+        text.push_str(&format!(
+            "⚠️  {adopted} lot(s) opened before the period: basis and term unknown, gain not totalled\n"
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::report::history::HistoryFill;
+    use standx_sdk::models::OrderSide;
+
+    fn fill(trade_id: u64, time_ms: i64, side: OrderSide, qty: f64, price: f64) -> HistoryFill {
+        HistoryFill {
+            trade_id,
+            order_id: None,
+            time_ms,
+            side,
+            price,
+            qty,
+            fee: 0.0,
+        }
+    }
+
+    #[test]
+    fn short_lot_books_proceeds_at_the_open() {
+        let period = PeriodFills {
+            symbol: "BTC-USD".to_string(),
+            from_ms: 0,
+            opening_position: 0.0,
+            opening_price: 0.0,
+            fills: vec![
+                fill(1, 1_000, OrderSide::Sell, 2.0, 100.0),
+                fill(2, 5_000, OrderSide::Buy, 3.0, 90.0),
+                fill(3, 9_000, OrderSide::Sell, 1.0, 95.0),
+            ],
+        };
+
+        let lots = symbol_lots(&period, LotMethod::Fifo);
+
+        assert_eq!(lots.len(), 2);
+        assert_eq!(lots[0].direction, "short");
+        assert_eq!(lots[0].proceeds, Some(200.0));
+        assert_eq!(lots[0].cost_basis, Some(180.0));
+        assert_eq!(lots[0].holding_secs, Some(4));
+        // The flip opened a 1.0 long at 90, closed at 95.
+        assert_eq!(lots[1].direction, "long");
+        assert_eq!(lots[1].open_trade_id, Some(2));
+        assert_eq!(lots[1].gain, Some(5.0));
+    }
+
+    #[test]
+    fn adopted_inventory_has_unknown_basis_and_term() {
+        let period = PeriodFills {
+            symbol: "BTC-USD".to_string(),
+            from_ms: 0,
+            opening_position: 1.0,
+            opening_price: 50.0,
+            fills: vec![fill(
+                7,
+                LONG_TERM_SECS * 1_000 + 1_000,
+                OrderSide::Sell,
+                1.0,
+                60.0,
+            )],
+        };
+
+        let lots = symbol_lots(&period, LotMethod::Lifo);
+
+        assert!(lots[0].adopted);
+        assert_eq!(lots[0].opened_at, None);
+        assert_eq!(lots[0].holding_secs, None);
+        assert_eq!(lots[0].open_trade_id, None);
+        assert_eq!(lots[0].term, "unknown");
+        assert_eq!(lots[0].cost_basis, None);
+        assert_eq!(lots[0].proceeds, Some(60.0));
+        assert_eq!(lots[0].gain, None);
+        let table = render(&lots, OutputFormat::Table).unwrap();
+        assert!(table.contains("net gain 0.0000"), "{table}");
+        assert!(table.contains("basis and term unknown"), "{table}");
+    }
+
+    #[test]
+    fn opens_after_adopted_inventory_stay_separate_lots() {
+        let period = PeriodFills {
+            symbol: "BTC-USD".to_string(),
+            from_ms: 0,
+            opening_position: 1.0,
+            opening_price: 50.0,
+            fills: vec![
+                fill(1, 1_000, OrderSide::Buy, 1.0, 60.0),
+                fill(2, 2_000, OrderSide::Buy, 1.0, 70.0),
+                fill(3, 9_000, OrderSide::Sell, 3.0, 80.0),
+            ],
+        };
+
+        let lots = symbol_lots(&period, LotMethod::Fifo);
+
+        assert_eq!(lots.len(), 3);
+        assert!(lots[0].adopted);
+        assert_eq!(lots[0].gain, None);
+        for (lot, (open_trade_id, opened_at, gain)) in lots[1..].iter().zip([
+            (1, "1970-01-01T00:00:01Z", 20.0),
+            (2, "1970-01-01T00:00:02Z", 10.0),
+        ]) {
+            assert!(!lot.adopted);
+            assert_eq!(lot.open_trade_id, Some(open_trade_id));
+            assert_eq!(lot.opened_at.as_deref(), Some(opened_at));
+            assert_eq!(lot.term, "short");
+            assert_eq!(lot.gain, Some(gain));
+        }
+        let table = render(&lots, OutputFormat::Table).unwrap();
+        assert!(table.contains("net gain 30.0000"), "{table}");
+    }
+
+    #[test]
+    fn csv_schema_is_stable() {
+        let header = "symbol,direction,qty,opened_at,closed_at,holding_secs,term,open_price,\
+                      close_price,cost_basis,proceeds,open_fee,close_fee,gain,open_trade_id,\
+                      close_trade_id,adopted";
+        assert_eq!(
+            render(&[], OutputFormat::Csv).unwrap(),
+            format!("{header}\n")
+        );
+
+        let period = PeriodFills {
+            symbol: "ETH-USD".to_string(),
+            from_ms: 0,
+            opening_position: 0.0,
+            opening_price: 0.0,
+            fills: vec![
+                fill(1, 0, OrderSide::Buy, 1.0, 10.0),
+                fill(2, 1_000, OrderSide::Sell, 1.0, 11.0),
+            ],
+        };
+        let csv = render(&symbol_lots(&period, LotMethod::Hifo), OutputFormat::Csv).unwrap();
+
+        assert_eq!(csv.lines().next().unwrap(), header);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "ETH-USD,long,1.0,1970-01-01T00:00:00Z,1970-01-01T00:00:01Z,1,short,10.0,\
+             11.0,10.0,11.0,0.0,0.0,1.0,1,2,false"
+        );
+    }
+}
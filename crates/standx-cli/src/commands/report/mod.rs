@@ -0,0 +1,192 @@
+//! Account reports rebuilt from the venue's trade and funding history.
+
+mod history;
+mod inventory;
+mod lots;
+mod pnl;
+
+use super::schema::{array_of, trace, Definitions};
+use super::util::parse_time_string;
+use crate::cli::{CostBasisMethod, LotMethod, OutputFormat, ReportCommands};
+use anyhow::{bail, Result};
+use history::{load_period_fills, price_at};
+use inventory::QTY_EPSILON;
+use lots::LotRow;
+use pnl::{FundingPoint, PnlReport, PnlRow, SymbolHistory};
+use serde_json::Value;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::Position;
+
+/// Handle report commands
+pub async fn handle_report(command: ReportCommands, output_format: OutputFormat) -> Result<()> {
+    let client = StandXClient::new()?;
+
+    match command {
+        ReportCommands::Pnl {
+            from,
+            to,
+            symbol,
+            method,
+        } => {
+            let (from, to) = report_range(&from, to.as_deref())?;
+            let report = build_pnl_report(&client, from, to, symbol, method).await?;
+            print!("{}", pnl::render(&report, output_format)?);
+        }
+        ReportCommands::Lots {
+            from,
+            to,
+            symbol,
+            method,
+        } => {
+            let (from, to) = report_range(&from, to.as_deref())?;
+            let lots = build_lot_export(&client, from, to, symbol, method).await?;
+            print!("{}", lots::render(&lots, output_format)?);
+        }
+    }
+    Ok(())
+}
+
+/// JSON output schema of `report <subcommand>`, for `standx schema`.
+pub(super) fn output_schema(subcommand: &str, definitions: &mut Definitions) -> Option<Value> {
+    for method in [
+        CostBasisMethod::Fifo,
+        CostBasisMethod::Lifo,
+        CostBasisMethod::Hifo,
+        CostBasisMethod::Average,
+    ] {
+        trace(definitions, &method);
+    }
+    match subcommand {
+        "pnl" => {
+            let row = PnlRow {
+                unrealized_pnl: Some(0.0),
+                ..PnlRow::default()
+            };
+            let report = PnlReport {
+                from: String::new(),
+                to: String::new(),
+                method: CostBasisMethod::Fifo,
+                symbols: vec![row.clone()],
+                daily: vec![row.clone()],
+                total: row,
+            };
+            Some(trace(definitions, &report))
+        }
+        "lots" => {
+            let lot = LotRow {
+                symbol: String::new(),
+                direction: "long",
+                qty: 0.0,
+                opened_at: Some(String::new()),
+                closed_at: String::new(),
+                holding_secs: Some(0),
+                term: "short",
+                open_price: Some(0.0),
+                close_price: 0.0,
+                cost_basis: Some(0.0),
+                proceeds: Some(0.0),
+                open_fee: 0.0,
+                close_fee: 0.0,
+                gain: Some(0.0),
+                open_trade_id: Some(0),
+                close_trade_id: 0,
+                adopted: false,
+            };
+            Some(array_of(definitions, &lot))
+        }
+        _ => None,
+    }
+}
+
+/// Resolve `--from`/`--to` into Unix seconds, clamping the end to now.
+fn report_range(from: &str, to: Option<&str>) -> Result<(i64, i64)> {
+    let now = chrono::Utc::now().timestamp();
+    let from = parse_time_string(from, false)?;
+    let to = match to {
+        Some(to) => parse_time_string(to, true)?.min(now),
+        None => now,
+    };
+    if from >= to {
+        bail!("report start must be before its end");
+    }
+    Ok((from, to))
+}
+
+/// The symbols a report covers: the requested one, or every listed symbol.
+async fn report_symbols(client: &StandXClient, symbol: Option<String>) -> Result<Vec<String>> {
+    Ok(match symbol {
+        Some(symbol) => vec![symbol],
+        None => client
+            .get_symbol_info()
+            .await?
+            .into_iter()
+            .map(|info| info.symbol)
+            .collect(),
+    })
+}
+
+async fn build_pnl_report(
+    client: &StandXClient,
+    from: i64,
+    to: i64,
+    symbol: Option<String>,
+    method: CostBasisMethod,
+) -> Result<PnlReport> {
+    let positions = client.get_positions(None).await?;
+    let mut report = PnlReport::new(from, to, method);
+    for symbol in report_symbols(client, symbol).await? {
+        if let Some(history) = load_symbol_history(client, &positions, &symbol, from, to).await? {
+            report.push(&history);
+        }
+    }
+    Ok(report)
+}
+
+/// Add funding and the end price to one symbol's period fills, or `None`
+/// when the symbol was neither traded nor held during the period.
+async fn load_symbol_history(
+    client: &StandXClient,
+    positions: &[Position],
+    symbol: &str,
+    from: i64,
+    to: i64,
+) -> Result<Option<SymbolHistory>> {
+    let Some(period) = load_period_fills(client, positions, symbol, from, to).await? else {
+        return Ok(None);
+    };
+    let end_price = if period.closing_position().abs() > QTY_EPSILON {
+        price_at(client, symbol, to).await?
+    } else {
+        0.0
+    };
+    let from_ms = from.saturating_mul(1_000);
+    let to_ms = to.saturating_mul(1_000);
+    let funding = FundingPoint::in_period(
+        &client.get_funding_rate(symbol, from, to).await?,
+        from_ms,
+        to_ms,
+    )?;
+
+    Ok(Some(SymbolHistory {
+        period,
+        funding,
+        end_price,
+    }))
+}
+
+async fn build_lot_export(
+    client: &StandXClient,
+    from: i64,
+    to: i64,
+    symbol: Option<String>,
+    method: LotMethod,
+) -> Result<Vec<LotRow>> {
+    let positions = client.get_positions(None).await?;
+    let mut lots = Vec::new();
+    for symbol in report_symbols(client, symbol).await? {
+        if let Some(period) = load_period_fills(client, &positions, &symbol, from, to).await? {
+            lots.extend(lots::symbol_lots(&period, method));
+        }
+    }
+    Ok(lots)
+}
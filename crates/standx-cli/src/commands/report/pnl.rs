@@ -0,0 +1,472 @@
+//! Period PnL report: realized PnL rebuilt from fills under a cost-basis
+//! method, trading fees, estimated funding, and open inventory marked at the
+//! end price.
+
+use super::history::{parse_time_ms, PeriodFills};
+use super::inventory::{ClosedLot, InventoryBook, QTY_EPSILON};
+use crate::cli::{CostBasisMethod, OutputFormat};
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+use standx_sdk::models::FundingRate;
+use std::collections::BTreeMap;
+
+/// Everything needed to rebuild one symbol's PnL over `[from, to]`.
+#[derive(Debug, Clone)]
+pub(super) struct SymbolHistory {
+    pub period: PeriodFills,
+    /// Funding settlements inside the period.
+    pub funding: Vec<FundingPoint>,
+    /// Price open inventory is marked at when the period ends.
+    pub end_price: f64,
+}
+
+/// One funding settlement: longs pay `rate × notional` when the rate is
+/// positive, shorts receive it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct FundingPoint {
+    pub time_ms: i64,
+    pub rate: f64,
+    pub mark: f64,
+}
+
+impl FundingPoint {
+    pub(super) fn parse(rate: &FundingRate) -> Result<Self> {
+        let time_ms = parse_time_ms(&rate.time).ok_or_else(|| {
+            anyhow::anyhow!(
+                "funding record {} has invalid time '{}'",
+                rate.id,
+                rate.time
+            )
+        })?;
+        let number = |field: &str, raw: &str| {
+            raw.parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| {
+                    anyhow::anyhow!("funding record {} has invalid {field} '{raw}'", rate.id)
+                })
+        };
+        Ok(Self {
+            time_ms,
+            rate: number("funding_rate", &rate.funding_rate)?,
+            mark: number("mark_price", &rate.mark_price)?,
+        })
+    }
+
+    /// Settlements inside `[from_ms, to_ms]` in ascending time, the order
+    /// [`symbol_pnl`] merges them with the fills. The API's order is not
+    /// relied on.
+    pub(super) fn in_period(rates: &[FundingRate], from_ms: i64, to_ms: i64) -> Result<Vec<Self>> {
+        let mut points = rates
+            .iter()
+            .map(Self::parse)
+            .filter(|point| {
+                point.as_ref().map_or(true, |point| {
+                    point.time_ms >= from_ms && point.time_ms <= to_ms
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        points.sort_by_key(|point| point.time_ms);
+        Ok(points)
+    }
+}
+
+/// One report line: a symbol-day, a symbol total, or the grand total.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct PnlRow {
+    pub symbol: String,
+    /// `YYYY-MM-DD` (UTC) for daily rows, `total` for summaries.
+    pub period: String,
+    pub fills: u64,
+    /// Traded notional in quote units.
+    pub volume: f64,
+    pub realized_pnl: f64,
+    /// Fees paid (negative when rebates exceeded fees).
+    pub fees: f64,
+    /// Estimated funding received (negative when paid).
+    pub funding: f64,
+    /// Open inventory marked at the end price; summaries only.
+    pub unrealized_pnl: Option<f64>,
+    /// `realized - fees + funding + unrealized`.
+    pub net_pnl: f64,
+    /// Signed position at the end of the row's period.
+    pub position: f64,
+}
+
+impl PnlRow {
+    fn new(symbol: &str, period: String) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            period,
+            ..Self::default()
+        }
+    }
+
+    fn settle_net(&mut self) {
+        self.net_pnl =
+            self.realized_pnl - self.fees + self.funding + self.unrealized_pnl.unwrap_or(0.0);
+    }
+
+    fn accumulate(&mut self, other: &PnlRow) {
+        self.fills += other.fills;
+        self.volume += other.volume;
+        self.realized_pnl += other.realized_pnl;
+        self.fees += other.fees;
+        self.funding += other.funding;
+        self.unrealized_pnl = match (self.unrealized_pnl, other.unrealized_pnl) {
+            (None, None) => None,
+            (left, right) => Some(left.unwrap_or(0.0) + right.unwrap_or(0.0)),
+        };
+        self.net_pnl += other.net_pnl;
+    }
+}
+
+/// Full report: per-symbol summaries, per-symbol-day breakdown, grand total.
+#[derive(Debug, Clone, Serialize)]
+pub(super) struct PnlReport {
+    pub from: String,
+    pub to: String,
+    pub method: CostBasisMethod,
+    pub symbols: Vec<PnlRow>,
+    pub daily: Vec<PnlRow>,
+    pub total: PnlRow,
+}
+
+impl PnlReport {
+    pub(super) fn new(from: i64, to: i64, method: CostBasisMethod) -> Self {
+        let mut total = PnlRow::new("ALL", "total".to_string());
+        total.unrealized_pnl = Some(0.0);
+        Self {
+            from: format_time(from * 1_000),
+            to: format_time(to * 1_000),
+            method,
+            symbols: Vec::new(),
+            daily: Vec::new(),
+            total,
+        }
+    }
+
+    pub(super) fn push(&mut self, history: &SymbolHistory) {
+        let (summary, daily) = symbol_pnl(history, self.method);
+        self.total.accumulate(&summary);
+        self.symbols.push(summary);
+        self.daily.extend(daily);
+    }
+}
+
+/// Replay one symbol's fills and funding in time order.
+pub(super) fn symbol_pnl(
+    history: &SymbolHistory,
+    method: CostBasisMethod,
+) -> (PnlRow, Vec<PnlRow>) {
+    let period = &history.period;
+    let mut book = InventoryBook::new(method);
+    if period.opening_position.abs() > QTY_EPSILON {
+        book.adopt(
+            period.opening_position,
+            period.opening_price,
+            period.from_ms,
+        );
+    }
+
+    let mut days: BTreeMap<String, PnlRow> = BTreeMap::new();
+    let mut fills = period.fills.iter().peekable();
+    let mut funding = history.funding.iter().peekable();
+    loop {
+        // A fill and a settlement at the same instant: settle funding on the
+        // position held going into that instant.
+        let take_funding = match (fills.peek(), funding.peek()) {
+            (None, None) => break,
+            (Some(_), None) => false,
+            (None, Some(_)) => true,
+            (Some(fill), Some(point)) => point.time_ms <= fill.time_ms,
+        };
+        if take_funding {
+            let point = funding.next().expect("peeked funding point");
+            let day = day_row(&mut days, &period.symbol, point.time_ms);
+            day.funding += -book.position() * point.mark * point.rate;
+            day.position = book.position();
+        } else {
+            let fill = fills.next().expect("peeked fill");
+            let realized: f64 = book.apply(fill).iter().map(ClosedLot::realized).sum();
+            let day = day_row(&mut days, &period.symbol, fill.time_ms);
+            day.fills += 1;
+            day.volume += fill.qty * fill.price;
+            day.realized_pnl += realized;
+            day.fees += fill.fee;
+            day.position = book.position();
+        }
+    }
+
+    let mut summary = PnlRow::new(&period.symbol, "total".to_string());
+    let daily: Vec<PnlRow> = days
+        .into_values()
+        .map(|mut day| {
+            day.settle_net();
+            summary.accumulate(&day);
+            day
+        })
+        .collect();
+    summary.position = book.position();
+    summary.unrealized_pnl = Some(book.unrealized(history.end_price));
+    summary.settle_net();
+    (summary, daily)
+}
+
+fn day_row<'a>(
+    days: &'a mut BTreeMap<String, PnlRow>,
+    symbol: &str,
+    time_ms: i64,
+) -> &'a mut PnlRow {
+    let day = chrono::DateTime::from_timestamp_millis(time_ms)
+        .map(|time| time.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| time_ms.to_string());
+    days.entry(day.clone())
+        .or_insert_with(|| PnlRow::new(symbol, day))
+}
+
+fn format_time(time_ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(time_ms)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_else(|| time_ms.to_string())
+}
+
+pub(super) fn render(report: &PnlReport, output_format: OutputFormat) -> Result<String> {
+    Ok(match output_format {
+        OutputFormat::Table => format_pnl_table(report),
+        OutputFormat::Json => format!("{}\n", output::format_json(report)?),
+        OutputFormat::Csv => {
+            let rows: Vec<&PnlRow> = report
+                .daily
+                .iter()
+                .chain(report.symbols.iter())
+                .chain(std::iter::once(&report.total))
+                .collect();
+            output::format_csv(&rows)?
+        }
+        OutputFormat::Quiet => format!("{:.2}\n", report.total.net_pnl),
+    })
+}
+
+fn format_pnl_table(report: &PnlReport) -> String {
+    let mut text = format!(
+        "=== PnL Report ({} cost basis) ===\n{} → {}\n\n",
+        match report.method {
+            CostBasisMethod::Fifo => "FIFO",
+            CostBasisMethod::Lifo => "LIFO",
+            CostBasisMethod::Hifo => "HIFO",
+            CostBasisMethod::Average => "average",
+        },
+        report.from,
+        report.to
+    );
+    if report.symbols.is_empty() {
+        text.push_str("ℹ️  No trades or positions in the specified time range\n");
+        return text;
+    }
+
+    let header = format!(
+        "{:<12} {:<10} {:>6} {:>14} {:>12} {:>10} {:>10} {:>12} {:>12} {:>10}",
+        "SYMBOL",
+        "PERIOD",
+        "FILLS",
+        "VOLUME",
+        "REALIZED",
+        "FEES",
+        "FUNDING",
+        "UNREALIZED",
+        "NET",
+        "POSITION"
+    );
+    let separator = "-".repeat(header.len());
+    let line = |row: &PnlRow| {
+        format!(
+            "{:<12} {:<10} {:>6} {:>14.2} {:>12.4} {:>10.4} {:>10.4} {:>12} {:>12.4} {:>10}\n",
+            row.symbol,
+            row.period,
+            row.fills,
+            row.volume,
+            row.realized_pnl,
+            row.fees,
+            row.funding,
+            row.unrealized_pnl
+                .map(|value| format!("{value:.4}"))
+                .unwrap_or_else(|| "-".to_string()),
+            row.net_pnl,
+            format!("{}", row.position)
+        )
+    };
+
+    text.push_str("--- By symbol ---\n");
+    text.push_str(&format!("{header}\n{separator}\n"));
+    for row in &report.symbols {
+        text.push_str(&line(row));
+    }
+    text.push_str(&format!("{separator}\n"));
+    text.push_str(&line(&report.total));
+
+    text.push_str("\n--- By day (UTC) ---\n");
+    text.push_str(&format!("{header}\n{separator}\n"));
+    for row in &report.daily {
+        text.push_str(&line(row));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::report::history::HistoryFill;
+    use standx_sdk::models::OrderSide;
+
+    const DAY_MS: i64 = 86_400_000;
+
+    fn fill(trade_id: u64, time_ms: i64, side: OrderSide, price: f64, qty: f64) -> HistoryFill {
+        HistoryFill {
+            trade_id,
+            order_id: None,
+            time_ms,
+            side,
+            price,
+            qty,
+            fee: 0.1,
+        }
+    }
+
+    fn period(opening_position: f64, opening_price: f64, fills: Vec<HistoryFill>) -> PeriodFills {
+        PeriodFills {
+            symbol: "BTC-USD".to_string(),
+            from_ms: 0,
+            opening_position,
+            opening_price,
+            fills,
+        }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn report_splits_realized_fees_funding_and_unrealized() {
+        let history = SymbolHistory {
+            period: period(
+                1.0,
+                100.0,
+                vec![
+                    fill(1, DAY_MS + 10, OrderSide::Buy, 110.0, 1.0),
+                    fill(2, 2 * DAY_MS + 10, OrderSide::Sell, 120.0, 1.5),
+                ],
+            ),
+            funding: vec![FundingPoint {
+                time_ms: DAY_MS + 20,
+                rate: 0.001,
+                mark: 100.0,
+            }],
+            end_price: 130.0,
+        };
+
+        let (summary, daily) = symbol_pnl(&history, CostBasisMethod::Fifo);
+
+        assert_eq!(daily.len(), 2);
+        assert_eq!(daily[0].period, "1970-01-02");
+        // Long 2 at the settlement: pays 2 × 100 × 0.1%.
+        assert_close(daily[0].funding, -0.2);
+        // FIFO closes the opening lot (+20) and half the day-one lot (+5).
+        assert_close(daily[1].realized_pnl, 25.0);
+        assert_close(summary.fees, 0.2);
+        assert_close(summary.position, 0.5);
+        assert_close(summary.unrealized_pnl.unwrap(), 10.0);
+        assert_close(summary.net_pnl, 25.0 - 0.2 - 0.2 + 10.0);
+    }
+
+    #[test]
+    fn average_cost_changes_realized_but_not_total_pnl() {
+        let history = SymbolHistory {
+            period: period(
+                0.0,
+                0.0,
+                vec![
+                    fill(1, 10, OrderSide::Buy, 100.0, 1.0),
+                    fill(2, 20, OrderSide::Buy, 110.0, 1.0),
+                    fill(3, 30, OrderSide::Sell, 120.0, 1.0),
+                ],
+            ),
+            funding: Vec::new(),
+            end_price: 115.0,
+        };
+
+        let (fifo, _) = symbol_pnl(&history, CostBasisMethod::Fifo);
+        let (average, _) = symbol_pnl(&history, CostBasisMethod::Average);
+
+        assert_close(fifo.realized_pnl, 20.0);
+        assert_close(average.realized_pnl, 15.0);
+        assert_close(fifo.net_pnl, average.net_pnl);
+    }
+
+    #[test]
+    fn csv_output_flattens_daily_symbol_and_total_rows() {
+        let mut report = PnlReport::new(0, DAY_MS / 1_000, CostBasisMethod::Fifo);
+        report.push(&SymbolHistory {
+            period: period(0.0, 0.0, vec![fill(1, 10, OrderSide::Buy, 10.0, 1.0)]),
+            funding: Vec::new(),
+            end_price: 11.0,
+        });
+
+        let csv = render(&report, OutputFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert!(lines[0].starts_with("symbol,period,fills,volume"));
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].starts_with("BTC-USD,1970-01-01,1,"));
+        assert!(lines[3].starts_with("ALL,total,1,"));
+    }
+
+    fn funding_rate(id: i64, time: &str, rate: &str) -> FundingRate {
+        FundingRate {
+            id,
+            symbol: "BTC-USD".to_string(),
+            funding_rate: rate.to_string(),
+            mark_price: "100".to_string(),
+            index_price: "100".to_string(),
+            premium: "0".to_string(),
+            time: time.to_string(),
+            created_at: time.to_string(),
+            updated_at: time.to_string(),
+        }
+    }
+
+    #[test]
+    fn funding_returned_newest_first_settles_against_the_right_position() {
+        // Newest first, as some venues page it.
+        let rates = vec![
+            funding_rate(3, "1970-01-01T03:00:00Z", "0.002"),
+            funding_rate(2, "1970-01-01T02:00:00Z", "0.001"),
+            funding_rate(1, "1970-01-01T00:00:00Z", "0.005"),
+        ];
+        let funding = FundingPoint::in_period(&rates, 1, DAY_MS).unwrap();
+        let times: Vec<i64> = funding.iter().map(|point| point.time_ms).collect();
+        // The settlement at the period start boundary is outside `[1, DAY_MS]`.
+        assert_eq!(times, vec![7_200_000, 10_800_000]);
+
+        let history = SymbolHistory {
+            period: period(
+                0.0,
+                0.0,
+                vec![
+                    fill(1, 3_600_000, OrderSide::Buy, 100.0, 1.0),
+                    fill(2, 9_000_000, OrderSide::Sell, 100.0, 1.0),
+                ],
+            ),
+            funding,
+            end_price: 100.0,
+        };
+        let (summary, _) = symbol_pnl(&history, CostBasisMethod::Fifo);
+        // Only the 02:00 settlement falls while long 1: pays 1 × 100 × 0.1%.
+        assert_close(summary.funding, -0.1);
+    }
+}
@@ -0,0 +1,186 @@
+//! `standx risk` — read-only risk analysis of hypothetical orders
+//!
+//! `risk whatif` fetches the balance, open positions and the symbol's
+//! position config, then runs the order through the margin model in
+//! [`whatif`] without sending anything.
+
+mod whatif;
+
+use super::schema::{trace, Definitions};
+use crate::cli::*;
+use anyhow::{anyhow, bail, Result};
+use serde_json::Value;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{OrderSide, Position};
+use whatif::{Held, Inputs};
+
+/// Handle risk commands
+pub async fn handle_risk(command: RiskCommands, output_format: OutputFormat) -> Result<()> {
+    match command {
+        RiskCommands::Whatif {
+            symbol,
+            qty,
+            price,
+            leverage,
+            margin_mode,
+            mmr,
+        } => {
+            if !qty.is_finite() || qty == 0.0 {
+                bail!("--qty must be a non-zero number (negative to sell)");
+            }
+            if price.is_some_and(|price| !price.is_finite() || price <= 0.0) {
+                bail!("--price must be positive");
+            }
+            if leverage == Some(0) {
+                bail!("--leverage must be at least 1");
+            }
+            if mmr.is_some_and(|mmr| !(0.0..1.0).contains(&mmr)) {
+                bail!("--mmr must be a fraction between 0 and 1 (e.g. 0.005)");
+            }
+            let client = StandXClient::new()?;
+            let inputs =
+                fetch_inputs(&client, &symbol, qty, price, leverage, margin_mode, mmr).await?;
+            print!(
+                "{}",
+                whatif::render(&whatif::simulate(&inputs), output_format)?
+            );
+        }
+    }
+    Ok(())
+}
+
+fn number(value: &str) -> f64 {
+    value.parse().unwrap_or(0.0)
+}
+
+fn signed_qty(position: &Position) -> f64 {
+    let qty = number(&position.qty);
+    match position.side {
+        Some(OrderSide::Sell) => -qty.abs(),
+        Some(OrderSide::Buy) => qty.abs(),
+        None => qty,
+    }
+}
+
+/// Maintenance rate of a position, when the venue reports one.
+fn position_rate(position: &Position) -> Option<f64> {
+    position
+        .mmr
+        .as_deref()
+        .and_then(|mmr| mmr.parse::<f64>().ok())
+        .filter(|mmr| mmr.is_finite() && *mmr > 0.0)
+}
+
+async fn fetch_inputs(
+    client: &StandXClient,
+    symbol: &str,
+    qty: f64,
+    price: Option<f64>,
+    leverage: Option<u32>,
+    margin_mode: Option<MarginMode>,
+    mmr: Option<f64>,
+) -> Result<Inputs> {
+    let (balance, positions, symbol_info) = tokio::try_join!(
+        client.get_balance(),
+        client.get_positions(None),
+        client.get_symbol_info(),
+    )?;
+    let info = symbol_info
+        .into_iter()
+        .find(|info| info.symbol.eq_ignore_ascii_case(symbol))
+        .ok_or_else(|| anyhow!("Symbol {} not found", symbol))?;
+    let symbol = info.symbol.clone();
+    let max_leverage = info
+        .max_leverage
+        .parse::<f64>()
+        .ok()
+        .filter(|max| *max > 0.0);
+    // Same fallback as `leverage get`: symbol defaults in cross mode.
+    let (config_leverage, config_mode) = match client.get_position_config(&symbol).await {
+        Ok(config) => (config.leverage, MarginMode::from_api(&config.margin_mode)),
+        Err(_) => (info.def_leverage.clone(), Some(MarginMode::Cross)),
+    };
+
+    let open: Vec<&Position> = positions
+        .iter()
+        .filter(|position| number(&position.qty) != 0.0)
+        .collect();
+    let current = open.iter().find(|position| position.symbol == symbol);
+    let mark_price = match current.map(|position| number(&position.mark_price)) {
+        Some(mark) if mark > 0.0 => mark,
+        _ => number(&client.get_symbol_price(&symbol).await?.mark_price),
+    };
+    if mark_price <= 0.0 {
+        bail!("no mark price for {}", symbol);
+    }
+
+    let estimated = max_leverage.map(|max| 1.0 / (2.0 * max));
+    let (maintenance_rate, maintenance_rate_source) =
+        match (mmr, current.and_then(|position| position_rate(position))) {
+            (Some(mmr), _) => (mmr, "flag"),
+            (None, Some(rate)) => (rate, "position"),
+            (None, None) => (
+                estimated.ok_or_else(|| {
+                    anyhow!("no maintenance rate known for {}; pass --mmr", symbol)
+                })?,
+                "estimated",
+            ),
+        };
+    let current_mode = current.and_then(|position| MarginMode::from_api(&position.margin_mode));
+    let leverage = match leverage {
+        Some(leverage) => f64::from(leverage),
+        None => current
+            .map(|position| number(&position.leverage))
+            .filter(|leverage| *leverage > 0.0)
+            .unwrap_or_else(|| number(&config_leverage)),
+    };
+    if leverage <= 0.0 {
+        bail!("no leverage known for {}; pass --leverage", symbol);
+    }
+
+    // Other cross positions share the account equity backing this one.
+    let others_maintenance = open
+        .iter()
+        .filter(|position| position.symbol != symbol)
+        .filter(|position| {
+            MarginMode::from_api(&position.margin_mode) != Some(MarginMode::Isolated)
+        })
+        .map(|position| {
+            let notional = signed_qty(position).abs() * number(&position.mark_price);
+            notional * position_rate(position).or(estimated).unwrap_or(0.0)
+        })
+        .sum();
+
+    Ok(Inputs {
+        symbol,
+        qty,
+        price: price.unwrap_or(mark_price),
+        mark_price,
+        leverage,
+        max_leverage,
+        mode: margin_mode
+            .or(current_mode)
+            .or(config_mode)
+            .unwrap_or_default(),
+        current_mode,
+        maintenance_rate,
+        maintenance_rate_source,
+        equity: number(&balance.equity),
+        available: number(&balance.cross_available),
+        held: current.map_or_else(Held::default, |position| Held {
+            qty: signed_qty(position),
+            entry_price: number(&position.entry_price),
+            initial_margin: number(&position.initial_margin),
+            upnl: number(&position.upnl),
+        }),
+        others_maintenance,
+    })
+}
+
+/// JSON output schema of `risk <subcommand>`, for `standx schema`.
+pub(super) fn output_schema(subcommand: &str, definitions: &mut Definitions) -> Option<Value> {
+    match subcommand {
+        "whatif" => Some(trace(definitions, &whatif::example())),
+        _ => None,
+    }
+}
@@ -0,0 +1,477 @@
+//! Margin and liquidation model behind `risk whatif`.
+//!
+//! Positions are valued at the current mark. Initial margin is notional over
+//! leverage and maintenance margin is notional times the maintenance rate.
+//! In cross mode the liquidation price is where account equity meets the
+//! maintenance margin of every cross position, holding the other symbols at
+//! their current marks. In isolated mode only the position's own margin
+//! counts. Fees and funding are ignored, so treat the result as an estimate
+//! next to the venue's own `liq_price`.
+
+use crate::cli::{MarginMode, OutputFormat};
+use crate::output;
+use anyhow::Result;
+use serde::Serialize;
+
+/// Quantities closer to zero than this count as flat.
+const QTY_EPSILON: f64 = 1e-12;
+
+/// The position already held on the simulated symbol.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct Held {
+    /// Signed: positive long, negative short.
+    pub qty: f64,
+    pub entry_price: f64,
+    pub initial_margin: f64,
+    pub upnl: f64,
+}
+
+/// Everything the simulation needs, already fetched.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Inputs {
+    pub symbol: String,
+    /// Signed order size: positive buys, negative sells.
+    pub qty: f64,
+    pub price: f64,
+    pub mark_price: f64,
+    pub leverage: f64,
+    pub max_leverage: Option<f64>,
+    pub mode: MarginMode,
+    /// Margin mode of the position held now, when there is one.
+    pub current_mode: Option<MarginMode>,
+    pub maintenance_rate: f64,
+    pub maintenance_rate_source: &'static str,
+    pub equity: f64,
+    pub available: f64,
+    pub held: Held,
+    /// Maintenance margin of the account's other cross positions.
+    pub others_maintenance: f64,
+}
+
+/// Margin state of the symbol's position, before or after the order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct PositionRisk {
+    pub qty: f64,
+    pub entry_price: f64,
+    pub notional: f64,
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+    pub unrealized_pnl: f64,
+    pub liquidation_price: Option<f64>,
+    /// Maintenance margin over the equity backing it, in percent; the
+    /// position is liquidated at 100.
+    pub margin_ratio_pct: Option<f64>,
+    pub equity: f64,
+    pub available: f64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(super) struct WhatIf {
+    pub symbol: String,
+    pub order_qty: f64,
+    pub price: f64,
+    pub mark_price: f64,
+    pub mode: MarginMode,
+    pub leverage: f64,
+    pub maintenance_rate: f64,
+    /// `flag`, `position` (the venue's `mmr`) or `estimated` (half the
+    /// initial rate at maximum leverage).
+    pub maintenance_rate_source: &'static str,
+    pub realized_pnl: f64,
+    pub before: PositionRisk,
+    pub after: PositionRisk,
+    pub warnings: Vec<String>,
+}
+
+/// Position after filling `qty` at `price`: (qty, entry price, realized PnL).
+fn fill(held: Held, qty: f64, price: f64) -> (f64, f64, f64) {
+    let after = held.qty + qty;
+    if held.qty.abs() < QTY_EPSILON || held.qty.signum() == qty.signum() {
+        let entry = (held.qty.abs() * held.entry_price + qty.abs() * price) / after.abs();
+        return (after, entry, 0.0);
+    }
+    let closed = qty.abs().min(held.qty.abs());
+    let realized = closed * (price - held.entry_price) * held.qty.signum();
+    let entry = if after.abs() < QTY_EPSILON {
+        0.0
+    } else if after.signum() == held.qty.signum() {
+        held.entry_price
+    } else {
+        price
+    };
+    (after, entry, realized)
+}
+
+/// Price at which `equity + qty * (P - reference)` falls to
+/// `fixed_maintenance + |qty| * P * rate`.
+fn liquidation_price(
+    qty: f64,
+    reference: f64,
+    equity: f64,
+    fixed_maintenance: f64,
+    rate: f64,
+) -> Option<f64> {
+    if qty.abs() < QTY_EPSILON {
+        return None;
+    }
+    let denominator = qty - qty.abs() * rate;
+    if denominator.abs() < QTY_EPSILON {
+        return None;
+    }
+    let price = (fixed_maintenance - equity + qty * reference) / denominator;
+    (price > 0.0 && price.is_finite()).then_some(price)
+}
+
+fn ratio(maintenance: f64, backing: f64) -> Option<f64> {
+    (backing > 0.0).then(|| maintenance / backing * 100.0)
+}
+
+struct State {
+    qty: f64,
+    entry_price: f64,
+    initial_margin: f64,
+    upnl: f64,
+    equity: f64,
+    available: f64,
+}
+
+fn assess(inputs: &Inputs, mode: MarginMode, state: State) -> PositionRisk {
+    let notional = state.qty.abs() * inputs.mark_price;
+    let maintenance = notional * inputs.maintenance_rate;
+    let (liquidation_price, margin_ratio_pct) = match mode {
+        MarginMode::Cross => (
+            liquidation_price(
+                state.qty,
+                inputs.mark_price,
+                state.equity,
+                inputs.others_maintenance,
+                inputs.maintenance_rate,
+            ),
+            ratio(inputs.others_maintenance + maintenance, state.equity),
+        ),
+        MarginMode::Isolated => (
+            liquidation_price(
+                state.qty,
+                state.entry_price,
+                state.initial_margin,
+                0.0,
+                inputs.maintenance_rate,
+            ),
+            (state.qty.abs() >= QTY_EPSILON)
+                .then(|| ratio(maintenance, state.initial_margin + state.upnl))
+                .flatten(),
+        ),
+    };
+    PositionRisk {
+        qty: state.qty,
+        entry_price: state.entry_price,
+        notional,
+        initial_margin: state.initial_margin,
+        maintenance_margin: maintenance,
+        unrealized_pnl: state.upnl,
+        liquidation_price,
+        margin_ratio_pct,
+        equity: state.equity,
+        available: state.available,
+    }
+}
+
+pub(super) fn simulate(inputs: &Inputs) -> WhatIf {
+    let held = inputs.held;
+    let before = assess(
+        inputs,
+        inputs.current_mode.unwrap_or(inputs.mode),
+        State {
+            qty: held.qty,
+            entry_price: held.entry_price,
+            initial_margin: held.initial_margin,
+            upnl: held.upnl,
+            equity: inputs.equity,
+            available: inputs.available,
+        },
+    );
+
+    let (qty, entry_price, realized_pnl) = fill(held, inputs.qty, inputs.price);
+    let upnl = qty * (inputs.mark_price - entry_price);
+    let initial_margin = match inputs.mode {
+        MarginMode::Cross => qty.abs() * inputs.mark_price / inputs.leverage,
+        MarginMode::Isolated => qty.abs() * entry_price / inputs.leverage,
+    };
+    let equity = inputs.equity + realized_pnl + upnl - held.upnl;
+    let margin_change = initial_margin - held.initial_margin;
+    // Cross availability moves with unrealized PnL; isolated availability
+    // only with realized PnL and the margin moved into the position.
+    let available = match inputs.mode {
+        MarginMode::Cross => inputs.available + (equity - inputs.equity) - margin_change,
+        MarginMode::Isolated => inputs.available + realized_pnl - margin_change,
+    };
+    let after = assess(
+        inputs,
+        inputs.mode,
+        State {
+            qty,
+            entry_price,
+            initial_margin,
+            upnl,
+            equity,
+            available,
+        },
+    );
+
+    let mut warnings = Vec::new();
+    if let Some(max) = inputs.max_leverage.filter(|max| inputs.leverage > *max) {
+        warnings.push(format!(
+            "leverage {}x exceeds the {} maximum of {}x",
+            inputs.leverage, inputs.symbol, max
+        ));
+    }
+    if after.available < 0.0 {
+        warnings.push(format!(
+            "insufficient available balance: short by {:.2}",
+            -after.available
+        ));
+    }
+    if after.margin_ratio_pct.is_some_and(|ratio| ratio >= 100.0) {
+        warnings.push("the resulting position would be liquidated at the current mark".into());
+    }
+    if let Some(current) = inputs.current_mode.filter(|mode| *mode != inputs.mode) {
+        warnings.push(format!(
+            "the open position is {}; the venue may refuse a margin-mode change while it is open",
+            current.as_str()
+        ));
+    }
+
+    WhatIf {
+        symbol: inputs.symbol.clone(),
+        order_qty: inputs.qty,
+        price: inputs.price,
+        mark_price: inputs.mark_price,
+        mode: inputs.mode,
+        leverage: inputs.leverage,
+        maintenance_rate: inputs.maintenance_rate,
+        maintenance_rate_source: inputs.maintenance_rate_source,
+        realized_pnl,
+        before,
+        after,
+        warnings,
+    }
+}
+
+/// A result with every optional field set, for `standx schema`.
+pub(super) fn example() -> WhatIf {
+    let risk = PositionRisk {
+        liquidation_price: Some(0.0),
+        margin_ratio_pct: Some(0.0),
+        ..PositionRisk::default()
+    };
+    WhatIf {
+        before: risk.clone(),
+        after: risk,
+        warnings: vec![String::new()],
+        ..WhatIf::default()
+    }
+}
+
+pub(super) fn render(whatif: &WhatIf, output_format: OutputFormat) -> Result<String> {
+    Ok(match output_format {
+        OutputFormat::Table => format_table(whatif),
+        OutputFormat::Json => format!("{}\n", output::format_json(whatif)?),
+        OutputFormat::Csv => output::format_csv(&[whatif])?,
+        OutputFormat::Quiet => format!("{}\n", optional(whatif.after.liquidation_price, 4)),
+    })
+}
+
+fn optional(value: Option<f64>, decimals: usize) -> String {
+    value
+        .map(|value| format!("{value:.decimals$}"))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn format_table(whatif: &WhatIf) -> String {
+    let side = if whatif.order_qty >= 0.0 {
+        "Buy"
+    } else {
+        "Sell"
+    };
+    let mut text = format!(
+        "=== What-if: {} {} {} @ {} ===\n",
+        side,
+        whatif.order_qty.abs(),
+        whatif.symbol,
+        whatif.price
+    );
+    text.push_str(&format!(
+        "  Mode: {}  Leverage: {}x  Mark: {}  Maintenance rate: {:.4}% ({})\n\n",
+        whatif.mode.as_str(),
+        whatif.leverage,
+        whatif.mark_price,
+        whatif.maintenance_rate * 100.0,
+        whatif.maintenance_rate_source
+    ));
+    let header = format!("{:<22} {:>16} {:>16}", "", "BEFORE", "AFTER");
+    let separator = "-".repeat(header.len());
+    text.push_str(&format!("{header}\n{separator}\n"));
+    let (before, after) = (&whatif.before, &whatif.after);
+    let rows: [(&str, String, String); 10] = [
+        (
+            "Position",
+            format!("{}", before.qty),
+            format!("{}", after.qty),
+        ),
+        (
+            "Entry price",
+            format!("{:.4}", before.entry_price),
+            format!("{:.4}", after.entry_price),
+        ),
+        (
+            "Notional",
+            format!("{:.2}", before.notional),
+            format!("{:.2}", after.notional),
+        ),
+        (
+            "Initial margin",
+            format!("{:.2}", before.initial_margin),
+            format!("{:.2}", after.initial_margin),
+        ),
+        (
+            "Maintenance margin",
+            format!("{:.2}", before.maintenance_margin),
+            format!("{:.2}", after.maintenance_margin),
+        ),
+        (
+            "Unrealized PnL",
+            format!("{:.2}", before.unrealized_pnl),
+            format!("{:.2}", after.unrealized_pnl),
+        ),
+        (
+            "Liquidation price",
+            optional(before.liquidation_price, 4),
+            optional(after.liquidation_price, 4),
+        ),
+        (
+            "Margin ratio %",
+            optional(before.margin_ratio_pct, 2),
+            optional(after.margin_ratio_pct, 2),
+        ),
+        (
+            "Equity",
+            format!("{:.2}", before.equity),
+            format!("{:.2}", after.equity),
+        ),
+        (
+            "Available",
+            format!("{:.2}", before.available),
+            format!("{:.2}", after.available),
+        ),
+    ];
+    for (label, before, after) in rows {
+        text.push_str(&format!("{label:<22} {before:>16} {after:>16}\n"));
+    }
+    if whatif.realized_pnl != 0.0 {
+        text.push_str(&format!("\n  Realized PnL: {:+.2}\n", whatif.realized_pnl));
+    }
+    if !whatif.warnings.is_empty() {
+        text.push('\n');
+    }
+    for warning in &whatif.warnings {
+        text.push_str(&format!("⚠️  {warning}\n"));
+    }
+    text.push_str("\nℹ️  Estimate only (fees and funding ignored); nothing was placed.\n");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inputs(qty: f64, mode: MarginMode) -> Inputs {
+        Inputs {
+            symbol: "BTC-USD".to_string(),
+            qty,
+            price: 100.0,
+            mark_price: 100.0,
+            leverage: 10.0,
+            max_leverage: Some(20.0),
+            mode,
+            current_mode: None,
+            maintenance_rate: 0.05,
+            maintenance_rate_source: "flag",
+            equity: 1_000.0,
+            available: 1_000.0,
+            held: Held::default(),
+            others_maintenance: 0.0,
+        }
+    }
+
+    fn close(actual: Option<f64>, expected: f64) {
+        let actual = actual.expect("value");
+        assert!((actual - expected).abs() < 1e-6, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn isolated_long_liquidates_where_margin_meets_maintenance() {
+        let result = simulate(&inputs(10.0, MarginMode::Isolated));
+        let after = &result.after;
+        assert_eq!(after.notional, 1_000.0);
+        assert_eq!(after.initial_margin, 100.0);
+        assert_eq!(after.maintenance_margin, 50.0);
+        assert_eq!(after.available, 900.0);
+        close(after.margin_ratio_pct, 50.0);
+        // 100 + 10 (P - 100) = 0.5 P  =>  P = 900 / 9.5
+        close(after.liquidation_price, 900.0 / 9.5);
+        assert!(result.warnings.is_empty());
+        assert_eq!(result.before.liquidation_price, None);
+    }
+
+    #[test]
+    fn cross_short_is_backed_by_the_whole_account() {
+        let mut short = inputs(-10.0, MarginMode::Cross);
+        short.others_maintenance = 20.0;
+        let after = simulate(&short).after;
+        // 1000 - 10 (P - 100) = 20 + 0.5 P  =>  P = 1980 / 10.5
+        close(after.liquidation_price, 1_980.0 / 10.5);
+        close(after.margin_ratio_pct, 7.0);
+        assert_eq!(after.available, 900.0);
+    }
+
+    #[test]
+    fn reducing_realizes_pnl_and_flipping_reprices_entry() {
+        let mut reduce = inputs(-4.0, MarginMode::Cross);
+        reduce.price = 110.0;
+        reduce.mark_price = 110.0;
+        reduce.held = Held {
+            qty: 10.0,
+            entry_price: 100.0,
+            initial_margin: 110.0,
+            upnl: 100.0,
+        };
+        let result = simulate(&reduce);
+        assert_eq!(result.realized_pnl, 40.0);
+        assert_eq!(result.after.qty, 6.0);
+        assert_eq!(result.after.entry_price, 100.0);
+        assert!((result.after.equity - 1_000.0).abs() < 1e-9);
+
+        let mut flip = reduce.clone();
+        flip.qty = -15.0;
+        let result = simulate(&flip);
+        assert_eq!(result.realized_pnl, 100.0);
+        assert_eq!(result.after.qty, -5.0);
+        assert_eq!(result.after.entry_price, 110.0);
+    }
+
+    #[test]
+    fn warns_about_leverage_balance_liquidation_and_mode_changes() {
+        let mut risky = inputs(500.0, MarginMode::Isolated);
+        risky.leverage = 25.0;
+        risky.current_mode = Some(MarginMode::Cross);
+        risky.held.qty = 1.0;
+        risky.held.entry_price = 100.0;
+        let warnings = simulate(&risky).warnings;
+        // 25x leaves 4% initial margin, below the 5% maintenance rate.
+        assert_eq!(warnings.len(), 4, "{warnings:?}");
+        assert!(warnings[0].contains("maximum of 20x"));
+        assert!(warnings[1].starts_with("insufficient available balance"));
+        assert!(warnings[2].contains("liquidated"));
+        assert!(warnings[3].contains("cross"));
+    }
+}
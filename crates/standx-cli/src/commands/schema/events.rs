@@ -0,0 +1,754 @@
+//! Catalog of the JSON event lines the maker and `watch` write in JSON mode.
+//!
+//! The events are built with `json!` literals rather than types, so their
+//! field lists live here; the test at the bottom scans the emitting sources
+//! and fails when an action or a field is missing from the catalog. Only the
+//! shared envelope fields are typed; payload fields are named but left open.
+
+use serde_json::{json, Map, Value};
+
+/// One event kind, keyed by its `action` value.
+pub(super) struct Event {
+    pub action: &'static str,
+    pub description: &'static str,
+    /// Every top-level field the event may carry.
+    pub fields: &'static [&'static str],
+}
+
+/// Events of `standx maker run` (and `watch`, which reuses the notifier).
+pub(super) const MAKER_EVENTS: &[Event] = &[
+    Event {
+        action: "cycle_summary",
+        description: "One per quoting cycle: market, inventory, quote counts and running PnL",
+        fields: &[
+            "ts",
+            "cycle",
+            "mode",
+            "symbol",
+            "action",
+            "mark",
+            "best_bid",
+            "best_ask",
+            "market_source",
+            "market_fallback_reason",
+            "ws_snapshot",
+            "position",
+            "starting_position",
+            "account",
+            "holds",
+            "places",
+            "cancels",
+            "fills",
+            "pnl",
+            "fills_total",
+            "uptime_pct",
+            "avg_capture_bps",
+            "performance",
+            "halted",
+            "vol_bps",
+            "rolling_vol_bps",
+            "adaptive_spread_enabled",
+            "adaptive_spread_tier",
+            "effective_spread_bps",
+            "effective_refresh_bps",
+            "guard_enabled",
+            "guard_active",
+            "guard_side",
+            "external_divergence_bps",
+            "external_basis_bps",
+            "skew_shift_bps",
+            "fair_value",
+            "fair_value_deviation_bps",
+            "fair_value_capped",
+            "book_mid",
+            "microprice",
+            "book_imbalance",
+            "size_skew_enabled",
+            "size_skew_active",
+            "size_skew_add_side",
+            "size_skew_inventory_ratio",
+            "size_skew_add_qty",
+        ],
+    },
+    Event {
+        action: "place",
+        description: "A quote was placed",
+        fields: &[
+            "ts", "cycle", "mode", "symbol", "mark", "action", "side", "level", "price", "qty",
+        ],
+    },
+    Event {
+        action: "cancel",
+        description: "A resting quote was cancelled",
+        fields: &[
+            "ts", "cycle", "mode", "symbol", "mark", "action", "side", "level", "price", "reason",
+            "order_id",
+        ],
+    },
+    Event {
+        action: "hold",
+        description: "A resting quote was kept",
+        fields: &[
+            "ts",
+            "cycle",
+            "mode",
+            "symbol",
+            "mark",
+            "action",
+            "side",
+            "level",
+            "price",
+            "age_cycles",
+            "drift_bps",
+        ],
+    },
+    Event {
+        action: "fill",
+        description: "A maker order filled (paper or live)",
+        fields: &[
+            "ts",
+            "cycle",
+            "mode",
+            "symbol",
+            "action",
+            "origin",
+            "order_id",
+            "trade_id",
+            "trade_ts",
+            "side",
+            "price",
+            "qty",
+            "mark_at_fill",
+            "event_time_ms",
+            "role",
+            "fee_quote",
+            "rebate_quote",
+        ],
+    },
+    Event {
+        action: "skip",
+        description: "A cycle placed nothing because the book was unusable",
+        fields: &[
+            "ts",
+            "cycle",
+            "mode",
+            "symbol",
+            "action",
+            "mark",
+            "reason",
+            "divergence_bps",
+            "max_divergence_bps",
+        ],
+    },
+    Event {
+        action: "place_pending",
+        description: "A placement is waiting for its order response",
+        fields: &[
+            "ts", "cycle", "mode", "symbol", "action", "side", "level", "price", "detail",
+        ],
+    },
+    Event {
+        action: "place_rejected_async",
+        description: "The venue rejected a placement after it was sent",
+        fields: &[
+            "ts", "cycle", "mode", "symbol", "action", "side", "level", "price", "detail",
+        ],
+    },
+    Event {
+        action: "inventory_exit_submitted",
+        description: "A reduce-only exit order was sent to shed inventory",
+        fields: &[
+            "ts", "cycle", "mode", "symbol", "action", "side", "level", "price", "detail",
+        ],
+    },
+    Event {
+        action: "external_guard",
+        description: "The external price guard suppressed or released a side",
+        fields: &[
+            "ts",
+            "cycle",
+            "symbol",
+            "action",
+            "active",
+            "side",
+            "divergence_bps",
+        ],
+    },
+    Event {
+        action: "performance_summary",
+        description: "Session performance at shutdown",
+        fields: &[
+            "action",
+            "symbol",
+            "passive_fills",
+            "passive_qty",
+            "passive_cashflow_quote",
+            "passive_capture_bps",
+            "exit_fills",
+            "exit_qty",
+            "exit_cashflow_quote",
+            "gross_spread_quote",
+            "fee_quote",
+            "rebate_quote",
+            "execution_costs_unavailable",
+            "funding_quote",
+            "funding_available",
+            "net_pnl_complete",
+            "exit_cost_quote",
+            "inventory_mtm_change_quote",
+            "net_pnl_quote",
+            "position",
+            "markout_1s_bps",
+            "markout_5s_bps",
+            "markout_30s_bps",
+            "markout_1s_unavailable",
+            "markout_5s_unavailable",
+            "markout_30s_unavailable",
+            "time_weighted_uptime_pct",
+            "eligible_bid_qty_ms",
+            "eligible_ask_qty_ms",
+            "eligible_total_qty_ms",
+            "inventory_observed_ms",
+            "inventory_nonzero_ms",
+            "inventory_abs_qty_ms",
+            "inventory_avg_abs_qty",
+        ],
+    },
+    Event {
+        action: "account_event_lag",
+        description: "Delay between an account stream event and its arrival",
+        fields: &[
+            "action",
+            "symbol",
+            "cycle",
+            "channel",
+            "seq",
+            "event_time",
+            "event_time_ms",
+            "received_utc_ms",
+            "account_event_lag_ms",
+            "available",
+        ],
+    },
+    Event {
+        action: "order_latency",
+        description: "Timeline of one place or cancel request",
+        fields: &[
+            "action",
+            "request_id",
+            "kind",
+            "generation",
+            "cycle",
+            "symbol",
+            "side",
+            "level",
+            "order_id",
+            "market_source",
+            "recovery",
+            "intent_utc_ms",
+            "place_write_ms",
+            "place_ack_ms",
+            "place_effective_ms",
+            "cancel_write_ms",
+            "cancel_ack_ms",
+            "cancel_effective_ms",
+            "fill_after_cancel_ms",
+            "timeout_phase",
+            "timeout_ms",
+            "outcome",
+        ],
+    },
+    Event {
+        action: "order_latency_summary",
+        description: "Latency percentiles per request kind at shutdown",
+        fields: &[
+            "action",
+            "symbol",
+            "kind",
+            "requests",
+            "accepted",
+            "rejected",
+            "effective",
+            "timeout",
+            "invalidated",
+            "process_ended",
+            "pending",
+            "reject_rate",
+            "timeout_rate",
+            "write",
+            "ack",
+            "effective_latency",
+            "fill_after_cancel",
+            "write_p50_ms",
+            "write_p95_ms",
+            "write_p99_ms",
+            "ack_p50_ms",
+            "ack_p95_ms",
+            "ack_p99_ms",
+            "effective_latency_p50_ms",
+            "effective_latency_p95_ms",
+            "effective_latency_p99_ms",
+            "fill_after_cancel_p50_ms",
+            "fill_after_cancel_p95_ms",
+            "fill_after_cancel_p99_ms",
+        ],
+    },
+    Event {
+        action: "ledger_sync",
+        description: "Startup position and PnL baseline",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "starting_position",
+            "baseline_mark",
+            "pnl_baseline",
+            "historical_maker_orders",
+            "historical_maker_trades_ignored",
+            "history_window_seconds",
+            "history_order_limit",
+            "history_trade_limit",
+            "current_run_fills",
+        ],
+    },
+    Event {
+        action: "session_resumed",
+        description: "A saved session passed venue checks and was resumed",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "run_order_prefix",
+            "session_started_at",
+            "saved_at_ms",
+            "downtime_ms",
+            "cycle",
+            "current_run_fills",
+            "position",
+        ],
+    },
+    Event {
+        action: "config_reload",
+        description: "A maker TOML reload was applied, rejected, invalid, or a no-op",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "cycle",
+            "source",
+            "path",
+            "changes",
+            "restart_only_fields",
+            "error",
+        ],
+    },
+    Event {
+        action: "control",
+        description: "A control-socket command was applied, rejected, or answered (status)",
+        fields: &[
+            "ts", "symbol", "action", "event", "cycle", "command", "value", "controls", "error",
+        ],
+    },
+    Event {
+        action: "inventory_adopted",
+        description: "A non-flat starting position was adopted as inventory",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "starting_position",
+            "baseline_mark",
+            "pnl_baseline",
+        ],
+    },
+    Event {
+        action: "startup_rejected",
+        description: "The starting position exceeds max_position; the run refuses to start",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "position",
+            "max_position",
+            "message",
+        ],
+    },
+    Event {
+        action: "position_reconciliation",
+        description: "Expected and observed positions were compared",
+        fields: &[
+            "ts",
+            "symbol",
+            "cycle",
+            "action",
+            "event",
+            "cause",
+            "severity",
+            "message",
+            "expected_position",
+            "observed_position",
+        ],
+    },
+    Event {
+        action: "stop_loss",
+        description: "The session stop-loss triggered",
+        fields: &[
+            "ts",
+            "symbol",
+            "cycle",
+            "action",
+            "event",
+            "pnl",
+            "stop_loss",
+        ],
+    },
+    Event {
+        action: "maker_cleanup",
+        description: "Cancelling maker orders on shutdown",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "severity",
+            "remaining_maker_orders",
+            "attempt",
+            "max_attempts",
+            "message",
+        ],
+    },
+    Event {
+        action: "order_response_reconnect",
+        description: "The order response stream is reconnecting",
+        fields: &[
+            "ts",
+            "symbol",
+            "action",
+            "event",
+            "attempt",
+            "max_attempts",
+            "message",
+        ],
+    },
+    Event {
+        action: "lifecycle",
+        description: "Start, stop and restart notices",
+        fields: &["ts", "symbol", "action", "event", "message", "text"],
+    },
+    Event {
+        action: "risk_notification",
+        description: "A risk condition was detected or cleared",
+        fields: &[
+            "text",
+            "ts",
+            "symbol",
+            "cycle",
+            "action",
+            "kind",
+            "severity",
+            "event",
+            "message",
+            "request_id",
+            "request_kind",
+            "timeout_phase",
+            "age_ms",
+            "timeout_ms",
+            "recovery_target",
+            "position_before",
+            "position_after",
+            "position_delta",
+            "expected_position",
+            "observed_position",
+        ],
+    },
+    Event {
+        action: "alert",
+        description: "An alert rule fired or resolved",
+        fields: &[
+            "ts", "symbol", "action", "kind", "firing", "message", "text",
+        ],
+    },
+];
+
+/// Events of `standx maker replay`.
+pub(super) const REPLAY_EVENTS: &[Event] = &[
+    Event {
+        action: "replay_cycle",
+        description: "One replayed cycle and the actions it planned",
+        fields: &[
+            "action",
+            "symbol",
+            "git_sha",
+            "config_hash",
+            "seed",
+            "event_time_ms",
+            "cycle",
+            "halted",
+            "skip",
+            "rolling_vol_bps",
+            "adaptive_spread_enabled",
+            "adaptive_spread_tier",
+            "effective_spread_bps",
+            "effective_refresh_bps",
+            "size_skew_enabled",
+            "size_skew_active",
+            "size_skew_add_side",
+            "size_skew_inventory_ratio",
+            "size_skew_add_qty",
+            "actions",
+        ],
+    },
+    Event {
+        action: "replay_summary",
+        description: "Performance over the whole replay",
+        fields: &[
+            "action",
+            "schema_version",
+            "symbol",
+            "git_sha",
+            "config_hash",
+            "seed",
+            "strategy",
+            "cycles",
+            "passive_fills",
+            "passive_qty",
+            "passive_cashflow_quote",
+            "passive_capture_bps",
+            "exit_fills",
+            "exit_qty",
+            "exit_cashflow_quote",
+            "gross_spread_quote",
+            "fee_quote",
+            "rebate_quote",
+            "execution_costs_unavailable",
+            "funding_quote",
+            "funding_available",
+            "net_pnl_complete",
+            "exit_cost_quote",
+            "inventory_mtm_change_quote",
+            "net_pnl_quote",
+            "position",
+            "markouts",
+            "time_weighted_uptime_pct",
+            "observed_ms",
+            "two_sided_ms",
+            "eligible_bid_qty_ms",
+            "eligible_ask_qty_ms",
+            "eligible_total_qty_ms",
+            "inventory_observed_ms",
+            "inventory_nonzero_ms",
+            "inventory_abs_qty_ms",
+            "inventory_avg_abs_qty",
+        ],
+    },
+];
+
+/// Events of `standx maker backtest`.
+pub(super) const BACKTEST_EVENTS: &[Event] = &[Event {
+    action: "backtest_summary",
+    description: "Order flow and performance of a simulated session",
+    fields: &[
+        "action",
+        "symbol",
+        "start_time",
+        "end_time",
+        "final_mark",
+        "cycles",
+        "skipped_cycles",
+        "halted_cycles",
+        "places",
+        "cancels",
+        "post_only_rejects",
+        "passive_fills",
+        "partial_fills",
+        "exits",
+        "failed_exits",
+        "gaps",
+        "performance",
+    ],
+}];
+
+/// Events of `standx maker sweep`.
+pub(super) const SWEEP_EVENTS: &[Event] = &[
+    Event {
+        action: "sweep_candidate",
+        description: "One grid candidate, best first; rejected ones have no rank",
+        fields: &[
+            "action",
+            "rank",
+            "params",
+            "net_pnl_quote",
+            "markout_5s_bps",
+            "uptime_pct",
+            "inventory_pct",
+            "passive_fills",
+            "exit_fills",
+            "post_only_rejects",
+            "error",
+        ],
+    },
+    Event {
+        action: "sweep_summary",
+        description: "Grid size, outcome counts and the best candidate",
+        fields: &[
+            "action",
+            "symbol",
+            "rank_by",
+            "candidates",
+            "evaluated",
+            "rejected",
+            "best_params",
+            "results_out",
+            "best_out",
+        ],
+    },
+];
+
+/// Events of `standx maker ws-command-canary`.
+pub(super) const CANARY_EVENTS: &[Event] = &[Event {
+    action: "ws_command_canary",
+    description: "One stage of the WebSocket order-command canary",
+    fields: &[
+        "ts",
+        "action",
+        "event",
+        "symbol",
+        "client_order_id",
+        "request_id",
+        "order_id",
+        "response_code",
+        "response_message",
+        "quantity",
+        "price",
+        "position",
+    ],
+}];
+
+/// Type of a field shared by every event that carries it.
+fn envelope(field: &str) -> Value {
+    match field {
+        "ts" => json!({ "type": "string", "format": "date-time" }),
+        "symbol" | "event" | "severity" | "message" | "text" => json!({ "type": "string" }),
+        "cycle" => json!({ "type": "integer", "minimum": 0 }),
+        "mode" => json!({ "type": "string", "enum": ["live", "paper"] }),
+        _ => json!({}),
+    }
+}
+
+/// JSON Schema of one event line.
+pub(super) fn schema(event: &Event) -> Value {
+    let properties: Map<String, Value> = event
+        .fields
+        .iter()
+        .map(|field| {
+            let schema = match *field {
+                "action" => json!({ "const": event.action }),
+                other => envelope(other),
+            };
+            (field.to_string(), schema)
+        })
+        .collect();
+    json!({
+        "description": event.description,
+        "type": "object",
+        "properties": properties,
+        "required": ["action"],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    const SOURCES: &[&str] = &[
+        include_str!("../maker/backtest.rs"),
+        include_str!("../maker/canary.rs"),
+        include_str!("../maker/notify.rs"),
+        include_str!("../maker/output.rs"),
+        include_str!("../maker/recovery.rs"),
+        include_str!("../maker/replay.rs"),
+        include_str!("../maker/runtime/recovery_flow.rs"),
+        include_str!("../maker/sweep.rs"),
+    ];
+
+    /// Top-level keys of every `json!` object literal with a literal
+    /// `"action"`, grouped by action.
+    fn emitted() -> BTreeMap<String, Vec<String>> {
+        let mut found: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for source in SOURCES {
+            let mut rest = *source;
+            while let Some(at) = rest.find("\"action\": \"") {
+                let after = &rest[at + 11..];
+                let action = &after[..after.find('"').unwrap()];
+                let start = rest[..at].rfind("json!({").unwrap() + 6;
+                let keys = found.entry(action.to_string()).or_default();
+                let mut depth = 0;
+                let mut chars = rest[start..].char_indices();
+                while let Some((offset, c)) = chars.next() {
+                    match c {
+                        '{' | '[' | '(' => depth += 1,
+                        '}' | ']' | ')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        '"' => {
+                            let tail = &rest[start + offset + 1..];
+                            let end = tail.find('"').unwrap();
+                            if depth == 1 && tail[end + 1..].starts_with(':') {
+                                keys.push(tail[..end].to_string());
+                            }
+                            for _ in 0..=end {
+                                chars.next();
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                rest = &rest[at + 11..];
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn catalog_covers_every_emitted_action_and_field() {
+        let catalog: Vec<&Event> = MAKER_EVENTS
+            .iter()
+            .chain(REPLAY_EVENTS)
+            .chain(BACKTEST_EVENTS)
+            .chain(SWEEP_EVENTS)
+            .chain(CANARY_EVENTS)
+            .collect();
+        for (action, keys) in emitted() {
+            let event = catalog
+                .iter()
+                .find(|event| event.action == action)
+                .unwrap_or_else(|| panic!("event `{action}` missing from the schema catalog"));
+            for key in keys {
+                assert!(
+                    event.fields.contains(&key.as_str()),
+                    "field `{key}` of event `{action}` missing from the schema catalog"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn event_schema_types_the_envelope_and_pins_the_action() {
+        let schema = schema(&MAKER_EVENTS[1]);
+        assert_eq!(schema["properties"]["action"], json!({ "const": "place" }));
+        assert_eq!(
+            schema["properties"]["mode"]["enum"],
+            json!(["live", "paper"])
+        );
+        assert_eq!(schema["properties"]["qty"], json!({}));
+    }
+}
@@ -0,0 +1,314 @@
+//! Example values of the `standx-sdk` models, traced for their shape.
+//!
+//! Struct literals (rather than JSON fixtures) make a new model field a
+//! compile error here instead of a silently stale schema. Every `Option` is
+//! `Some`, every `Vec` has an element and every enum variant is traced at
+//! least once, so the definitions come out complete.
+
+use super::trace::{trace, Definitions};
+use standx_sdk::account_stream::{BalanceUpdate, OrderUpdate, PositionUpdate, TradeUpdate};
+use standx_sdk::models::*;
+
+fn s() -> String {
+    String::new()
+}
+
+pub(super) fn symbol_info() -> SymbolInfo {
+    SymbolInfo {
+        symbol: s(),
+        base_asset: s(),
+        quote_asset: s(),
+        base_decimals: 0,
+        price_tick_decimals: 0,
+        qty_tick_decimals: 0,
+        min_order_qty: s(),
+        def_leverage: s(),
+        max_leverage: s(),
+        maker_fee: s(),
+        taker_fee: s(),
+        status: s(),
+    }
+}
+
+pub(super) fn market_data() -> MarketData {
+    MarketData {
+        symbol: s(),
+        mark_price: s(),
+        index_price: s(),
+        last_price: s(),
+        volume_24h: s(),
+        high_24h: s(),
+        low_24h: s(),
+        funding_rate: s(),
+        change_24h_percent: s(),
+        next_funding_time: s(),
+    }
+}
+
+pub(super) fn price_data() -> PriceData {
+    PriceData {
+        symbol: s(),
+        mark_price: s(),
+        index_price: s(),
+        last_price: s(),
+        timestamp: s(),
+    }
+}
+
+pub(super) fn kline_data() -> KlineData {
+    KlineData {
+        symbol: Some(s()),
+        interval: Some(s()),
+        time: 0,
+        open: s(),
+        high: s(),
+        low: s(),
+        close: s(),
+        volume: 0.0,
+        volume_quote: 0.0,
+    }
+}
+
+pub(super) fn order_book() -> OrderBook {
+    OrderBook {
+        symbol: s(),
+        bids: vec![[s(), s()]],
+        asks: vec![[s(), s()]],
+        timestamp: s(),
+    }
+}
+
+pub(super) fn trade() -> Trade {
+    Trade {
+        id: 0,
+        time: s(),
+        price: s(),
+        qty: s(),
+        side: Some(s()),
+        is_buyer_taker: false,
+        fee_asset: Some(s()),
+        fee_qty: Some(s()),
+        pnl: Some(s()),
+        order_id: Some(0),
+        symbol: Some(s()),
+        value: Some(s()),
+    }
+}
+
+pub(super) fn kline() -> Kline {
+    Kline {
+        time: s(),
+        open: s(),
+        high: s(),
+        low: s(),
+        close: s(),
+        volume: s(),
+    }
+}
+
+pub(super) fn funding_rate() -> FundingRate {
+    FundingRate {
+        id: 0,
+        symbol: s(),
+        funding_rate: s(),
+        mark_price: s(),
+        index_price: s(),
+        premium: s(),
+        time: s(),
+        created_at: s(),
+        updated_at: s(),
+    }
+}
+
+pub(super) fn block_trade() -> BlockTrade {
+    BlockTrade {
+        id: 0,
+        symbol: s(),
+        side: s(),
+        price: s(),
+        qty: s(),
+        block_status: s(),
+        match_policy: s(),
+        maker_address: s(),
+        taker_info_list: vec![TakerInfo {
+            address: s(),
+            side: Some(s()),
+        }],
+        expire_time: 0,
+    }
+}
+
+pub(super) fn order() -> Order {
+    Order {
+        id: s(),
+        cl_ord_id: Some(s()),
+        symbol: s(),
+        side: OrderSide::Buy,
+        order_type: OrderType::Limit,
+        qty: s(),
+        fill_qty: s(),
+        price: s(),
+        status: OrderStatus::New,
+        created_at: s(),
+        updated_at: s(),
+    }
+}
+
+pub(super) fn position() -> Position {
+    Position {
+        id: 0,
+        symbol: s(),
+        side: Some(OrderSide::Buy),
+        qty: s(),
+        entry_price: s(),
+        entry_value: s(),
+        holding_margin: s(),
+        initial_margin: s(),
+        leverage: s(),
+        mark_price: s(),
+        margin_asset: s(),
+        margin_mode: s(),
+        position_value: s(),
+        realized_pnl: s(),
+        required_margin: s(),
+        status: s(),
+        upnl: s(),
+        roe: Some(s()),
+        time: s(),
+        created_at: s(),
+        updated_at: s(),
+        liq_price: Some(s()),
+        mmr: Some(s()),
+        user: s(),
+    }
+}
+
+pub(super) fn position_config() -> PositionConfig {
+    PositionConfig {
+        symbol: s(),
+        leverage: s(),
+        max_leverage: s(),
+        def_leverage: s(),
+        margin_mode: s(),
+    }
+}
+
+pub(super) fn balance() -> Balance {
+    Balance {
+        balance: s(),
+        cross_available: s(),
+        cross_balance: s(),
+        cross_margin: s(),
+        cross_upnl: s(),
+        equity: s(),
+        isolated_balance: s(),
+        isolated_upnl: s(),
+        locked: s(),
+        pnl_24h: s(),
+        pnl_freeze: s(),
+        upnl: s(),
+    }
+}
+
+pub(super) fn dashboard_snapshot() -> DashboardSnapshot {
+    DashboardSnapshot {
+        timestamp: s(),
+        account: Some(balance()),
+        positions: vec![position()],
+        total_realized_pnl: s(),
+        orders: vec![order()],
+        market: vec![market_data()],
+        trades: vec![trade()],
+        order_book: Some(order_book()),
+    }
+}
+
+pub(super) fn portfolio_snapshot() -> PortfolioSnapshot {
+    PortfolioSnapshot {
+        timestamp: s(),
+        total_value_usd: s(),
+        total_pnl_24h: s(),
+        total_pnl_realized: s(),
+        positions: vec![position()],
+    }
+}
+
+pub(super) fn order_update() -> OrderUpdate {
+    OrderUpdate {
+        seq: 0,
+        order_id: 0,
+        cl_ord_id: Some(s()),
+        symbol: s(),
+        side: OrderSide::Buy,
+        qty: s(),
+        fill_qty: s(),
+        fill_avg_price: s(),
+        price: s(),
+        status: OrderStatus::New,
+        reduce_only: false,
+        updated_at: s(),
+    }
+}
+
+pub(super) fn position_update() -> PositionUpdate {
+    PositionUpdate {
+        seq: 0,
+        id: 0,
+        symbol: s(),
+        side: Some(OrderSide::Buy),
+        qty: s(),
+        entry_price: s(),
+        realized_pnl: s(),
+        status: s(),
+        updated_at: s(),
+    }
+}
+
+pub(super) fn trade_update() -> TradeUpdate {
+    TradeUpdate {
+        seq: 0,
+        trade_id: 0,
+        order_id: 0,
+        symbol: s(),
+        side: OrderSide::Buy,
+        price: s(),
+        qty: s(),
+        trade_ts: s(),
+    }
+}
+
+pub(super) fn balance_update() -> BalanceUpdate {
+    BalanceUpdate {
+        seq: 0,
+        account_type: s(),
+        token: s(),
+        free: s(),
+        total: s(),
+        locked: s(),
+        occupied: s(),
+        updated_at: s(),
+    }
+}
+
+/// Trace every variant of the model enums so their definitions list all
+/// values, not just the ones the examples above happen to use.
+pub(super) fn trace_enums(definitions: &mut Definitions) {
+    for side in [OrderSide::Buy, OrderSide::Sell] {
+        trace(definitions, &side);
+    }
+    for order_type in [OrderType::Limit, OrderType::Market] {
+        trace(definitions, &order_type);
+    }
+    for status in [
+        OrderStatus::New,
+        OrderStatus::PartiallyFilled,
+        OrderStatus::Filled,
+        OrderStatus::Canceled,
+        OrderStatus::Rejected,
+        OrderStatus::Expired,
+        OrderStatus::Open,
+        OrderStatus::Untriggered,
+    ] {
+        trace(definitions, &status);
+    }
+}
@@ -0,0 +1,413 @@
+//! `standx schema` — a machine-readable description of the CLI.
+//!
+//! The document lists every command with its arguments (as clap parses
+//! them) and the JSON Schema of what it prints with `-o json`: documents
+//! are traced from the serde types in `standx_sdk::models` and the CLI's
+//! own report types, event streams from the maker event catalog.
+//!
+//! `schema_version` is bumped by hand when the document layout changes
+//! incompatibly; `fingerprint` is a hash of the command and output
+//! descriptions, so integrations can detect any change to the interface
+//! they were built against without diffing the document.
+
+mod events;
+mod examples;
+mod trace;
+
+pub(in crate::commands) use trace::{array_of, trace, Definitions};
+
+use super::tools::{typed_default, value_type};
+use crate::cli::{Cli, OutputFormat, SchemaArgs};
+use crate::output;
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, Command, CommandFactory};
+use events::{Event, BACKTEST_EVENTS, CANARY_EVENTS, MAKER_EVENTS, REPLAY_EVENTS, SWEEP_EVENTS};
+use serde_json::{json, Map, Value};
+
+/// Layout version of the schema document.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Handle `standx schema`
+pub async fn handle_schema(args: SchemaArgs, output_format: OutputFormat) -> Result<()> {
+    let document = document(&args.command)?;
+    match output_format {
+        OutputFormat::Quiet => println!("{}", document["fingerprint"].as_str().unwrap_or("")),
+        // The document is nested throughout; tables and CSV have nothing
+        // useful to show, so every other format prints it as JSON.
+        _ => println!("{}", output::format_json(&document)?),
+    }
+    Ok(())
+}
+
+/// The schema document, narrowed to the command at `path` when non-empty.
+pub(super) fn document(path: &[String]) -> Result<Value> {
+    let root = Cli::command();
+    let mut definitions = Definitions::new();
+    examples::trace_enums(&mut definitions);
+    let mut commands = Vec::new();
+    for command in visible_subcommands(&root) {
+        describe(command, &[], &mut definitions, &mut commands);
+    }
+    let global_arguments: Vec<Value> = root
+        .get_arguments()
+        .filter(|arg| arg.is_global_set() && !arg.is_hide_set())
+        .map(argument)
+        .collect();
+    let fingerprint = fingerprint(&json!([global_arguments, commands, definitions]));
+
+    if !path.is_empty() {
+        let prefix = path.join(" ");
+        commands.retain(|command| {
+            let name = command["command"].as_str().unwrap_or("");
+            name == prefix || name.starts_with(&format!("{prefix} "))
+        });
+        if commands.is_empty() {
+            bail!("unknown command '{prefix}'");
+        }
+    }
+
+    Ok(json!({
+        "schema_version": SCHEMA_VERSION,
+        "cli_version": env!("CARGO_PKG_VERSION"),
+        "fingerprint": fingerprint,
+        "global_arguments": global_arguments,
+        "commands": commands,
+        "definitions": definitions,
+    }))
+}
+
+fn visible_subcommands(command: &Command) -> impl Iterator<Item = &Command> {
+    command
+        .get_subcommands()
+        .filter(|subcommand| !subcommand.is_hide_set() && subcommand.get_name() != "help")
+}
+
+/// Append `command` and its descendants to `out`, parents first.
+fn describe(
+    command: &Command,
+    parent: &[&str],
+    definitions: &mut Definitions,
+    out: &mut Vec<Value>,
+) {
+    let path: Vec<&str> = parent
+        .iter()
+        .copied()
+        .chain(std::iter::once(command.get_name()))
+        .collect();
+    let subcommands: Vec<&Command> = visible_subcommands(command).collect();
+    let mut entry = Map::new();
+    entry.insert("command".into(), json!(path.join(" ")));
+    let aliases: Vec<&str> = command.get_visible_aliases().collect();
+    if !aliases.is_empty() {
+        entry.insert("aliases".into(), json!(aliases));
+    }
+    if let Some(about) = command.get_long_about().or_else(|| command.get_about()) {
+        entry.insert("description".into(), json!(about.to_string()));
+    }
+    let arguments: Vec<Value> = command
+        .get_arguments()
+        .filter(|arg| !arg.is_global_set() && !arg.is_hide_set())
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+        .map(argument)
+        .collect();
+    entry.insert("arguments".into(), json!(arguments));
+    // A command whose subcommand is optional (`portfolio`) also runs bare.
+    if subcommands.is_empty() || !command.is_subcommand_required_set() {
+        entry.insert("output".into(), output_of(&path, definitions));
+    }
+    if !subcommands.is_empty() {
+        let names: Vec<&str> = subcommands.iter().map(|sub| sub.get_name()).collect();
+        entry.insert("subcommands".into(), json!(names));
+    }
+    out.push(Value::Object(entry));
+    for subcommand in subcommands {
+        describe(subcommand, &path, definitions, out);
+    }
+}
+
+/// One argument as a shell user would pass it.
+fn argument(arg: &Arg) -> Value {
+    let kind = value_type(arg);
+    let mut entry = Map::new();
+    entry.insert("name".into(), json!(arg.get_id().as_str()));
+    if arg.is_positional() {
+        entry.insert("position".into(), json!(arg.get_index()));
+    }
+    if let Some(long) = arg.get_long() {
+        entry.insert("long".into(), json!(format!("--{long}")));
+    }
+    if let Some(short) = arg.get_short() {
+        entry.insert("short".into(), json!(format!("-{short}")));
+    }
+    entry.insert("type".into(), json!(kind));
+    entry.insert("required".into(), json!(arg.is_required_set()));
+    let multiple = matches!(arg.get_action(), ArgAction::Append)
+        || arg
+            .get_num_args()
+            .is_some_and(|range| range.max_values() > 1);
+    if multiple {
+        entry.insert("multiple".into(), json!(true));
+    }
+    if let Some(delimiter) = arg.get_value_delimiter() {
+        entry.insert("delimiter".into(), json!(delimiter.to_string()));
+    }
+    let values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|value| !value.is_hide_set())
+        .map(|value| value.get_name().to_string())
+        .collect();
+    if kind != "boolean" && !values.is_empty() {
+        entry.insert("enum".into(), json!(values));
+    }
+    if kind != "boolean" {
+        let defaults: Vec<Value> = arg
+            .get_default_values()
+            .iter()
+            .map(|value| typed_default(kind, &value.to_string_lossy()))
+            .collect();
+        match defaults.len() {
+            0 => {}
+            1 => {
+                entry.insert("default".into(), defaults[0].clone());
+            }
+            _ => {
+                entry.insert("default".into(), json!(defaults));
+            }
+        }
+    }
+    if let Some(env) = arg.get_env() {
+        entry.insert("env".into(), json!(env.to_string_lossy()));
+    }
+    if let Some(help) = arg.get_long_help().or_else(|| arg.get_help()) {
+        entry.insert("description".into(), json!(help.to_string()));
+    }
+    Value::Object(entry)
+}
+
+/// A single JSON document on stdout (repeated under `--watch`).
+fn document_output(schema: Value) -> Value {
+    json!({ "kind": "document", "schema": schema })
+}
+
+/// One JSON record per line, for as long as the command runs.
+fn event_output(schema: Value) -> Value {
+    json!({ "kind": "events", "schema": schema })
+}
+
+fn catalog(events: &[Event]) -> Value {
+    let schemas: Vec<Value> = events.iter().map(events::schema).collect();
+    event_output(json!({ "oneOf": schemas }))
+}
+
+/// What the leaf command at `path` prints with `-o json`.
+fn output_of(path: &[&str], defs: &mut Definitions) -> Value {
+    match path {
+        ["config", _] => document_output(json!({ "type": "object" })),
+        ["market", "symbols"] => document_output(array_of(defs, &examples::symbol_info())),
+        ["market", "ticker"] => document_output(trace(defs, &examples::market_data())),
+        ["market", "tickers"] => document_output(array_of(defs, &examples::market_data())),
+        ["market", "trades"] | ["trade", "history"] => {
+            document_output(array_of(defs, &examples::trade()))
+        }
+        ["market", "depth"] => document_output(trace(defs, &examples::order_book())),
+        ["market", "kline"] => document_output(array_of(defs, &examples::kline())),
+        ["market", "funding"] => document_output(array_of(defs, &examples::funding_rate())),
+        ["account", "balances"] => document_output(trace(defs, &examples::balance())),
+        ["account", "positions"] => document_output(array_of(defs, &examples::position())),
+        ["account", "orders" | "history"] => document_output(array_of(defs, &examples::order())),
+        ["leverage", _] => document_output(trace(defs, &examples::position_config())),
+        ["stream", "price"] => event_output(trace(defs, &examples::price_data())),
+        ["stream", "depth"] => event_output(trace(defs, &examples::order_book())),
+        ["stream", "trade"] => event_output(trace(defs, &examples::trade())),
+        ["stream", "kline"] => event_output(trace(defs, &examples::kline_data())),
+        ["stream", "order"] => event_output(trace(defs, &examples::order_update())),
+        ["stream", "position"] => event_output(trace(defs, &examples::position_update())),
+        ["stream", "balance"] => event_output(trace(defs, &examples::balance_update())),
+        ["stream", "fills"] => event_output(trace(defs, &examples::trade_update())),
+        ["dashboard"] => document_output(trace(defs, &examples::dashboard_snapshot())),
+        ["portfolio"] => document_output(trace(defs, &examples::portfolio_snapshot())),
+        ["portfolio", "record"] => event_output(super::portfolio::sample_schema(defs)),
+        ["portfolio", "history"] => document_output(super::portfolio::history_schema(defs)),
+        ["block", "list"] => document_output(array_of(defs, &examples::block_trade())),
+        ["block", "watch"] => event_output(trace(defs, &examples::block_trade())),
+        ["maker", "run"] => catalog(MAKER_EVENTS),
+        ["maker", "replay"] => catalog(REPLAY_EVENTS),
+        ["maker", "backtest"] => catalog(BACKTEST_EVENTS),
+        ["maker", "sweep"] => catalog(SWEEP_EVENTS),
+        ["maker", "ws-command-canary"] => catalog(CANARY_EVENTS),
+        ["maker", "ctl", command] => {
+            document_output(super::maker::ctl_output_schema(command, defs))
+        }
+        ["maker", "status"] => {
+            let status = super::maker::MakerStatus {
+                live_running: false,
+                lock_path: String::new(),
+                symbol: Some(String::new()),
+                maker_orders: vec![examples::order()],
+                positions: vec![examples::position()],
+            };
+            document_output(trace(defs, &status))
+        }
+        ["watch"] => match MAKER_EVENTS.iter().find(|event| event.action == "alert") {
+            Some(alert) => event_output(events::schema(alert)),
+            None => json!({ "kind": "text" }),
+        },
+        ["report", subcommand] => match super::report::output_schema(subcommand, defs) {
+            Some(schema) => document_output(schema),
+            None => json!({ "kind": "text" }),
+        },
+        ["data", subcommand] => match super::data::output_schema(subcommand, defs) {
+            Some(schema) => document_output(schema),
+            None => json!({ "kind": "text" }),
+        },
+        ["apply"] => document_output(super::plan::apply_output_schema(defs)),
+        ["audit", subcommand] => match super::audit::output_schema(subcommand, defs) {
+            Some(schema) => document_output(schema),
+            None => json!({ "kind": "text" }),
+        },
+        ["risk", subcommand] => match super::risk::output_schema(subcommand, defs) {
+            Some(schema) => document_output(schema),
+            None => json!({ "kind": "text" }),
+        },
+        ["schema"] => document_output(json!({ "type": "object" })),
+        _ => json!({ "kind": "text" }),
+    }
+}
+
+/// FNV-1a (64-bit) of the compact JSON encoding, as 16 hex digits.
+fn fingerprint(value: &Value) -> String {
+    let hash = value
+        .to_string()
+        .bytes()
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        });
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command<'a>(document: &'a Value, name: &str) -> &'a Value {
+        document["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|command| command["command"] == name)
+            .unwrap_or_else(|| panic!("no command `{name}`"))
+    }
+
+    #[test]
+    fn describes_arguments_as_clap_parses_them() {
+        let document = document(&[]).unwrap();
+        assert_eq!(document["schema_version"], SCHEMA_VERSION);
+
+        let create = command(&document, "order create");
+        let side = create["arguments"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|arg| arg["name"] == "side")
+            .unwrap();
+        assert_eq!(side["required"], true);
+
+        let funding = command(&document, "market funding");
+        let days = funding["arguments"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|arg| arg["name"] == "days")
+            .unwrap();
+        assert_eq!(days["type"], "integer");
+        assert_eq!(days["default"], 7);
+
+        let output = document["global_arguments"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|arg| arg["name"] == "output")
+            .unwrap();
+        assert_eq!(output["short"], "-o");
+        assert!(output["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("ndjson")));
+        assert_eq!(command(&document, "market")["aliases"], json!(["m"]));
+    }
+
+    #[test]
+    fn every_leaf_command_declares_its_output() {
+        let document = document(&[]).unwrap();
+        for command in document["commands"].as_array().unwrap() {
+            if command.get("subcommands").is_none() {
+                assert!(
+                    command["output"]["kind"].is_string(),
+                    "{} has no output",
+                    command["command"]
+                );
+            }
+        }
+        let ticker = command(&document, "market ticker");
+        assert_eq!(ticker["output"]["kind"], "document");
+        assert_eq!(
+            ticker["output"]["schema"],
+            json!({ "$ref": "#/definitions/MarketData" })
+        );
+        let market_data = &document["definitions"]["MarketData"];
+        assert!(market_data["properties"].get("high_price_24h").is_some());
+
+        let orders = &document["definitions"]["Order"];
+        assert_eq!(
+            orders["properties"]["side"],
+            json!({ "$ref": "#/definitions/OrderSide" })
+        );
+        assert_eq!(
+            document["definitions"]["OrderSide"]["enum"],
+            json!(["buy", "sell"])
+        );
+        assert_eq!(
+            document["definitions"]["OrderStatus"]["enum"]
+                .as_array()
+                .unwrap()
+                .len(),
+            8
+        );
+
+        let maker = command(&document, "maker run");
+        assert_eq!(maker["output"]["kind"], "events");
+        assert!(maker["output"]["schema"]["oneOf"].as_array().unwrap().len() > 10);
+        assert_eq!(command(&document, "order create")["output"]["kind"], "text");
+        assert_eq!(
+            command(&document, "maker ctl status")["output"]["schema"],
+            json!({ "$ref": "#/definitions/StatusReply" })
+        );
+        assert_eq!(
+            command(&document, "maker ctl widen")["output"]["schema"],
+            json!({ "$ref": "#/definitions/AppliedReply" })
+        );
+        assert!(document["definitions"]["StatusReply"]["properties"]
+            .get("wind_down")
+            .is_some());
+    }
+
+    #[test]
+    fn narrows_to_a_command_and_keeps_the_full_fingerprint() {
+        let full = document(&[]).unwrap();
+        let market = document(&["market".to_string()]).unwrap();
+        let names: Vec<&str> = market["commands"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|command| command["command"].as_str().unwrap())
+            .collect();
+        assert_eq!(names[0], "market");
+        assert!(names.iter().all(|name| name.starts_with("market")));
+        assert!(names.contains(&"market ticker"));
+        assert_eq!(market["fingerprint"], full["fingerprint"]);
+        assert_eq!(full["fingerprint"].as_str().unwrap().len(), 16);
+
+        assert!(document(&["nope".to_string()]).is_err());
+    }
+}
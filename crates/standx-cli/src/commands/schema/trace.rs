@@ -0,0 +1,514 @@
+//! JSON Schema derived from `Serialize` implementations.
+//!
+//! [`trace`] serializes an example value through a [`Serializer`] that
+//! records shapes instead of data, so field names, renames, skipped fields
+//! and nesting all come from the serde derive. Named structs and unit enums
+//! become entries in [`Definitions`] referenced by `$ref`, which lets
+//! several examples of one enum accumulate its full variant list.
+//!
+//! Example values only matter for their shape: an `Option` must be `Some`
+//! to reveal its inner type (it is still marked nullable) and a sequence
+//! needs one element.
+
+use serde::ser::{self, Serialize, Serializer};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Named schemas collected while tracing, keyed by Rust type name.
+pub(in crate::commands) type Definitions = BTreeMap<String, Value>;
+
+/// Schema of `example`, registering named types in `definitions`.
+pub(in crate::commands) fn trace<T: Serialize + ?Sized>(
+    definitions: &mut Definitions,
+    example: &T,
+) -> Value {
+    example
+        .serialize(Tracer { definitions })
+        .unwrap_or_else(|error| json!({ "description": error.0 }))
+}
+
+/// Schema of an array of `example`.
+pub(in crate::commands) fn array_of<T: Serialize>(
+    definitions: &mut Definitions,
+    example: &T,
+) -> Value {
+    json!({ "type": "array", "items": trace(definitions, example) })
+}
+
+#[derive(Debug)]
+pub(super) struct TraceError(String);
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+impl ser::Error for TraceError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/definitions/{name}") })
+}
+
+fn nullable(schema: Value) -> Value {
+    match schema.get("type").and_then(Value::as_str) {
+        Some(kind) if schema.as_object().is_some_and(|o| o.len() == 1) => {
+            json!({ "type": [kind, "null"] })
+        }
+        _ => json!({ "anyOf": [schema, { "type": "null" }] }),
+    }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    let null = json!("null");
+    match (schema.get("type"), schema.get("anyOf")) {
+        (Some(Value::Array(kinds)), _) => kinds.contains(&null),
+        (Some(kind), _) => *kind == null,
+        (_, Some(Value::Array(options))) => options.iter().any(is_nullable),
+        _ => false,
+    }
+}
+
+/// Items schema of a sequence: the first element's, or any when empty.
+fn items(elements: Vec<Value>) -> Value {
+    elements.into_iter().next().unwrap_or_else(|| json!({}))
+}
+
+/// Record a named struct, merging with any earlier trace of the same type
+/// (internally tagged enums trace one variant at a time): properties are
+/// unioned and only fields present every time stay required.
+fn define_struct(definitions: &mut Definitions, name: &str, fields: Vec<(String, Value)>) {
+    let required: Vec<Value> = fields
+        .iter()
+        .filter(|(_, schema)| {
+            !is_nullable(schema) && schema.as_object().is_some_and(|o| !o.is_empty())
+        })
+        .map(|(key, _)| json!(key))
+        .collect();
+    let properties: Map<String, Value> = fields.into_iter().collect();
+    match definitions.get_mut(name) {
+        Some(existing) => {
+            if let Some(Value::Object(known)) = existing.get_mut("properties") {
+                for (key, schema) in properties {
+                    known.entry(key).or_insert(schema);
+                }
+            }
+            if let Some(Value::Array(known)) = existing.get_mut("required") {
+                known.retain(|key| required.contains(key));
+            }
+        }
+        None => {
+            definitions.insert(
+                name.to_string(),
+                json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                }),
+            );
+        }
+    }
+}
+
+fn define_variant(definitions: &mut Definitions, name: &str, variant: &str) {
+    let entry = definitions
+        .entry(name.to_string())
+        .or_insert_with(|| json!({ "type": "string", "enum": [] }));
+    if let Some(Value::Array(variants)) = entry.get_mut("enum") {
+        if !variants.contains(&json!(variant)) {
+            variants.push(json!(variant));
+        }
+    }
+}
+
+struct Tracer<'a> {
+    definitions: &'a mut Definitions,
+}
+
+impl<'a> Serializer for Tracer<'a> {
+    type Ok = Value;
+    type Error = TraceError;
+    type SerializeSeq = Seq<'a>;
+    type SerializeTuple = Seq<'a>;
+    type SerializeTupleStruct = Seq<'a>;
+    type SerializeTupleVariant = Variant<Seq<'a>>;
+    type SerializeMap = MapTracer<'a>;
+    type SerializeStruct = Struct<'a>;
+    type SerializeStructVariant = Variant<Struct<'a>>;
+
+    fn serialize_bool(self, _: bool) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "boolean" }))
+    }
+    fn serialize_i8(self, _: i8) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer" }))
+    }
+    fn serialize_i16(self, _: i16) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer" }))
+    }
+    fn serialize_i32(self, _: i32) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer" }))
+    }
+    fn serialize_i64(self, _: i64) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer" }))
+    }
+    fn serialize_u8(self, _: u8) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer", "minimum": 0 }))
+    }
+    fn serialize_u16(self, _: u16) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer", "minimum": 0 }))
+    }
+    fn serialize_u32(self, _: u32) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer", "minimum": 0 }))
+    }
+    fn serialize_u64(self, _: u64) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "integer", "minimum": 0 }))
+    }
+    fn serialize_f32(self, _: f32) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "number" }))
+    }
+    fn serialize_f64(self, _: f64) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "number" }))
+    }
+    fn serialize_char(self, _: char) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "string" }))
+    }
+    fn serialize_str(self, _: &str) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "string" }))
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "array", "items": { "type": "integer" } }))
+    }
+    fn serialize_none(self) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "null" }))
+    }
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Value, TraceError> {
+        Ok(nullable(value.serialize(self)?))
+    }
+    fn serialize_unit(self) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "null" }))
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "null" }))
+    }
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Value, TraceError> {
+        define_variant(self.definitions, name, variant);
+        Ok(reference(name))
+    }
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Value, TraceError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value, TraceError> {
+        let inner = value.serialize(self)?;
+        Ok(json!({
+            "type": "object",
+            "properties": { variant: inner },
+            "required": [variant],
+        }))
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Seq<'a>, TraceError> {
+        Ok(Seq::new(self.definitions, None))
+    }
+    fn serialize_tuple(self, len: usize) -> Result<Seq<'a>, TraceError> {
+        Ok(Seq::new(self.definitions, Some(len)))
+    }
+    fn serialize_tuple_struct(self, _: &'static str, len: usize) -> Result<Seq<'a>, TraceError> {
+        Ok(Seq::new(self.definitions, Some(len)))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Variant<Seq<'a>>, TraceError> {
+        Ok(Variant {
+            variant,
+            inner: Seq::new(self.definitions, Some(len)),
+        })
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<MapTracer<'a>, TraceError> {
+        Ok(MapTracer {
+            definitions: self.definitions,
+            values: Vec::new(),
+        })
+    }
+    fn serialize_struct(self, name: &'static str, _: usize) -> Result<Struct<'a>, TraceError> {
+        Ok(Struct {
+            definitions: self.definitions,
+            name: Some(name),
+            fields: Vec::new(),
+        })
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Variant<Struct<'a>>, TraceError> {
+        Ok(Variant {
+            variant,
+            inner: Struct {
+                definitions: self.definitions,
+                name: None,
+                fields: Vec::new(),
+            },
+        })
+    }
+}
+
+pub(super) struct Seq<'a> {
+    definitions: &'a mut Definitions,
+    len: Option<usize>,
+    elements: Vec<Value>,
+}
+
+impl<'a> Seq<'a> {
+    fn new(definitions: &'a mut Definitions, len: Option<usize>) -> Self {
+        Self {
+            definitions,
+            len,
+            elements: Vec::new(),
+        }
+    }
+
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        let schema = value.serialize(Tracer {
+            definitions: self.definitions,
+        })?;
+        self.elements.push(schema);
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        let mut schema = json!({ "type": "array", "items": items(self.elements) });
+        if let Some(len) = self.len {
+            schema["minItems"] = json!(len);
+            schema["maxItems"] = json!(len);
+        }
+        schema
+    }
+}
+
+impl ser::SerializeSeq for Seq<'_> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTuple for Seq<'_> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(self.finish())
+    }
+}
+
+impl ser::SerializeTupleStruct for Seq<'_> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        self.push(value)
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(self.finish())
+    }
+}
+
+pub(super) struct MapTracer<'a> {
+    definitions: &'a mut Definitions,
+    values: Vec<Value>,
+}
+
+impl ser::SerializeMap for MapTracer<'_> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, _: &T) -> Result<(), TraceError> {
+        Ok(())
+    }
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        let schema = value.serialize(Tracer {
+            definitions: self.definitions,
+        })?;
+        self.values.push(schema);
+        Ok(())
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(json!({ "type": "object", "additionalProperties": items(self.values) }))
+    }
+}
+
+pub(super) struct Struct<'a> {
+    definitions: &'a mut Definitions,
+    /// `None` for enum struct variants, which are described inline.
+    name: Option<&'static str>,
+    fields: Vec<(String, Value)>,
+}
+
+impl Struct<'_> {
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), TraceError> {
+        let schema = value.serialize(Tracer {
+            definitions: self.definitions,
+        })?;
+        self.fields.push((key.to_string(), schema));
+        Ok(())
+    }
+
+    fn finish(self) -> Value {
+        match self.name {
+            Some(name) => {
+                define_struct(self.definitions, name, self.fields);
+                reference(name)
+            }
+            None => {
+                let mut scratch = Definitions::new();
+                define_struct(&mut scratch, "", self.fields);
+                scratch.remove("").unwrap_or_default()
+            }
+        }
+    }
+}
+
+impl ser::SerializeStruct for Struct<'_> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TraceError> {
+        self.field(key, value)
+    }
+    fn skip_field(&mut self, key: &'static str) -> Result<(), TraceError> {
+        // Skipped only when empty (`skip_serializing_if`): present at other
+        // times, but its shape is unknown from this example.
+        self.fields.push((key.to_string(), json!({})));
+        Ok(())
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(self.finish())
+    }
+}
+
+/// An externally tagged enum variant: `{ "<variant>": <inner> }`.
+pub(super) struct Variant<T> {
+    variant: &'static str,
+    inner: T,
+}
+
+impl<T> Variant<T> {
+    fn wrap(variant: &str, inner: Value) -> Value {
+        json!({
+            "type": "object",
+            "properties": { variant: inner },
+            "required": [variant],
+        })
+    }
+}
+
+impl ser::SerializeTupleVariant for Variant<Seq<'_>> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), TraceError> {
+        self.inner.push(value)
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(Self::wrap(self.variant, self.inner.finish()))
+    }
+}
+
+impl ser::SerializeStructVariant for Variant<Struct<'_>> {
+    type Ok = Value;
+    type Error = TraceError;
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), TraceError> {
+        self.inner.field(key, value)
+    }
+    fn end(self) -> Result<Value, TraceError> {
+        Ok(Self::wrap(self.variant, self.inner.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "lowercase")]
+    enum Side {
+        Buy,
+        Sell,
+    }
+
+    #[derive(Serialize)]
+    struct Fill {
+        side: Side,
+        #[serde(rename = "px")]
+        price: String,
+        qty: f64,
+        fee: Option<u64>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        levels: Vec<[String; 2]>,
+    }
+
+    #[test]
+    fn traces_structs_into_definitions_with_nullability_and_enums() {
+        let mut definitions = Definitions::new();
+        let fill = Fill {
+            side: Side::Buy,
+            price: String::new(),
+            qty: 0.0,
+            fee: Some(0),
+            tags: Vec::new(),
+            levels: vec![[String::new(), String::new()]],
+        };
+        let schema = array_of(&mut definitions, &fill);
+        trace(&mut definitions, &Side::Sell);
+
+        assert_eq!(schema["items"], json!({ "$ref": "#/definitions/Fill" }));
+        let fill = &definitions["Fill"];
+        assert_eq!(fill["properties"]["px"], json!({ "type": "string" }));
+        assert_eq!(
+            fill["properties"]["fee"],
+            json!({ "anyOf": [{ "type": "integer", "minimum": 0 }, { "type": "null" }] })
+        );
+        assert_eq!(fill["properties"]["levels"]["items"]["maxItems"], json!(2));
+        assert_eq!(fill["required"], json!(["side", "px", "qty", "levels"]));
+        assert_eq!(definitions["Side"]["enum"], json!(["buy", "sell"]));
+    }
+}
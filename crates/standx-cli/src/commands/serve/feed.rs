@@ -0,0 +1,392 @@
+//! The warm WebSocket session behind `/v1/live` and `/v1/stream`.
+//!
+//! One public socket carries `price`, `depth_book` and `public_trade` for
+//! every `--symbols` entry, and (when credentials exist) one account stream
+//! carries order, position, fill and balance updates. Both reconnect on
+//! their own; every event is fanned out to stream clients through a
+//! broadcast channel, and the latest price and book per symbol are kept for
+//! `/v1/live` and for priming new streams.
+
+use serde_json::{json, Map, Value};
+use standx_sdk::account_stream::{AccountChannel, AccountEvent, AccountStream};
+use standx_sdk::websocket::{StandXWebSocket, WsMessage};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// How long a dropped stream waits before reconnecting.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Events a slow stream client may fall behind by before it is told it
+/// skipped some.
+const BROADCAST_CAPACITY: usize = 4096;
+
+/// Stream channels, as named in `?channels=` and SSE `event:` lines.
+pub(super) const CHANNELS: &[&str] = &[
+    "price", "depth", "trade", "order", "position", "fill", "balance", "status",
+];
+
+/// One update fanned out to stream clients.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Event {
+    pub channel: &'static str,
+    pub symbol: Option<String>,
+    pub data: Value,
+}
+
+impl Event {
+    /// The event as one SSE message.
+    pub(super) fn to_sse(&self) -> String {
+        format!("event: {}\ndata: {}\n\n", self.channel, self.data)
+    }
+}
+
+/// Shared state of the warm session.
+pub(super) struct Hub {
+    tx: broadcast::Sender<Arc<Event>>,
+    /// symbol → { "price": …, "depth": … }
+    latest: Mutex<BTreeMap<String, Map<String, Value>>>,
+    pub market_connected: AtomicBool,
+    pub account_connected: AtomicBool,
+    /// Credentials were found, so the account stream runs.
+    pub authenticated: bool,
+}
+
+impl Hub {
+    pub(super) fn new(authenticated: bool) -> Self {
+        Self {
+            tx: broadcast::channel(BROADCAST_CAPACITY).0,
+            latest: Mutex::new(BTreeMap::new()),
+            market_connected: AtomicBool::new(false),
+            account_connected: AtomicBool::new(false),
+            authenticated,
+        }
+    }
+
+    pub(super) fn subscribe(&self) -> broadcast::Receiver<Arc<Event>> {
+        self.tx.subscribe()
+    }
+
+    fn publish(&self, event: Event) {
+        if let (Some(symbol), "price" | "depth") = (&event.symbol, event.channel) {
+            self.latest
+                .lock()
+                .expect("hub lock poisoned")
+                .entry(symbol.clone())
+                .or_default()
+                .insert(event.channel.to_string(), event.data.clone());
+        }
+        // No receivers is fine: nobody is streaming right now.
+        let _ = self.tx.send(Arc::new(event));
+    }
+
+    /// Latest price and book per symbol, as cached events.
+    pub(super) fn latest_events(&self) -> Vec<Event> {
+        let latest = self.latest.lock().expect("hub lock poisoned");
+        let mut events = Vec::new();
+        for (symbol, channels) in latest.iter() {
+            for channel in ["price", "depth"] {
+                if let Some(data) = channels.get(channel) {
+                    events.push(Event {
+                        channel,
+                        symbol: Some(symbol.clone()),
+                        data: data.clone(),
+                    });
+                }
+            }
+        }
+        events
+    }
+
+    /// `/v1/live`: connection state plus the latest price and book per symbol.
+    pub(super) fn snapshot(&self) -> Value {
+        let latest = self.latest.lock().expect("hub lock poisoned");
+        json!({
+            "market_connected": self.market_connected.load(Ordering::Relaxed),
+            "account_connected": self.account_connected.load(Ordering::Relaxed),
+            "symbols": *latest,
+        })
+    }
+
+    fn status(&self, stream: &str, connected: bool, reason: Option<String>) {
+        let flag = match stream {
+            "market" => &self.market_connected,
+            _ => &self.account_connected,
+        };
+        flag.store(connected, Ordering::Relaxed);
+        self.publish(Event {
+            channel: "status",
+            symbol: None,
+            data: json!({ "stream": stream, "connected": connected, "reason": reason }),
+        });
+    }
+
+    fn apply_market(&self, message: WsMessage) {
+        let event = |channel, symbol: Option<String>, data: serde_json::Result<Value>| {
+            if let Ok(data) = data {
+                self.publish(Event {
+                    channel,
+                    symbol: symbol.filter(|symbol| !symbol.is_empty()),
+                    data,
+                });
+            }
+        };
+        match message {
+            WsMessage::Connected => self.status("market", true, None),
+            WsMessage::Disconnected => self.status("market", false, None),
+            WsMessage::Error(error) => self.status("market", false, Some(error)),
+            WsMessage::Price(update) => event(
+                "price",
+                Some(update.data.symbol.clone()),
+                serde_json::to_value(&update.data),
+            ),
+            WsMessage::Depth(update) => event(
+                "depth",
+                Some(update.data.symbol.clone()),
+                serde_json::to_value(&update.data),
+            ),
+            WsMessage::Trade(trade) => {
+                event("trade", trade.symbol.clone(), serde_json::to_value(&trade))
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_account(&self, event: AccountEvent) {
+        let publish = |channel, symbol: Option<String>, data: serde_json::Result<Value>| {
+            if let Ok(data) = data {
+                self.publish(Event {
+                    channel,
+                    symbol,
+                    data,
+                });
+            }
+        };
+        match event {
+            AccountEvent::Connected { .. } => self.status("account", true, None),
+            AccountEvent::Disconnected { reason } | AccountEvent::Error { reason } => {
+                self.status("account", false, Some(reason))
+            }
+            AccountEvent::Order(update) => publish(
+                "order",
+                Some(update.symbol.clone()),
+                serde_json::to_value(&update),
+            ),
+            AccountEvent::Position(update) => publish(
+                "position",
+                Some(update.symbol.clone()),
+                serde_json::to_value(&update),
+            ),
+            AccountEvent::Trade(fill) => publish(
+                "fill",
+                Some(fill.symbol.clone()),
+                serde_json::to_value(&fill),
+            ),
+            AccountEvent::Balance(balance) => {
+                publish("balance", None, serde_json::to_value(&balance))
+            }
+        }
+    }
+}
+
+/// Which events one stream client asked for; an empty list means all.
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct Filter {
+    pub channels: Vec<String>,
+    pub symbols: Vec<String>,
+}
+
+impl Filter {
+    /// From `?channels=price,depth&symbols=BTC-USD`.
+    pub(super) fn from_pairs(pairs: &[(String, String)]) -> Result<Self, String> {
+        let list = |key: &str, upper: bool| -> Vec<String> {
+            pairs
+                .iter()
+                .filter(|(name, _)| name == key)
+                .flat_map(|(_, value)| value.split(','))
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(|value| {
+                    if upper {
+                        value.to_uppercase()
+                    } else {
+                        value.to_lowercase()
+                    }
+                })
+                .collect()
+        };
+        if let Some((unknown, _)) = pairs
+            .iter()
+            .find(|(name, _)| name != "channels" && name != "symbols")
+        {
+            return Err(format!("unknown parameter '{unknown}'"));
+        }
+        let filter = Self {
+            channels: list("channels", false),
+            symbols: list("symbols", true),
+        };
+        if let Some(unknown) = filter
+            .channels
+            .iter()
+            .find(|channel| !CHANNELS.contains(&channel.as_str()))
+        {
+            return Err(format!(
+                "unknown channel '{unknown}' (expected {})",
+                CHANNELS.join(", ")
+            ));
+        }
+        Ok(filter)
+    }
+
+    /// Events without a symbol (status, balance) pass any symbol filter.
+    pub(super) fn matches(&self, event: &Event) -> bool {
+        let channel = self.channels.is_empty()
+            || self.channels.iter().any(|channel| channel == event.channel);
+        let symbol = match &event.symbol {
+            Some(symbol) => {
+                self.symbols.is_empty()
+                    || self
+                        .symbols
+                        .iter()
+                        .any(|wanted| wanted.eq_ignore_ascii_case(symbol))
+            }
+            None => true,
+        };
+        channel && symbol
+    }
+}
+
+/// Start the market feed and, when authenticated, the account feed.
+pub(super) fn spawn(hub: Arc<Hub>, symbols: Vec<String>, verbose: bool) -> Vec<JoinHandle<()>> {
+    let mut tasks = vec![tokio::spawn(run_market_feed(hub.clone(), symbols, verbose))];
+    if hub.authenticated {
+        tasks.push(tokio::spawn(run_account_feed(hub)));
+    }
+    tasks
+}
+
+/// Public stream for every symbol, rebuilt whenever the managed connection
+/// gives up.
+async fn run_market_feed(hub: Arc<Hub>, symbols: Vec<String>, verbose: bool) {
+    loop {
+        let session = async {
+            let ws = StandXWebSocket::without_auth_with_verbose(verbose)?;
+            for symbol in &symbols {
+                for channel in ["price", "depth_book", "public_trade"] {
+                    ws.subscribe(channel, Some(symbol)).await?;
+                }
+            }
+            let (mut rx, _handle) = ws.connect_managed().await?;
+            while let Some(message) = rx.recv().await {
+                hub.apply_market(message);
+            }
+            standx_sdk::Result::Ok(())
+        };
+        let reason = session.await.err().map(|error| error.to_string());
+        hub.status("market", false, reason);
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Authenticated order/position/fill/balance stream, reconnected with a
+/// fresh epoch after every drop.
+async fn run_account_feed(hub: Arc<Hub>) {
+    let mut epoch = 0;
+    loop {
+        epoch += 1;
+        let connected = match AccountStream::new(epoch) {
+            Ok(stream) => {
+                stream
+                    .connect(&[
+                        AccountChannel::Order,
+                        AccountChannel::Position,
+                        AccountChannel::Trade,
+                        AccountChannel::Balance,
+                    ])
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        match connected {
+            Ok((mut rx, _health, _handle)) => {
+                while let Some(event) = rx.recv().await {
+                    hub.apply_account(event);
+                }
+            }
+            Err(error) => hub.status("account", false, Some(error.to_string())),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use standx_sdk::models::PriceData;
+    use standx_sdk::websocket::WsMarketUpdate;
+
+    fn pairs(query: &[(&str, &str)]) -> Vec<(String, String)> {
+        query
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn filter_selects_channels_and_symbols() {
+        let filter = Filter::from_pairs(&pairs(&[
+            ("channels", "price,fill"),
+            ("symbols", "btc-usd"),
+        ]))
+        .unwrap();
+        let event = |channel, symbol: Option<&str>| Event {
+            channel,
+            symbol: symbol.map(str::to_string),
+            data: Value::Null,
+        };
+        assert!(filter.matches(&event("price", Some("BTC-USD"))));
+        assert!(!filter.matches(&event("price", Some("ETH-USD"))));
+        assert!(!filter.matches(&event("depth", Some("BTC-USD"))));
+        assert!(Filter::default().matches(&event("status", None)));
+
+        assert!(Filter::from_pairs(&pairs(&[("channels", "orderbook")])).is_err());
+        assert!(Filter::from_pairs(&pairs(&[("symbol", "BTC-USD")])).is_err());
+    }
+
+    #[tokio::test]
+    async fn hub_fans_out_and_keeps_the_latest_price() {
+        let hub = Hub::new(false);
+        let mut rx = hub.subscribe();
+        let price: PriceData = serde_json::from_value(json!({
+            "symbol": "BTC-USD",
+            "mark_price": "100",
+            "index_price": "100",
+            "last_price": "101",
+            "timestamp": "2026-10-18T00:00:00Z",
+        }))
+        .unwrap();
+        hub.apply_market(WsMessage::Connected);
+        hub.apply_market(WsMessage::Price(WsMarketUpdate {
+            data: price,
+            seq: None,
+            server_time: None,
+            envelope_time: None,
+            payload_time: None,
+            received_at: std::time::Instant::now(),
+        }));
+
+        let status = rx.recv().await.unwrap();
+        assert_eq!(status.channel, "status");
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.channel, "price");
+        assert!(event.to_sse().starts_with("event: price\ndata: {"));
+
+        let snapshot = hub.snapshot();
+        assert_eq!(snapshot["market_connected"], true);
+        assert_eq!(snapshot["symbols"]["BTC-USD"]["price"]["last_price"], "101");
+        assert_eq!(hub.latest_events().len(), 1);
+    }
+}
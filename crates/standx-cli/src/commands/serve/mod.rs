@@ -0,0 +1,483 @@
+//! `standx serve` — local HTTP API over one warm session
+//!
+//! Keeps a single `StandXClient` and WebSocket session alive so local tools
+//! can query and trade without spawning a process (and re-authenticating)
+//! per command. Endpoints mirror the CLI: `/v1/<command>/<subcommand>` runs
+//! the same commands `standx mcp` exposes as tools, with arguments from the
+//! query string (GET) or a JSON object body (POST), validated by the CLI
+//! parser itself.
+//!
+//! | Route                      | Purpose                                   |
+//! |----------------------------|-------------------------------------------|
+//! | `GET /healthz`             | liveness and stream state (no auth)       |
+//! | `GET /v1/commands`         | every endpoint with its argument schema   |
+//! | `GET /v1/live`             | latest price and book per `--symbols`     |
+//! | `GET /v1/stream`           | server-sent events from the warm session  |
+//! | `GET /v1/market/ticker?…`  | read commands (also accept POST)          |
+//! | `POST /v1/order/create`    | order commands (POST only)                |
+//!
+//! Everything but `/healthz` needs `Authorization: Bearer <token>`. Order
+//! endpoints follow the same [`Policy`] as the MCP tools: refused in the
+//! `read-only` profile, previewed under `--dry-run`, and otherwise run only
+//! with `"confirm": true` unless started with `--yes`.
+
+mod feed;
+
+use super::tools::{self, Access, Policy, Prepared, Refusal, Tool};
+use super::util::shutdown_signal;
+use crate::cli::ServeArgs;
+use crate::config::Config;
+use anyhow::{Context, Result};
+use feed::{Filter, Hub};
+use futures::stream::{self, StreamExt};
+use http_body_util::combinators::UnsyncBoxBody;
+use http_body_util::{BodyExt, Full, Limited, StreamBody};
+use hyper::body::{Bytes, Frame, Incoming};
+use hyper::header::{self, HeaderValue};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Map, Value};
+use standx_sdk::account_stream::AccountStream;
+use standx_sdk::client::StandXClient;
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast::error::RecvError;
+
+type Body = UnsyncBoxBody<Bytes, Infallible>;
+
+/// Largest request body accepted.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Comment sent on idle streams so proxies and clients keep them open.
+const KEEPALIVE: Duration = Duration::from_secs(15);
+
+/// Handle the serve command
+pub async fn handle_serve(
+    args: ServeArgs,
+    dry_run: bool,
+    auto_confirm: bool,
+    verbose: bool,
+) -> Result<()> {
+    let token = match args.token.filter(|token| !token.trim().is_empty()) {
+        Some(token) => token,
+        None => {
+            let path = token_path();
+            let token = load_or_create_token(&path)?;
+            eprintln!("serve: bearer token in {}", path.display());
+            token
+        }
+    };
+    let symbols: Vec<String> = args
+        .symbols
+        .iter()
+        .map(|symbol| symbol.trim().to_uppercase())
+        .filter(|symbol| !symbol.is_empty())
+        .collect();
+
+    let hub = Arc::new(Hub::new(AccountStream::new(0).is_ok()));
+    let state = Arc::new(State {
+        policy: Policy::new(args.profile, dry_run, auto_confirm),
+        token,
+        client: StandXClient::new()?,
+        hub: hub.clone(),
+    });
+    let listener = TcpListener::bind(args.listen)
+        .await
+        .with_context(|| format!("failed to listen on {}", args.listen))?;
+    if !args.listen.ip().is_loopback() {
+        eprintln!(
+            "serve: ⚠️  listening on {} — anyone who can reach it and holds the token can use \
+             this account",
+            args.listen
+        );
+    }
+    eprintln!(
+        "serve: http://{} ({} profile{}, streaming {})",
+        args.listen,
+        tools::profile_name(args.profile),
+        if dry_run { ", dry run" } else { "" },
+        symbols.join(",")
+    );
+    let feeds = feed::spawn(hub, symbols, verbose);
+    // Created once, so a signal between accepts is not lost.
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let result = loop {
+        let (stream, peer) = tokio::select! {
+            _ = &mut shutdown => break Ok(()),
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(error) => {
+                    tracing::warn!("serve: accept failed: {error}");
+                    continue;
+                }
+            },
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let state = state.clone();
+                async move { Ok::<_, Infallible>(state.handle(request).await) }
+            });
+            if let Err(error) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::debug!("serve: connection from {peer}: {error}");
+            }
+        });
+    };
+    for task in feeds {
+        task.abort();
+    }
+    result
+}
+
+/// Where a generated token is kept, so restarts keep the same one.
+fn token_path() -> PathBuf {
+    Config::default_config_dir().join("serve.token")
+}
+
+fn load_or_create_token(path: &PathBuf) -> Result<String> {
+    if let Ok(existing) = std::fs::read_to_string(path) {
+        let existing = existing.trim();
+        if !existing.is_empty() {
+            return Ok(existing.to_string());
+        }
+    }
+    let token = format!(
+        "{}{}",
+        uuid::Uuid::new_v4().simple(),
+        uuid::Uuid::new_v4().simple()
+    );
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600); // Owner read/write only
+    }
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("failed to write {}", path.display()))?;
+    std::io::Write::write_all(&mut file, format!("{token}\n").as_bytes())?;
+    Ok(token)
+}
+
+/// Compare without an early exit, so response timing does not reveal how
+/// much of a guessed token was right.
+fn token_matches(expected: &str, presented: &str) -> bool {
+    let (expected, presented) = (expected.as_bytes(), presented.as_bytes());
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// What a request path addresses.
+#[derive(Debug, PartialEq)]
+enum Route {
+    Health,
+    Commands,
+    Live,
+    Stream,
+    Tool(&'static Tool),
+    NotFound,
+}
+
+fn route(path: &str) -> Route {
+    let path = path.trim_end_matches('/');
+    match path {
+        "/healthz" => Route::Health,
+        "/v1/commands" => Route::Commands,
+        "/v1/live" => Route::Live,
+        "/v1/stream" => Route::Stream,
+        _ => {
+            let Some(rest) = path.strip_prefix("/v1/") else {
+                return Route::NotFound;
+            };
+            let segments: Vec<&str> = rest.split('/').collect();
+            tools::find_by_path(&segments).map_or(Route::NotFound, Route::Tool)
+        }
+    }
+}
+
+/// Decoded `key=value` pairs of a request's query string.
+fn query_pairs(request: &Request<Incoming>) -> Vec<(String, String)> {
+    let query = request.uri().query().unwrap_or_default();
+    reqwest::Url::parse(&format!("http://localhost/?{query}"))
+        .map(|url| url.query_pairs().into_owned().collect())
+        .unwrap_or_default()
+}
+
+fn json_response(status: StatusCode, value: &Value) -> Response<Body> {
+    let mut body = serde_json::to_vec_pretty(value).unwrap_or_default();
+    body.push(b'\n');
+    let mut response = Response::new(Full::new(Bytes::from(body)).boxed_unsync());
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    json_response(status, &json!({ "error": message }))
+}
+
+fn refusal_response(refusal: &Refusal) -> Response<Body> {
+    let status = match refusal {
+        Refusal::Disabled(_) => StatusCode::FORBIDDEN,
+        Refusal::Invalid(_) => StatusCode::BAD_REQUEST,
+        Refusal::Unconfirmed(_) => StatusCode::PRECONDITION_REQUIRED,
+    };
+    error_response(status, refusal.message())
+}
+
+struct State {
+    policy: Policy,
+    token: String,
+    client: StandXClient,
+    hub: Arc<Hub>,
+}
+
+impl State {
+    fn authorized(&self, request: &Request<Incoming>) -> bool {
+        request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|presented| token_matches(&self.token, presented.trim()))
+    }
+
+    async fn handle(&self, request: Request<Incoming>) -> Response<Body> {
+        let route = route(request.uri().path());
+        if route == Route::Health {
+            return json_response(StatusCode::OK, &self.health());
+        }
+        if !self.authorized(&request) {
+            let mut response = error_response(StatusCode::UNAUTHORIZED, "missing or wrong token");
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            return response;
+        }
+        let method = request.method().clone();
+        match (route, method) {
+            (Route::Commands, Method::GET) => {
+                json_response(StatusCode::OK, &json!({ "commands": self.commands() }))
+            }
+            (Route::Live, Method::GET) => json_response(StatusCode::OK, &self.hub.snapshot()),
+            (Route::Stream, Method::GET) => match Filter::from_pairs(&query_pairs(&request)) {
+                Ok(filter) => self.stream(filter),
+                Err(message) => error_response(StatusCode::BAD_REQUEST, &message),
+            },
+            (Route::Tool(tool), Method::POST) => self.run(tool, request).await,
+            (Route::Tool(tool), Method::GET) if tool.access == Access::Read => {
+                self.run(tool, request).await
+            }
+            (Route::Tool(_), _) => {
+                let mut response = error_response(
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "order commands must be sent with POST",
+                );
+                response
+                    .headers_mut()
+                    .insert(header::ALLOW, HeaderValue::from_static("POST"));
+                response
+            }
+            (Route::NotFound, _) => error_response(StatusCode::NOT_FOUND, "no such endpoint"),
+            _ => error_response(StatusCode::METHOD_NOT_ALLOWED, "use GET"),
+        }
+    }
+
+    fn health(&self) -> Value {
+        let snapshot = self.hub.snapshot();
+        json!({
+            "status": "ok",
+            "version": env!("CARGO_PKG_VERSION"),
+            "market_connected": snapshot["market_connected"],
+            "account_connected": snapshot["account_connected"],
+            "authenticated": self.hub.authenticated,
+        })
+    }
+
+    fn commands(&self) -> Vec<Value> {
+        self.policy
+            .tools()
+            .map(|tool| {
+                let methods = match tool.access {
+                    Access::Read => json!(["GET", "POST"]),
+                    Access::Write => json!(["POST"]),
+                };
+                json!({
+                    "path": format!("/v1/{}", tool.path.join("/")),
+                    "methods": methods,
+                    "command": format!("standx {}", tool.path.join(" ")),
+                    "description": self.policy.description(tool),
+                    "arguments": self.policy.input_schema(tool),
+                })
+            })
+            .collect()
+    }
+
+    /// Run one command endpoint.
+    async fn run(&self, tool: &'static Tool, request: Request<Incoming>) -> Response<Body> {
+        let arguments = if request.method() == Method::GET {
+            self.policy
+                .arguments_from_pairs(tool, &query_pairs(&request))
+        } else {
+            read_arguments(request).await.map_err(Refusal::Invalid)
+        };
+        let prepared = arguments.and_then(|arguments| self.policy.prepare(tool, arguments));
+        let command = match prepared {
+            Ok(Prepared::Run(command)) => command,
+            Ok(Prepared::Preview(preview)) => return json_response(StatusCode::OK, &preview),
+            Err(refusal) => return refusal_response(&refusal),
+        };
+        match tools::execute(&self.client, command).await {
+            Ok(value) => json_response(StatusCode::OK, &value),
+            Err(error) => error_response(StatusCode::BAD_GATEWAY, &format!("{error:#}")),
+        }
+    }
+
+    /// `/v1/stream`: cached prices and books first, then live events.
+    fn stream(&self, filter: Filter) -> Response<Body> {
+        let primed: Vec<String> = self
+            .hub
+            .latest_events()
+            .iter()
+            .filter(|event| filter.matches(event))
+            .map(|event| event.to_sse())
+            .collect();
+        let live = stream::unfold(
+            (self.hub.subscribe(), filter),
+            |(mut rx, filter)| async move {
+                loop {
+                    let text = match tokio::time::timeout(KEEPALIVE, rx.recv()).await {
+                        Err(_) => ": keepalive\n\n".to_string(),
+                        Ok(Ok(event)) if filter.matches(&event) => event.to_sse(),
+                        Ok(Ok(_)) => continue,
+                        Ok(Err(RecvError::Lagged(skipped))) => {
+                            format!("event: lagged\ndata: {}\n\n", json!({ "skipped": skipped }))
+                        }
+                        Ok(Err(RecvError::Closed)) => return None,
+                    };
+                    return Some((text, (rx, filter)));
+                }
+            },
+        );
+        let frames = stream::iter(primed)
+            .chain(live)
+            .map(|text| Ok::<_, Infallible>(Frame::data(Bytes::from(text))));
+        let mut response = Response::new(StreamBody::new(frames).boxed_unsync());
+        let headers = response.headers_mut();
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/event-stream"),
+        );
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        response
+    }
+}
+
+/// A POST body: a JSON object of arguments; empty means none.
+async fn read_arguments(request: Request<Incoming>) -> Result<Map<String, Value>, String> {
+    let body = Limited::new(request.into_body(), MAX_BODY_BYTES)
+        .collect()
+        .await
+        .map_err(|error| format!("failed to read body: {error}"))?
+        .to_bytes();
+    if body.iter().all(u8::is_ascii_whitespace) {
+        return Ok(Map::new());
+    }
+    match serde_json::from_slice(&body) {
+        Ok(Value::Object(arguments)) => Ok(arguments),
+        Ok(_) => Err("body must be a JSON object of arguments".to_string()),
+        Err(error) => Err(format!("invalid JSON body: {error}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn routes_mirror_cli_command_paths() {
+        assert_eq!(route("/healthz"), Route::Health);
+        assert_eq!(route("/v1/stream/"), Route::Stream);
+        assert_eq!(
+            route("/v1/order/cancel-all"),
+            Route::Tool(tools::find("order_cancel_all").unwrap())
+        );
+        assert_eq!(
+            route("/v1/market/ticker"),
+            Route::Tool(tools::find("market_ticker").unwrap())
+        );
+        assert_eq!(route("/v1/auth/login"), Route::NotFound);
+        assert_eq!(route("/v1/market"), Route::NotFound);
+        assert_eq!(route("/market/ticker"), Route::NotFound);
+    }
+
+    #[test]
+    fn token_comparison_needs_an_exact_match() {
+        assert!(token_matches("s3cret-token", "s3cret-token"));
+        assert!(!token_matches("s3cret-token", "s3cret-tokem"));
+        assert!(!token_matches("s3cret-token", "s3cret"));
+        assert!(!token_matches("s3cret-token", ""));
+    }
+
+    #[test]
+    fn generated_token_is_private_and_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("standx").join("serve.token");
+        let token = load_or_create_token(&path).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(load_or_create_token(&path).unwrap(), token);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn refusals_map_to_http_statuses() {
+        let policy = Policy::new(crate::cli::ToolProfile::ReadOnly, false, false);
+        let tool = tools::find("order_cancel_all").unwrap();
+        let arguments = json!({ "symbol": "BTC-USD" }).as_object().unwrap().clone();
+        let refusal = policy.prepare(tool, arguments).unwrap_err();
+        assert_eq!(refusal_response(&refusal).status(), StatusCode::FORBIDDEN);
+
+        let policy = Policy::new(crate::cli::ToolProfile::Trading, false, false);
+        let arguments = json!({ "symbol": "BTC-USD" }).as_object().unwrap().clone();
+        let refusal = policy.prepare(tool, arguments).unwrap_err();
+        assert_eq!(
+            refusal_response(&refusal).status(),
+            StatusCode::PRECONDITION_REQUIRED
+        );
+
+        let tool = tools::find("market_depth").unwrap();
+        let pairs = vec![("limit".to_string(), "many".to_string())];
+        let refusal = policy
+            .arguments_from_pairs(tool, &pairs)
+            .and_then(|arguments| policy.prepare(tool, arguments))
+            .unwrap_err();
+        assert_eq!(refusal_response(&refusal).status(), StatusCode::BAD_REQUEST);
+    }
+}
@@ -1,10 +1,29 @@
 use crate::cli::*;
+use crate::output;
 use anyhow::Result;
 use standx_sdk::account_stream::{AccountChannel, AccountEvent, AccountStream};
 use standx_sdk::websocket::{StandXWebSocket, WsMessage};
 
 /// Handle stream commands
-pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()> {
+///
+/// In JSON mode every update is one record on stdout (see
+/// [`output::format_event`]), so `-o ndjson`, `-o yaml`, `--fields` and
+/// `--template` apply per event and the banner moves to stderr.
+pub async fn handle_stream(
+    command: StreamCommands,
+    output_format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let structured = output_format == OutputFormat::Json;
+    let banner = |text: String| {
+        if structured {
+            eprintln!("{text}\nPress Ctrl+C to exit\n");
+        } else {
+            println!("{text}");
+            println!("Press Ctrl+C to exit\n");
+        }
+    };
+
     match command {
         // Public channels - no auth required
         StreamCommands::Price { symbol } => {
@@ -12,11 +31,14 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let _ = ws.subscribe("price", Some(&symbol)).await;
             let mut rx = ws.connect().await?;
 
-            println!("Streaming price for {}", symbol);
-            println!("Press Ctrl+C to exit\n");
+            banner(format!("Streaming price for {}", symbol));
 
             while let Some(msg) = rx.recv().await {
                 if let WsMessage::Price(data) = msg {
+                    if structured {
+                        print_event(&data.data)?;
+                        continue;
+                    }
                     println!(
                         "{} | Mark: {} | Index: {} | Last: {}",
                         data.data.timestamp,
@@ -32,11 +54,20 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let _ = ws.subscribe("depth_book", Some(&symbol)).await;
             let mut rx = ws.connect().await?;
 
-            println!("Streaming depth for {} (top {} levels)", symbol, levels);
-            println!("Press Ctrl+C to exit\n");
+            banner(format!(
+                "Streaming depth for {} (top {} levels)",
+                symbol, levels
+            ));
 
             while let Some(msg) = rx.recv().await {
                 if let WsMessage::Depth(data) = msg {
+                    if structured {
+                        let mut book = data.data;
+                        book.asks.truncate(levels);
+                        book.bids.truncate(levels);
+                        print_event(&book)?;
+                        continue;
+                    }
                     println!("\n=== Order Book: {} ===", data.data.symbol);
                     println!("Asks:");
                     for ask in data.data.asks.iter().take(levels) {
@@ -54,11 +85,14 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let _ = ws.subscribe("public_trade", Some(&symbol)).await;
             let mut rx = ws.connect().await?;
 
-            println!("Streaming trades for {}", symbol);
-            println!("Press Ctrl+C to exit\n");
+            banner(format!("Streaming trades for {}", symbol));
 
             while let Some(msg) = rx.recv().await {
                 if let WsMessage::Trade(data) = msg {
+                    if structured {
+                        print_event(&data)?;
+                        continue;
+                    }
                     let side = data.side.as_deref().unwrap_or({
                         if data.is_buyer_taker {
                             "buy"
@@ -85,11 +119,14 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
                 .await?;
             let mut rx = ws.connect().await?;
 
-            println!("Streaming kline for {} [{}]", symbol, interval);
-            println!("Press Ctrl+C to exit\n");
+            banner(format!("Streaming kline for {} [{}]", symbol, interval));
 
             while let Some(msg) = rx.recv().await {
                 if let WsMessage::Kline(data) = msg {
+                    if structured {
+                        print_event(&data)?;
+                        continue;
+                    }
                     // Convert timestamp to readable time
                     let time_str = chrono::DateTime::from_timestamp_millis(data.time)
                         .map(|dt| dt.format("%H:%M:%S").to_string())
@@ -114,12 +151,15 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let stream = AccountStream::new(1)?;
             let (mut rx, _health, _handle) = stream.connect(&[AccountChannel::Order]).await?;
 
-            println!("Streaming order updates");
-            println!("Press Ctrl+C to exit\n");
+            banner("Streaming order updates".to_string());
 
             while let Some(msg) = rx.recv().await {
                 if let AccountEvent::Order(data) = msg {
-                    println!("Order update: {}", serde_json::to_string(&data)?);
+                    if structured {
+                        print_event(&data)?;
+                    } else {
+                        println!("Order update: {}", serde_json::to_string(&data)?);
+                    }
                 }
             }
         }
@@ -127,12 +167,15 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let stream = AccountStream::new(1)?;
             let (mut rx, _health, _handle) = stream.connect(&[AccountChannel::Position]).await?;
 
-            println!("Streaming position updates");
-            println!("Press Ctrl+C to exit\n");
+            banner("Streaming position updates".to_string());
 
             while let Some(msg) = rx.recv().await {
                 if let AccountEvent::Position(data) = msg {
-                    println!("Position update: {}", serde_json::to_string(&data)?);
+                    if structured {
+                        print_event(&data)?;
+                    } else {
+                        println!("Position update: {}", serde_json::to_string(&data)?);
+                    }
                 }
             }
         }
@@ -140,12 +183,15 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let stream = AccountStream::new(1)?;
             let (mut rx, _health, _handle) = stream.connect(&[AccountChannel::Balance]).await?;
 
-            println!("Streaming balance updates");
-            println!("Press Ctrl+C to exit\n");
+            banner("Streaming balance updates".to_string());
 
             while let Some(msg) = rx.recv().await {
                 if let AccountEvent::Balance(data) = msg {
-                    println!("Balance update: {}", serde_json::to_string(&data)?);
+                    if structured {
+                        print_event(&data)?;
+                    } else {
+                        println!("Balance update: {}", serde_json::to_string(&data)?);
+                    }
                 }
             }
         }
@@ -153,11 +199,14 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
             let stream = AccountStream::new(1)?;
             let (mut rx, _health, _handle) = stream.connect(&[AccountChannel::Trade]).await?;
 
-            println!("Streaming fill/trade updates");
-            println!("Press Ctrl+C to exit\n");
+            banner("Streaming fill/trade updates".to_string());
 
             while let Some(msg) = rx.recv().await {
                 if let AccountEvent::Trade(data) = msg {
+                    if structured {
+                        print_event(&data)?;
+                        continue;
+                    }
                     println!(
                         "Fill {} | {} | Price: {} | Qty: {}",
                         data.trade_id,
@@ -172,3 +221,9 @@ pub async fn handle_stream(command: StreamCommands, verbose: bool) -> Result<()>
 
     Ok(())
 }
+
+/// Print one update as a structured output record.
+fn print_event<T: serde::Serialize>(event: &T) -> Result<()> {
+    println!("{}", output::format_event(&serde_json::to_value(event)?));
+    Ok(())
+}
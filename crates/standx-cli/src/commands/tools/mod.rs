@@ -0,0 +1,395 @@
+//! CLI subcommands exposed to programs: the registry, the call policy, and
+//! what each command does once its arguments parsed.
+//!
+//! Shared by `standx mcp` (tools over stdio) and `standx serve` (HTTP
+//! endpoints), so both frontends expose the same commands with the same
+//! arguments and the same safety rules. Results are the SDK models
+//! serialized as they are for `--output json`.
+
+mod schema;
+
+pub(super) use schema::{typed_default, value_type};
+
+use super::account::position_quantity_is_nonzero_or_invalid;
+use super::data::{cached_funding, cached_klines, cached_recent_trades};
+use super::maker::maker_status;
+use super::order::{cancel, cancel_all, create_params, submit, Journal};
+use super::util::parse_time_string;
+use crate::cli::{
+    AccountCommands, Cli, Commands, MakerCommands, MarketCommands, OrderCommands, ToolProfile,
+    TradeCommands,
+};
+use crate::market_cache::MarketCache;
+use anyhow::{bail, Result};
+use clap::{Command, CommandFactory};
+use serde_json::{json, Map, Value};
+use standx_sdk::client::StandXClient;
+
+/// Whether a tool can change account state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Access {
+    Read,
+    Write,
+}
+
+/// One exposed command, backed by the CLI subcommand at `path`.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct Tool {
+    pub name: &'static str,
+    pub path: &'static [&'static str],
+    pub access: Access,
+}
+
+const fn read(name: &'static str, path: &'static [&'static str]) -> Tool {
+    Tool {
+        name,
+        path,
+        access: Access::Read,
+    }
+}
+
+const fn write(name: &'static str, path: &'static [&'static str]) -> Tool {
+    Tool {
+        name,
+        path,
+        access: Access::Write,
+    }
+}
+
+pub(super) const TOOLS: &[Tool] = &[
+    read("market_symbols", &["market", "symbols"]),
+    read("market_ticker", &["market", "ticker"]),
+    read("market_tickers", &["market", "tickers"]),
+    read("market_trades", &["market", "trades"]),
+    read("market_depth", &["market", "depth"]),
+    read("market_kline", &["market", "kline"]),
+    read("market_funding", &["market", "funding"]),
+    read("account_balances", &["account", "balances"]),
+    read("account_positions", &["account", "positions"]),
+    read("account_orders", &["account", "orders"]),
+    read("account_history", &["account", "history"]),
+    read("trade_history", &["trade", "history"]),
+    read("maker_status", &["maker", "status"]),
+    write("order_create", &["order", "create"]),
+    write("order_cancel", &["order", "cancel"]),
+    write("order_cancel_all", &["order", "cancel-all"]),
+];
+
+pub(super) fn find(name: &str) -> Option<&'static Tool> {
+    TOOLS.iter().find(|tool| tool.name == name)
+}
+
+/// The tool whose CLI path is `path`, e.g. `["order", "cancel-all"]`.
+pub(super) fn find_by_path(path: &[&str]) -> Option<&'static Tool> {
+    TOOLS.iter().find(|tool| tool.path == path)
+}
+
+/// Argument every order tool takes on top of its CLI arguments.
+pub(super) const CONFIRM_ARG: &str = "confirm";
+
+pub(super) fn profile_name(profile: ToolProfile) -> &'static str {
+    match profile {
+        ToolProfile::ReadOnly => "read-only",
+        ToolProfile::Trading => "trading",
+    }
+}
+
+/// A call the policy accepted.
+#[derive(Debug)]
+pub(super) enum Prepared {
+    /// Run the parsed command.
+    Run(Commands),
+    /// `--dry-run`: report what would have run instead.
+    Preview(Value),
+}
+
+/// Why a call was not accepted.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) enum Refusal {
+    /// The profile does not expose the tool.
+    Disabled(String),
+    /// The arguments do not form a valid command line.
+    Invalid(String),
+    /// An order tool was called without `"confirm": true`.
+    Unconfirmed(String),
+}
+
+impl Refusal {
+    pub(super) fn message(&self) -> &str {
+        match self {
+            Refusal::Disabled(message)
+            | Refusal::Invalid(message)
+            | Refusal::Unconfirmed(message) => message,
+        }
+    }
+}
+
+/// Which tools are exposed and how order tools are guarded:
+/// - the `read-only` profile neither lists nor runs them;
+/// - `--dry-run` answers with the command that would have run;
+/// - otherwise each call must carry `"confirm": true`, unless started with
+///   `--yes` / `STANDX_AUTO_CONFIRM`.
+pub(super) struct Policy {
+    pub profile: ToolProfile,
+    pub dry_run: bool,
+    pub auto_confirm: bool,
+    root: Command,
+}
+
+impl Policy {
+    pub(super) fn new(profile: ToolProfile, dry_run: bool, auto_confirm: bool) -> Self {
+        Self {
+            profile,
+            dry_run,
+            auto_confirm,
+            root: Cli::command(),
+        }
+    }
+
+    /// Tools visible under the profile.
+    pub(super) fn tools(&self) -> impl Iterator<Item = &'static Tool> + '_ {
+        TOOLS
+            .iter()
+            .filter(|tool| tool.access == Access::Read || self.profile == ToolProfile::Trading)
+    }
+
+    fn subcommand(&self, tool: &Tool) -> &Command {
+        let mut command = &self.root;
+        for name in tool.path {
+            command = command
+                .find_subcommand(name)
+                .expect("tool paths are checked against the CLI in tests");
+        }
+        command
+    }
+
+    /// One-line description, from the subcommand's help.
+    pub(super) fn description(&self, tool: &Tool) -> String {
+        self.subcommand(tool)
+            .get_about()
+            .map(ToString::to_string)
+            .unwrap_or_default()
+    }
+
+    /// JSON Schema of the tool's arguments, including [`CONFIRM_ARG`] for
+    /// order tools.
+    pub(super) fn input_schema(&self, tool: &Tool) -> Value {
+        let mut input_schema = schema::input_schema(self.subcommand(tool));
+        if tool.access == Access::Write {
+            input_schema["properties"][CONFIRM_ARG] = json!({
+                "type": "boolean",
+                "description": "Set to true to execute; without it the call is refused \
+                                (ignored when the server runs with --yes or --dry-run)",
+            });
+        }
+        input_schema
+    }
+
+    /// Arguments from `key=value` pairs (a URL query), typed by the schema.
+    pub(super) fn arguments_from_pairs(
+        &self,
+        tool: &Tool,
+        pairs: &[(String, String)],
+    ) -> std::result::Result<Map<String, Value>, Refusal> {
+        let (confirm, pairs): (Vec<_>, Vec<_>) =
+            pairs.iter().partition(|(key, _)| key == CONFIRM_ARG);
+        let mut arguments = schema::arguments_from_pairs(self.subcommand(tool), &pairs)
+            .map_err(Refusal::Invalid)?;
+        if let Some((_, value)) = confirm.last() {
+            arguments.insert(CONFIRM_ARG.to_string(), json!(value == "true"));
+        }
+        Ok(arguments)
+    }
+
+    /// Validate a call and apply the order-tool safety rules.
+    pub(super) fn prepare(
+        &self,
+        tool: &Tool,
+        mut arguments: Map<String, Value>,
+    ) -> std::result::Result<Prepared, Refusal> {
+        if tool.access == Access::Write && self.profile == ToolProfile::ReadOnly {
+            return Err(Refusal::Disabled(format!(
+                "{} is disabled in the read-only profile; restart with --profile trading",
+                tool.name
+            )));
+        }
+        let confirmed = match tool.access {
+            Access::Write => arguments.remove(CONFIRM_ARG) == Some(Value::Bool(true)),
+            Access::Read => false,
+        };
+        let argv = schema::to_argv(self.subcommand(tool), tool.path, &arguments)
+            .map_err(Refusal::Invalid)?;
+        let cli = schema::parse(&argv).map_err(Refusal::Invalid)?;
+
+        if tool.access == Access::Write {
+            let command_line = argv.join(" ");
+            if self.dry_run {
+                return Ok(Prepared::Preview(json!({
+                    "dry_run": true,
+                    "tool": tool.name,
+                    "would_execute": command_line,
+                    "note": "the server was started with --dry-run; nothing was sent",
+                })));
+            }
+            if !confirmed && !self.auto_confirm {
+                return Err(Refusal::Unconfirmed(format!(
+                    "confirmation required: call {} again with \"confirm\": true to \
+                     execute `{command_line}`",
+                    tool.name
+                )));
+            }
+        }
+        Ok(Prepared::Run(cli.command))
+    }
+}
+
+fn to_value<T: serde::Serialize>(value: &T) -> Result<Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Run a parsed command and return its JSON result.
+pub(super) async fn execute(client: &StandXClient, command: Commands) -> Result<Value> {
+    match command {
+        Commands::Market { command } => market(client, command).await,
+        Commands::Account { command } => account(client, command).await,
+        Commands::Trade { command } => {
+            let TradeCommands::History {
+                symbol,
+                from,
+                to,
+                limit,
+            } = command;
+            let now = chrono::Utc::now().timestamp();
+            let from = from.map_or(Ok(now - 86400), |from| parse_time_string(&from, false))?;
+            let to = to.map_or(Ok(now), |to| parse_time_string(&to, true))?;
+            to_value(&client.get_user_trades(&symbol, from, to, limit).await?)
+        }
+        Commands::Maker { command } => match *command {
+            MakerCommands::Status { symbol } => {
+                to_value(&maker_status(client, symbol.as_deref()).await?)
+            }
+            _ => bail!("only maker status is available as a tool"),
+        },
+        Commands::Order { command } => order(client, command).await,
+        _ => bail!("command is not available as a tool"),
+    }
+}
+
+async fn market(client: &StandXClient, command: MarketCommands) -> Result<Value> {
+    let cache = |no_cache: bool| (!no_cache).then(MarketCache::open_default);
+    match command {
+        MarketCommands::Symbols => to_value(&client.get_symbol_info().await?),
+        MarketCommands::Ticker { symbol } => to_value(&client.get_symbol_market(&symbol).await?),
+        MarketCommands::Tickers => {
+            let mut tickers = Vec::new();
+            for info in client.get_symbol_info().await? {
+                tickers.push(client.get_symbol_market(&info.symbol).await?);
+            }
+            to_value(&tickers)
+        }
+        MarketCommands::Trades {
+            symbol,
+            limit,
+            no_cache,
+        } => {
+            let cache = cache(no_cache);
+            to_value(&cached_recent_trades(client, cache.as_ref(), &symbol, limit).await?)
+        }
+        MarketCommands::Depth { symbol, limit } => {
+            to_value(&client.get_depth(&symbol, limit).await?)
+        }
+        MarketCommands::Kline {
+            symbol,
+            resolution,
+            from,
+            to,
+            limit,
+            no_cache,
+        } => {
+            let now = chrono::Utc::now().timestamp();
+            let from = from.map_or(Ok(now - 86400), |from| parse_time_string(&from, false))?;
+            let to = to.map_or(Ok(now), |to| parse_time_string(&to, false))?;
+            let cache = cache(no_cache);
+            let mut klines =
+                cached_klines(client, cache.as_ref(), &symbol, &resolution, from, to).await?;
+            if let Some(limit) = limit {
+                klines.truncate(limit as usize);
+            }
+            to_value(&klines)
+        }
+        MarketCommands::Funding {
+            symbol,
+            days,
+            no_cache,
+        } => {
+            let now = chrono::Utc::now().timestamp();
+            let cache = cache(no_cache);
+            to_value(
+                &cached_funding(client, cache.as_ref(), &symbol, now - days * 86400, now).await?,
+            )
+        }
+    }
+}
+
+async fn account(client: &StandXClient, command: AccountCommands) -> Result<Value> {
+    match command {
+        AccountCommands::Balances => to_value(&client.get_balance().await?),
+        AccountCommands::Positions { symbol } => {
+            let mut positions = client.get_positions(symbol.as_deref()).await?;
+            positions.retain(|position| position_quantity_is_nonzero_or_invalid(&position.qty));
+            to_value(&positions)
+        }
+        AccountCommands::Orders { symbol } => {
+            to_value(&client.get_open_orders(symbol.as_deref()).await?)
+        }
+        AccountCommands::History { symbol, limit } => to_value(
+            &client
+                .get_order_history(symbol.as_deref(), Some(limit))
+                .await?,
+        ),
+        AccountCommands::Config { .. } => bail!("position config is not implemented"),
+    }
+}
+
+async fn order(client: &StandXClient, command: OrderCommands) -> Result<Value> {
+    match command {
+        command @ OrderCommands::Create { .. } => {
+            let params = create_params(command)?;
+            let submission = submit(client, params, &Journal::open_default()).await?;
+            // Tells a retrying agent whether its order was already placed.
+            let mut order = to_value(&submission.order)?;
+            order["existing"] = json!(submission.existing);
+            Ok(order)
+        }
+        OrderCommands::Cancel { symbol, order_id } => {
+            cancel(client, &symbol, &order_id).await?;
+            Ok(json!({ "cancelled": order_id, "symbol": symbol }))
+        }
+        OrderCommands::CancelAll { symbol } => {
+            cancel_all(client, &symbol).await?;
+            Ok(json!({ "cancelled_all": symbol }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_tool_maps_to_a_cli_subcommand() {
+        let root = Cli::command();
+        for tool in TOOLS {
+            let mut command = &root;
+            for name in tool.path {
+                command = command
+                    .find_subcommand(name)
+                    .unwrap_or_else(|| panic!("{} has no subcommand {name}", tool.name));
+            }
+        }
+        assert!(TOOLS
+            .iter()
+            .filter(|tool| tool.access == Access::Write)
+            .all(|tool| tool.path[0] == "order"));
+    }
+}
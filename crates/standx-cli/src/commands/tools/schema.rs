@@ -0,0 +1,326 @@
+//! Tool input schemas derived from the clap definitions, and the reverse
+//! mapping from tool arguments back to a command line.
+//!
+//! Arguments are validated by re-parsing that command line with [`Cli`], so
+//! an agent gets exactly the checks (required values, enums, numeric ranges)
+//! a shell user would.
+
+use crate::cli::Cli;
+use clap::{Arg, ArgAction, Command};
+use serde_json::{json, Map, Value};
+use std::any::TypeId;
+
+/// JSON Schema type of an argument's value.
+pub(in crate::commands) fn value_type(arg: &Arg) -> &'static str {
+    if matches!(arg.get_action(), ArgAction::SetTrue | ArgAction::SetFalse) {
+        return "boolean";
+    }
+    let id = arg.get_value_parser().type_id();
+    let integers = [
+        TypeId::of::<u8>(),
+        TypeId::of::<u16>(),
+        TypeId::of::<u32>(),
+        TypeId::of::<u64>(),
+        TypeId::of::<usize>(),
+        TypeId::of::<i32>(),
+        TypeId::of::<i64>(),
+    ];
+    if integers.iter().any(|integer| id == *integer) {
+        "integer"
+    } else if id == TypeId::of::<f64>() {
+        "number"
+    } else {
+        "string"
+    }
+}
+
+/// Arguments a tool exposes: everything on the subcommand except global and
+/// hidden flags.
+fn tool_args(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_global_set() && !arg.is_hide_set())
+        .filter(|arg| !matches!(arg.get_id().as_str(), "help" | "version"))
+}
+
+pub(in crate::commands) fn typed_default(kind: &str, raw: &str) -> Value {
+    match kind {
+        "integer" => raw.parse::<i64>().map_or_else(|_| json!(raw), |v| json!(v)),
+        "number" => raw.parse::<f64>().map_or_else(|_| json!(raw), |v| json!(v)),
+        "boolean" => json!(raw == "true"),
+        _ => json!(raw),
+    }
+}
+
+/// JSON Schema for the arguments of `command`.
+pub(super) fn input_schema(command: &Command) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for arg in tool_args(command) {
+        let kind = value_type(arg);
+        let mut property = Map::new();
+        let values: Vec<Value> = arg
+            .get_possible_values()
+            .iter()
+            .filter(|value| !value.is_hide_set())
+            .map(|value| json!(value.get_name()))
+            .collect();
+        let mut item = json!({ "type": kind });
+        if kind != "boolean" && !values.is_empty() {
+            item["enum"] = Value::Array(values);
+        }
+        if matches!(arg.get_action(), ArgAction::Append) {
+            property.insert("type".into(), json!("array"));
+            property.insert("items".into(), item);
+        } else if let Value::Object(item) = item {
+            property.extend(item);
+        }
+        if let Some(help) = arg.get_long_help().or_else(|| arg.get_help()) {
+            property.insert("description".into(), json!(help.to_string()));
+        }
+        if let Some(default) = arg.get_default_values().first() {
+            let default = default.to_string_lossy();
+            if kind != "boolean" {
+                property.insert("default".into(), typed_default(kind, &default));
+            }
+        }
+        if arg.is_required_set() {
+            required.push(json!(arg.get_id().as_str()));
+        }
+        properties.insert(arg.get_id().to_string(), Value::Object(property));
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn scalar(name: &str, value: &Value) -> Result<String, String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::Bool(value) => Ok(value.to_string()),
+        _ => Err(format!(
+            "argument '{name}' must be a string, number or boolean"
+        )),
+    }
+}
+
+/// Command line for `path` with `arguments`, starting with the binary name.
+///
+/// Options are passed as `--long=value` and positionals after `--`, so a
+/// value that starts with a dash is never mistaken for a flag.
+pub(super) fn to_argv(
+    command: &Command,
+    path: &[&str],
+    arguments: &Map<String, Value>,
+) -> Result<Vec<String>, String> {
+    let args: Vec<&Arg> = tool_args(command).collect();
+    if let Some(unknown) = arguments
+        .keys()
+        .find(|key| !args.iter().any(|arg| arg.get_id() == key.as_str()))
+    {
+        return Err(format!("unknown argument '{unknown}'"));
+    }
+
+    let mut argv: Vec<String> = std::iter::once("standx")
+        .chain(path.iter().copied())
+        .map(str::to_string)
+        .collect();
+    let mut positionals: Vec<(usize, String)> = Vec::new();
+    for arg in &args {
+        let name = arg.get_id().as_str();
+        let Some(value) = arguments.get(name).filter(|value| !value.is_null()) else {
+            continue;
+        };
+        if arg.is_positional() {
+            positionals.push((arg.get_index().unwrap_or(usize::MAX), scalar(name, value)?));
+            continue;
+        }
+        let long = arg.get_long().unwrap_or(name);
+        match (arg.get_action(), value) {
+            (ArgAction::SetTrue, Value::Bool(true)) | (ArgAction::SetFalse, Value::Bool(false)) => {
+                argv.push(format!("--{long}"));
+            }
+            (ArgAction::SetTrue | ArgAction::SetFalse, Value::Bool(_)) => {}
+            (ArgAction::SetTrue | ArgAction::SetFalse, _) => {
+                return Err(format!("argument '{name}' must be a boolean"));
+            }
+            (ArgAction::Append, Value::Array(values)) => {
+                for value in values {
+                    argv.push(format!("--{long}={}", scalar(name, value)?));
+                }
+            }
+            _ => argv.push(format!("--{long}={}", scalar(name, value)?)),
+        }
+    }
+    if !positionals.is_empty() {
+        positionals.sort_by_key(|(index, _)| *index);
+        argv.push("--".to_string());
+        argv.extend(positionals.into_iter().map(|(_, value)| value));
+    }
+    Ok(argv)
+}
+
+/// Arguments from `key=value` pairs, as in a URL query string.
+///
+/// Keys are argument ids (`order_id`) or long flags (`order-id`). Values are
+/// typed by the schema so they survive [`to_argv`]: booleans accept `true`,
+/// `false` or an empty value (`?reduce_only`), and repeated keys collect
+/// into a list for repeatable arguments. Anything that does not convert is
+/// passed through as a string for the CLI parser to reject.
+pub(super) fn arguments_from_pairs(
+    command: &Command,
+    pairs: &[&(String, String)],
+) -> Result<Map<String, Value>, String> {
+    let args: Vec<&Arg> = tool_args(command).collect();
+    let mut arguments = Map::new();
+    for (key, raw) in pairs {
+        let arg = args
+            .iter()
+            .find(|arg| arg.get_id() == key.as_str() || arg.get_long() == Some(key.as_str()))
+            .ok_or_else(|| format!("unknown argument '{key}'"))?;
+        let name = arg.get_id().to_string();
+        let value = match value_type(arg) {
+            "boolean" => match raw.as_str() {
+                "" | "true" => json!(true),
+                "false" => json!(false),
+                _ => return Err(format!("argument '{name}' must be true or false")),
+            },
+            "integer" => raw.parse::<i64>().map_or_else(|_| json!(raw), |v| json!(v)),
+            "number" => raw.parse::<f64>().map_or_else(|_| json!(raw), |v| json!(v)),
+            _ => json!(raw),
+        };
+        if matches!(arg.get_action(), ArgAction::Append) {
+            let list = arguments.entry(name).or_insert_with(|| json!([]));
+            if let Value::Array(list) = list {
+                list.push(value);
+            }
+        } else {
+            arguments.insert(name, value);
+        }
+    }
+    Ok(arguments)
+}
+
+/// Parse `argv` exactly as the shell entry point would.
+pub(super) fn parse(argv: &[String]) -> Result<Cli, String> {
+    use clap::Parser;
+    Cli::try_parse_from(argv).map_err(|error| error.render().to_string().trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Commands, MarketCommands, OrderCommands};
+    use clap::CommandFactory;
+
+    fn subcommand(path: &[&str]) -> Command {
+        let mut command = Cli::command();
+        for name in path {
+            command = command.find_subcommand(name).unwrap().clone();
+        }
+        command
+    }
+
+    #[test]
+    fn schema_follows_the_clap_definition() {
+        let schema = input_schema(&subcommand(&["order", "create"]));
+        assert_eq!(
+            schema["required"],
+            json!(["symbol", "side", "order_type", "qty"])
+        );
+        assert_eq!(schema["properties"]["reduce_only"]["type"], "boolean");
+        assert_eq!(schema["properties"]["price"]["type"], "string");
+        assert!(schema["properties"].get("dry_run").is_none());
+
+        let schema = input_schema(&subcommand(&["market", "funding"]));
+        assert_eq!(schema["properties"]["days"]["type"], "integer");
+        assert_eq!(schema["properties"]["days"]["default"], 7);
+    }
+
+    #[test]
+    fn arguments_round_trip_through_the_cli_parser() {
+        let path = ["order", "create"];
+        let arguments = json!({
+            "symbol": "BTC-USD",
+            "side": "sell",
+            "order_type": "limit",
+            "qty": "0.01",
+            "price": "-1",
+            "reduce_only": true,
+        });
+        let argv = to_argv(&subcommand(&path), &path, arguments.as_object().unwrap()).unwrap();
+        let Commands::Order { command } = parse(&argv).unwrap().command else {
+            panic!("expected an order command");
+        };
+        let OrderCommands::Create {
+            side,
+            price,
+            reduce_only,
+            ..
+        } = command
+        else {
+            panic!("expected order create");
+        };
+        assert_eq!(side, "sell");
+        assert_eq!(price.as_deref(), Some("-1"));
+        assert!(reduce_only);
+
+        let path = ["market", "trades"];
+        let arguments = json!({ "symbol": "ETH-USD", "limit": 5 });
+        let argv = to_argv(&subcommand(&path), &path, arguments.as_object().unwrap()).unwrap();
+        let Commands::Market { command } = parse(&argv).unwrap().command else {
+            panic!("expected a market command");
+        };
+        assert!(matches!(
+            command,
+            MarketCommands::Trades { limit: Some(5), .. }
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_and_invalid_arguments() {
+        let path = ["market", "depth"];
+        let command = subcommand(&path);
+        let unknown = json!({ "symbol": "BTC-USD", "levels": 5 });
+        assert!(to_argv(&command, &path, unknown.as_object().unwrap()).is_err());
+
+        let invalid = json!({ "symbol": "BTC-USD", "limit": "many" });
+        let argv = to_argv(&command, &path, invalid.as_object().unwrap()).unwrap();
+        assert!(parse(&argv).is_err());
+
+        let missing = json!({});
+        let argv = to_argv(&command, &path, missing.as_object().unwrap()).unwrap();
+        assert!(parse(&argv).is_err());
+    }
+
+    #[test]
+    fn query_pairs_are_typed_by_the_schema() {
+        let command = subcommand(&["order", "create"]);
+        let pairs = [
+            ("symbol".to_string(), "BTC-USD".to_string()),
+            ("order_type".to_string(), "market".to_string()),
+            ("reduce-only".to_string(), String::new()),
+        ];
+        let pairs: Vec<_> = pairs.iter().collect();
+        let arguments = arguments_from_pairs(&command, &pairs).unwrap();
+        assert_eq!(
+            Value::Object(arguments),
+            json!({ "symbol": "BTC-USD", "order_type": "market", "reduce_only": true })
+        );
+
+        let command = subcommand(&["market", "depth"]);
+        let pairs = [("limit".to_string(), "5".to_string())];
+        let pairs: Vec<_> = pairs.iter().collect();
+        assert_eq!(
+            arguments_from_pairs(&command, &pairs).unwrap()["limit"],
+            json!(5)
+        );
+        let pairs = [("levels".to_string(), "5".to_string())];
+        let pairs: Vec<_> = pairs.iter().collect();
+        assert!(arguments_from_pairs(&command, &pairs).is_err());
+    }
+}
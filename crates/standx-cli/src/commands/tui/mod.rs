@@ -0,0 +1,323 @@
+//! `standx dashboard --tui` — interactive full-screen dashboard
+//!
+//! Panes for tickers, positions, open orders, live depth and recent fills,
+//! updated from the WebSocket streams rather than by polling: the public
+//! `price` channel for every tracked symbol, `depth_book` for the selected
+//! one, and (when authenticated) the account order/position/trade channels.
+//! Positions and open orders are snapshotted over REST at start and again
+//! whenever the account stream reconnects, so nothing missed while it was
+//! down lingers on screen.
+//!
+//! Cancelling an order or flattening a position always goes through a
+//! confirmation modal; see [`state::App::handle_key`] for the bindings.
+
+mod state;
+mod view;
+
+use super::order::cancel;
+use super::util::{is_auth_error, shutdown_signal};
+use crate::audit;
+use anyhow::{Context, Result};
+use crossterm::event::{Event, EventStream, KeyEventKind};
+use futures::future::join_all;
+use futures::StreamExt;
+use ratatui::DefaultTerminal;
+use standx_sdk::account_stream::{AccountChannel, AccountEvent, AccountStream};
+use standx_sdk::client::order::{create_order_body, CreateOrderParams};
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::OrderType;
+use standx_sdk::websocket::{StandXWebSocket, WsMessage};
+use state::{Action, App};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// How long a dropped stream waits before reconnecting.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Depth levels requested for the initial book snapshot.
+const DEPTH_SNAPSHOT_LEVELS: u32 = 20;
+
+/// Which market connection a message came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Feed {
+    Tickers,
+    Book,
+}
+
+/// Run the interactive dashboard until the user quits.
+pub(super) async fn run_tui(symbols: Vec<String>, verbose: bool) -> Result<()> {
+    let client = StandXClient::new()?;
+    let symbols: Vec<String> = if symbols.is_empty() {
+        client
+            .get_symbol_info()
+            .await
+            .context("failed to list symbols")?
+            .into_iter()
+            .map(|info| info.symbol.to_uppercase())
+            .collect()
+    } else {
+        symbols.iter().map(|symbol| symbol.to_uppercase()).collect()
+    };
+    if symbols.is_empty() {
+        anyhow::bail!("no symbols to show");
+    }
+
+    // Credentials are only checked up front; an expired token surfaces as
+    // an auth error from the snapshot below.
+    let mut app = App::new(symbols, AccountStream::new(0).is_ok());
+    refresh_account(&client, &mut app).await;
+    let prices = join_all(
+        app.symbols
+            .iter()
+            .map(|symbol| client.get_symbol_price(symbol)),
+    )
+    .await;
+    for price in prices.into_iter().flatten() {
+        app.apply_price(&price);
+    }
+    if let Ok(book) = client
+        .get_depth(&app.selected, Some(DEPTH_SNAPSHOT_LEVELS))
+        .await
+    {
+        app.apply_depth(book);
+    }
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &client, &mut app, verbose).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    client: &StandXClient,
+    app: &mut App,
+    verbose: bool,
+) -> Result<()> {
+    let (market_tx, mut market_rx) = mpsc::channel::<(Feed, WsMessage)>(1024);
+    let tickers = tokio::spawn(run_market_feed(
+        Feed::Tickers,
+        app.symbols.clone(),
+        market_tx.clone(),
+        verbose,
+    ));
+    let mut book = tokio::spawn(run_market_feed(
+        Feed::Book,
+        vec![app.selected.clone()],
+        market_tx.clone(),
+        verbose,
+    ));
+    let (account_tx, mut account_rx) = mpsc::channel::<AccountEvent>(1024);
+    let account = app
+        .authenticated
+        .then(|| tokio::spawn(run_account_feed(account_tx)));
+    let mut keys = EventStream::new();
+    // Created once: a signal that lands while drawing or executing an
+    // action is then still pending at the next select.
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    let result = loop {
+        terminal.draw(|frame| view::draw(frame, app))?;
+        let action = tokio::select! {
+            _ = &mut shutdown => Some(Action::Quit),
+            event = keys.next() => match event {
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => app.handle_key(key),
+                Some(Ok(_)) => None,
+                Some(Err(error)) => break Err(error.into()),
+                None => Some(Action::Quit),
+            },
+            Some((feed, message)) = market_rx.recv() => {
+                apply_market(app, feed, message);
+                None
+            }
+            Some(event) = account_rx.recv() => {
+                apply_account(client, app, event).await;
+                None
+            }
+        };
+        match action {
+            None => {}
+            Some(Action::Quit) => break Ok(()),
+            Some(Action::SwitchSymbol(symbol)) => {
+                book.abort();
+                book = tokio::spawn(run_market_feed(
+                    Feed::Book,
+                    vec![symbol.clone()],
+                    market_tx.clone(),
+                    verbose,
+                ));
+                app.status = format!("depth → {symbol}");
+            }
+            Some(action) => execute(client, app, action).await,
+        }
+    };
+
+    tickers.abort();
+    book.abort();
+    if let Some(account) = account {
+        account.abort();
+    }
+    result
+}
+
+fn apply_market(app: &mut App, feed: Feed, message: WsMessage) {
+    match message {
+        WsMessage::Price(update) => app.apply_price(&update.data),
+        WsMessage::Depth(update) => app.apply_depth(update.data),
+        WsMessage::Connected if feed == Feed::Tickers => app.market_connected = true,
+        WsMessage::Disconnected if feed == Feed::Tickers => app.market_connected = false,
+        WsMessage::Error(error) => app.status = format!("market stream: {error}"),
+        _ => {}
+    }
+}
+
+async fn apply_account(client: &StandXClient, app: &mut App, event: AccountEvent) {
+    match event {
+        AccountEvent::Connected { .. } => {
+            app.account_connected = true;
+            refresh_account(client, app).await;
+        }
+        AccountEvent::Order(update) => app.apply_order(&update),
+        AccountEvent::Position(update) => app.apply_position(&update),
+        AccountEvent::Trade(trade) => app.apply_fill(&trade),
+        AccountEvent::Balance(_) => {}
+        AccountEvent::Disconnected { reason } | AccountEvent::Error { reason } => {
+            app.account_connected = false;
+            app.status = format!("account stream: {reason}");
+        }
+    }
+}
+
+/// Replace positions and open orders with a REST snapshot.
+async fn refresh_account(client: &StandXClient, app: &mut App) {
+    if !app.authenticated {
+        return;
+    }
+    let (positions, orders) =
+        tokio::join!(client.get_positions(None), client.get_open_orders(None));
+    match (positions, orders) {
+        (Ok(positions), Ok(orders)) => {
+            app.set_positions(&positions);
+            app.set_orders(&orders);
+        }
+        (Err(error), _) | (_, Err(error)) => {
+            if is_auth_error(&error) {
+                app.authenticated = false;
+                app.status = "not authenticated — run 'standx auth login'".to_string();
+            } else {
+                app.status = format!("account snapshot failed: {error}");
+            }
+        }
+    }
+}
+
+/// Send a confirmed order action; the account stream reflects the result.
+async fn execute(client: &StandXClient, app: &mut App, action: Action) {
+    let result = match &action {
+        Action::Cancel { symbol, order_id } => cancel(client, symbol, order_id).await,
+        Action::Flatten { symbol, side, qty } => {
+            let params = CreateOrderParams {
+                symbol: symbol.clone(),
+                side: *side,
+                order_type: OrderType::Market,
+                quantity: qty.clone(),
+                reduce_only: true,
+                ..CreateOrderParams::default()
+            };
+            audit::audited(
+                "order_create",
+                symbol,
+                create_order_body(&params),
+                client.create_order(params),
+            )
+            .await
+            .map(|_| ())
+            .map_err(Into::into)
+        }
+        Action::Quit | Action::SwitchSymbol(_) => Ok(()),
+    };
+    app.status = match (&action, result) {
+        (Action::Cancel { order_id, .. }, Ok(())) => format!("cancel sent for {order_id}"),
+        (Action::Flatten { symbol, .. }, Ok(())) => format!("flatten sent for {symbol}"),
+        (_, Ok(())) => String::new(),
+        (_, Err(error)) => format!("⚠️  {error}"),
+    };
+}
+
+/// Public stream for one pane, rebuilt whenever the managed connection gives
+/// up. `Feed::Tickers` subscribes `price` for every symbol, `Feed::Book`
+/// subscribes `depth_book`.
+async fn run_market_feed(
+    feed: Feed,
+    symbols: Vec<String>,
+    tx: mpsc::Sender<(Feed, WsMessage)>,
+    verbose: bool,
+) {
+    let channel = match feed {
+        Feed::Tickers => "price",
+        Feed::Book => "depth_book",
+    };
+    loop {
+        let session = async {
+            let ws = StandXWebSocket::without_auth_with_verbose(verbose)?;
+            for symbol in &symbols {
+                ws.subscribe(channel, Some(symbol)).await?;
+            }
+            let (mut rx, _handle) = ws.connect_managed().await?;
+            while let Some(message) = rx.recv().await {
+                if tx.send((feed, message)).await.is_err() {
+                    break;
+                }
+            }
+            standx_sdk::Result::Ok(())
+        };
+        if let Err(error) = session.await {
+            let _ = tx
+                .send((feed, WsMessage::Error(format!("{channel}: {error}"))))
+                .await;
+        }
+        if tx.is_closed() {
+            return;
+        }
+        let _ = tx.send((feed, WsMessage::Disconnected)).await;
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Authenticated order/position/trade stream, reconnected with a fresh
+/// epoch after every drop.
+async fn run_account_feed(tx: mpsc::Sender<AccountEvent>) {
+    let mut epoch = 0;
+    loop {
+        epoch += 1;
+        let connected = match AccountStream::new(epoch) {
+            Ok(stream) => {
+                stream
+                    .connect(&[
+                        AccountChannel::Order,
+                        AccountChannel::Position,
+                        AccountChannel::Trade,
+                    ])
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+        match connected {
+            Ok((mut rx, _health, _handle)) => {
+                while let Some(event) = rx.recv().await {
+                    if tx.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                let reason = error.to_string();
+                if tx.send(AccountEvent::Error { reason }).await.is_err() {
+                    return;
+                }
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -0,0 +1,583 @@
+//! Pure dashboard state: everything the view draws and every key binding.
+//!
+//! Nothing here touches the terminal or the network, so stream folding,
+//! navigation and the confirmation flow are unit-tested directly.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use standx_sdk::account_stream::{OrderUpdate, PositionUpdate, TradeUpdate};
+use standx_sdk::models::{Order, OrderBook, OrderSide, OrderStatus, Position, PriceData};
+use std::collections::{HashMap, VecDeque};
+
+/// Fills kept for the recent-fills pane.
+const MAX_FILLS: usize = 50;
+
+/// Latest prices for one symbol.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct Ticker {
+    pub mark: Option<f64>,
+    pub index: Option<f64>,
+    pub last: Option<f64>,
+}
+
+/// An open position; `qty` is signed (negative = short).
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct PositionRow {
+    pub symbol: String,
+    pub qty: f64,
+    /// Unsigned quantity exactly as the venue reported it, reused verbatim
+    /// when flattening so no float formatting sneaks into the order.
+    pub qty_text: String,
+    pub entry: f64,
+    /// Venue-reported unrealized PnL, used until a mark price streams in.
+    pub reported_upnl: Option<f64>,
+}
+
+/// A resting order.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct OrderRow {
+    pub id: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub qty: String,
+    pub filled: String,
+    pub price: String,
+    pub status: OrderStatus,
+}
+
+/// One of our own executions.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Fill {
+    pub time: String,
+    pub symbol: String,
+    pub side: OrderSide,
+    pub price: String,
+    pub qty: String,
+}
+
+/// Selectable panes, in Tab order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Pane {
+    Tickers,
+    Positions,
+    Orders,
+}
+
+impl Pane {
+    fn next(self) -> Self {
+        match self {
+            Self::Tickers => Self::Positions,
+            Self::Positions => Self::Orders,
+            Self::Orders => Self::Tickers,
+        }
+    }
+
+    fn previous(self) -> Self {
+        match self {
+            Self::Tickers => Self::Orders,
+            Self::Positions => Self::Tickers,
+            Self::Orders => Self::Positions,
+        }
+    }
+}
+
+/// What the event loop should do after a key press.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Action {
+    Quit,
+    /// Move the depth pane to another symbol.
+    SwitchSymbol(String),
+    Cancel {
+        symbol: String,
+        order_id: String,
+    },
+    /// Reduce-only market order closing `qty` on `symbol`.
+    Flatten {
+        symbol: String,
+        side: OrderSide,
+        qty: String,
+    },
+}
+
+impl Action {
+    /// Prompt shown in the confirmation modal.
+    pub(super) fn prompt(&self) -> String {
+        match self {
+            Self::Cancel { symbol, order_id } => format!("Cancel order {order_id} on {symbol}?"),
+            Self::Flatten { symbol, side, qty } => format!(
+                "Flatten {symbol}: {} {qty} at market (reduce-only)?",
+                side_label(*side)
+            ),
+            Self::Quit | Self::SwitchSymbol(_) => String::new(),
+        }
+    }
+}
+
+pub(super) fn side_label(side: OrderSide) -> &'static str {
+    match side {
+        OrderSide::Buy => "BUY",
+        OrderSide::Sell => "SELL",
+    }
+}
+
+fn parse_f64(raw: &str) -> Option<f64> {
+    raw.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+/// `HH:MM:SS` (UTC) from an epoch-millis or RFC 3339 timestamp; anything else
+/// is shown as received.
+fn short_time(raw: &str) -> String {
+    let parsed = match raw.parse::<i64>() {
+        Ok(millis) => chrono::DateTime::from_timestamp_millis(millis),
+        Err(_) => chrono::DateTime::parse_from_rfc3339(raw)
+            .ok()
+            .map(|time| time.to_utc()),
+    };
+    parsed.map_or_else(
+        || raw.to_string(),
+        |time| time.format("%H:%M:%S").to_string(),
+    )
+}
+
+fn is_open(status: OrderStatus) -> bool {
+    matches!(
+        status,
+        OrderStatus::New
+            | OrderStatus::Open
+            | OrderStatus::PartiallyFilled
+            | OrderStatus::Untriggered
+    )
+}
+
+/// Whole dashboard state.
+#[derive(Debug)]
+pub(super) struct App {
+    /// Tracked symbols, in ticker-pane order.
+    pub symbols: Vec<String>,
+    /// Symbol whose depth is shown.
+    pub selected: String,
+    pub tickers: HashMap<String, Ticker>,
+    pub positions: Vec<PositionRow>,
+    pub orders: Vec<OrderRow>,
+    pub depth: Option<OrderBook>,
+    pub fills: VecDeque<Fill>,
+    pub focus: Pane,
+    /// Cursor per pane: tickers, positions, orders.
+    cursors: [usize; 3],
+    /// Destructive action awaiting confirmation.
+    pub modal: Option<Action>,
+    pub status: String,
+    pub authenticated: bool,
+    pub market_connected: bool,
+    pub account_connected: bool,
+}
+
+impl App {
+    pub(super) fn new(symbols: Vec<String>, authenticated: bool) -> Self {
+        let selected = symbols.first().cloned().unwrap_or_default();
+        Self {
+            symbols,
+            selected,
+            tickers: HashMap::new(),
+            positions: Vec::new(),
+            orders: Vec::new(),
+            depth: None,
+            fills: VecDeque::new(),
+            focus: Pane::Tickers,
+            cursors: [0; 3],
+            modal: None,
+            status: String::new(),
+            authenticated,
+            market_connected: false,
+            account_connected: false,
+        }
+    }
+
+    /// Cursor row of `pane`, clamped to its current length.
+    pub(super) fn cursor(&self, pane: Pane) -> usize {
+        let len = self.pane_len(pane);
+        self.cursors[pane as usize].min(len.saturating_sub(1))
+    }
+
+    fn pane_len(&self, pane: Pane) -> usize {
+        match pane {
+            Pane::Tickers => self.symbols.len(),
+            Pane::Positions => self.positions.len(),
+            Pane::Orders => self.orders.len(),
+        }
+    }
+
+    fn move_cursor(&mut self, delta: isize) {
+        let len = self.pane_len(self.focus);
+        if len == 0 {
+            return;
+        }
+        let current = self.cursor(self.focus) as isize;
+        self.cursors[self.focus as usize] = (current + delta).clamp(0, len as isize - 1) as usize;
+    }
+
+    /// Unrealized PnL of `position` at the streamed mark price.
+    pub(super) fn upnl(&self, position: &PositionRow) -> Option<f64> {
+        self.tickers
+            .get(&position.symbol)
+            .and_then(|ticker| ticker.mark)
+            .map(|mark| (mark - position.entry) * position.qty)
+            .or(position.reported_upnl)
+    }
+
+    pub(super) fn set_positions(&mut self, positions: &[Position]) {
+        self.positions = positions
+            .iter()
+            .filter_map(|position| {
+                let qty = parse_f64(&position.qty)?;
+                let signed = match position.side {
+                    Some(OrderSide::Sell) => -qty.abs(),
+                    Some(OrderSide::Buy) => qty.abs(),
+                    None => qty,
+                };
+                (signed != 0.0).then(|| PositionRow {
+                    symbol: position.symbol.to_uppercase(),
+                    qty: signed,
+                    qty_text: position.qty.trim_start_matches('-').to_string(),
+                    entry: parse_f64(&position.entry_price).unwrap_or(0.0),
+                    reported_upnl: parse_f64(&position.upnl),
+                })
+            })
+            .collect();
+        self.positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    }
+
+    pub(super) fn set_orders(&mut self, orders: &[Order]) {
+        self.orders = orders
+            .iter()
+            .filter(|order| is_open(order.status))
+            .map(|order| OrderRow {
+                id: order.id.clone(),
+                symbol: order.symbol.to_uppercase(),
+                side: order.side,
+                qty: order.qty.clone(),
+                filled: order.fill_qty.clone(),
+                price: order.price.clone(),
+                status: order.status,
+            })
+            .collect();
+    }
+
+    pub(super) fn apply_price(&mut self, price: &PriceData) {
+        let ticker = self.tickers.entry(price.symbol.to_uppercase()).or_default();
+        ticker.mark = parse_f64(&price.mark_price).or(ticker.mark);
+        ticker.index = parse_f64(&price.index_price).or(ticker.index);
+        ticker.last = parse_f64(&price.last_price).or(ticker.last);
+    }
+
+    /// Take a depth snapshot if it belongs to the selected symbol; books
+    /// still in flight from before a symbol switch are dropped.
+    pub(super) fn apply_depth(&mut self, book: OrderBook) {
+        if book.symbol.is_empty() || book.symbol.eq_ignore_ascii_case(&self.selected) {
+            self.depth = Some(book);
+        }
+    }
+
+    pub(super) fn apply_order(&mut self, update: &OrderUpdate) {
+        let id = update.order_id.to_string();
+        let existing = self.orders.iter().position(|order| order.id == id);
+        if !is_open(update.status) {
+            if let Some(index) = existing {
+                self.orders.remove(index);
+            }
+            return;
+        }
+        let row = OrderRow {
+            id,
+            symbol: update.symbol.to_uppercase(),
+            side: update.side,
+            qty: update.qty.clone(),
+            filled: update.fill_qty.clone(),
+            price: update.price.clone(),
+            status: update.status,
+        };
+        match existing {
+            Some(index) => self.orders[index] = row,
+            None => self.orders.push(row),
+        }
+    }
+
+    pub(super) fn apply_position(&mut self, update: &PositionUpdate) {
+        let symbol = update.symbol.to_uppercase();
+        let existing = self.positions.iter().position(|row| row.symbol == symbol);
+        let qty = parse_f64(&update.qty).unwrap_or(0.0);
+        let signed = match update.side {
+            Some(OrderSide::Sell) => -qty.abs(),
+            Some(OrderSide::Buy) => qty.abs(),
+            None => qty,
+        };
+        if signed == 0.0 {
+            if let Some(index) = existing {
+                self.positions.remove(index);
+            }
+            return;
+        }
+        let row = PositionRow {
+            symbol,
+            qty: signed,
+            qty_text: update.qty.trim_start_matches('-').to_string(),
+            entry: parse_f64(&update.entry_price).unwrap_or(0.0),
+            reported_upnl: None,
+        };
+        match existing {
+            Some(index) => self.positions[index] = row,
+            None => {
+                self.positions.push(row);
+                self.positions.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+            }
+        }
+    }
+
+    pub(super) fn apply_fill(&mut self, trade: &TradeUpdate) {
+        self.fills.push_front(Fill {
+            time: short_time(&trade.trade_ts),
+            symbol: trade.symbol.to_uppercase(),
+            side: trade.side,
+            price: trade.price.clone(),
+            qty: trade.qty.clone(),
+        });
+        self.fills.truncate(MAX_FILLS);
+    }
+
+    fn select_symbol(&mut self, symbol: String) -> Option<Action> {
+        if symbol == self.selected {
+            return None;
+        }
+        self.selected = symbol.clone();
+        self.depth = None;
+        if let Some(index) = self.symbols.iter().position(|s| *s == symbol) {
+            self.cursors[Pane::Tickers as usize] = index;
+        }
+        Some(Action::SwitchSymbol(symbol))
+    }
+
+    fn step_symbol(&mut self, delta: isize) -> Option<Action> {
+        if self.symbols.is_empty() {
+            return None;
+        }
+        let len = self.symbols.len() as isize;
+        let current = self
+            .symbols
+            .iter()
+            .position(|symbol| *symbol == self.selected)
+            .unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(len) as usize;
+        self.select_symbol(self.symbols[next].clone())
+    }
+
+    /// Apply a key press. Destructive requests only open the confirmation
+    /// modal; the action is returned once the user confirms it.
+    pub(super) fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        if self.modal.is_some() {
+            return match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => self.modal.take(),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    self.modal = None;
+                    self.status = "cancelled".to_string();
+                    None
+                }
+                _ => None,
+            };
+        }
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(Action::Quit),
+            KeyCode::Tab => self.focus = self.focus.next(),
+            KeyCode::BackTab => self.focus = self.focus.previous(),
+            KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+            KeyCode::Left | KeyCode::Char('[') => return self.step_symbol(-1),
+            KeyCode::Right | KeyCode::Char(']') => return self.step_symbol(1),
+            KeyCode::Enter if self.focus == Pane::Tickers => {
+                let symbol = self.symbols.get(self.cursor(Pane::Tickers)).cloned()?;
+                return self.select_symbol(symbol);
+            }
+            KeyCode::Char('c') | KeyCode::Delete if self.focus == Pane::Orders => {
+                self.request(self.selected_cancel());
+            }
+            KeyCode::Char('x') | KeyCode::Char('f') if self.focus == Pane::Positions => {
+                self.request(self.selected_flatten());
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn request(&mut self, action: Option<Action>) {
+        if !self.authenticated {
+            self.status = "not authenticated — run 'standx auth login'".to_string();
+            return;
+        }
+        match action {
+            Some(action) => self.modal = Some(action),
+            None => self.status = "nothing selected".to_string(),
+        }
+    }
+
+    fn selected_cancel(&self) -> Option<Action> {
+        let order = self.orders.get(self.cursor(Pane::Orders))?;
+        Some(Action::Cancel {
+            symbol: order.symbol.clone(),
+            order_id: order.id.clone(),
+        })
+    }
+
+    fn selected_flatten(&self) -> Option<Action> {
+        let position = self.positions.get(self.cursor(Pane::Positions))?;
+        Some(Action::Flatten {
+            symbol: position.symbol.clone(),
+            side: if position.qty > 0.0 {
+                OrderSide::Sell
+            } else {
+                OrderSide::Buy
+            },
+            qty: position.qty_text.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn order_update(id: u64, status: OrderStatus) -> OrderUpdate {
+        OrderUpdate {
+            seq: 1,
+            order_id: id,
+            cl_ord_id: None,
+            symbol: "btc-usd".to_string(),
+            side: OrderSide::Buy,
+            qty: "0.5".to_string(),
+            fill_qty: "0".to_string(),
+            fill_avg_price: String::new(),
+            price: "60000".to_string(),
+            status,
+            reduce_only: false,
+            updated_at: String::new(),
+        }
+    }
+
+    fn position_update(side: Option<OrderSide>, qty: &str) -> PositionUpdate {
+        PositionUpdate {
+            seq: 1,
+            id: 1,
+            symbol: "ETH-USD".to_string(),
+            side,
+            qty: qty.to_string(),
+            entry_price: "2000".to_string(),
+            realized_pnl: "0".to_string(),
+            status: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn app() -> App {
+        App::new(vec!["BTC-USD".to_string(), "ETH-USD".to_string()], true)
+    }
+
+    #[test]
+    fn order_updates_upsert_and_drop_terminal_orders() {
+        let mut app = app();
+        app.apply_order(&order_update(7, OrderStatus::New));
+        app.apply_order(&order_update(7, OrderStatus::PartiallyFilled));
+        assert_eq!(app.orders.len(), 1);
+        assert_eq!(app.orders[0].symbol, "BTC-USD");
+        assert_eq!(app.orders[0].status, OrderStatus::PartiallyFilled);
+
+        app.apply_order(&order_update(7, OrderStatus::Canceled));
+        assert!(app.orders.is_empty());
+    }
+
+    #[test]
+    fn position_updates_track_sign_and_mark_to_market() {
+        let mut app = app();
+        app.apply_position(&position_update(Some(OrderSide::Sell), "2"));
+        assert_eq!(app.positions[0].qty, -2.0);
+
+        app.tickers.insert(
+            "ETH-USD".to_string(),
+            Ticker {
+                mark: Some(1_900.0),
+                ..Ticker::default()
+            },
+        );
+        assert_eq!(app.upnl(&app.positions[0]), Some(200.0));
+
+        app.apply_position(&position_update(Some(OrderSide::Sell), "0"));
+        assert!(app.positions.is_empty());
+    }
+
+    #[test]
+    fn destructive_actions_need_confirmation() {
+        let mut app = app();
+        app.apply_position(&position_update(Some(OrderSide::Buy), "1.25"));
+        app.focus = Pane::Positions;
+
+        assert_eq!(app.handle_key(key(KeyCode::Char('x'))), None);
+        assert!(app.modal.is_some());
+        // Unrelated keys neither confirm nor dismiss.
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), None);
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('y'))),
+            Some(Action::Flatten {
+                symbol: "ETH-USD".to_string(),
+                side: OrderSide::Sell,
+                qty: "1.25".to_string(),
+            })
+        );
+        assert!(app.modal.is_none());
+
+        app.apply_order(&order_update(9, OrderStatus::New));
+        app.focus = Pane::Orders;
+        assert_eq!(app.handle_key(key(KeyCode::Char('c'))), None);
+        assert_eq!(app.handle_key(key(KeyCode::Esc)), None);
+        assert!(app.modal.is_none());
+    }
+
+    #[test]
+    fn unauthenticated_sessions_cannot_request_orders() {
+        let mut app = App::new(vec!["BTC-USD".to_string()], false);
+        app.apply_order(&order_update(1, OrderStatus::New));
+        app.focus = Pane::Orders;
+        assert_eq!(app.handle_key(key(KeyCode::Char('c'))), None);
+        assert!(app.modal.is_none());
+    }
+
+    #[test]
+    fn switching_symbol_resets_depth_and_ignores_stale_books() {
+        let mut app = app();
+        assert_eq!(
+            app.handle_key(key(KeyCode::Right)),
+            Some(Action::SwitchSymbol("ETH-USD".to_string()))
+        );
+        assert_eq!(app.selected, "ETH-USD");
+        assert_eq!(app.cursor(Pane::Tickers), 1);
+
+        let stale = OrderBook {
+            symbol: "BTC-USD".to_string(),
+            bids: vec![],
+            asks: vec![],
+            timestamp: String::new(),
+        };
+        app.apply_depth(stale);
+        assert!(app.depth.is_none());
+
+        // Wraps around, and Enter on the highlighted ticker is a no-op when
+        // it is already selected.
+        assert_eq!(
+            app.handle_key(key(KeyCode::Right)),
+            Some(Action::SwitchSymbol("BTC-USD".to_string()))
+        );
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+    }
+}
@@ -0,0 +1,333 @@
+//! ratatui rendering of [`App`].
+
+use super::state::{side_label, Action, App, Pane};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Row, Table, TableState};
+use ratatui::Frame;
+use standx_sdk::models::OrderSide;
+
+/// Book levels shown per side.
+const DEPTH_LEVELS: usize = 10;
+
+const HELP: &str = "q quit · Tab pane · ↑↓ select · ←→/Enter symbol · c cancel order · x flatten";
+
+fn price(value: Option<f64>) -> String {
+    value.map_or_else(|| "-".to_string(), |value| format!("{value:.2}"))
+}
+
+fn side_style(side: OrderSide) -> Style {
+    match side {
+        OrderSide::Buy => Style::default().fg(Color::Green),
+        OrderSide::Sell => Style::default().fg(Color::Red),
+    }
+}
+
+fn pnl_style(value: Option<f64>) -> Style {
+    match value {
+        Some(value) if value > 0.0 => Style::default().fg(Color::Green),
+        Some(value) if value < 0.0 => Style::default().fg(Color::Red),
+        _ => Style::default(),
+    }
+}
+
+fn pane_block(title: String, focused: bool) -> Block<'static> {
+    let style = if focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(style)
+        .title(title)
+}
+
+fn header(cells: &[&'static str]) -> Row<'static> {
+    Row::new(cells.to_vec()).style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+fn highlight() -> Style {
+    Style::default().add_modifier(Modifier::REVERSED)
+}
+
+/// Draw one frame.
+pub(super) fn draw(frame: &mut Frame, app: &App) {
+    let [body, footer] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(10), Constraint::Length(2)])
+        .areas(frame.area());
+    let [left, right] = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(62), Constraint::Percentage(38)])
+        .areas(body);
+    let [tickers, positions, orders] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+        ])
+        .areas(left);
+    let [depth, fills] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .areas(right);
+
+    draw_tickers(frame, app, tickers);
+    draw_positions(frame, app, positions);
+    draw_orders(frame, app, orders);
+    draw_depth(frame, app, depth);
+    draw_fills(frame, app, fills);
+    draw_footer(frame, app, footer);
+    if let Some(action) = &app.modal {
+        draw_modal(frame, action);
+    }
+}
+
+fn draw_tickers(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.symbols.iter().map(|symbol| {
+        let ticker = app.tickers.get(symbol).cloned().unwrap_or_default();
+        let marker = if *symbol == app.selected { "▶" } else { " " };
+        Row::new(vec![
+            format!("{marker} {symbol}"),
+            price(ticker.mark),
+            price(ticker.index),
+            price(ticker.last),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header(&["SYMBOL", "MARK", "INDEX", "LAST"]))
+    .block(pane_block(
+        "Tickers".to_string(),
+        app.focus == Pane::Tickers,
+    ))
+    .row_highlight_style(highlight());
+    render_table(frame, app, Pane::Tickers, table, area);
+}
+
+fn draw_positions(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.positions.iter().map(|position| {
+        let upnl = app.upnl(position);
+        let (side, style) = if position.qty > 0.0 {
+            ("LONG", side_style(OrderSide::Buy))
+        } else {
+            ("SHORT", side_style(OrderSide::Sell))
+        };
+        Row::new(vec![
+            Span::raw(position.symbol.clone()),
+            Span::styled(side, style),
+            Span::raw(position.qty_text.clone()),
+            Span::raw(format!("{:.2}", position.entry)),
+            Span::styled(price(upnl), pnl_style(upnl)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(12),
+            Constraint::Length(6),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(12),
+        ],
+    )
+    .header(header(&["SYMBOL", "SIDE", "QTY", "ENTRY", "UPNL"]))
+    .block(pane_block(
+        account_title("Positions", app),
+        app.focus == Pane::Positions,
+    ))
+    .row_highlight_style(highlight());
+    render_table(frame, app, Pane::Positions, table, area);
+}
+
+fn draw_orders(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.orders.iter().map(|order| {
+        Row::new(vec![
+            Span::raw(order.id.clone()),
+            Span::raw(order.symbol.clone()),
+            Span::styled(side_label(order.side), side_style(order.side)),
+            Span::raw(order.price.clone()),
+            Span::raw(format!("{}/{}", order.filled, order.qty)),
+            Span::raw(format!("{:?}", order.status)),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(14),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(12),
+            Constraint::Length(14),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header(&[
+        "ID", "SYMBOL", "SIDE", "PRICE", "FILLED", "STATUS",
+    ]))
+    .block(pane_block(
+        account_title("Open orders", app),
+        app.focus == Pane::Orders,
+    ))
+    .row_highlight_style(highlight());
+    render_table(frame, app, Pane::Orders, table, area);
+}
+
+fn account_title(title: &str, app: &App) -> String {
+    if app.authenticated {
+        title.to_string()
+    } else {
+        format!("{title} (not authenticated)")
+    }
+}
+
+fn render_table(frame: &mut Frame, app: &App, pane: Pane, table: Table, area: Rect) {
+    let mut state = TableState::default();
+    if app.focus == pane {
+        state.select(Some(app.cursor(pane)));
+    }
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_depth(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Depth {}", app.selected));
+    let Some(book) = &app.depth else {
+        frame.render_widget(Paragraph::new("waiting for book…").block(block), area);
+        return;
+    };
+    // Best prices meet at the spread row: asks descending above it, bids
+    // descending below it.
+    let mut asks = sorted_levels(&book.asks);
+    asks.truncate(DEPTH_LEVELS);
+    asks.reverse();
+    let mut bids = sorted_levels(&book.bids);
+    bids.reverse();
+    let ask_rows = asks.into_iter().map(|level| {
+        Row::new(vec![level[0].clone(), level[1].clone()]).style(side_style(OrderSide::Sell))
+    });
+    let spread = match (book.best_bid(), book.best_ask()) {
+        (Some(bid), Some(ask)) => match (bid.parse::<f64>(), ask.parse::<f64>()) {
+            (Ok(bid), Ok(ask)) => format!("spread {:.2}", ask - bid),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    };
+    let spread_row =
+        Row::new(vec![spread, String::new()]).style(Style::default().add_modifier(Modifier::DIM));
+    let bid_rows = bids.into_iter().take(DEPTH_LEVELS).map(|level| {
+        Row::new(vec![level[0].clone(), level[1].clone()]).style(side_style(OrderSide::Buy))
+    });
+    let rows: Vec<Row> = ask_rows
+        .chain(std::iter::once(spread_row))
+        .chain(bid_rows)
+        .collect();
+    let table = Table::new(rows, [Constraint::Length(14), Constraint::Length(14)])
+        .header(header(&["PRICE", "QTY"]))
+        .block(block);
+    frame.render_widget(table, area);
+}
+
+/// Levels ordered by ascending price; unparsable levels are dropped.
+fn sorted_levels(levels: &[[String; 2]]) -> Vec<&[String; 2]> {
+    let mut levels: Vec<(f64, &[String; 2])> = levels
+        .iter()
+        .filter_map(|level| Some((level[0].parse::<f64>().ok()?, level)))
+        .collect();
+    levels.sort_by(|a, b| a.0.total_cmp(&b.0));
+    levels.into_iter().map(|(_, level)| level).collect()
+}
+
+fn draw_fills(frame: &mut Frame, app: &App, area: Rect) {
+    let rows = app.fills.iter().map(|fill| {
+        Row::new(vec![
+            Span::raw(fill.time.clone()),
+            Span::raw(fill.symbol.clone()),
+            Span::styled(side_label(fill.side), side_style(fill.side)),
+            Span::raw(fill.price.clone()),
+            Span::raw(fill.qty.clone()),
+        ])
+    });
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(12),
+            Constraint::Min(8),
+        ],
+    )
+    .header(header(&["TIME", "SYMBOL", "SIDE", "PRICE", "QTY"]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(account_title("Recent fills", app)),
+    );
+    frame.render_widget(table, area);
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let link = |label: &'static str, up: bool| {
+        let style = if up {
+            Style::default().fg(Color::Green)
+        } else {
+            Style::default().fg(Color::Red)
+        };
+        Span::styled(format!("{label} {} ", if up { "●" } else { "○" }), style)
+    };
+    let mut status = vec![link("market", app.market_connected)];
+    if app.authenticated {
+        status.push(link("account", app.account_connected));
+    }
+    status.push(Span::raw(app.status.clone()));
+    let lines = vec![
+        Line::from(status),
+        Line::from(Span::styled(
+            HELP,
+            Style::default().add_modifier(Modifier::DIM),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(lines), area);
+}
+
+fn draw_modal(frame: &mut Frame, action: &Action) {
+    let area = frame.area();
+    let width = 60.min(area.width);
+    let height = 5.min(area.height);
+    let modal = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    let lines = vec![
+        Line::from(action.prompt()),
+        Line::from(""),
+        Line::from(Span::styled(
+            "y / Enter confirm · n / Esc cancel",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    frame.render_widget(Clear, modal);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title("Confirm"),
+        ),
+        modal,
+    );
+}
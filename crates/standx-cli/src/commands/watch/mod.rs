@@ -0,0 +1,571 @@
+//! `standx watch` — alert rules evaluated against live market data
+//!
+//! Mark/index/last and book metrics come from the public `price` and
+//! `depth_book` streams and are evaluated on every update; funding rates and
+//! positions are polled over REST every `--poll-secs` (positions only when a
+//! position rule exists, which requires authentication). Firing and resolved
+//! transitions go through the maker's notifier, so the same Slack, Feishu,
+//! Telegram and raw webhook formats apply.
+//!
+//! The rules file is re-read when it changes on disk or on SIGHUP. A file
+//! that fails to parse is reported and the previous rules stay in force;
+//! rules that survive a reload keep their firing/cooldown state.
+
+mod rules;
+
+use super::maker::MakerNotifier;
+use super::util::shutdown_signal;
+use crate::cli::{OutputFormat, WatchArgs};
+use anyhow::{bail, Context, Result};
+use rules::{parse_duration, parse_rule, parse_rules, Metric, Rule, RuleState, Transition};
+use standx_maker::Alert;
+use standx_sdk::client::StandXClient;
+use standx_sdk::models::{OrderSide, Position};
+use standx_sdk::websocket::{StandXWebSocket, WsMessage};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc;
+
+/// How long the feed waits before rebuilding a dropped connection.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Label for account-wide position rules.
+const ACCOUNT_LABEL: &str = "ACCOUNT";
+
+/// Latest observed values for one symbol.
+#[derive(Debug, Clone, Default)]
+struct SymbolMetrics {
+    mark: Option<f64>,
+    index: Option<f64>,
+    last: Option<f64>,
+    bid: Option<f64>,
+    ask: Option<f64>,
+    funding: Option<f64>,
+}
+
+/// Position metrics: signed quantity, unrealized PnL and absolute notional.
+#[derive(Debug, Clone, Copy, Default)]
+struct PositionMetrics {
+    qty: f64,
+    pnl: f64,
+    notional: f64,
+}
+
+#[derive(Debug, Default)]
+struct MarketState {
+    symbols: HashMap<String, SymbolMetrics>,
+    /// `None` until the first successful position poll.
+    positions: Option<HashMap<String, PositionMetrics>>,
+}
+
+impl MarketState {
+    /// Value of `metric` for `symbol` (`None` = whole account, positions only).
+    fn value(&self, metric: Metric, symbol: Option<&str>) -> Option<f64> {
+        if metric.is_position() {
+            let positions = self.positions.as_ref()?;
+            let metrics = match symbol {
+                Some(symbol) => positions.get(symbol).copied().unwrap_or_default(),
+                None => positions
+                    .values()
+                    .fold(PositionMetrics::default(), |total, position| {
+                        PositionMetrics {
+                            qty: total.qty + position.qty,
+                            pnl: total.pnl + position.pnl,
+                            notional: total.notional + position.notional,
+                        }
+                    }),
+            };
+            return Some(match metric {
+                Metric::PositionPnl => metrics.pnl,
+                Metric::PositionQty => metrics.qty,
+                _ => metrics.notional,
+            });
+        }
+        let metrics = self.symbols.get(symbol?)?;
+        let mid = metrics
+            .bid
+            .zip(metrics.ask)
+            .map(|(bid, ask)| (bid + ask) / 2.0);
+        match metric {
+            Metric::Mark => metrics.mark,
+            Metric::Index => metrics.index,
+            Metric::Last => metrics.last,
+            Metric::Bid => metrics.bid,
+            Metric::Ask => metrics.ask,
+            Metric::Mid => mid,
+            Metric::SpreadBps => {
+                let (bid, ask, mid) = (metrics.bid?, metrics.ask?, mid?);
+                (mid > 0.0).then(|| (ask - bid) / mid * 1e4)
+            }
+            Metric::Funding => metrics.funding,
+            _ => None,
+        }
+    }
+}
+
+/// A rule bound to one symbol (or to the account).
+#[derive(Debug, Clone)]
+struct BoundRule {
+    rule: Rule,
+    symbol: Option<String>,
+    /// Stable identity for carrying state across reloads.
+    key: String,
+}
+
+impl BoundRule {
+    fn label(&self) -> &str {
+        self.symbol.as_deref().unwrap_or(ACCOUNT_LABEL)
+    }
+}
+
+/// Expand rules without a symbol over `default_symbols`; account-wide
+/// position rules stay unbound.
+fn bind_rules(rules: Vec<Rule>, default_symbols: &[String]) -> Result<Vec<BoundRule>> {
+    let mut bound = Vec::new();
+    for rule in rules {
+        let symbols = match (&rule.symbol, rule.metric.is_position()) {
+            (Some(symbol), _) => vec![Some(symbol.clone())],
+            (None, true) => vec![None],
+            (None, false) if default_symbols.is_empty() => bail!(
+                "rule '{}' names no symbol; prefix it with one or pass --symbols",
+                rule.text
+            ),
+            (None, false) => default_symbols.iter().cloned().map(Some).collect(),
+        };
+        for symbol in symbols {
+            let key = format!("{}|{}", symbol.as_deref().unwrap_or("*"), rule.text);
+            bound.push(BoundRule {
+                rule: rule.clone(),
+                symbol,
+                key,
+            });
+        }
+    }
+    Ok(bound)
+}
+
+/// Rules from the file plus `--rule` arguments.
+fn load_rules(args: &WatchArgs, default_symbols: &[String]) -> Result<Vec<BoundRule>> {
+    let mut rules = Vec::new();
+    if let Some(path) = &args.rules {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read rules file '{}'", path.display()))?;
+        rules.extend(parse_rules(&contents)?);
+    }
+    for rule in &args.rule {
+        rules.push(parse_rule(rule).with_context(|| format!("--rule '{rule}'"))?);
+    }
+    if rules.is_empty() {
+        bail!("no rules; pass --rules FILE or --rule \"BTC-USD mark > 70000\"");
+    }
+    bind_rules(rules, default_symbols)
+}
+
+fn modified_at(path: &Option<PathBuf>) -> Option<SystemTime> {
+    std::fs::metadata(path.as_ref()?).ok()?.modified().ok()
+}
+
+/// Rule set with its evaluation state.
+struct Watcher {
+    rules: Vec<BoundRule>,
+    states: HashMap<String, RuleState>,
+    cooldown: Duration,
+}
+
+impl Watcher {
+    /// Swap in a new rule set, keeping state for rules that are still present.
+    fn replace_rules(&mut self, rules: Vec<BoundRule>) {
+        self.states
+            .retain(|key, _| rules.iter().any(|rule| &rule.key == key));
+        self.rules = rules;
+    }
+
+    /// Market symbols referenced by the rules.
+    fn symbols(&self) -> BTreeSet<String> {
+        self.rules
+            .iter()
+            .filter(|rule| !rule.rule.metric.is_position())
+            .filter_map(|rule| rule.symbol.clone())
+            .collect()
+    }
+
+    fn needs(&self, predicate: impl Fn(Metric) -> bool) -> bool {
+        self.rules.iter().any(|rule| predicate(rule.rule.metric))
+    }
+
+    /// Evaluate the rules `filter` selects against `state`.
+    fn evaluate(
+        &mut self,
+        state: &MarketState,
+        filter: impl Fn(&BoundRule) -> bool,
+    ) -> Vec<(Alert, String)> {
+        let now = Instant::now();
+        let mut alerts = Vec::new();
+        for rule in self.rules.iter().filter(|rule| filter(rule)) {
+            let Some(value) = state.value(rule.rule.metric, rule.symbol.as_deref()) else {
+                continue;
+            };
+            let rule_state = self.states.entry(rule.key.clone()).or_default();
+            let Some(transition) = rule.rule.evaluate(rule_state, value, now, self.cooldown) else {
+                continue;
+            };
+            let message = match transition {
+                Transition::Fired => format!("{} (now {})", rule.rule.text, format_value(value)),
+                Transition::Resolved => {
+                    format!("{} cleared (now {})", rule.rule.text, format_value(value))
+                }
+            };
+            alerts.push((
+                Alert {
+                    kind: rule.rule.metric.name(),
+                    firing: transition == Transition::Fired,
+                    message,
+                },
+                rule.label().to_string(),
+            ));
+        }
+        alerts
+    }
+}
+
+fn format_value(value: f64) -> String {
+    if value != 0.0 && value.abs() < 0.01 {
+        format!("{:.4}%", value * 100.0)
+    } else {
+        format!("{value:.4}")
+    }
+}
+
+fn parse_f64(raw: &str) -> Option<f64> {
+    raw.parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+fn position_metrics(positions: &[Position]) -> HashMap<String, PositionMetrics> {
+    let mut metrics: HashMap<String, PositionMetrics> = HashMap::new();
+    for position in positions {
+        let qty = parse_f64(&position.qty).unwrap_or(0.0);
+        let signed = match position.side {
+            Some(OrderSide::Sell) => -qty.abs(),
+            Some(OrderSide::Buy) => qty.abs(),
+            None => qty,
+        };
+        let entry = metrics.entry(position.symbol.to_uppercase()).or_default();
+        entry.qty += signed;
+        entry.pnl += parse_f64(&position.upnl).unwrap_or(0.0);
+        entry.notional += parse_f64(&position.position_value).unwrap_or(0.0).abs();
+    }
+    metrics
+}
+
+/// Handle the watch command
+pub async fn handle_watch(
+    args: WatchArgs,
+    output_format: OutputFormat,
+    verbose: bool,
+) -> Result<()> {
+    let default_symbols: Vec<String> = args
+        .symbols
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|symbol| !symbol.is_empty())
+        .map(str::to_uppercase)
+        .collect();
+    let cooldown = parse_duration(&args.cooldown).context("--cooldown")?;
+    let mut watcher = Watcher {
+        rules: load_rules(&args, &default_symbols)?,
+        states: HashMap::new(),
+        cooldown,
+    };
+    let notifier = MakerNotifier::new(
+        output_format,
+        args.alert_webhook.clone(),
+        args.alert_webhook_format,
+    );
+    let client = StandXClient::new()?;
+    let mut state = MarketState::default();
+
+    let mut feed_symbols = watcher.symbols();
+    let (tx, mut rx) = mpsc::channel::<WsMessage>(1024);
+    let mut feed = tokio::spawn(run_feed(feed_symbols.clone(), tx.clone(), verbose));
+    eprintln!(
+        "watch: {} rule(s) on {} — press Ctrl+C to stop",
+        watcher.rules.len(),
+        describe_symbols(&feed_symbols)
+    );
+    for rule in &watcher.rules {
+        eprintln!("  [{}] {}", rule.label(), rule.rule.text);
+    }
+
+    let mut poll_tick = tokio::time::interval(Duration::from_secs(args.poll_secs.max(1)));
+    let mut reload_tick = tokio::time::interval(Duration::from_secs(args.reload_secs.max(1)));
+    reload_tick.reset();
+    let mut rules_modified = modified_at(&args.rules);
+    #[cfg(unix)]
+    let mut hangup = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()).ok();
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        let reload = tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            _ = async {
+                #[cfg(unix)]
+                if let Some(hangup) = hangup.as_mut() {
+                    hangup.recv().await;
+                    return;
+                }
+                std::future::pending::<()>().await
+            } => true,
+            _ = reload_tick.tick() => {
+                let modified = modified_at(&args.rules);
+                let changed = modified != rules_modified;
+                rules_modified = modified;
+                changed
+            }
+            message = rx.recv() => {
+                let Some(message) = message else { break };
+                if let Some(symbol) = apply_message(&mut state, message) {
+                    let alerts = watcher.evaluate(&state, |rule| {
+                        !rule.rule.metric.is_position()
+                            && rule.rule.metric != Metric::Funding
+                            && rule.symbol.as_deref() == Some(symbol.as_str())
+                    });
+                    notify(&notifier, alerts).await;
+                }
+                false
+            }
+            _ = poll_tick.tick() => {
+                poll(&client, &watcher, &mut state).await;
+                let alerts = watcher.evaluate(&state, |rule| {
+                    rule.rule.metric.is_position() || rule.rule.metric == Metric::Funding
+                });
+                notify(&notifier, alerts).await;
+                false
+            }
+        };
+        if !reload {
+            continue;
+        }
+        match load_rules(&args, &default_symbols) {
+            Ok(rules) => {
+                watcher.replace_rules(rules);
+                eprintln!("watch: reloaded {} rule(s)", watcher.rules.len());
+                let symbols = watcher.symbols();
+                if symbols != feed_symbols {
+                    feed.abort();
+                    feed_symbols = symbols;
+                    feed = tokio::spawn(run_feed(feed_symbols.clone(), tx.clone(), verbose));
+                    eprintln!("watch: now streaming {}", describe_symbols(&feed_symbols));
+                }
+            }
+            Err(error) => eprintln!("⚠️  watch: keeping previous rules; reload failed: {error:#}"),
+        }
+    }
+
+    feed.abort();
+    eprintln!("watch: stopped");
+    Ok(())
+}
+
+fn describe_symbols(symbols: &BTreeSet<String>) -> String {
+    if symbols.is_empty() {
+        "positions only".to_string()
+    } else {
+        symbols.iter().cloned().collect::<Vec<_>>().join(",")
+    }
+}
+
+/// Fold a stream message into `state`, returning the symbol it updated.
+fn apply_message(state: &mut MarketState, message: WsMessage) -> Option<String> {
+    match message {
+        WsMessage::Price(update) => {
+            let symbol = update.data.symbol.to_uppercase();
+            let metrics = state.symbols.entry(symbol.clone()).or_default();
+            metrics.mark = parse_f64(&update.data.mark_price).or(metrics.mark);
+            metrics.index = parse_f64(&update.data.index_price).or(metrics.index);
+            metrics.last = parse_f64(&update.data.last_price).or(metrics.last);
+            Some(symbol)
+        }
+        WsMessage::Depth(update) => {
+            let symbol = update.data.symbol.to_uppercase();
+            if symbol.is_empty() {
+                return None;
+            }
+            let metrics = state.symbols.entry(symbol.clone()).or_default();
+            metrics.bid = update.data.best_bid().and_then(parse_f64);
+            metrics.ask = update.data.best_ask().and_then(parse_f64);
+            Some(symbol)
+        }
+        _ => None,
+    }
+}
+
+/// Refresh funding rates and positions the rules need.
+async fn poll(client: &StandXClient, watcher: &Watcher, state: &mut MarketState) {
+    if watcher.needs(|metric| metric == Metric::Funding) {
+        for symbol in watcher.symbols() {
+            match client.get_symbol_market(&symbol).await {
+                Ok(market) => {
+                    state.symbols.entry(symbol).or_default().funding =
+                        parse_f64(&market.funding_rate);
+                }
+                Err(error) => eprintln!("⚠️  watch: funding poll for {symbol} failed: {error}"),
+            }
+        }
+    }
+    if watcher.needs(Metric::is_position) {
+        match client.get_positions(None).await {
+            Ok(positions) => state.positions = Some(position_metrics(&positions)),
+            Err(error) => eprintln!("⚠️  watch: position poll failed: {error}"),
+        }
+    }
+}
+
+async fn notify(notifier: &MakerNotifier, alerts: Vec<(Alert, String)>) {
+    for (alert, label) in alerts {
+        notifier.alert(&alert, &label, false).await;
+    }
+}
+
+/// Public price + depth stream for `symbols`; rebuilt when the managed
+/// connection gives up.
+async fn run_feed(symbols: BTreeSet<String>, tx: mpsc::Sender<WsMessage>, verbose: bool) {
+    if symbols.is_empty() {
+        return;
+    }
+    loop {
+        if let Err(error) = feed_session(&symbols, &tx, verbose).await {
+            eprintln!("⚠️  watch: feed error: {error:#}");
+        }
+        if tx.is_closed() {
+            return;
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+async fn feed_session(
+    symbols: &BTreeSet<String>,
+    tx: &mpsc::Sender<WsMessage>,
+    verbose: bool,
+) -> Result<()> {
+    let ws = StandXWebSocket::without_auth_with_verbose(verbose)
+        .context("failed to build StandX websocket client")?;
+    for symbol in symbols {
+        ws.subscribe("price", Some(symbol)).await?;
+        ws.subscribe("depth_book", Some(symbol)).await?;
+    }
+    let (mut rx, _handle) = ws
+        .connect_managed()
+        .await
+        .context("failed to connect StandX websocket")?;
+    while let Some(message) = rx.recv().await {
+        if tx.send(message).await.is_err() {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watcher(lines: &[&str], symbols: &[&str]) -> Watcher {
+        let rules = lines.iter().map(|line| parse_rule(line).unwrap()).collect();
+        let symbols: Vec<String> = symbols.iter().map(|symbol| symbol.to_string()).collect();
+        Watcher {
+            rules: bind_rules(rules, &symbols).unwrap(),
+            states: HashMap::new(),
+            cooldown: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn unbound_market_rules_expand_over_watched_symbols() {
+        let watcher = watcher(
+            &[
+                "funding < -0.01%",
+                "position pnl < -500",
+                "SOL-USD mark > 1",
+            ],
+            &["BTC-USD", "ETH-USD"],
+        );
+        let labels: Vec<&str> = watcher.rules.iter().map(BoundRule::label).collect();
+
+        assert_eq!(labels, vec!["BTC-USD", "ETH-USD", "ACCOUNT", "SOL-USD"]);
+        assert_eq!(
+            watcher.symbols().into_iter().collect::<Vec<_>>(),
+            vec!["BTC-USD", "ETH-USD", "SOL-USD"]
+        );
+        assert!(bind_rules(vec![parse_rule("mark > 1").unwrap()], &[]).is_err());
+    }
+
+    #[test]
+    fn spread_and_account_pnl_are_derived_from_state() {
+        let mut state = MarketState::default();
+        state.symbols.insert(
+            "BTC-USD".to_string(),
+            SymbolMetrics {
+                bid: Some(99.9),
+                ask: Some(100.1),
+                ..SymbolMetrics::default()
+            },
+        );
+        let spread = state.value(Metric::SpreadBps, Some("BTC-USD")).unwrap();
+        assert!((spread - 20.0).abs() < 1e-9);
+        assert_eq!(state.value(Metric::PositionPnl, None), None);
+
+        state.positions = Some(HashMap::from([
+            (
+                "BTC-USD".to_string(),
+                PositionMetrics {
+                    qty: 1.0,
+                    pnl: -300.0,
+                    notional: 100.0,
+                },
+            ),
+            (
+                "ETH-USD".to_string(),
+                PositionMetrics {
+                    qty: -2.0,
+                    pnl: -250.0,
+                    notional: 50.0,
+                },
+            ),
+        ]));
+        assert_eq!(state.value(Metric::PositionPnl, None), Some(-550.0));
+        assert_eq!(state.value(Metric::PositionQty, Some("SOL-USD")), Some(0.0));
+    }
+
+    #[test]
+    fn reload_keeps_state_of_surviving_rules() {
+        let mut watcher = watcher(&["BTC-USD mark > 100", "BTC-USD mark < 50"], &[]);
+        let mut state = MarketState::default();
+        state.symbols.insert(
+            "BTC-USD".to_string(),
+            SymbolMetrics {
+                mark: Some(120.0),
+                ..SymbolMetrics::default()
+            },
+        );
+        let fired = watcher.evaluate(&state, |_| true);
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].0.firing);
+        assert_eq!(fired[0].0.kind, "mark");
+
+        let reloaded = bind_rules(vec![parse_rule("BTC-USD  mark > 100").unwrap()], &[]).unwrap();
+        watcher.replace_rules(reloaded);
+
+        // Still firing after the reload: no duplicate alert.
+        assert!(watcher.evaluate(&state, |_| true).is_empty());
+        state.symbols.get_mut("BTC-USD").unwrap().mark = Some(90.0);
+        let resolved = watcher.evaluate(&state, |_| true);
+        assert!(!resolved[0].0.firing);
+        assert!(resolved[0].0.message.contains("cleared"));
+    }
+}
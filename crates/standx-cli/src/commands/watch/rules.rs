@@ -0,0 +1,355 @@
+//! Alert rule language for `standx watch`.
+//!
+//! One rule per line: `[SYMBOL] METRIC OP VALUE [hysteresis=VALUE]
+//! [cooldown=DURATION]`, e.g. `BTC-USD mark > 70000`, `funding < -0.01%`,
+//! `ETH-USD spread_bps > 20 hysteresis=5 cooldown=10m` or
+//! `position pnl < -500`. `#` starts a comment. Values accept a `%` suffix
+//! (`-0.01%` = `-0.0001`).
+//!
+//! A rule fires once when its condition starts holding and resolves once the
+//! value has moved back past the threshold by `hysteresis`, so a price
+//! hovering at the line does not flap. `cooldown` is the minimum time between
+//! two firings of the same rule.
+
+use anyhow::{anyhow, bail, Context, Result};
+use std::time::{Duration, Instant};
+
+/// A watched quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Metric {
+    Mark,
+    Index,
+    Last,
+    Bid,
+    Ask,
+    Mid,
+    SpreadBps,
+    Funding,
+    PositionPnl,
+    PositionQty,
+    PositionNotional,
+}
+
+impl Metric {
+    /// Parse a metric at the start of `tokens`, returning it and the number of
+    /// tokens it used (`position pnl` takes two).
+    fn parse(tokens: &[&str]) -> Option<(Self, usize)> {
+        let first = tokens.first()?.to_ascii_lowercase();
+        if first == "position" {
+            let second = tokens.get(1)?.to_ascii_lowercase();
+            return Self::parse_position(&second).map(|metric| (metric, 2));
+        }
+        if let Some(rest) = first.strip_prefix("position_") {
+            return Self::parse_position(rest).map(|metric| (metric, 1));
+        }
+        let metric = match first.as_str() {
+            "mark" => Self::Mark,
+            "index" => Self::Index,
+            "last" => Self::Last,
+            "bid" => Self::Bid,
+            "ask" => Self::Ask,
+            "mid" => Self::Mid,
+            "spread_bps" | "spread" => Self::SpreadBps,
+            "funding" => Self::Funding,
+            _ => return None,
+        };
+        Some((metric, 1))
+    }
+
+    fn parse_position(name: &str) -> Option<Self> {
+        match name {
+            "pnl" | "upnl" => Some(Self::PositionPnl),
+            "qty" | "size" => Some(Self::PositionQty),
+            "notional" | "value" => Some(Self::PositionNotional),
+            _ => None,
+        }
+    }
+
+    pub(super) fn name(self) -> &'static str {
+        match self {
+            Self::Mark => "mark",
+            Self::Index => "index",
+            Self::Last => "last",
+            Self::Bid => "bid",
+            Self::Ask => "ask",
+            Self::Mid => "mid",
+            Self::SpreadBps => "spread_bps",
+            Self::Funding => "funding",
+            Self::PositionPnl => "position_pnl",
+            Self::PositionQty => "position_qty",
+            Self::PositionNotional => "position_notional",
+        }
+    }
+
+    /// Needs authenticated position polling.
+    pub(super) fn is_position(self) -> bool {
+        matches!(
+            self,
+            Self::PositionPnl | Self::PositionQty | Self::PositionNotional
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    fn parse(token: &str) -> Option<Self> {
+        match token {
+            ">" => Some(Self::Gt),
+            ">=" => Some(Self::Ge),
+            "<" => Some(Self::Lt),
+            "<=" => Some(Self::Le),
+            _ => None,
+        }
+    }
+
+    fn above(self) -> bool {
+        matches!(self, Self::Gt | Self::Ge)
+    }
+}
+
+/// One parsed rule.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Rule {
+    /// Source text, whitespace-normalized; identifies the rule across reloads.
+    pub text: String,
+    /// Explicit symbol; `None` applies a market rule to every watched symbol
+    /// and a position rule to the whole account.
+    pub symbol: Option<String>,
+    pub metric: Metric,
+    op: Op,
+    threshold: f64,
+    hysteresis: f64,
+    cooldown: Option<Duration>,
+}
+
+/// Per-rule evaluation state, kept across rules-file reloads.
+#[derive(Debug, Clone, Default)]
+pub(super) struct RuleState {
+    firing: bool,
+    last_fired: Option<Instant>,
+}
+
+/// A state change worth notifying about.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Transition {
+    Fired,
+    Resolved,
+}
+
+impl Rule {
+    fn breached(&self, value: f64) -> bool {
+        match self.op {
+            Op::Gt => value > self.threshold,
+            Op::Ge => value >= self.threshold,
+            Op::Lt => value < self.threshold,
+            Op::Le => value <= self.threshold,
+        }
+    }
+
+    fn recovered(&self, value: f64) -> bool {
+        if self.op.above() {
+            value < self.threshold - self.hysteresis
+                || (self.hysteresis == 0.0 && !self.breached(value))
+        } else {
+            value > self.threshold + self.hysteresis
+                || (self.hysteresis == 0.0 && !self.breached(value))
+        }
+    }
+
+    /// Advance `state` with a fresh observation.
+    pub(super) fn evaluate(
+        &self,
+        state: &mut RuleState,
+        value: f64,
+        now: Instant,
+        default_cooldown: Duration,
+    ) -> Option<Transition> {
+        if !value.is_finite() {
+            return None;
+        }
+        if state.firing {
+            if self.recovered(value) {
+                state.firing = false;
+                return Some(Transition::Resolved);
+            }
+            return None;
+        }
+        let cooldown = self.cooldown.unwrap_or(default_cooldown);
+        let cooling = state
+            .last_fired
+            .is_some_and(|fired| now.duration_since(fired) < cooldown);
+        if self.breached(value) && !cooling {
+            state.firing = true;
+            state.last_fired = Some(now);
+            return Some(Transition::Fired);
+        }
+        None
+    }
+}
+
+/// Parse a number with an optional `%` suffix.
+fn parse_value(raw: &str) -> Result<f64> {
+    let (number, scale) = match raw.strip_suffix('%') {
+        Some(number) => (number, 0.01),
+        None => (raw, 1.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite())
+        .map(|value| value * scale)
+        .ok_or_else(|| anyhow!("invalid number '{raw}'"))
+}
+
+/// Parse `30s`, `10m`, `1h` or plain seconds.
+pub(super) fn parse_duration(raw: &str) -> Result<Duration> {
+    let (number, unit) = match raw.char_indices().find(|(_, c)| c.is_ascii_alphabetic()) {
+        Some((index, _)) => raw.split_at(index),
+        None => (raw, "s"),
+    };
+    let value: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("invalid duration '{raw}'"))?;
+    let secs = match unit {
+        "s" => value,
+        "m" => value * 60,
+        "h" => value * 3_600,
+        _ => bail!("invalid duration unit in '{raw}' (use s, m or h)"),
+    };
+    Ok(Duration::from_secs(secs))
+}
+
+/// Parse one rule.
+pub(super) fn parse_rule(line: &str) -> Result<Rule> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (symbol, rest) = match Metric::parse(&tokens) {
+        Some(_) => (None, tokens.as_slice()),
+        None => match tokens.split_first() {
+            Some((symbol, rest)) => (Some(symbol.to_uppercase()), rest),
+            None => bail!("empty rule"),
+        },
+    };
+    let (metric, used) = Metric::parse(rest).ok_or_else(|| {
+        anyhow!(
+            "unknown metric in '{line}' (mark, index, last, bid, ask, mid, spread_bps, \
+             funding, position pnl|qty|notional)"
+        )
+    })?;
+    let rest = &rest[used..];
+    let (Some(op), Some(value)) = (rest.first(), rest.get(1)) else {
+        bail!("rule '{line}' needs a comparison, e.g. '> 70000'");
+    };
+    let op = Op::parse(op).ok_or_else(|| anyhow!("invalid comparison '{op}' (>, >=, <, <=)"))?;
+    let mut rule = Rule {
+        text: tokens.join(" "),
+        symbol,
+        metric,
+        op,
+        threshold: parse_value(value)?,
+        hysteresis: 0.0,
+        cooldown: None,
+    };
+    for option in &rest[2..] {
+        match option.split_once('=') {
+            Some(("hysteresis", value)) => {
+                rule.hysteresis = parse_value(value)?.abs();
+            }
+            Some(("cooldown", value)) => rule.cooldown = Some(parse_duration(value)?),
+            _ => bail!("unknown rule option '{option}' (hysteresis=, cooldown=)"),
+        }
+    }
+    Ok(rule)
+}
+
+/// Parse a rules file, skipping blank lines and `#` comments.
+pub(super) fn parse_rules(contents: &str) -> Result<Vec<Rule>> {
+    contents
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            (!line.is_empty()).then_some((index + 1, line))
+        })
+        .map(|(number, line)| parse_rule(line).with_context(|| format!("rules line {number}")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COOLDOWN: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn parses_the_documented_rule_forms() {
+        let rule = parse_rule("BTC-USD mark > 70000").unwrap();
+        assert_eq!(rule.symbol.as_deref(), Some("BTC-USD"));
+        assert_eq!(rule.metric, Metric::Mark);
+        assert_eq!(rule.threshold, 70_000.0);
+
+        let rule = parse_rule("funding < -0.01%").unwrap();
+        assert_eq!(rule.symbol, None);
+        assert!((rule.threshold + 0.0001).abs() < 1e-15);
+
+        let rule = parse_rule("spread_bps > 20 hysteresis=5 cooldown=10m").unwrap();
+        assert_eq!(rule.metric, Metric::SpreadBps);
+        assert_eq!(rule.hysteresis, 5.0);
+        assert_eq!(rule.cooldown, Some(Duration::from_secs(600)));
+
+        let rule = parse_rule("position   pnl < -500").unwrap();
+        assert_eq!(rule.metric, Metric::PositionPnl);
+        assert_eq!(rule.text, "position pnl < -500");
+    }
+
+    #[test]
+    fn rejects_malformed_rules_with_line_numbers() {
+        assert!(parse_rule("BTC-USD volume > 1").is_err());
+        assert!(parse_rule("BTC-USD mark >> 1").is_err());
+        assert!(parse_rule("BTC-USD mark > 1 snooze=5").is_err());
+        let error = parse_rules("# header\nmark > 1\nmark ~ 2\n").unwrap_err();
+        assert!(format!("{error:#}").contains("rules line 3"));
+    }
+
+    #[test]
+    fn hysteresis_keeps_a_hovering_value_from_flapping() {
+        let rule = parse_rule("mark > 100 hysteresis=2").unwrap();
+        let mut state = RuleState::default();
+        let now = Instant::now();
+
+        assert_eq!(
+            rule.evaluate(&mut state, 101.0, now, COOLDOWN),
+            Some(Transition::Fired)
+        );
+        assert_eq!(rule.evaluate(&mut state, 99.0, now, COOLDOWN), None);
+        assert_eq!(rule.evaluate(&mut state, 101.0, now, COOLDOWN), None);
+        assert_eq!(
+            rule.evaluate(&mut state, 97.5, now, COOLDOWN),
+            Some(Transition::Resolved)
+        );
+    }
+
+    #[test]
+    fn cooldown_delays_refiring() {
+        let rule = parse_rule("funding < -0.01%").unwrap();
+        let mut state = RuleState::default();
+        let start = Instant::now();
+
+        assert!(rule.evaluate(&mut state, -0.001, start, COOLDOWN).is_some());
+        assert_eq!(
+            rule.evaluate(&mut state, 0.0, start, COOLDOWN),
+            Some(Transition::Resolved)
+        );
+        assert_eq!(rule.evaluate(&mut state, -0.001, start, COOLDOWN), None);
+        assert_eq!(
+            rule.evaluate(&mut state, -0.001, start + COOLDOWN, COOLDOWN),
+            Some(Transition::Fired)
+        );
+    }
+}
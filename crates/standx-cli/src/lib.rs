@@ -1,13 +1,16 @@
 //! StandX CLI library
 //!
-//! Command definitions, handlers, output formatting, configuration, and
-//! telemetry for the `standx` binary. Protocol types and API access live in
-//! the [`standx_sdk`] crate and are re-exported here for convenience (and so
-//! existing `standx_cli::models::...` imports keep working).
+//! Command definitions, handlers, output formatting, configuration, the
+//! audit log and telemetry for the `standx` binary. Protocol types and API
+//! access live in the [`standx_sdk`] crate and are re-exported here for
+//! convenience (and so existing `standx_cli::models::...` imports keep
+//! working).
 
+pub mod audit;
 pub mod cli;
 pub mod commands;
 pub mod config;
+pub mod market_cache;
 pub mod output;
 pub mod telemetry;
 
@@ -1,11 +1,13 @@
 use clap::Parser;
+use standx_cli::audit;
 use standx_cli::cli::{
     load_maker_local_env, should_load_maker_local_env, AlertWebhookFormat, Cli, Commands,
-    MakerCommands, OutputFormat,
+    MakerCommands, OutputFormat, PortfolioCommands,
 };
 use standx_cli::commands;
 use standx_cli::commands::{FailSafeShutdown, FAIL_SAFE_EXIT_CODE};
-use standx_cli::telemetry::Telemetry;
+use standx_cli::output;
+use standx_cli::telemetry::{sanitize_args, Telemetry};
 
 /// Print cool splash screen
 fn print_splash_screen() {
@@ -70,18 +72,19 @@ async fn async_main(args: Vec<String>) {
     let mut telemetry = Telemetry::new();
 
     // Initialize logging
-    if cli.verbose {
-        tracing_subscriber::fmt()
-            .with_max_level(tracing::Level::DEBUG)
-            .init();
+    let level = if cli.verbose {
+        tracing::Level::DEBUG
     } else if cli.quiet {
-        tracing_subscriber::fmt()
-            .with_max_level(tracing::Level::ERROR)
-            .init();
+        tracing::Level::ERROR
     } else {
-        tracing_subscriber::fmt()
-            .with_max_level(tracing::Level::INFO)
-            .init();
+        tracing::Level::INFO
+    };
+    let logs = tracing_subscriber::fmt().with_max_level(level);
+    if matches!(cli.command, Commands::Mcp(_)) {
+        // stdout carries the MCP protocol stream.
+        logs.with_writer(std::io::stderr).init();
+    } else {
+        logs.init();
     }
 
     // Track command start
@@ -92,9 +95,39 @@ async fn async_main(args: Vec<String>) {
     let args: Vec<String> = std::env::args().collect();
     telemetry.track_command_start(command_name, &args);
 
-    // Handle dry run mode
-    if cli.dry_run {
-        let output = cli.output;
+    // Every order, leverage, margin and maker mutation this run sends is
+    // appended to the audit log under this context
+    audit::configure(audit::Context {
+        source: command_name.to_string(),
+        profile: cli.config.clone().unwrap_or_else(|| "default".to_string()),
+        command: sanitize_args(&args).join(" "),
+        log: audit::AuditLog::open_default(),
+    });
+
+    // Resolve the output format handlers see and the process-wide encoding,
+    // field selection and template behind it
+    let output = match cli.output_settings() {
+        Ok((output, options)) => {
+            output::configure(options);
+            output
+        }
+        Err(e) => {
+            let boxed_error: Box<dyn std::error::Error> = Box::new(e);
+            print_error(&boxed_error, OutputFormat::Table);
+            telemetry.track_command_complete(command_name, false, Some(&boxed_error.to_string()));
+            std::process::exit(2);
+        }
+    };
+
+    // Handle dry run mode (the MCP and HTTP servers apply it per call, apply
+    // validates without executing, and --plan-out never executes anyway)
+    if cli.dry_run
+        && cli.plan_out.is_none()
+        && !matches!(
+            cli.command,
+            Commands::Mcp(_) | Commands::Serve(_) | Commands::Apply(_)
+        )
+    {
         match handle_dry_run(&cli.command, output).await {
             Ok(_) => {
                 telemetry.track_command_complete(command_name, true, None);
@@ -113,17 +146,14 @@ async fn async_main(args: Vec<String>) {
         }
     }
 
-    // Set default output to JSON in OpenClaw mode
-    let output = if cli.quiet {
-        OutputFormat::Quiet
-    } else if cli.openclaw {
-        OutputFormat::Json
-    } else {
-        cli.output
+    // Execute command (or only record it as a plan) and handle errors
+    let result = match &cli.plan_out {
+        Some(path) => commands::handle_plan_out(&cli.command, path, output)
+            .await
+            .map_err(Into::into),
+        None => execute_command(cli.command, output, cli.verbose, cli.dry_run, cli.yes).await,
     };
-
-    // Execute command and handle errors
-    match execute_command(cli.command, output, cli.verbose).await {
+    match result {
         Ok(_) => {
             telemetry.track_command_complete(command_name, true, None);
         }
@@ -165,6 +195,16 @@ fn command_name(command: &Commands) -> &'static str {
         Commands::Portfolio { .. } => "portfolio",
         Commands::Block { .. } => "block",
         Commands::Maker { .. } => "maker",
+        Commands::Report { .. } => "report",
+        Commands::Data { .. } => "data",
+        Commands::Watch(_) => "watch",
+        Commands::Record(_) => "record",
+        Commands::Mcp(_) => "mcp",
+        Commands::Serve(_) => "serve",
+        Commands::Apply(_) => "apply",
+        Commands::Schema(_) => "schema",
+        Commands::Audit { .. } => "audit",
+        Commands::Risk { .. } => "risk",
         Commands::LagRecorder { .. } => "lag-recorder",
     }
 }
@@ -232,6 +272,8 @@ async fn execute_command(
     command: Commands,
     output: OutputFormat,
     verbose: bool,
+    dry_run: bool,
+    yes: bool,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match command {
         Commands::Config { command } => {
@@ -259,20 +301,37 @@ async fn execute_command(
             commands::handle_margin(command).await?;
         }
         Commands::Stream { command } => {
-            commands::handle_stream(command, verbose).await?;
+            commands::handle_stream(command, output, verbose).await?;
         }
         Commands::Dashboard {
             symbols,
             verbose,
             watch,
             compact,
+            no_cache,
+            tui,
         } => {
-            commands::handle_dashboard(symbols, verbose, watch, compact, output).await?;
+            commands::handle_dashboard(symbols, verbose, watch, compact, no_cache, tui, output)
+                .await?;
         }
-        Commands::Portfolio { verbose, watch } => {
-            let command = commands::PortfolioCommand::Snapshot {
-                _verbose: verbose,
-                watch,
+        Commands::Portfolio {
+            verbose,
+            watch,
+            record,
+            command,
+        } => {
+            let command = match command {
+                None => commands::PortfolioCommand::Snapshot {
+                    _verbose: verbose,
+                    watch,
+                    record,
+                },
+                Some(PortfolioCommands::Record { interval }) => {
+                    commands::PortfolioCommand::Record { interval }
+                }
+                Some(PortfolioCommands::History { from, to }) => {
+                    commands::PortfolioCommand::History { from, to }
+                }
             };
             commands::handle_portfolio(command, output).await?;
         }
@@ -291,6 +350,36 @@ async fn execute_command(
                 });
             }
         }
+        Commands::Report { command } => {
+            commands::handle_report(command, output).await?;
+        }
+        Commands::Data { command } => {
+            commands::handle_data(command, output).await?;
+        }
+        Commands::Watch(args) => {
+            commands::handle_watch(args, output, verbose).await?;
+        }
+        Commands::Record(args) => {
+            commands::handle_record(args, verbose).await?;
+        }
+        Commands::Mcp(args) => {
+            commands::handle_mcp(args, dry_run, yes).await?;
+        }
+        Commands::Serve(args) => {
+            commands::handle_serve(args, dry_run, yes, verbose).await?;
+        }
+        Commands::Apply(args) => {
+            commands::handle_apply(args, output, dry_run, yes).await?;
+        }
+        Commands::Schema(args) => {
+            commands::handle_schema(args, output).await?;
+        }
+        Commands::Audit { command } => {
+            commands::handle_audit(command, output).await?;
+        }
+        Commands::Risk { command } => {
+            commands::handle_risk(command, output).await?;
+        }
         Commands::LagRecorder {
             symbol,
             hl_coin,
@@ -317,12 +406,35 @@ async fn handle_dry_run(command: &Commands, output: OutputFormat) -> Result<(),
         Commands::Leverage { .. } => "⚠️  WOULD MODIFY LEVERAGE - POSITION IMPACT",
         Commands::Margin { .. } => "⚠️  WOULD MODIFY MARGIN - POSITION IMPACT",
         Commands::Stream { .. } => "Would start real-time data stream",
+        Commands::Dashboard { tui: true, .. } => {
+            "⚠️  WOULD OPEN INTERACTIVE DASHBOARD - CAN CANCEL ORDERS / FLATTEN POSITIONS (after confirmation)"
+        }
         Commands::Dashboard { .. } => "Would fetch dashboard data (read-only, safe to execute)",
-        Commands::Portfolio { .. } => "Would fetch portfolio data (read-only, safe to execute)",
+        Commands::Portfolio { .. } => {
+            "Would fetch portfolio data or read/append the local portfolio history (no orders)"
+        }
         Commands::Block { .. } => "Would fetch block trades (authenticated, read-only)",
         Commands::Maker { .. } => {
             "⚠️  WOULD RUN MAKER BOT - PLACES/CANCELS ORDERS WITH --live (paper mode without)"
         }
+        Commands::Report { .. } => {
+            "Would rebuild an account report from trade history (read-only, safe to execute)"
+        }
+        Commands::Data { .. } => "Would sync or inspect the local market-data cache (no orders)",
+        Commands::Watch(_) => {
+            "Would evaluate alert rules on live market data and send notifications (no orders)"
+        }
+        Commands::Record(_) => {
+            "Would record public market data to rotating NDJSON files (read-only, safe to execute)"
+        }
+        Commands::Mcp(_) => "Would serve MCP tools on stdio (order tools answer with previews)",
+        Commands::Serve(_) => "Would serve the local HTTP API (order endpoints answer with previews)",
+        Commands::Apply(_) => "⚠️  WOULD EXECUTE A SAVED PLAN - FINANCIAL IMPACT",
+        Commands::Schema(_) => "Would print the command and output schema (read-only, safe to execute)",
+        Commands::Audit { .. } => "Would read the local audit log (read-only, safe to execute)",
+        Commands::Risk { .. } => {
+            "Would simulate margin and liquidation for an order without placing it (read-only, safe to execute)"
+        }
         Commands::LagRecorder { .. } => {
             "Would record StandX/Hyperliquid prices to NDJSON (read-only, safe to execute)"
         }
@@ -342,19 +454,29 @@ async fn handle_dry_run(command: &Commands, output: OutputFormat) -> Result<(),
         Commands::Portfolio { .. } => "portfolio",
         Commands::Block { .. } => "block",
         Commands::Maker { .. } => "maker",
+        Commands::Report { .. } => "report",
+        Commands::Data { .. } => "data",
+        Commands::Watch(_) => "watch",
+        Commands::Record(_) => "record",
+        Commands::Mcp(_) => "mcp",
+        Commands::Serve(_) => "serve",
+        Commands::Apply(_) => "apply",
+        Commands::Schema(_) => "schema",
+        Commands::Audit { .. } => "audit",
+        Commands::Risk { .. } => "risk",
         Commands::LagRecorder { .. } => "lag-recorder",
     };
     let dry_run_info = serde_json::json!({
         "dry_run": true,
         "command": command_label,
         "description": description,
-        "would_execute": !matches!(command, Commands::Order { .. } | Commands::Leverage { .. } | Commands::Margin { .. } | Commands::Maker { .. }),
+        "would_execute": !matches!(command, Commands::Order { .. } | Commands::Leverage { .. } | Commands::Margin { .. } | Commands::Maker { .. } | Commands::Apply(_)),
         "note": "Remove --dry-run to execute"
     });
 
     match output {
         OutputFormat::Json => {
-            println!("{}", serde_json::to_string_pretty(&dry_run_info).unwrap());
+            println!("{}", output::format_json(&dry_run_info)?);
         }
         _ => {
             println!("🔍 DRY RUN MODE");
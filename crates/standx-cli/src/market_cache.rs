@@ -0,0 +1,484 @@
+//! Local on-disk cache of historical market data
+//!
+//! One JSON file per series (symbol × kline resolution, funding, or public
+//! trades) under the data directory. Each series records the time ranges it
+//! has fully fetched (`coverage`), so a read only asks the API for the
+//! uncovered parts and `standx data sync` can backfill, extend and repair a
+//! series incrementally. Files are replaced atomically (write + rename).
+//!
+//! Set `STANDX_CACHE_DIR` to relocate the cache.
+
+use crate::models::{FundingRate, Kline, Trade};
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the cache directory.
+pub const CACHE_DIR_ENV: &str = "STANDX_CACHE_DIR";
+
+/// On-disk format version; a mismatching file is discarded and refetched.
+const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// A record type that can be stored in a cached series.
+pub trait CachedRecord: Clone + Serialize + DeserializeOwned {
+    /// Ordering key: Unix seconds plus a tie-breaker for records that share a
+    /// timestamp. `None` records cannot be cached and are dropped.
+    fn cache_key(&self) -> Option<(i64, u64)>;
+}
+
+impl CachedRecord for Kline {
+    fn cache_key(&self) -> Option<(i64, u64)> {
+        Some((self.time.parse().ok()?, 0))
+    }
+}
+
+impl CachedRecord for FundingRate {
+    fn cache_key(&self) -> Option<(i64, u64)> {
+        Some((parse_time_secs(&self.time)?, 0))
+    }
+}
+
+impl CachedRecord for Trade {
+    fn cache_key(&self) -> Option<(i64, u64)> {
+        Some((parse_time_secs(&self.time)?, self.id))
+    }
+}
+
+/// RFC3339 or Unix (seconds or milliseconds) timestamp as Unix seconds.
+fn parse_time_secs(value: &str) -> Option<i64> {
+    if let Ok(timestamp) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(timestamp.timestamp());
+    }
+    let raw = value.parse::<i64>().ok()?;
+    Some(if raw.abs() < 1_000_000_000_000 {
+        raw
+    } else {
+        raw / 1_000
+    })
+}
+
+/// Which series of a symbol a file holds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SeriesKind {
+    Kline { resolution: String },
+    Funding,
+    Trades,
+}
+
+/// Identifies one cached series.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeriesKey {
+    pub symbol: String,
+    #[serde(flatten)]
+    pub kind: SeriesKind,
+}
+
+impl SeriesKey {
+    pub fn kline(symbol: &str, resolution: &str) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            kind: SeriesKind::Kline {
+                resolution: resolution.to_uppercase(),
+            },
+        }
+    }
+
+    pub fn funding(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            kind: SeriesKind::Funding,
+        }
+    }
+
+    pub fn trades(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_uppercase(),
+            kind: SeriesKind::Trades,
+        }
+    }
+
+    fn file_name(&self) -> String {
+        match &self.kind {
+            SeriesKind::Kline { resolution } => format!("kline-{resolution}.json"),
+            SeriesKind::Funding => "funding.json".to_string(),
+            SeriesKind::Trades => "trades.json".to_string(),
+        }
+    }
+
+    fn parse(symbol: &str, file_name: &str) -> Option<Self> {
+        let stem = file_name.strip_suffix(".json")?;
+        Some(match stem {
+            "funding" => Self::funding(symbol),
+            "trades" => Self::trades(symbol),
+            _ => Self::kline(symbol, stem.strip_prefix("kline-")?),
+        })
+    }
+}
+
+/// Bar length of a kline resolution in seconds, or `None` for calendar
+/// resolutions (`1M`) whose length varies and which are never cached.
+pub fn resolution_secs(resolution: &str) -> Option<i64> {
+    match resolution.to_uppercase().as_str() {
+        "1D" | "D" => Some(86_400),
+        "1W" | "W" => Some(604_800),
+        "3S" => Some(3),
+        minutes => minutes
+            .parse::<i64>()
+            .ok()
+            .filter(|m| *m > 0)
+            .map(|m| m * 60),
+    }
+}
+
+/// One cached series: records keyed by time plus the fully-fetched ranges.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "T: CachedRecord")]
+pub struct CachedSeries<T: CachedRecord> {
+    version: u32,
+    /// Sorted, non-overlapping inclusive `[from, to]` ranges (Unix seconds)
+    /// whose records are all present.
+    coverage: Vec<(i64, i64)>,
+    /// Ranges known to be missing records that cannot be refetched (e.g. the
+    /// public trade tape moved on between two snapshots).
+    #[serde(default)]
+    gap_markers: Vec<(i64, i64)>,
+    /// Unix seconds of the last successful write.
+    #[serde(default)]
+    synced_at: Option<i64>,
+    records: Vec<T>,
+}
+
+impl<T: CachedRecord> Default for CachedSeries<T> {
+    fn default() -> Self {
+        Self {
+            version: CACHE_FORMAT_VERSION,
+            coverage: Vec::new(),
+            gap_markers: Vec::new(),
+            synced_at: None,
+            records: Vec::new(),
+        }
+    }
+}
+
+impl<T: CachedRecord> CachedSeries<T> {
+    pub fn coverage(&self) -> &[(i64, i64)] {
+        &self.coverage
+    }
+
+    pub fn gap_markers(&self) -> &[(i64, i64)] {
+        &self.gap_markers
+    }
+
+    pub fn synced_at(&self) -> Option<i64> {
+        self.synced_at
+    }
+
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Cached records with timestamps in `[from, to]`, oldest first.
+    pub fn range(&self, from: i64, to: i64) -> Vec<T> {
+        self.records
+            .iter()
+            .filter(|record| {
+                record
+                    .cache_key()
+                    .is_some_and(|(time, _)| time >= from && time <= to)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// The newest `limit` records, newest first.
+    pub fn latest(&self, limit: usize) -> Vec<T> {
+        self.records.iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Newest cached timestamp.
+    pub fn last_time(&self) -> Option<i64> {
+        self.records
+            .last()
+            .and_then(|record| record.cache_key())
+            .map(|(time, _)| time)
+    }
+
+    /// Parts of `[from, to]` not covered yet, oldest first.
+    pub fn uncovered(&self, from: i64, to: i64) -> Vec<(i64, i64)> {
+        let mut missing = Vec::new();
+        let mut cursor = from;
+        for &(start, end) in &self.coverage {
+            if end < cursor {
+                continue;
+            }
+            if start > to {
+                break;
+            }
+            if start > cursor {
+                missing.push((cursor, start - 1));
+            }
+            cursor = cursor.max(end.saturating_add(1));
+            if cursor > to {
+                return missing;
+            }
+        }
+        if cursor <= to {
+            missing.push((cursor, to));
+        }
+        missing
+    }
+
+    /// Missing bars inside covered ranges for a fixed bar length: any step
+    /// between consecutive bar open times longer than `step`.
+    pub fn holes(&self, step: i64) -> Vec<(i64, i64)> {
+        let times: Vec<i64> = self
+            .records
+            .iter()
+            .filter_map(|record| record.cache_key().map(|(time, _)| time))
+            .collect();
+        let mut holes = Vec::new();
+        for &(start, end) in &self.coverage {
+            let inside: Vec<i64> = times
+                .iter()
+                .copied()
+                .filter(|time| *time >= start && *time <= end)
+                .collect();
+            for pair in inside.windows(2) {
+                if pair[1] - pair[0] > step {
+                    holes.push((pair[0] + step, pair[1] - 1));
+                }
+            }
+        }
+        holes
+    }
+
+    /// Merge fetched records (replacing any with the same key) and mark
+    /// `[from, to]` as covered when `from <= to`.
+    pub fn merge(&mut self, records: Vec<T>, from: i64, to: i64) {
+        let mut by_key: BTreeMap<(i64, u64), T> = self
+            .records
+            .drain(..)
+            .filter_map(|record| record.cache_key().map(|key| (key, record)))
+            .collect();
+        for record in records {
+            if let Some(key) = record.cache_key() {
+                by_key.insert(key, record);
+            }
+        }
+        self.records = by_key.into_values().collect();
+        if from <= to {
+            self.add_coverage(from, to);
+        }
+    }
+
+    /// Record a range that is known to be missing and cannot be refetched.
+    pub fn mark_gap(&mut self, from: i64, to: i64) {
+        if from <= to && !self.gap_markers.contains(&(from, to)) {
+            self.gap_markers.push((from, to));
+        }
+    }
+
+    fn add_coverage(&mut self, from: i64, to: i64) {
+        self.coverage.push((from, to));
+        self.coverage.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(self.coverage.len());
+        for (start, end) in self.coverage.drain(..) {
+            match merged.last_mut() {
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        self.coverage = merged;
+    }
+}
+
+/// Handle to the cache directory.
+#[derive(Debug, Clone)]
+pub struct MarketCache {
+    root: PathBuf,
+}
+
+impl MarketCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// The cache under `$STANDX_CACHE_DIR`, or the user data directory.
+    pub fn open_default() -> Self {
+        let root = std::env::var_os(CACHE_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| {
+                dirs::data_dir()
+                    .unwrap_or_else(|| PathBuf::from("."))
+                    .join("standx")
+                    .join("market-cache")
+            });
+        Self::new(root)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn path(&self, key: &SeriesKey) -> PathBuf {
+        self.root.join(&key.symbol).join(key.file_name())
+    }
+
+    /// Load a series; a missing file or an older format yields an empty one.
+    pub fn load<T: CachedRecord>(&self, key: &SeriesKey) -> Result<CachedSeries<T>> {
+        let path = self.path(key);
+        let contents = match std::fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(CachedSeries::default())
+            }
+            Err(error) => return Err(error).with_context(|| format!("reading {}", path.display())),
+        };
+        let series: CachedSeries<T> = serde_json::from_str(&contents)
+            .with_context(|| format!("parsing market cache file {}", path.display()))?;
+        if series.version != CACHE_FORMAT_VERSION {
+            return Ok(CachedSeries::default());
+        }
+        Ok(series)
+    }
+
+    /// Atomically replace a series file.
+    pub fn save<T: CachedRecord>(
+        &self,
+        key: &SeriesKey,
+        series: &mut CachedSeries<T>,
+    ) -> Result<()> {
+        let path = self.path(key);
+        let dir = path.parent().expect("series path has a symbol directory");
+        std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+        series.synced_at = Some(chrono::Utc::now().timestamp());
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_vec(series)?)
+            .with_context(|| format!("writing {}", tmp.display()))?;
+        std::fs::rename(&tmp, &path).with_context(|| format!("replacing {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Every series present on disk, sorted by symbol then file.
+    pub fn list(&self) -> Result<Vec<SeriesKey>> {
+        let mut keys = Vec::new();
+        let symbols = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(keys),
+            Err(error) => return Err(error.into()),
+        };
+        for symbol in symbols {
+            let symbol = symbol?;
+            if !symbol.file_type()?.is_dir() {
+                continue;
+            }
+            let symbol_name = symbol.file_name().to_string_lossy().to_string();
+            for file in std::fs::read_dir(symbol.path())? {
+                let file_name = file?.file_name().to_string_lossy().to_string();
+                if let Some(key) = SeriesKey::parse(&symbol_name, &file_name) {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort_by(|a, b| {
+            a.symbol
+                .cmp(&b.symbol)
+                .then_with(|| a.file_name().cmp(&b.file_name()))
+        });
+        Ok(keys)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bar(time: i64) -> Kline {
+        Kline {
+            time: time.to_string(),
+            open: "1".to_string(),
+            high: "1".to_string(),
+            low: "1".to_string(),
+            close: "1".to_string(),
+            volume: "0".to_string(),
+        }
+    }
+
+    #[test]
+    fn uncovered_returns_only_the_missing_parts() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(Vec::new(), 100, 199);
+        series.merge(Vec::new(), 300, 399);
+
+        assert_eq!(series.uncovered(150, 350), vec![(200, 299)]);
+        assert_eq!(
+            series.uncovered(0, 500),
+            vec![(0, 99), (200, 299), (400, 500)]
+        );
+        assert!(series.uncovered(120, 180).is_empty());
+    }
+
+    #[test]
+    fn adjacent_coverage_ranges_coalesce() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(Vec::new(), 0, 59);
+        series.merge(Vec::new(), 60, 119);
+        series.merge(Vec::new(), 100, 200);
+
+        assert_eq!(series.coverage(), &[(0, 200)]);
+    }
+
+    #[test]
+    fn holes_find_missing_bars_inside_coverage() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(vec![bar(0), bar(60), bar(240), bar(300)], 0, 300);
+
+        assert_eq!(series.holes(60), vec![(120, 239)]);
+        assert_eq!(series.range(60, 240).len(), 2);
+    }
+
+    #[test]
+    fn merge_replaces_records_with_the_same_key() {
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(vec![bar(0)], 0, 0);
+        let mut updated = bar(0);
+        updated.close = "2".to_string();
+        series.merge(vec![updated], 1, 0);
+
+        assert_eq!(series.len(), 1);
+        assert_eq!(series.range(0, 0)[0].close, "2");
+        assert_eq!(series.coverage(), &[(0, 0)]);
+    }
+
+    #[test]
+    fn series_round_trip_through_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = MarketCache::new(dir.path());
+        let key = SeriesKey::kline("btc-usd", "1d");
+        let mut series = CachedSeries::<Kline>::default();
+        series.merge(vec![bar(0), bar(86_400)], 0, 86_400);
+
+        cache.save(&key, &mut series).unwrap();
+        let loaded: CachedSeries<Kline> = cache.load(&key).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.coverage(), &[(0, 86_400)]);
+        assert!(loaded.synced_at().is_some());
+        assert_eq!(cache.list().unwrap(), vec![key]);
+    }
+
+    #[test]
+    fn resolutions_map_to_bar_lengths() {
+        assert_eq!(resolution_secs("1"), Some(60));
+        assert_eq!(resolution_secs("240"), Some(14_400));
+        assert_eq!(resolution_secs("1d"), Some(86_400));
+        assert_eq!(resolution_secs("1M"), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! `--fields` selection over serialized records.
+//!
+//! A field is a dotted path such as `account.balance` or `positions.0.symbol`.
+//! Object keys match by name and numeric segments index arrays; any other
+//! segment applied to an array is mapped over its elements, so
+//! `positions.symbol` on a `DashboardSnapshot` yields every position's symbol.
+
+use serde_json::{Map, Value};
+
+/// Resolve a dotted `path` against `value`. Missing paths resolve to `null`;
+/// an empty path or `.` is the value itself.
+pub fn lookup(value: &Value, path: &str) -> Value {
+    let path = path.trim();
+    if path.is_empty() || path == "." {
+        return value.clone();
+    }
+    let segments: Vec<&str> = path.split('.').collect();
+    resolve(value, &segments)
+}
+
+fn resolve(value: &Value, segments: &[&str]) -> Value {
+    let Some((head, rest)) = segments.split_first() else {
+        return value.clone();
+    };
+    match value {
+        Value::Object(map) => map.get(*head).map_or(Value::Null, |v| resolve(v, rest)),
+        Value::Array(items) => match head.parse::<usize>() {
+            Ok(index) => items.get(index).map_or(Value::Null, |v| resolve(v, rest)),
+            Err(_) => Value::Array(items.iter().map(|item| resolve(item, segments)).collect()),
+        },
+        _ => Value::Null,
+    }
+}
+
+/// Keep only `fields` of one record, keyed by each path as written and in
+/// the order given.
+pub fn select(record: &Value, fields: &[String]) -> Value {
+    Value::Object(
+        fields
+            .iter()
+            .map(|field| (field.clone(), lookup(record, field)))
+            .collect(),
+    )
+}
+
+/// Flatten nested objects into dotted keys (`account.balance`). Arrays are
+/// kept whole: expanding them by index would give every record its own
+/// column set.
+pub fn flatten(record: &Value) -> Map<String, Value> {
+    let mut out = Map::new();
+    match record {
+        Value::Object(map) => flatten_into(&mut out, "", map),
+        other => {
+            out.insert("value".to_string(), other.clone());
+        }
+    }
+    out
+}
+
+fn flatten_into(out: &mut Map<String, Value>, prefix: &str, map: &Map<String, Value>) {
+    for (key, value) in map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            Value::Object(inner) if !inner.is_empty() => flatten_into(out, &path, inner),
+            other => {
+                out.insert(path, other.clone());
+            }
+        }
+    }
+}
+
+/// Render a value as a bare cell: strings unquoted, `null` empty and
+/// anything else as compact JSON.
+pub fn cell(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Whether any top-level field of `record` is itself an object or array, i.e.
+/// the record cannot go through the flat `csv` serializer as-is.
+pub fn is_nested(record: &Value) -> bool {
+    match record {
+        Value::Object(map) => map
+            .values()
+            .any(|v| matches!(v, Value::Object(_) | Value::Array(_))),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn lookup_walks_objects_indexes_and_projects_arrays() {
+        let snapshot = json!({
+            "account": {"balance": "100"},
+            "positions": [{"symbol": "BTC-USD"}, {"symbol": "ETH-USD"}],
+        });
+        assert_eq!(lookup(&snapshot, "account.balance"), json!("100"));
+        assert_eq!(lookup(&snapshot, "positions.1.symbol"), json!("ETH-USD"));
+        assert_eq!(
+            lookup(&snapshot, "positions.symbol"),
+            json!(["BTC-USD", "ETH-USD"])
+        );
+        assert_eq!(lookup(&snapshot, "account.missing"), Value::Null);
+        assert_eq!(lookup(&snapshot, "."), snapshot);
+    }
+
+    #[test]
+    fn select_keeps_requested_order_and_flatten_dots_nested_keys() {
+        let record = json!({"b": 1, "a": {"x": 2, "y": [3]}});
+        let selected = select(&record, &["a.x".to_string(), "b".to_string()]);
+        let keys: Vec<&String> = selected.as_object().unwrap().keys().collect();
+        assert_eq!(keys, ["a.x", "b"]);
+
+        let flat = flatten(&record);
+        assert_eq!(flat["a.x"], json!(2));
+        assert_eq!(flat["a.y"], json!([3]));
+        assert!(is_nested(&record));
+        assert!(!is_nested(&json!({"a": 1})));
+    }
+}
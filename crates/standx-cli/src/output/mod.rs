@@ -1,7 +1,67 @@
 //! Output formatting utilities
+//!
+//! Command handlers pick a branch per [`crate::cli::OutputFormat`]; the
+//! structured branch goes through [`format_json`] (documents) or
+//! [`format_event`] (one line per streamed event), which apply the
+//! process-wide [`OutputOptions`] set from the global `--output`, `--fields`
+//! and `--template` flags. That keeps `ndjson`, `yaml`, field selection and
+//! templates uniform across every command without each handler knowing
+//! about them.
+
+mod fields;
+mod template;
+
+pub use template::Template;
 
 use crate::models::*;
-use tabled::{Table as TabledTable, Tabled};
+use serde_json::Value;
+use std::sync::OnceLock;
+use tabled::{builder::Builder, Table as TabledTable, Tabled};
+
+/// How structured output is encoded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// Pretty JSON documents; compact JSON lines for events.
+    #[default]
+    Json,
+    /// One compact JSON value per line; arrays are split into their elements.
+    Ndjson,
+    /// YAML documents; events are separated by `---`.
+    Yaml,
+    /// Each record rendered through [`OutputOptions::template`].
+    Template,
+    /// A table of the `--fields` columns (tab-separated rows for events).
+    Table,
+}
+
+/// Process-wide output settings, see [`configure`].
+#[derive(Clone, Debug, Default)]
+pub struct OutputOptions {
+    pub encoding: Encoding,
+    /// `--fields` dotted paths; empty keeps every field.
+    pub fields: Vec<String>,
+    /// Parsed `--template`, used by [`Encoding::Template`].
+    pub template: Option<Template>,
+}
+
+static OPTIONS: OnceLock<OutputOptions> = OnceLock::new();
+
+/// Install the output settings for this process. Only the first call takes
+/// effect; without one, output is plain JSON with every field.
+pub fn configure(options: OutputOptions) {
+    let _ = OPTIONS.set(options);
+}
+
+fn options() -> &'static OutputOptions {
+    OPTIONS.get_or_init(OutputOptions::default)
+}
+
+/// Whether `--fields` narrowed the output, for handlers whose default
+/// rendering would otherwise pick a fixed subset (such as the dashboard's
+/// CSV positions).
+pub fn fields_selected() -> bool {
+    !options().fields.is_empty()
+}
 
 /// Format data as table
 pub fn format_table<T: Tabled>(data: Vec<T>) -> String {
@@ -13,15 +73,197 @@ pub fn format_item<T: Tabled>(item: T) -> String {
     TabledTable::new(vec![item]).to_string()
 }
 
-/// Format as JSON
+/// Format as JSON, or whichever structured encoding `--output` selected
 pub fn format_json<T: serde::Serialize>(data: &T) -> crate::Result<String> {
-    serde_json::to_string_pretty(data).map_err(|e| crate::Error::Json {
+    let options = options();
+    if options.encoding == Encoding::Json && options.fields.is_empty() {
+        return serde_json::to_string_pretty(data).map_err(json_error);
+    }
+    let document = serde_json::to_value(data).map_err(json_error)?;
+    Ok(render(options, document, false))
+}
+
+/// Format one event of a stream or watch loop as a single output record:
+/// a compact JSON line by default, and the selected encoding otherwise.
+pub fn format_event(event: &Value) -> String {
+    let options = options();
+    if options.encoding == Encoding::Json && options.fields.is_empty() {
+        return event.to_string();
+    }
+    render(options, event.clone(), true)
+}
+
+fn json_error(e: serde_json::Error) -> crate::Error {
+    crate::Error::Json {
         message: e.to_string(),
-    })
+    }
+}
+
+/// Render a serialized document; `event` selects the line-oriented form.
+fn render(options: &OutputOptions, document: Value, event: bool) -> String {
+    if options.encoding == Encoding::Table {
+        let (columns, rows) = tabulate(&records(&document), &options.fields);
+        if event {
+            return rows
+                .iter()
+                .map(|row| row.join("\t"))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        let mut builder = Builder::default();
+        builder.push_record(columns);
+        for row in rows {
+            builder.push_record(row);
+        }
+        return builder.build().to_string();
+    }
+
+    let document = if options.fields.is_empty() {
+        document
+    } else {
+        match document {
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| fields::select(item, &options.fields))
+                    .collect(),
+            ),
+            other => fields::select(&other, &options.fields),
+        }
+    };
+    match options.encoding {
+        Encoding::Json if event => document.to_string(),
+        Encoding::Json => {
+            serde_json::to_string_pretty(&document).expect("a JSON value always serializes")
+        }
+        Encoding::Ndjson => records(&document)
+            .iter()
+            .map(|record| record.to_string())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Encoding::Yaml => {
+            let body = serde_norway::to_string(&document).expect("a JSON value always serializes");
+            if event {
+                format!("---\n{}", body.trim_end())
+            } else {
+                body.trim_end().to_string()
+            }
+        }
+        Encoding::Template => {
+            let template = options
+                .template
+                .as_ref()
+                .expect("Encoding::Template is only configured with a template");
+            records(&document)
+                .iter()
+                .map(|record| {
+                    // Syntax errors are caught when the template loads; a
+                    // record that fails to render is reported and skipped.
+                    template.render(record).unwrap_or_else(|error| {
+                        eprintln!("--template: {error}");
+                        String::new()
+                    })
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Encoding::Table => unreachable!("handled above"),
+    }
+}
+
+/// The records of a document: the elements of an array, or the document.
+fn records(document: &Value) -> Vec<&Value> {
+    match document {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    }
 }
 
-/// Format as CSV (for lists)
+/// Column names and cell rows for tabular encodings: the `--fields` paths
+/// when given, otherwise every flattened key in first-seen order.
+fn tabulate(records: &[&Value], selected: &[String]) -> (Vec<String>, Vec<Vec<String>>) {
+    if !selected.is_empty() {
+        let rows = records
+            .iter()
+            .map(|record| {
+                selected
+                    .iter()
+                    .map(|field| fields::cell(&fields::lookup(record, field)))
+                    .collect()
+            })
+            .collect();
+        return (selected.to_vec(), rows);
+    }
+
+    let flat: Vec<_> = records
+        .iter()
+        .map(|record| fields::flatten(record))
+        .collect();
+    let mut columns: Vec<String> = Vec::new();
+    for record in &flat {
+        for key in record.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+    let rows = flat
+        .iter()
+        .map(|record| {
+            columns
+                .iter()
+                .map(|column| record.get(column).map(fields::cell).unwrap_or_default())
+                .collect()
+        })
+        .collect();
+    (columns, rows)
+}
+
+/// [`format_csv`], except that an empty list still prints its header row:
+/// the `--fields` selection, or `columns`. For exports whose consumers
+/// expect the same schema on every run.
+pub fn format_csv_with_header<T: serde::Serialize>(
+    data: &[T],
+    columns: &[&str],
+) -> crate::Result<String> {
+    if !data.is_empty() {
+        return format_csv(data);
+    }
+    let selected = &options().fields;
+    let mut wtr = csv::Writer::from_writer(vec![]);
+    if selected.is_empty() {
+        wtr.write_record(columns)
+    } else {
+        wtr.write_record(selected)
+    }
+    .map_err(|e| crate::Error::Unknown(e.to_string()))?;
+    let result = wtr
+        .into_inner()
+        .map_err(|e| crate::Error::Unknown(e.to_string()))?;
+    String::from_utf8(result).map_err(|e| crate::Error::Unknown(e.to_string()))
+}
+
+/// Format as CSV (for lists). Nested records, or any `--fields` selection,
+/// are written with dotted column names (`account.balance`).
 pub fn format_csv<T: serde::Serialize>(data: &[T]) -> crate::Result<String> {
+    let options = options();
+    let document = serde_json::to_value(data).map_err(json_error)?;
+    let records = records(&document);
+    if !options.fields.is_empty() || records.iter().any(|record| fields::is_nested(record)) {
+        let (columns, rows) = tabulate(&records, &options.fields);
+        let mut wtr = csv::Writer::from_writer(vec![]);
+        wtr.write_record(&columns)
+            .map_err(|e| crate::Error::Unknown(e.to_string()))?;
+        for row in rows {
+            wtr.write_record(&row)
+                .map_err(|e| crate::Error::Unknown(e.to_string()))?;
+        }
+        let result = wtr
+            .into_inner()
+            .map_err(|e| crate::Error::Unknown(e.to_string()))?;
+        return String::from_utf8(result).map_err(|e| crate::Error::Unknown(e.to_string()));
+    }
+
     let mut wtr = csv::Writer::from_writer(vec![]);
 
     for item in data {
@@ -552,4 +794,114 @@ mod tests {
         assert!(json.contains("BTC-USD"));
         assert!(json.contains("\"symbol\""));
     }
+
+    fn options(encoding: Encoding, fields: &[&str], template: Option<&str>) -> OutputOptions {
+        OutputOptions {
+            encoding,
+            fields: fields.iter().map(|field| field.to_string()).collect(),
+            template: template.map(|source| Template::parse(source).unwrap()),
+        }
+    }
+
+    #[test]
+    fn ndjson_splits_arrays_and_keeps_selected_fields() {
+        let rows = serde_json::json!([
+            {"symbol": "BTC-USD", "account": {"balance": "1"}},
+            {"symbol": "ETH-USD", "account": {"balance": "2"}},
+        ]);
+        let out = render(
+            &options(Encoding::Ndjson, &["account.balance"], None),
+            rows,
+            false,
+        );
+        assert_eq!(
+            out,
+            "{\"account.balance\":\"1\"}\n{\"account.balance\":\"2\"}"
+        );
+    }
+
+    #[test]
+    fn events_render_as_single_records_per_encoding() {
+        let event = serde_json::json!({"symbol": "BTC-USD", "price": "97000"});
+        assert_eq!(
+            render(
+                &options(Encoding::Json, &["price"], None),
+                event.clone(),
+                true
+            ),
+            "{\"price\":\"97000\"}"
+        );
+        assert_eq!(
+            render(&options(Encoding::Yaml, &[], None), event.clone(), true),
+            "---\nsymbol: BTC-USD\nprice: '97000'"
+        );
+        assert_eq!(
+            render(
+                &options(Encoding::Table, &["symbol", "price"], None),
+                event.clone(),
+                true
+            ),
+            "BTC-USD\t97000"
+        );
+        assert_eq!(
+            render(
+                &options(Encoding::Template, &[], Some("{{symbol}}={{price}}")),
+                event,
+                true
+            ),
+            "BTC-USD=97000"
+        );
+    }
+
+    #[test]
+    fn yaml_documents_read_back_as_the_same_value() {
+        let document = serde_json::json!({
+            "price": "97000.5",
+            "side": "yes",
+            "note": "a: b",
+            "empty": [],
+            "levels": [["1", "2"]],
+            "positions": [{"symbol": "BTC-USD", "qty": 1}, {"symbol": "ETH-USD", "qty": -2}],
+            "account": {"balance": "100", "meta": {}},
+        });
+        let yaml = render(&options(Encoding::Yaml, &[], None), document.clone(), false);
+        let parsed: Value = serde_norway::from_str(&yaml).unwrap();
+        assert_eq!(parsed, document);
+        assert!(yaml.starts_with("price: '97000.5'\n"), "{yaml}");
+    }
+
+    #[test]
+    fn table_encoding_uses_fields_as_columns() {
+        let snapshot = serde_json::json!({"positions": [{"symbol": "BTC-USD"}], "account": null});
+        let table = render(
+            &options(Encoding::Table, &["positions.symbol", "account"], None),
+            snapshot,
+            false,
+        );
+        assert!(table.contains("positions.symbol"));
+        assert!(table.contains("[\"BTC-USD\"]"));
+    }
+
+    #[test]
+    fn csv_flattens_nested_records_into_dotted_columns() {
+        #[derive(serde::Serialize)]
+        struct Row {
+            symbol: &'static str,
+            book: Book,
+        }
+        #[derive(serde::Serialize)]
+        struct Book {
+            bid: f64,
+            levels: Vec<u32>,
+        }
+        let csv = format_csv(&[Row {
+            symbol: "BTC-USD",
+            book: Book {
+                bid: 1.5,
+                levels: vec![1, 2],
+            },
+        }])
+        .unwrap();
+        assert_eq!(csv, "symbol,book.bid,book.levels\nBTC-USD,1.5,\"[1,2]\"\n");
+    }
 }
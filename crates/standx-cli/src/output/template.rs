@@ -0,0 +1,130 @@
+//! `--template` rendering with MiniJinja (Jinja2 syntax).
+//!
+//! Each record is rendered with its fields as top-level variables and the
+//! whole record as `record`, so `{{ symbol }}`, `{{ levels[0] }}`, loops
+//! such as `{% for p in positions %}…{% endfor %}`, conditionals and filters
+//! (`{{ record | tojson }}`) all work. `--fields` columns keep their dotted
+//! names and are reached as `record["account.balance"]`. `none` and
+//! undefined values render as nothing. Because templates usually arrive as a
+//! single shell argument, the escapes `\n`, `\t` and `\\` are recognised in
+//! the source.
+
+use minijinja::Environment;
+use serde_json::{Map, Value};
+
+const NAME: &str = "template";
+
+/// A parsed `--template`.
+#[derive(Clone, Debug)]
+pub struct Template {
+    env: Environment<'static>,
+}
+
+impl Template {
+    /// Load a template from the `--template` argument: inline text, or
+    /// `@path` to read it from a file.
+    pub fn load(arg: &str) -> crate::Result<Self> {
+        let source = match arg.strip_prefix('@') {
+            Some(path) => std::fs::read_to_string(path).map_err(|e| {
+                crate::Error::Unknown(format!("--template: cannot read {path}: {e}"))
+            })?,
+            None => arg.to_string(),
+        };
+        Self::parse(&source).map_err(|e| crate::Error::Unknown(format!("--template: {e}")))
+    }
+
+    /// Parse template source.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut env = Environment::new();
+        env.set_keep_trailing_newline(true);
+        env.set_formatter(|out, state, value| {
+            if value.is_none() || value.is_undefined() {
+                return Ok(());
+            }
+            minijinja::escape_formatter(out, state, value)
+        });
+        env.add_template_owned(NAME, unescape(source))
+            .map_err(|e| e.to_string())?;
+        Ok(Self { env })
+    }
+
+    /// Render one record.
+    pub fn render(&self, record: &Value) -> Result<String, String> {
+        let template = self.env.get_template(NAME).map_err(|e| e.to_string())?;
+        let mut context = match record {
+            Value::Object(map) => map.clone(),
+            _ => Map::new(),
+        };
+        context.entry("record").or_insert_with(|| record.clone());
+        template.render(&context).map_err(|e| e.to_string())
+    }
+}
+
+/// Resolve the shell-friendly escapes; anything else is kept verbatim.
+fn unescape(source: &str) -> String {
+    let mut out = String::with_capacity(source.len());
+    let mut chars = source.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn renders_fields_escapes_and_selected_columns() {
+        let template = Template::parse("{{ symbol }}\\t{{side}} @ {{book[0]}}\\n").unwrap();
+        let record = json!({"symbol": "BTC-USD", "side": "buy", "book": [1.5]});
+        assert_eq!(template.render(&record).unwrap(), "BTC-USD\tbuy @ 1.5\n");
+
+        let selected = json!({"account.balance": "100", "note": null});
+        let template =
+            Template::parse("{{record['account.balance']}}/{{note}}/{{missing}}").unwrap();
+        assert_eq!(template.render(&selected).unwrap(), "100//");
+    }
+
+    #[test]
+    fn loops_over_nested_rows_with_conditionals_and_filters() {
+        let snapshot = json!({
+            "positions": [
+                {"symbol": "BTC-USD", "qty": "0.5"},
+                {"symbol": "ETH-USD", "qty": "-2"},
+            ],
+        });
+        let template = Template::parse(
+            "{% for p in positions %}{{ p.symbol | lower }}={% if p.qty is startingwith('-') %}short{% else %}long{% endif %}{% if not loop.last %},{% endif %}{% endfor %}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&snapshot).unwrap(),
+            "btc-usd=long,eth-usd=short"
+        );
+
+        let template = Template::parse("{{ record | tojson }}").unwrap();
+        assert_eq!(template.render(&json!([1, "a"])).unwrap(), r#"[1,"a"]"#);
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert!(Template::parse("{{symbol").is_err());
+        assert!(Template::parse("{% for x in xs %}").is_err());
+        assert!(Template::load("@/nonexistent/standx-template").is_err());
+    }
+}
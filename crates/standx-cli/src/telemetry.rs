@@ -214,7 +214,7 @@ impl Telemetry {
 
 /// Redact secrets from CLI argv while preserving the flag names needed for
 /// aggregate usage analysis. Handles both `--flag value` and `--flag=value`.
-fn sanitize_args(args: &[String]) -> Vec<String> {
+pub fn sanitize_args(args: &[String]) -> Vec<String> {
     const SENSITIVE_FLAGS: &[&str] = &["--private-key", "--token", "--alert-webhook", "-p"];
 
     let mut sanitized = Vec::with_capacity(args.len());
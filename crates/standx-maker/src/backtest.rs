@@ -0,0 +1,1097 @@
+//! Event-driven maker backtest over recorded market data.
+//!
+//! [`crate::run_replay`] re-plans recorded cycles but takes fills as given, so
+//! it can only evaluate the configuration that produced them. This module
+//! simulates the fills instead: the planner runs on a fixed cadence against
+//! the recorded book, and its quotes are filled by the recorded trade stream
+//! through a queue-position model. Like replay it reads no clock,
+//! environment, filesystem or network, so any configuration can be evaluated
+//! offline and the same inputs always produce the same result.
+//!
+//! The fill model is deliberately conservative:
+//!
+//! - A place reaches the book `order_latency_ms` after the cycle that sent
+//!   it. If it would cross the book at that moment it is rejected as
+//!   post-only; otherwise it joins the back of the queue, behind everything
+//!   displayed at its price.
+//! - A trade at the quote's price first consumes the queue ahead, then fills
+//!   the quote, possibly partially. A trade through the price fills the quote
+//!   from the trade's quantity.
+//! - Depth updates shrink the queue ahead only when the level itself drops
+//!   below it: cancellations are assumed to come from behind the quote.
+//! - A book that moves through a resting quote fills its remainder at the
+//!   quote price.
+//! - A cancel takes effect `cancel_latency_ms` after it is sent, and the
+//!   quote can still fill until then.
+//! - Inventory exits are reduce-only taker orders that walk the recorded book
+//!   `order_latency_ms` after the cycle that requested them.
+//! - A recording gap drops the book and every quote without fills; cycles
+//!   skip until the next depth snapshot.
+//!
+//! Funding is not part of the recorded public streams, so summaries report it
+//! as unavailable.
+
+use crate::{
+    plan_cycle_with, preflight_cycle_at, quote_crosses_touch, Action, AdaptiveSpreadConfig,
+    BookLevel, CycleInput, ExecutionCosts, FairValueConfig, FillRole, MakerConfig, MarketDataMode,
+    MarketSnapshot, PerformanceError, PerformanceFill, PerformanceLedger, PerformanceSummary,
+    QuoteQualityInterval, QuoteStrategy, RestingQuote, SizeSkewConfig, SizeSkewController,
+    SpreadController, StrategyConfig, VolBreaker, VolatilityError,
+};
+use standx_sdk::models::OrderSide;
+use std::fmt;
+
+/// A normalized market-data event, in arrival order.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BacktestEvent {
+    Mark {
+        event_time_ms: i64,
+        mark: f64,
+    },
+    /// A full book snapshot; it replaces the previous one.
+    Depth {
+        event_time_ms: i64,
+        bids: Vec<BookLevel>,
+        asks: Vec<BookLevel>,
+    },
+    /// A public trade print. The aggressor is inferred from the book.
+    Trade {
+        event_time_ms: i64,
+        price: f64,
+        qty: f64,
+    },
+    /// The recording lost its connection; nothing is known until new depth.
+    Gap {
+        event_time_ms: i64,
+    },
+}
+
+impl BacktestEvent {
+    pub fn event_time_ms(&self) -> i64 {
+        match self {
+            Self::Mark { event_time_ms, .. }
+            | Self::Depth { event_time_ms, .. }
+            | Self::Trade { event_time_ms, .. }
+            | Self::Gap { event_time_ms } => *event_time_ms,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestSettings {
+    pub starting_position: f64,
+    /// Planner cadence, like `maker run --interval`.
+    pub cycle_interval_ms: i64,
+    /// Time from a cycle to its places (and exits) reaching the book.
+    pub order_latency_ms: i64,
+    /// Time from a cycle to its cancels taking effect.
+    pub cancel_latency_ms: i64,
+    /// Passive fee in bps of notional; negative is a rebate.
+    pub maker_fee_bps: f64,
+    /// Inventory-exit fee in bps of notional.
+    pub taker_fee_bps: f64,
+    pub max_divergence_bps: f64,
+    pub vol_window: usize,
+    pub vol_window_secs: Option<u64>,
+    pub vol_pause_bps: f64,
+    pub adaptive_spread: AdaptiveSpreadConfig,
+    pub size_skew: SizeSkewConfig,
+    pub inventory_exit_pct: f64,
+    pub inventory_exit_qty: f64,
+    pub strategy: StrategyConfig,
+    /// Quote anchor estimated from the simulated book each cycle.
+    pub fair_value: FairValueConfig,
+}
+
+/// Order-flow counts of a backtest.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BacktestStats {
+    /// Cycles with a mark, including skipped ones.
+    pub cycles: u64,
+    /// Cycles the preflight skipped (crossed/missing touch, divergence).
+    pub skipped_cycles: u64,
+    /// Cycles planned under a volatility halt.
+    pub halted_cycles: u64,
+    pub places: u64,
+    pub cancels: u64,
+    /// Places that would have crossed the book when they arrived.
+    pub post_only_rejects: u64,
+    /// Passive fill events, partial ones included.
+    pub passive_fills: u64,
+    /// Passive fills that left part of the quote resting.
+    pub partial_fills: u64,
+    pub exits: u64,
+    /// Exits that found no liquidity on the recorded book.
+    pub failed_exits: u64,
+    pub gaps: u64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestResult {
+    pub start_time_ms: i64,
+    pub end_time_ms: i64,
+    pub final_mark: f64,
+    pub stats: BacktestStats,
+    pub performance: PerformanceSummary,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BacktestError {
+    InvalidSettings(&'static str),
+    InvalidEvent { index: usize, reason: &'static str },
+    TimeRegression { previous_ms: i64, next_ms: i64 },
+    Performance(PerformanceError),
+    Volatility(VolatilityError),
+    AdaptiveSpread(String),
+    SizeSkew(String),
+    Strategy(String),
+    FairValue(String),
+    NoMarketData,
+}
+
+impl fmt::Display for BacktestError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSettings(reason) => {
+                write!(formatter, "invalid backtest settings: {reason}")
+            }
+            Self::InvalidEvent { index, reason } => {
+                write!(formatter, "invalid backtest event #{index}: {reason}")
+            }
+            Self::TimeRegression {
+                previous_ms,
+                next_ms,
+            } => write!(
+                formatter,
+                "backtest events go back in time ({next_ms} after {previous_ms})"
+            ),
+            Self::Performance(error) => write!(formatter, "backtest performance error: {error}"),
+            Self::Volatility(error) => write!(formatter, "backtest volatility error: {error}"),
+            Self::AdaptiveSpread(error) => {
+                write!(formatter, "invalid backtest adaptive spread: {error}")
+            }
+            Self::SizeSkew(error) => write!(formatter, "invalid backtest size skew: {error}"),
+            Self::Strategy(error) => write!(formatter, "invalid backtest strategy: {error}"),
+            Self::FairValue(error) => write!(formatter, "invalid backtest fair value: {error}"),
+            Self::NoMarketData => formatter.write_str("backtest data has no usable mark or book"),
+        }
+    }
+}
+
+impl std::error::Error for BacktestError {}
+
+impl From<PerformanceError> for BacktestError {
+    fn from(value: PerformanceError) -> Self {
+        Self::Performance(value)
+    }
+}
+
+impl From<VolatilityError> for BacktestError {
+    fn from(value: VolatilityError) -> Self {
+        Self::Volatility(value)
+    }
+}
+
+/// Simulate the maker over `events`, which must be in non-decreasing time
+/// order. The run ends at the last event.
+pub fn run_backtest(
+    cfg: &MakerConfig,
+    settings: &BacktestSettings,
+    events: &[BacktestEvent],
+) -> Result<BacktestResult, BacktestError> {
+    validate_settings(cfg, settings)?;
+    validate_events(events)?;
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Err(BacktestError::NoMarketData);
+    };
+    let mut simulator = Simulator::new(cfg, settings, first.event_time_ms())?;
+    for event in events {
+        simulator.advance_to(event.event_time_ms(), false)?;
+        simulator.apply(event)?;
+    }
+    let end_time_ms = last.event_time_ms();
+    simulator.advance_to(end_time_ms, true)?;
+    simulator.finish(end_time_ms)
+}
+
+fn validate_settings(cfg: &MakerConfig, settings: &BacktestSettings) -> Result<(), BacktestError> {
+    settings
+        .strategy
+        .validate()
+        .map_err(|error| BacktestError::Strategy(error.to_string()))?;
+    settings
+        .fair_value
+        .validate(cfg)
+        .map_err(|error| BacktestError::FairValue(error.to_string()))?;
+    if settings.cycle_interval_ms <= 0 {
+        return Err(BacktestError::InvalidSettings(
+            "cycle_interval_ms must be positive",
+        ));
+    }
+    if settings.order_latency_ms < 0 || settings.cancel_latency_ms < 0 {
+        return Err(BacktestError::InvalidSettings(
+            "latencies must be non-negative",
+        ));
+    }
+    if !settings.starting_position.is_finite()
+        || !settings.maker_fee_bps.is_finite()
+        || !settings.taker_fee_bps.is_finite()
+        || !settings.vol_pause_bps.is_finite()
+        || !settings.inventory_exit_pct.is_finite()
+        || !settings.inventory_exit_qty.is_finite()
+    {
+        return Err(BacktestError::InvalidSettings(
+            "position, fee, volatility and inventory-exit values must be finite",
+        ));
+    }
+    if !settings.max_divergence_bps.is_finite() || settings.max_divergence_bps < 0.0 {
+        return Err(BacktestError::InvalidSettings(
+            "max_divergence_bps must be finite and non-negative",
+        ));
+    }
+    if settings.vol_window_secs == Some(0) {
+        return Err(BacktestError::InvalidSettings(
+            "vol_window_secs must be positive",
+        ));
+    }
+    if settings.vol_window_secs.is_none() && settings.vol_window == 0 {
+        return Err(BacktestError::InvalidSettings(
+            "vol_window must be positive",
+        ));
+    }
+    Ok(())
+}
+
+fn validate_events(events: &[BacktestEvent]) -> Result<(), BacktestError> {
+    let positive = |value: f64| value.is_finite() && value > 0.0;
+    let mut previous_ms = None;
+    for (index, event) in events.iter().enumerate() {
+        let event_time_ms = event.event_time_ms();
+        if let Some(previous_ms) = previous_ms {
+            if event_time_ms < previous_ms {
+                return Err(BacktestError::TimeRegression {
+                    previous_ms,
+                    next_ms: event_time_ms,
+                });
+            }
+        }
+        previous_ms = Some(event_time_ms);
+        let reason = match event {
+            BacktestEvent::Mark { mark, .. } if !positive(*mark) => "mark must be positive",
+            BacktestEvent::Depth { bids, asks, .. }
+                if bids
+                    .iter()
+                    .chain(asks)
+                    .any(|level| !positive(level.price) || !level.qty.is_finite()) =>
+            {
+                "book levels need a positive price and a finite quantity"
+            }
+            BacktestEvent::Trade { price, qty, .. } if !positive(*price) || !positive(*qty) => {
+                "trades need a positive price and quantity"
+            }
+            _ => continue,
+        };
+        return Err(BacktestError::InvalidEvent { index, reason });
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+struct SimOrder {
+    id: u64,
+    side: OrderSide,
+    level: u32,
+    price: f64,
+    qty: f64,
+    filled: f64,
+    ref_center: f64,
+    placed_at_cycle: u64,
+    /// Set while the place is in flight.
+    active_at_ms: Option<i64>,
+    /// Set once a cancel has been sent.
+    cancel_at_ms: Option<i64>,
+    /// Displayed quantity ahead of this quote at its price.
+    ahead: f64,
+}
+
+impl SimOrder {
+    fn remaining(&self) -> f64 {
+        self.qty - self.filled
+    }
+
+    fn due_ms(&self) -> Option<i64> {
+        self.active_at_ms.or(self.cancel_at_ms)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct SimExit {
+    side: OrderSide,
+    qty: f64,
+    at_ms: i64,
+}
+
+struct Simulator<'a> {
+    cfg: &'a MakerConfig,
+    settings: &'a BacktestSettings,
+    start_time_ms: i64,
+    /// Bids best (highest) first.
+    bids: Vec<BookLevel>,
+    /// Asks best (lowest) first.
+    asks: Vec<BookLevel>,
+    mark: Option<f64>,
+    position: f64,
+    orders: Vec<SimOrder>,
+    exit: Option<SimExit>,
+    next_cycle_ms: i64,
+    next_id: u64,
+    breaker: VolBreaker,
+    spread_controller: SpreadController,
+    size_skew_controller: SizeSkewController,
+    strategy: Box<dyn QuoteStrategy>,
+    performance: Option<PerformanceLedger>,
+    stats: BacktestStats,
+}
+
+impl<'a> Simulator<'a> {
+    fn new(
+        cfg: &'a MakerConfig,
+        settings: &'a BacktestSettings,
+        start_time_ms: i64,
+    ) -> Result<Self, BacktestError> {
+        let breaker = match settings.vol_window_secs {
+            Some(seconds) => VolBreaker::new_duration(
+                seconds
+                    .checked_mul(1_000)
+                    .ok_or(BacktestError::InvalidSettings(
+                        "vol_window_secs is too large",
+                    ))?,
+                settings.vol_pause_bps,
+            ),
+            None => VolBreaker::new(settings.vol_window, settings.vol_pause_bps),
+        };
+        Ok(Self {
+            cfg,
+            settings,
+            start_time_ms,
+            bids: Vec::new(),
+            asks: Vec::new(),
+            mark: None,
+            position: settings.starting_position,
+            orders: Vec::new(),
+            exit: None,
+            next_cycle_ms: start_time_ms,
+            next_id: 1,
+            breaker,
+            spread_controller: SpreadController::new(settings.adaptive_spread.clone(), cfg)
+                .map_err(|error| BacktestError::AdaptiveSpread(error.to_string()))?,
+            size_skew_controller: SizeSkewController::new(settings.size_skew, cfg)
+                .map_err(|error| BacktestError::SizeSkew(error.to_string()))?,
+            strategy: settings.strategy.build(),
+            performance: None,
+            stats: BacktestStats::default(),
+        })
+    }
+
+    fn next_id(&mut self) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|level| level.price)
+    }
+
+    fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|level| level.price)
+    }
+
+    /// The recorded mark, or the book mid until one arrives.
+    fn current_mark(&self) -> Option<f64> {
+        self.mark
+            .or_else(|| match (self.best_bid(), self.best_ask()) {
+                (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+                _ => None,
+            })
+    }
+
+    fn same_price(&self, left: f64, right: f64) -> bool {
+        (left - right).abs() <= self.cfg.price_tick() / 2.0
+    }
+
+    fn qty_tolerance(&self) -> f64 {
+        self.cfg.qty_tick() / 2.0
+    }
+
+    /// Displayed quantity on `side`'s own book at `price`.
+    fn displayed_qty(&self, side: OrderSide, price: f64) -> f64 {
+        let levels = match side {
+            OrderSide::Buy => &self.bids,
+            OrderSide::Sell => &self.asks,
+        };
+        levels
+            .iter()
+            .find(|level| self.same_price(level.price, price))
+            .map_or(0.0, |level| level.qty)
+    }
+
+    /// Fire every order transition, exit and cycle due before `time_ms` (or
+    /// at it, when `inclusive`), in time order. Events at a timestamp are
+    /// applied before timers due at it. At equal times orders settle first,
+    /// then the exit, then the cycle.
+    fn advance_to(&mut self, time_ms: i64, inclusive: bool) -> Result<(), BacktestError> {
+        loop {
+            let order_due = self.orders.iter().filter_map(SimOrder::due_ms).min();
+            let exit_due = self.exit.map(|exit| exit.at_ms);
+            let next = [order_due, exit_due, Some(self.next_cycle_ms)]
+                .into_iter()
+                .flatten()
+                .min()
+                .expect("a cycle is always scheduled");
+            if next > time_ms || (next == time_ms && !inclusive) {
+                return Ok(());
+            }
+            if order_due == Some(next) {
+                self.settle_orders(next)?;
+            } else if exit_due == Some(next) {
+                self.execute_exit(next)?;
+            } else {
+                self.cycle(next)?;
+                self.next_cycle_ms = next.saturating_add(self.settings.cycle_interval_ms);
+            }
+        }
+    }
+
+    fn apply(&mut self, event: &BacktestEvent) -> Result<(), BacktestError> {
+        match event {
+            BacktestEvent::Mark { mark, .. } => self.mark = Some(*mark),
+            BacktestEvent::Depth {
+                event_time_ms,
+                bids,
+                asks,
+            } => self.apply_depth(*event_time_ms, bids, asks)?,
+            BacktestEvent::Trade {
+                event_time_ms,
+                price,
+                qty,
+            } => self.apply_trade(*event_time_ms, *price, *qty)?,
+            BacktestEvent::Gap { event_time_ms } => {
+                self.stats.gaps += 1;
+                self.bids.clear();
+                self.asks.clear();
+                self.orders.clear();
+                self.exit = None;
+                self.observe_quotes(*event_time_ms)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_depth(
+        &mut self,
+        event_time_ms: i64,
+        bids: &[BookLevel],
+        asks: &[BookLevel],
+    ) -> Result<(), BacktestError> {
+        self.bids = bids
+            .iter()
+            .copied()
+            .filter(|level| level.qty > 0.0)
+            .collect();
+        self.bids
+            .sort_by(|left, right| right.price.total_cmp(&left.price));
+        self.asks = asks
+            .iter()
+            .copied()
+            .filter(|level| level.qty > 0.0)
+            .collect();
+        self.asks
+            .sort_by(|left, right| left.price.total_cmp(&right.price));
+        let (best_bid, best_ask) = (self.best_bid(), self.best_ask());
+        let mut index = 0;
+        while index < self.orders.len() {
+            let order = &self.orders[index];
+            if order.active_at_ms.is_some() {
+                index += 1;
+                continue;
+            }
+            if quote_crosses_touch(order.side, order.price, best_bid, best_ask) {
+                let (qty, price) = (order.remaining(), order.price);
+                if self.fill_order(index, qty, price, event_time_ms)? {
+                    continue;
+                }
+            } else {
+                let displayed = self.displayed_qty(order.side, order.price);
+                let order = &mut self.orders[index];
+                order.ahead = order.ahead.min(displayed);
+            }
+            index += 1;
+        }
+        self.observe_quotes(event_time_ms)
+    }
+
+    fn apply_trade(
+        &mut self,
+        event_time_ms: i64,
+        price: f64,
+        qty: f64,
+    ) -> Result<(), BacktestError> {
+        // A print at or through a touch took that side; inside the spread the
+        // side nearer to the print is assumed.
+        let buyer_aggressor = match (self.best_bid(), self.best_ask()) {
+            (_, Some(ask)) if price >= ask => true,
+            (Some(bid), _) if price <= bid => false,
+            _ => match self.current_mark() {
+                Some(reference) => price > reference,
+                None => return Ok(()),
+            },
+        };
+        let resting_side = if buyer_aggressor {
+            OrderSide::Sell
+        } else {
+            OrderSide::Buy
+        };
+        let mut candidates: Vec<(f64, u64)> = self
+            .orders
+            .iter()
+            .filter(|order| order.active_at_ms.is_none() && order.side == resting_side)
+            .filter(|order| match resting_side {
+                OrderSide::Sell => order.price <= price || self.same_price(order.price, price),
+                OrderSide::Buy => order.price >= price || self.same_price(order.price, price),
+            })
+            .map(|order| (order.price, order.id))
+            .collect();
+        // Best-priced quotes trade first.
+        candidates.sort_by(|left, right| match resting_side {
+            OrderSide::Sell => left.0.total_cmp(&right.0).then(left.1.cmp(&right.1)),
+            OrderSide::Buy => right.0.total_cmp(&left.0).then(left.1.cmp(&right.1)),
+        });
+        let mut available = qty;
+        for (order_price, id) in candidates {
+            if available <= 0.0 {
+                break;
+            }
+            let Some(index) = self.orders.iter().position(|order| order.id == id) else {
+                continue;
+            };
+            if self.same_price(order_price, price) {
+                let order = &mut self.orders[index];
+                let consumed = order.ahead.min(available);
+                order.ahead -= consumed;
+                available -= consumed;
+            }
+            let fill = self.orders[index].remaining().min(available);
+            if fill > 0.0 {
+                available -= fill;
+                self.fill_order(index, fill, order_price, event_time_ms)?;
+            }
+        }
+        self.observe_quotes(event_time_ms)
+    }
+
+    /// Fill `qty` of the order at `index`; returns whether it left the book.
+    fn fill_order(
+        &mut self,
+        index: usize,
+        qty: f64,
+        price: f64,
+        event_time_ms: i64,
+    ) -> Result<bool, BacktestError> {
+        let tolerance = self.qty_tolerance();
+        let order = &mut self.orders[index];
+        order.filled += qty;
+        let (id, side, done) = (order.id, order.side, order.remaining() <= tolerance);
+        self.stats.passive_fills += 1;
+        if done {
+            self.orders.remove(index);
+        } else {
+            self.stats.partial_fills += 1;
+        }
+        let costs = costs(price * qty, self.settings.maker_fee_bps);
+        self.record_fill(
+            id,
+            FillRole::PassiveMaker,
+            side,
+            price,
+            qty,
+            event_time_ms,
+            costs,
+        )?;
+        Ok(done)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn record_fill(
+        &mut self,
+        order_id: u64,
+        role: FillRole,
+        side: OrderSide,
+        price: f64,
+        qty: f64,
+        event_time_ms: i64,
+        costs: ExecutionCosts,
+    ) -> Result<(), BacktestError> {
+        self.position += match side {
+            OrderSide::Buy => qty,
+            OrderSide::Sell => -qty,
+        };
+        let trade_id = self.next_id();
+        let mark_at_fill = self.current_mark().unwrap_or(price);
+        if let Some(performance) = self.performance.as_mut() {
+            performance.record_fill(PerformanceFill {
+                trade_id,
+                order_id,
+                role,
+                side,
+                price,
+                qty,
+                mark_at_fill,
+                event_time_ms,
+                costs: Some(costs),
+            })?;
+        }
+        self.strategy.record_fill(role, side, price, mark_at_fill);
+        Ok(())
+    }
+
+    fn settle_orders(&mut self, time_ms: i64) -> Result<(), BacktestError> {
+        let (best_bid, best_ask) = (self.best_bid(), self.best_ask());
+        let mut index = 0;
+        while index < self.orders.len() {
+            let order = &self.orders[index];
+            if order.due_ms() != Some(time_ms) {
+                index += 1;
+                continue;
+            }
+            if order.active_at_ms.is_some() {
+                if quote_crosses_touch(order.side, order.price, best_bid, best_ask) {
+                    self.stats.post_only_rejects += 1;
+                    self.orders.remove(index);
+                    continue;
+                }
+                let ahead = self.displayed_qty(order.side, order.price);
+                let order = &mut self.orders[index];
+                order.active_at_ms = None;
+                order.ahead = ahead;
+                index += 1;
+            } else {
+                self.orders.remove(index);
+            }
+        }
+        self.observe_quotes(time_ms)
+    }
+
+    fn execute_exit(&mut self, time_ms: i64) -> Result<(), BacktestError> {
+        let Some(exit) = self.exit.take() else {
+            return Ok(());
+        };
+        // Reduce-only: never more than the position, never past flat.
+        let reduces = match exit.side {
+            OrderSide::Buy => self.position < 0.0,
+            OrderSide::Sell => self.position > 0.0,
+        };
+        let mut remaining = if reduces {
+            exit.qty.min(self.position.abs())
+        } else {
+            0.0
+        };
+        let levels = match exit.side {
+            OrderSide::Buy => &self.asks,
+            OrderSide::Sell => &self.bids,
+        };
+        let (mut qty, mut notional) = (0.0, 0.0);
+        for level in levels {
+            if remaining <= 0.0 {
+                break;
+            }
+            let take = level.qty.min(remaining);
+            qty += take;
+            notional += take * level.price;
+            remaining -= take;
+        }
+        if qty <= self.qty_tolerance() {
+            if reduces {
+                self.stats.failed_exits += 1;
+            }
+            return Ok(());
+        }
+        let order_id = self.next_id();
+        let costs = costs(notional, self.settings.taker_fee_bps);
+        self.record_fill(
+            order_id,
+            FillRole::InventoryExit,
+            exit.side,
+            notional / qty,
+            qty,
+            time_ms,
+            costs,
+        )
+    }
+
+    fn cycle(&mut self, time_ms: i64) -> Result<(), BacktestError> {
+        let Some(mark) = self.current_mark() else {
+            return Ok(());
+        };
+        let performance = match self.performance.as_mut() {
+            Some(performance) => performance,
+            None => self
+                .performance
+                .insert(PerformanceLedger::new(self.position, mark)?),
+        };
+        performance.observe_market(time_ms, mark)?;
+        let cycle = self.stats.cycles;
+        self.stats.cycles += 1;
+        let market = MarketSnapshot {
+            mark,
+            best_bid: self.best_bid(),
+            best_ask: self.best_ask(),
+        };
+        let preflight = preflight_cycle_at(
+            &mut self.breaker,
+            time_ms,
+            market,
+            self.settings.max_divergence_bps,
+            true,
+        )?;
+        let spread_decision = self
+            .spread_controller
+            .observe(self.breaker.vol_bps(), self.cfg);
+        let cfg = self
+            .spread_controller
+            .effective_config(self.cfg, &spread_decision);
+        if preflight.skip.is_some() {
+            self.stats.skipped_cycles += 1;
+            return self.observe_quotes(time_ms);
+        }
+        if preflight.halted {
+            self.stats.halted_cycles += 1;
+        }
+        let size_skew = self.size_skew_controller.observe(self.position, &cfg);
+        let resting: Vec<RestingQuote> = self
+            .orders
+            .iter()
+            .filter(|order| order.active_at_ms.is_none() && order.cancel_at_ms.is_none())
+            .map(|order| RestingQuote {
+                order_id: Some(order.id.to_string()),
+                side: order.side,
+                level: order.level,
+                price: order.price,
+                qty: order.remaining(),
+                ref_center: order.ref_center,
+                placed_at_cycle: order.placed_at_cycle,
+            })
+            .collect();
+        let pending_slots: Vec<(OrderSide, u32)> = self
+            .orders
+            .iter()
+            .filter(|order| order.active_at_ms.is_some())
+            .map(|order| (order.side, order.level))
+            .collect();
+        let fair_value = self
+            .settings
+            .fair_value
+            .estimate(market.mark, &self.bids, &self.asks);
+        let plan = plan_cycle_with(
+            &cfg,
+            self.strategy.as_ref(),
+            CycleInput {
+                cycle,
+                market,
+                position: self.position,
+                resting: &resting,
+                pending_slots: &pending_slots,
+                market_data_mode: MarketDataMode::Active,
+                active_exit_enabled: true,
+                inventory_exit_pct: self.settings.inventory_exit_pct,
+                inventory_exit_qty: self.settings.inventory_exit_qty,
+                size_skew,
+                nonlinear_skew: Default::default(),
+                guard: Default::default(),
+                wind_down: false,
+                paused: false,
+                qty_tolerance: self.qty_tolerance(),
+                vol_bps: self.breaker.vol_bps(),
+                fair_value: fair_value.map(|fair| fair.price),
+            },
+            preflight.halted,
+        );
+        for action in plan.actions {
+            match action {
+                Action::Cancel { order_id, .. } => {
+                    let cancel_at_ms = time_ms.saturating_add(self.settings.cancel_latency_ms);
+                    if let Some(order) = self
+                        .orders
+                        .iter_mut()
+                        .find(|order| order_id.as_deref() == Some(order.id.to_string().as_str()))
+                    {
+                        order.cancel_at_ms = Some(cancel_at_ms);
+                        self.stats.cancels += 1;
+                    }
+                }
+                // The live executor drops places for slots still in flight.
+                Action::Place(quote) if pending_slots.contains(&(quote.side, quote.level)) => {}
+                Action::Place(quote) => {
+                    let id = self.next_id();
+                    self.orders.push(SimOrder {
+                        id,
+                        side: quote.side,
+                        level: quote.level,
+                        price: quote.price,
+                        qty: quote.qty,
+                        filled: 0.0,
+                        ref_center: plan.ref_center,
+                        placed_at_cycle: cycle,
+                        active_at_ms: Some(time_ms.saturating_add(self.settings.order_latency_ms)),
+                        cancel_at_ms: None,
+                        ahead: 0.0,
+                    });
+                    self.stats.places += 1;
+                }
+                Action::Hold { .. } => {}
+            }
+        }
+        if let (Some(exit), None) = (plan.inventory_exit, self.exit) {
+            self.exit = Some(SimExit {
+                side: exit.side,
+                qty: exit.qty,
+                at_ms: time_ms.saturating_add(self.settings.order_latency_ms),
+            });
+            self.stats.exits += 1;
+        }
+        self.observe_quotes(time_ms)
+    }
+
+    /// Record the quote quantity resting inside the band from `time_ms` on.
+    fn observe_quotes(&mut self, time_ms: i64) -> Result<(), BacktestError> {
+        let (Some(mark), Some(performance)) = (self.current_mark(), self.performance.as_mut())
+        else {
+            return Ok(());
+        };
+        let band = mark * self.cfg.band_bps / 10_000.0;
+        let (eligible_bid_qty, eligible_ask_qty) = self
+            .orders
+            .iter()
+            .filter(|order| order.active_at_ms.is_none())
+            .filter(|order| (order.price - mark).abs() <= band + f64::EPSILON)
+            .fold((0.0, 0.0), |(bid, ask), order| match order.side {
+                OrderSide::Buy => (bid + order.remaining(), ask),
+                OrderSide::Sell => (bid, ask + order.remaining()),
+            });
+        performance.observe_quote_quality(QuoteQualityInterval {
+            event_time_ms: time_ms,
+            eligible_bid_qty,
+            eligible_ask_qty,
+        })?;
+        Ok(())
+    }
+
+    fn finish(mut self, end_time_ms: i64) -> Result<BacktestResult, BacktestError> {
+        let final_mark = self.current_mark().ok_or(BacktestError::NoMarketData)?;
+        let mut performance = self.performance.take().ok_or(BacktestError::NoMarketData)?;
+        performance.finish(end_time_ms)?;
+        Ok(BacktestResult {
+            start_time_ms: self.start_time_ms,
+            end_time_ms,
+            final_mark,
+            stats: self.stats,
+            performance: performance.summary(final_mark)?,
+        })
+    }
+}
+
+/// Costs of `notional` at `fee_bps`; a negative rate is a rebate.
+fn costs(notional: f64, fee_bps: f64) -> ExecutionCosts {
+    let fee = notional * fee_bps / 10_000.0;
+    ExecutionCosts {
+        fee_quote: fee.max(0.0),
+        rebate_quote: (-fee).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> MakerConfig {
+        MakerConfig {
+            spread_bps: 5.0,
+            band_bps: 20.0,
+            level_step_bps: 2.0,
+            refresh_bps: 3.0,
+            levels: 1,
+            size: 1.0,
+            max_position: 10.0,
+            skew_bps: 0.0,
+            price_decimals: 2,
+            qty_decimals: 2,
+            min_order_qty: 0.01,
+        }
+    }
+
+    fn settings() -> BacktestSettings {
+        BacktestSettings {
+            starting_position: 0.0,
+            cycle_interval_ms: 1_000,
+            order_latency_ms: 100,
+            cancel_latency_ms: 100,
+            maker_fee_bps: 0.0,
+            taker_fee_bps: 0.0,
+            max_divergence_bps: 25.0,
+            vol_window: 12,
+            vol_window_secs: None,
+            vol_pause_bps: 0.0,
+            adaptive_spread: AdaptiveSpreadConfig::default(),
+            size_skew: SizeSkewConfig::default(),
+            inventory_exit_pct: 0.0,
+            inventory_exit_qty: 0.0,
+            strategy: StrategyConfig::default(),
+            fair_value: FairValueConfig::default(),
+        }
+    }
+
+    fn level(price: f64, qty: f64) -> BookLevel {
+        BookLevel { price, qty }
+    }
+
+    /// A book around 100 with `bid_qty` displayed where the maker bids
+    /// (99.95, 5 bps under mark).
+    fn depth(event_time_ms: i64, bid_qty: f64) -> BacktestEvent {
+        BacktestEvent::Depth {
+            event_time_ms,
+            bids: vec![level(99.99, 2.0), level(99.95, bid_qty)],
+            asks: vec![level(100.01, 2.0), level(100.05, 3.0)],
+        }
+    }
+
+    fn mark(event_time_ms: i64, mark: f64) -> BacktestEvent {
+        BacktestEvent::Mark {
+            event_time_ms,
+            mark,
+        }
+    }
+
+    fn trade(event_time_ms: i64, price: f64, qty: f64) -> BacktestEvent {
+        BacktestEvent::Trade {
+            event_time_ms,
+            price,
+            qty,
+        }
+    }
+
+    #[test]
+    fn quote_waits_behind_the_displayed_queue_and_fills_partially() {
+        let events = vec![
+            mark(0, 100.0),
+            depth(0, 3.0),
+            // The bid is live from 100ms behind 3.0 displayed at 99.95.
+            trade(200, 99.95, 2.0),
+            // 1.0 still ahead: 0.5 of this print reaches the quote.
+            trade(300, 99.95, 1.5),
+            trade(400, 99.95, 0.25),
+            mark(900, 100.0),
+        ];
+        let result = run_backtest(&config(), &settings(), &events).unwrap();
+        assert_eq!(result.stats.places, 2);
+        assert_eq!(result.stats.passive_fills, 2);
+        assert_eq!(result.stats.partial_fills, 2);
+        assert_eq!(result.performance.passive_fills, 2);
+        assert_eq!(result.performance.position, 0.75);
+        assert!((result.performance.passive_qty - 0.75).abs() < 1e-12);
+    }
+
+    #[test]
+    fn a_book_that_moves_through_the_quote_before_it_lands_rejects_it() {
+        let events = vec![
+            mark(0, 100.0),
+            depth(0, 3.0),
+            // Asks drop through the 99.95 bid while the place is in flight.
+            BacktestEvent::Depth {
+                event_time_ms: 50,
+                bids: vec![level(99.90, 1.0)],
+                asks: vec![level(99.93, 1.0)],
+            },
+            mark(900, 100.0),
+        ];
+        let result = run_backtest(&config(), &settings(), &events).unwrap();
+        assert_eq!(result.stats.post_only_rejects, 1);
+        assert_eq!(result.stats.passive_fills, 0);
+    }
+
+    #[test]
+    fn a_quote_still_fills_while_its_cancel_is_in_flight() {
+        let mut events = vec![mark(0, 100.0), depth(0, 0.0)];
+        // At 1s the mark moves 10bps: the bid is cancelled, but the cancel
+        // only lands at 1.1s and a print at 1.05s still hits it.
+        events.push(mark(1_000, 100.1));
+        events.push(BacktestEvent::Depth {
+            event_time_ms: 1_000,
+            bids: vec![level(100.04, 2.0)],
+            asks: vec![level(100.06, 2.0)],
+        });
+        events.push(trade(1_050, 99.95, 0.4));
+        events.push(mark(1_500, 100.1));
+        let result = run_backtest(&config(), &settings(), &events).unwrap();
+        assert_eq!(result.stats.cancels, 2);
+        assert_eq!(result.stats.passive_fills, 1);
+        assert_eq!(result.performance.position, 0.4);
+    }
+
+    #[test]
+    fn inventory_exit_walks_the_book_with_taker_costs() {
+        let mut cfg = config();
+        cfg.max_position = 1.0;
+        let mut settings = settings();
+        settings.starting_position = 1.0;
+        settings.inventory_exit_pct = 100.0;
+        settings.inventory_exit_qty = 1.0;
+        settings.taker_fee_bps = 5.0;
+        let events = vec![
+            mark(0, 100.0),
+            BacktestEvent::Depth {
+                event_time_ms: 0,
+                bids: vec![level(99.99, 0.4), level(99.98, 1.0)],
+                asks: vec![level(100.01, 1.0)],
+            },
+            mark(500, 100.0),
+        ];
+        let result = run_backtest(&cfg, &settings, &events).unwrap();
+        assert_eq!(result.stats.exits, 1);
+        assert_eq!(result.performance.exit_fills, 1);
+        assert_eq!(result.performance.position, 0.0);
+        let notional = 0.4 * 99.99 + 0.6 * 99.98;
+        assert!((result.performance.fee_quote - notional * 5.0 / 10_000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn gaps_drop_quotes_and_runs_are_deterministic() {
+        let events = vec![
+            mark(0, 100.0),
+            depth(0, 1.0),
+            BacktestEvent::Gap { event_time_ms: 500 },
+            // No fills without a book, even though the print crosses the bid.
+            trade(600, 99.90, 5.0),
+            mark(1_500, 100.0),
+            depth(1_800, 0.0),
+            trade(2_500, 99.95, 1.0),
+            mark(3_000, 100.0),
+        ];
+        let first = run_backtest(&config(), &settings(), &events).unwrap();
+        let second = run_backtest(&config(), &settings(), &events).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.stats.gaps, 1);
+        assert_eq!(first.stats.skipped_cycles, 1);
+        assert_eq!(first.stats.passive_fills, 1);
+        assert_eq!(first.performance.position, 1.0);
+        assert_eq!(first.end_time_ms, 3_000);
+
+        let unordered = vec![mark(10, 100.0), mark(5, 100.0)];
+        assert_eq!(
+            run_backtest(&config(), &settings(), &unordered),
+            Err(BacktestError::TimeRegression {
+                previous_ms: 10,
+                next_ms: 5,
+            })
+        );
+    }
+}
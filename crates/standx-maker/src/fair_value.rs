@@ -0,0 +1,319 @@
+//! Fair-value estimate for the quote anchor (`[fair_value]`).
+//!
+//! The ladder has always been anchored on the venue mark. Mark follows the
+//! book with a lag, so when flow leans one way the quotes on the heavy side
+//! are the ones that get picked off. The estimator blends mark with the book
+//! mid and the size-weighted microprice, and caps the result a few bps from
+//! mark so it can only nudge the anchor, never walk the quotes out of the
+//! band. Pure decision logic: the caller supplies the book.
+
+use crate::MakerConfig;
+use std::error::Error;
+use std::fmt;
+
+/// One price level of a book.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BookLevel {
+    pub price: f64,
+    pub qty: f64,
+}
+
+/// Operator configuration for the fair-value anchor.
+///
+/// `fair = (w_mark·mark + w_mid·mid + w_micro·microprice) / Σw`, with the
+/// weights of components the book cannot supply this cycle dropped, then
+/// clamped to `mark ± max_deviation_bps`. The microprice is
+/// `(bid·ask_size + ask·bid_size) / (bid_size + ask_size)`: it leans toward
+/// the side with less size, where the next trade is likelier to move price.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FairValueConfig {
+    pub enabled: bool,
+    pub mark_weight: f64,
+    pub mid_weight: f64,
+    pub microprice_weight: f64,
+    /// Size within this many bps behind each touch counts toward the
+    /// microprice and imbalance; 0 uses the touch level only.
+    pub depth_bps: f64,
+    /// The estimate never moves further than this from mark. Red line: the
+    /// far-side quote sits at `spread_bps + max_deviation_bps` from mark, so
+    /// the sum must stay inside `band_bps` (validated in [`Self::validate`]).
+    pub max_deviation_bps: f64,
+}
+
+impl Default for FairValueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            mark_weight: 0.5,
+            mid_weight: 0.0,
+            microprice_weight: 0.5,
+            depth_bps: 0.0,
+            max_deviation_bps: 2.0,
+        }
+    }
+}
+
+/// One cycle's estimate, with its inputs for telemetry.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FairValue {
+    /// The anchor the strategy quotes around.
+    pub price: f64,
+    pub mid: Option<f64>,
+    pub microprice: Option<f64>,
+    /// `(bid_size − ask_size) / (bid_size + ask_size)` within `depth_bps`;
+    /// positive means the bid is heavier.
+    pub imbalance: Option<f64>,
+    /// `price` relative to mark, after the cap.
+    pub deviation_bps: f64,
+    /// The blend was further from mark than `max_deviation_bps`.
+    pub capped: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FairValueError(String);
+
+impl FairValueError {
+    fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for FairValueError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+impl Error for FairValueError {}
+
+impl FairValueConfig {
+    /// Validate against the base strategy config. Invalid values are rejected
+    /// even when disabled so a bad file never rides along silently.
+    pub fn validate(&self, base: &MakerConfig) -> Result<(), FairValueError> {
+        let weights = [self.mark_weight, self.mid_weight, self.microprice_weight];
+        if weights
+            .iter()
+            .any(|weight| !weight.is_finite() || *weight < 0.0)
+        {
+            return Err(FairValueError::new(
+                "fair value weights must be finite and >= 0",
+            ));
+        }
+        if weights.iter().sum::<f64>() <= 0.0 {
+            return Err(FairValueError::new(
+                "fair value weights must not all be zero",
+            ));
+        }
+        if !self.depth_bps.is_finite() || self.depth_bps < 0.0 {
+            return Err(FairValueError::new(
+                "fair value depth_bps must be finite and >= 0",
+            ));
+        }
+        if !self.max_deviation_bps.is_finite() || self.max_deviation_bps <= 0.0 {
+            return Err(FairValueError::new(
+                "fair value max_deviation_bps must be finite and > 0",
+            ));
+        }
+        if self.enabled && base.spread_bps + self.max_deviation_bps > base.band_bps {
+            return Err(FairValueError::new(
+                "fair value violates band red line: spread_bps + max_deviation_bps must be <= band_bps",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The cycle's fair value, or `None` when disabled or mark is unusable
+    /// (the planner then anchors on mark as before). Levels need not be
+    /// sorted; non-positive or non-finite ones are ignored, and a one-sided
+    /// or crossed book leaves only the mark component.
+    pub fn estimate(&self, mark: f64, bids: &[BookLevel], asks: &[BookLevel]) -> Option<FairValue> {
+        if !self.enabled || !mark.is_finite() || mark <= 0.0 {
+            return None;
+        }
+        let touch = best(bids, f64::max).zip(best(asks, f64::min));
+        let touch = touch.filter(|(bid, ask)| bid < ask);
+        let mid = touch.map(|(bid, ask)| (bid + ask) / 2.0);
+        let sizes = touch.and_then(|(bid, ask)| {
+            let bid_qty = size_within(bids, |price| price >= bid * (1.0 - self.depth_bps / 1e4));
+            let ask_qty = size_within(asks, |price| price <= ask * (1.0 + self.depth_bps / 1e4));
+            (bid_qty + ask_qty > 0.0).then_some((bid, ask, bid_qty, ask_qty))
+        });
+        let microprice = sizes.map(|(bid, ask, bid_qty, ask_qty)| {
+            (bid * ask_qty + ask * bid_qty) / (bid_qty + ask_qty)
+        });
+        let imbalance =
+            sizes.map(|(_, _, bid_qty, ask_qty)| (bid_qty - ask_qty) / (bid_qty + ask_qty));
+
+        let components = [
+            (self.mark_weight, Some(mark)),
+            (self.mid_weight, mid),
+            (self.microprice_weight, microprice),
+        ];
+        let (weighted, total) = components
+            .iter()
+            .filter_map(|&(weight, value)| value.map(|value| (weight, value)))
+            .fold((0.0, 0.0), |(sum, total), (weight, value)| {
+                (sum + weight * value, total + weight)
+            });
+        let blend = if total > 0.0 { weighted / total } else { mark };
+        let raw_bps = (blend / mark - 1.0) * 1e4;
+        let deviation_bps = raw_bps.clamp(-self.max_deviation_bps, self.max_deviation_bps);
+        Some(FairValue {
+            price: mark * (1.0 + deviation_bps / 1e4),
+            mid,
+            microprice,
+            imbalance,
+            deviation_bps,
+            capped: raw_bps.abs() > self.max_deviation_bps,
+        })
+    }
+}
+
+fn usable(level: &BookLevel) -> bool {
+    level.price.is_finite() && level.price > 0.0 && level.qty.is_finite() && level.qty > 0.0
+}
+
+fn best(levels: &[BookLevel], pick: fn(f64, f64) -> f64) -> Option<f64> {
+    levels
+        .iter()
+        .filter(|level| usable(level))
+        .map(|level| level.price)
+        .reduce(pick)
+}
+
+fn size_within(levels: &[BookLevel], within: impl Fn(f64) -> bool) -> f64 {
+    levels
+        .iter()
+        .filter(|level| usable(level) && within(level.price))
+        .map(|level| level.qty)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: f64, qty: f64) -> BookLevel {
+        BookLevel { price, qty }
+    }
+
+    fn enabled(mark_weight: f64, mid_weight: f64, microprice_weight: f64) -> FairValueConfig {
+        FairValueConfig {
+            enabled: true,
+            mark_weight,
+            mid_weight,
+            microprice_weight,
+            max_deviation_bps: 10.0,
+            ..FairValueConfig::default()
+        }
+    }
+
+    fn base() -> MakerConfig {
+        MakerConfig {
+            spread_bps: 5.0,
+            band_bps: 20.0,
+            level_step_bps: 2.0,
+            refresh_bps: 3.0,
+            levels: 1,
+            size: 0.01,
+            max_position: 0.05,
+            skew_bps: 0.0,
+            price_decimals: 2,
+            qty_decimals: 3,
+            min_order_qty: 0.001,
+        }
+    }
+
+    #[test]
+    fn disabled_estimator_leaves_the_mark_anchor() {
+        let bids = [level(99.99, 1.0)];
+        let asks = [level(100.01, 9.0)];
+        assert_eq!(
+            FairValueConfig::default().estimate(100.0, &bids, &asks),
+            None
+        );
+    }
+
+    #[test]
+    fn microprice_leans_toward_the_thin_side() {
+        // Heavy bid, thin ask: the next trade is likelier to lift the ask.
+        let bids = [level(99.99, 3.0)];
+        let asks = [level(100.01, 1.0)];
+        let fair = enabled(0.0, 0.0, 1.0)
+            .estimate(100.0, &bids, &asks)
+            .unwrap();
+        assert!((fair.microprice.unwrap() - 100.005).abs() < 1e-9);
+        assert!((fair.imbalance.unwrap() - 0.5).abs() < 1e-12);
+        assert!((fair.price - 100.005).abs() < 1e-9);
+        assert!((fair.deviation_bps - 0.5).abs() < 1e-6);
+        assert!(!fair.capped);
+    }
+
+    #[test]
+    fn weights_blend_and_depth_counts_size_behind_the_touch() {
+        let bids = [level(99.99, 1.0), level(99.90, 5.0), level(99.0, 100.0)];
+        let asks = [level(100.01, 1.0), level(100.5, 100.0)];
+        let mut config = enabled(1.0, 1.0, 2.0);
+        let top = config.estimate(100.0, &bids, &asks).unwrap();
+        assert_eq!(top.imbalance, Some(0.0));
+        assert!((top.price - 100.0).abs() < 1e-9);
+
+        // 10 bps of depth reaches the 99.90 bid but not the 99.0 bid or the
+        // 100.5 ask: bid 6 vs ask 1.
+        config.depth_bps = 10.0;
+        let deep = config.estimate(100.0, &bids, &asks).unwrap();
+        let micro = (99.99 * 1.0 + 100.01 * 6.0) / 7.0;
+        assert!((deep.microprice.unwrap() - micro).abs() < 1e-9);
+        assert!((deep.imbalance.unwrap() - 5.0 / 7.0).abs() < 1e-12);
+        let expected = (100.0 + 100.0 + 2.0 * micro) / 4.0;
+        assert!((deep.price - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn deviation_is_capped_around_mark() {
+        // A stale mark far below a thin-ask book.
+        let bids = [level(100.09, 9.0)];
+        let asks = [level(100.11, 1.0)];
+        let mut config = enabled(0.0, 1.0, 0.0);
+        config.max_deviation_bps = 2.0;
+        let fair = config.estimate(100.0, &bids, &asks).unwrap();
+        assert!(fair.capped);
+        assert!((fair.deviation_bps - 2.0).abs() < 1e-12);
+        assert!((fair.price - 100.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn one_sided_or_crossed_book_falls_back_to_mark() {
+        let config = enabled(0.0, 1.0, 1.0);
+        let one_sided = config.estimate(100.0, &[level(99.99, 1.0)], &[]).unwrap();
+        assert_eq!(one_sided.mid, None);
+        assert_eq!(one_sided.microprice, None);
+        assert_eq!(one_sided.price, 100.0);
+        let crossed = config
+            .estimate(100.0, &[level(100.02, 1.0)], &[level(100.01, 1.0)])
+            .unwrap();
+        assert_eq!(crossed.price, 100.0);
+        // Zero-size levels carry no information.
+        let empty = config
+            .estimate(100.0, &[level(99.99, 0.0)], &[level(100.01, 0.0)])
+            .unwrap();
+        assert_eq!(empty.mid, None);
+        assert_eq!(empty.price, 100.0);
+    }
+
+    #[test]
+    fn validation_rejects_bad_weights_and_band_red_line() {
+        let base = base();
+        assert!(FairValueConfig::default().validate(&base).is_ok());
+        assert!(enabled(-1.0, 0.0, 1.0).validate(&base).is_err());
+        assert!(enabled(0.0, 0.0, 0.0).validate(&base).is_err());
+        assert!(enabled(1.0, 0.0, f64::NAN).validate(&base).is_err());
+        let mut config = enabled(1.0, 0.0, 1.0);
+        config.max_deviation_bps = 15.1;
+        assert!(config.validate(&base).is_err());
+        config.max_deviation_bps = 15.0;
+        assert!(config.validate(&base).is_ok());
+        config.depth_bps = -1.0;
+        assert!(config.validate(&base).is_err());
+    }
+}
@@ -9,7 +9,7 @@
 
 use crate::{
     is_current_run_client_order_id, ExecutionCosts, FillRole, MakerStats, PerformanceError,
-    PerformanceFill, PerformanceLedger,
+    PerformanceFill, PerformanceLedger, PerformanceSnapshot,
 };
 use standx_sdk::models::OrderSide;
 use std::collections::{HashSet, VecDeque};
@@ -149,6 +149,17 @@ impl fmt::Display for LedgerError {
 
 impl std::error::Error for LedgerError {}
 
+/// Durable ledger state carried across a supervised restart. ID lists are
+/// sorted ascending so repeated snapshots of the same state are identical.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerSnapshot {
+    pub expected_position: f64,
+    pub maker_order_ids: Vec<u64>,
+    pub exit_order_ids: Vec<u64>,
+    pub seen_trade_ids: Vec<u64>,
+    pub performance: Option<PerformanceSnapshot>,
+}
+
 #[derive(Debug)]
 pub struct MakerLedger {
     pub expected_position: f64,
@@ -195,6 +206,40 @@ impl MakerLedger {
         self.performance = None;
     }
 
+    /// Capture durable state as of `saved_at_ms`. Trades still buffered for
+    /// an unowned order are not carried: a restart cancels that order, and a
+    /// real fill it left behind surfaces as a venue position mismatch when
+    /// the session is resumed.
+    pub fn snapshot(&self, saved_at_ms: i64) -> Result<LedgerSnapshot, PerformanceError> {
+        Ok(LedgerSnapshot {
+            expected_position: self.expected_position,
+            maker_order_ids: sorted_ids(&self.maker_order_ids),
+            exit_order_ids: sorted_ids(&self.exit_order_ids),
+            seen_trade_ids: sorted_ids(&self.seen_trade_ids),
+            performance: self
+                .performance
+                .as_ref()
+                .map(|performance| performance.snapshot(saved_at_ms))
+                .transpose()?,
+        })
+    }
+
+    /// Rebuild a ledger from [`Self::snapshot`].
+    pub fn restore(snapshot: LedgerSnapshot) -> Result<Self, PerformanceError> {
+        Ok(Self {
+            expected_position: snapshot.expected_position,
+            maker_order_ids: snapshot.maker_order_ids.into_iter().collect(),
+            exit_order_ids: snapshot.exit_order_ids.into_iter().collect(),
+            seen_trade_ids: snapshot.seen_trade_ids.into_iter().collect(),
+            pending_trade_ids: HashSet::new(),
+            pending_trades: VecDeque::new(),
+            performance: snapshot
+                .performance
+                .map(PerformanceLedger::restore)
+                .transpose()?,
+        })
+    }
+
     /// Adopt an order only when its client ID belongs to this run.
     pub fn adopt_order(
         &mut self,
@@ -394,6 +439,12 @@ impl MakerLedger {
     }
 }
 
+fn sorted_ids(ids: &HashSet<u64>) -> Vec<u64> {
+    let mut sorted: Vec<u64> = ids.iter().copied().collect();
+    sorted.sort_unstable();
+    sorted
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -18,7 +18,9 @@
 use standx_sdk::models::OrderSide;
 
 pub mod account_projection;
+pub mod backtest;
 pub mod external_guard;
+pub mod fair_value;
 pub mod inventory;
 pub mod latency;
 pub mod ledger;
@@ -29,6 +31,8 @@ pub mod recovery;
 pub mod replay;
 pub mod risk;
 pub mod runtime;
+pub mod session;
+pub mod strategy;
 pub mod volatility;
 
 pub use account_projection::{
@@ -37,9 +41,13 @@ pub use account_projection::{
     ProjectionPendingRequest, ProjectionRegistryError, ProjectionRequestResolution,
     MAX_PENDING_ORDER_REQUESTS,
 };
+pub use backtest::{
+    run_backtest, BacktestError, BacktestEvent, BacktestResult, BacktestSettings, BacktestStats,
+};
 pub use external_guard::{
     ExternalDivergence, GuardConfig, GuardController, GuardDecision, GuardError,
 };
+pub use fair_value::{BookLevel, FairValue, FairValueConfig, FairValueError};
 pub use inventory::{
     NonlinearSkewConfig, SizeSkewConfig, SizeSkewController, SizeSkewDecision, SizeSkewError,
 };
@@ -47,7 +55,7 @@ pub use latency::{
     LatencyError, LatencyMetricSummary, LatencyRequest, LatencyRequestContext, LatencyRequestKind,
     LatencyRequestOutcome, LatencySummary, OrderLatencyTracker,
 };
-pub use ledger::{LedgerError, LedgerTrade, MakerFill, MakerLedger, TradeSource};
+pub use ledger::{LedgerError, LedgerSnapshot, LedgerTrade, MakerFill, MakerLedger, TradeSource};
 pub use market_data::{
     MarketDataFaultClass, MarketDataHealth, MarketDataMode, MarketDataObservation,
     MarketDataTransition, MARKET_DATA_BAD_GRACE_MS, MARKET_DATA_BAD_OBSERVATIONS_TO_DEGRADE,
@@ -59,19 +67,25 @@ pub use ownership::{
     MAKER_CL_ORD_ID_PREFIX,
 };
 pub use performance::{
-    ExecutionCosts, FillRole, InventoryTimeSummary, MarkoutSummary, PerformanceError,
-    PerformanceFill, PerformanceLedger, PerformanceSummary, QuoteQualityInterval, QuoteTimeSummary,
-    MARKOUT_WINDOWS_MS,
+    ExecutionCosts, FillRole, InventoryTimeSummary, MarkoutAccumulator, MarkoutSummary,
+    PerformanceError, PerformanceFill, PerformanceLedger, PerformanceSnapshot, PerformanceSummary,
+    QuoteQualityInterval, QuoteTimeSummary, MARKOUT_WINDOWS_MS,
 };
 pub use recovery::{recovery_retry_delay_secs, MAX_RECOVERY_RETRY_BACKOFF_SECS};
 pub use replay::{
     run_replay, ReplayCycle, ReplayCycleOutcome, ReplayError, ReplayEvent, ReplayResult,
     ReplaySettings,
 };
-pub use risk::{PositionAlertAnchor, PositionRiskEvent, PositionRiskKind};
+pub use risk::{PositionAlertAnchor, PositionAlertSnapshot, PositionRiskEvent, PositionRiskKind};
 pub use runtime::{
-    order_cancel_rejection_reason, MakerEffect, MakerEvent, MakerState, RecoveryTarget,
-    RequestTimeoutPhase, RuntimeStopReason, WorkToken, MAX_CONSECUTIVE_CYCLE_ERRORS,
+    order_cancel_rejection_reason, ControlCommand, MakerEffect, MakerEvent, MakerState,
+    OperatorControls, RecoveryCounts, RecoveryTarget, RequestTimeoutPhase, RuntimeStopReason,
+    WorkToken, MAX_CONSECUTIVE_CYCLE_ERRORS,
+};
+pub use session::{check_resume_orders, check_resume_position, ResumeError, SessionSnapshot};
+pub use strategy::{
+    AvellanedaStoikovConfig, AvellanedaStoikovStrategy, LadderStrategy, QuoteStrategy,
+    StrategyConfig, StrategyError,
 };
 pub use volatility::{
     AdaptiveSpreadConfig, AdaptiveSpreadError, SpreadController, SpreadDecision, SpreadTier,
@@ -415,6 +429,21 @@ pub struct MakerStats {
     last_position: f64,
 }
 
+/// Durable [`MakerStats`] state carried across a supervised restart.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MakerStatsSnapshot {
+    pub cycles: u64,
+    pub two_sided_cycles: u64,
+    pub buy_fills: u64,
+    pub sell_fills: u64,
+    pub filled_qty: f64,
+    pub cash: f64,
+    pub spread_bps_sum: f64,
+    pub spread_bps_n: u64,
+    pub max_abs_position: f64,
+    pub last_position: f64,
+}
+
 impl MakerStats {
     /// Start a maker session while adopting an existing venue position.
     /// Session PnL is zero at `baseline_mark`; venue/account PnL retains its
@@ -428,6 +457,39 @@ impl MakerStats {
         }
     }
 
+    /// Capture every accumulator, including the private ones, for a durable
+    /// session snapshot.
+    pub fn snapshot(&self) -> MakerStatsSnapshot {
+        MakerStatsSnapshot {
+            cycles: self.cycles,
+            two_sided_cycles: self.two_sided_cycles,
+            buy_fills: self.buy_fills,
+            sell_fills: self.sell_fills,
+            filled_qty: self.filled_qty,
+            cash: self.cash,
+            spread_bps_sum: self.spread_bps_sum,
+            spread_bps_n: self.spread_bps_n,
+            max_abs_position: self.max_abs_position,
+            last_position: self.last_position,
+        }
+    }
+
+    /// Continue a session from [`Self::snapshot`].
+    pub fn restore(snapshot: MakerStatsSnapshot) -> Self {
+        Self {
+            cycles: snapshot.cycles,
+            two_sided_cycles: snapshot.two_sided_cycles,
+            buy_fills: snapshot.buy_fills,
+            sell_fills: snapshot.sell_fills,
+            filled_qty: snapshot.filled_qty,
+            cash: snapshot.cash,
+            spread_bps_sum: snapshot.spread_bps_sum,
+            spread_bps_n: snapshot.spread_bps_n,
+            max_abs_position: snapshot.max_abs_position,
+            last_position: snapshot.last_position,
+        }
+    }
+
     /// Record an executed fill at `price` against `mark` at fill time.
     pub fn record_fill(&mut self, side: OrderSide, price: f64, qty: f64, mark: f64) {
         self.filled_qty += qty;
@@ -640,8 +702,25 @@ pub struct CycleInput<'a> {
     /// position through the reduce-only exit path, ignoring the configured
     /// exit thresholds. Converges to flat instead of re-accumulating.
     pub wind_down: bool,
+    /// Operator pause from the control socket: no new quotes (resting ones
+    /// are cancelled), while inventory exits keep running.
+    pub paused: bool,
     /// Positions at or below this magnitude count as flat during wind-down.
     pub qty_tolerance: f64,
+    /// Rolling volatility from the caller's [`VolBreaker`]: the mark's
+    /// peak-to-trough range over its window, in bps.
+    pub vol_bps: f64,
+    /// Anchor from the caller's [`FairValueConfig::estimate`]; `None` keeps
+    /// quoting around mark.
+    pub fair_value: Option<f64>,
+}
+
+impl CycleInput<'_> {
+    /// The price strategies quote around: the fair value when one was
+    /// estimated, otherwise mark. Band and no-cross guards stay on mark.
+    pub fn anchor(&self) -> f64 {
+        self.fair_value.unwrap_or(self.market.mark)
+    }
 }
 
 /// A deterministic plan for the executor to apply after a successful preflight.
@@ -671,6 +750,18 @@ pub struct CyclePlan {
 /// residual position above `input.qty_tolerance` yields a reduce-only exit
 /// plan regardless of the configured exit thresholds.
 pub fn plan_cycle(cfg: &MakerConfig, input: CycleInput<'_>, halted: bool) -> CyclePlan {
+    plan_cycle_with(cfg, &LadderStrategy, input, halted)
+}
+
+/// [`plan_cycle`] with the desired quotes and their anchor taken from
+/// `strategy`. Exits, halts, wind-down, the band/no-cross/tick guards, the
+/// exposure cap and the anti-flicker reconcile are the same for every model.
+pub fn plan_cycle_with(
+    cfg: &MakerConfig,
+    strategy: &dyn QuoteStrategy,
+    input: CycleInput<'_>,
+    halted: bool,
+) -> CyclePlan {
     let market_active = input.market_data_mode == MarketDataMode::Active;
     let requested_inventory_exit = (market_active
         && (input.active_exit_enabled || input.wind_down))
@@ -693,20 +784,26 @@ pub fn plan_cycle(cfg: &MakerConfig, input: CycleInput<'_>, halted: bool) -> Cyc
     let inventory_exit = (!halted && market_active)
         .then_some(requested_inventory_exit.clone())
         .flatten();
+    let center = strategy.center(cfg, &input);
     // Wind-down never places new quotes, even once flat: the session must
     // converge to flat instead of re-accumulating inventory.
-    let desired = if halted || !market_active || inventory_exit.is_some() || input.wind_down {
+    let desired = if halted
+        || !market_active
+        || inventory_exit.is_some()
+        || input.wind_down
+        || input.paused
+    {
         Vec::new()
     } else {
-        let raw = compute_desired_quotes(
+        let raw = guard_desired_quotes(
             cfg,
             input.market.mark,
             input.market.best_bid,
             input.market.best_ask,
             input.position,
             input.size_skew,
-            input.nonlinear_skew,
             input.guard,
+            &strategy.desired_quotes(cfg, &input),
         );
         cap_desired_exposure(cfg, input.position, &raw, input.pending_slots)
     };
@@ -714,18 +811,17 @@ pub fn plan_cycle(cfg: &MakerConfig, input: CycleInput<'_>, halted: bool) -> Cyc
     CyclePlan {
         requested_inventory_exit,
         inventory_exit,
-        actions: reconcile(
+        actions: reconcile_around(
             cfg,
             input.market.mark,
-            input.position,
+            center,
             input.market.best_bid,
             input.market.best_ask,
             &desired,
             input.resting,
             input.cycle,
-            input.nonlinear_skew,
         ),
-        ref_center: skew_center_with(cfg, input.nonlinear_skew, input.market.mark, input.position),
+        ref_center: center,
     }
 }
 
@@ -788,6 +884,21 @@ impl AlertMonitor {
         self
     }
 
+    /// Adopt the thresholds of `next` (a config reload). Each alert keeps its
+    /// firing state so a retune neither re-fires nor silently clears one that
+    /// is still armed; the new threshold decides when it clears. An alert the
+    /// reload disables is dropped without a clear message.
+    pub fn retune(&mut self, next: AlertMonitor) {
+        *self = Self {
+            loss_on: self.loss_on && next.loss_limit > 0.0,
+            inv_on: self.inv_on && next.inventory_pct > 0.0,
+            uptime_on: self.uptime_on && next.uptime_floor > 0.0,
+            equity_on: self.equity_on && next.equity_floor > 0.0,
+            margin_on: self.margin_on && next.margin_floor > 0.0,
+            ..next
+        };
+    }
+
     /// Whether any threshold is configured.
     pub fn enabled(&self) -> bool {
         self.loss_limit > 0.0 || self.inventory_pct > 0.0 || self.uptime_floor > 0.0
@@ -937,23 +1048,24 @@ impl AlertMonitor {
     }
 }
 
-/// Compute the desired quote set for the current market snapshot.
+/// Turn a strategy's proposed quotes into the desired quote set for the
+/// current market snapshot.
 ///
-/// Applies, in order: the inventory-skewed spread/level ladder, the band clamp,
-/// the no-cross clamp, directional tick rounding (with band re-entry), the
-/// min-qty filter, and max-position side suppression. Quotes that fail a guard
+/// Applies, in order: max-position and external-guard side suppression, the
+/// min-qty filter, size skew, the band clamp, the no-cross clamp, and
+/// directional tick rounding (with band re-entry). Quotes that fail a guard
 /// are dropped; duplicate prices after clamping/rounding are collapsed (outer
 /// level wins nothing — the inner level is kept).
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn compute_desired_quotes(
+pub(crate) fn guard_desired_quotes(
     cfg: &MakerConfig,
     mark: f64,
     best_bid: Option<f64>,
     best_ask: Option<f64>,
     position: f64,
     size_skew: SizeSkewDecision,
-    nonlinear_skew: NonlinearSkewConfig,
     guard: GuardDecision,
+    proposed: &[DesiredQuote],
 ) -> Vec<DesiredQuote> {
     let mut out = Vec::new();
     if !mark.is_finite()
@@ -964,24 +1076,18 @@ pub(crate) fn compute_desired_quotes(
         return out;
     }
 
-    let qty = round_to_decimals(cfg.size, cfg.qty_decimals);
-    if qty < cfg.min_order_qty || qty <= 0.0 {
-        return out;
-    }
-
     let tick = cfg.price_tick();
     // Band eligibility is defined around the TRUE mark, not the skewed center.
     let band_lo = mark * (1.0 - cfg.band_bps / 1e4);
     let band_hi = mark * (1.0 + cfg.band_bps / 1e4);
 
-    // Ladder is centered on the inventory-skewed price; the band/no-cross
-    // guards below still reference the true mark and touch.
-    let center = skew_center_with(cfg, nonlinear_skew, mark, position);
-
     let suppress_buy = position >= cfg.max_position;
     let suppress_sell = position <= -cfg.max_position;
 
-    for side in [OrderSide::Buy, OrderSide::Sell] {
+    let mut last_buy: Option<f64> = None;
+    let mut last_sell: Option<f64> = None;
+    for quote in proposed {
+        let side = quote.side;
         if (side == OrderSide::Buy && suppress_buy) || (side == OrderSide::Sell && suppress_sell) {
             continue;
         }
@@ -992,6 +1098,10 @@ pub(crate) fn compute_desired_quotes(
         if guard.active && guard.endangered == Some(side) {
             continue;
         }
+        let qty = round_to_decimals(quote.qty, cfg.qty_decimals);
+        if !qty.is_finite() || qty < cfg.min_order_qty || qty <= 0.0 {
+            continue;
+        }
         let side_qty = if size_skew.active && size_skew.add_side == Some(side) {
             let Some(add_qty) = size_skew.add_qty else {
                 continue;
@@ -1000,77 +1110,74 @@ pub(crate) fn compute_desired_quotes(
         } else {
             qty
         };
-        let mut last_price: Option<f64> = None;
-        for level in 0..cfg.levels {
-            let offset_bps = cfg.spread_bps + level as f64 * cfg.level_step_bps;
-            let raw_price = match side {
-                OrderSide::Buy => center * (1.0 - offset_bps / 1e4),
-                OrderSide::Sell => center * (1.0 + offset_bps / 1e4),
-            };
-
-            // Intersect the eligibility band with the post-only no-cross
-            // interval. If no tick can satisfy both, omit this side instead of
-            // emitting a quote outside the band or relying on ALO rejection.
-            let (price_lo, price_hi) = match side {
-                OrderSide::Buy => (
-                    band_lo,
-                    best_ask.map_or(band_hi, |ask| band_hi.min(ask - tick)),
-                ),
-                OrderSide::Sell => (
-                    best_bid.map_or(band_lo, |bid| band_lo.max(bid + tick)),
-                    band_hi,
-                ),
-            };
-            let price_tolerance = tick * 1e-6;
-            if !raw_price.is_finite()
-                || !price_lo.is_finite()
-                || !price_hi.is_finite()
-                || price_lo > price_hi + price_tolerance
-            {
-                continue;
-            }
+        let raw_price = quote.price;
+
+        // Intersect the eligibility band with the post-only no-cross
+        // interval. If no tick can satisfy both, omit this quote instead of
+        // emitting it outside the band or relying on ALO rejection.
+        let (price_lo, price_hi) = match side {
+            OrderSide::Buy => (
+                band_lo,
+                best_ask.map_or(band_hi, |ask| band_hi.min(ask - tick)),
+            ),
+            OrderSide::Sell => (
+                best_bid.map_or(band_lo, |bid| band_lo.max(bid + tick)),
+                band_hi,
+            ),
+        };
+        let price_tolerance = tick * 1e-6;
+        if !raw_price.is_finite()
+            || !price_lo.is_finite()
+            || !price_hi.is_finite()
+            || price_lo > price_hi + price_tolerance
+        {
+            continue;
+        }
 
-            let mut price = raw_price.clamp(price_lo, price_hi);
+        let mut price = raw_price.clamp(price_lo, price_hi);
 
-            // Directional tick rounding: away from mark, so rounding never
-            // pushes us through the touch.
-            price = match side {
-                OrderSide::Buy => floor_to_decimals(price, cfg.price_decimals),
-                OrderSide::Sell => ceil_to_decimals(price, cfg.price_decimals),
-            };
+        // Directional tick rounding: away from mark, so rounding never
+        // pushes us through the touch.
+        price = match side {
+            OrderSide::Buy => floor_to_decimals(price, cfg.price_decimals),
+            OrderSide::Sell => ceil_to_decimals(price, cfg.price_decimals),
+        };
 
-            // Directional rounding can leave the feasible interval when the
-            // band boundary is not tick-aligned. Snap back to the nearest
-            // valid tick, then re-check every constraint.
-            if price < price_lo {
-                price = ceil_to_decimals(price_lo, cfg.price_decimals);
-            } else if price > price_hi {
-                price = floor_to_decimals(price_hi, cfg.price_decimals);
-            }
+        // Directional rounding can leave the feasible interval when the
+        // band boundary is not tick-aligned. Snap back to the nearest
+        // valid tick, then re-check every constraint.
+        if price < price_lo {
+            price = ceil_to_decimals(price_lo, cfg.price_decimals);
+        } else if price > price_hi {
+            price = floor_to_decimals(price_hi, cfg.price_decimals);
+        }
 
-            if !price.is_finite()
-                || price <= 0.0
-                || price < price_lo - price_tolerance
-                || price > price_hi + price_tolerance
-                || best_ask.is_some_and(|ask| side == OrderSide::Buy && price >= ask)
-                || best_bid.is_some_and(|bid| side == OrderSide::Sell && price <= bid)
-            {
-                continue;
-            }
+        if !price.is_finite()
+            || price <= 0.0
+            || price < price_lo - price_tolerance
+            || price > price_hi + price_tolerance
+            || best_ask.is_some_and(|ask| side == OrderSide::Buy && price >= ask)
+            || best_bid.is_some_and(|bid| side == OrderSide::Sell && price <= bid)
+        {
+            continue;
+        }
 
-            // Collapse duplicate levels (clamping can flatten the ladder).
-            if last_price == Some(price) {
-                continue;
-            }
-            last_price = Some(price);
-
-            out.push(DesiredQuote {
-                side,
-                level,
-                price,
-                qty: side_qty,
-            });
+        // Collapse duplicate levels (clamping can flatten the ladder).
+        let last_price = match side {
+            OrderSide::Buy => &mut last_buy,
+            OrderSide::Sell => &mut last_sell,
+        };
+        if *last_price == Some(price) {
+            continue;
         }
+        *last_price = Some(price);
+
+        out.push(DesiredQuote {
+            side,
+            level: quote.level,
+            price,
+            qty: side_qty,
+        });
     }
 
     out
@@ -1132,6 +1239,32 @@ pub fn resting_quotes_would_cross(
         .any(|quote| quote_crosses_touch(quote.side, quote.price, best_bid, best_ask))
 }
 
+/// [`reconcile_around`] the ladder's inventory-skewed center.
+#[cfg(test)]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn reconcile(
+    cfg: &MakerConfig,
+    mark: f64,
+    position: f64,
+    best_bid: Option<f64>,
+    best_ask: Option<f64>,
+    desired: &[DesiredQuote],
+    resting: &[RestingQuote],
+    cycle: u64,
+    nonlinear_skew: NonlinearSkewConfig,
+) -> Vec<Action> {
+    reconcile_around(
+        cfg,
+        mark,
+        skew_center_with(cfg, nonlinear_skew, mark, position),
+        best_bid,
+        best_ask,
+        desired,
+        resting,
+        cycle,
+    )
+}
+
 /// Diff desired vs resting quotes, applying the anti-flicker hold rule.
 ///
 /// Decision table per resting quote (checked in order):
@@ -1145,29 +1278,28 @@ pub fn resting_quotes_would_cross(
 /// | 5 | quote center drifted > refresh_bps from ref_center | Cancel (MarkMovedBeyondRefresh) |
 /// | 6 | otherwise                                        | Hold (anti-flicker)           |
 ///
-/// The center (row 5) is `skew_center(mark, position)`, so this single rule
-/// re-quotes on both mark movement and inventory skew; with skew off it is the
-/// bare mark, identical to prior behavior. Every desired quote without a
+/// The center (row 5) is the strategy's [`QuoteStrategy::center`]; for the
+/// ladder that is `skew_center(mark, position)`, so this single rule re-quotes
+/// on both mark movement and inventory skew, and with skew off it is the bare
+/// mark. Every desired quote without a
 /// surviving resting counterpart yields a `Place`. The returned Vec orders all
 /// Cancels before all Places so the executor frees margin before re-placing;
 /// Holds come last.
 #[allow(clippy::too_many_arguments)]
-pub(crate) fn reconcile(
+pub(crate) fn reconcile_around(
     cfg: &MakerConfig,
     mark: f64,
-    position: f64,
+    center: f64,
     best_bid: Option<f64>,
     best_ask: Option<f64>,
     desired: &[DesiredQuote],
     resting: &[RestingQuote],
     cycle: u64,
-    nonlinear_skew: NonlinearSkewConfig,
 ) -> Vec<Action> {
     // Band/no-cross reference the true mark and touch; the anti-flicker anchor
-    // uses the inventory-skewed center.
+    // uses the strategy's (by default inventory-skewed) center.
     let band_lo = mark * (1.0 - cfg.band_bps / 1e4);
     let band_hi = mark * (1.0 + cfg.band_bps / 1e4);
-    let center = skew_center_with(cfg, nonlinear_skew, mark, position);
 
     let desired_has = |side: OrderSide, level: u32| -> bool {
         desired.iter().any(|d| d.side == side && d.level == level)
@@ -1258,15 +1390,15 @@ mod tests {
         best_ask: Option<f64>,
         position: f64,
     ) -> Vec<DesiredQuote> {
-        compute_desired_quotes(
+        guard_desired_quotes(
             cfg,
             mark,
             best_bid,
             best_ask,
             position,
             SizeSkewDecision::INACTIVE,
-            NonlinearSkewConfig::default(),
             GuardDecision::INACTIVE,
+            &strategy::ladder(cfg, skew_center(cfg, mark, position), cfg.spread_bps),
         )
     }
 
@@ -2107,7 +2239,10 @@ mod tests {
             nonlinear_skew: Default::default(),
             guard: Default::default(),
             wind_down: false,
+            paused: false,
             qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
         };
 
         let exit_plan = plan_cycle(&c, input, false);
@@ -2164,7 +2299,10 @@ mod tests {
                 nonlinear_skew: Default::default(),
                 guard: Default::default(),
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2180,6 +2318,197 @@ mod tests {
         assert!(buy_places.is_empty());
     }
 
+    /// Proposes quotes no guard would allow, to exercise the shared risk layer.
+    #[derive(Debug)]
+    struct Reckless;
+
+    impl QuoteStrategy for Reckless {
+        fn name(&self) -> &'static str {
+            "reckless"
+        }
+
+        fn center(&self, _cfg: &MakerConfig, _input: &CycleInput<'_>) -> f64 {
+            100.5
+        }
+
+        fn desired_quotes(&self, _cfg: &MakerConfig, _input: &CycleInput<'_>) -> Vec<DesiredQuote> {
+            vec![
+                DesiredQuote {
+                    side: OrderSide::Buy,
+                    level: 0,
+                    price: 101.0,
+                    qty: 0.012345,
+                },
+                DesiredQuote {
+                    side: OrderSide::Sell,
+                    level: 0,
+                    price: 120.0,
+                    qty: 0.01,
+                },
+                DesiredQuote {
+                    side: OrderSide::Sell,
+                    level: 1,
+                    price: 130.0,
+                    qty: 0.01,
+                },
+                DesiredQuote {
+                    side: OrderSide::Sell,
+                    level: 2,
+                    price: 100.3,
+                    qty: 0.0001,
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn custom_strategy_quotes_pass_through_band_cross_and_tick_guards() {
+        let c = cfg();
+        let input = CycleInput {
+            cycle: 1,
+            market: MarketSnapshot {
+                mark: 100.0,
+                best_bid: Some(99.9),
+                best_ask: Some(100.1),
+            },
+            position: 0.0,
+            resting: &[],
+            pending_slots: &[],
+            market_data_mode: MarketDataMode::Active,
+            active_exit_enabled: false,
+            inventory_exit_pct: 0.0,
+            inventory_exit_qty: 0.0,
+            size_skew: Default::default(),
+            nonlinear_skew: Default::default(),
+            guard: Default::default(),
+            wind_down: false,
+            paused: false,
+            qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
+        };
+
+        let plan = plan_cycle_with(&c, &Reckless, input, false);
+        let places = plan
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Place(quote) => Some((quote.side, quote.level, quote.price, quote.qty)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // The crossing bid stops a tick inside the ask with its size rounded;
+        // both far asks clamp onto the band edge and collapse into one; the
+        // sub-minimum ask is dropped.
+        assert_eq!(
+            places,
+            vec![
+                (OrderSide::Buy, 0, 100.09, 0.0123),
+                (OrderSide::Sell, 0, 100.2, 0.01),
+            ]
+        );
+        assert_eq!(plan.ref_center, 100.5);
+    }
+
+    #[test]
+    fn ladder_strategy_is_the_default_plan() {
+        let c = MakerConfig {
+            levels: 3,
+            skew_bps: 4.0,
+            ..cfg()
+        };
+        let input = CycleInput {
+            cycle: 2,
+            market: MarketSnapshot {
+                mark: 100.0,
+                best_bid: Some(99.95),
+                best_ask: Some(100.05),
+            },
+            position: 0.02,
+            resting: &[],
+            pending_slots: &[],
+            market_data_mode: MarketDataMode::Active,
+            active_exit_enabled: false,
+            inventory_exit_pct: 0.0,
+            inventory_exit_qty: 0.0,
+            size_skew: Default::default(),
+            nonlinear_skew: Default::default(),
+            guard: Default::default(),
+            wind_down: false,
+            paused: false,
+            qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
+        };
+
+        assert_eq!(StrategyConfig::default().name(), "ladder");
+        assert_eq!(
+            plan_cycle_with(&c, StrategyConfig::default().build().as_ref(), input, false),
+            plan_cycle(&c, input, false)
+        );
+        assert_eq!(
+            plan_cycle(&c, input, false).ref_center,
+            skew_center(&c, 100.0, 0.02)
+        );
+    }
+
+    #[test]
+    fn fair_value_moves_the_quote_anchor_but_not_the_band() {
+        let c = cfg();
+        let input = |fair_value| CycleInput {
+            cycle: 1,
+            market: MarketSnapshot {
+                mark: 100.0,
+                best_bid: Some(99.8),
+                best_ask: Some(100.2),
+            },
+            position: 0.0,
+            resting: &[],
+            pending_slots: &[],
+            market_data_mode: MarketDataMode::Active,
+            active_exit_enabled: false,
+            inventory_exit_pct: 0.0,
+            inventory_exit_qty: 0.0,
+            size_skew: Default::default(),
+            nonlinear_skew: Default::default(),
+            guard: Default::default(),
+            wind_down: false,
+            paused: false,
+            qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value,
+        };
+        let places = |plan: &CyclePlan| {
+            plan.actions
+                .iter()
+                .filter_map(|action| match action {
+                    Action::Place(quote) => Some((quote.side, quote.price)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let at_mark = plan_cycle(&c, input(None), false);
+        assert_eq!(at_mark.ref_center, 100.0);
+        assert_eq!(
+            places(&at_mark),
+            vec![(OrderSide::Buy, 99.9), (OrderSide::Sell, 100.1)]
+        );
+
+        let leaning = plan_cycle(&c, input(Some(100.05)), false);
+        assert_eq!(leaning.ref_center, 100.05);
+        assert_eq!(
+            places(&leaning),
+            vec![(OrderSide::Buy, 99.94), (OrderSide::Sell, 100.16)]
+        );
+
+        // An anchor past the band still only quotes inside `mark ± band`.
+        let far = plan_cycle(&c, input(Some(100.5)), false);
+        for (_, price) in places(&far) {
+            assert!((99.8 - 1e-9..=100.2 + 1e-9).contains(&price));
+        }
+    }
+
     #[test]
     fn paused_market_data_cancels_without_placing_or_exiting() {
         let c = cfg();
@@ -2207,7 +2536,10 @@ mod tests {
                 nonlinear_skew: Default::default(),
                 guard: Default::default(),
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2269,7 +2601,10 @@ mod tests {
                         nonlinear_skew: Default::default(),
                         guard: Default::default(),
                         wind_down: false,
+                        paused: false,
                         qty_tolerance: 0.0005,
+                        vol_bps: 0.0,
+                        fair_value: None,
                     };
                     let default_plan = plan_cycle(&c, input, false);
                     let inactive_plan = plan_cycle(
@@ -2335,7 +2670,10 @@ mod tests {
                     nonlinear_skew: Default::default(),
                     guard: Default::default(),
                     wind_down: false,
+                    paused: false,
                     qty_tolerance: 0.0005,
+                    vol_bps: 0.0,
+                    fair_value: None,
                 };
                 let default_plan = plan_cycle(&c, input, false);
                 let candidate_plan = plan_cycle(
@@ -2431,7 +2769,10 @@ mod tests {
                     divergence_bps: Some(9.0),
                 },
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2500,7 +2841,10 @@ mod tests {
                     divergence_bps: Some(-8.0),
                 },
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2549,7 +2893,10 @@ mod tests {
                     divergence_bps: Some(1.0),
                 },
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2660,6 +3007,32 @@ mod tests {
         assert!(a[0].firing);
     }
 
+    // 34a. Retuning keeps a held breach latched under the new threshold and
+    // drops alerts the new config disables.
+    #[test]
+    fn alerts_retune_keeps_firing_state() {
+        let mut m = AlertMonitor::new(1.0, 80.0, 0.0);
+        let mut s = MakerStats::default();
+        s.record_fill(OrderSide::Buy, 100.0, 1.0, 100.0);
+        assert_eq!(m.evaluate(&s, 1.0, 98.0, 1.25, 5).len(), 2);
+
+        m.retune(AlertMonitor::new(1.5, 0.0, 0.0));
+        // Still breached at the new limit: no repeat fire.
+        assert!(m.evaluate(&s, 1.0, 98.0, 1.25, 6).is_empty());
+        // Clears at the new limit's hysteresis (pnl -0.5 > -0.75).
+        let a = m.evaluate(&s, 1.0, 99.5, 1.25, 7);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].kind, "loss");
+        assert!(!a[0].firing);
+
+        // Re-enabling inventory starts from a clean latch and fires afresh.
+        m.retune(AlertMonitor::new(1.5, 80.0, 0.0));
+        let a = m.evaluate(&s, 1.0, 99.5, 1.25, 8);
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].kind, "inventory");
+        assert!(a[0].firing);
+    }
+
     // 35. Wind-down: any residual position yields a full reduce-only exit
     // plan and no new quotes, even with configured exits fully disabled
     // (the frozen live configs). A vol halt suppresses the taker exit but
@@ -2689,7 +3062,10 @@ mod tests {
             nonlinear_skew: Default::default(),
             guard: Default::default(),
             wind_down: true,
+            paused: false,
             qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
         };
         let plan = plan_cycle(&c, input, false);
         assert_eq!(
@@ -2742,7 +3118,10 @@ mod tests {
                 nonlinear_skew: Default::default(),
                 guard: Default::default(),
                 wind_down: true,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
@@ -2775,7 +3154,10 @@ mod tests {
             nonlinear_skew: Default::default(),
             guard: Default::default(),
             wind_down: true,
+            paused: false,
             qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
         };
         // 0.02 is below the configured 80%-of-max trigger (0.04): the
         // configured path stays inactive, wind-down exits everything.
@@ -2797,4 +3179,62 @@ mod tests {
             })
         );
     }
+
+    // 38. Operator pause pulls every resting quote and places none, yet the
+    // configured inventory exit still fires; unpaused, the same input quotes.
+    #[test]
+    fn pause_cancels_quotes_but_keeps_inventory_exits() {
+        let c = cfg();
+        let resting = vec![
+            resting(OrderSide::Buy, 0, 99.9, 100.0),
+            resting(OrderSide::Sell, 0, 100.1, 100.0),
+        ];
+        let mk = |position: f64, paused: bool| CycleInput {
+            cycle: 10,
+            market: MarketSnapshot {
+                mark: 100.0,
+                best_bid: Some(99.9),
+                best_ask: Some(100.1),
+            },
+            position,
+            resting: &resting,
+            pending_slots: &[],
+            market_data_mode: MarketDataMode::Active,
+            active_exit_enabled: true,
+            inventory_exit_pct: 80.0,
+            inventory_exit_qty: 0.01,
+            size_skew: Default::default(),
+            nonlinear_skew: Default::default(),
+            guard: Default::default(),
+            wind_down: false,
+            paused,
+            qty_tolerance: 0.0005,
+            vol_bps: 0.0,
+            fair_value: None,
+        };
+        let paused = plan_cycle(&c, mk(0.0, true), false);
+        assert_eq!(
+            paused
+                .actions
+                .iter()
+                .filter(|action| matches!(action, Action::Cancel { .. }))
+                .count(),
+            2
+        );
+        assert!(!paused
+            .actions
+            .iter()
+            .any(|action| matches!(action, Action::Place(_))));
+        assert_eq!(
+            plan_cycle(&c, mk(0.04, true), false).inventory_exit,
+            Some(InventoryExit {
+                side: OrderSide::Sell,
+                qty: 0.01,
+            })
+        );
+        assert!(!plan_cycle(&c, mk(0.0, false), false)
+            .actions
+            .iter()
+            .any(|action| matches!(action, Action::Cancel { .. })));
+    }
 }
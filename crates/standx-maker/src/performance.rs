@@ -5,6 +5,7 @@
 //! I/O, so the same trace can be replayed byte-for-byte in tests and offline
 //! tools. Existing strategy decisions do not depend on these observations.
 
+use crate::ReplayEvent;
 use standx_sdk::models::OrderSide;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
@@ -152,13 +153,14 @@ impl fmt::Display for PerformanceError {
 
 impl std::error::Error for PerformanceError {}
 
+/// Running quantity-weighted totals for one post-fill horizon.
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
-struct MarkoutAccumulator {
-    qty: f64,
-    quote_pnl: f64,
-    bps_qty_sum: f64,
-    samples: u64,
-    unavailable: u64,
+pub struct MarkoutAccumulator {
+    pub qty: f64,
+    pub quote_pnl: f64,
+    pub bps_qty_sum: f64,
+    pub samples: u64,
+    pub unavailable: u64,
 }
 
 /// Quantity-weighted result for one post-fill horizon.
@@ -320,6 +322,41 @@ pub struct PerformanceSummary {
     pub inventory_time: InventoryTimeSummary,
 }
 
+/// Durable performance state carried across a supervised restart.
+///
+/// [`PerformanceLedger::snapshot`] closes the open quote interval at the
+/// snapshot time, censors unresolved markouts, and folds inventory exposure
+/// into a running total: the market is not observed while the process is
+/// down, so none of them may span the gap.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerformanceSnapshot {
+    pub starting_position: f64,
+    pub starting_mark: f64,
+    pub position: f64,
+    pub fill_cash: f64,
+    pub passive_fills: u64,
+    pub passive_qty: f64,
+    pub passive_cashflow_quote: f64,
+    pub passive_capture_bps_qty_sum: f64,
+    pub exit_fills: u64,
+    pub exit_qty: f64,
+    pub exit_cashflow_quote: f64,
+    pub gross_spread_quote: f64,
+    pub fee_quote: f64,
+    pub rebate_quote: f64,
+    pub funding_quote: f64,
+    pub funding_observed: bool,
+    pub exit_cost_quote: f64,
+    /// Sorted ascending.
+    pub seen_trade_ids: Vec<u64>,
+    /// Sorted ascending by trade ID.
+    pub execution_costs: Vec<(u64, ExecutionCosts)>,
+    pub markouts: [MarkoutAccumulator; 3],
+    pub last_funding_time_ms: Option<i64>,
+    pub quote_time: QuoteTimeSummary,
+    pub inventory_time: InventoryTimeSummary,
+}
+
 /// Current-run performance state. Trade IDs are deduplicated defensively even
 /// though the authoritative maker ledger should only forward accepted fills.
 #[derive(Clone, Debug)]
@@ -345,12 +382,20 @@ pub struct PerformanceLedger {
     costs_by_trade_id: HashMap<u64, ExecutionCosts>,
     markets: Vec<MarketObservation>,
     inventory_events: Vec<InventoryEvent>,
+    /// Position at `observation_start_ms`: the starting position, or the
+    /// restored position after a resume.
+    inventory_base_position: f64,
+    /// Inventory exposure accrued before a resume; zero for a fresh session.
+    carried_inventory_time: InventoryTimeSummary,
     observation_start_ms: Option<i64>,
     observation_end_ms: Option<i64>,
     pending_markouts: Vec<PendingMarkout>,
     markouts: [MarkoutAccumulator; 3],
     last_funding_time_ms: Option<i64>,
     quote_time: QuoteTimeTracker,
+    /// Accepted fills and funding not yet taken by [`Self::take_journal`];
+    /// `None` until [`Self::enable_journal`].
+    journal: Option<Vec<ReplayEvent>>,
 }
 
 impl PerformanceLedger {
@@ -383,15 +428,119 @@ impl PerformanceLedger {
             costs_by_trade_id: HashMap::new(),
             markets: Vec::new(),
             inventory_events: Vec::new(),
+            inventory_base_position: starting_position,
+            carried_inventory_time: InventoryTimeSummary::default(),
             observation_start_ms: None,
             observation_end_ms: None,
             pending_markouts: Vec::new(),
             markouts: [MarkoutAccumulator::default(); 3],
             last_funding_time_ms: None,
             quote_time: QuoteTimeTracker::default(),
+            journal: None,
+        })
+    }
+
+    /// Capture durable state as of `saved_at_ms` without disturbing the live
+    /// ledger. The journal is not carried; it belongs to the replay trace.
+    pub fn snapshot(&self, saved_at_ms: i64) -> Result<PerformanceSnapshot, PerformanceError> {
+        let mut quote_time = self.quote_time;
+        quote_time.finish(saved_at_ms)?;
+        let mut markouts = self.markouts;
+        for pending in &self.pending_markouts {
+            markouts[pending.window_index].unavailable += 1;
+        }
+        let mut seen_trade_ids: Vec<u64> = self.seen_trade_ids.iter().copied().collect();
+        seen_trade_ids.sort_unstable();
+        let mut execution_costs: Vec<(u64, ExecutionCosts)> = self
+            .costs_by_trade_id
+            .iter()
+            .map(|(trade_id, costs)| (*trade_id, *costs))
+            .collect();
+        execution_costs.sort_unstable_by_key(|(trade_id, _)| *trade_id);
+        Ok(PerformanceSnapshot {
+            starting_position: self.starting_position,
+            starting_mark: self.starting_mark,
+            position: self.position,
+            fill_cash: self.fill_cash,
+            passive_fills: self.passive_fills,
+            passive_qty: self.passive_qty,
+            passive_cashflow_quote: self.passive_cashflow_quote,
+            passive_capture_bps_qty_sum: self.passive_capture_bps_qty_sum,
+            exit_fills: self.exit_fills,
+            exit_qty: self.exit_qty,
+            exit_cashflow_quote: self.exit_cashflow_quote,
+            gross_spread_quote: self.gross_spread_quote,
+            fee_quote: self.fee_quote,
+            rebate_quote: self.rebate_quote,
+            funding_quote: self.funding_quote,
+            funding_observed: self.funding_observed,
+            exit_cost_quote: self.exit_cost_quote,
+            seen_trade_ids,
+            execution_costs,
+            markouts,
+            last_funding_time_ms: self.last_funding_time_ms,
+            quote_time: quote_time.summary,
+            inventory_time: self.inventory_time_summary(),
         })
     }
 
+    /// Rebuild a ledger from [`Self::snapshot`]. Market and quote observation
+    /// restart with the next sample, so downtime is never counted as observed.
+    pub fn restore(snapshot: PerformanceSnapshot) -> Result<Self, PerformanceError> {
+        let mut ledger = Self::new(snapshot.starting_position, snapshot.starting_mark)?;
+        if !snapshot.position.is_finite() {
+            return Err(PerformanceError::InvalidStartingPoint {
+                position: snapshot.position,
+                mark: snapshot.starting_mark,
+            });
+        }
+        ledger.seen_trade_ids = snapshot.seen_trade_ids.into_iter().collect();
+        for (trade_id, costs) in snapshot.execution_costs {
+            if !ledger.seen_trade_ids.contains(&trade_id) {
+                return Err(PerformanceError::UnknownExecutionCosts { trade_id });
+            }
+            if !valid_costs(costs) {
+                return Err(PerformanceError::InvalidFill { trade_id });
+            }
+            ledger.costs_by_trade_id.insert(trade_id, costs);
+        }
+        ledger.position = snapshot.position;
+        ledger.fill_cash = snapshot.fill_cash;
+        ledger.passive_fills = snapshot.passive_fills;
+        ledger.passive_qty = snapshot.passive_qty;
+        ledger.passive_cashflow_quote = snapshot.passive_cashflow_quote;
+        ledger.passive_capture_bps_qty_sum = snapshot.passive_capture_bps_qty_sum;
+        ledger.exit_fills = snapshot.exit_fills;
+        ledger.exit_qty = snapshot.exit_qty;
+        ledger.exit_cashflow_quote = snapshot.exit_cashflow_quote;
+        ledger.gross_spread_quote = snapshot.gross_spread_quote;
+        ledger.fee_quote = snapshot.fee_quote;
+        ledger.rebate_quote = snapshot.rebate_quote;
+        ledger.funding_quote = snapshot.funding_quote;
+        ledger.funding_observed = snapshot.funding_observed;
+        ledger.exit_cost_quote = snapshot.exit_cost_quote;
+        ledger.inventory_base_position = snapshot.position;
+        ledger.carried_inventory_time = snapshot.inventory_time;
+        ledger.markouts = snapshot.markouts;
+        ledger.last_funding_time_ms = snapshot.last_funding_time_ms;
+        ledger.quote_time.summary = snapshot.quote_time;
+        Ok(ledger)
+    }
+
+    /// Keep every accepted fill and funding cashflow, in arrival order, until
+    /// [`Self::take_journal`], so a caller can write them to a replay trace.
+    pub fn enable_journal(&mut self) {
+        self.journal.get_or_insert_with(Vec::new);
+    }
+
+    /// Drain the fills and funding accepted since the last call.
+    pub fn take_journal(&mut self) -> Vec<ReplayEvent> {
+        self.journal
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Returns false for a duplicate stable trade ID.
     pub fn record_fill(&mut self, fill: PerformanceFill) -> Result<bool, PerformanceError> {
         if fill.trade_id == 0
@@ -424,6 +573,9 @@ impl PerformanceLedger {
         if let Some(costs) = fill.costs {
             self.apply_execution_costs(fill.trade_id, costs);
         }
+        if let Some(journal) = &mut self.journal {
+            journal.push(ReplayEvent::Fill(fill));
+        }
         match fill.role {
             FillRole::PassiveMaker => {
                 self.passive_fills += 1;
@@ -489,6 +641,15 @@ impl PerformanceLedger {
             return Err(PerformanceError::ConflictingExecutionCosts { trade_id });
         }
         self.apply_execution_costs(trade_id, costs);
+        // Costs that arrive before the journal is drained travel with the fill.
+        if let Some(ReplayEvent::Fill(fill)) = self
+            .journal
+            .iter_mut()
+            .flatten()
+            .find(|event| matches!(event, ReplayEvent::Fill(fill) if fill.trade_id == trade_id))
+        {
+            fill.costs = Some(costs);
+        }
         Ok(true)
     }
 
@@ -562,6 +723,12 @@ impl PerformanceLedger {
         self.last_funding_time_ms = Some(event_time_ms);
         self.funding_observed = true;
         self.funding_quote += cashflow_quote;
+        if let Some(journal) = &mut self.journal {
+            journal.push(ReplayEvent::Funding {
+                event_time_ms,
+                cashflow_quote,
+            });
+        }
         Ok(())
     }
 
@@ -659,25 +826,25 @@ impl PerformanceLedger {
     }
 
     fn inventory_time_summary(&self) -> InventoryTimeSummary {
-        let (Some(start_ms), Some(end_ms)) = (self.observation_start_ms, self.observation_end_ms)
-        else {
-            return InventoryTimeSummary::default();
-        };
-        let mut events = self.inventory_events.clone();
-        events.sort_by_key(|event| (event.event_time_ms, event.trade_id));
-        let mut position = self.starting_position;
-        let mut cursor_ms = start_ms;
         let mut summary = InventoryTimeSummary {
-            observed_ms: end_ms.saturating_sub(start_ms),
-            ..InventoryTimeSummary::default()
+            avg_abs_qty: 0.0,
+            ..self.carried_inventory_time
         };
-        for event in events {
-            let event_ms = event.event_time_ms.clamp(start_ms, end_ms);
-            accrue_inventory_time(&mut summary, position, event_ms.saturating_sub(cursor_ms));
-            position += event.delta;
-            cursor_ms = cursor_ms.max(event_ms);
+        if let (Some(start_ms), Some(end_ms)) = (self.observation_start_ms, self.observation_end_ms)
+        {
+            let mut events = self.inventory_events.clone();
+            events.sort_by_key(|event| (event.event_time_ms, event.trade_id));
+            let mut position = self.inventory_base_position;
+            let mut cursor_ms = start_ms;
+            summary.observed_ms += end_ms.saturating_sub(start_ms);
+            for event in events {
+                let event_ms = event.event_time_ms.clamp(start_ms, end_ms);
+                accrue_inventory_time(&mut summary, position, event_ms.saturating_sub(cursor_ms));
+                position += event.delta;
+                cursor_ms = cursor_ms.max(event_ms);
+            }
+            accrue_inventory_time(&mut summary, position, end_ms.saturating_sub(cursor_ms));
         }
-        accrue_inventory_time(&mut summary, position, end_ms.saturating_sub(cursor_ms));
         summary.avg_abs_qty = if summary.observed_ms > 0 {
             summary.abs_qty_ms / summary.observed_ms as f64
         } else {
@@ -739,6 +906,38 @@ fn accrue_inventory_time(summary: &mut InventoryTimeSummary, position: f64, dura
 mod tests {
     use super::*;
 
+    #[test]
+    fn journal_keeps_accepted_fills_and_funding_in_arrival_order() {
+        let mut ledger = PerformanceLedger::new(0.0, 100.0).unwrap();
+        let mut first = fill(1, FillRole::PassiveMaker, OrderSide::Buy, 99.0, 1.0, 0);
+        first.costs = None;
+        ledger.record_fill(first).unwrap();
+        assert!(ledger.take_journal().is_empty());
+
+        ledger.enable_journal();
+        let second = fill(2, FillRole::PassiveMaker, OrderSide::Sell, 101.0, 1.0, 10);
+        let mut late = second;
+        late.costs = None;
+        ledger.record_fill(late).unwrap();
+        ledger.record_fill(late).unwrap();
+        ledger.record_funding(20, -0.5).unwrap();
+        ledger
+            .record_execution_costs(2, ExecutionCosts::default())
+            .unwrap();
+
+        assert_eq!(
+            ledger.take_journal(),
+            vec![
+                ReplayEvent::Fill(second),
+                ReplayEvent::Funding {
+                    event_time_ms: 20,
+                    cashflow_quote: -0.5,
+                },
+            ]
+        );
+        assert!(ledger.take_journal().is_empty());
+    }
+
     fn fill(
         trade_id: u64,
         role: FillRole,
@@ -988,6 +1187,78 @@ mod tests {
         assert!((summary.eligible_total_qty_ms - 9_000.0).abs() < 1e-12);
     }
 
+    #[test]
+    fn snapshot_restore_keeps_attribution_and_excludes_downtime() {
+        let mut ledger = PerformanceLedger::new(0.0, 100.0).unwrap();
+        ledger.observe_market(0, 100.0).unwrap();
+        ledger
+            .observe_quote_quality(QuoteQualityInterval {
+                event_time_ms: 0,
+                eligible_bid_qty: 1.0,
+                eligible_ask_qty: 1.0,
+            })
+            .unwrap();
+        ledger
+            .record_fill(fill(
+                1,
+                FillRole::PassiveMaker,
+                OrderSide::Buy,
+                99.0,
+                1.0,
+                0,
+            ))
+            .unwrap();
+        ledger.observe_market(2_000, 100.5).unwrap();
+        let before = ledger.summary(100.5).unwrap();
+
+        let snapshot = ledger.snapshot(2_000).unwrap();
+        assert_eq!(snapshot.seen_trade_ids, vec![1]);
+        // The 5s and 30s horizons were still open: censored, not carried.
+        assert_eq!(snapshot.markouts[1].unavailable, 1);
+        assert_eq!(snapshot.markouts[2].unavailable, 1);
+        assert_eq!(snapshot.quote_time.observed_ms, 2_000);
+
+        let mut resumed = PerformanceLedger::restore(snapshot).unwrap();
+        assert!(!resumed
+            .record_fill(fill(
+                1,
+                FillRole::PassiveMaker,
+                OrderSide::Buy,
+                99.0,
+                1.0,
+                0,
+            ))
+            .unwrap());
+        // Ten seconds of downtime before the next observation.
+        resumed.observe_market(12_000, 100.5).unwrap();
+        resumed.observe_market(13_000, 100.5).unwrap();
+        resumed.finish(13_000).unwrap();
+        let after = resumed.summary(100.5).unwrap();
+
+        assert!((after.net_pnl_quote - before.net_pnl_quote).abs() < 1e-12);
+        assert_eq!(after.passive_fills, 1);
+        assert_eq!(after.markouts[0].samples, 1);
+        assert_eq!(after.markouts[1].pending, 0);
+        assert_eq!(after.quote_time.observed_ms, 2_000);
+        assert_eq!(after.inventory_time.observed_ms, 3_000);
+        assert_eq!(after.inventory_time.nonzero_ms, 3_000);
+    }
+
+    #[test]
+    fn restore_rejects_costs_for_unknown_trades() {
+        let mut snapshot = PerformanceLedger::new(0.0, 100.0)
+            .unwrap()
+            .snapshot(0)
+            .unwrap();
+        snapshot
+            .execution_costs
+            .push((7, ExecutionCosts::default()));
+        assert_eq!(
+            PerformanceLedger::restore(snapshot).unwrap_err(),
+            PerformanceError::UnknownExecutionCosts { trade_id: 7 }
+        );
+    }
+
     #[test]
     fn rejects_non_monotonic_typed_observations() {
         let mut ledger = PerformanceLedger::new(0.0, 100.0).unwrap();
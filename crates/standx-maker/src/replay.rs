@@ -6,10 +6,11 @@
 //! network.
 
 use crate::{
-    plan_cycle, preflight_cycle_at, AdaptiveSpreadConfig, CyclePlan, CyclePreflight, MakerConfig,
-    MarketSnapshot, PerformanceError, PerformanceFill, PerformanceLedger, PerformanceSummary,
-    QuoteQualityInterval, RestingQuote, SizeSkewConfig, SizeSkewController, SizeSkewDecision,
-    SpreadController, SpreadDecision, VolBreaker, VolatilityError,
+    plan_cycle_with, preflight_cycle_at, AdaptiveSpreadConfig, CyclePlan, CyclePreflight,
+    MakerConfig, MarketSnapshot, PerformanceError, PerformanceFill, PerformanceLedger,
+    PerformanceSummary, QuoteQualityInterval, RestingQuote, SizeSkewConfig, SizeSkewController,
+    SizeSkewDecision, SpreadController, SpreadDecision, StrategyConfig, VolBreaker,
+    VolatilityError,
 };
 use standx_sdk::models::OrderSide;
 use std::fmt;
@@ -28,6 +29,7 @@ pub struct ReplaySettings {
     pub active_exit_enabled: bool,
     pub inventory_exit_pct: f64,
     pub inventory_exit_qty: f64,
+    pub strategy: StrategyConfig,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -40,6 +42,9 @@ pub struct ReplayCycle {
     pub pending_slots: Vec<(OrderSide, u32)>,
     pub eligible_bid_qty: f64,
     pub eligible_ask_qty: f64,
+    /// The quote anchor the session estimated, when fair value was enabled.
+    /// Replay reuses it: the trace does not carry book depth to re-derive it.
+    pub fair_value: Option<f64>,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -75,6 +80,7 @@ pub enum ReplayError {
     Volatility(VolatilityError),
     AdaptiveSpread(String),
     SizeSkew(String),
+    Strategy(String),
     MissingFinalMark,
 }
 
@@ -88,6 +94,7 @@ impl fmt::Display for ReplayError {
                 write!(formatter, "invalid replay adaptive spread: {error}")
             }
             Self::SizeSkew(error) => write!(formatter, "invalid replay size skew: {error}"),
+            Self::Strategy(error) => write!(formatter, "invalid replay strategy: {error}"),
             Self::MissingFinalMark => formatter.write_str("replay has no final market mark"),
         }
     }
@@ -134,6 +141,7 @@ pub fn run_replay(
         .map_err(|error| ReplayError::AdaptiveSpread(error.to_string()))?;
     let mut size_skew_controller = SizeSkewController::new(settings.size_skew, cfg)
         .map_err(|error| ReplayError::SizeSkew(error.to_string()))?;
+    let mut strategy = settings.strategy.build();
     let mut outcomes = Vec::new();
     let mut final_mark = None;
 
@@ -159,8 +167,9 @@ pub fn run_replay(
                 let size_skew_decision =
                     size_skew_controller.observe(cycle.position, &effective_cfg);
                 let plan = preflight.skip.is_none().then(|| {
-                    plan_cycle(
+                    plan_cycle_with(
                         &effective_cfg,
+                        strategy.as_ref(),
                         crate::CycleInput {
                             cycle: cycle.cycle,
                             market: cycle.market,
@@ -175,7 +184,10 @@ pub fn run_replay(
                             nonlinear_skew: Default::default(),
                             guard: Default::default(),
                             wind_down: false,
+                            paused: false,
                             qty_tolerance: 0.0005,
+                            vol_bps: breaker.vol_bps(),
+                            fair_value: cycle.fair_value,
                         },
                         preflight.halted,
                     )
@@ -191,6 +203,7 @@ pub fn run_replay(
             }
             ReplayEvent::Fill(fill) => {
                 performance.record_fill(*fill)?;
+                strategy.record_fill(fill.role, fill.side, fill.price, fill.mark_at_fill);
             }
             ReplayEvent::Funding {
                 event_time_ms,
@@ -207,6 +220,10 @@ pub fn run_replay(
 }
 
 fn validate_settings(settings: &ReplaySettings) -> Result<(), ReplayError> {
+    settings
+        .strategy
+        .validate()
+        .map_err(|error| ReplayError::Strategy(error.to_string()))?;
     if !settings.max_divergence_bps.is_finite() || settings.max_divergence_bps < 0.0 {
         return Err(ReplayError::InvalidSettings(
             "max_divergence_bps must be finite and non-negative",
@@ -266,6 +283,7 @@ mod tests {
             active_exit_enabled: false,
             inventory_exit_pct: 0.0,
             inventory_exit_qty: 0.0,
+            strategy: StrategyConfig::default(),
         }
     }
 
@@ -292,6 +310,7 @@ mod tests {
             pending_slots: Vec::new(),
             eligible_bid_qty: 1.0,
             eligible_ask_qty: 1.0,
+            fair_value: None,
         })
     }
 
@@ -369,6 +388,7 @@ mod tests {
             pending_slots: Vec::new(),
             eligible_bid_qty: 1.0,
             eligible_ask_qty: 0.0,
+            fair_value: None,
         })];
         let result = run_replay(&config(), settings(), &events, 1_000).unwrap();
         assert!(result.cycles[0].preflight.skip.is_some());
@@ -28,6 +28,14 @@ pub struct PositionAlertAnchor {
     neutral_deadband: f64,
 }
 
+/// The observation and notification anchors carried across a restart, so a
+/// resumed session does not re-alert on inventory it already reported.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PositionAlertSnapshot {
+    pub last_observed_position: f64,
+    pub notification_anchor: f64,
+}
+
 impl PositionAlertAnchor {
     pub fn new(position: f64, change_pct: f64, neutral_deadband: f64) -> Self {
         Self {
@@ -45,6 +53,30 @@ impl PositionAlertAnchor {
         }
     }
 
+    /// Continue alerting from a saved anchor. Thresholds come from the
+    /// current configuration, not the snapshot.
+    pub fn restore(
+        snapshot: PositionAlertSnapshot,
+        change_pct: f64,
+        neutral_deadband: f64,
+    ) -> Self {
+        Self {
+            notification_anchor: snapshot.notification_anchor,
+            ..Self::new(
+                snapshot.last_observed_position,
+                change_pct,
+                neutral_deadband,
+            )
+        }
+    }
+
+    pub fn snapshot(&self) -> PositionAlertSnapshot {
+        PositionAlertSnapshot {
+            last_observed_position: self.last_observed_position,
+            notification_anchor: self.notification_anchor,
+        }
+    }
+
     /// Evaluate a position update without sending a notification.
     pub fn evaluate(
         &mut self,
@@ -134,6 +166,16 @@ mod tests {
         assert!(boundary.evaluate(0.126, 0.8, 0.0, 0.0005).is_none());
     }
 
+    #[test]
+    fn restored_anchor_keeps_the_notification_baseline() {
+        let mut anchor = PositionAlertAnchor::new(0.0, 20.0, 0.1);
+        assert!(anchor.evaluate(0.10, 0.8, 0.0, 0.0005).is_none());
+        let mut resumed = PositionAlertAnchor::restore(anchor.snapshot(), 20.0, 0.1);
+        let event = resumed.evaluate(0.161, 0.8, 0.0, 0.0005).unwrap();
+        assert_eq!(event.kind, PositionRiskKind::Jump);
+        assert!(event.before.abs() < 1e-12);
+    }
+
     #[test]
     fn threshold_crossings_use_the_last_observation_not_the_notification_anchor() {
         let mut exit = PositionAlertAnchor::new(0.3, 0.0, 0.1);
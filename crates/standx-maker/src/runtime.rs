@@ -2,6 +2,8 @@
 
 use std::collections::VecDeque;
 
+use crate::MakerConfig;
+
 pub const MAX_CONSECUTIVE_CYCLE_ERRORS: u32 = 3;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -34,6 +36,22 @@ pub enum RecoveryTarget {
 }
 
 impl RecoveryTarget {
+    pub const ALL: [Self; 4] = [
+        Self::AccountStream,
+        Self::OrderResponse,
+        Self::PositionReconciliation,
+        Self::MarketData,
+    ];
+
+    fn index(self) -> usize {
+        match self {
+            Self::AccountStream => 0,
+            Self::OrderResponse => 1,
+            Self::PositionReconciliation => 2,
+            Self::MarketData => 3,
+        }
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             Self::AccountStream => "account_stream",
@@ -44,6 +62,23 @@ impl RecoveryTarget {
     }
 }
 
+/// Freezes entered and recoveries completed per target since startup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RecoveryCounts {
+    freezes: [u64; 4],
+    recoveries: [u64; 4],
+}
+
+impl RecoveryCounts {
+    pub fn freezes(&self, target: RecoveryTarget) -> u64 {
+        self.freezes[target.index()]
+    }
+
+    pub fn recoveries(&self, target: RecoveryTarget) -> u64 {
+        self.recoveries[target.index()]
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RequestTimeoutPhase {
     Acknowledgement,
@@ -81,12 +116,16 @@ pub enum RuntimeStopReason {
         target: RecoveryTarget,
         reason: String,
     },
+    /// `stop` from the runtime control socket: a graceful, operator-driven
+    /// shutdown rather than a fail-safe.
+    Operator,
 }
 
 impl RuntimeStopReason {
     fn detail(&self) -> String {
         match self {
             Self::CtrlC => "Ctrl+C".to_string(),
+            Self::Operator => "operator stop".to_string(),
             Self::OrderResponse(reason)
             | Self::PositionReconciliation(reason)
             | Self::MarketData(reason)
@@ -98,7 +137,66 @@ impl RuntimeStopReason {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// An operator command from the runtime control socket. Arguments arrive
+/// already validated against the run config by the CLI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ControlCommand {
+    /// Stop quoting (resting quotes are cancelled); exits still run.
+    Pause,
+    /// Lift a pause. A latched flatten stays latched.
+    Resume,
+    /// Extra half-spread in bps on top of the (possibly adaptive) spread;
+    /// 0 removes it.
+    Widen(f64),
+    /// Per-level quote size override; `None` restores the configured size.
+    Size(Option<f64>),
+    /// Latch wind-down: no new quotes, flatten via reduce-only exits.
+    Flatten,
+    Stop,
+}
+
+impl ControlCommand {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Pause => "pause",
+            Self::Resume => "resume",
+            Self::Widen(_) => "widen",
+            Self::Size(_) => "size",
+            Self::Flatten => "flatten",
+            Self::Stop => "stop",
+        }
+    }
+}
+
+/// Operator overrides layered over the run config for every cycle. They
+/// survive freezes and recoveries; only the operator changes them.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct OperatorControls {
+    pub paused: bool,
+    pub widen_bps: f64,
+    pub size: Option<f64>,
+    pub flatten: bool,
+}
+
+impl OperatorControls {
+    /// `cfg` with the size override applied; the base the cycle plans from.
+    pub fn sized(&self, cfg: &MakerConfig) -> MakerConfig {
+        let mut sized = cfg.clone();
+        if let Some(size) = self.size {
+            sized.size = size;
+        }
+        sized
+    }
+
+    /// Adds the operator widen after the adaptive spread tier is chosen, so
+    /// it holds across tier changes.
+    pub fn widened(&self, mut cfg: MakerConfig) -> MakerConfig {
+        cfg.spread_bps += self.widen_bps;
+        cfg
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub enum MakerEvent {
     StartupReady,
     Timer,
@@ -134,6 +232,7 @@ pub enum MakerEvent {
         message: String,
     },
     StopRequested(RuntimeStopReason),
+    Control(ControlCommand),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -160,6 +259,8 @@ pub struct MakerState {
     recovery_target: Option<RecoveryTarget>,
     replan_requested: bool,
     consecutive_cycle_errors: u32,
+    controls: OperatorControls,
+    recovery_counts: RecoveryCounts,
     effects: VecDeque<MakerEffect>,
 }
 
@@ -172,6 +273,8 @@ impl MakerState {
             recovery_target: None,
             replan_requested: false,
             consecutive_cycle_errors: 0,
+            controls: OperatorControls::default(),
+            recovery_counts: RecoveryCounts::default(),
             effects: VecDeque::new(),
         }
     }
@@ -186,8 +289,7 @@ impl MakerState {
         self.generation
     }
 
-    #[cfg(test)]
-    pub(crate) fn is_frozen(&self) -> bool {
+    pub fn is_frozen(&self) -> bool {
         matches!(self.phase, RuntimePhase::Frozen { .. })
     }
 
@@ -210,6 +312,20 @@ impl MakerState {
         self.consecutive_cycle_errors
     }
 
+    pub fn controls(&self) -> OperatorControls {
+        self.controls
+    }
+
+    pub fn recovery_counts(&self) -> RecoveryCounts {
+        self.recovery_counts
+    }
+
+    /// Whether the runtime is already shutting down; control commands are
+    /// ignored from then on.
+    pub fn is_stopping(&self) -> bool {
+        matches!(self.phase, RuntimePhase::Stopping { .. })
+    }
+
     fn transition(&mut self, event: MakerEvent) -> Vec<MakerEffect> {
         match event {
             MakerEvent::StartupReady => {
@@ -305,7 +421,9 @@ impl MakerState {
                     return Vec::new();
                 }
                 self.in_flight = None;
-                self.recovery_target = None;
+                if let Some(target) = self.recovery_target.take() {
+                    self.recovery_counts.recoveries[target.index()] += 1;
+                }
                 self.consecutive_cycle_errors = 0;
                 self.phase = RuntimePhase::Ready;
                 self.request_cycle()
@@ -335,6 +453,32 @@ impl MakerState {
                 RecoveryTarget::OrderResponse,
             ),
             MakerEvent::StopRequested(reason) => self.stop(reason),
+            MakerEvent::Control(command) => self.control(command),
+        }
+    }
+
+    fn control(&mut self, command: ControlCommand) -> Vec<MakerEffect> {
+        if matches!(self.phase, RuntimePhase::Stopping { .. }) {
+            return Vec::new();
+        }
+        match command {
+            ControlCommand::Stop => return self.stop(RuntimeStopReason::Operator),
+            ControlCommand::Pause => self.controls.paused = true,
+            ControlCommand::Resume => self.controls.paused = false,
+            ControlCommand::Widen(bps) => self.controls.widen_bps = bps,
+            ControlCommand::Size(size) => self.controls.size = size,
+            ControlCommand::Flatten => self.controls.flatten = true,
+        }
+        // Replan right away so the new posture reaches the book now rather
+        // than at the next timer; a frozen runtime picks it up on recovery.
+        if !matches!(self.phase, RuntimePhase::Ready) {
+            return Vec::new();
+        }
+        if self.in_flight.is_some() {
+            self.replan_requested = true;
+            Vec::new()
+        } else {
+            self.request_cycle()
         }
     }
 
@@ -367,6 +511,7 @@ impl MakerState {
         let mut effects = self.abort_effect();
         self.phase = RuntimePhase::Frozen { reason };
         self.recovery_target = Some(target);
+        self.recovery_counts.freezes[target.index()] += 1;
         let token = WorkToken {
             generation: self.generation,
             kind: WorkKind::Cleanup,
@@ -624,6 +769,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn control_commands_update_overrides_and_replan_immediately() {
+        let mut state = MakerState::starting();
+        state.handle(MakerEvent::StartupReady);
+        let token = next_cycle(&mut state);
+        // A command during an in-flight cycle coalesces into one replan.
+        state.handle(MakerEvent::Control(ControlCommand::Pause));
+        state.handle(MakerEvent::Control(ControlCommand::Widen(5.0)));
+        assert_eq!(state.next_effect(), None);
+        state.handle(MakerEvent::CycleCompleted(token));
+        assert_eq!(state.next_effect(), Some(MakerEffect::CommitCycle(token)));
+        let token = next_cycle(&mut state);
+        state.handle(MakerEvent::CycleCompleted(token));
+        state.next_effect();
+
+        // An idle runtime replans as soon as the command lands.
+        state.handle(MakerEvent::Control(ControlCommand::Size(Some(0.5))));
+        next_cycle(&mut state);
+        state.handle(MakerEvent::Control(ControlCommand::Flatten));
+        state.handle(MakerEvent::Control(ControlCommand::Resume));
+        assert_eq!(
+            state.controls(),
+            OperatorControls {
+                paused: false,
+                widen_bps: 5.0,
+                size: Some(0.5),
+                flatten: true,
+            }
+        );
+    }
+
+    #[test]
+    fn frozen_runtime_records_controls_without_cycling() {
+        let mut state = MakerState::starting();
+        state.handle(MakerEvent::StartupReady);
+        next_cycle(&mut state);
+        state.handle(MakerEvent::PositionMismatch);
+        while state.next_effect().is_some() {}
+        state.handle(MakerEvent::Control(ControlCommand::Pause));
+        assert_eq!(state.next_effect(), None);
+        assert!(state.controls().paused);
+    }
+
+    #[test]
+    fn control_stop_is_an_operator_stop_and_seals_the_runtime() {
+        let mut state = MakerState::starting();
+        state.handle(MakerEvent::StartupReady);
+        let token = next_cycle(&mut state);
+        state.handle(MakerEvent::Control(ControlCommand::Stop));
+        assert_eq!(state.next_effect(), Some(MakerEffect::AbortInFlight(token)));
+        assert_eq!(
+            state.next_effect(),
+            Some(MakerEffect::Stop(RuntimeStopReason::Operator))
+        );
+        assert!(state.is_stopping());
+        state.handle(MakerEvent::Control(ControlCommand::Pause));
+        assert_eq!(state.next_effect(), None);
+        assert!(!state.controls().paused);
+    }
+
+    #[test]
+    fn operator_controls_size_before_and_widen_after_the_spread_tier() {
+        let cfg = MakerConfig {
+            spread_bps: 8.0,
+            band_bps: 30.0,
+            level_step_bps: 2.0,
+            refresh_bps: 4.0,
+            levels: 1,
+            size: 0.01,
+            max_position: 0.2,
+            skew_bps: 0.0,
+            price_decimals: 2,
+            qty_decimals: 3,
+            min_order_qty: 0.001,
+        };
+        let controls = OperatorControls {
+            widen_bps: 3.0,
+            size: Some(0.05),
+            ..OperatorControls::default()
+        };
+        let sized = controls.sized(&cfg);
+        assert_eq!(sized.size, 0.05);
+        assert_eq!(sized.spread_bps, 8.0);
+        assert_eq!(controls.widened(sized).spread_bps, 11.0);
+        assert_eq!(OperatorControls::default().sized(&cfg).size, 0.01);
+    }
+
     #[test]
     fn unmatched_response_fails_closed_immediately() {
         let mut state = MakerState::starting();
@@ -727,6 +959,7 @@ mod tests {
                 target: RecoveryTarget::OrderResponse,
                 reason: "boom".to_string(),
             },
+            RuntimeStopReason::Operator,
         ]
     }
 
@@ -958,6 +1191,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn recovery_counts_tally_freezes_and_completed_recoveries_per_target() {
+        for (event, target) in freeze_cases() {
+            let (mut state, cleanup) = freeze_and_take_cleanup(event.clone(), target);
+            assert_eq!(state.recovery_counts().freezes(target), 1);
+            assert_eq!(state.recovery_counts().recoveries(target), 0);
+            state.handle(MakerEvent::CleanupCompleted(cleanup));
+            let recovery = match state.next_effect() {
+                Some(MakerEffect::Recover { token, .. }) => token,
+                effect => panic!("expected recovery, got {effect:?}"),
+            };
+            state.handle(MakerEvent::RecoverySucceeded(recovery));
+            // A stale repeat must not count twice.
+            state.handle(MakerEvent::RecoverySucceeded(recovery));
+            let counts = state.recovery_counts();
+            for other in RecoveryTarget::ALL {
+                let expected = u64::from(other == target);
+                assert_eq!(counts.freezes(other), expected, "{event:?} {other:?}");
+                assert_eq!(counts.recoveries(other), expected, "{event:?} {other:?}");
+            }
+        }
+    }
+
     #[test]
     fn recovery_success_resets_the_cycle_error_streak() {
         let mut state = MakerState::starting();
@@ -0,0 +1,219 @@
+//! Durable maker session state across supervised restarts.
+//!
+//! A [`SessionSnapshot`] bundles the accounting a restarted process needs to
+//! continue the same logical session: the fill ledger (with its performance
+//! attribution), session stats, and the position-alert anchors. The CLI owns
+//! the file format and the atomic write; this module only captures, restores,
+//! and decides whether the venue still matches what was saved.
+
+use crate::{
+    is_current_run_client_order_id, is_maker_client_order_id, LedgerSnapshot, MakerLedger,
+    MakerStats, MakerStatsSnapshot, PerformanceError, PositionAlertAnchor, PositionAlertSnapshot,
+};
+use std::fmt;
+
+/// Everything that must survive a restart for session PnL, markouts, and
+/// alert anchors to continue where they stopped.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SessionSnapshot {
+    pub ledger: LedgerSnapshot,
+    pub stats: MakerStatsSnapshot,
+    pub position_alert: PositionAlertSnapshot,
+}
+
+impl SessionSnapshot {
+    /// Capture the session as of `saved_at_ms` without disturbing it.
+    pub fn capture(
+        ledger: &MakerLedger,
+        stats: &MakerStats,
+        position_alert: &PositionAlertAnchor,
+        saved_at_ms: i64,
+    ) -> Result<Self, PerformanceError> {
+        Ok(Self {
+            ledger: ledger.snapshot(saved_at_ms)?,
+            stats: stats.snapshot(),
+            position_alert: position_alert.snapshot(),
+        })
+    }
+
+    /// Rebuild the session state. Alert thresholds come from the current
+    /// configuration so a restart may retune them.
+    pub fn restore(
+        self,
+        alert_position_change_pct: f64,
+        neutral_deadband: f64,
+    ) -> Result<(MakerLedger, MakerStats, PositionAlertAnchor), PerformanceError> {
+        Ok((
+            MakerLedger::restore(self.ledger)?,
+            MakerStats::restore(self.stats),
+            PositionAlertAnchor::restore(
+                self.position_alert,
+                alert_position_change_pct,
+                neutral_deadband,
+            ),
+        ))
+    }
+}
+
+/// Why the venue no longer matches a saved session.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ResumeError {
+    /// Fills landed while the process was down that the ledger never saw.
+    PositionMismatch { expected: f64, observed: f64 },
+    /// A maker order from another run is open, so another maker session may
+    /// be live on this account.
+    ForeignMakerOrder { client_order_id: String },
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PositionMismatch { expected, observed } => write!(
+                formatter,
+                "venue position {observed:+.8} does not match saved session position {expected:+.8}"
+            ),
+            Self::ForeignMakerOrder { client_order_id } => write!(
+                formatter,
+                "open maker order {client_order_id} does not belong to the saved session"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+/// Refuse to resume while another run's maker orders are open: every open
+/// maker order must carry the saved run prefix. Orders without the maker
+/// prefix are manual and ignored. Runs before startup cleanup, so a live
+/// foreign session is never cancelled by a resume.
+pub fn check_resume_orders<'a>(
+    run_order_prefix: &str,
+    open_client_order_ids: impl IntoIterator<Item = Option<&'a str>>,
+) -> Result<(), ResumeError> {
+    for client_order_id in open_client_order_ids {
+        if is_maker_client_order_id(client_order_id)
+            && !is_current_run_client_order_id(client_order_id, run_order_prefix)
+        {
+            return Err(ResumeError::ForeignMakerOrder {
+                client_order_id: client_order_id.unwrap_or_default().to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Require the venue position to equal the saved ledger position within
+/// `qty_tolerance`. Runs after startup cleanup, once no saved order can fill.
+pub fn check_resume_position(
+    snapshot: &SessionSnapshot,
+    venue_position: f64,
+    qty_tolerance: f64,
+) -> Result<(), ResumeError> {
+    let expected = snapshot.ledger.expected_position;
+    if !venue_position.is_finite() || (venue_position - expected).abs() > qty_tolerance {
+        return Err(ResumeError::PositionMismatch {
+            expected,
+            observed: venue_position,
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LedgerTrade, TradeSource};
+    use standx_sdk::models::OrderSide;
+
+    const PREFIX: &str = "sxmk-0123456789ab-";
+
+    fn session_with_fill() -> (MakerLedger, MakerStats, PositionAlertAnchor) {
+        let mut ledger = MakerLedger::new(0.0);
+        ledger.enable_performance(100.0).unwrap();
+        let mut stats = MakerStats::with_inventory_baseline(0.0, 100.0);
+        assert!(ledger.adopt_order(7, Some("sxmk-0123456789ab-q00000001b0"), PREFIX));
+        ledger
+            .record_trade(
+                LedgerTrade {
+                    trade_id: 1,
+                    order_id: 7,
+                    side: OrderSide::Buy,
+                    price: 99.9,
+                    qty: 0.2,
+                    mark: 100.0,
+                    trade_ts: "2026-07-14T00:00:00Z",
+                    event_time_ms: 1_000,
+                    costs: None,
+                    source: TradeSource::AccountStream,
+                },
+                &mut stats,
+            )
+            .unwrap();
+        stats.end_cycle(ledger.expected_position, true);
+        let anchor = PositionAlertAnchor::new(0.2, 20.0, 0.05);
+        (ledger, stats, anchor)
+    }
+
+    #[test]
+    fn restored_session_continues_pnl_and_dedupes_seen_trades() {
+        let (ledger, stats, anchor) = session_with_fill();
+        let snapshot = SessionSnapshot::capture(&ledger, &stats, &anchor, 2_000).unwrap();
+        let (mut resumed, mut resumed_stats, _) = snapshot.clone().restore(20.0, 0.05).unwrap();
+
+        assert!((resumed.expected_position - 0.2).abs() < 1e-12);
+        assert_eq!(resumed_stats.fills(), 1);
+        assert_eq!(resumed_stats.cycles, 1);
+        assert!((resumed_stats.pnl(0.2, 100.0) - stats.pnl(0.2, 100.0)).abs() < 1e-12);
+        // A REST backfill of the pre-restart trade must not count twice.
+        let replayed = resumed
+            .record_trade(
+                LedgerTrade {
+                    trade_id: 1,
+                    order_id: 7,
+                    side: OrderSide::Buy,
+                    price: 99.9,
+                    qty: 0.2,
+                    mark: 100.0,
+                    trade_ts: "2026-07-14T00:00:00Z",
+                    event_time_ms: 1_000,
+                    costs: None,
+                    source: TradeSource::RestBackfill,
+                },
+                &mut resumed_stats,
+            )
+            .unwrap();
+        assert!(replayed.is_none());
+        assert!((resumed.expected_position - 0.2).abs() < 1e-12);
+        assert_eq!(
+            SessionSnapshot::capture(&resumed, &resumed_stats, &anchor, 2_000)
+                .unwrap()
+                .ledger
+                .seen_trade_ids,
+            snapshot.ledger.seen_trade_ids
+        );
+    }
+
+    #[test]
+    fn resume_requires_matching_position_and_owned_maker_orders() {
+        let (ledger, stats, anchor) = session_with_fill();
+        let snapshot = SessionSnapshot::capture(&ledger, &stats, &anchor, 2_000).unwrap();
+        let manual = Some("manual-1");
+        let own = Some("sxmk-0123456789ab-q00000002s0");
+
+        assert_eq!(check_resume_orders(PREFIX, [manual, own, None]), Ok(()));
+        assert_eq!(
+            check_resume_orders(PREFIX, [manual, Some("sxmk-ffffffffffff-q00000001b0")]),
+            Err(ResumeError::ForeignMakerOrder {
+                client_order_id: "sxmk-ffffffffffff-q00000001b0".to_string(),
+            })
+        );
+        assert_eq!(check_resume_position(&snapshot, 0.2004, 0.0005), Ok(()));
+        assert_eq!(
+            check_resume_position(&snapshot, 0.3, 0.0005),
+            Err(ResumeError::PositionMismatch {
+                expected: 0.2,
+                observed: 0.3,
+            })
+        );
+    }
+}
@@ -0,0 +1,454 @@
+//! Pluggable quote models.
+//!
+//! A [`QuoteStrategy`] decides where the maker would like to quote; it never
+//! decides what is safe to send. [`crate::plan_cycle_with`] passes whatever a
+//! strategy proposes through the shared risk layer: the eligibility band and
+//! post-only no-cross clamp, directional tick rounding, the minimum order
+//! size, max-position and external-guard side suppression, size skew,
+//! [`crate::cap_desired_exposure`] and the anti-flicker reconcile. A new
+//! model therefore only has to produce prices and sizes.
+
+use crate::{skew_center_with, CycleInput, DesiredQuote, FillRole, MakerConfig};
+use standx_sdk::models::OrderSide;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
+/// A quoting model. Implementations must be deterministic in their inputs —
+/// the cycle input and the fills passed to [`Self::record_fill`] — so replay
+/// and backtest reproduce live decisions.
+pub trait QuoteStrategy: fmt::Debug + Send + Sync {
+    /// Stable identifier, as selected by `[strategy] kind` in maker TOML.
+    fn name(&self) -> &'static str;
+
+    /// The price the model quotes around this cycle. Resting quotes record it
+    /// at placement and are re-quoted once it drifts more than `refresh_bps`.
+    fn center(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> f64;
+
+    /// Quotes the model would like resting, per side inner level first.
+    /// Prices and quantities need not be tick-aligned or inside the band;
+    /// the shared risk layer clamps, rounds or drops them.
+    fn desired_quotes(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> Vec<DesiredQuote>;
+
+    /// Observe one of the session's own fills, in the order the caller's
+    /// ledger accepted it. Stateless models ignore it.
+    fn record_fill(&mut self, _role: FillRole, _side: OrderSide, _price: f64, _mark: f64) {}
+}
+
+/// Which [`QuoteStrategy`] a session runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum StrategyConfig {
+    /// [`LadderStrategy`], the original engine.
+    #[default]
+    Ladder,
+    /// [`AvellanedaStoikovStrategy`].
+    AvellanedaStoikov(AvellanedaStoikovConfig),
+}
+
+impl StrategyConfig {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ladder => LadderStrategy.name(),
+            Self::AvellanedaStoikov(_) => AVELLANEDA_STOIKOV,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        match self {
+            Self::Ladder => Ok(()),
+            Self::AvellanedaStoikov(config) => config.validate(),
+        }
+    }
+
+    pub fn build(&self) -> Box<dyn QuoteStrategy> {
+        match self {
+            Self::Ladder => Box::new(LadderStrategy),
+            Self::AvellanedaStoikov(config) => Box::new(AvellanedaStoikovStrategy::new(*config)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrategyError(String);
+
+impl StrategyError {
+    fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+impl fmt::Display for StrategyError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.0)
+    }
+}
+
+impl Error for StrategyError {}
+
+/// Mark-centered symmetric ladder: `levels` quotes per side, `spread_bps`
+/// from the inventory-skewed center for level 0 and `level_step_bps` further
+/// for each outer level. The center shifts away from mark by linear (or,
+/// when enabled, nonlinear) inventory skew.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LadderStrategy;
+
+impl QuoteStrategy for LadderStrategy {
+    fn name(&self) -> &'static str {
+        "ladder"
+    }
+
+    fn center(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> f64 {
+        skew_center_with(cfg, input.nonlinear_skew, input.anchor(), input.position)
+    }
+
+    fn desired_quotes(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> Vec<DesiredQuote> {
+        ladder(cfg, self.center(cfg, input), cfg.spread_bps)
+    }
+}
+
+const AVELLANEDA_STOIKOV: &str = "avellaneda_stoikov";
+
+/// Avellaneda–Stoikov parameters (`[strategy] kind = "avellaneda_stoikov"`).
+///
+/// Everything is in bps of mark, with inventory `q` counted in `size` lots
+/// and `σ` the volatility breaker's rolling range, so the horizon is the
+/// volatility window:
+///
+/// - reservation price `r = anchor × (1 − q·γ·σ² / 1e4)`, the anchor being mark
+///   or the cycle's fair value
+/// - half-spread `γ·σ²/2 + ln(1 + γ/κ)/γ`, never below `spread_bps`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AvellanedaStoikovConfig {
+    /// Risk aversion `γ`, per bps.
+    pub risk_aversion: f64,
+    /// Order-arrival decay `κ` per bps of quote depth, assumed until fills
+    /// arrive: the fill intensity at depth `δ` is taken as `A·e^(−κδ)`.
+    pub kappa: f64,
+    /// How many fills `kappa` is worth against the observed ones.
+    pub kappa_prior_fills: f64,
+    /// Most recent passive fills the calibrated `κ` is drawn from.
+    pub kappa_window: usize,
+}
+
+impl Default for AvellanedaStoikovConfig {
+    fn default() -> Self {
+        Self {
+            risk_aversion: 0.05,
+            kappa: 0.2,
+            kappa_prior_fills: 20.0,
+            kappa_window: 200,
+        }
+    }
+}
+
+impl AvellanedaStoikovConfig {
+    pub fn validate(&self) -> Result<(), StrategyError> {
+        if !self.risk_aversion.is_finite()
+            || !self.kappa.is_finite()
+            || !self.kappa_prior_fills.is_finite()
+        {
+            return Err(StrategyError::new(
+                "avellaneda_stoikov values must be finite",
+            ));
+        }
+        if self.risk_aversion <= 0.0 {
+            return Err(StrategyError::new(
+                "avellaneda_stoikov risk_aversion must be > 0",
+            ));
+        }
+        if self.kappa <= 0.0 {
+            return Err(StrategyError::new("avellaneda_stoikov kappa must be > 0"));
+        }
+        if self.kappa_prior_fills <= 0.0 {
+            return Err(StrategyError::new(
+                "avellaneda_stoikov kappa_prior_fills must be > 0",
+            ));
+        }
+        if self.kappa_window == 0 {
+            return Err(StrategyError::new(
+                "avellaneda_stoikov kappa_window must be >= 1",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Inventory-aware quoting after Avellaneda & Stoikov (2008): quotes sit
+/// around a reservation price that leans against the position, at the
+/// half-spread that maximizes expected utility under exponential fill
+/// intensity.
+///
+/// `κ` is calibrated from the session's own passive fills: each fill's depth
+/// from the mark is treated as an exponential draw with rate `κ`, and a gamma
+/// prior of `kappa_prior_fills` fills at `kappa` keeps the estimate stable
+/// until there is history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvellanedaStoikovStrategy {
+    config: AvellanedaStoikovConfig,
+    depths_bps: VecDeque<f64>,
+    depth_sum_bps: f64,
+}
+
+impl AvellanedaStoikovStrategy {
+    pub fn new(config: AvellanedaStoikovConfig) -> Self {
+        Self {
+            config,
+            depths_bps: VecDeque::new(),
+            depth_sum_bps: 0.0,
+        }
+    }
+
+    /// Posterior mean of `κ` over the fill window.
+    pub fn kappa(&self) -> f64 {
+        let prior = self.config.kappa_prior_fills;
+        (prior + self.depths_bps.len() as f64)
+            / (prior / self.config.kappa + self.depth_sum_bps.max(0.0))
+    }
+
+    /// How far below mark (negative: above) inventory pushes the
+    /// reservation price, in bps.
+    pub fn reservation_shift_bps(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> f64 {
+        if cfg.size <= 0.0 || !cfg.size.is_finite() || !input.position.is_finite() {
+            return 0.0;
+        }
+        let lots = input.position / cfg.size;
+        lots * self.config.risk_aversion * variance(input.vol_bps)
+    }
+
+    /// Level-0 distance from the reservation price, in bps.
+    pub fn half_spread_bps(&self, cfg: &MakerConfig, vol_bps: f64) -> f64 {
+        let gamma = self.config.risk_aversion;
+        let optimal = gamma * variance(vol_bps) / 2.0 + (1.0 + gamma / self.kappa()).ln() / gamma;
+        optimal.max(cfg.spread_bps)
+    }
+}
+
+fn variance(vol_bps: f64) -> f64 {
+    if vol_bps.is_finite() && vol_bps > 0.0 {
+        vol_bps * vol_bps
+    } else {
+        0.0
+    }
+}
+
+impl QuoteStrategy for AvellanedaStoikovStrategy {
+    fn name(&self) -> &'static str {
+        AVELLANEDA_STOIKOV
+    }
+
+    fn center(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> f64 {
+        input.anchor() * (1.0 - self.reservation_shift_bps(cfg, input) / 1e4)
+    }
+
+    fn desired_quotes(&self, cfg: &MakerConfig, input: &CycleInput<'_>) -> Vec<DesiredQuote> {
+        ladder(
+            cfg,
+            self.center(cfg, input),
+            self.half_spread_bps(cfg, input.vol_bps),
+        )
+    }
+
+    fn record_fill(&mut self, role: FillRole, side: OrderSide, price: f64, mark: f64) {
+        if role != FillRole::PassiveMaker || !price.is_finite() || !mark.is_finite() || mark <= 0.0
+        {
+            return;
+        }
+        let depth_bps = match side {
+            OrderSide::Buy => (mark - price) / mark * 1e4,
+            OrderSide::Sell => (price - mark) / mark * 1e4,
+        }
+        .max(0.0);
+        self.depths_bps.push_back(depth_bps);
+        self.depth_sum_bps += depth_bps;
+        while self.depths_bps.len() > self.config.kappa_window {
+            if let Some(oldest) = self.depths_bps.pop_front() {
+                self.depth_sum_bps -= oldest;
+            }
+        }
+    }
+}
+
+/// `cfg.levels` quotes per side, `spread_bps` from `center` for level 0 and
+/// `level_step_bps` further per outer level, Buy side first.
+pub(crate) fn ladder(cfg: &MakerConfig, center: f64, spread_bps: f64) -> Vec<DesiredQuote> {
+    [OrderSide::Buy, OrderSide::Sell]
+        .into_iter()
+        .flat_map(|side| {
+            (0..cfg.levels).map(move |level| {
+                let offset_bps = spread_bps + level as f64 * cfg.level_step_bps;
+                DesiredQuote {
+                    side,
+                    level,
+                    price: match side {
+                        OrderSide::Buy => center * (1.0 - offset_bps / 1e4),
+                        OrderSide::Sell => center * (1.0 + offset_bps / 1e4),
+                    },
+                    qty: cfg.size,
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{plan_cycle_with, Action, MarketDataMode, MarketSnapshot};
+
+    fn cfg() -> MakerConfig {
+        MakerConfig {
+            spread_bps: 2.0,
+            band_bps: 40.0,
+            level_step_bps: 2.0,
+            refresh_bps: 3.0,
+            levels: 1,
+            size: 0.01,
+            max_position: 0.05,
+            skew_bps: 0.0,
+            price_decimals: 2,
+            qty_decimals: 4,
+            min_order_qty: 0.001,
+        }
+    }
+
+    fn input(position: f64, vol_bps: f64) -> CycleInput<'static> {
+        CycleInput {
+            cycle: 1,
+            market: MarketSnapshot {
+                mark: 100.0,
+                best_bid: Some(99.99),
+                best_ask: Some(100.01),
+            },
+            position,
+            resting: &[],
+            pending_slots: &[],
+            market_data_mode: MarketDataMode::Active,
+            active_exit_enabled: false,
+            inventory_exit_pct: 0.0,
+            inventory_exit_qty: 0.0,
+            size_skew: Default::default(),
+            nonlinear_skew: Default::default(),
+            guard: Default::default(),
+            wind_down: false,
+            paused: false,
+            qty_tolerance: 0.0005,
+            vol_bps,
+            fair_value: None,
+        }
+    }
+
+    fn strategy() -> AvellanedaStoikovStrategy {
+        AvellanedaStoikovStrategy::new(AvellanedaStoikovConfig {
+            risk_aversion: 0.1,
+            kappa: 0.2,
+            kappa_prior_fills: 10.0,
+            kappa_window: 4,
+        })
+    }
+
+    #[test]
+    fn reservation_price_leans_against_inventory_by_gamma_and_variance() {
+        let model = strategy();
+        let c = cfg();
+        assert_eq!(model.center(&c, &input(0.0, 5.0)), 100.0);
+        // Two lots long at σ = 5 bps: 2 × 0.1 × 25 = 5 bps below mark.
+        assert!((model.reservation_shift_bps(&c, &input(0.02, 5.0)) - 5.0).abs() < 1e-9);
+        assert!((model.center(&c, &input(0.02, 5.0)) - 99.95).abs() < 1e-9);
+        assert!((model.center(&c, &input(-0.02, 5.0)) - 100.05).abs() < 1e-9);
+        // Without volatility there is no inventory risk to lean against.
+        assert_eq!(model.center(&c, &input(0.02, 0.0)), 100.0);
+    }
+
+    #[test]
+    fn half_spread_combines_volatility_and_arrival_terms_above_the_floor() {
+        let model = strategy();
+        let c = cfg();
+        let arrival = (1.0_f64 + 0.1 / 0.2).ln() / 0.1;
+        assert!((model.half_spread_bps(&c, 0.0) - arrival).abs() < 1e-9);
+        assert!((model.half_spread_bps(&c, 4.0) - (0.8 + arrival)).abs() < 1e-9);
+        let floor = MakerConfig {
+            spread_bps: 10.0,
+            ..cfg()
+        };
+        assert_eq!(model.half_spread_bps(&floor, 0.0), 10.0);
+    }
+
+    #[test]
+    fn kappa_calibrates_from_recent_passive_fill_depths() {
+        let mut model = strategy();
+        assert!((model.kappa() - 0.2).abs() < 1e-12);
+
+        // Exits carry no information about passive arrival intensity.
+        model.record_fill(FillRole::InventoryExit, OrderSide::Buy, 99.0, 100.0);
+        assert!((model.kappa() - 0.2).abs() < 1e-12);
+        for _ in 0..10 {
+            model.record_fill(FillRole::PassiveMaker, OrderSide::Buy, 99.99, 100.0);
+        }
+        // Ten 1 bps fills against a 10-fill prior at 5 bps mean depth, but
+        // only the last 4 stay in the window: (10 + 4) / (50 + 4).
+        assert!((model.kappa() - 14.0 / 54.0).abs() < 1e-9);
+        // A fill at the mark counts as zero depth.
+        model.record_fill(FillRole::PassiveMaker, OrderSide::Sell, 100.0, 100.0);
+        assert!((model.kappa() - 14.0 / 53.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn avellaneda_stoikov_quotes_pass_through_the_shared_guards() {
+        let c = cfg();
+        let mut model = strategy();
+        let plan = plan_cycle_with(&c, &model, input(0.04, 6.0), false);
+        // Shift 4 × 0.1 × 36 = 14.4 bps; half-spread 1.8 + 4.05 = 5.85 bps.
+        assert!((plan.ref_center - 100.0 * (1.0 - 14.4e-4)).abs() < 1e-9);
+        let places = plan
+            .actions
+            .iter()
+            .filter_map(|action| match action {
+                Action::Place(quote) => Some((quote.side, quote.price)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        // The ask would sit below the bid; post-only clamps it a tick above.
+        assert_eq!(
+            places,
+            vec![(OrderSide::Buy, 99.79), (OrderSide::Sell, 100.0)]
+        );
+
+        for _ in 0..4 {
+            model.record_fill(FillRole::PassiveMaker, OrderSide::Buy, 99.0, 100.0);
+        }
+        let wider = plan_cycle_with(&c, &model, input(0.0, 0.0), false);
+        let bid = wider.actions.iter().find_map(|action| match action {
+            Action::Place(quote) if quote.side == OrderSide::Buy => Some(quote.price),
+            _ => None,
+        });
+        // Deep fills lower κ, which widens the optimal spread.
+        assert!(model.kappa() < 0.2);
+        assert!(bid.unwrap() < 99.94);
+    }
+
+    #[test]
+    fn strategy_config_validates_before_building() {
+        assert_eq!(StrategyConfig::default().validate(), Ok(()));
+        let config = StrategyConfig::AvellanedaStoikov(AvellanedaStoikovConfig::default());
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.build().name(), "avellaneda_stoikov");
+        for invalid in [
+            AvellanedaStoikovConfig {
+                risk_aversion: 0.0,
+                ..Default::default()
+            },
+            AvellanedaStoikovConfig {
+                kappa: f64::NAN,
+                ..Default::default()
+            },
+            AvellanedaStoikovConfig {
+                kappa_window: 0,
+                ..Default::default()
+            },
+        ] {
+            assert!(StrategyConfig::AvellanedaStoikov(invalid)
+                .validate()
+                .is_err());
+        }
+    }
+}
@@ -529,7 +529,10 @@ mod tests {
                 nonlinear_skew: Default::default(),
                 guard: Default::default(),
                 wind_down: false,
+                paused: false,
                 qty_tolerance: 0.0005,
+                vol_bps: 0.0,
+                fair_value: None,
             },
             false,
         );
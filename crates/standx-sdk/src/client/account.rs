@@ -329,10 +329,7 @@ impl StandXClient {
     pub async fn change_leverage(&self, symbol: &str, leverage: u32) -> Result<()> {
         let url = format!("{}/api/change_leverage", self.base_url);
 
-        let body = serde_json::json!({
-            "symbol": symbol,
-            "leverage": leverage,
-        });
+        let body = change_leverage_body(symbol, leverage);
         let body_str = body.to_string();
 
         let headers = self.build_auth_headers(Some(&body_str)).await?;
@@ -363,10 +360,7 @@ impl StandXClient {
     pub async fn change_margin_mode(&self, symbol: &str, mode: &str) -> Result<()> {
         let url = format!("{}/api/change_margin_mode", self.base_url);
 
-        let body = serde_json::json!({
-            "symbol": symbol,
-            "margin_mode": mode,
-        });
+        let body = change_margin_mode_body(symbol, mode);
         let body_str = body.to_string();
 
         let headers = self.build_auth_headers(Some(&body_str)).await?;
@@ -397,24 +391,7 @@ impl StandXClient {
     pub async fn transfer_margin(&self, symbol: &str, amount: &str, direction: &str) -> Result<()> {
         let url = format!("{}/api/transfer_margin", self.base_url);
 
-        // Convert amount to negative for withdraw
-        let amount_val: f64 = amount.parse().map_err(|_| Error::Api {
-            code: 400,
-            message: format!("Invalid amount: {}", amount),
-            endpoint: None,
-            retryable: false,
-        })?;
-
-        let final_amount = if direction == "withdraw" {
-            -amount_val.abs()
-        } else {
-            amount_val.abs()
-        };
-
-        let body = serde_json::json!({
-            "symbol": symbol,
-            "amount_in": final_amount.to_string(),
-        });
+        let body = transfer_margin_body(symbol, amount, direction)?;
         let body_str = body.to_string();
 
         let headers = self.build_auth_headers(Some(&body_str)).await?;
@@ -442,6 +419,48 @@ impl StandXClient {
     }
 }
 
+/// JSON body `change_leverage` signs and posts to `/api/change_leverage`.
+pub fn change_leverage_body(symbol: &str, leverage: u32) -> serde_json::Value {
+    serde_json::json!({
+        "symbol": symbol,
+        "leverage": leverage,
+    })
+}
+
+/// JSON body `change_margin_mode` signs and posts to `/api/change_margin_mode`.
+pub fn change_margin_mode_body(symbol: &str, mode: &str) -> serde_json::Value {
+    serde_json::json!({
+        "symbol": symbol,
+        "margin_mode": mode,
+    })
+}
+
+/// JSON body `transfer_margin` signs and posts to `/api/transfer_margin`;
+/// a `withdraw` is sent as a negative amount.
+pub fn transfer_margin_body(
+    symbol: &str,
+    amount: &str,
+    direction: &str,
+) -> Result<serde_json::Value> {
+    let amount_val: f64 = amount.parse().map_err(|_| Error::Api {
+        code: 400,
+        message: format!("Invalid amount: {}", amount),
+        endpoint: None,
+        retryable: false,
+    })?;
+
+    let final_amount = if direction == "withdraw" {
+        -amount_val.abs()
+    } else {
+        amount_val.abs()
+    };
+
+    Ok(serde_json::json!({
+        "symbol": symbol,
+        "amount_in": final_amount.to_string(),
+    }))
+}
+
 #[cfg(test)]
 mod trade_history_tests {
     use super::*;
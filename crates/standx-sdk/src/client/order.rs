@@ -154,7 +154,8 @@ impl StandXClient {
     }
 }
 
-pub(crate) fn create_order_body(params: &CreateOrderParams) -> serde_json::Value {
+/// JSON body `create_order` signs and posts to `/api/new_order`.
+pub fn create_order_body(params: &CreateOrderParams) -> serde_json::Value {
     let order_type = match params.order_type {
         OrderType::Market => "market",
         OrderType::Limit => "limit",
@@ -199,11 +200,13 @@ pub(crate) fn create_order_body(params: &CreateOrderParams) -> serde_json::Value
     body
 }
 
-pub(crate) fn cancel_order_body(order_id: i64) -> serde_json::Value {
+/// JSON body `cancel_order` signs and posts to `/api/cancel_order`.
+pub fn cancel_order_body(order_id: i64) -> serde_json::Value {
     json!({ "order_id": order_id })
 }
 
-fn cancel_orders_body(order_ids: &[i64]) -> serde_json::Value {
+/// JSON body `cancel_orders` signs and posts to `/api/cancel_orders`.
+pub fn cancel_orders_body(order_ids: &[i64]) -> serde_json::Value {
     json!({ "order_id_list": order_ids })
 }
 